@@ -700,6 +700,18 @@ const ci = {
             "df -h",
           ].join("\n"),
         },
+        {
+          // the base binary of `deno compile --no-web-apis`, which is also
+          // used by the tests
+          name: "Build denort-lite",
+          if: [
+            "matrix.job == 'test' && (matrix.profile == 'debug' ||",
+            "matrix.use_sysroot || github.repository == 'denoland/deno')",
+          ].join("\n"),
+          run:
+            "cargo build ${{ matrix.profile == 'release' && '--release' || '' }} --locked -p deno --bin denort --features lite --target-dir target/lite",
+          env: { CARGO_PROFILE_DEV_DEBUG: 0 },
+        },
         {
           name: "Upload PR artifact (linux)",
           if: [
@@ -729,6 +741,8 @@ const ci = {
             "zip -r deno-${{ matrix.arch }}-unknown-linux-gnu.zip deno",
             "strip denort",
             "zip -r denort-${{ matrix.arch }}-unknown-linux-gnu.zip denort",
+            "strip ../lite/release/denort",
+            "(cd ../lite/release && zip -r ../../release/denort-lite-${{ matrix.arch }}-unknown-linux-gnu.zip denort)",
             "./deno types > lib.deno.d.ts",
           ].join("\n"),
         },
@@ -755,6 +769,8 @@ const ci = {
             "zip -r deno-${{ matrix.arch }}-apple-darwin.zip deno",
             "strip denort",
             "zip -r denort-${{ matrix.arch }}-apple-darwin.zip denort",
+            "strip ../lite/release/denort",
+            "(cd ../lite/release && zip -r ../../release/denort-lite-${{ matrix.arch }}-apple-darwin.zip denort)",
           ]
             .join("\n"),
         },
@@ -770,6 +786,7 @@ const ci = {
           run: [
             "Compress-Archive -CompressionLevel Optimal -Force -Path target/release/deno.exe -DestinationPath target/release/deno-${{ matrix.arch }}-pc-windows-msvc.zip",
             "Compress-Archive -CompressionLevel Optimal -Force -Path target/release/denort.exe -DestinationPath target/release/denort-${{ matrix.arch }}-pc-windows-msvc.zip",
+            "Compress-Archive -CompressionLevel Optimal -Force -Path target/lite/release/denort.exe -DestinationPath target/release/denort-lite-${{ matrix.arch }}-pc-windows-msvc.zip",
           ].join("\n"),
         },
         {
@@ -1030,14 +1047,19 @@ const ci = {
             files: [
               "target/release/deno-x86_64-pc-windows-msvc.zip",
               "target/release/denort-x86_64-pc-windows-msvc.zip",
+              "target/release/denort-lite-x86_64-pc-windows-msvc.zip",
               "target/release/deno-x86_64-unknown-linux-gnu.zip",
               "target/release/denort-x86_64-unknown-linux-gnu.zip",
+              "target/release/denort-lite-x86_64-unknown-linux-gnu.zip",
               "target/release/deno-x86_64-apple-darwin.zip",
               "target/release/denort-x86_64-apple-darwin.zip",
+              "target/release/denort-lite-x86_64-apple-darwin.zip",
               "target/release/deno-aarch64-unknown-linux-gnu.zip",
               "target/release/denort-aarch64-unknown-linux-gnu.zip",
+              "target/release/denort-lite-aarch64-unknown-linux-gnu.zip",
               "target/release/deno-aarch64-apple-darwin.zip",
               "target/release/denort-aarch64-apple-darwin.zip",
+              "target/release/denort-lite-aarch64-apple-darwin.zip",
               "target/release/deno_src.tar.gz",
               "target/release/lib.deno.d.ts",
             ].join("\n"),
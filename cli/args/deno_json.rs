@@ -1,12 +1,21 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use std::collections::HashSet;
+use std::path::Path;
 
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
 use deno_core::serde_json;
+use deno_core::ModuleSpecifier;
+use deno_runtime::fmt_errors::ErrorTheme;
 use deno_semver::jsr::JsrDepPackageReq;
 use deno_semver::jsr::JsrPackageReqReference;
 use deno_semver::npm::NpmPackageReqReference;
 
+use super::flags_net;
+use super::PermissionFlags;
+
 pub fn deno_json_deps(
   config: &deno_config::ConfigFile,
 ) -> HashSet<JsrDepPackageReq> {
@@ -49,3 +58,389 @@ fn values_to_set<'a>(
   }
   entries
 }
+
+/// The top level properties of a configuration file that aren't part of
+/// [`deno_config::ConfigFileJson`], like `"permissions"` or `"extends"`.
+#[derive(Clone, Debug)]
+pub struct ConfigFileExtras {
+  specifier: ModuleSpecifier,
+  properties: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ConfigFileExtras {
+  /// Parse the configuration file for its extra properties. This should be
+  /// done once when the file is loaded, rather than for each property.
+  pub fn read(config: &deno_config::ConfigFile) -> Result<Self, AnyError> {
+    let mut properties = serde_json::Map::new();
+    if let Ok(path) = config.specifier.to_file_path() {
+      let text = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed reading {}", path.display()))?;
+      let value =
+        jsonc_parser::parse_to_serde_value(&text, &Default::default())
+          .with_context(|| format!("Failed parsing {}", path.display()))?;
+      if let Some(serde_json::Value::Object(obj)) = value {
+        properties = obj;
+      }
+    }
+    Ok(Self {
+      specifier: config.specifier.clone(),
+      properties,
+    })
+  }
+
+  fn get(&self, name: &str) -> Option<serde_json::Value> {
+    self.properties.get(name).cloned()
+  }
+}
+
+/// Resolve the `"errorTheme"` property of the configuration file, which is
+/// either the name of a preset or an object of overrides with an optional
+/// `"preset"` to start from.
+pub fn resolve_error_theme(
+  extras: &ConfigFileExtras,
+) -> Result<Option<ErrorTheme>, AnyError> {
+  let Some(value) = extras.get("errorTheme") else {
+    return Ok(None);
+  };
+  let theme = match value {
+    serde_json::Value::String(preset) => {
+      preset.parse::<ErrorTheme>().map_err(AnyError::msg)?
+    }
+    serde_json::Value::Object(mut obj) => {
+      let mut theme = match obj.remove("preset") {
+        Some(serde_json::Value::String(preset)) => {
+          ErrorTheme::from_preset(&preset).with_context(|| {
+            format!("Unknown error theme preset \"{preset}\"")
+          })?
+        }
+        Some(_) => bail!("\"errorTheme.preset\" must be a string"),
+        None => ErrorTheme::default(),
+      };
+      for (key, value) in obj {
+        let value = match value {
+          serde_json::Value::String(value) => value,
+          serde_json::Value::Bool(value) => value.to_string(),
+          _ => bail!("\"errorTheme.{key}\" must be a string or boolean"),
+        };
+        theme.set(&key, &value).map_err(AnyError::msg)?;
+      }
+      theme
+    }
+    _ => bail!("\"errorTheme\" must be a string or an object"),
+  };
+  Ok(Some(theme))
+}
+
+/// Resolve the `"extends"` property of the configuration file, which lists
+/// import maps (or other configuration files with `"imports"` and `"scopes"`)
+/// that are layered beneath the configuration file's own import map.
+pub fn resolve_import_map_extends(
+  extras: &ConfigFileExtras,
+) -> Result<Vec<ModuleSpecifier>, AnyError> {
+  let extends = match extras.get("extends") {
+    None => Vec::new(),
+    Some(serde_json::Value::String(extends)) => vec![extends],
+    Some(serde_json::Value::Array(values)) => values
+      .into_iter()
+      .map(|value| match value {
+        serde_json::Value::String(extends) => Ok(extends),
+        _ => bail!("\"extends\" must be a string or an array of strings"),
+      })
+      .collect::<Result<_, _>>()?,
+    Some(_) => bail!("\"extends\" must be a string or an array of strings"),
+  };
+  extends
+    .iter()
+    .map(|extends| {
+      extras.specifier.join(extends).with_context(|| {
+        format!("Invalid import map \"{extends}\" in \"extends\"")
+      })
+    })
+    .collect()
+}
+
+/// Resolve the modules of the `"repl.init"` property of the configuration
+/// file, which are evaluated before the REPL prompt appears.
+pub fn resolve_repl_init(
+  extras: &ConfigFileExtras,
+) -> Result<Vec<ModuleSpecifier>, AnyError> {
+  let Some(value) = extras.get("repl") else {
+    return Ok(Vec::new());
+  };
+  let serde_json::Value::Object(mut obj) = value else {
+    bail!("\"repl\" must be an object");
+  };
+  let init = match obj.remove("init") {
+    None => Vec::new(),
+    Some(serde_json::Value::String(init)) => vec![init],
+    Some(serde_json::Value::Array(values)) => values
+      .into_iter()
+      .map(|value| match value {
+        serde_json::Value::String(init) => Ok(init),
+        _ => bail!("\"repl.init\" must be a string or an array of strings"),
+      })
+      .collect::<Result<_, _>>()?,
+    Some(_) => bail!("\"repl.init\" must be a string or an array of strings"),
+  };
+  init
+    .iter()
+    .map(|init| {
+      extras
+        .specifier
+        .join(init)
+        .with_context(|| format!("Invalid module \"{init}\" in \"repl.init\""))
+    })
+    .collect()
+}
+
+/// Apply the entries of the `"overrides"` property of the configuration file
+/// whose `"when"` conditions match the current platform. Each entry's
+/// `"compilerOptions"` and `"tasks"` are merged over the top level ones, with
+/// later entries taking precedence.
+pub fn apply_config_overrides(
+  config: &mut deno_config::ConfigFile,
+  extras: &ConfigFileExtras,
+) -> Result<(), AnyError> {
+  for mut entry in matching_overrides(extras)? {
+    if let Some(value) = entry.remove("compilerOptions") {
+      merge_object(
+        &mut config.json.compiler_options,
+        value,
+        "compilerOptions",
+      )?;
+    }
+    if let Some(value) = entry.remove("tasks") {
+      merge_object(&mut config.json.tasks, value, "tasks")?;
+    }
+  }
+  Ok(())
+}
+
+/// Resolve the default permissions of the `"permissions"` property of the
+/// configuration file along with those of the matching `"overrides"`. These
+/// are only used when opted into with `-P` and no permission flags are
+/// provided.
+pub fn resolve_permissions(
+  extras: &ConfigFileExtras,
+) -> Result<Option<PermissionFlags>, AnyError> {
+  let mut permissions = match extras.get("permissions") {
+    None => None,
+    Some(serde_json::Value::Object(obj)) => Some(obj),
+    Some(_) => bail!("\"permissions\" must be an object"),
+  };
+  for mut entry in matching_overrides(extras)? {
+    match entry.remove("permissions") {
+      None => {}
+      Some(serde_json::Value::Object(obj)) => {
+        permissions.get_or_insert_with(Default::default).extend(obj);
+      }
+      Some(_) => bail!("\"overrides[].permissions\" must be an object"),
+    }
+  }
+  let Some(permissions) = permissions else {
+    return Ok(None);
+  };
+  let config_dir = extras
+    .specifier
+    .to_file_path()
+    .ok()
+    .and_then(|path| Some(path.parent()?.to_path_buf()));
+  permission_flags_from_json(permissions, config_dir.as_deref(), "permissions")
+    .map(Some)
+}
+
+/// Parse an object of permission names to `true`, `false` or a list of
+/// allowed values, as used by "permissions" in the config file. Relative paths
+/// are resolved against `base_dir`.
+pub fn permission_flags_from_json(
+  permissions: serde_json::Map<String, serde_json::Value>,
+  base_dir: Option<&Path>,
+  property: &str,
+) -> Result<PermissionFlags, AnyError> {
+  let mut flags = PermissionFlags::default();
+  for (name, value) in permissions {
+    let to_list = |value: serde_json::Value| -> Result<_, AnyError> {
+      match value {
+        serde_json::Value::Bool(true) => Ok(Some(Vec::new())),
+        serde_json::Value::Bool(false) => Ok(None),
+        serde_json::Value::Array(values) => values
+          .into_iter()
+          .map(|value| match value {
+            serde_json::Value::String(value) => Ok(value),
+            _ => bail!(
+              "\"{property}.{name}\" must be a boolean or an array of strings"
+            ),
+          })
+          .collect::<Result<Vec<_>, _>>()
+          .map(Some),
+        _ => bail!(
+          "\"{property}.{name}\" must be a boolean or an array of strings"
+        ),
+      }
+    };
+    let to_bool = |value: serde_json::Value| match value {
+      serde_json::Value::Bool(value) => Ok(value),
+      _ => bail!("\"{property}.{name}\" must be a boolean"),
+    };
+    // paths are relative to the configuration file rather than the cwd
+    let relative_to_base = |list: Option<Vec<String>>| {
+      list.map(|list| {
+        list
+          .into_iter()
+          .map(|path| match base_dir {
+            Some(dir) => dir.join(path).to_string_lossy().into_owned(),
+            None => path,
+          })
+          .collect()
+      })
+    };
+    match name.as_str() {
+      "all" => flags.allow_all = to_bool(value)?,
+      "hrtime" => flags.allow_hrtime = to_bool(value)?,
+      "env" => flags.allow_env = to_list(value)?,
+      "ffi" => flags.allow_ffi = relative_to_base(to_list(value)?),
+      "net" => {
+        flags.allow_net = match to_list(value)? {
+          Some(list) => Some(flags_net::parse(list)?),
+          None => None,
+        };
+      }
+      "read" => flags.allow_read = relative_to_base(to_list(value)?),
+      "run" => flags.allow_run = to_list(value)?,
+      "sys" => flags.allow_sys = to_list(value)?,
+      "write" => flags.allow_write = relative_to_base(to_list(value)?),
+      _ => bail!("Unknown permission \"{name}\" in \"{property}\""),
+    }
+  }
+  Ok(flags)
+}
+
+fn matching_overrides(
+  extras: &ConfigFileExtras,
+) -> Result<Vec<serde_json::Map<String, serde_json::Value>>, AnyError> {
+  let entries = match extras.get("overrides") {
+    None => return Ok(Vec::new()),
+    Some(serde_json::Value::Array(values)) => values,
+    Some(value @ serde_json::Value::Object(_)) => vec![value],
+    Some(_) => bail!("\"overrides\" must be an object or an array of objects"),
+  };
+  let mut matching = Vec::with_capacity(entries.len());
+  for entry in entries {
+    let serde_json::Value::Object(mut entry) = entry else {
+      bail!("\"overrides\" must be an object or an array of objects");
+    };
+    let when = entry.remove("when").unwrap_or_default();
+    if let Some(key) = entry.keys().find(|key| {
+      !matches!(key.as_str(), "compilerOptions" | "tasks" | "permissions")
+    }) {
+      bail!("Unsupported property \"{key}\" in \"overrides\"");
+    }
+    if override_matches(when, std::env::consts::OS, std::env::consts::ARCH)? {
+      matching.push(entry);
+    }
+  }
+  Ok(matching)
+}
+
+fn override_matches(
+  when: serde_json::Value,
+  os: &str,
+  arch: &str,
+) -> Result<bool, AnyError> {
+  let when = match when {
+    serde_json::Value::Null => return Ok(true),
+    serde_json::Value::Object(when) => when,
+    _ => bail!("\"overrides[].when\" must be an object"),
+  };
+  for (key, value) in when {
+    let current = match key.as_str() {
+      // use the same names as `Deno.build.os`
+      "os" if os == "macos" => "darwin",
+      "os" => os,
+      "arch" => arch,
+      _ => bail!("Unsupported condition \"{key}\" in \"overrides[].when\""),
+    };
+    let matches = match value {
+      serde_json::Value::String(value) => value == current,
+      serde_json::Value::Array(values) => {
+        values.iter().any(|value| value.as_str() == Some(current))
+      }
+      _ => bail!(
+        "\"overrides[].when.{key}\" must be a string or an array of strings"
+      ),
+    };
+    if !matches {
+      return Ok(false);
+    }
+  }
+  Ok(true)
+}
+
+fn merge_object(
+  target: &mut Option<serde_json::Value>,
+  value: serde_json::Value,
+  name: &str,
+) -> Result<(), AnyError> {
+  let serde_json::Value::Object(value) = value else {
+    bail!("\"overrides[].{name}\" must be an object");
+  };
+  let target =
+    target.get_or_insert_with(|| serde_json::Value::Object(Default::default()));
+  let Some(target) = target.as_object_mut() else {
+    bail!("\"{name}\" must be an object");
+  };
+  target.extend(value);
+  Ok(())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+  use deno_core::serde_json::json;
+
+  #[test]
+  fn override_matches_conditions() {
+    assert!(
+      override_matches(serde_json::Value::Null, "linux", "x86_64").unwrap()
+    );
+    assert!(
+      override_matches(json!({ "os": "linux" }), "linux", "x86_64").unwrap()
+    );
+    assert!(
+      !override_matches(json!({ "os": "windows" }), "linux", "x86_64").unwrap()
+    );
+    assert!(
+      override_matches(json!({ "os": "darwin" }), "macos", "aarch64").unwrap()
+    );
+    assert!(override_matches(
+      json!({ "os": ["linux", "darwin"], "arch": "aarch64" }),
+      "macos",
+      "aarch64"
+    )
+    .unwrap());
+    assert!(!override_matches(
+      json!({ "os": "linux", "arch": "aarch64" }),
+      "linux",
+      "x86_64"
+    )
+    .unwrap());
+    assert!(
+      override_matches(json!({ "cpu": "x64" }), "linux", "x86_64").is_err()
+    );
+    assert!(override_matches(json!({ "os": 1 }), "linux", "x86_64").is_err());
+  }
+
+  #[test]
+  fn merge_object_overrides_keys() {
+    let mut target = Some(json!({ "build": "a", "test": "b" }));
+    merge_object(&mut target, json!({ "build": "c" }), "tasks").unwrap();
+    assert_eq!(target, Some(json!({ "build": "c", "test": "b" })));
+
+    let mut target = None;
+    merge_object(&mut target, json!({ "strict": false }), "compilerOptions")
+      .unwrap();
+    assert_eq!(target, Some(json!({ "strict": false })));
+
+    assert!(merge_object(&mut target, json!(true), "tasks").is_err());
+  }
+}
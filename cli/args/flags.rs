@@ -17,6 +17,7 @@ use deno_core::error::AnyError;
 use deno_core::resolve_url_or_path;
 use deno_core::url::Url;
 use deno_graph::GraphKind;
+use deno_runtime::fmt_errors::StackFrameCategory;
 use deno_runtime::permissions::parse_sys_kind;
 use deno_runtime::permissions::PermissionsOptions;
 use log::debug;
@@ -55,6 +56,7 @@ pub struct BenchFlags {
   pub files: FileFlags,
   pub filter: Option<String>,
   pub json: bool,
+  pub compare: Option<String>,
   pub no_run: bool,
   pub watch: Option<WatchFlags>,
 }
@@ -62,18 +64,34 @@ pub struct BenchFlags {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct BundleFlags {
   pub source_file: String,
+  pub additional_source_files: Vec<String>,
   pub out_file: Option<String>,
+  pub out_dir: Option<String>,
+  pub minify: bool,
+  pub source_map: Option<BundleSourceMap>,
   pub watch: Option<WatchFlags>,
 }
 
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum BundleSourceMap {
+  External,
+  Inline,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CacheFlags {
   pub files: Vec<String>,
+  pub prune: bool,
+  pub dry_run: bool,
+  pub export: Option<String>,
+  pub import: Option<String>,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct CheckFlags {
   pub files: Vec<String>,
+  pub error_limit: Option<usize>,
+  pub summary: bool,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -84,6 +102,11 @@ pub struct CompileFlags {
   pub target: Option<String>,
   pub no_terminal: bool,
   pub include: Vec<String>,
+  pub icon: Option<String>,
+  pub product_name: Option<String>,
+  pub version: Option<String>,
+  pub sign_cmd: Option<String>,
+  pub no_web_apis: bool,
 }
 
 impl CompileFlags {
@@ -116,6 +139,14 @@ pub struct CoverageFlags {
   pub include: Vec<String>,
   pub exclude: Vec<String>,
   pub r#type: CoverageType,
+  pub fail_under_lines: Option<u8>,
+  pub fail_under_branches: Option<u8>,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct CoverageMergeFlags {
+  pub files: FileFlags,
+  pub output: String,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -140,6 +171,8 @@ pub struct DocHtmlFlag {
 pub struct DocFlags {
   pub private: bool,
   pub json: bool,
+  /// The version of the JSON object to output instead of an array of nodes.
+  pub json_version: Option<u32>,
   pub lint: bool,
   pub html: Option<DocHtmlFlag>,
   pub source_files: DocSourceFileFlag,
@@ -162,13 +195,14 @@ pub struct FmtFlags {
   pub single_quote: Option<bool>,
   pub prose_wrap: Option<String>,
   pub no_semicolons: Option<bool>,
+  pub stdin_filepath: Option<String>,
   pub watch: Option<WatchFlags>,
 }
 
 impl FmtFlags {
   pub fn is_stdin(&self) -> bool {
     let args = &self.files.include;
-    args.len() == 1 && args[0] == "-"
+    self.stdin_filepath.is_some() || (args.len() == 1 && args[0] == "-")
   }
 }
 
@@ -180,9 +214,26 @@ pub struct InitFlags {
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InfoFlags {
   pub json: bool,
+  pub graph: Option<InfoGraphFormat>,
   pub file: Option<String>,
 }
 
+/// The output format of uncaught errors, set with `--error-format`.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ErrorFormat {
+  /// Human readable, colored output.
+  #[default]
+  Pretty,
+  /// Machine readable JSON output.
+  Json,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum InfoGraphFormat {
+  Dot,
+  Mermaid,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct InstallFlagsGlobal {
   pub module_url: String,
@@ -241,6 +292,8 @@ pub struct LintFlags {
   pub maybe_rules_exclude: Option<Vec<String>>,
   pub json: bool,
   pub compact: bool,
+  pub format: Option<String>,
+  pub max_warnings: Option<usize>,
   pub watch: Option<WatchFlags>,
 }
 
@@ -251,6 +304,34 @@ impl LintFlags {
   }
 }
 
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PermissionsFlags {
+  /// The script whose grants are managed when there is no configuration
+  /// file.
+  pub script: Option<String>,
+  pub subcommand: PermissionGrantsFlags,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum PermissionGrantsFlags {
+  List,
+  Revoke {
+    /// Revoke every grant of the project when `None`.
+    name: Option<String>,
+    /// Revoke every grant of the permission when `None`.
+    value: Option<String>,
+  },
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StorageFlags {
+  List,
+  Clear {
+    /// Clear the storage of every origin when `None`.
+    origin: Option<String>,
+  },
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Default)]
 pub struct ReplFlags {
   pub eval_files: Option<Vec<String>>,
@@ -317,6 +398,7 @@ pub struct WatchFlagsWithPaths {
 pub struct TaskFlags {
   pub cwd: Option<String>,
   pub task: Option<String>,
+  pub watch: Option<WatchFlags>,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -326,6 +408,7 @@ pub enum TestReporterConfig {
   Dot,
   Junit,
   Tap,
+  Json,
 }
 
 #[derive(Clone, Debug, Default, Eq, PartialEq)]
@@ -343,6 +426,36 @@ pub struct TestFlags {
   pub watch: Option<WatchFlags>,
   pub reporter: TestReporterConfig,
   pub junit_path: Option<String>,
+  pub shard: Option<TestShard>,
+  pub retries: u32,
+  pub timeout: Option<u64>,
+  pub update_snapshots: bool,
+  pub changed: Option<String>,
+}
+
+/// One of `count` parts the tests of a run are split into, selected with
+/// `--shard=<index>/<count>`. `index` is 1-based.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct TestShard {
+  pub index: NonZeroUsize,
+  pub count: NonZeroUsize,
+}
+
+impl FromStr for TestShard {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let invalid = || format!("Invalid shard \"{s}\", expected <index>/<count>");
+    let (index, count) = s.split_once('/').ok_or_else(invalid)?;
+    let index = index.parse::<NonZeroUsize>().map_err(|_| invalid())?;
+    let count = count.parse::<NonZeroUsize>().map_err(|_| invalid())?;
+    if index > count {
+      return Err(format!(
+        "Invalid shard \"{s}\", index must not be greater than {count}"
+      ));
+    }
+    Ok(TestShard { index, count })
+  }
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -380,6 +493,7 @@ pub enum DenoSubcommand {
   Compile(CompileFlags),
   Completions(CompletionsFlags),
   Coverage(CoverageFlags),
+  CoverageMerge(CoverageMergeFlags),
   Doc(DocFlags),
   Eval(EvalFlags),
   Fmt(FmtFlags),
@@ -390,9 +504,11 @@ pub enum DenoSubcommand {
   Uninstall(UninstallFlags),
   Lsp,
   Lint(LintFlags),
+  Permissions(PermissionsFlags),
   Repl(ReplFlags),
   Run(RunFlags),
   Serve(ServeFlags),
+  Storage(StorageFlags),
   Task(TaskFlags),
   Test(TestFlags),
   Types,
@@ -500,22 +616,32 @@ pub struct Flags {
   /// the language server is configured with an explicit cache option.
   pub cache_path: Option<PathBuf>,
   pub cached_only: bool,
+  pub revalidate_in_background: bool,
   pub type_check_mode: TypeCheckMode,
   pub config_flag: ConfigFlag,
   pub node_modules_dir: Option<bool>,
   pub vendor: Option<bool>,
   pub enable_op_summary_metrics: bool,
   pub enable_testing_features: bool,
+  pub error_format: ErrorFormat,
+  pub error_context_lines: usize,
+  pub error_max_cause_depth: Option<usize>,
+  pub hide_stack_frames: Vec<StackFrameCategory>,
   pub ext: Option<String>,
   pub ignore: Vec<String>,
   pub import_map_path: Option<String>,
+  /// Import maps from `--import-map` given more than once, which are merged
+  /// on top of `import_map_path` in order.
+  pub additional_import_map_paths: Vec<String>,
   pub env_file: Option<String>,
   pub inspect_brk: Option<SocketAddr>,
   pub inspect_wait: Option<SocketAddr>,
   pub inspect: Option<SocketAddr>,
   pub location: Option<Url>,
+  pub local_storage_quota: Option<u64>,
   pub lock_write: bool,
   pub lock: Option<String>,
+  pub frozen_lockfile: bool,
   pub log_level: Option<Level>,
   pub no_remote: bool,
   pub no_lock: bool,
@@ -525,6 +651,7 @@ pub struct Flags {
   pub strace_ops: Option<Vec<String>>,
   pub unstable_config: UnstableConfig,
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
+  pub http1_only: bool,
   pub v8_flags: Vec<String>,
   pub code_cache_enabled: bool,
   pub permissions: PermissionFlags,
@@ -549,6 +676,8 @@ pub struct PermissionFlags {
   pub deny_sys: Option<Vec<String>>,
   pub allow_write: Option<Vec<String>>,
   pub deny_write: Option<Vec<String>>,
+  pub permission_policy: Option<String>,
+  pub permission_set: bool,
   pub no_prompt: bool,
 }
 
@@ -571,6 +700,7 @@ impl PermissionFlags {
       || self.deny_sys.is_some()
       || self.allow_write.is_some()
       || self.deny_write.is_some()
+      || self.permission_policy.is_some()
   }
 
   pub fn to_options(
@@ -650,6 +780,23 @@ impl Flags {
 
     if self.permissions.allow_all {
       args.push("--allow-all".to_string());
+      // deny flags take precedence over --allow-all, so they must be kept
+      let deny_flags = Flags {
+        permissions: PermissionFlags {
+          deny_env: self.permissions.deny_env.clone(),
+          deny_hrtime: self.permissions.deny_hrtime,
+          deny_ffi: self.permissions.deny_ffi.clone(),
+          deny_net: self.permissions.deny_net.clone(),
+          deny_read: self.permissions.deny_read.clone(),
+          deny_run: self.permissions.deny_run.clone(),
+          deny_sys: self.permissions.deny_sys.clone(),
+          deny_write: self.permissions.deny_write.clone(),
+          permission_policy: self.permissions.permission_policy.clone(),
+          ..Default::default()
+        },
+        ..Default::default()
+      };
+      args.extend(deny_flags.to_permission_args());
       return args;
     }
 
@@ -829,6 +976,10 @@ impl Flags {
       args.push("--deny-hrtime".to_string());
     }
 
+    if let Some(policy) = &self.permissions.permission_policy {
+      args.push(format!("--permission-policy={policy}"));
+    }
+
     args
   }
 
@@ -841,6 +992,10 @@ impl Flags {
     use DenoSubcommand::*;
 
     match &self.subcommand {
+      Fmt(FmtFlags {
+        stdin_filepath: Some(path),
+        ..
+      }) => Some(vec![current_dir.join(path)]),
       Fmt(FmtFlags { files, .. }) => {
         Some(files.include.iter().map(|p| current_dir.join(p)).collect())
       }
@@ -1010,6 +1165,14 @@ impl Flags {
           ..
         }),
       ..
+    })
+    | DenoSubcommand::Task(TaskFlags {
+      watch:
+        Some(WatchFlags {
+          exclude: excluded_paths,
+          ..
+        }),
+      ..
     }) = &self.subcommand
     {
       let cwd = std::env::current_dir()?;
@@ -1031,6 +1194,15 @@ static ENV_VARIABLES_HELP: &str = color_print::cstr!(
                          private repositories
                          (e.g. "abcde12345@deno.land;54321edcba@github.com")
 
+    <g>DENO_NETRC</>           Path of a netrc file whose credentials are used
+                         over https for hosts without an auth token
+
+    <g>DENO_CLIENT_CERTS</>    A semi-colon separated list of PEM encoded client
+                         certificates, private keys and hostnames to use
+                         when fetching remote modules from servers that
+                         require mutual TLS
+                         (e.g. "cert.pem,key.pem@deno.land")
+
     <g>DENO_FUTURE</>          Set to "1" to enable APIs that will take effect in
                          Deno 2
 
@@ -1038,6 +1210,18 @@ static ENV_VARIABLES_HELP: &str = color_print::cstr!(
 
     <g>DENO_DIR</>             Set the cache directory
 
+    <g>DENO_ERROR_CONTEXT_LINES</>
+                         Number of source lines to show around the line an
+                         uncaught error was thrown from (defaults to 0)
+
+    <g>DENO_ERROR_MAX_CAUSE_DEPTH</>
+                         Number of causes to show for an uncaught error
+                         (defaults to 20)
+
+    <g>DENO_ERROR_THEME</>     Colors used for uncaught errors. Either a preset
+                         ("high-contrast", "no-italic") and/or overrides
+                         (e.g. "no-italic,location=blue,lineNumber=gray")
+
     <g>DENO_INSTALL_ROOT</>    Set deno install's output directory
                          (defaults to $HOME/.deno/bin)
 
@@ -1049,6 +1233,10 @@ static ENV_VARIABLES_HELP: &str = color_print::cstr!(
                          History file is disabled when the value is empty
                          (defaults to $DENO_DIR/deno_history.txt)
 
+    <g>DENO_REPL_HISTORY_SIZE</>
+                         Maximum number of entries kept in the REPL history
+                         (defaults to 1000)
+
     <g>DENO_NO_PACKAGE_JSON</> Disables auto-resolution of package.json
 
     <g>DENO_NO_PROMPT</>       Set to disable permission prompts on access
@@ -1140,6 +1328,29 @@ pub fn flags_from_vec(args: Vec<OsString>) -> clap::error::Result<Flags> {
     };
   }
 
+  if let Some(error_format) = matches.get_one::<String>("error-format") {
+    flags.error_format = match error_format.as_str() {
+      "pretty" => ErrorFormat::Pretty,
+      "json" => ErrorFormat::Json,
+      _ => unreachable!(),
+    };
+  }
+
+  if let Some(context_lines) =
+    matches.remove_one::<usize>("error-context-lines")
+  {
+    flags.error_context_lines = context_lines;
+  }
+
+  flags.error_max_cause_depth =
+    matches.remove_one::<usize>("error-max-cause-depth");
+
+  if let Some(categories) = matches.remove_many::<String>("hide-stack-frames") {
+    flags.hide_stack_frames = categories
+      .map(|category| StackFrameCategory::from_str(&category).unwrap())
+      .collect();
+  }
+
   if let Some((subcommand, mut m)) = matches.remove_subcommand() {
     match subcommand.as_str() {
       "add" => add_parse(&mut flags, &mut m),
@@ -1159,9 +1370,11 @@ pub fn flags_from_vec(args: Vec<OsString>) -> clap::error::Result<Flags> {
       "jupyter" => jupyter_parse(&mut flags, &mut m),
       "lint" => lint_parse(&mut flags, &mut m),
       "lsp" => lsp_parse(&mut flags, &mut m),
+      "permissions" => permissions_parse(&mut flags, &mut m),
       "repl" => repl_parse(&mut flags, &mut m),
       "run" => run_parse(&mut flags, &mut m, app)?,
       "serve" => serve_parse(&mut flags, &mut m, app)?,
+      "storage" => storage_parse(&mut flags, &mut m),
       "task" => task_parse(&mut flags, &mut m),
       "test" => test_parse(&mut flags, &mut m),
       "types" => types_parse(&mut flags, &mut m),
@@ -1249,6 +1462,15 @@ fn clap_root() -> Command {
       Arg::new("unstable-byonm")
         .long("unstable-byonm")
         .help("Enable unstable 'bring your own node_modules' feature")
+        .long_help(
+          "Enable unstable 'bring your own node_modules' feature.
+
+Bare specifiers are resolved against the node_modules directory created by
+a package manager such as npm, using the \"exports\" field of the packages,
+and specifiers starting with # are resolved using the \"imports\" field of
+the closest package.json, so projects migrating from Node can run without
+rewriting their imports.",
+        )
         .env("DENO_UNSTABLE_BYONM")
         .value_parser(FalseyValueParser::new())
         .action(ArgAction::SetTrue)
@@ -1296,6 +1518,58 @@ fn clap_root() -> Command {
         .action(ArgAction::SetTrue)
         .global(true),
     )
+    .arg(
+      Arg::new("error-format")
+        .long("error-format")
+        .help("Set the output format of uncaught errors")
+        .long_help(
+          "Set the output format of uncaught errors. The \"json\" format prints
+the error message, stack frames, source line and cause chain as a
+single line of JSON.",
+        )
+        .value_parser(["pretty", "json"])
+        .global(true),
+    )
+    .arg(
+      Arg::new("error-context-lines")
+        .long("error-context-lines")
+        .help("Number of source lines to show around the line an error was thrown from")
+        .value_name("N")
+        .env("DENO_ERROR_CONTEXT_LINES")
+        .value_parser(value_parser!(usize))
+        .global(true),
+    )
+    .arg(
+      Arg::new("error-max-cause-depth")
+        .long("error-max-cause-depth")
+        .help("Number of causes to show for an uncaught error")
+        .long_help(
+          "Number of causes to show for an uncaught error. Deeper causes are
+summarized as \"... N more causes\". Defaults to 20.",
+        )
+        .value_name("N")
+        .env("DENO_ERROR_MAX_CAUSE_DEPTH")
+        .value_parser(value_parser!(usize))
+        .global(true),
+    )
+    .arg(
+      Arg::new("hide-stack-frames")
+        .long("hide-stack-frames")
+        .help("Hide stack frames of dependencies in uncaught errors")
+        .long_help(
+          "Hide stack frames of dependencies in uncaught errors, so that frames of
+your own code stand out. Accepts a comma separated list of categories:
+  node_modules  npm packages
+  vendor        vendored remote modules
+  internal      Deno's built-in modules",
+        )
+        .value_name("CATEGORIES")
+        .num_args(1..)
+        .use_value_delimiter(true)
+        .require_equals(true)
+        .value_parser(["node_modules", "vendor", "internal"])
+        .global(true),
+    )
     .subcommand(run_subcommand())
     .subcommand(serve_subcommand())
     .defer(|cmd| {
@@ -1318,8 +1592,10 @@ fn clap_root() -> Command {
         .subcommand(uninstall_subcommand())
         .subcommand(lsp_subcommand())
         .subcommand(lint_subcommand())
+        .subcommand(permissions_subcommand())
         .subcommand(publish_subcommand())
         .subcommand(repl_subcommand())
+        .subcommand(storage_subcommand())
         .subcommand(task_subcommand())
         .subcommand(test_subcommand())
         .subcommand(types_subcommand())
@@ -1368,7 +1644,13 @@ and report results to standard output:
 Directory arguments are expanded to all contained files matching the
 glob {*_,*.,}bench.{js,mjs,ts,mts,jsx,tsx}:
 
-  deno bench src/",
+  deno bench src/
+
+Compare against the results of a previous run, failing if a benchmark got
+slower by more than the margin of error of the measurements:
+
+  deno bench --json > baseline.json
+  deno bench --compare=baseline.json",
     )
     .defer(|cmd| {
       runtime_args(cmd, true, false)
@@ -1379,6 +1661,14 @@ glob {*_,*.,}bench.{js,mjs,ts,mts,jsx,tsx}:
             .action(ArgAction::SetTrue)
             .help("UNSTABLE: Output benchmark result in JSON format"),
         )
+        .arg(
+          Arg::new("compare")
+            .long("compare")
+            .value_name("FILE")
+            .require_equals(true)
+            .value_hint(ValueHint::FilePath)
+            .help("Compare results against a baseline saved with --json and fail on regressions"),
+        )
         .arg(
           Arg::new("ignore")
             .long("ignore")
@@ -1410,6 +1700,7 @@ glob {*_,*.,}bench.{js,mjs,ts,mts,jsx,tsx}:
         .arg(watch_arg(false))
         .arg(watch_exclude_arg())
         .arg(no_clear_screen_arg())
+        .arg(clear_screen_arg())
         .arg(script_arg().last(true))
         .arg(env_file_arg())
     })
@@ -1428,7 +1719,13 @@ Output a single JavaScript file with all dependencies.
 
 If no output file is given, the output is written to standard output:
 
-  deno bundle jsr:@std/http/file-server",
+  deno bundle jsr:@std/http/file-server
+
+Bundle multiple entry points into an output directory. Dependencies shared
+between the entry points and dynamically imported modules are split into
+separate chunks, and a manifest.json describing the chunks is written:
+
+  deno bundle --outdir dist/ main.ts worker.ts",
     )
     .defer(|cmd| {
       compile_args(cmd)
@@ -1440,9 +1737,40 @@ If no output file is given, the output is written to standard output:
             .value_hint(ValueHint::FilePath),
         )
         .arg(Arg::new("out_file").value_hint(ValueHint::FilePath))
+        .arg(
+          Arg::new("additional_source_files")
+            .num_args(1..)
+            .requires("outdir")
+            .value_hint(ValueHint::FilePath)
+            .help("Additional entry points when bundling to an output directory"),
+        )
+        .arg(
+          Arg::new("outdir")
+            .long("outdir")
+            .value_name("DIR")
+            .help("Bundle all given files as entry points into this directory, splitting shared code into chunks")
+            .value_hint(ValueHint::DirPath),
+        )
+        .arg(
+          Arg::new("minify")
+            .long("minify")
+            .action(ArgAction::SetTrue)
+            .help("Minify the bundle by removing whitespace and shortening variable names"),
+        )
+        .arg(
+          Arg::new("sourcemap")
+            .long("sourcemap")
+            .value_name("KIND")
+            .num_args(0..=1)
+            .require_equals(true)
+            .default_missing_value("external")
+            .value_parser(["external", "inline"])
+            .help("Emit a source map for the bundle, either as a separate .map file (default) or inlined into the bundle"),
+        )
         .arg(watch_arg(false))
         .arg(watch_exclude_arg())
         .arg(no_clear_screen_arg())
+        .arg(clear_screen_arg())
         .arg(executable_ext_arg())
     })
 }
@@ -1459,15 +1787,70 @@ them in the local cache, without running any code:
   deno cache jsr:@std/http/file-server
 
 Future runs of this module will trigger no downloads or compilation unless
---reload is specified.",
+--reload is specified.
+
+Show the disk usage of each origin and list the cached remote modules, their
+emits and the npm packages that aren't referenced by the graph of the provided
+modules, the lockfile or the lockfile of any other project that used the cache:
+
+  deno cache --prune main.ts
+
+Use --force to remove them. This requires a lockfile or modules to keep, since
+the cache is shared by every project.
+
+Export the cached remote modules of a graph to an archive, along with their
+checksums, and import it on another machine to populate its cache without
+network access:
+
+  deno cache --export cache.tar.zst main.ts
+  deno cache --import cache.tar.zst",
     )
     .defer(|cmd| {
-      compile_args(cmd).arg(check_arg(false)).arg(
-        Arg::new("file")
-          .num_args(1..)
-          .required(true)
-          .value_hint(ValueHint::FilePath),
-      )
+      compile_args(cmd)
+        .arg(check_arg(false))
+        .arg(
+          Arg::new("prune")
+            .long("prune")
+            .help("Remove unreferenced modules and packages from the cache")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("dry-run")
+            .long("dry-run")
+            .help("List what would be removed without removing it (default)")
+            .requires("prune")
+            .conflicts_with("force")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("force")
+            .long("force")
+            .help("Remove the unreferenced modules and packages")
+            .requires("prune")
+            .action(ArgAction::SetTrue),
+        )
+        .arg(
+          Arg::new("export")
+            .long("export")
+            .value_name("FILE")
+            .help("Export the cached remote modules of the graph to an archive")
+            .conflicts_with_all(["prune", "import"])
+            .value_hint(ValueHint::FilePath),
+        )
+        .arg(
+          Arg::new("import")
+            .long("import")
+            .value_name("FILE")
+            .help("Import the remote modules of an exported archive")
+            .conflicts_with("prune")
+            .value_hint(ValueHint::FilePath),
+        )
+        .arg(
+          Arg::new("file")
+            .num_args(1..)
+            .required_unless_present_any(["prune", "import"])
+            .value_hint(ValueHint::FilePath),
+        )
     })
 }
 
@@ -1479,6 +1862,15 @@ fn check_subcommand() -> Command {
 
   deno check jsr:@std/http/file-server
 
+When no files are provided, all the files in the current directory are
+type-checked, using the \"include\" and \"exclude\" options of the
+configuration file:
+
+  deno check
+
+The results are cached, so only the modules affected by a change are
+type-checked again.
+
 Unless --reload is specified, this command will not re-download already cached dependencies.",
       )
     .defer(|cmd| compile_args_without_check_args(cmd).arg(
@@ -1497,10 +1889,27 @@ Unless --reload is specified, this command will not re-download already cached d
           .conflicts_with("no-remote")
           .hide(true)
       )
+      .arg(
+        Arg::new("error-limit")
+          .long("error-limit")
+          .help("Maximum number of diagnostics to display")
+          .long_help(
+            "Maximum number of diagnostics to display. The remaining diagnostics
+are counted by category instead of being displayed.",
+          )
+          .value_name("N")
+          .value_parser(value_parser!(usize))
+          .conflicts_with("summary")
+      )
+      .arg(
+        Arg::new("summary")
+          .long("summary")
+          .help("Only display the number of diagnostics per file")
+          .action(ArgAction::SetTrue)
+      )
       .arg(
         Arg::new("file")
-          .num_args(1..)
-          .required(true)
+          .num_args(0..)
           .value_hint(ValueHint::FilePath),
       )
     )
@@ -1525,10 +1934,14 @@ is something generic like 'main', 'mod', 'index' or 'cli', and the path has no
 parent, take the file name of the parent path. Otherwise settle with the
 generic name. If the resulting name has an '@...' suffix, strip it.
 
-Cross-compiling to different target architectures is supported using the
-`--target` flag. On the first invocation with deno will download proper
-binary and cache it in $DENO_DIR. The aarch64-apple-darwin target is not
-supported in canary.
+Cross-compiling to different target architectures is supported from any host
+using the `--target` flag:
+
+  deno compile --target x86_64-pc-windows-msvc main.ts
+
+On the first compilation for a target, deno will download the matching base
+binary and cache it in $DENO_DIR for later compilations. The
+aarch64-apple-darwin target is not supported in canary.
 ",
     )
     .defer(|cmd| {
@@ -1537,12 +1950,18 @@ supported in canary.
       .arg(
         Arg::new("include")
           .long("include")
-          .help("Additional module to include in the module graph")
+          .help("Additional module or directory to include in the executable")
           .long_help(
             "Includes an additional module in the compiled executable's module
-    graph. Use this flag if a dynamically imported module or a web worker main
-    module fails to load in the executable. This flag can be passed multiple
-    times, to include multiple additional modules.",
+    graph. Dynamic imports of string literals and web workers created with
+    `new Worker(new URL(\"./worker.ts\", import.meta.url))` are included
+    automatically, so use this flag if any other dynamically imported module
+    or web worker main module fails to load in the executable. This flag can
+    be passed multiple times, to include multiple additional modules.
+
+    When a directory is provided, all of its files are embedded in the
+    executable and can be read with the file system APIs and fetch at the
+    same path as when compiling, for example relative to import.meta.url.",
           )
           .action(ArgAction::Append)
           .value_hint(ValueHint::FilePath),
@@ -1558,7 +1977,7 @@ supported in canary.
       .arg(
         Arg::new("target")
           .long("target")
-          .help("Target OS architecture")
+          .help("Target OS architecture (defaults to the current host)")
           .value_parser([
             "x86_64-unknown-linux-gnu",
             "aarch64-unknown-linux-gnu",
@@ -1573,6 +1992,48 @@ supported in canary.
           .help("Hide terminal on Windows")
           .action(ArgAction::SetTrue),
       )
+      .arg(
+        Arg::new("no-web-apis")
+          .long("no-web-apis")
+          .help("Use a smaller runtime without rarely used web APIs")
+          .long_help(
+            "Use a smaller base binary for the executable, which excludes rarely \
+used web APIs in order to reduce the size of the executable. Currently, this \
+excludes the WebGPU API, so `navigator.gpu` is undefined, and \
+`createImageBitmap()`, which rejects.",
+          )
+          .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("icon")
+          .long("icon")
+          .help("Set the icon of the executable from an .ico file (Windows only)")
+          .value_hint(ValueHint::FilePath),
+      )
+      .arg(
+        Arg::new("product-name")
+          .long("product-name")
+          .help("Set the product name of the executable (Windows and macOS only)"),
+      )
+      .arg(
+        Arg::new("version")
+          .long("version")
+          .help("Set the version of the executable, ex. 1.2.3 (Windows and macOS only)"),
+      )
+      .arg(
+        Arg::new("sign-cmd")
+          .long("sign-cmd")
+          .value_name("COMMAND")
+          .help("Command used to sign the executable after it is written")
+          .long_help(
+            "Command used to sign the executable after it is written. The path \
+of the executable is appended as the last argument and the command is run \
+using the same shell as `deno task`.
+
+  deno compile --sign-cmd=\"codesign --sign 'Developer ID Application: Me'\" main.ts
+  deno compile --sign-cmd=\"signtool sign /fd SHA256 /a\" main.ts",
+          ),
+      )
       .arg(executable_ext_arg())
       .arg(env_file_arg())
       .arg(script_arg().required(true).trailing_var_arg(true))
@@ -1629,11 +2090,21 @@ Write a report using the lcov format:
 
   deno coverage --lcov --output=cov.lcov cov_profile/
 
-Generate html reports from lcov:
+Generate an HTML report in the given directory (defaults to cov_profile/html/):
+
+  deno coverage --html=html_cov cov_profile/
 
-  genhtml -o html_cov cov.lcov
+Fail if less than 80% of lines or 70% of branches are covered:
+
+  deno coverage --fail-under-lines=80 --fail-under-branches=70 cov_profile
+
+Combine the profiles of several runs before generating a report:
+
+  deno coverage merge cov_linux cov_windows --output=cov_merged
 ",
     )
+    .args_conflicts_with_subcommands(true)
+    .subcommand(coverage_merge_subcommand())
     .defer(|cmd| {
       cmd
         .arg(
@@ -1688,10 +2159,18 @@ Generate html reports from lcov:
         .arg(
           Arg::new("html")
             .long("html")
+            .value_name("DIR")
+            .num_args(0..=1)
+            .require_equals(true)
             .help(
               "Output coverage report in HTML format in the given directory",
             )
-            .action(ArgAction::SetTrue),
+            .long_help(
+              "Output coverage report in HTML format in the given directory.
+If DIR is not specified, the report is written to the html/ directory of the
+first coverage profile directory.",
+            )
+            .value_hint(ValueHint::DirPath),
         )
         .arg(
           Arg::new("detailed")
@@ -1699,6 +2178,26 @@ Generate html reports from lcov:
             .help("Output coverage report in detailed format in the terminal.")
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("fail-under-lines")
+            .long("fail-under-lines")
+            .value_name("PERCENT")
+            .require_equals(true)
+            .value_parser(value_parser!(u8).range(0..=100))
+            .help(
+              "Exit with an error if line coverage is below this percentage",
+            ),
+        )
+        .arg(
+          Arg::new("fail-under-branches")
+            .long("fail-under-branches")
+            .value_name("PERCENT")
+            .require_equals(true)
+            .value_parser(value_parser!(u8).range(0..=100))
+            .help(
+              "Exit with an error if branch coverage is below this percentage",
+            ),
+        )
         .arg(
           Arg::new("files")
             .num_args(0..)
@@ -1708,6 +2207,40 @@ Generate html reports from lcov:
     })
 }
 
+fn coverage_merge_subcommand() -> Command {
+  Command::new("merge")
+    .about("Merge coverage profiles")
+    .long_about(
+      "Merge coverage profiles collected by several runs into a single profile.
+
+Hit counts of scripts that were covered by more than one run are summed, so
+reports generated from the merged profile reflect all of the runs:
+
+  deno coverage merge cov_shard_1 cov_shard_2 --output=cov_merged
+  deno coverage cov_merged
+",
+    )
+    .defer(|cmd| {
+      cmd
+        .arg(
+          Arg::new("output")
+            .short('o')
+            .long("output")
+            .value_name("DIR")
+            .required(true)
+            .help("Directory to write the merged coverage profile to")
+            .value_hint(ValueHint::DirPath),
+        )
+        .arg(
+          Arg::new("files")
+            .num_args(1..)
+            .required(true)
+            .action(ArgAction::Append)
+            .value_hint(ValueHint::AnyPath),
+        )
+    })
+}
+
 fn doc_subcommand() -> Command {
   Command::new("doc")
     .about("Show documentation for a module")
@@ -1732,14 +2265,25 @@ Output documentation in JSON format:
 
     deno doc --json ./path/to/module.ts
 
-Lint a module for documentation diagnostics:
+Lint a module for documentation diagnostics, like exported symbols without
+JSDoc or {@link} tags that can't be resolved:
 
     deno doc --lint ./path/to/module.ts
 
+Output the documentation as a versioned JSON object, which lists the
+documentation diagnostics as well with --lint:
+
+    deno doc --json --json-version=1 ./path/to/module.ts
+    deno doc --lint --json --json-version=1 ./path/to/module.ts
+
 Target a specific symbol:
 
     deno doc ./path/to/module.ts MyClass.someField
 
+Output the documentation of a specific symbol in JSON format:
+
+    deno doc --json --filter=MyClass.someField ./path/to/module.ts
+
 Show documentation for runtime built-ins:
 
     deno doc
@@ -1759,6 +2303,14 @@ Show documentation for runtime built-ins:
             .help("Output documentation in JSON format")
             .action(ArgAction::SetTrue),
         )
+        .arg(
+          Arg::new("json-version")
+            .long("json-version")
+            .help("Output a JSON object of the given version instead of an array of nodes")
+            .require_equals(true)
+            .value_parser(value_parser!(u32).range(1..=1))
+            .requires("json"),
+        )
         .arg(
           Arg::new("html")
             .long("html")
@@ -1793,7 +2345,6 @@ Show documentation for runtime built-ins:
             .long("filter")
             .help("Dot separated path to symbol")
             .required(false)
-            .conflicts_with("json")
             .conflicts_with("lint")
             .conflicts_with("html"),
         )
@@ -1879,6 +2430,18 @@ Format stdin and write to stdout:
 
   cat file.ts | deno fmt -
 
+Format stdin as if it was the contents of a file at the given path, which
+selects the formatter from its extension and applies the configuration file
+that would apply to it:
+
+  cat README.md | deno fmt --stdin-filepath=docs/README.md
+
+Configure the formatter with flags, which take precedence over the
+\"lineWidth\", \"indentWidth\", \"useTabs\", \"singleQuote\", \"proseWrap\" and
+\"semiColons\" options in the \"fmt\" section of the configuration file:
+
+  deno fmt --line-width=100 --indent-width=4 --single-quote --no-semicolons
+
 Ignore formatting code by preceding it with an ignore comment:
 
   // deno-fmt-ignore
@@ -1907,6 +2470,22 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
               "ts", "tsx", "js", "jsx", "md", "json", "jsonc", "ipynb",
             ]),
         )
+        .arg(
+          Arg::new("stdin-filepath")
+            .long("stdin-filepath")
+            .require_equals(true)
+            .value_name("PATH")
+            .help("Format stdin as the contents of the file at this path")
+            .long_help(
+              "Format the text read from stdin as the contents of the file at \
+this path and write the result to stdout. The extension of the path selects \
+the formatter and the configuration file is discovered from it. The text is \
+written unchanged if the path is excluded from formatting. The file doesn't \
+need to exist.",
+            )
+            .conflicts_with_all(["files", "ext", "watch"])
+            .value_hint(ValueHint::FilePath),
+        )
         .arg(
           Arg::new("ignore")
             .long("ignore")
@@ -1926,6 +2505,7 @@ Ignore formatting a file by adding an ignore comment at the top of the file:
         .arg(watch_arg(false))
         .arg(watch_exclude_arg())
         .arg(no_clear_screen_arg())
+        .arg(clear_screen_arg())
         .arg(
           Arg::new("use-tabs")
             .long("use-tabs")
@@ -2013,6 +2593,11 @@ type: JavaScript, TypeScript, or JSON.
 emit: Local path of compiled source code. (TypeScript only.)
 dependencies: Dependency tree of the source file.
 
+Output the module graph in Graphviz DOT or Mermaid syntax:
+
+  deno info --graph=dot main.ts | dot -Tsvg > graph.svg
+  deno info --graph=mermaid main.ts
+
 Without any additional arguments, 'deno info' shows:
 
 DENO_DIR: Directory containing Deno-managed files.
@@ -2034,6 +2619,7 @@ TypeScript compiler cache: Subdirectory containing TS compiler output.",
       .arg(no_npm_arg())
       .arg(lock_arg())
       .arg(lock_write_arg())
+      .arg(frozen_lockfile_arg())
       .arg(no_lock_arg())
       .arg(config_arg())
       .arg(import_map_arg())
@@ -2044,6 +2630,15 @@ TypeScript compiler cache: Subdirectory containing TS compiler output.",
           .long("json")
           .help("UNSTABLE: Outputs the information in JSON format")
           .action(ArgAction::SetTrue),
+      )
+      .arg(
+        Arg::new("graph")
+          .long("graph")
+          .help("Outputs the module graph in Graphviz DOT or Mermaid syntax with the modules colored by origin")
+          .value_parser(["dot", "mermaid"])
+          .require_equals(true)
+          .requires("file")
+          .conflicts_with("json"),
       ))
 }
 
@@ -2199,6 +2794,10 @@ Print result as JSON:
 
   deno lint --json
 
+Print result as SARIF, e.g. for GitHub code scanning:
+
+  deno lint --format=sarif > deno-lint.sarif
+
 Read from stdin:
 
   cat file.ts | deno lint -
@@ -2213,8 +2812,13 @@ rule name:
 
   // deno-lint-ignore no-explicit-any
   // deno-lint-ignore require-await no-empty
+  // deno-lint-ignore-next-line no-explicit-any
+
+Names of rules to ignore must be specified after ignore comment. The reason for
+ignoring them can follow \"--\", which is required when the
+\"requireSuppressionReason\" lint option is enabled:
 
-Names of rules to ignore must be specified after ignore comment.
+  // deno-lint-ignore no-explicit-any -- the type comes from JSON
 
 Ignore linting a file by adding an ignore comment at the top of the file:
 
@@ -2286,6 +2890,34 @@ Ignore linting a file by adding an ignore comment at the top of the file:
             .action(ArgAction::SetTrue)
             .conflicts_with("json"),
         )
+        .arg(
+          Arg::new("format")
+            .long("format")
+            .require_equals(true)
+            .value_name("FORMAT")
+            .value_parser(["pretty", "json", "compact", "sarif"])
+            .conflicts_with_all(["json", "compact"])
+            .help("Output lint result in the given format")
+            .long_help(
+              "Output lint result in the given format. The \"sarif\" format \
+is SARIF 2.1.0, which can be uploaded to GitHub code scanning and other \
+static analysis tools.",
+            ),
+        )
+        .arg(
+          Arg::new("max-warnings")
+            .long("max-warnings")
+            .require_equals(true)
+            .value_name("N")
+            .value_parser(value_parser!(usize))
+            .help("Fail if more than N problems are reported as warnings")
+            .long_help(
+              "Fail if more than N problems are reported as warnings. Rules \
+report warnings when their severity is set to \"warn\" in the \"severity\" \
+option of the \"lint\" section of the configuration file. By default warnings \
+don't cause a failure.",
+            ),
+        )
         .arg(
           Arg::new("files")
             .num_args(1..)
@@ -2296,21 +2928,139 @@ Ignore linting a file by adding an ignore comment at the top of the file:
         .arg(watch_arg(false))
         .arg(watch_exclude_arg())
         .arg(no_clear_screen_arg())
+        .arg(clear_screen_arg())
     })
 }
 
-fn repl_subcommand() -> Command {
-  Command::new("repl")
-    .about("Read Eval Print Loop")
-    .defer(|cmd| runtime_args(cmd, true, true)
-      .arg(check_arg(false))
-      .arg(
-        Arg::new("eval-file")
-          .long("eval-file")
+fn permissions_subcommand() -> Command {
+  Command::new("permissions")
+    .about("Manage permissions granted to the project")
+    .long_about(
+      "Manage the permissions granted to the project by answering a permission
+prompt with \"always allow for this project\". Stored permissions are granted
+on subsequent runs in addition to the permission flags.
+
+The project is the directory of the configuration file. When there is none,
+the permissions are stored for the script that was run, which is specified
+with --script:
+
+  deno permissions --script main.ts list
+
+List the stored permissions:
+
+  deno permissions list
+
+Revoke a stored permission, all stored permissions of a kind or all stored
+permissions of the project:
+
+  deno permissions revoke read /etc/hosts
+  deno permissions revoke net
+  deno permissions revoke --all",
+    )
+    .subcommand_required(true)
+    .defer(|cmd| {
+      cmd
+        .arg(config_arg())
+        .arg(no_config_arg())
+        .arg(
+          Arg::new("script")
+            .long("script")
+            .value_name("FILE")
+            .help("The script whose permissions are managed when there is no configuration file")
+            .value_hint(ValueHint::FilePath),
+        )
+        .subcommand(Command::new("list").about("List the stored permissions"))
+        .subcommand(
+          Command::new("revoke")
+            .about("Revoke stored permissions")
+            .arg(
+              Arg::new("name")
+                .required_unless_present("all")
+                .value_parser([
+                  "env", "ffi", "hrtime", "net", "read", "run", "sys", "write",
+                ])
+                .help("Kind of permission to revoke"),
+            )
+            .arg(Arg::new("value").help(
+              "Value of the permission to revoke, like a path or a host. \
+Revokes all values when omitted",
+            ))
+            .arg(
+              Arg::new("all")
+                .long("all")
+                .conflicts_with("name")
+                .action(ArgAction::SetTrue)
+                .help("Revoke all stored permissions of the project"),
+            ),
+        )
+    })
+}
+
+fn storage_subcommand() -> Command {
+  Command::new("storage")
+    .about("Inspect and clear data stored with localStorage")
+    .long_about(
+      "Inspect and clear the data stored with localStorage.
+
+Data is stored per origin: the origin of '--location' when it is set,
+otherwise the configuration file or the main module.
+
+List the origins with stored data:
+
+  deno storage ls
+
+Clear the data of an origin, as shown by 'deno storage ls', or of all origins:
+
+  deno storage clear https://example.com
+  deno storage clear --all",
+    )
+    .subcommand_required(true)
+    .defer(|cmd| {
+      cmd
+        .subcommand(
+          Command::new("ls").about("List the origins with stored data"),
+        )
+        .subcommand(
+          Command::new("clear")
+            .about("Clear stored data")
+            .arg(
+              Arg::new("origin")
+                .required_unless_present("all")
+                .help("Origin to clear the data of"),
+            )
+            .arg(
+              Arg::new("all")
+                .long("all")
+                .conflicts_with("origin")
+                .action(ArgAction::SetTrue)
+                .help("Clear the data of all origins"),
+            ),
+        )
+    })
+}
+
+fn repl_subcommand() -> Command {
+  Command::new("repl")
+    .about("Read Eval Print Loop")
+    .long_about(
+      "Read Eval Print Loop
+
+Print the type of an expression inferred by TypeScript, in the context of the
+code evaluated so far:
+
+  > const user = { name: \"Deno\", age: 5 };
+  > .type user
+  { name: string; age: number; }",
+    )
+    .defer(|cmd| runtime_args(cmd, true, true)
+      .arg(check_arg(false))
+      .arg(
+        Arg::new("eval-file")
+          .long("eval-file")
           .num_args(1..)
           .use_value_delimiter(true)
           .require_equals(true)
-          .help("Evaluates the provided file(s) as scripts when the REPL starts. Accepts file paths and URLs.")
+          .help("Evaluates the provided file(s) as scripts when the REPL starts. Accepts file paths and URLs. Runs after the files in \"repl.init\" of the configuration file.")
           .value_hint(ValueHint::AnyPath),
       )
       .arg(
@@ -2329,6 +3079,7 @@ fn run_subcommand() -> Command {
     .arg(watch_exclude_arg())
     .arg(hmr_arg(true))
     .arg(no_clear_screen_arg())
+    .arg(clear_screen_arg())
     .arg(executable_ext_arg())
     .arg(
       script_arg()
@@ -2391,6 +3142,7 @@ fn serve_subcommand() -> Command {
     .arg(watch_exclude_arg())
     .arg(hmr_arg(true))
     .arg(no_clear_screen_arg())
+    .arg(clear_screen_arg())
     .arg(executable_ext_arg())
     .arg(
       script_arg()
@@ -2423,7 +3175,33 @@ fn task_subcommand() -> Command {
     .long_about(
       "Run a task defined in the configuration file
 
-  deno task build",
+  deno task build
+
+Tasks are run by a cross-platform shell, so the same script works on
+Windows, macOS and Linux. It supports pipelines (|), boolean lists (&& and
+||), sequential (;) and async (&) commands, subshells, command substitution,
+glob expansion and environment variable assignments before a command:
+
+  \"tasks\": {
+    \"lint\": \"deno lint src/*.ts && echo done\",
+    \"serve\": \"PORT=8080 deno run --allow-net server.ts\"
+  }
+
+A task can also be an object with the command and its options. Tasks listed
+in \"dependsOn\" run first, \"cwd\" and \"env\" configure how the command runs,
+and \"watch\" lists the files that re-run the task with --watch:
+
+  \"tasks\": {
+    \"build\": {
+      \"cmd\": \"deno run -A build.ts\",
+      \"dependsOn\": [\"codegen\"],
+      \"cwd\": \"./web\",
+      \"env\": { \"MODE\": \"production\" },
+      \"watch\": [\"src/**/*.ts\"]
+    }
+  }
+
+  deno task --watch build",
     )
     .defer(|cmd| {
       cmd
@@ -2437,6 +3215,13 @@ fn task_subcommand() -> Command {
             .help("Specify the directory to run the task in")
             .value_hint(ValueHint::DirPath),
         )
+        .arg(watch_arg(false).long_help(
+          "Watch for file changes and re-run the task automatically.
+The files are configured with the \"watch\" option of the task.",
+        ))
+        .arg(no_clear_screen_arg())
+        .arg(clear_screen_arg())
+        .arg(watch_exclude_arg())
     })
 }
 
@@ -2483,7 +3268,7 @@ Directory arguments are expanded to all contained files matching the glob
     .arg(
       Arg::new("trace-leaks")
         .long("trace-leaks")
-        .help("Enable tracing of leaks. Useful when debugging leaking ops in test, but impacts test execution time.")
+        .help("Enable tracing of leaks. Useful when debugging leaking ops and resources in test, but impacts test execution time.")
         .action(ArgAction::SetTrue),
     )
     .arg(
@@ -2497,6 +3282,11 @@ Directory arguments are expanded to all contained files matching the glob
         .long("fail-fast")
         .alias("failfast")
         .help("Stop after N errors. Defaults to stopping after first failure.")
+        .long_help(
+          "Stop after N errors. Defaults to stopping after first failure. Tests
+that are still running in parallel when the limit is reached are cancelled
+and the remaining test modules are skipped.",
+        )
         .num_args(0..=1)
         .require_equals(true)
         .value_name("N")
@@ -2523,6 +3313,69 @@ Directory arguments are expanded to all contained files matching the glob
         .require_equals(true)
         .value_parser(value_parser!(u64)),
     )
+    .arg(
+      Arg::new("shard")
+        .long("shard")
+        .value_name("INDEX/COUNT")
+        .help("Only run the tests of one shard, e.g. --shard=3/8")
+        .long_help(
+          "Split the tests into COUNT shards and only run the tests of shard INDEX,
+e.g. --shard=3/8. Tests are assigned to shards deterministically, so running
+every shard once, e.g. on separate CI machines, runs every test exactly once.",
+        )
+        .require_equals(true)
+        .value_parser(|s: &str| s.parse::<TestShard>()),
+    )
+    .arg(
+      Arg::new("retries")
+        .long("retries")
+        .value_name("N")
+        .help("Run failing tests again, up to N times")
+        .long_help(
+          "Run failing tests again, up to N times. Tests that pass after being
+retried are reported as flaky. Can be overridden for a single test with
+the `retries` option of Deno.test().",
+        )
+        .require_equals(true)
+        .value_parser(value_parser!(u32)),
+    )
+    .arg(
+      Arg::new("timeout")
+        .long("timeout")
+        .value_name("MS")
+        .help("Fail tests that take longer than the given number of milliseconds")
+        .long_help(
+          "Fail tests that take longer than the given number of milliseconds,
+showing where they were stuck. Can be overridden for a single test with
+the `timeout` option of Deno.test().",
+        )
+        .require_equals(true)
+        .value_parser(value_parser!(u64).range(1..)),
+    )
+    .arg(
+      Arg::new("changed")
+        .long("changed")
+        .value_name("BASE_REF")
+        .help("Only run test modules affected by changes since a git ref")
+        .long_help(
+          "Only run the test modules that depend on files changed since the
+given git ref (defaults to HEAD), including uncommitted and untracked
+files.",
+        )
+        .num_args(0..=1)
+        .require_equals(true)
+        .default_missing_value("HEAD"),
+    )
+    .arg(
+      Arg::new("update-snapshots")
+        .long("update-snapshots")
+        .help("Update the snapshots used by TestContext.assertSnapshot()")
+        .long_help(
+          "Update the snapshots used by TestContext.assertSnapshot(). Snapshots
+are stored in a `__snapshots__` directory next to each test module.",
+        )
+        .action(ArgAction::SetTrue),
+    )
     .arg(
       Arg::new("coverage")
         .long("coverage")
@@ -2539,15 +3392,19 @@ Directory arguments are expanded to all contained files matching the glob
       Arg::new("parallel")
         .long("parallel")
         .help("Run test modules in parallel. Parallelism defaults to the number of available CPUs or the value in the DENO_JOBS environment variable.")
-        .conflicts_with("jobs")
         .action(ArgAction::SetTrue)
     )
     .arg(
       Arg::new("jobs")
         .short('j')
         .long("jobs")
-        .help("deprecated: The `--jobs` flag is deprecated and will be removed in Deno 2.0. Use the `--parallel` flag with possibly the `DENO_JOBS` environment variable instead.")
-        .hide(true)
+        .value_name("N")
+        .help("Run test modules in parallel, in up to N workers at a time")
+        .long_help(
+          "Run test modules in parallel, in up to N workers at a time. Defaults
+to the number of available CPUs when no value is given. The output of
+each test module is reported once it has completed.",
+        )
         .num_args(0..=1)
         .value_parser(value_parser!(NonZeroUsize)),
     )
@@ -2565,6 +3422,7 @@ Directory arguments are expanded to all contained files matching the glob
     )
     .arg(watch_exclude_arg())
     .arg(no_clear_screen_arg())
+    .arg(clear_screen_arg())
     .arg(script_arg().last(true))
     .arg(
       Arg::new("junit-path")
@@ -2577,7 +3435,7 @@ Directory arguments are expanded to all contained files matching the glob
       Arg::new("reporter")
         .long("reporter")
         .help("Select reporter to use. Default to 'pretty'.")
-        .value_parser(["pretty", "dot", "junit", "tap"])
+        .value_parser(["pretty", "dot", "junit", "tap", "json"])
     )
     .arg(env_file_arg())
   )
@@ -2664,7 +3522,13 @@ maps remote specifiers to the downloaded files.
 
 Remote modules and multiple modules may also be specified:
 
-  deno vendor main.ts test.deps.ts jsr:@std/path",
+  deno vendor main.ts test.deps.ts jsr:@std/path
+
+Running it again updates an existing output directory in place: modules
+that are new are added and the files of modules that are no longer depended
+on are removed. Already vendored modules are kept as they are, unless
+--force is provided. When a lockfile is used, the integrity of the vendored
+modules is recorded in it.",
       )
     .defer(|cmd| cmd
       .arg(
@@ -2756,6 +3620,7 @@ fn compile_args_without_check_args(app: Command) -> Command {
     .arg(reload_arg())
     .arg(lock_arg())
     .arg(lock_write_arg())
+    .arg(frozen_lockfile_arg())
     .arg(no_lock_arg())
     .arg(ca_file_arg())
 }
@@ -2801,23 +3666,27 @@ static DENY_WRITE_HELP: &str = concat!(
 );
 
 static ALLOW_NET_HELP: &str = concat!(
-  "Allow network access. Optionally specify allowed IP addresses and host names, with ports as necessary.\n",
+  "Allow network access. Optionally specify allowed IP addresses, CIDR blocks and host names, with \"*.\" for any subdomain, and ports or port ranges as necessary.\n",
+  "CIDR blocks only match IP addresses that are used directly, host names are not resolved to check them.\n",
   "Docs: https://deno.land/manual@v",
   env!("CARGO_PKG_VERSION"),
   "/basics/permissions\n",
   "Examples:\n",
   "  --allow-net\n",
-  "  --allow-net=\"localhost:8080,deno.land\""
+  "  --allow-net=\"localhost:8080,deno.land\"\n",
+  "  --allow-net=\"*.deno.land,10.0.0.0/8:8000-8080\""
 );
 
 static DENY_NET_HELP: &str = concat!(
-  "Deny network access. Optionally specify denied IP addresses and host names, with ports as necessary.\n",
+  "Deny network access. Optionally specify denied IP addresses, CIDR blocks and host names, with \"*.\" for any subdomain, and ports or port ranges as necessary.\n",
+  "CIDR blocks only match IP addresses that are used directly, host names are not resolved to check them.\n",
   "Docs: https://deno.land/manual@v",
   env!("CARGO_PKG_VERSION"),
   "/basics/permissions\n",
   "Examples:\n",
   "  --deny-net\n",
-  "  --deny-net=\"localhost:8080,deno.land\""
+  "  --deny-net=\"localhost:8080,deno.land\"\n",
+  "  --deny-net=\"*.deno.land,10.0.0.0/8:8000-8080\""
 );
 
 static ALLOW_ENV_HELP: &str = concat!(
@@ -2921,6 +3790,23 @@ static ALLOW_ALL_HELP: &str = concat!(
   "/basics/permissions\n"
 );
 
+static PERMISSION_SET_HELP: &str = concat!(
+  "Grant the permissions of the \"permissions\" property of the config file.\n",
+  "They are ignored when other permission flags are provided.\n",
+  "Docs: https://deno.land/manual@v",
+  env!("CARGO_PKG_VERSION"),
+  "/getting_started/configuration_file\n"
+);
+
+static PERMISSION_POLICY_HELP: &str = concat!(
+  "Load permissions from a policy file. The policy lists the allowed paths,\n",
+  "hosts, environment variables and libraries, and can restrict the\n",
+  "permissions of workers loaded from specific origins.\n",
+  "Docs: https://deno.land/manual@v",
+  env!("CARGO_PKG_VERSION"),
+  "/basics/permissions\n"
+);
+
 fn permission_args(app: Command) -> Command {
   app
     .arg(
@@ -3107,6 +3993,21 @@ fn permission_args(app: Command) -> Command {
         .action(ArgAction::SetTrue)
         .help(ALLOW_ALL_HELP),
     )
+    .arg(
+      Arg::new("permission-policy")
+        .long("permission-policy")
+        .require_equals(true)
+        .value_name("FILE")
+        .help(PERMISSION_POLICY_HELP)
+        .value_hint(ValueHint::FilePath),
+    )
+    .arg(
+      Arg::new("permission-set")
+        .short('P')
+        .long("permission-set")
+        .action(ArgAction::SetTrue)
+        .help(PERMISSION_SET_HELP),
+    )
     .arg(
       Arg::new("no-prompt")
         .long("no-prompt")
@@ -3133,9 +4034,12 @@ fn runtime_args(
   };
   app
     .arg(cached_only_arg())
+    .arg(revalidate_in_background_arg())
     .arg(location_arg())
+    .arg(local_storage_quota_arg())
     .arg(v8_flags_arg())
     .arg(seed_arg())
+    .arg(http1_only_arg())
     .arg(enable_testing_features_arg())
     .arg(strace_ops_arg())
 }
@@ -3179,7 +4083,17 @@ static IMPORT_MAP_HELP: &str = concat!(
   "Load import map file from local file or remote URL.
 Docs: https://docs.deno.com/runtime/manual/basics/import_maps
 Specification: https://wicg.github.io/import-maps/
-Examples: https://github.com/WICG/import-maps#the-import-map",
+Examples: https://github.com/WICG/import-maps#the-import-map
+
+This flag can be given multiple times, in which case the import maps are
+merged in order and the entries of later import maps take precedence over
+the entries of earlier ones. A warning is printed for each entry that is
+overridden with a different address:
+
+  --import-map=base_import_map.json --import-map=member/import_map.json
+
+Import maps specified with this flag replace the import map of the
+configuration file, including the import maps of its \"extends\" property.",
 );
 
 fn import_map_arg() -> Arg {
@@ -3190,6 +4104,7 @@ fn import_map_arg() -> Arg {
     .help("Load import map file")
     .long_help(IMPORT_MAP_HELP)
     .value_hint(ValueHint::FilePath)
+    .action(ArgAction::Append)
 }
 
 fn env_file_arg() -> Arg {
@@ -3243,6 +4158,24 @@ fn cached_only_arg() -> Arg {
     .long("cached-only")
     .action(ArgAction::SetTrue)
     .help("Require that remote dependencies are already cached")
+    .long_help(
+      "Require that remote dependencies are already cached. Cached versions are
+used even when they are stale according to their cache headers, in which
+case a warning lists the stale modules.",
+    )
+}
+
+fn revalidate_in_background_arg() -> Arg {
+  Arg::new("revalidate-in-background")
+    .long("revalidate-in-background")
+    .action(ArgAction::SetTrue)
+    .help("Refresh stale remote dependencies after the program starts")
+    .long_help(
+      "Refresh the remote dependencies whose cached version is stale according
+to their cache headers in the background, after the program starts, instead
+of blocking startup. The refreshed versions are used on the next run.",
+    )
+    .conflicts_with_all(["cached-only", "reload"])
 }
 
 /// Used for subcommands that operate on executable scripts only.
@@ -3277,6 +4210,14 @@ fn location_arg() -> Arg {
     .value_hint(ValueHint::Url)
 }
 
+fn local_storage_quota_arg() -> Arg {
+  Arg::new("local-storage-quota")
+    .long("local-storage-quota")
+    .value_name("MEGABYTES")
+    .help("Maximum size of the data stored with localStorage, 10MB by default")
+    .value_parser(value_parser!(u64).range(1..))
+}
+
 fn enable_testing_features_arg() -> Arg {
   Arg::new("enable-testing-features-do-not-use")
     .long("enable-testing-features-do-not-use")
@@ -3315,9 +4256,17 @@ fn seed_arg() -> Arg {
     .value_parser(value_parser!(u64))
 }
 
+fn http1_only_arg() -> Arg {
+  Arg::new("http1-only")
+    .long("http1-only")
+    .help("Disable HTTP/2 for fetch() and Deno.createHttpClient() and only use HTTP/1.1")
+    .action(ArgAction::SetTrue)
+}
+
 fn hmr_arg(takes_files: bool) -> Arg {
   let arg = Arg::new("hmr")
-    .long("unstable-hmr")
+    .long("watch-hmr")
+    .alias("unstable-hmr")
     .help("UNSTABLE: Watch for file changes and hot replace modules")
     .conflicts_with("watch");
 
@@ -3329,9 +4278,18 @@ fn hmr_arg(takes_files: bool) -> Arg {
       .use_value_delimiter(true)
       .require_equals(true)
       .long_help(
-        "Watch for file changes and restart process automatically.
-Local files from entry point module graph are watched by default.
-Additional paths might be watched by passing them as arguments to this flag.",
+        "Watch for file changes and hot replace changed modules without
+restarting the process. Local files from entry point module graph are watched
+by default. Additional paths or glob patterns might be watched by passing them
+as arguments to this flag.
+After a module is replaced, a \"deno:hmr\" event is dispatched on globalThis
+with the specifier of the module in \"event.detail.path\":
+
+  addEventListener(\"deno:hmr\", (e) => console.log(e.detail.path));
+
+The process is restarted when a module can't be replaced, e.g. when its
+top-level code changed, or when a file that isn't a module changed.
+Also available as --unstable-hmr.",
       )
       .value_hint(ValueHint::AnyPath)
   } else {
@@ -3356,8 +4314,13 @@ fn watch_arg(takes_files: bool) -> Arg {
       .require_equals(true)
       .long_help(
         "Watch for file changes and restart process automatically.
-Local files from entry point module graph are watched by default.
-Additional paths might be watched by passing them as arguments to this flag.",
+Local files from entry point module graph and files read by the program are
+watched by default. Additional paths or glob patterns might be watched by
+passing them as arguments to this flag, e.g. --watch=data/,assets/**/*.json.
+Use --watch-exclude to skip files or patterns.
+When restarting, the program receives an \"unload\" event and the changed
+paths are set in the DENO_WATCH_CHANGED_PATHS environment variable, separated
+like the paths in PATH.",
       )
       .value_hint(ValueHint::AnyPath)
   } else {
@@ -3373,9 +4336,19 @@ fn no_clear_screen_arg() -> Arg {
     .requires("watch")
     .long("no-clear-screen")
     .action(ArgAction::SetTrue)
+    .overrides_with("clear-screen")
     .help("Do not clear terminal screen when under watch mode")
 }
 
+fn clear_screen_arg() -> Arg {
+  Arg::new("clear-screen")
+    .requires("watch")
+    .long("clear-screen")
+    .action(ArgAction::SetTrue)
+    .overrides_with("no-clear-screen")
+    .help("Clear terminal screen when under watch mode (default)")
+}
+
 fn no_code_cache_arg() -> Arg {
   Arg::new("no-code-cache")
     .long("no-code-cache")
@@ -3473,6 +4446,14 @@ fn lock_write_arg() -> Arg {
     .conflicts_with("no-lock")
 }
 
+fn frozen_lockfile_arg() -> Arg {
+  Arg::new("frozen-lockfile")
+    .long("frozen-lockfile")
+    .action(ArgAction::SetTrue)
+    .help("Error out if the lock file is out of date instead of updating it.")
+    .conflicts_with_all(["lock-write", "no-lock"])
+}
+
 fn no_lock_arg() -> Arg {
   Arg::new("no-lock")
     .long("no-lock")
@@ -3569,6 +4550,7 @@ fn bench_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.permissions.no_prompt = true;
 
   let json = matches.get_flag("json");
+  let compare = matches.remove_one::<String>("compare");
 
   let ignore = match matches.remove_many::<String>("ignore") {
     Some(f) => f.collect(),
@@ -3595,6 +4577,7 @@ fn bench_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     files: FileFlags { include, ignore },
     filter,
     json,
+    compare,
     no_run,
     watch: watch_arg_parse(matches),
   });
@@ -3606,38 +4589,85 @@ fn bundle_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   compile_args_parse(flags, matches);
 
   let source_file = matches.remove_one::<String>("source_file").unwrap();
+  let out_dir = matches.remove_one::<String>("outdir");
+  let mut additional_source_files = Vec::new();
 
   let out_file =
     if let Some(out_file) = matches.remove_one::<String>("out_file") {
       flags.permissions.allow_write = Some(vec![]);
-      Some(out_file)
+      if out_dir.is_some() {
+        // with an output directory all positional arguments are entry points
+        additional_source_files.push(out_file);
+        None
+      } else {
+        Some(out_file)
+      }
     } else {
       None
     };
+  if out_dir.is_some() {
+    flags.permissions.allow_write = Some(vec![]);
+    if let Some(files) =
+      matches.remove_many::<String>("additional_source_files")
+    {
+      additional_source_files.extend(files);
+    }
+  }
+
+  let minify = matches.get_flag("minify");
+  let source_map =
+    matches
+      .remove_one::<String>("sourcemap")
+      .map(|kind| match kind.as_str() {
+        "inline" => BundleSourceMap::Inline,
+        _ => BundleSourceMap::External,
+      });
 
   ext_arg_parse(flags, matches);
 
   flags.subcommand = DenoSubcommand::Bundle(BundleFlags {
     source_file,
+    additional_source_files,
     out_file,
+    out_dir,
+    minify,
+    source_map,
     watch: watch_arg_parse(matches),
   });
 }
 
 fn cache_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   compile_args_parse(flags, matches);
-  let files = matches.remove_many::<String>("file").unwrap().collect();
-  flags.subcommand = DenoSubcommand::Cache(CacheFlags { files });
+  let files = match matches.remove_many::<String>("file") {
+    Some(f) => f.collect(),
+    None => vec![],
+  };
+  flags.subcommand = DenoSubcommand::Cache(CacheFlags {
+    files,
+    prune: matches.get_flag("prune"),
+    dry_run: matches.get_flag("prune") && !matches.get_flag("force"),
+    export: matches.remove_one::<String>("export"),
+    import: matches.remove_one::<String>("import"),
+  });
 }
 
 fn check_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.type_check_mode = TypeCheckMode::Local;
   compile_args_without_check_parse(flags, matches);
-  let files = matches.remove_many::<String>("file").unwrap().collect();
+  let files = match matches.remove_many::<String>("file") {
+    Some(f) => f.collect(),
+    None => vec![],
+  };
   if matches.get_flag("all") || matches.get_flag("remote") {
     flags.type_check_mode = TypeCheckMode::All;
   }
-  flags.subcommand = DenoSubcommand::Check(CheckFlags { files });
+  let error_limit = matches.remove_one::<usize>("error-limit");
+  let summary = matches.get_flag("summary");
+  flags.subcommand = DenoSubcommand::Check(CheckFlags {
+    files,
+    error_limit,
+    summary,
+  });
 }
 
 fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
@@ -3650,6 +4680,11 @@ fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let output = matches.remove_one::<String>("output");
   let target = matches.remove_one::<String>("target");
   let no_terminal = matches.get_flag("no-terminal");
+  let icon = matches.remove_one::<String>("icon");
+  let product_name = matches.remove_one::<String>("product-name");
+  let version = matches.remove_one::<String>("version");
+  let sign_cmd = matches.remove_one::<String>("sign-cmd");
+  let no_web_apis = matches.get_flag("no-web-apis");
   let include = match matches.remove_many::<String>("include") {
     Some(f) => f.collect(),
     None => vec![],
@@ -3663,6 +4698,11 @@ fn compile_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     target,
     no_terminal,
     include,
+    icon,
+    product_name,
+    version,
+    sign_cmd,
+    no_web_apis,
   });
 }
 
@@ -3696,6 +4736,9 @@ fn completions_parse(
 }
 
 fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  if let Some(("merge", mut merge_matches)) = matches.remove_subcommand() {
+    return coverage_merge_parse(flags, &mut merge_matches);
+  }
   let files = match matches.remove_many::<String>("files") {
     Some(f) => f.collect(),
     None => vec!["coverage".to_string()], // default
@@ -3712,16 +4755,19 @@ fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     Some(f) => f.collect(),
     None => vec![],
   };
+  let mut output = matches.remove_one::<String>("output");
   let r#type = if matches.get_flag("lcov") {
     CoverageType::Lcov
-  } else if matches.get_flag("html") {
+  } else if matches.contains_id("html") {
+    output = matches.remove_one::<String>("html");
     CoverageType::Html
   } else if matches.get_flag("detailed") {
     CoverageType::Detailed
   } else {
     CoverageType::Summary
   };
-  let output = matches.remove_one::<String>("output");
+  let fail_under_lines = matches.remove_one::<u8>("fail-under-lines");
+  let fail_under_branches = matches.remove_one::<u8>("fail-under-branches");
   flags.subcommand = DenoSubcommand::Coverage(CoverageFlags {
     files: FileFlags {
       include: files,
@@ -3731,6 +4777,20 @@ fn coverage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     include,
     exclude,
     r#type,
+    fail_under_lines,
+    fail_under_branches,
+  });
+}
+
+fn coverage_merge_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let files = matches.remove_many::<String>("files").unwrap().collect();
+  let output = matches.remove_one::<String>("output").unwrap();
+  flags.subcommand = DenoSubcommand::CoverageMerge(CoverageMergeFlags {
+    files: FileFlags {
+      include: files,
+      ignore: vec![],
+    },
+    output,
   });
 }
 
@@ -3763,6 +4823,7 @@ fn doc_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let private = matches.get_flag("private");
   let lint = matches.get_flag("lint");
   let json = matches.get_flag("json");
+  let json_version = matches.remove_one::<u32>("json-version");
   let filter = matches.remove_one::<String>("filter");
   let html = if matches.get_flag("html") {
     let name = matches.remove_one::<String>("name");
@@ -3777,6 +4838,7 @@ fn doc_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.subcommand = DenoSubcommand::Doc(DocFlags {
     source_files,
     json,
+    json_version,
     lint,
     html,
     filter,
@@ -3831,6 +4893,7 @@ fn fmt_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let single_quote = matches.remove_one::<bool>("single-quote");
   let prose_wrap = matches.remove_one::<String>("prose-wrap");
   let no_semicolons = matches.remove_one::<bool>("no-semicolons");
+  let stdin_filepath = matches.remove_one::<String>("stdin-filepath");
 
   flags.subcommand = DenoSubcommand::Fmt(FmtFlags {
     check: matches.get_flag("check"),
@@ -3841,6 +4904,7 @@ fn fmt_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     single_quote,
     prose_wrap,
     no_semicolons,
+    stdin_filepath,
     watch: watch_arg_parse(matches),
   });
 }
@@ -3862,9 +4926,18 @@ fn info_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   no_remote_arg_parse(flags, matches);
   no_npm_arg_parse(flags, matches);
   let json = matches.get_flag("json");
+  let graph =
+    matches
+      .remove_one::<String>("graph")
+      .map(|graph| match graph.as_str() {
+        "dot" => InfoGraphFormat::Dot,
+        "mermaid" => InfoGraphFormat::Mermaid,
+        _ => unreachable!(),
+      });
   flags.subcommand = DenoSubcommand::Info(InfoFlags {
     file: matches.remove_one::<String>("file"),
     json,
+    graph,
   });
 }
 
@@ -3923,6 +4996,32 @@ fn lsp_parse(flags: &mut Flags, _matches: &mut ArgMatches) {
   flags.subcommand = DenoSubcommand::Lsp;
 }
 
+fn permissions_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  config_args_parse(flags, matches);
+  let script = matches.remove_one::<String>("script");
+  let subcommand = match matches.remove_subcommand() {
+    Some((subcommand, mut m)) if subcommand == "revoke" => {
+      PermissionGrantsFlags::Revoke {
+        name: m.remove_one::<String>("name"),
+        value: m.remove_one::<String>("value"),
+      }
+    }
+    _ => PermissionGrantsFlags::List,
+  };
+  flags.subcommand =
+    DenoSubcommand::Permissions(PermissionsFlags { script, subcommand });
+}
+
+fn storage_parse(flags: &mut Flags, matches: &mut ArgMatches) {
+  let subcommand = match matches.remove_subcommand() {
+    Some((subcommand, mut m)) if subcommand == "clear" => StorageFlags::Clear {
+      origin: m.remove_one::<String>("origin"),
+    },
+    _ => StorageFlags::List,
+  };
+  flags.subcommand = DenoSubcommand::Storage(subcommand);
+}
+
 fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   config_args_parse(flags, matches);
   let files = match matches.remove_many::<String>("files") {
@@ -3961,6 +5060,8 @@ fn lint_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     maybe_rules_exclude,
     json,
     compact,
+    format: matches.remove_one::<String>("format"),
+    max_warnings: matches.remove_one::<usize>("max-warnings"),
     watch: watch_arg_parse(matches),
   });
 }
@@ -4081,6 +5182,7 @@ fn task_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   let mut task_flags = TaskFlags {
     cwd: matches.remove_one::<String>("cwd"),
     task: None,
+    watch: watch_arg_parse(matches),
   };
 
   if let Some((task, mut matches)) = matches.remove_subcommand() {
@@ -4151,23 +5253,15 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     flags.argv.extend(script_arg);
   }
 
-  let concurrent_jobs = if matches.get_flag("parallel") {
-    if let Ok(value) = env::var("DENO_JOBS") {
-      value.parse::<NonZeroUsize>().ok()
+  let concurrent_jobs = if matches.contains_id("jobs") {
+    if let Some(value) = matches.remove_one::<NonZeroUsize>("jobs") {
+      Some(value)
     } else {
       std::thread::available_parallelism().ok()
     }
-  } else if matches.contains_id("jobs") {
-    // We can't change this to use the log crate because its not configured
-    // yet at this point since the flags haven't been parsed. This flag is
-    // deprecated though so it's not worth changing the code to use the log
-    // crate here and this is only done for testing anyway.
-    eprintln!(
-      "⚠️ {}",
-      crate::colors::yellow("The `--jobs` flag is deprecated and will be removed in Deno 2.0.\nUse the `--parallel` flag with possibly the `DENO_JOBS` environment variable instead.\nLearn more at: https://docs.deno.com/runtime/manual/basics/env_variables"),
-    );
-    if let Some(value) = matches.remove_one::<NonZeroUsize>("jobs") {
-      Some(value)
+  } else if matches.get_flag("parallel") {
+    if let Ok(value) = env::var("DENO_JOBS") {
+      value.parse::<NonZeroUsize>().ok()
     } else {
       std::thread::available_parallelism().ok()
     }
@@ -4190,13 +5284,19 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
         "junit" => TestReporterConfig::Junit,
         "dot" => TestReporterConfig::Dot,
         "tap" => TestReporterConfig::Tap,
+        "json" => TestReporterConfig::Json,
         _ => unreachable!(),
       }
     } else {
       TestReporterConfig::Pretty
     };
 
-  if matches!(reporter, TestReporterConfig::Dot | TestReporterConfig::Tap) {
+  if matches!(
+    reporter,
+    TestReporterConfig::Dot
+      | TestReporterConfig::Tap
+      | TestReporterConfig::Json
+  ) {
     flags.log_level = Some(Level::Error);
   }
 
@@ -4214,6 +5314,11 @@ fn test_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     watch: watch_arg_parse(matches),
     reporter,
     junit_path,
+    shard: matches.remove_one::<TestShard>("shard"),
+    retries: matches.remove_one::<u32>("retries").unwrap_or(0),
+    timeout: matches.remove_one::<u64>("timeout"),
+    update_snapshots: matches.get_flag("update-snapshots"),
+    changed: matches.remove_one::<String>("changed"),
   });
 }
 
@@ -4371,6 +5476,14 @@ fn permission_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
     flags.allow_all();
   }
 
+  if let Some(policy) = matches.remove_one::<String>("permission-policy") {
+    flags.permissions.permission_policy = Some(policy);
+  }
+
+  if matches.get_flag("permission-set") {
+    flags.permissions.permission_set = true;
+  }
+
   if matches.get_flag("no-prompt") {
     flags.permissions.no_prompt = true;
   }
@@ -4396,6 +5509,7 @@ fn runtime_args_parse(
 ) {
   compile_args_parse(flags, matches);
   cached_only_arg_parse(flags, matches);
+  revalidate_in_background_arg_parse(flags, matches);
   if include_perms {
     permission_args_parse(flags, matches);
   }
@@ -4403,8 +5517,10 @@ fn runtime_args_parse(
     inspect_arg_parse(flags, matches);
   }
   location_arg_parse(flags, matches);
+  flags.local_storage_quota = matches.remove_one::<u64>("local-storage-quota");
   v8_flags_arg_parse(flags, matches);
   seed_arg_parse(flags, matches);
+  flags.http1_only = matches.get_flag("http1-only");
   enable_testing_features_arg_parse(flags, matches);
   env_file_arg_parse(flags, matches);
   strace_ops_parse(flags, matches);
@@ -4442,7 +5558,12 @@ fn inspect_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
 }
 
 fn import_map_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
-  flags.import_map_path = matches.remove_one::<String>("import-map");
+  if let Some(mut import_map_paths) =
+    matches.remove_many::<String>("import-map")
+  {
+    flags.import_map_path = import_map_paths.next();
+    flags.additional_import_map_paths = import_map_paths.collect();
+  }
 }
 
 fn env_file_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
@@ -4487,6 +5608,13 @@ fn cached_only_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   }
 }
 
+fn revalidate_in_background_arg_parse(
+  flags: &mut Flags,
+  matches: &mut ArgMatches,
+) {
+  flags.revalidate_in_background = matches.get_flag("revalidate-in-background");
+}
+
 fn ext_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   flags.ext = matches.remove_one::<String>("ext");
 }
@@ -4543,6 +5671,7 @@ fn lock_args_parse(flags: &mut Flags, matches: &mut ArgMatches) {
   if matches.get_flag("lock-write") {
     flags.lock_write = true;
   }
+  flags.frozen_lockfile = matches.get_flag("frozen-lockfile");
 }
 
 fn lock_arg_parse(flags: &mut Flags, matches: &mut ArgMatches) {
@@ -4700,67 +5829,66 @@ mod tests {
   }
 
   #[test]
-  fn upgrade() {
-    let r = flags_from_vec(svec!["deno", "upgrade", "--dry-run", "--force"]);
-    let flags = r.unwrap();
+  fn error_format() {
+    let r =
+      flags_from_vec(svec!["deno", "run", "--error-format=json", "script.ts"]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
-          force: true,
-          dry_run: true,
-          canary: false,
-          version: None,
-          output: None,
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string()
+        )),
+        error_format: ErrorFormat::Json,
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
+
+    let r =
+      flags_from_vec(svec!["deno", "--error-format", "yaml", "run", "a.ts"]);
+    assert!(r.is_err());
   }
 
   #[test]
-  fn upgrade_with_output_flag() {
-    let r = flags_from_vec(svec!["deno", "upgrade", "--output", "example.txt"]);
+  fn hide_stack_frames() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--hide-stack-frames=vendor,node_modules",
+      "script.ts"
+    ]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
-          force: false,
-          dry_run: false,
-          canary: false,
-          version: None,
-          output: Some(String::from("example.txt")),
-        }),
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string()
+        )),
+        hide_stack_frames: vec![
+          StackFrameCategory::Vendor,
+          StackFrameCategory::NodeModules
+        ],
+        code_cache_enabled: true,
         ..Flags::default()
       }
     );
   }
 
   #[test]
-  fn version() {
-    let r = flags_from_vec(svec!["deno", "--version"]);
-    assert_eq!(
-      r.unwrap_err().kind(),
-      clap::error::ErrorKind::DisplayVersion
-    );
-    let r = flags_from_vec(svec!["deno", "-V"]);
-    assert_eq!(
-      r.unwrap_err().kind(),
-      clap::error::ErrorKind::DisplayVersion
-    );
-  }
-
-  #[test]
-  fn run_reload() {
-    let r = flags_from_vec(svec!["deno", "run", "-r", "script.ts"]);
-    let flags = r.unwrap();
+  fn error_context_lines() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--error-context-lines",
+      "3",
+      "script.ts"
+    ]);
     assert_eq!(
-      flags,
+      r.unwrap(),
       Flags {
         subcommand: DenoSubcommand::Run(RunFlags::new_default(
           "script.ts".to_string()
         )),
-        reload: true,
+        error_context_lines: 3,
         code_cache_enabled: true,
         ..Flags::default()
       }
@@ -4768,9 +5896,150 @@ mod tests {
   }
 
   #[test]
-  fn run_watch() {
-    let r = flags_from_vec(svec!["deno", "run", "--watch", "script.ts"]);
-    let flags = r.unwrap();
+  fn error_max_cause_depth() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--error-max-cause-depth",
+      "5",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string()
+        )),
+        error_max_cause_depth: Some(5),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn upgrade() {
+    let r = flags_from_vec(svec!["deno", "upgrade", "--dry-run", "--force"]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
+          force: true,
+          dry_run: true,
+          canary: false,
+          version: None,
+          output: None,
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn upgrade_with_output_flag() {
+    let r = flags_from_vec(svec!["deno", "upgrade", "--output", "example.txt"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Upgrade(UpgradeFlags {
+          force: false,
+          dry_run: false,
+          canary: false,
+          version: None,
+          output: Some(String::from("example.txt")),
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn version() {
+    let r = flags_from_vec(svec!["deno", "--version"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::DisplayVersion
+    );
+    let r = flags_from_vec(svec!["deno", "-V"]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::DisplayVersion
+    );
+  }
+
+  #[test]
+  fn run_reload() {
+    let r = flags_from_vec(svec!["deno", "run", "-r", "script.ts"]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string()
+        )),
+        reload: true,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn run_watch() {
+    let r = flags_from_vec(svec!["deno", "run", "--watch", "script.ts"]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            paths: vec![],
+            no_clear_screen: false,
+            exclude: vec![],
+          }),
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch",
+      "--no-clear-screen",
+      "script.ts"
+    ]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: false,
+            paths: vec![],
+            no_clear_screen: true,
+            exclude: vec![],
+          }),
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--watch",
+      "--no-clear-screen",
+      "--clear-screen",
+      "script.ts"
+    ]);
+    let flags = r.unwrap();
     assert_eq!(
       flags,
       Flags {
@@ -4792,6 +6061,7 @@ mod tests {
       "deno",
       "run",
       "--watch",
+      "--clear-screen",
       "--no-clear-screen",
       "script.ts"
     ]);
@@ -4838,6 +6108,26 @@ mod tests {
       }
     );
 
+    let r =
+      flags_from_vec(svec!["deno", "run", "--watch-hmr=foo.txt", "script.ts"]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags {
+          script: "script.ts".to_string(),
+          watch: Some(WatchFlagsWithPaths {
+            hmr: true,
+            paths: vec![String::from("foo.txt")],
+            no_clear_screen: false,
+            exclude: vec![],
+          }),
+        }),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
     let r = flags_from_vec(svec![
       "deno",
       "run",
@@ -5412,6 +6702,7 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          stdin_filepath: None,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -5435,6 +6726,7 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          stdin_filepath: None,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -5458,6 +6750,7 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          stdin_filepath: None,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -5481,6 +6774,7 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          stdin_filepath: None,
           watch: Some(Default::default()),
         }),
         ext: Some("ts".to_string()),
@@ -5505,6 +6799,7 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          stdin_filepath: None,
           watch: Some(WatchFlags {
             hmr: false,
             no_clear_screen: true,
@@ -5539,6 +6834,7 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          stdin_filepath: None,
           watch: Some(Default::default()),
         }),
         ext: Some("ts".to_string()),
@@ -5562,6 +6858,7 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          stdin_filepath: None,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -5593,6 +6890,7 @@ mod tests {
           single_quote: None,
           prose_wrap: None,
           no_semicolons: None,
+          stdin_filepath: None,
           watch: Some(Default::default()),
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
@@ -5629,6 +6927,7 @@ mod tests {
           single_quote: Some(true),
           prose_wrap: Some("never".to_string()),
           no_semicolons: Some(true),
+          stdin_filepath: None,
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
@@ -5659,12 +6958,57 @@ mod tests {
           single_quote: Some(false),
           prose_wrap: None,
           no_semicolons: Some(false),
+          stdin_filepath: None,
+          watch: Default::default(),
+        }),
+        ext: Some("ts".to_string()),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn fmt_stdin_filepath() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "fmt",
+      "--stdin-filepath=docs/README.md",
+      "--check"
+    ]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags,
+      Flags {
+        subcommand: DenoSubcommand::Fmt(FmtFlags {
+          check: true,
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          use_tabs: None,
+          line_width: None,
+          indent_width: None,
+          single_quote: None,
+          prose_wrap: None,
+          no_semicolons: None,
+          stdin_filepath: Some("docs/README.md".to_string()),
           watch: Default::default(),
         }),
         ext: Some("ts".to_string()),
         ..Flags::default()
       }
     );
+
+    let r =
+      flags_from_vec(svec!["deno", "fmt", "--stdin-filepath=mod.ts", "mod.ts"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "fmt",
+      "--stdin-filepath=mod.ts",
+      "--ext=md"
+    ]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -5685,6 +7029,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          format: None,
+          max_warnings: None,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -5713,6 +7059,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          format: None,
+          max_warnings: None,
           watch: Some(Default::default()),
         }),
         ..Flags::default()
@@ -5742,6 +7090,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          format: None,
+          max_warnings: None,
           watch: Some(WatchFlags {
             hmr: false,
             no_clear_screen: true,
@@ -5773,6 +7123,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          format: None,
+          max_warnings: None,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -5795,6 +7147,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          format: None,
+          max_warnings: None,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -5822,6 +7176,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: false,
+          format: None,
+          max_warnings: None,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -5850,6 +7206,8 @@ mod tests {
           maybe_rules_exclude: Some(svec!["no-const-assign"]),
           json: false,
           compact: false,
+          format: None,
+          max_warnings: None,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -5872,6 +7230,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          format: None,
+          max_warnings: None,
           watch: Default::default(),
         }),
         ..Flags::default()
@@ -5901,6 +7261,8 @@ mod tests {
           maybe_rules_exclude: None,
           json: true,
           compact: false,
+          format: None,
+          max_warnings: None,
           watch: Default::default(),
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
@@ -5931,12 +7293,65 @@ mod tests {
           maybe_rules_exclude: None,
           json: false,
           compact: true,
+          format: None,
+          max_warnings: None,
           watch: Default::default(),
         }),
         config_flag: ConfigFlag::Path("Deno.jsonc".to_string()),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "lint", "--max-warnings=0"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          format: None,
+          max_warnings: Some(0),
+          watch: Default::default(),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "lint", "--format=sarif"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Lint(LintFlags {
+          files: FileFlags {
+            include: vec![],
+            ignore: vec![],
+          },
+          fix: false,
+          rules: false,
+          maybe_rules_tags: None,
+          maybe_rules_include: None,
+          maybe_rules_exclude: None,
+          json: false,
+          compact: false,
+          format: Some("sarif".to_string()),
+          max_warnings: None,
+          watch: Default::default(),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "lint", "--format=sarif", "--json"]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -5959,6 +7374,10 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Cache(CacheFlags {
           files: svec!["script.ts"],
+          prune: false,
+          dry_run: false,
+          export: None,
+          import: None,
         }),
         ..Flags::default()
       }
@@ -5966,28 +7385,152 @@ mod tests {
   }
 
   #[test]
-  fn check() {
-    let r = flags_from_vec(svec!["deno", "check", "script.ts"]);
+  fn cache_prune() {
+    let r = flags_from_vec(svec!["deno", "cache", "--prune", "--dry-run"]);
     assert_eq!(
       r.unwrap(),
       Flags {
-        subcommand: DenoSubcommand::Check(CheckFlags {
-          files: svec!["script.ts"],
+        subcommand: DenoSubcommand::Cache(CacheFlags {
+          files: vec![],
+          prune: true,
+          dry_run: true,
+          export: None,
+          import: None,
         }),
-        type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
 
-    for all_flag in ["--remote", "--all"] {
-      let r = flags_from_vec(svec!["deno", "check", all_flag, "script.ts"]);
-      assert_eq!(
-        r.unwrap(),
-        Flags {
-          subcommand: DenoSubcommand::Check(CheckFlags {
-            files: svec!["script.ts"],
-          }),
-          type_check_mode: TypeCheckMode::All,
+    let r = flags_from_vec(svec!["deno", "cache", "--prune", "main.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Cache(CacheFlags {
+          files: svec!["main.ts"],
+          prune: true,
+          dry_run: true,
+          export: None,
+          import: None,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "cache", "--prune", "--force", "main.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Cache(CacheFlags {
+          files: svec!["main.ts"],
+          prune: true,
+          dry_run: false,
+          export: None,
+          import: None,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "cache", "--dry-run", "main.ts"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "cache"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn cache_export_and_import() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "cache",
+      "--export",
+      "cache.tar.zst",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Cache(CacheFlags {
+          files: svec!["main.ts"],
+          prune: false,
+          dry_run: false,
+          export: Some("cache.tar.zst".to_string()),
+          import: None,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "cache", "--import", "cache.tar.zst"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Cache(CacheFlags {
+          files: vec![],
+          prune: false,
+          dry_run: false,
+          export: None,
+          import: Some("cache.tar.zst".to_string()),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "cache", "--export", "cache.tar.zst"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "cache",
+      "--export",
+      "a.tar.zst",
+      "--import",
+      "b.tar.zst",
+      "main.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn check() {
+    let r = flags_from_vec(svec!["deno", "check", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Check(CheckFlags {
+          files: svec!["script.ts"],
+          error_limit: None,
+          summary: false,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "check"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Check(CheckFlags {
+          files: vec![],
+          error_limit: None,
+          summary: false,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    for all_flag in ["--remote", "--all"] {
+      let r = flags_from_vec(svec!["deno", "check", all_flag, "script.ts"]);
+      assert_eq!(
+        r.unwrap(),
+        Flags {
+          subcommand: DenoSubcommand::Check(CheckFlags {
+            files: svec!["script.ts"],
+            error_limit: None,
+            summary: false,
+          }),
+          type_check_mode: TypeCheckMode::All,
           ..Flags::default()
         }
       );
@@ -6006,6 +7549,50 @@ mod tests {
     }
   }
 
+  #[test]
+  fn check_error_limit_and_summary() {
+    let r =
+      flags_from_vec(svec!["deno", "check", "--error-limit", "5", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Check(CheckFlags {
+          files: svec!["script.ts"],
+          error_limit: Some(5),
+          summary: false,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "check", "--summary", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Check(CheckFlags {
+          files: svec!["script.ts"],
+          error_limit: None,
+          summary: true,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "check",
+      "--summary",
+      "--error-limit=5",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap_err().kind(),
+      clap::error::ErrorKind::ArgumentConflict
+    );
+  }
+
   #[test]
   fn info() {
     let r = flags_from_vec(svec!["deno", "info", "script.ts"]);
@@ -6014,6 +7601,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
+          graph: None,
           file: Some("script.ts".to_string()),
         }),
         ..Flags::default()
@@ -6026,6 +7614,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
+          graph: None,
           file: Some("script.ts".to_string()),
         }),
         reload: true,
@@ -6039,6 +7628,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
+          graph: None,
           file: Some("script.ts".to_string()),
         }),
         ..Flags::default()
@@ -6051,6 +7641,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
+          graph: None,
           file: None
         }),
         ..Flags::default()
@@ -6063,6 +7654,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: true,
+          graph: None,
           file: None
         }),
         ..Flags::default()
@@ -6082,6 +7674,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
+          graph: None,
           file: None
         }),
         config_flag: ConfigFlag::Path("tsconfig.json".to_owned()),
@@ -6090,6 +7683,27 @@ mod tests {
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "info", "--graph=mermaid", "mod.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Info(InfoFlags {
+          json: false,
+          graph: Some(InfoGraphFormat::Mermaid),
+          file: Some("mod.ts".to_string()),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "info", "--graph=svg", "mod.ts"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "info", "--graph=dot"]);
+    assert!(r.is_err());
+    let r =
+      flags_from_vec(svec!["deno", "info", "--graph=dot", "--json", "mod.ts"]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -6431,6 +8045,83 @@ mod tests {
     );
   }
 
+  #[test]
+  fn allow_all_with_deny_flags() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "-A",
+      "--deny-read=/etc",
+      "--deny-net=metadata.internal",
+      "script.ts"
+    ]);
+    let flags = r.unwrap();
+    assert!(flags.permissions.allow_all);
+    assert_eq!(flags.permissions.deny_read, Some(svec!["/etc"]));
+    assert_eq!(flags.permissions.deny_net, Some(svec!["metadata.internal"]));
+    assert_eq!(
+      flags.to_permission_args(),
+      svec![
+        "--allow-all",
+        "--deny-read=/etc",
+        "--deny-net=metadata.internal"
+      ]
+    );
+  }
+
+  #[test]
+  fn http1_only() {
+    let r = flags_from_vec(svec!["deno", "run", "--http1-only", "script.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        http1_only: true,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn permission_policy() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--permission-policy=policy.json",
+      "script.ts"
+    ]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags.permissions,
+      PermissionFlags {
+        permission_policy: Some("policy.json".to_string()),
+        ..Default::default()
+      }
+    );
+    assert!(flags.permissions.has_permission());
+    assert_eq!(
+      flags.to_permission_args(),
+      svec!["--permission-policy=policy.json"]
+    );
+  }
+
+  #[test]
+  fn permission_set() {
+    let r = flags_from_vec(svec!["deno", "run", "-P", "script.ts"]);
+    let flags = r.unwrap();
+    assert_eq!(
+      flags.permissions,
+      PermissionFlags {
+        permission_set: true,
+        ..Default::default()
+      }
+    );
+    assert!(!flags.permissions.has_permission());
+  }
+
   #[test]
   fn deny_read_denylist() {
     use test_util::TempDir;
@@ -6922,13 +8613,109 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Bundle(BundleFlags {
           source_file: "source.ts".to_string(),
+          additional_source_files: vec![],
+          out_file: None,
+          out_dir: None,
+          minify: false,
+          source_map: None,
+          watch: Default::default(),
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn bundle_with_outdir() {
+    let r = flags_from_vec(svec![
+      "deno", "bundle", "--outdir", "dist", "a.ts", "b.ts", "c.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Bundle(BundleFlags {
+          source_file: "a.ts".to_string(),
+          additional_source_files: svec!["b.ts", "c.ts"],
           out_file: None,
+          out_dir: Some("dist".to_string()),
+          minify: false,
+          source_map: None,
           watch: Default::default(),
         }),
+        permissions: PermissionFlags {
+          allow_write: Some(vec![]),
+          ..Default::default()
+        },
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "bundle", "--outdir", "dist", "a.ts"]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Bundle(BundleFlags {
+        source_file: "a.ts".to_string(),
+        additional_source_files: vec![],
+        out_file: None,
+        out_dir: Some("dist".to_string()),
+        minify: false,
+        source_map: None,
+        watch: Default::default(),
+      })
+    );
+
+    // more than one output file requires --outdir
+    let r = flags_from_vec(svec!["deno", "bundle", "a.ts", "b.js", "c.ts"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn bundle_with_minify_and_sourcemap() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "bundle",
+      "--minify",
+      "--sourcemap",
+      "source.ts",
+      "bundle.js"
+    ]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Bundle(BundleFlags {
+        source_file: "source.ts".to_string(),
+        additional_source_files: vec![],
+        out_file: Some("bundle.js".to_string()),
+        out_dir: None,
+        minify: true,
+        source_map: Some(BundleSourceMap::External),
+        watch: Default::default(),
+      })
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "bundle",
+      "--sourcemap=inline",
+      "source.ts"
+    ]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Bundle(BundleFlags {
+        source_file: "source.ts".to_string(),
+        additional_source_files: vec![],
+        out_file: None,
+        out_dir: None,
+        minify: false,
+        source_map: Some(BundleSourceMap::Inline),
+        watch: Default::default(),
+      })
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "bundle", "--sourcemap=other", "source.ts"]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -6947,7 +8734,11 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Bundle(BundleFlags {
           source_file: "source.ts".to_string(),
+          additional_source_files: vec![],
           out_file: Some("bundle.js".to_string()),
+          out_dir: None,
+          minify: false,
+          source_map: None,
           watch: Default::default(),
         }),
         permissions: PermissionFlags {
@@ -6970,7 +8761,11 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Bundle(BundleFlags {
           source_file: "source.ts".to_string(),
+          additional_source_files: vec![],
           out_file: Some("bundle.js".to_string()),
+          out_dir: None,
+          minify: false,
+          source_map: None,
           watch: Default::default(),
         }),
         type_check_mode: TypeCheckMode::Local,
@@ -6997,7 +8792,11 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Bundle(BundleFlags {
           source_file: "source.ts".to_string(),
+          additional_source_files: vec![],
           out_file: None,
+          out_dir: None,
+          minify: false,
+          source_map: None,
           watch: Default::default(),
         }),
         type_check_mode: TypeCheckMode::Local,
@@ -7017,7 +8816,11 @@ mod tests {
         reload: true,
         subcommand: DenoSubcommand::Bundle(BundleFlags {
           source_file: "source.ts".to_string(),
+          additional_source_files: vec![],
           out_file: None,
+          out_dir: None,
+          minify: false,
+          source_map: None,
           watch: Default::default(),
         }),
         type_check_mode: TypeCheckMode::Local,
@@ -7035,7 +8838,11 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Bundle(BundleFlags {
           source_file: "script.ts".to_string(),
+          additional_source_files: vec![],
           out_file: None,
+          out_dir: None,
+          minify: false,
+          source_map: None,
           watch: Default::default(),
         }),
         type_check_mode: TypeCheckMode::None,
@@ -7052,7 +8859,11 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Bundle(BundleFlags {
           source_file: "source.ts".to_string(),
+          additional_source_files: vec![],
           out_file: None,
+          out_dir: None,
+          minify: false,
+          source_map: None,
           watch: Some(Default::default()),
         }),
         type_check_mode: TypeCheckMode::Local,
@@ -7075,7 +8886,11 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Bundle(BundleFlags {
           source_file: "source.ts".to_string(),
+          additional_source_files: vec![],
           out_file: None,
+          out_dir: None,
+          minify: false,
+          source_map: None,
           watch: Some(WatchFlags {
             hmr: false,
             no_clear_screen: true,
@@ -7109,6 +8924,34 @@ mod tests {
     );
   }
 
+  #[test]
+  fn run_multiple_import_maps() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--import-map=base.json",
+      "--import-map=member/import_map.json",
+      "--import-map",
+      "override.json",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        import_map_path: Some("base.json".to_owned()),
+        additional_import_map_paths: svec![
+          "member/import_map.json",
+          "override.json"
+        ],
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn info_import_map() {
     let r = flags_from_vec(svec![
@@ -7143,6 +8986,10 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Cache(CacheFlags {
           files: svec!["script.ts"],
+          prune: false,
+          dry_run: false,
+          export: None,
+          import: None,
         }),
         import_map_path: Some("import_map.json".to_owned()),
         ..Flags::default()
@@ -7165,6 +9012,7 @@ mod tests {
           source_files: DocSourceFileFlag::Paths(vec!["script.ts".to_owned()]),
           private: false,
           json: false,
+          json_version: None,
           html: None,
           lint: false,
           filter: None,
@@ -7232,6 +9080,10 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Cache(CacheFlags {
           files: svec!["script.ts", "script_two.ts"],
+          prune: false,
+          dry_run: false,
+          export: None,
+          import: None,
         }),
         ..Flags::default()
       }
@@ -7280,8 +9132,37 @@ mod tests {
   }
 
   #[test]
-  fn install() {
-    let r =
+  fn run_local_storage_quota() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--local-storage-quota=50",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        local_storage_quota: Some(50),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--local-storage-quota=0",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn install() {
+    let r =
       flags_from_vec(svec!["deno", "install", "jsr:@std/http/file-server"]);
     assert_eq!(
       r.unwrap(),
@@ -7785,6 +9666,36 @@ mod tests {
     );
   }
 
+  #[test]
+  fn revalidate_in_background() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--revalidate-in-background",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        revalidate_in_background: true,
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--revalidate-in-background",
+      "--cached-only",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn allow_net_allowlist_with_ports() {
     let r = flags_from_vec(svec![
@@ -7919,6 +9830,38 @@ mod tests {
     );
   }
 
+  #[test]
+  fn frozen_lockfile() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--frozen-lockfile",
+      "--lock=deno.lock",
+      "script.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Run(RunFlags::new_default(
+          "script.ts".to_string(),
+        )),
+        frozen_lockfile: true,
+        lock: Some(String::from("deno.lock")),
+        code_cache_enabled: true,
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "run",
+      "--frozen-lockfile",
+      "--lock-write",
+      "script.ts"
+    ]);
+    assert!(r.is_err());
+  }
+
   #[test]
   fn lock_write() {
     let r = flags_from_vec(svec![
@@ -8063,6 +10006,11 @@ mod tests {
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
+          shard: None,
+          retries: 0,
+          timeout: None,
+          update_snapshots: false,
+          changed: None,
         }),
         unstable_config: UnstableConfig {
           legacy_flag_enabled: true,
@@ -8149,6 +10097,11 @@ mod tests {
           coverage_dir: None,
           watch: Default::default(),
           junit_path: None,
+          shard: None,
+          retries: 0,
+          timeout: None,
+          update_snapshots: false,
+          changed: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -8161,6 +10114,24 @@ mod tests {
 
     let r = flags_from_vec(svec!["deno", "test", "--jobs=0"]);
     assert!(r.is_err());
+
+    let r = flags_from_vec(svec!["deno", "test", "--parallel", "--jobs=2"]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Test(TestFlags {
+        concurrent_jobs: Some(NonZeroUsize::new(2).unwrap()),
+        ..Default::default()
+      })
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--jobs", "2"]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Test(TestFlags {
+        concurrent_jobs: Some(NonZeroUsize::new(2).unwrap()),
+        ..Default::default()
+      })
+    );
   }
 
   #[test]
@@ -8186,6 +10157,11 @@ mod tests {
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
+          shard: None,
+          retries: 0,
+          timeout: None,
+          update_snapshots: false,
+          changed: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -8227,6 +10203,11 @@ mod tests {
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
+          shard: None,
+          retries: 0,
+          timeout: None,
+          update_snapshots: false,
+          changed: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -8311,6 +10292,24 @@ mod tests {
       }
     );
 
+    let r = flags_from_vec(svec!["deno", "test", "--reporter=json"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          reporter: TestReporterConfig::Json,
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        log_level: Some(Level::Error),
+        ..Flags::default()
+      }
+    );
+
     let r = flags_from_vec(svec![
       "deno",
       "test",
@@ -8339,6 +10338,115 @@ mod tests {
     assert!(r.is_err());
   }
 
+  #[test]
+  fn test_shard() {
+    let r = flags_from_vec(svec!["deno", "test", "--shard=3/8"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          shard: Some(TestShard {
+            index: NonZeroUsize::new(3).unwrap(),
+            count: NonZeroUsize::new(8).unwrap(),
+          }),
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+
+    for invalid in ["3", "0/8", "9/8", "a/8", "3/0"] {
+      let r =
+        flags_from_vec(svec!["deno", "test", format!("--shard={invalid}")]);
+      assert!(r.is_err(), "{invalid}");
+    }
+  }
+
+  #[test]
+  fn test_timeout() {
+    let r = flags_from_vec(svec!["deno", "test", "--timeout=500"]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Test(TestFlags {
+        timeout: Some(500),
+        ..Default::default()
+      })
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--timeout=0"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn test_changed() {
+    let r = flags_from_vec(svec!["deno", "test", "--changed"]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Test(TestFlags {
+        changed: Some("HEAD".to_string()),
+        ..Default::default()
+      })
+    );
+
+    let r = flags_from_vec(svec!["deno", "test", "--changed=main", "foo.ts"]);
+    assert_eq!(
+      r.unwrap().subcommand,
+      DenoSubcommand::Test(TestFlags {
+        changed: Some("main".to_string()),
+        files: FileFlags {
+          include: vec!["foo.ts".to_string()],
+          ignore: vec![],
+        },
+        ..Default::default()
+      })
+    );
+  }
+
+  #[test]
+  fn test_update_snapshots() {
+    let r = flags_from_vec(svec!["deno", "test", "--update-snapshots"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          update_snapshots: true,
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn test_retries() {
+    let r = flags_from_vec(svec!["deno", "test", "--retries=2"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Test(TestFlags {
+          retries: 2,
+          ..Default::default()
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn test_shuffle() {
     let r = flags_from_vec(svec!["deno", "test", "--shuffle=1"]);
@@ -8362,6 +10470,11 @@ mod tests {
           watch: Default::default(),
           reporter: Default::default(),
           junit_path: None,
+          shard: None,
+          retries: 0,
+          timeout: None,
+          update_snapshots: false,
+          changed: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -8396,6 +10509,11 @@ mod tests {
           watch: Some(Default::default()),
           reporter: Default::default(),
           junit_path: None,
+          shard: None,
+          retries: 0,
+          timeout: None,
+          update_snapshots: false,
+          changed: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -8429,6 +10547,11 @@ mod tests {
           watch: Some(Default::default()),
           reporter: Default::default(),
           junit_path: None,
+          shard: None,
+          retries: 0,
+          timeout: None,
+          update_snapshots: false,
+          changed: None,
         }),
         permissions: PermissionFlags {
           no_prompt: true,
@@ -8468,6 +10591,11 @@ mod tests {
           }),
           reporter: Default::default(),
           junit_path: None,
+          shard: None,
+          retries: 0,
+          timeout: None,
+          update_snapshots: false,
+          changed: None,
         }),
         type_check_mode: TypeCheckMode::Local,
         permissions: PermissionFlags {
@@ -8513,7 +10641,11 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Bundle(BundleFlags {
           source_file: "source.ts".to_string(),
+          additional_source_files: vec![],
           out_file: None,
+          out_dir: None,
+          minify: false,
+          source_map: None,
           watch: Default::default(),
         }),
         type_check_mode: TypeCheckMode::Local,
@@ -8557,6 +10689,10 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Cache(CacheFlags {
           files: svec!["script.ts", "script_two.ts"],
+          prune: false,
+          dry_run: false,
+          export: None,
+          import: None,
         }),
         ca_data: Some(CaData::File("example.crt".to_owned())),
         ..Flags::default()
@@ -8578,6 +10714,7 @@ mod tests {
       Flags {
         subcommand: DenoSubcommand::Info(InfoFlags {
           json: false,
+          graph: None,
           file: Some("https://example.com".to_string()),
         }),
         ca_data: Some(CaData::File("example.crt".to_owned())),
@@ -8595,6 +10732,7 @@ mod tests {
         subcommand: DenoSubcommand::Doc(DocFlags {
           private: false,
           json: true,
+          json_version: None,
           html: None,
           lint: false,
           source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
@@ -8620,6 +10758,7 @@ mod tests {
         subcommand: DenoSubcommand::Doc(DocFlags {
           private: false,
           json: false,
+          json_version: None,
           lint: false,
           html: Some(DocHtmlFlag {
             name: Some("My library".to_string()),
@@ -8647,6 +10786,7 @@ mod tests {
         subcommand: DenoSubcommand::Doc(DocFlags {
           private: false,
           json: false,
+          json_version: None,
           html: Some(DocHtmlFlag {
             name: Some("My library".to_string()),
             output: String::from("./foo"),
@@ -8676,6 +10816,7 @@ mod tests {
         subcommand: DenoSubcommand::Doc(DocFlags {
           private: false,
           json: false,
+          json_version: None,
           html: None,
           lint: false,
           source_files: DocSourceFileFlag::Paths(vec![
@@ -8694,6 +10835,7 @@ mod tests {
         subcommand: DenoSubcommand::Doc(DocFlags {
           private: false,
           json: false,
+          json_version: None,
           html: None,
           lint: false,
           source_files: Default::default(),
@@ -8703,6 +10845,69 @@ mod tests {
       }
     );
 
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--json",
+      "--filter=SomeClass",
+      "path/to/module.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          private: false,
+          lint: false,
+          json: true,
+          json_version: None,
+          html: None,
+          source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
+          filter: Some("SomeClass".to_string()),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--json",
+      "--json-version=1",
+      "path/to/module.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Doc(DocFlags {
+          private: false,
+          lint: false,
+          json: true,
+          json_version: Some(1),
+          html: None,
+          source_files: DocSourceFileFlag::Paths(svec!["path/to/module.ts"]),
+          filter: None,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--json",
+      "--json-version=2",
+      "path/to/module.ts"
+    ]);
+    assert!(r.is_err());
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "doc",
+      "--json-version=1",
+      "path/to/module.ts"
+    ]);
+    assert!(r.is_err());
+
     let r = flags_from_vec(svec![
       "deno",
       "doc",
@@ -8717,6 +10922,7 @@ mod tests {
           private: false,
           lint: false,
           json: false,
+          json_version: None,
           html: None,
           source_files: DocSourceFileFlag::Builtin,
           filter: Some("Deno.Listener".to_string()),
@@ -8740,6 +10946,7 @@ mod tests {
           private: true,
           lint: false,
           json: false,
+          json_version: None,
           html: None,
           source_files: DocSourceFileFlag::Paths(svec!["path/to/module.js"]),
           filter: None,
@@ -8763,6 +10970,7 @@ mod tests {
           private: false,
           lint: false,
           json: false,
+          json_version: None,
           html: None,
           source_files: DocSourceFileFlag::Paths(vec![
             "path/to/module.js".to_string(),
@@ -8787,6 +10995,7 @@ mod tests {
         subcommand: DenoSubcommand::Doc(DocFlags {
           private: false,
           json: false,
+          json_version: None,
           html: None,
           lint: false,
           source_files: DocSourceFileFlag::Paths(vec![
@@ -8816,6 +11025,7 @@ mod tests {
           private: false,
           lint: true,
           json: false,
+          json_version: None,
           html: None,
           source_files: DocSourceFileFlag::Paths(vec![
             "path/to/module.js".to_string(),
@@ -8895,7 +11105,12 @@ mod tests {
           args: vec![],
           target: None,
           no_terminal: false,
-          include: vec![]
+          include: vec![],
+          icon: None,
+          product_name: None,
+          version: None,
+          sign_cmd: None,
+          no_web_apis: false,
         }),
         type_check_mode: TypeCheckMode::Local,
         ..Flags::default()
@@ -8917,7 +11132,12 @@ mod tests {
           args: svec!["foo", "bar", "-p", "8080"],
           target: None,
           no_terminal: true,
-          include: vec![]
+          include: vec![],
+          icon: None,
+          product_name: None,
+          version: None,
+          sign_cmd: None,
+          no_web_apis: false,
         }),
         import_map_path: Some("import_map.json".to_string()),
         no_remote: true,
@@ -8943,6 +11163,100 @@ mod tests {
     );
   }
 
+  #[test]
+  fn compile_with_windows_resources() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "compile",
+      "--target",
+      "x86_64-pc-windows-msvc",
+      "--icon",
+      "app.ico",
+      "--product-name",
+      "My App",
+      "--version",
+      "1.2.3",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "main.ts".to_string(),
+          output: None,
+          args: vec![],
+          target: Some("x86_64-pc-windows-msvc".to_string()),
+          no_terminal: false,
+          include: vec![],
+          icon: Some("app.ico".to_string()),
+          product_name: Some("My App".to_string()),
+          version: Some("1.2.3".to_string()),
+          sign_cmd: None,
+          no_web_apis: false,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn compile_with_sign_cmd() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "compile",
+      "--sign-cmd",
+      "codesign --sign -",
+      "main.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "main.ts".to_string(),
+          output: None,
+          args: vec![],
+          target: None,
+          no_terminal: false,
+          include: vec![],
+          icon: None,
+          product_name: None,
+          version: None,
+          sign_cmd: Some("codesign --sign -".to_string()),
+          no_web_apis: false,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn compile_no_web_apis() {
+    let r =
+      flags_from_vec(svec!["deno", "compile", "--no-web-apis", "main.ts"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Compile(CompileFlags {
+          source_file: "main.ts".to_string(),
+          output: None,
+          args: vec![],
+          target: None,
+          no_terminal: false,
+          include: vec![],
+          icon: None,
+          product_name: None,
+          version: None,
+          sign_cmd: None,
+          no_web_apis: true,
+        }),
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn coverage() {
     let r = flags_from_vec(svec!["deno", "coverage", "foo.json"]);
@@ -8984,10 +11298,236 @@ mod tests {
           exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
           r#type: CoverageType::Lcov,
           output: Some(String::from("foo.lcov")),
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn coverage_with_thresholds() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--fail-under-lines=80",
+      "--fail-under-branches=70",
+      "foo.json"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["foo.json".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          fail_under_lines: Some(80),
+          fail_under_branches: Some(70),
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "--fail-under-lines=101",
+      "foo.json"
+    ]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn coverage_with_html() {
+    let r = flags_from_vec(svec!["deno", "coverage", "--html", "foo.json"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["foo.json".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          r#type: CoverageType::Html,
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "coverage", "--html=html_cov", "foo.json"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Coverage(CoverageFlags {
+          files: FileFlags {
+            include: vec!["foo.json".to_string()],
+            ignore: vec![],
+          },
+          include: vec![r"^file:".to_string()],
+          exclude: vec![r"test\.(js|mjs|ts|jsx|tsx)$".to_string()],
+          r#type: CoverageType::Html,
+          output: Some("html_cov".to_string()),
+          ..CoverageFlags::default()
+        }),
+        ..Flags::default()
+      }
+    );
+  }
+
+  #[test]
+  fn coverage_merge() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "coverage",
+      "merge",
+      "cov_a",
+      "cov_b",
+      "-o",
+      "cov_merged"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::CoverageMerge(CoverageMergeFlags {
+          files: FileFlags {
+            include: vec!["cov_a".to_string(), "cov_b".to_string()],
+            ignore: vec![],
+          },
+          output: "cov_merged".to_string(),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "coverage", "merge", "cov_a"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn permissions_list_and_revoke() {
+    let r = flags_from_vec(svec!["deno", "permissions", "list"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Permissions(PermissionsFlags {
+          script: None,
+          subcommand: PermissionGrantsFlags::List,
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "permissions", "revoke", "read", "/etc"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Permissions(PermissionsFlags {
+          script: None,
+          subcommand: PermissionGrantsFlags::Revoke {
+            name: Some("read".to_string()),
+            value: Some("/etc".to_string()),
+          },
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "permissions", "revoke", "--all"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Permissions(PermissionsFlags {
+          script: None,
+          subcommand: PermissionGrantsFlags::Revoke {
+            name: None,
+            value: None,
+          },
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec![
+      "deno",
+      "permissions",
+      "--script",
+      "main.ts",
+      "list"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Permissions(PermissionsFlags {
+          script: Some("main.ts".to_string()),
+          subcommand: PermissionGrantsFlags::List,
         }),
         ..Flags::default()
       }
     );
+
+    let r = flags_from_vec(svec!["deno", "permissions", "revoke"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "permissions", "revoke", "foo"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "permissions"]);
+    assert!(r.is_err());
+  }
+
+  #[test]
+  fn storage_ls_and_clear() {
+    let r = flags_from_vec(svec!["deno", "storage", "ls"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Storage(StorageFlags::List),
+        ..Flags::default()
+      }
+    );
+
+    let r =
+      flags_from_vec(svec!["deno", "storage", "clear", "https://example.com"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Storage(StorageFlags::Clear {
+          origin: Some("https://example.com".to_string()),
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "storage", "clear", "--all"]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Storage(StorageFlags::Clear {
+          origin: None
+        }),
+        ..Flags::default()
+      }
+    );
+
+    let r = flags_from_vec(svec!["deno", "storage", "clear"]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec![
+      "deno",
+      "storage",
+      "clear",
+      "--all",
+      "https://example.com"
+    ]);
+    assert!(r.is_err());
+    let r = flags_from_vec(svec!["deno", "storage"]);
+    assert!(r.is_err());
   }
 
   #[test]
@@ -9048,6 +11588,14 @@ mod tests {
       flags.config_path_args(&cwd),
       Some(vec![cwd.join("dir/a.js"), cwd.join("dir/b.js")])
     );
+
+    let flags =
+      flags_from_vec(svec!["deno", "fmt", "--stdin-filepath=dir/a.md"])
+        .unwrap();
+    assert_eq!(
+      flags.config_path_args(&cwd),
+      Some(vec![cwd.join("dir/a.md")])
+    );
   }
 
   #[test]
@@ -9120,6 +11668,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          watch: None,
         }),
         argv: svec!["hello", "world"],
         ..Flags::default()
@@ -9133,11 +11682,37 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          watch: None,
         }),
         ..Flags::default()
       }
     );
 
+    let r = flags_from_vec(svec![
+      "deno",
+      "task",
+      "--watch",
+      "--no-clear-screen",
+      "build",
+      "--flag"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Task(TaskFlags {
+          cwd: None,
+          task: Some("build".to_string()),
+          watch: Some(WatchFlags {
+            hmr: false,
+            no_clear_screen: true,
+            exclude: vec![],
+          }),
+        }),
+        argv: svec!["--flag"],
+        ..Flags::default()
+      }
+    );
+
     let r = flags_from_vec(svec!["deno", "task", "--cwd", "foo", "build"]);
     assert_eq!(
       r.unwrap(),
@@ -9145,6 +11720,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: Some("foo".to_string()),
           task: Some("build".to_string()),
+          watch: None,
         }),
         ..Flags::default()
       }
@@ -9169,6 +11745,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          watch: None,
         }),
         argv: svec!["--", "hello", "world"],
         config_flag: ConfigFlag::Path("deno.json".to_owned()),
@@ -9185,6 +11762,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: Some("foo".to_string()),
           task: Some("build".to_string()),
+          watch: None,
         }),
         argv: svec!["--", "hello", "world"],
         ..Flags::default()
@@ -9202,6 +11780,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          watch: None,
         }),
         argv: svec!["--"],
         ..Flags::default()
@@ -9218,6 +11797,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          watch: None,
         }),
         argv: svec!["-1", "--test"],
         ..Flags::default()
@@ -9234,6 +11814,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          watch: None,
         }),
         argv: svec!["--test"],
         ..Flags::default()
@@ -9252,6 +11833,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: Some("build".to_string()),
+          watch: None,
         }),
         unstable_config: UnstableConfig {
           legacy_flag_enabled: true,
@@ -9272,6 +11854,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          watch: None,
         }),
         ..Flags::default()
       }
@@ -9287,6 +11870,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          watch: None,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
@@ -9303,6 +11887,7 @@ mod tests {
         subcommand: DenoSubcommand::Task(TaskFlags {
           cwd: None,
           task: None,
+          watch: None,
         }),
         config_flag: ConfigFlag::Path("deno.jsonc".to_string()),
         ..Flags::default()
@@ -9346,6 +11931,7 @@ mod tests {
         subcommand: DenoSubcommand::Bench(BenchFlags {
           filter: Some("- foo".to_string()),
           json: true,
+          compare: None,
           no_run: true,
           files: FileFlags {
             include: vec!["dir1/".to_string(), "dir2/".to_string()],
@@ -9381,6 +11967,7 @@ mod tests {
         subcommand: DenoSubcommand::Bench(BenchFlags {
           filter: None,
           json: false,
+          compare: None,
           no_run: false,
           files: FileFlags {
             include: vec![],
@@ -9398,6 +11985,38 @@ mod tests {
     );
   }
 
+  #[test]
+  fn bench_compare() {
+    let r = flags_from_vec(svec![
+      "deno",
+      "bench",
+      "--compare=baseline.json",
+      "bench.ts"
+    ]);
+    assert_eq!(
+      r.unwrap(),
+      Flags {
+        subcommand: DenoSubcommand::Bench(BenchFlags {
+          filter: None,
+          json: false,
+          compare: Some("baseline.json".to_string()),
+          no_run: false,
+          files: FileFlags {
+            include: vec!["bench.ts".to_string()],
+            ignore: vec![],
+          },
+          watch: None,
+        }),
+        permissions: PermissionFlags {
+          no_prompt: true,
+          ..Default::default()
+        },
+        type_check_mode: TypeCheckMode::Local,
+        ..Flags::default()
+      }
+    );
+  }
+
   #[test]
   fn run_with_check() {
     let r = flags_from_vec(svec!["deno", "run", "--check", "script.ts",]);
@@ -1,7 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
-use deno_core::url::Url;
-use std::net::IpAddr;
+use deno_runtime::deno_permissions::NetDescriptor;
 use std::str::FromStr;
 
 #[derive(Debug, PartialEq, Eq)]
@@ -26,10 +25,32 @@ impl FromStr for BarePort {
   }
 }
 
+/// Returns the port or port range of a "bare port" like ":8080" or
+/// ":8000-8080".
+fn parse_bare_ports(host_and_port: &str) -> Option<String> {
+  if let Ok(port) = host_and_port.parse::<BarePort>() {
+    return Some(port.0.to_string());
+  }
+  let ports = host_and_port.strip_prefix(':')?;
+  if ports.contains('-')
+    && NetDescriptor::from_str(&format!("localhost:{ports}")).is_ok()
+  {
+    Some(ports.to_string())
+  } else {
+    None
+  }
+}
+
+/// Whether the value is a host name, an IP address, a wildcard subdomain like
+/// `*.example.com` or a CIDR block like `10.0.0.0/8`, with an optional port or
+/// port range.
+fn is_net_descriptor(host_and_port: &str) -> bool {
+  NetDescriptor::from_str(host_and_port).is_ok()
+}
+
 pub fn validator(host_and_port: &str) -> Result<String, String> {
-  if Url::parse(&format!("internal://{host_and_port}")).is_ok()
-    || host_and_port.parse::<IpAddr>().is_ok()
-    || host_and_port.parse::<BarePort>().is_ok()
+  if is_net_descriptor(host_and_port)
+    || parse_bare_ports(host_and_port).is_some()
   {
     Ok(host_and_port.to_string())
   } else {
@@ -43,15 +64,13 @@ pub fn validator(host_and_port: &str) -> Result<String, String> {
 pub fn parse(paths: Vec<String>) -> clap::error::Result<Vec<String>> {
   let mut out: Vec<String> = vec![];
   for host_and_port in paths.iter() {
-    if Url::parse(&format!("internal://{host_and_port}")).is_ok()
-      || host_and_port.parse::<IpAddr>().is_ok()
-    {
-      out.push(host_and_port.to_owned())
-    } else if let Ok(port) = host_and_port.parse::<BarePort>() {
+    if let Some(ports) = parse_bare_ports(host_and_port) {
       // we got bare port, let's add default hosts
       for host in ["0.0.0.0", "127.0.0.1", "localhost"].iter() {
-        out.push(format!("{}:{}", host, port.0));
+        out.push(format!("{}:{}", host, ports));
       }
+    } else if is_net_descriptor(host_and_port) {
+      out.push(host_and_port.to_owned())
     } else {
       return Err(clap::Error::raw(
         clap::error::ErrorKind::InvalidValue,
@@ -182,6 +201,48 @@ mod tests {
     assert_eq!(actual, expected);
   }
 
+  #[test]
+  fn parse_net_args_patterns() {
+    let entries = svec![
+      "*.deno.land",
+      "*.deno.land:443",
+      "10.0.0.0/8",
+      "10.0.0.0/8:8000-8080",
+      "[fe80::]/10",
+      "fe80::/10",
+      "localhost:8000-8080",
+      ":8000-8080"
+    ];
+    let expected = svec![
+      "*.deno.land",
+      "*.deno.land:443",
+      "10.0.0.0/8",
+      "10.0.0.0/8:8000-8080",
+      "[fe80::]/10",
+      "fe80::/10",
+      "localhost:8000-8080",
+      "0.0.0.0:8000-8080",
+      "127.0.0.1:8000-8080",
+      "localhost:8000-8080"
+    ];
+    let actual = parse(entries).unwrap();
+    assert_eq!(actual, expected);
+  }
+
+  #[test]
+  fn parse_net_args_pattern_errors() {
+    for entry in [
+      "10.0.0.0/33",
+      "10.0.0.0/x",
+      "deno.land:8080-80",
+      "deno.land:80-",
+      ":8080-80",
+      "deno.land/path",
+    ] {
+      assert!(parse(svec![entry]).is_err(), "{entry}");
+    }
+  }
+
   #[test]
   fn parse_net_args_ipv6_error1() {
     let entries = svec![":::"];
@@ -1,5 +1,8 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::serde_json;
@@ -9,20 +12,53 @@ use import_map::ImportMap;
 use import_map::ImportMapDiagnostic;
 use log::warn;
 
+use super::deno_json;
+use super::deno_json::ConfigFileExtras;
 use super::ConfigFile;
 use crate::file_fetcher::FileFetcher;
 
+/// Resolves the import map to use.
+///
+/// When import maps are specified (ex. `--import-map` given multiple times),
+/// they are merged in order and the entries of later maps take precedence
+/// over the entries of earlier ones. Otherwise, the import maps listed in the
+/// `"extends"` property of the configuration file are merged in order beneath
+/// the import map of the configuration file itself. Since the configuration
+/// file overrides the import maps it extends on purpose, only the conflicts
+/// between the extended import maps are reported.
 pub async fn resolve_import_map(
-  specified_specifier: Option<&Url>,
+  specified_specifiers: &[Url],
   maybe_config_file: Option<&ConfigFile>,
+  maybe_config_file_extras: Option<&ConfigFileExtras>,
   file_fetcher: &FileFetcher,
 ) -> Result<Option<ImportMap>, AnyError> {
-  if let Some(specifier) = specified_specifier {
-    resolve_import_map_from_specifier(specifier.clone(), file_fetcher)
-      .await
-      .with_context(|| format!("Unable to load '{}' import map", specifier))
-      .map(Some)
+  let mut layers = Vec::new();
+  let mut config_import_map_url = None;
+  if !specified_specifiers.is_empty() {
+    for specifier in specified_specifiers {
+      let value = fetch_import_map_value(specifier, file_fetcher)
+        .await
+        .with_context(|| {
+          format!("Unable to load '{}' import map", specifier)
+        })?;
+      layers.push((specifier.clone(), value));
+    }
   } else if let Some(config_file) = maybe_config_file {
+    let extends = match maybe_config_file_extras {
+      Some(extras) => deno_json::resolve_import_map_extends(extras)?,
+      None => Vec::new(),
+    };
+    for specifier in extends {
+      let value = fetch_import_map_value(&specifier, file_fetcher)
+        .await
+        .with_context(|| {
+          format!(
+            "Unable to load '{}' import map extended by '{}'",
+            specifier, config_file.specifier
+          )
+        })?;
+      layers.push((specifier, value));
+    }
     let maybe_url_and_value = config_file
       .to_import_map_value(|specifier| {
         let specifier = specifier.clone();
@@ -41,33 +77,215 @@ pub async fn resolve_import_map(
           config_file.specifier
         )
       })?;
-    match maybe_url_and_value {
-      Some((url, value)) => {
-        import_map_from_value(url.into_owned(), value).map(Some)
-      }
-      None => Ok(None),
+    if let Some((url, value)) = maybe_url_and_value {
+      let url = url.into_owned();
+      config_import_map_url = Some(url.clone());
+      layers.push((url, value));
     }
-  } else {
-    Ok(None)
   }
+
+  if layers.len() <= 1 {
+    return layers
+      .pop()
+      .map(|(url, value)| import_map_from_value(url, value))
+      .transpose();
+  }
+
+  for (url, value) in &layers {
+    // surface errors and diagnostics of each import map on its own, before
+    // the entries are merged
+    import_map_from_value(url.clone(), value.clone())?;
+  }
+  let (url, value, conflicts) =
+    merge_import_maps(layers, config_import_map_url.as_ref());
+  print_import_map_conflicts(&conflicts);
+  let result = import_map::parse_from_value(url, value)?;
+  Ok(Some(result.import_map))
 }
 
-async fn resolve_import_map_from_specifier(
-  specifier: Url,
+async fn fetch_import_map_value(
+  specifier: &Url,
   file_fetcher: &FileFetcher,
-) -> Result<ImportMap, AnyError> {
-  let value: serde_json::Value = if specifier.scheme() == "data" {
+) -> Result<serde_json::Value, AnyError> {
+  if specifier.scheme() == "data" {
     let data_url_text =
-      deno_graph::source::RawDataUrl::parse(&specifier)?.decode()?;
-    serde_json::from_str(&data_url_text)?
+      deno_graph::source::RawDataUrl::parse(specifier)?.decode()?;
+    Ok(serde_json::from_str(&data_url_text)?)
   } else {
     let file = file_fetcher
-      .fetch(&specifier, &PermissionsContainer::allow_all())
+      .fetch(specifier, &PermissionsContainer::allow_all())
       .await?
       .into_text_decoded()?;
-    serde_json::from_str(&file.source)?
-  };
-  import_map_from_value(specifier, value)
+    Ok(serde_json::from_str(&file.source)?)
+  }
+}
+
+/// An entry of an import map that was replaced by an entry of an import map
+/// with a higher precedence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportMapConflict {
+  scope: Option<String>,
+  key: String,
+  overridden: (Url, serde_json::Value),
+  winner: (Url, serde_json::Value),
+}
+
+impl std::fmt::Display for ImportMapConflict {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(f, "\"{}\"", self.key)?;
+    if let Some(scope) = &self.scope {
+      write!(f, " in scope \"{}\"", scope)?;
+    }
+    write!(
+      f,
+      " is mapped to {} in {}, which overrides {} in {}",
+      self.winner.1, self.winner.0, self.overridden.1, self.overridden.0
+    )
+  }
+}
+
+/// Merges import maps, ordered from the lowest to the highest precedence. The
+/// entries of the import map of the configuration file, which overrides the
+/// import maps it extends on purpose, aren't reported as conflicts.
+pub fn merge_import_maps(
+  layers: Vec<(Url, serde_json::Value)>,
+  config_import_map_url: Option<&Url>,
+) -> (Url, serde_json::Value, Vec<ImportMapConflict>) {
+  let (url, value, mut conflicts) = merge_import_map_values(layers);
+  if let Some(config_import_map_url) = config_import_map_url {
+    conflicts.retain(|conflict| conflict.winner.0 != *config_import_map_url);
+  }
+  (url, value, conflicts)
+}
+
+/// Merges the values of several import maps, ordered from the lowest to the
+/// highest precedence, into a single import map value.
+///
+/// Relative keys and addresses are resolved against the URL of the import map
+/// they come from, so the merged value doesn't depend on its base URL, which
+/// is the URL of the import map with the highest precedence.
+fn merge_import_map_values(
+  layers: Vec<(Url, serde_json::Value)>,
+) -> (Url, serde_json::Value, Vec<ImportMapConflict>) {
+  type Entries = serde_json::Map<String, serde_json::Value>;
+
+  fn merge_entries(
+    base_url: &Url,
+    scope: Option<&str>,
+    entries: Entries,
+    merged: &mut Entries,
+    sources: &mut HashMap<(Option<String>, String), Url>,
+    conflicts: &mut Vec<ImportMapConflict>,
+  ) {
+    for (key, address) in entries {
+      let key = resolve_relative_url(base_url, &key);
+      let address = match address {
+        serde_json::Value::String(address) => {
+          serde_json::Value::String(resolve_relative_url(base_url, &address))
+        }
+        address => address,
+      };
+      let is_same_address = |previous: &serde_json::Value| {
+        // compare URLs in their normalized form, so that the same address
+        // that is written differently isn't reported as a conflict
+        match (previous.as_str(), address.as_str()) {
+          (Some(previous), Some(address)) => {
+            match (Url::parse(previous), Url::parse(address)) {
+              (Ok(previous), Ok(address)) => previous == address,
+              _ => previous == address,
+            }
+          }
+          _ => *previous == address,
+        }
+      };
+      let source_key = (scope.map(ToOwned::to_owned), key.clone());
+      if let Some(previous) = merged.get(&key) {
+        if !is_same_address(previous) {
+          conflicts.push(ImportMapConflict {
+            scope: scope.map(ToOwned::to_owned),
+            key: key.clone(),
+            overridden: (sources[&source_key].clone(), previous.clone()),
+            winner: (base_url.clone(), address.clone()),
+          });
+        }
+      }
+      sources.insert(source_key, base_url.clone());
+      merged.insert(key, address);
+    }
+  }
+
+  let mut imports = Entries::new();
+  let mut scopes = BTreeMap::<String, Entries>::new();
+  let mut sources = HashMap::new();
+  let mut conflicts = Vec::new();
+  let mut url = None;
+  for (base_url, mut value) in layers {
+    if let Some(serde_json::Value::Object(entries)) =
+      value.get_mut("imports").map(|v| v.take())
+    {
+      merge_entries(
+        &base_url,
+        None,
+        entries,
+        &mut imports,
+        &mut sources,
+        &mut conflicts,
+      );
+    }
+    if let Some(serde_json::Value::Object(value_scopes)) =
+      value.get_mut("scopes").map(|v| v.take())
+    {
+      for (scope, entries) in value_scopes {
+        let serde_json::Value::Object(entries) = entries else {
+          continue;
+        };
+        let scope = base_url.join(&scope).map(String::from).unwrap_or(scope);
+        let merged = scopes.entry(scope.clone()).or_default();
+        merge_entries(
+          &base_url,
+          Some(&scope),
+          entries,
+          merged,
+          &mut sources,
+          &mut conflicts,
+        );
+      }
+    }
+    url = Some(base_url);
+  }
+
+  let value = serde_json::json!({
+    "imports": imports,
+    "scopes": scopes,
+  });
+  (url.unwrap(), value, conflicts)
+}
+
+/// Resolves keys and addresses that are relative URLs (ex. `./mod.ts`)
+/// against the URL of their import map. Bare specifiers are left as-is.
+fn resolve_relative_url(base_url: &Url, specifier: &str) -> String {
+  if specifier.starts_with("./")
+    || specifier.starts_with("../")
+    || specifier.starts_with('/')
+  {
+    if let Ok(url) = base_url.join(specifier) {
+      return url.into();
+    }
+  }
+  specifier.to_string()
+}
+
+fn print_import_map_conflicts(conflicts: &[ImportMapConflict]) {
+  if !conflicts.is_empty() {
+    warn!(
+      "Import map conflicts:\n{}",
+      conflicts
+        .iter()
+        .map(|c| format!("  - {c}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+    );
+  }
 }
 
 pub fn import_map_from_value(
@@ -125,3 +343,106 @@ pub fn enhance_import_map_value_with_workspace_members(
   import_map_value["imports"] = serde_json::Value::Object(imports);
   ::import_map::ext::expand_import_map_value(import_map_value)
 }
+
+#[cfg(test)]
+mod test {
+  use deno_core::serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn merge_import_map_values_later_maps_take_precedence() {
+    let base_url = Url::parse("file:///project/import_map.json").unwrap();
+    let member_url =
+      Url::parse("file:///project/member/import_map.json").unwrap();
+    let (url, value, conflicts) = merge_import_map_values(vec![
+      (
+        base_url.clone(),
+        json!({
+          "imports": {
+            "@std/assert": "jsr:@std/assert@0.220",
+            "utils/": "./utils/",
+          },
+          "scopes": {
+            "./vendor/": { "a": "./vendor/a.ts" },
+          },
+        }),
+      ),
+      (
+        member_url.clone(),
+        json!({
+          "imports": {
+            "@std/assert": "jsr:@std/assert@0.221",
+            "./local.ts": "./local_override.ts",
+          },
+          "scopes": {
+            "../vendor/": { "b": "../vendor/b.ts" },
+          },
+        }),
+      ),
+    ]);
+    assert_eq!(url, member_url);
+    assert_eq!(
+      value,
+      json!({
+        "imports": {
+          "@std/assert": "jsr:@std/assert@0.221",
+          "utils/": "file:///project/utils/",
+          "file:///project/member/local.ts":
+            "file:///project/member/local_override.ts",
+        },
+        "scopes": {
+          "file:///project/vendor/": {
+            "a": "file:///project/vendor/a.ts",
+            "b": "file:///project/vendor/b.ts",
+          },
+        },
+      })
+    );
+    assert_eq!(
+      conflicts,
+      vec![ImportMapConflict {
+        scope: None,
+        key: "@std/assert".to_string(),
+        overridden: (base_url, json!("jsr:@std/assert@0.220")),
+        winner: (member_url, json!("jsr:@std/assert@0.221")),
+      }]
+    );
+    assert_eq!(
+      conflicts[0].to_string(),
+      concat!(
+        "\"@std/assert\" is mapped to \"jsr:@std/assert@0.221\" in ",
+        "file:///project/member/import_map.json, which overrides ",
+        "\"jsr:@std/assert@0.220\" in file:///project/import_map.json"
+      )
+    );
+  }
+
+  #[test]
+  fn merge_import_map_values_same_entry_is_not_a_conflict() {
+    let a = Url::parse("file:///a/import_map.json").unwrap();
+    let b = Url::parse("file:///a/other.json").unwrap();
+    let (_, _, conflicts) = merge_import_map_values(vec![
+      (a, json!({ "imports": { "foo": "./foo.ts" } })),
+      (b, json!({ "imports": { "foo": "./foo.ts" } })),
+    ]);
+    assert!(conflicts.is_empty());
+  }
+
+  #[test]
+  fn merge_import_map_values_normalized_address_is_not_a_conflict() {
+    let a = Url::parse("file:///a/import_map.json").unwrap();
+    let b = Url::parse("file:///a/other.json").unwrap();
+    let (_, _, conflicts) = merge_import_map_values(vec![
+      (
+        a,
+        json!({ "imports": { "foo": "https://example.com/foo.ts" } }),
+      ),
+      (
+        b,
+        json!({ "imports": { "foo": "https://EXAMPLE.com/a/../foo.ts" } }),
+      ),
+    ]);
+    assert!(conflicts.is_empty());
+  }
+}
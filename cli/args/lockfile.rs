@@ -1,9 +1,18 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 
+use deno_ast::MediaType;
+use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::serde_json::Value;
+use deno_core::ModuleSpecifier;
 
+use crate::args::jsr_url;
 use crate::args::ConfigFile;
 use crate::Flags;
 
@@ -16,6 +25,27 @@ pub fn discover(
   flags: &Flags,
   maybe_config_file: Option<&ConfigFile>,
 ) -> Result<Option<Lockfile>, AnyError> {
+  let maybe_filename = resolve_lockfile_path(flags, maybe_config_file)?;
+  if flags.frozen_lockfile
+    && !maybe_filename
+      .as_ref()
+      .is_some_and(|filename| filename.exists())
+  {
+    bail!(concat!(
+      "--frozen-lockfile requires an existing lockfile, but none was found.\n",
+      "Run without --frozen-lockfile to create one.",
+    ));
+  }
+  match maybe_filename {
+    Some(filename) => Ok(Some(Lockfile::new(filename, flags.lock_write)?)),
+    None => Ok(None),
+  }
+}
+
+fn resolve_lockfile_path(
+  flags: &Flags,
+  maybe_config_file: Option<&ConfigFile>,
+) -> Result<Option<PathBuf>, AnyError> {
   if flags.no_lock
     || matches!(
       flags.subcommand,
@@ -41,7 +71,158 @@ pub fn discover(
       None => return Ok(None),
     },
   };
+  Ok(Some(filename))
+}
+
+/// Errors when a remote module that isn't in the frozen lockfile is about to
+/// be fetched, so that `--frozen-lockfile` fails before downloading it. JSR
+/// packages are pinned by their integrity instead, which is checked when the
+/// lockfile is written.
+pub fn check_frozen_remote(
+  lockfile: &Lockfile,
+  specifier: &ModuleSpecifier,
+) -> Result<(), AnyError> {
+  if !matches!(specifier.scheme(), "http" | "https")
+    || specifier.as_str().starts_with(jsr_url().as_str())
+    // declaration files aren't stored in the lockfile
+    || MediaType::from_specifier(specifier).is_declaration()
+  {
+    return Ok(());
+  }
+  let content = &lockfile.content;
+  if content.remote.contains_key(specifier.as_str())
+    || content.redirects.contains_key(specifier.as_str())
+  {
+    return Ok(());
+  }
+  Err(frozen_lockfile_error(
+    lockfile,
+    &[format!("+ remote > {specifier}")],
+  ))
+}
+
+/// Writes the lockfile if its content changed. When the lockfile is frozen,
+/// this errors with the entries that changed instead.
+pub fn write_lockfile(
+  lockfile: &Lockfile,
+  frozen: bool,
+) -> Result<(), AnyError> {
+  if frozen && lockfile.has_content_changed {
+    let original = std::fs::read_to_string(&lockfile.filename)
+      .ok()
+      .and_then(|text| serde_json::from_str::<Value>(&text).ok())
+      .unwrap_or(Value::Null);
+    let current = serde_json::from_str::<Value>(&lockfile.as_json_string())?;
+    let changes = lockfile_diff(&original, &current);
+    if !changes.is_empty() {
+      return Err(frozen_lockfile_error(lockfile, &changes));
+    }
+    return Ok(());
+  }
+  lockfile.write().context("Failed writing lockfile.")
+}
 
-  let lockfile = Lockfile::new(filename, flags.lock_write)?;
-  Ok(Some(lockfile))
+fn frozen_lockfile_error(lockfile: &Lockfile, changes: &[String]) -> AnyError {
+  anyhow!(
+    concat!(
+      "The lockfile is out of date and --frozen-lockfile is specified.\n",
+      "Run without --frozen-lockfile to update {}.\n\n{}",
+    ),
+    lockfile.filename.display(),
+    changes
+      .iter()
+      .map(|change| format!("  {change}"))
+      .collect::<Vec<_>>()
+      .join("\n")
+  )
+}
+
+/// Lists the entries that differ between two lockfiles, where each entry is
+/// the path to a value that isn't an object.
+fn lockfile_diff(original: &Value, current: &Value) -> Vec<String> {
+  fn flatten(
+    value: &Value,
+    path: &mut Vec<String>,
+    entries: &mut BTreeMap<String, String>,
+  ) {
+    match value {
+      Value::Object(map) => {
+        for (key, value) in map {
+          path.push(key.clone());
+          flatten(value, path, entries);
+          path.pop();
+        }
+      }
+      Value::Null => {}
+      value => {
+        entries.insert(path.join(" > "), value.to_string());
+      }
+    }
+  }
+
+  let mut original_entries = BTreeMap::new();
+  flatten(original, &mut Vec::new(), &mut original_entries);
+  let mut current_entries = BTreeMap::new();
+  flatten(current, &mut Vec::new(), &mut current_entries);
+
+  let mut changes = Vec::new();
+  for (path, original_value) in &original_entries {
+    match current_entries.get(path) {
+      Some(current_value) if current_value == original_value => {}
+      Some(current_value) => {
+        changes.push(format!("~ {path}: {original_value} -> {current_value}"))
+      }
+      None => changes.push(format!("- {path}: {original_value}")),
+    }
+  }
+  for (path, current_value) in &current_entries {
+    if !original_entries.contains_key(path) {
+      changes.push(format!("+ {path}: {current_value}"));
+    }
+  }
+  changes
+}
+
+#[cfg(test)]
+mod test {
+  use deno_core::serde_json::json;
+
+  use super::*;
+
+  #[test]
+  fn test_lockfile_diff() {
+    let original = json!({
+      "version": "3",
+      "redirects": {
+        "https://deno.land/x/mod.ts": "https://deno.land/x/mod@1.0.0/mod.ts",
+      },
+      "remote": {
+        "https://deno.land/x/mod@1.0.0/mod.ts": "abc",
+        "https://deno.land/x/mod@1.0.0/other.ts": "def",
+      },
+    });
+    let current = json!({
+      "version": "3",
+      "packages": {
+        "specifiers": {
+          "npm:chalk@5": "npm:chalk@5.0.1",
+        },
+      },
+      "redirects": {
+        "https://deno.land/x/mod.ts": "https://deno.land/x/mod@1.0.0/mod.ts",
+      },
+      "remote": {
+        "https://deno.land/x/mod@1.0.0/mod.ts": "123",
+      },
+    });
+    assert_eq!(
+      lockfile_diff(&original, &current),
+      vec![
+        "~ remote > https://deno.land/x/mod@1.0.0/mod.ts: \"abc\" -> \"123\"",
+        "- remote > https://deno.land/x/mod@1.0.0/other.ts: \"def\"",
+        "+ packages > specifiers > npm:chalk@5: \"npm:chalk@5.0.1\"",
+      ]
+    );
+    assert!(lockfile_diff(&original, &original).is_empty());
+  }
 }
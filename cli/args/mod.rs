@@ -6,7 +6,11 @@ mod flags_net;
 mod import_map;
 mod lockfile;
 pub mod package_json;
+mod permission_grants;
+mod permission_policy;
 
+use self::deno_json::ConfigFileExtras;
+pub use self::import_map::merge_import_maps;
 pub use self::import_map::resolve_import_map;
 use self::package_json::PackageJsonDeps;
 use ::import_map::ImportMap;
@@ -31,11 +35,16 @@ pub use deno_config::TsConfigType;
 pub use deno_config::TsTypeLib;
 pub use deno_config::WorkspaceConfig;
 pub use flags::*;
+pub use lockfile::check_frozen_remote;
+pub use lockfile::write_lockfile;
 pub use lockfile::Lockfile;
 pub use lockfile::LockfileError;
 pub use package_json::PackageJsonDepsProvider;
+pub use permission_grants::PermissionGrantStore;
+pub use permission_policy::PermissionPolicy;
 
 use deno_ast::ModuleSpecifier;
+use deno_core::anyhow::anyhow;
 use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
@@ -48,6 +57,8 @@ use deno_runtime::deno_tls::rustls;
 use deno_runtime::deno_tls::rustls::RootCertStore;
 use deno_runtime::deno_tls::rustls_pemfile;
 use deno_runtime::deno_tls::webpki_roots;
+use deno_runtime::fmt_errors::format_js_error_options;
+use deno_runtime::fmt_errors::set_format_js_error_options;
 use deno_runtime::inspector_server::InspectorServer;
 use deno_runtime::permissions::PermissionsOptions;
 use deno_terminal::colors;
@@ -66,10 +77,14 @@ use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::sync::Once;
 use thiserror::Error;
 
 use crate::args::import_map::enhance_import_map_value_with_workspace_members;
+use crate::args::permission_grants::apply_permission_grants;
+use crate::cache::DenoDir;
 use crate::file_fetcher::FileFetcher;
+use crate::util::file_watcher::resolve_watch_paths;
 use crate::util::fs::canonicalize_path_maybe_not_exists;
 use crate::version;
 
@@ -247,6 +262,7 @@ pub struct BenchOptions {
   pub files: FilePatterns,
   pub filter: Option<String>,
   pub json: bool,
+  pub compare: Option<PathBuf>,
   pub no_run: bool,
 }
 
@@ -265,6 +281,7 @@ impl BenchOptions {
       )?,
       filter: bench_flags.filter,
       json: bench_flags.json,
+      compare: bench_flags.compare.map(|compare| initial_cwd.join(compare)),
       no_run: bench_flags.no_run,
     })
   }
@@ -274,7 +291,115 @@ impl BenchOptions {
 pub struct FmtOptions {
   pub check: bool,
   pub options: FmtOptionsConfig,
+  pub markdown: MarkdownFmtOptions,
+  /// Urls of the dprint Wasm plugins used to format other file types.
+  pub plugins: Vec<Url>,
   pub files: FilePatterns,
+  /// The options of the workspace members with their own `fmt` config,
+  /// keyed by member directory.
+  pub member_options: Vec<(PathBuf, FmtOptionsConfig)>,
+}
+
+/// Options for formatting markdown files that are read from the `fmt` config
+/// by the CLI instead of deno_config.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MarkdownFmtOptions {
+  /// Whether to format the contents of fenced code blocks in supported
+  /// languages, configured with `"markdownCodeBlocks": false`.
+  pub format_code_blocks: bool,
+}
+
+impl Default for MarkdownFmtOptions {
+  fn default() -> Self {
+    Self {
+      format_code_blocks: true,
+    }
+  }
+}
+
+/// The options of the `fmt` config that deno_config doesn't know about.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CliFmtConfig {
+  pub markdown: MarkdownFmtOptions,
+  pub plugins: Vec<Url>,
+}
+
+const MARKDOWN_CODE_BLOCKS_KEY: &str = "markdownCodeBlocks";
+const PLUGINS_KEY: &str = "plugins";
+
+/// Reads the `fmt` config of `config_file`, including the options that are
+/// handled by the CLI.
+pub fn resolve_fmt_config(
+  config_file: &ConfigFile,
+) -> Result<(Option<FmtConfig>, CliFmtConfig), AnyError> {
+  let mut cli_config = CliFmtConfig::default();
+  let has_cli_options = config_file
+    .json
+    .fmt
+    .as_ref()
+    .and_then(|fmt| fmt.as_object())
+    .is_some_and(|fmt| {
+      fmt.contains_key(MARKDOWN_CODE_BLOCKS_KEY)
+        || fmt.contains_key(PLUGINS_KEY)
+    });
+  if !has_cli_options {
+    return Ok((config_file.to_fmt_config()?, cli_config));
+  }
+
+  let mut config_file = config_file.clone();
+  if let Some(fmt) = config_file
+    .json
+    .fmt
+    .as_mut()
+    .and_then(|fmt| fmt.as_object_mut())
+  {
+    match fmt.remove(MARKDOWN_CODE_BLOCKS_KEY) {
+      Some(serde_json::Value::Bool(value)) => {
+        cli_config.markdown.format_code_blocks = value;
+      }
+      Some(_) => bail!(
+        "Invalid fmt config: \"{}\" must be a boolean",
+        MARKDOWN_CODE_BLOCKS_KEY
+      ),
+      None => {}
+    }
+    cli_config.plugins =
+      remove_plugins_option(fmt, &config_file.specifier, "fmt")?;
+  }
+  Ok((config_file.to_fmt_config()?, cli_config))
+}
+
+/// Removes the `plugins` option from the `section` config and resolves the
+/// plugin specifiers relative to the config file.
+fn remove_plugins_option(
+  config: &mut serde_json::Map<String, serde_json::Value>,
+  config_specifier: &Url,
+  section: &str,
+) -> Result<Vec<Url>, AnyError> {
+  let plugins = match config.remove(PLUGINS_KEY) {
+    Some(serde_json::Value::Array(plugins)) => plugins,
+    Some(_) => bail!(
+      "Invalid {} config: \"{}\" must be an array of strings",
+      section,
+      PLUGINS_KEY
+    ),
+    None => return Ok(Vec::new()),
+  };
+  let mut urls = Vec::with_capacity(plugins.len());
+  for plugin in plugins {
+    let serde_json::Value::String(plugin) = plugin else {
+      bail!(
+        "Invalid {} config: \"{}\" must be an array of strings",
+        section,
+        PLUGINS_KEY
+      );
+    };
+    let url = config_specifier.join(&plugin).with_context(|| {
+      format!("Invalid {section} plugin specifier \"{plugin}\"")
+    })?;
+    urls.push(url);
+  }
+  Ok(urls)
 }
 
 impl Default for FmtOptions {
@@ -288,12 +413,23 @@ impl FmtOptions {
     Self {
       check: false,
       options: FmtOptionsConfig::default(),
+      markdown: MarkdownFmtOptions::default(),
+      plugins: Vec::new(),
       files: FilePatterns::new_with_base(base),
+      member_options: Vec::new(),
     }
   }
 
+  /// Gets the options to format the file at `path` with, which are the ones
+  /// of the workspace member containing it if it has any.
+  pub fn options_for_path(&self, path: &Path) -> &FmtOptionsConfig {
+    resolve_workspace_member_value(&self.member_options, path)
+      .unwrap_or(&self.options)
+  }
+
   pub fn resolve(
     maybe_fmt_config: Option<FmtConfig>,
+    cli_config: CliFmtConfig,
     maybe_fmt_flags: Option<FmtFlags>,
     initial_cwd: &Path,
   ) -> Result<Self, AnyError> {
@@ -306,15 +442,50 @@ impl FmtOptions {
         maybe_fmt_flags.as_ref(),
         maybe_config_options,
       ),
+      markdown: cli_config.markdown,
+      plugins: cli_config.plugins,
       files: resolve_files(
         maybe_config_files,
         maybe_fmt_flags.map(|f| f.files),
         initial_cwd,
       )?,
+      member_options: Vec::new(),
     })
   }
 }
 
+/// Gets the value of the innermost workspace member directory that contains
+/// `path`.
+pub fn resolve_workspace_member_value<'a, T>(
+  members: &'a [(PathBuf, T)],
+  path: &Path,
+) -> Option<&'a T> {
+  members
+    .iter()
+    .filter(|(dir, _)| path.starts_with(dir))
+    .max_by_key(|(dir, _)| dir.components().count())
+    .map(|(_, value)| value)
+}
+
+/// Uses the options of the workspace member, falling back to the ones of the
+/// workspace root for the options the member doesn't set.
+fn merge_fmt_options(
+  member: FmtOptionsConfig,
+  root: Option<&FmtOptionsConfig>,
+) -> FmtOptionsConfig {
+  let Some(root) = root else {
+    return member;
+  };
+  FmtOptionsConfig {
+    use_tabs: member.use_tabs.or(root.use_tabs),
+    line_width: member.line_width.or(root.line_width),
+    indent_width: member.indent_width.or(root.indent_width),
+    single_quote: member.single_quote.or(root.single_quote),
+    prose_wrap: member.prose_wrap.or(root.prose_wrap),
+    semi_colons: member.semi_colons.or(root.semi_colons),
+  }
+}
+
 fn resolve_fmt_options(
   fmt_flags: Option<&FmtFlags>,
   options: Option<FmtOptionsConfig>,
@@ -369,6 +540,11 @@ pub struct TestOptions {
   pub trace_leaks: bool,
   pub reporter: TestReporterConfig,
   pub junit_path: Option<String>,
+  pub shard: Option<TestShard>,
+  pub retries: u32,
+  pub timeout: Option<u64>,
+  pub update_snapshots: bool,
+  pub changed: Option<String>,
 }
 
 impl TestOptions {
@@ -397,6 +573,11 @@ impl TestOptions {
       trace_leaks: test_flags.trace_leaks,
       reporter: test_flags.reporter,
       junit_path: test_flags.junit_path,
+      shard: test_flags.shard,
+      retries: test_flags.retries,
+      timeout: test_flags.timeout,
+      update_snapshots: test_flags.update_snapshots,
+      changed: test_flags.changed,
     })
   }
 }
@@ -407,6 +588,19 @@ pub enum LintReporterKind {
   Pretty,
   Json,
   Compact,
+  Sarif,
+}
+
+impl LintReporterKind {
+  fn parse(value: &str) -> Option<Self> {
+    match value {
+      "pretty" => Some(LintReporterKind::Pretty),
+      "json" => Some(LintReporterKind::Json),
+      "compact" => Some(LintReporterKind::Compact),
+      "sarif" => Some(LintReporterKind::Sarif),
+      _ => None,
+    }
+  }
 }
 
 #[derive(Clone, Debug)]
@@ -415,6 +609,17 @@ pub struct LintOptions {
   pub files: FilePatterns,
   pub reporter_kind: LintReporterKind,
   pub fix: bool,
+  pub plugins: Vec<Url>,
+  /// The severity of the rules configured in the config file. Rules that
+  /// aren't in here are reported as errors.
+  pub severity: HashMap<String, LintSeverity>,
+  pub max_warnings: Option<usize>,
+  /// Report `deno-lint-ignore` comments that don't explain the suppression
+  /// with a `-- <reason>`.
+  pub require_suppression_reason: bool,
+  /// The rules of the workspace members with their own `lint.rules` config,
+  /// keyed by member directory.
+  pub member_rules: Vec<(PathBuf, LintRulesConfig)>,
 }
 
 impl Default for LintOptions {
@@ -430,15 +635,22 @@ impl LintOptions {
       files: FilePatterns::new_with_base(base),
       reporter_kind: Default::default(),
       fix: false,
+      plugins: Vec::new(),
+      severity: HashMap::new(),
+      max_warnings: None,
+      require_suppression_reason: false,
+      member_rules: Vec::new(),
     }
   }
 
   pub fn resolve(
     maybe_lint_config: Option<LintConfig>,
+    cli_config: CliLintConfig,
     maybe_lint_flags: Option<LintFlags>,
     initial_cwd: &Path,
   ) -> Result<Self, AnyError> {
     let fix = maybe_lint_flags.as_ref().map(|f| f.fix).unwrap_or(false);
+    let max_warnings = maybe_lint_flags.as_ref().and_then(|f| f.max_warnings);
     let mut maybe_reporter_kind =
       maybe_lint_flags.as_ref().and_then(|lint_flags| {
         if lint_flags.json {
@@ -446,7 +658,10 @@ impl LintOptions {
         } else if lint_flags.compact {
           Some(LintReporterKind::Compact)
         } else {
-          None
+          lint_flags
+            .format
+            .as_deref()
+            .and_then(LintReporterKind::parse)
         }
       });
 
@@ -454,12 +669,10 @@ impl LintOptions {
       // Flag not set, so try to get lint reporter from the config file.
       if let Some(lint_config) = &maybe_lint_config {
         maybe_reporter_kind = match lint_config.report.as_deref() {
-          Some("json") => Some(LintReporterKind::Json),
-          Some("compact") => Some(LintReporterKind::Compact),
-          Some("pretty") => Some(LintReporterKind::Pretty),
-          Some(_) => {
-            bail!("Invalid lint report type in config file")
-          }
+          Some(report) => match LintReporterKind::parse(report) {
+            Some(kind) => Some(kind),
+            None => bail!("Invalid lint report type in config file"),
+          },
           None => None,
         }
       }
@@ -495,18 +708,188 @@ impl LintOptions {
         maybe_rules_tags,
         maybe_rules_include,
         maybe_rules_exclude,
+        &cli_config.severity,
       ),
       fix,
+      plugins: cli_config.plugins,
+      severity: cli_config.severity,
+      require_suppression_reason: cli_config.require_suppression_reason,
+      max_warnings,
+      member_rules: Vec::new(),
     })
   }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LintSeverity {
+  Off,
+  Warn,
+  Error,
+}
+
+/// The options of the `lint` config that deno_config doesn't know about.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CliLintConfig {
+  pub plugins: Vec<Url>,
+  pub severity: HashMap<String, LintSeverity>,
+  pub require_suppression_reason: bool,
+}
+
+const SEVERITY_KEY: &str = "severity";
+const REQUIRE_SUPPRESSION_REASON_KEY: &str = "requireSuppressionReason";
+
+/// Reads the `lint` config of `config_file`, including the options that are
+/// handled by the CLI.
+pub fn resolve_lint_config(
+  config_file: &ConfigFile,
+) -> Result<(Option<LintConfig>, CliLintConfig), AnyError> {
+  let has_cli_options = config_file
+    .json
+    .lint
+    .as_ref()
+    .and_then(|lint| lint.as_object())
+    .is_some_and(|lint| {
+      lint.contains_key(PLUGINS_KEY)
+        || lint.contains_key(SEVERITY_KEY)
+        || lint.contains_key(REQUIRE_SUPPRESSION_REASON_KEY)
+    });
+  if !has_cli_options {
+    return Ok((config_file.to_lint_config()?, CliLintConfig::default()));
+  }
+
+  let mut cli_config = CliLintConfig::default();
+  let mut config_file = config_file.clone();
+  if let Some(lint) = config_file
+    .json
+    .lint
+    .as_mut()
+    .and_then(|lint| lint.as_object_mut())
+  {
+    cli_config.plugins =
+      remove_plugins_option(lint, &config_file.specifier, "lint")?;
+    if let Some(severity) = lint.remove(SEVERITY_KEY) {
+      cli_config.severity = serde_json::from_value(severity).map_err(|_| {
+        anyhow!(
+          "Invalid lint config: \"{}\" must map rule names to \"off\", \"warn\" or \"error\"",
+          SEVERITY_KEY
+        )
+      })?;
+    }
+    match lint.remove(REQUIRE_SUPPRESSION_REASON_KEY) {
+      Some(serde_json::Value::Bool(value)) => {
+        cli_config.require_suppression_reason = value;
+      }
+      Some(_) => bail!(
+        "Invalid lint config: \"{}\" must be a boolean",
+        REQUIRE_SUPPRESSION_REASON_KEY
+      ),
+      None => {}
+    }
+  }
+  Ok((config_file.to_lint_config()?, cli_config))
+}
+
+/// The options of the `tasks` config that deno_config doesn't know about.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CliTasksConfig {
+  pub tasks: HashMap<String, CliTaskOptions>,
+}
+
+/// The options of a task that is written as an object.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "camelCase")]
+pub struct CliTaskOptions {
+  /// The names of the tasks that run before this task.
+  pub depends_on: Vec<String>,
+  /// The directory to run the task in, relative to the config file, unless
+  /// `--cwd` is specified.
+  pub cwd: Option<String>,
+  /// Additional environment variables of the task.
+  pub env: HashMap<String, String>,
+  /// The files or globs that re-run the task with `deno task --watch`,
+  /// relative to the directory the task runs in.
+  pub watch: Vec<String>,
+}
+
+const TASK_COMMAND_KEY: &str = "cmd";
+
+/// Reads the `tasks` config of `config_file`, including the tasks that are
+/// written as an object with a command and its options, like
+/// `{ "cmd": "deno run build.ts", "dependsOn": ["codegen"] }`.
+pub fn resolve_tasks_config(
+  config_file: &ConfigFile,
+) -> Result<(IndexMap<String, deno_config::Task>, CliTasksConfig), AnyError> {
+  let has_cli_options = config_file
+    .json
+    .tasks
+    .as_ref()
+    .and_then(|tasks| tasks.as_object())
+    .is_some_and(|tasks| {
+      tasks
+        .values()
+        .any(|task| task.get(TASK_COMMAND_KEY).is_some())
+    });
+  if !has_cli_options {
+    return Ok((
+      config_file.resolve_tasks_config()?,
+      CliTasksConfig::default(),
+    ));
+  }
+
+  let mut cli_config = CliTasksConfig::default();
+  let mut config_file = config_file.clone();
+  if let Some(tasks) = config_file
+    .json
+    .tasks
+    .as_mut()
+    .and_then(|tasks| tasks.as_object_mut())
+  {
+    for (name, task) in tasks.iter_mut() {
+      let Some(task_obj) = task.as_object_mut() else {
+        continue;
+      };
+      let Some(command) = task_obj.remove(TASK_COMMAND_KEY) else {
+        continue;
+      };
+      let serde_json::Value::String(command) = command else {
+        bail!(
+          "Invalid task \"{}\": \"{}\" must be a string",
+          name,
+          TASK_COMMAND_KEY
+        );
+      };
+      let options = serde_json::from_value::<CliTaskOptions>(
+        serde_json::Value::Object(std::mem::take(task_obj)),
+      )
+      .with_context(|| format!("Invalid task \"{}\"", name))?;
+      cli_config.tasks.insert(name.clone(), options);
+      *task = serde_json::Value::String(command);
+    }
+  }
+  Ok((config_file.resolve_tasks_config()?, cli_config))
+}
+
 fn resolve_lint_rules_options(
   maybe_lint_rules_config: Option<LintRulesConfig>,
   mut maybe_rules_tags: Option<Vec<String>>,
   mut maybe_rules_include: Option<Vec<String>>,
   mut maybe_rules_exclude: Option<Vec<String>>,
+  severity: &HashMap<String, LintSeverity>,
 ) -> LintRulesConfig {
+  // rules that are turned off are excluded, unless explicitly included with
+  // the `--rules-include` flag
+  let disabled_rules = severity
+    .iter()
+    .filter(|(_, severity)| **severity == LintSeverity::Off)
+    .map(|(code, _)| code)
+    .filter(|code| {
+      !maybe_rules_include
+        .as_ref()
+        .is_some_and(|include| include.contains(code))
+    })
+    .cloned()
+    .collect::<Vec<_>>();
   if let Some(config_rules) = maybe_lint_rules_config {
     // Try to get configured rules. CLI flags take precedence
     // over config file, i.e. if there's `rules.include` in config file
@@ -521,6 +904,17 @@ fn resolve_lint_rules_options(
       maybe_rules_tags = config_rules.tags;
     }
   }
+  if !disabled_rules.is_empty() {
+    if let Some(include) = &mut maybe_rules_include {
+      include.retain(|code| !disabled_rules.contains(code));
+    }
+    let exclude = maybe_rules_exclude.get_or_insert_with(Vec::new);
+    for code in disabled_rules {
+      if !exclude.contains(&code) {
+        exclude.push(code);
+      }
+    }
+  }
   LintRulesConfig {
     exclude: maybe_rules_exclude,
     include: maybe_rules_include,
@@ -721,6 +1115,7 @@ pub struct CliOptions {
   maybe_node_modules_folder: Option<PathBuf>,
   maybe_vendor_folder: Option<PathBuf>,
   maybe_config_file: Option<ConfigFile>,
+  maybe_config_file_extras: Option<ConfigFileExtras>,
   maybe_package_json: Option<PackageJson>,
   maybe_lockfile: Option<Arc<Mutex<Lockfile>>>,
   overrides: CliOptionOverrides,
@@ -734,6 +1129,7 @@ impl CliOptions {
     flags: Flags,
     initial_cwd: PathBuf,
     maybe_config_file: Option<ConfigFile>,
+    maybe_config_file_extras: Option<ConfigFileExtras>,
     maybe_lockfile: Option<Arc<Mutex<Lockfile>>>,
     maybe_package_json: Option<PackageJson>,
     force_global_cache: bool,
@@ -765,12 +1161,18 @@ impl CliOptions {
     } else {
       resolve_vendor_folder(&initial_cwd, &flags, maybe_config_file.as_ref())
     };
-    let maybe_workspace_config =
+    let mut maybe_workspace_config =
       if let Some(config_file) = maybe_config_file.as_ref() {
         config_file.to_workspace_config()?
       } else {
         None
       };
+    if let Some(workspace_config) = &mut maybe_workspace_config {
+      for member in &mut workspace_config.members {
+        let extras = ConfigFileExtras::read(&member.config_file)?;
+        deno_json::apply_config_overrides(&mut member.config_file, &extras)?;
+      }
+    }
 
     if let Some(env_file_name) = &flags.env_file {
       if (from_filename(env_file_name)).is_err() {
@@ -793,6 +1195,7 @@ impl CliOptions {
       flags,
       initial_cwd,
       maybe_config_file,
+      maybe_config_file_extras,
       maybe_lockfile,
       maybe_package_json,
       maybe_node_modules_folder,
@@ -804,7 +1207,7 @@ impl CliOptions {
     })
   }
 
-  pub fn from_flags(flags: Flags) -> Result<Self, AnyError> {
+  pub fn from_flags(mut flags: Flags) -> Result<Self, AnyError> {
     let initial_cwd =
       std::env::current_dir().with_context(|| "Failed getting cwd.")?;
     let additional_config_file_names =
@@ -819,7 +1222,7 @@ impl CliOptions {
         DenoSubcommand::Task(..)
       ),
     };
-    let maybe_config_file = ConfigFile::discover(
+    let mut maybe_config_file = ConfigFile::discover(
       &flags.config_flag,
       flags.config_path_args(&initial_cwd),
       &initial_cwd,
@@ -827,6 +1230,35 @@ impl CliOptions {
       &parse_options,
     )?;
 
+    let maybe_config_file_extras = maybe_config_file
+      .as_ref()
+      .map(ConfigFileExtras::read)
+      .transpose()?;
+    if let (Some(config_file), Some(extras)) =
+      (&mut maybe_config_file, &maybe_config_file_extras)
+    {
+      deno_json::apply_config_overrides(config_file, extras)?;
+      if flags.permissions.permission_set
+        && !flags.permissions.has_permission()
+        && config_permissions_apply(&flags, config_file, &initial_cwd)
+      {
+        if let Some(mut permissions) = deno_json::resolve_permissions(extras)? {
+          permissions.no_prompt = flags.permissions.no_prompt;
+          flags.permissions = permissions;
+        }
+      }
+    }
+
+    if env::var_os("DENO_ERROR_THEME").is_none() {
+      if let Some(extras) = &maybe_config_file_extras {
+        if let Some(theme) = deno_json::resolve_error_theme(extras)? {
+          let mut options = format_js_error_options();
+          options.theme = theme;
+          set_format_js_error_options(options);
+        }
+      }
+    }
+
     let mut maybe_package_json = None;
     if flags.config_flag == deno_config::ConfigFlag::Disabled
       || flags.no_npm
@@ -855,6 +1287,7 @@ impl CliOptions {
       flags,
       initial_cwd,
       maybe_config_file,
+      maybe_config_file_extras,
       maybe_lock_file.map(|l| Arc::new(Mutex::new(l))),
       maybe_package_json,
       false,
@@ -890,6 +1323,12 @@ impl CliOptions {
     }
   }
 
+  /// Whether remote modules with a stale cached version should be refreshed
+  /// after the program starts rather than before it.
+  pub fn revalidate_in_background(&self) -> bool {
+    self.flags.revalidate_in_background
+  }
+
   pub fn npm_system_info(&self) -> NpmSystemInfo {
     match self.sub_command() {
       DenoSubcommand::Compile(CompileFlags {
@@ -953,6 +1392,27 @@ impl CliOptions {
     }
   }
 
+  /// Resolve the specifiers of the import maps from `--import-map` given more
+  /// than once, which are merged on top of the specified import map.
+  pub fn resolve_additional_import_map_specifiers(
+    &self,
+  ) -> Result<Vec<ModuleSpecifier>, AnyError> {
+    if self.overrides.import_map_specifier.is_some() {
+      return Ok(Vec::new());
+    }
+    self
+      .flags
+      .additional_import_map_paths
+      .iter()
+      .map(|import_map_path| {
+        deno_core::resolve_url_or_path(import_map_path, &self.initial_cwd)
+          .with_context(|| {
+            format!("Bad URL (\"{import_map_path}\") for import map.")
+          })
+      })
+      .collect()
+  }
+
   pub async fn resolve_import_map(
     &self,
     file_fetcher: &FileFetcher,
@@ -1004,10 +1464,15 @@ impl CliOptions {
       return Ok(None);
     }
 
-    let import_map_specifier = self.resolve_specified_import_map_specifier()?;
+    let import_map_specifiers = self
+      .resolve_specified_import_map_specifier()?
+      .into_iter()
+      .chain(self.resolve_additional_import_map_specifiers()?)
+      .collect::<Vec<_>>();
     resolve_import_map(
-      import_map_specifier.as_ref(),
+      &import_map_specifiers,
       self.maybe_config_file().as_ref(),
+      self.maybe_config_file_extras.as_ref(),
       file_fetcher,
     )
     .await
@@ -1084,6 +1549,12 @@ impl CliOptions {
         resolve_url_or_path(&run_flags.script, self.initial_cwd())
           .map_err(AnyError::from)
       }
+      DenoSubcommand::Permissions(PermissionsFlags {
+        script: Some(script),
+        ..
+      }) => {
+        resolve_url_or_path(script, self.initial_cwd()).map_err(AnyError::from)
+      }
       _ => {
         bail!("No main module.")
       }
@@ -1157,6 +1628,7 @@ impl CliOptions {
       maybe_node_modules_folder: Some(path),
       maybe_vendor_folder: self.maybe_vendor_folder.clone(),
       maybe_config_file: self.maybe_config_file.clone(),
+      maybe_config_file_extras: self.maybe_config_file_extras.clone(),
       maybe_package_json: self.maybe_package_json.clone(),
       maybe_lockfile: self.maybe_lockfile.clone(),
       maybe_workspace_config: self.maybe_workspace_config.clone(),
@@ -1235,11 +1707,16 @@ impl CliOptions {
     self.maybe_lockfile.clone()
   }
 
+  pub fn frozen_lockfile(&self) -> bool {
+    self.flags.frozen_lockfile
+  }
+
   pub fn resolve_tasks_config(
     &self,
-  ) -> Result<IndexMap<String, deno_config::Task>, AnyError> {
+  ) -> Result<(IndexMap<String, deno_config::Task>, CliTasksConfig), AnyError>
+  {
     if let Some(config_file) = &self.maybe_config_file {
-      config_file.resolve_tasks_config()
+      resolve_tasks_config(config_file)
     } else if self.maybe_package_json.is_some() {
       Ok(Default::default())
     } else {
@@ -1281,6 +1758,14 @@ impl CliOptions {
     &self.maybe_config_file
   }
 
+  /// Modules from the configuration file to evaluate when the REPL starts.
+  pub fn resolve_repl_init(&self) -> Result<Vec<ModuleSpecifier>, AnyError> {
+    match &self.maybe_config_file_extras {
+      Some(extras) => deno_json::resolve_repl_init(extras),
+      None => Ok(Vec::new()),
+    }
+  }
+
   pub fn maybe_workspace_config(&self) -> &Option<WorkspaceConfig> {
     &self.maybe_workspace_config
   }
@@ -1308,24 +1793,96 @@ impl CliOptions {
     &self,
     fmt_flags: FmtFlags,
   ) -> Result<FmtOptions, AnyError> {
-    let maybe_fmt_config = if let Some(config_file) = &self.maybe_config_file {
-      config_file.to_fmt_config()?
-    } else {
-      None
-    };
-    FmtOptions::resolve(maybe_fmt_config, Some(fmt_flags), &self.initial_cwd)
+    let (maybe_fmt_config, cli_fmt_config) =
+      if let Some(config_file) = &self.maybe_config_file {
+        resolve_fmt_config(config_file)?
+      } else {
+        (None, CliFmtConfig::default())
+      };
+    let root_options = maybe_fmt_config.as_ref().map(|c| c.options.clone());
+    let mut member_options = Vec::new();
+    for (dir, config_file) in self.workspace_member_config_files() {
+      let Some(member_config) = resolve_fmt_config(config_file)?.0 else {
+        continue;
+      };
+      let options =
+        merge_fmt_options(member_config.options, root_options.as_ref());
+      member_options
+        .push((dir, resolve_fmt_options(Some(&fmt_flags), Some(options))));
+    }
+    let mut fmt_options = FmtOptions::resolve(
+      maybe_fmt_config,
+      cli_fmt_config,
+      Some(fmt_flags),
+      &self.initial_cwd,
+    )?;
+    fmt_options.member_options = member_options;
+    Ok(fmt_options)
   }
 
   pub fn resolve_lint_options(
     &self,
     lint_flags: LintFlags,
   ) -> Result<LintOptions, AnyError> {
-    let maybe_lint_config = if let Some(config_file) = &self.maybe_config_file {
-      config_file.to_lint_config()?
-    } else {
-      None
+    let (maybe_lint_config, cli_lint_config) =
+      if let Some(config_file) = &self.maybe_config_file {
+        resolve_lint_config(config_file)?
+      } else {
+        (None, CliLintConfig::default())
+      };
+    let root_rules = maybe_lint_config.as_ref().map(|c| c.rules.clone());
+    let mut member_rules = Vec::new();
+    for (dir, config_file) in self.workspace_member_config_files() {
+      let Some(member_config) = resolve_lint_config(config_file)?.0 else {
+        continue;
+      };
+      let rules = member_config.rules;
+      if rules.tags.is_none()
+        && rules.include.is_none()
+        && rules.exclude.is_none()
+      {
+        continue;
+      }
+      let root_rules = root_rules.clone().unwrap_or_default();
+      let rules = LintRulesConfig {
+        tags: rules.tags.or(root_rules.tags),
+        include: rules.include.or(root_rules.include),
+        exclude: rules.exclude.or(root_rules.exclude),
+      };
+      member_rules.push((
+        dir,
+        resolve_lint_rules_options(
+          Some(rules),
+          lint_flags.maybe_rules_tags.clone(),
+          lint_flags.maybe_rules_include.clone(),
+          lint_flags.maybe_rules_exclude.clone(),
+          &cli_lint_config.severity,
+        ),
+      ));
+    }
+    let mut lint_options = LintOptions::resolve(
+      maybe_lint_config,
+      cli_lint_config,
+      Some(lint_flags),
+      &self.initial_cwd,
+    )?;
+    lint_options.member_rules = member_rules;
+    Ok(lint_options)
+  }
+
+  /// The directories and config files of the workspace members.
+  fn workspace_member_config_files(&self) -> Vec<(PathBuf, &ConfigFile)> {
+    let Some(workspace_config) = &self.maybe_workspace_config else {
+      return Vec::new();
     };
-    LintOptions::resolve(maybe_lint_config, Some(lint_flags), &self.initial_cwd)
+    workspace_config
+      .members
+      .iter()
+      .filter_map(|member| {
+        let path = member.config_file.specifier.to_file_path().ok()?;
+        Some((path.parent()?.to_path_buf(), &member.config_file))
+      })
+      .collect()
   }
 
   pub fn resolve_config_excludes(&self) -> Result<PathOrPatternSet, AnyError> {
@@ -1338,6 +1895,15 @@ impl CliOptions {
     Ok(maybe_config_files.map(|f| f.exclude).unwrap_or_default())
   }
 
+  /// Files to type-check when none are provided to `deno check`.
+  pub fn resolve_check_files(&self) -> Result<FilePatterns, AnyError> {
+    let maybe_files_config = match &self.maybe_config_file {
+      Some(config_file) => Some(config_file.to_files_config()?),
+      None => None,
+    };
+    resolve_files(maybe_files_config, None, &self.initial_cwd)
+  }
+
   pub fn resolve_test_options(
     &self,
     test_flags: TestFlags,
@@ -1540,8 +2106,83 @@ impl CliOptions {
     &self.flags.permissions
   }
 
+  /// Permissions of the flags and the permission policy along with the ones
+  /// stored for the project. Prompts answered with "always allow" are stored
+  /// for the project as well.
   pub fn permissions_options(&self) -> Result<PermissionsOptions, AnyError> {
-    self.flags.permissions.to_options(Some(&self.initial_cwd))
+    static PERSIST_GRANT_CALLBACK: Once = Once::new();
+
+    let mut permissions = self.flags.permissions.clone();
+    if let Some(policy) = self.permission_policy()? {
+      policy.apply(&mut permissions);
+    }
+    if let Some(store) = self.permission_grant_store()? {
+      apply_permission_grants(&store.grants()?, &mut permissions)?;
+      if !permissions.no_prompt {
+        // the callback is global, so it's only installed for the first
+        // options that are resolved, e.g. not again on watcher restarts
+        PERSIST_GRANT_CALLBACK.call_once(|| {
+          deno_runtime::permissions::set_persist_grant_callback(Box::new(
+            move |name, value| {
+              if let Err(err) = store.add(name, value) {
+                log::warn!(
+                  "{} Failed storing permission grant: {:#}",
+                  colors::yellow("Warning"),
+                  err
+                );
+              }
+            },
+          ));
+        });
+      }
+    }
+    permissions.to_options(Some(&self.initial_cwd))
+  }
+
+  /// The policy file passed with `--permission-policy`, if any.
+  pub fn permission_policy(
+    &self,
+  ) -> Result<Option<PermissionPolicy>, AnyError> {
+    match &self.flags.permissions.permission_policy {
+      Some(path) => {
+        PermissionPolicy::read(&self.initial_cwd.join(path)).map(Some)
+      }
+      None => Ok(None),
+    }
+  }
+
+  /// The store of permissions granted with the "always allow" option of
+  /// permission prompts. The project is the directory of the configuration
+  /// file, or the main module when there is none. There is no store when
+  /// there is neither.
+  pub fn permission_grant_store(
+    &self,
+  ) -> Result<Option<PermissionGrantStore>, AnyError> {
+    let maybe_config_dir = self
+      .maybe_config_file
+      .as_ref()
+      .and_then(|config_file| config_file.specifier.to_file_path().ok())
+      .and_then(|path| Some(path.parent()?.to_path_buf()));
+    let project = match maybe_config_dir {
+      Some(config_dir) => config_dir.to_string_lossy().into_owned(),
+      None => match self.resolve_main_module() {
+        Ok(main_module) => main_module.to_string(),
+        Err(_) => return Ok(None),
+      },
+    };
+    let deno_dir = DenoDir::new(self.maybe_custom_root().clone())?;
+    Ok(Some(PermissionGrantStore::new(
+      deno_dir.permission_grants_file_path(),
+      project,
+    )))
+  }
+
+  /// The `localStorage` quota in bytes.
+  pub fn local_storage_quota(&self) -> Option<usize> {
+    self
+      .flags
+      .local_storage_quota
+      .map(|megabytes| (megabytes as usize).saturating_mul(1024 * 1024))
   }
 
   pub fn reload_flag(&self) -> bool {
@@ -1587,6 +2228,10 @@ impl CliOptions {
     &self.flags.unsafely_ignore_certificate_errors
   }
 
+  pub fn http1_only(&self) -> bool {
+    self.flags.http1_only
+  }
+
   pub fn legacy_unstable_flag(&self) -> bool {
     self.flags.unstable_config.legacy_flag_enabled
   }
@@ -1654,9 +2299,24 @@ impl CliOptions {
     if let DenoSubcommand::Run(RunFlags {
       watch: Some(WatchFlagsWithPaths { paths, .. }),
       ..
+    })
+    | DenoSubcommand::Serve(ServeFlags {
+      watch: Some(WatchFlagsWithPaths { paths, .. }),
+      ..
     }) = &self.flags.subcommand
     {
-      full_paths.extend(paths.iter().map(|path| self.initial_cwd.join(path)));
+      match resolve_watch_paths(&self.initial_cwd, paths) {
+        Ok(paths) => full_paths.extend(paths),
+        Err(err) => {
+          log::warn!(
+            "{} Failed resolving watch paths: {:#}",
+            colors::yellow("Warning"),
+            err
+          );
+          full_paths
+            .extend(paths.iter().map(|path| self.initial_cwd.join(path)));
+        }
+      }
     }
 
     if let Ok(Some(import_map_path)) = self
@@ -1665,6 +2325,21 @@ impl CliOptions {
     {
       full_paths.push(import_map_path);
     }
+    if let Ok(specifiers) = self.resolve_additional_import_map_specifiers() {
+      full_paths
+        .extend(specifiers.iter().filter_map(|s| s.to_file_path().ok()));
+    }
+    if let (Ok(None), Some(extras)) = (
+      self.resolve_specified_import_map_specifier(),
+      &self.maybe_config_file_extras,
+    ) {
+      // the import maps extended by the configuration file are only used
+      // when no import map is specified
+      if let Ok(specifiers) = deno_json::resolve_import_map_extends(extras) {
+        full_paths
+          .extend(specifiers.iter().filter_map(|s| s.to_file_path().ok()));
+      }
+    }
     if let Some(specifier) = self.maybe_config_file_specifier() {
       if specifier.scheme() == "file" {
         if let Ok(path) = specifier.to_file_path() {
@@ -1733,6 +2408,48 @@ fn resolve_vendor_folder(
   }
 }
 
+/// Whether the `"permissions"` of the config file apply, which is only the
+/// case when the entrypoints are local modules in the config file's directory,
+/// so that running a remote or unrelated module never gets them.
+fn config_permissions_apply(
+  flags: &Flags,
+  config_file: &ConfigFile,
+  cwd: &Path,
+) -> bool {
+  let Some(config_dir) = config_file
+    .specifier
+    .to_file_path()
+    .ok()
+    .and_then(|path| Some(path.parent()?.to_path_buf()))
+  else {
+    return false;
+  };
+  let entrypoints = match &flags.subcommand {
+    DenoSubcommand::Run(RunFlags { script, .. })
+    | DenoSubcommand::Serve(ServeFlags { script, .. }) => {
+      match resolve_url_or_path(script, cwd) {
+        Ok(specifier) if specifier.scheme() == "file" => {
+          specifier.to_file_path().into_iter().collect()
+        }
+        _ => return false,
+      }
+    }
+    DenoSubcommand::Test(TestFlags { files, .. })
+    | DenoSubcommand::Bench(BenchFlags { files, .. }) => {
+      if files.include.is_empty() {
+        vec![cwd.to_path_buf()]
+      } else {
+        files.include.iter().map(|path| cwd.join(path)).collect()
+      }
+    }
+    _ => return false,
+  };
+  !entrypoints.is_empty()
+    && entrypoints
+      .iter()
+      .all(|path| deno_core::normalize_path(path).starts_with(&config_dir))
+}
+
 fn resolve_import_map_specifier(
   maybe_import_map_path: Option<&str>,
   maybe_config_file: Option<&ConfigFile>,
@@ -1884,6 +2601,196 @@ mod test {
     assert_eq!(actual, Some(expected_specifier));
   }
 
+  #[test]
+  fn resolve_fmt_config_cli_options() {
+    let config_specifier =
+      ModuleSpecifier::parse("file:///deno/deno.json").unwrap();
+    let config_file = ConfigFile::new(
+      r#"{
+        "fmt": {
+          "lineWidth": 100,
+          "markdownCodeBlocks": false,
+          "plugins": [
+            "./plugins/toml.wasm",
+            "https://plugins.dprint.dev/css.wasm"
+          ]
+        }
+      }"#,
+      config_specifier.clone(),
+      &deno_config::ParseOptions::default(),
+    )
+    .unwrap();
+    let (fmt_config, cli_config) = resolve_fmt_config(&config_file).unwrap();
+    assert_eq!(fmt_config.unwrap().options.line_width, Some(100));
+    assert_eq!(
+      cli_config,
+      CliFmtConfig {
+        markdown: MarkdownFmtOptions {
+          format_code_blocks: false,
+        },
+        plugins: vec![
+          Url::parse("file:///deno/plugins/toml.wasm").unwrap(),
+          Url::parse("https://plugins.dprint.dev/css.wasm").unwrap(),
+        ],
+      }
+    );
+
+    let config_file = ConfigFile::new(
+      r#"{ "fmt": { "plugins": "toml.wasm" } }"#,
+      config_specifier,
+      &deno_config::ParseOptions::default(),
+    )
+    .unwrap();
+    assert!(resolve_fmt_config(&config_file).is_err());
+  }
+
+  #[test]
+  fn resolve_lint_config_plugins() {
+    let config_file = ConfigFile::new(
+      r#"{
+        "lint": {
+          "rules": { "exclude": ["no-var"] },
+          "plugins": ["./lint/plugin.ts"]
+        }
+      }"#,
+      ModuleSpecifier::parse("file:///deno/deno.json").unwrap(),
+      &deno_config::ParseOptions::default(),
+    )
+    .unwrap();
+    let (lint_config, cli_config) = resolve_lint_config(&config_file).unwrap();
+    assert_eq!(
+      lint_config.unwrap().rules.exclude,
+      Some(vec!["no-var".to_string()])
+    );
+    assert_eq!(
+      cli_config.plugins,
+      vec![Url::parse("file:///deno/lint/plugin.ts").unwrap()]
+    );
+  }
+
+  #[test]
+  fn resolve_tasks_config_dependencies() {
+    let config_file = ConfigFile::new(
+      r#"{
+        "tasks": {
+          "codegen": "deno run codegen.ts",
+          "build": {
+            "cmd": "deno run build.ts",
+            "dependsOn": ["codegen"]
+          },
+          "check": { "cmd": "deno check main.ts" },
+          "serve": {
+            "cmd": "deno run -A main.ts",
+            "cwd": "./server",
+            "env": { "PORT": "8080" },
+            "watch": ["src/**/*.ts"]
+          }
+        }
+      }"#,
+      ModuleSpecifier::parse("file:///deno/deno.json").unwrap(),
+      &deno_config::ParseOptions::default(),
+    )
+    .unwrap();
+    let (tasks, cli_config) = resolve_tasks_config(&config_file).unwrap();
+    assert_eq!(
+      tasks.keys().collect::<Vec<_>>(),
+      vec!["codegen", "build", "check", "serve"]
+    );
+    assert!(matches!(
+      &tasks["build"],
+      deno_config::Task::Definition(command) if command == "deno run build.ts"
+    ));
+    assert_eq!(
+      cli_config.tasks["build"].depends_on,
+      vec!["codegen".to_string()]
+    );
+    assert_eq!(cli_config.tasks["check"], CliTaskOptions::default());
+    assert!(!cli_config.tasks.contains_key("codegen"));
+
+    assert_eq!(
+      cli_config.tasks["serve"],
+      CliTaskOptions {
+        depends_on: vec![],
+        cwd: Some("./server".to_string()),
+        env: HashMap::from([("PORT".to_string(), "8080".to_string())]),
+        watch: vec!["src/**/*.ts".to_string()],
+      }
+    );
+
+    let config_file = ConfigFile::new(
+      r#"{ "tasks": { "build": { "cmd": "deno run build.ts", "dependsOn": "codegen" } } }"#,
+      ModuleSpecifier::parse("file:///deno/deno.json").unwrap(),
+      &deno_config::ParseOptions::default(),
+    )
+    .unwrap();
+    assert!(resolve_tasks_config(&config_file).is_err());
+
+    let config_file = ConfigFile::new(
+      r#"{ "tasks": { "build": { "cmd": "deno run build.ts", "deps": [] } } }"#,
+      ModuleSpecifier::parse("file:///deno/deno.json").unwrap(),
+      &deno_config::ParseOptions::default(),
+    )
+    .unwrap();
+    assert!(resolve_tasks_config(&config_file).is_err());
+  }
+
+  #[test]
+  fn resolve_lint_config_severity() {
+    let config_file = ConfigFile::new(
+      r#"{
+        "lint": {
+          "rules": { "include": ["no-debugger"] },
+          "severity": { "no-var": "warn", "no-debugger": "off" },
+          "requireSuppressionReason": true
+        }
+      }"#,
+      ModuleSpecifier::parse("file:///deno/deno.json").unwrap(),
+      &deno_config::ParseOptions::default(),
+    )
+    .unwrap();
+    let (lint_config, cli_config) = resolve_lint_config(&config_file).unwrap();
+    assert_eq!(
+      cli_config.severity,
+      HashMap::from([
+        ("no-var".to_string(), LintSeverity::Warn),
+        ("no-debugger".to_string(), LintSeverity::Off),
+      ])
+    );
+    assert!(cli_config.require_suppression_reason);
+    let rules = resolve_lint_rules_options(
+      lint_config.map(|c| c.rules),
+      None,
+      None,
+      None,
+      &cli_config.severity,
+    );
+    assert_eq!(rules.include, Some(vec![]));
+    assert_eq!(rules.exclude, Some(vec!["no-debugger".to_string()]));
+
+    // explicitly included rules are still run
+    let rules = resolve_lint_rules_options(
+      None,
+      None,
+      Some(vec!["no-debugger".to_string()]),
+      None,
+      &cli_config.severity,
+    );
+    assert_eq!(rules.include, Some(vec!["no-debugger".to_string()]));
+    assert_eq!(rules.exclude, None);
+
+    let config_file = ConfigFile::new(
+      r#"{ "lint": { "severity": { "no-var": "warning" } } }"#,
+      ModuleSpecifier::parse("file:///deno/deno.json").unwrap(),
+      &deno_config::ParseOptions::default(),
+    )
+    .unwrap();
+    let err = resolve_lint_config(&config_file).unwrap_err();
+    assert_eq!(
+      err.to_string(),
+      "Invalid lint config: \"severity\" must map rule names to \"off\", \"warn\" or \"error\""
+    );
+  }
+
   #[test]
   fn resolve_import_map_none() {
     let config_text = r#"{}"#;
@@ -2030,4 +2937,44 @@ mod test {
     let reg_api_url = jsr_api_url();
     assert!(reg_api_url.as_str().ends_with('/'));
   }
+
+  #[test]
+  fn test_resolve_workspace_member_value() {
+    let members = vec![
+      (PathBuf::from("/repo/a"), "a"),
+      (PathBuf::from("/repo/a/nested"), "nested"),
+      (PathBuf::from("/repo/b"), "b"),
+    ];
+    let resolve =
+      |path: &str| resolve_workspace_member_value(&members, Path::new(path));
+    assert_eq!(resolve("/repo/a/mod.ts"), Some(&"a"));
+    assert_eq!(resolve("/repo/a/nested/mod.ts"), Some(&"nested"));
+    assert_eq!(resolve("/repo/b/mod.ts"), Some(&"b"));
+    assert_eq!(resolve("/repo/bc/mod.ts"), None);
+    assert_eq!(resolve("/repo/main.ts"), None);
+  }
+
+  #[test]
+  fn test_merge_fmt_options() {
+    let root = FmtOptionsConfig {
+      line_width: Some(100),
+      semi_colons: Some(false),
+      ..Default::default()
+    };
+    let member = FmtOptionsConfig {
+      line_width: Some(80),
+      single_quote: Some(true),
+      ..Default::default()
+    };
+    assert_eq!(
+      merge_fmt_options(member.clone(), Some(&root)),
+      FmtOptionsConfig {
+        line_width: Some(80),
+        single_quote: Some(true),
+        semi_colons: Some(false),
+        ..Default::default()
+      }
+    );
+    assert_eq!(merge_fmt_options(member.clone(), None), member);
+  }
 }
@@ -0,0 +1,231 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+
+use super::PermissionFlags;
+use crate::util::fs::atomic_write_file;
+
+/// Grants of each permission, keyed by permission name. An empty list means
+/// that all access was granted.
+pub type PermissionGrants = BTreeMap<String, Vec<String>>;
+
+/// Permissions granted with the "always allow for this project" option of a
+/// permission prompt. The grants of every project are kept in a single file in
+/// the DENO_DIR rather than in the project itself, so that a project can't
+/// grant itself permissions. A project is identified by the directory of its
+/// configuration file, or by its main module when there is none.
+#[derive(Debug, Clone)]
+pub struct PermissionGrantStore {
+  file_path: PathBuf,
+  project: String,
+}
+
+impl PermissionGrantStore {
+  pub fn new(file_path: PathBuf, project: String) -> Self {
+    Self { file_path, project }
+  }
+
+  pub fn project(&self) -> &str {
+    &self.project
+  }
+
+  /// The grants stored for the project.
+  pub fn grants(&self) -> Result<PermissionGrants, AnyError> {
+    let mut projects = self.read()?;
+    Ok(projects.remove(&self.project_key()).unwrap_or_default())
+  }
+
+  /// Store a grant for the project. A value of `None` grants all access.
+  pub fn add(&self, name: &str, value: Option<&str>) -> Result<(), AnyError> {
+    let mut projects = self.read()?;
+    let grants = projects.entry(self.project_key()).or_default();
+    let is_new = !grants.contains_key(name);
+    let values = grants.entry(name.to_string()).or_default();
+    match value {
+      None => values.clear(),
+      // an empty list already grants everything
+      Some(_) if values.is_empty() && !is_new => return Ok(()),
+      Some(value) => {
+        if !values.iter().any(|v| v == value) {
+          values.push(value.to_string());
+        }
+      }
+    }
+    self.write(&projects)
+  }
+
+  /// Remove stored grants of the project. Removes every grant when `name` is
+  /// `None` and every grant of the permission when `value` is `None`. Returns
+  /// whether anything was removed.
+  pub fn revoke(
+    &self,
+    name: Option<&str>,
+    value: Option<&str>,
+  ) -> Result<bool, AnyError> {
+    let mut projects = self.read()?;
+    let key = self.project_key();
+    let Some(grants) = projects.get_mut(&key) else {
+      return Ok(false);
+    };
+    let removed = match (name, value) {
+      (None, _) => {
+        grants.clear();
+        true
+      }
+      (Some(name), None) => grants.remove(name).is_some(),
+      (Some(name), Some(value)) => match grants.get_mut(name) {
+        Some(values) => {
+          let len = values.len();
+          values.retain(|v| v != value);
+          if values.is_empty() {
+            grants.remove(name);
+          }
+          len != values.len()
+        }
+        None => false,
+      },
+    };
+    if grants.is_empty() {
+      projects.remove(&key);
+    }
+    if removed {
+      self.write(&projects)?;
+    }
+    Ok(removed)
+  }
+
+  fn project_key(&self) -> String {
+    self.project.clone()
+  }
+
+  fn read(&self) -> Result<BTreeMap<String, PermissionGrants>, AnyError> {
+    let text = match std::fs::read_to_string(&self.file_path) {
+      Ok(text) => text,
+      Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+        return Ok(Default::default())
+      }
+      Err(err) => {
+        return Err(err).with_context(|| {
+          format!("Failed reading {}", self.file_path.display())
+        })
+      }
+    };
+    serde_json::from_str(&text)
+      .with_context(|| format!("Failed parsing {}", self.file_path.display()))
+  }
+
+  fn write(
+    &self,
+    projects: &BTreeMap<String, PermissionGrants>,
+  ) -> Result<(), AnyError> {
+    let text = serde_json::to_string_pretty(projects)?;
+    atomic_write_file(&self.file_path, text, 0o600)
+      .with_context(|| format!("Failed writing {}", self.file_path.display()))
+  }
+}
+
+/// Add stored grants to the permission flags. Grants never replace a flag
+/// that already allows all access.
+pub fn apply_permission_grants(
+  grants: &PermissionGrants,
+  flags: &mut PermissionFlags,
+) -> Result<(), AnyError> {
+  for (name, values) in grants {
+    let list = match name.as_str() {
+      "hrtime" => {
+        flags.allow_hrtime = true;
+        continue;
+      }
+      "env" => &mut flags.allow_env,
+      "ffi" => &mut flags.allow_ffi,
+      "net" => &mut flags.allow_net,
+      "read" => &mut flags.allow_read,
+      "run" => &mut flags.allow_run,
+      "sys" => &mut flags.allow_sys,
+      "write" => &mut flags.allow_write,
+      _ => bail!("Unknown permission \"{name}\" in stored permission grants"),
+    };
+    match list {
+      Some(list) if list.is_empty() => {}
+      Some(list) if !values.is_empty() => list.extend(values.iter().cloned()),
+      _ => *list = Some(values.clone()),
+    }
+  }
+  Ok(())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn add_and_revoke_grants() {
+    let temp_dir = test_util::TempDir::new();
+    let file_path = temp_dir.path().join("permissions.json").to_path_buf();
+    let store =
+      PermissionGrantStore::new(file_path.clone(), "/project".to_string());
+    let other = PermissionGrantStore::new(file_path, "/other".to_string());
+
+    store.add("read", Some("/project/data")).unwrap();
+    store.add("read", Some("/project/data")).unwrap();
+    store.add("net", Some("example.com")).unwrap();
+    store.add("hrtime", None).unwrap();
+    other.add("env", Some("HOME")).unwrap();
+    assert_eq!(
+      store.grants().unwrap(),
+      BTreeMap::from([
+        ("hrtime".to_string(), vec![]),
+        ("net".to_string(), vec!["example.com".to_string()]),
+        ("read".to_string(), vec!["/project/data".to_string()]),
+      ])
+    );
+
+    // granting all access replaces the granted values
+    store.add("net", None).unwrap();
+    store.add("net", Some("deno.land")).unwrap();
+    assert_eq!(store.grants().unwrap()["net"], Vec::<String>::new());
+
+    assert!(store.revoke(Some("read"), Some("/project/data")).unwrap());
+    assert!(!store.revoke(Some("read"), None).unwrap());
+    assert!(store.revoke(Some("net"), None).unwrap());
+    assert_eq!(store.grants().unwrap().len(), 1);
+    assert!(store.revoke(None, None).unwrap());
+    assert!(store.grants().unwrap().is_empty());
+    assert_eq!(other.grants().unwrap().len(), 1);
+  }
+
+  #[test]
+  fn apply_grants_to_flags() {
+    let grants = BTreeMap::from([
+      ("env".to_string(), vec!["HOME".to_string()]),
+      ("hrtime".to_string(), vec![]),
+      ("net".to_string(), vec![]),
+      ("read".to_string(), vec!["/data".to_string()]),
+    ]);
+    let mut flags = PermissionFlags {
+      allow_env: Some(vec![]),
+      allow_read: Some(vec!["/tmp".to_string()]),
+      ..Default::default()
+    };
+    apply_permission_grants(&grants, &mut flags).unwrap();
+    assert_eq!(
+      flags,
+      PermissionFlags {
+        allow_env: Some(vec![]),
+        allow_hrtime: true,
+        allow_net: Some(vec![]),
+        allow_read: Some(vec!["/tmp".to_string(), "/data".to_string()]),
+        ..Default::default()
+      }
+    );
+
+    let grants = BTreeMap::from([("foo".to_string(), vec![])]);
+    assert!(apply_permission_grants(&grants, &mut flags).is_err());
+  }
+}
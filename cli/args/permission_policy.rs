@@ -0,0 +1,270 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::ModuleSpecifier;
+use deno_runtime::permissions::Permissions;
+use deno_runtime::permissions::PermissionsContainer;
+
+use super::deno_json::permission_flags_from_json;
+use super::PermissionFlags;
+
+/// A permission policy file passed with `--permission-policy`. The
+/// "permissions" of the policy are granted to the program like permission
+/// flags, while the entries of "origins" restrict the workers whose main
+/// module is loaded from that origin to a subset of them:
+///
+/// ```json
+/// {
+///   "permissions": { "read": ["./data"], "net": ["api.example.com"] },
+///   "origins": {
+///     "file://": { "read": ["./data"] },
+///     "https://jsr.io": { "net": ["api.example.com"] }
+///   }
+/// }
+/// ```
+///
+/// Paths are relative to the policy file.
+#[derive(Debug)]
+pub struct PermissionPolicy {
+  permissions: PermissionFlags,
+  origins: HashMap<String, Permissions>,
+}
+
+impl PermissionPolicy {
+  pub fn read(path: &Path) -> Result<Self, AnyError> {
+    let text = std::fs::read_to_string(path).with_context(|| {
+      format!("Failed reading permission policy {}", path.display())
+    })?;
+    let base_dir = path.parent().unwrap_or(path);
+    Self::parse(&text, base_dir).with_context(|| {
+      format!("Failed parsing permission policy {}", path.display())
+    })
+  }
+
+  fn parse(text: &str, base_dir: &Path) -> Result<Self, AnyError> {
+    let serde_json::Value::Object(mut policy) = serde_json::from_str(text)?
+    else {
+      bail!("The permission policy must be an object");
+    };
+    let permissions = match policy.remove("permissions") {
+      None => PermissionFlags::default(),
+      Some(serde_json::Value::Object(obj)) => {
+        permission_flags_from_json(obj, Some(base_dir), "permissions")?
+      }
+      Some(_) => bail!("\"permissions\" must be an object"),
+    };
+    let mut origins = HashMap::new();
+    match policy.remove("origins") {
+      None => {}
+      Some(serde_json::Value::Object(obj)) => {
+        for (origin, value) in obj {
+          let serde_json::Value::Object(value) = value else {
+            bail!("\"origins.{origin}\" must be an object");
+          };
+          let specifier =
+            ModuleSpecifier::parse(&origin).with_context(|| {
+              format!("Invalid origin \"{origin}\" in \"origins\"")
+            })?;
+          let property = format!("origins.{origin}");
+          let mut options =
+            permission_flags_from_json(value, Some(base_dir), &property)?
+              .to_options(Some(base_dir))?;
+          options.prompt = false;
+          origins.insert(
+            origin_key(&specifier),
+            Permissions::from_options(&options)?,
+          );
+        }
+      }
+      Some(_) => bail!("\"origins\" must be an object"),
+    }
+    if let Some(key) = policy.keys().next() {
+      bail!("Unknown property \"{key}\" in the permission policy");
+    }
+    Ok(Self {
+      permissions,
+      origins,
+    })
+  }
+
+  /// The permissions granted to the program by the policy.
+  pub fn permissions(&self) -> &PermissionFlags {
+    &self.permissions
+  }
+
+  /// Add the permissions granted by the policy to the permission flags.
+  pub fn apply(&self, flags: &mut PermissionFlags) {
+    let policy = &self.permissions;
+    flags.allow_all |= policy.allow_all;
+    flags.allow_hrtime |= policy.allow_hrtime;
+    for (list, policy_list) in [
+      (&mut flags.allow_env, &policy.allow_env),
+      (&mut flags.allow_ffi, &policy.allow_ffi),
+      (&mut flags.allow_net, &policy.allow_net),
+      (&mut flags.allow_read, &policy.allow_read),
+      (&mut flags.allow_run, &policy.allow_run),
+      (&mut flags.allow_sys, &policy.allow_sys),
+      (&mut flags.allow_write, &policy.allow_write),
+    ] {
+      match (list, policy_list) {
+        (_, None) => {}
+        (Some(list), _) if list.is_empty() => {}
+        (Some(list), Some(policy_list)) if !policy_list.is_empty() => {
+          list.extend(policy_list.iter().cloned())
+        }
+        (list, policy_list) => *list = policy_list.clone(),
+      }
+    }
+  }
+
+  /// Restrict the permissions of a worker to the ones the policy allows for
+  /// the origin of its main module. Workers loaded from an origin that isn't
+  /// listed keep their permissions.
+  pub fn restrict(
+    &self,
+    main_module: &ModuleSpecifier,
+    permissions: PermissionsContainer,
+  ) -> PermissionsContainer {
+    match self.origins.get(&origin_key(main_module)) {
+      Some(allowed) => {
+        let restricted = permissions.0 .0.lock().intersect(allowed);
+        PermissionsContainer::new(restricted)
+      }
+      None => permissions,
+    }
+  }
+}
+
+/// Local modules don't have an origin of their own, so they're all listed
+/// under "file://".
+fn origin_key(specifier: &ModuleSpecifier) -> String {
+  match specifier.scheme() {
+    "file" => "file://".to_string(),
+    _ => specifier.origin().ascii_serialization(),
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use std::path::PathBuf;
+
+  use super::*;
+
+  #[test]
+  fn parse_policy() {
+    let base_dir = if cfg!(windows) {
+      PathBuf::from("C:\\app")
+    } else {
+      PathBuf::from("/app")
+    };
+    let policy = PermissionPolicy::parse(
+      r#"{
+        "permissions": {
+          "read": ["./data"],
+          "net": ["api.example.com"],
+          "env": ["HOME"]
+        },
+        "origins": {
+          "file://": { "read": ["./data/public"], "env": true },
+          "https://jsr.io/": { "net": ["api.example.com"] }
+        }
+      }"#,
+      &base_dir,
+    )
+    .unwrap();
+    assert_eq!(
+      policy.permissions(),
+      &PermissionFlags {
+        allow_env: Some(vec!["HOME".to_string()]),
+        allow_net: Some(vec!["api.example.com".to_string()]),
+        allow_read: Some(vec![base_dir
+          .join("./data")
+          .to_string_lossy()
+          .into_owned()]),
+        ..Default::default()
+      }
+    );
+
+    let permissions = PermissionsContainer::new(
+      Permissions::from_options(
+        &policy.permissions().to_options(Some(&base_dir)).unwrap(),
+      )
+      .unwrap(),
+    );
+    let main_module =
+      ModuleSpecifier::from_file_path(base_dir.join("main.ts")).unwrap();
+    let mut restricted = policy.restrict(&main_module, permissions.clone());
+    assert!(restricted
+      .check_read(&base_dir.join("data/public/a.txt"), "test")
+      .is_ok());
+    assert!(restricted
+      .check_read(&base_dir.join("data/a.txt"), "test")
+      .is_err());
+    assert!(restricted.check_env("HOME").is_ok());
+    assert!(restricted.check_env("PATH").is_err());
+
+    let main_module =
+      ModuleSpecifier::parse("https://jsr.io/@std/http/mod.ts").unwrap();
+    let mut restricted = policy.restrict(&main_module, permissions.clone());
+    assert!(restricted
+      .check_read(&base_dir.join("data/public/a.txt"), "test")
+      .is_err());
+    assert!(restricted.check_env("HOME").is_err());
+
+    // unlisted origins keep their permissions
+    let main_module =
+      ModuleSpecifier::parse("https://deno.land/x/mod.ts").unwrap();
+    let mut restricted = policy.restrict(&main_module, permissions);
+    assert!(restricted.check_env("HOME").is_ok());
+  }
+
+  #[test]
+  fn apply_policy_to_flags() {
+    let policy = PermissionPolicy::parse(
+      r#"{ "permissions": { "env": ["HOME"], "net": true, "read": ["/data"] } }"#,
+      Path::new("/"),
+    )
+    .unwrap();
+    let mut flags = PermissionFlags {
+      allow_env: Some(vec![]),
+      allow_read: Some(vec!["/tmp".to_string()]),
+      permission_policy: Some("policy.json".to_string()),
+      ..Default::default()
+    };
+    policy.apply(&mut flags);
+    assert_eq!(
+      flags,
+      PermissionFlags {
+        allow_env: Some(vec![]),
+        allow_net: Some(vec![]),
+        allow_read: Some(vec![
+          "/tmp".to_string(),
+          Path::new("/").join("/data").to_string_lossy().into_owned()
+        ]),
+        permission_policy: Some("policy.json".to_string()),
+        ..Default::default()
+      }
+    );
+  }
+
+  #[test]
+  fn parse_policy_errors() {
+    let base_dir = PathBuf::from("/app");
+    for text in [
+      "[]",
+      r#"{ "permissions": [] }"#,
+      r#"{ "permissions": { "foo": true } }"#,
+      r#"{ "origins": { "not an origin": {} } }"#,
+      r#"{ "origins": { "https://jsr.io": { "net": 1 } } }"#,
+      r#"{ "allow": {} }"#,
+    ] {
+      assert!(PermissionPolicy::parse(text, &base_dir).is_err(), "{text}");
+    }
+  }
+}
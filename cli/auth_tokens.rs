@@ -2,15 +2,23 @@
 
 use base64::prelude::BASE64_STANDARD;
 use base64::Engine;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
 use deno_core::ModuleSpecifier;
+use deno_runtime::deno_tls::load_certs;
+use deno_runtime::deno_tls::load_private_keys;
+use deno_runtime::deno_tls::TlsKey;
 use log::debug;
 use log::error;
 use std::borrow::Cow;
 use std::fmt;
+use std::io::BufReader;
 use std::net::IpAddr;
 use std::net::Ipv4Addr;
 use std::net::Ipv6Addr;
 use std::net::SocketAddr;
+use std::path::PathBuf;
 use std::str::FromStr;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -23,6 +31,9 @@ pub enum AuthTokenData {
 pub struct AuthToken {
   host: AuthDomain,
   token: AuthTokenData,
+  /// Whether the token may only be sent over `https:`, as is the case for the
+  /// credentials of a netrc file.
+  https_only: bool,
 }
 
 impl fmt::Display for AuthToken {
@@ -50,6 +61,8 @@ pub enum AuthDomain {
   IpPort(SocketAddr),
   /// Suffix match, no dot. May include a port.
   Suffix(Cow<'static, str>),
+  /// Exact match of the hostname, irrespective of the port.
+  Host(String),
 }
 
 impl<T: ToString> From<T> for AuthDomain {
@@ -111,6 +124,7 @@ impl AuthDomain {
 
         false
       }
+      Self::Host(ref expected) => host.eq_ignore_ascii_case(expected),
     }
   }
 }
@@ -135,11 +149,13 @@ impl AuthTokens {
             tokens.push(AuthToken {
               host,
               token: AuthTokenData::Basic { username, password },
+              https_only: false,
             })
           } else {
             tokens.push(AuthToken {
               host,
               token: AuthTokenData::Bearer(token.to_string()),
+              https_only: false,
             });
           }
         } else {
@@ -152,6 +168,68 @@ impl AuthTokens {
     Self(tokens)
   }
 
+  /// Add the credentials of the `machine` entries of a netrc file, which are
+  /// only used for hosts that don't match a token that was already added.
+  ///
+  /// Each entry only matches its exact hostname over `https:`. The `default`
+  /// entry is ignored so that credentials are never sent to hosts that aren't
+  /// explicitly listed, for example after a redirect.
+  pub fn with_netrc(mut self, netrc: &str) -> Self {
+    let mut entries = Vec::new();
+    let mut machine: Option<String> = None;
+    let mut login: Option<String> = None;
+    let mut password: Option<String> = None;
+    let mut in_macdef = false;
+    let mut words = Vec::new();
+    for line in netrc.lines() {
+      if in_macdef {
+        // macro definitions end with an empty line
+        in_macdef = !line.trim().is_empty();
+        continue;
+      }
+      words.extend(line.split_whitespace().map(ToOwned::to_owned));
+      if words.iter().any(|w| w == "macdef") {
+        in_macdef = true;
+        words.truncate(words.iter().position(|w| w == "macdef").unwrap());
+      }
+    }
+    let mut words = words.into_iter();
+    while let Some(word) = words.next() {
+      match word.as_str() {
+        "machine" | "default" => {
+          entries.push((machine.take(), login.take(), password.take()));
+          if word == "machine" {
+            machine = words.next();
+          }
+        }
+        "login" => login = words.next(),
+        "password" => password = words.next(),
+        "account" => {
+          words.next();
+        }
+        _ => {}
+      }
+    }
+    entries.push((machine, login, password));
+
+    let mut count = 0;
+    for entry in entries {
+      if let (Some(machine), username, Some(password)) = entry {
+        self.0.push(AuthToken {
+          host: AuthDomain::Host(machine.to_lowercase()),
+          token: AuthTokenData::Basic {
+            username: username.unwrap_or_default(),
+            password,
+          },
+          https_only: true,
+        });
+        count += 1;
+      }
+    }
+    debug!("Parsed {} netrc credential(s).", count);
+    self
+  }
+
   /// Attempt to match the provided specifier to the tokens in the set.  The
   /// matching occurs from the right of the hostname plus port, irrespective of
   /// scheme.  For example `https://www.deno.land:8080/` would match a token
@@ -159,7 +237,9 @@ impl AuthTokens {
   /// matching is case insensitive.
   pub fn get(&self, specifier: &ModuleSpecifier) -> Option<AuthToken> {
     self.0.iter().find_map(|t| {
-      if t.host.matches(specifier) {
+      if t.host.matches(specifier)
+        && (!t.https_only || specifier.scheme() == "https")
+      {
         Some(t.clone())
       } else {
         None
@@ -168,6 +248,99 @@ impl AuthTokens {
   }
 }
 
+/// The path of the netrc file to read credentials from, which is the value of
+/// the `DENO_NETRC` environment variable. The netrc file is only read when
+/// opted into, since it usually holds credentials for other tools as well.
+pub fn netrc_path() -> Option<PathBuf> {
+  std::env::var_os("DENO_NETRC")
+    .filter(|path| !path.is_empty())
+    .map(PathBuf::from)
+}
+
+/// A client certificate that is presented when connecting to the hosts that
+/// match its domain, for servers that require mutual TLS.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCertificate {
+  host: AuthDomain,
+  pub cert_path: PathBuf,
+  pub key_path: PathBuf,
+}
+
+impl ClientCertificate {
+  /// Read the PEM encoded certificate chain and private key.
+  pub fn load(&self) -> Result<TlsKey, AnyError> {
+    let cert_bytes = std::fs::read(&self.cert_path).with_context(|| {
+      format!(
+        "Failed reading client certificate '{}'",
+        self.cert_path.display()
+      )
+    })?;
+    let certs = load_certs(&mut BufReader::new(cert_bytes.as_slice()))?;
+    let key_bytes = std::fs::read(&self.key_path).with_context(|| {
+      format!("Failed reading client key '{}'", self.key_path.display())
+    })?;
+    let Some(key) = load_private_keys(&key_bytes)?.into_iter().next() else {
+      bail!("No private key found in '{}'", self.key_path.display());
+    };
+    Ok(TlsKey(certs, key))
+  }
+}
+
+/// The client certificates to present when fetching remote modules, per
+/// host.
+#[derive(Debug, Clone, Default)]
+pub struct ClientCertificates(Vec<ClientCertificate>);
+
+impl ClientCertificates {
+  /// Create a set of client certificates based on the provided string, which
+  /// is intended to be the value of the `DENO_CLIENT_CERTS` environment
+  /// variable. The string is expected to be a semi-colon separated string,
+  /// where each value is `{cert_path},{key_path}@{hostname}`.
+  pub fn new(maybe_certs_str: Option<String>) -> Self {
+    let mut certs = Vec::new();
+    if let Some(certs_str) = maybe_certs_str {
+      for cert_str in certs_str.split(';').filter(|s| !s.is_empty()) {
+        let parsed = cert_str.rsplit_once('@').and_then(|(paths, host)| {
+          let (cert_path, key_path) = paths.split_once(',')?;
+          Some(ClientCertificate {
+            host: AuthDomain::from(host),
+            cert_path: PathBuf::from(cert_path),
+            key_path: PathBuf::from(key_path),
+          })
+        });
+        match parsed {
+          Some(cert) => certs.push(cert),
+          None => error!("Badly formed client certificate discarded."),
+        }
+      }
+      debug!("Parsed {} client certificate(s).", certs.len());
+    }
+
+    Self(certs)
+  }
+
+  /// Find the client certificate for the provided specifier, along with its
+  /// index in the set.
+  pub fn get(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Option<(usize, &ClientCertificate)> {
+    self
+      .0
+      .iter()
+      .enumerate()
+      .find(|(_, cert)| cert.host.matches(specifier))
+  }
+
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -332,4 +505,77 @@ mod tests {
       assert_eq!(actual, expected);
     }
   }
+
+  #[test]
+  fn test_auth_tokens_netrc() {
+    let netrc = r#"
+machine deno.land
+  login user
+  password secret
+
+machine Example.com login other password pass123 account acc
+
+macdef init
+  cd /pub
+  machine not-a-machine.com login a password b
+
+default login anonymous password guest
+"#;
+    let auth_tokens = AuthTokens::new(None).with_netrc(netrc);
+    let fixture = resolve_url("https://deno.land/x/mod.ts").unwrap();
+    assert_eq!(
+      auth_tokens.get(&fixture).unwrap().to_string(),
+      "Basic dXNlcjpzZWNyZXQ="
+    );
+    // exact hostname matches, irrespective of the port
+    let fixture = resolve_url("https://example.com:8080/mod.ts").unwrap();
+    assert_eq!(
+      auth_tokens.get(&fixture).unwrap().token,
+      AuthTokenData::Basic {
+        username: "other".to_string(),
+        password: "pass123".to_string(),
+      }
+    );
+    let fixture = resolve_url("https://www.deno.land/x/mod.ts").unwrap();
+    assert_eq!(auth_tokens.get(&fixture), None);
+    // the credentials are never sent over plain http
+    let fixture = resolve_url("http://deno.land/x/mod.ts").unwrap();
+    assert_eq!(auth_tokens.get(&fixture), None);
+    // macro definitions and the default entry are ignored
+    let fixture = resolve_url("https://not-a-machine.com/mod.ts").unwrap();
+    assert_eq!(auth_tokens.get(&fixture), None);
+    let fixture = resolve_url("https://other.com/mod.ts").unwrap();
+    assert_eq!(auth_tokens.get(&fixture), None);
+  }
+
+  #[test]
+  fn test_auth_tokens_take_precedence_over_netrc() {
+    let auth_tokens = AuthTokens::new(Some("abc123@deno.land".to_string()))
+      .with_netrc("machine deno.land login user password secret");
+    let fixture = resolve_url("https://deno.land/x/mod.ts").unwrap();
+    assert_eq!(
+      auth_tokens.get(&fixture).unwrap().to_string(),
+      "Bearer abc123"
+    );
+  }
+
+  #[test]
+  fn test_client_certificates() {
+    let client_certs = ClientCertificates::new(Some(
+      "cert.pem,key.pem@deno.land;invalid@example.com;a.pem,b.pem@localhost:8443"
+        .to_string(),
+    ));
+    assert_eq!(client_certs.len(), 2);
+    let fixture = resolve_url("https://www.deno.land/x/mod.ts").unwrap();
+    let (index, cert) = client_certs.get(&fixture).unwrap();
+    assert_eq!(index, 0);
+    assert_eq!(cert.cert_path, PathBuf::from("cert.pem"));
+    assert_eq!(cert.key_path, PathBuf::from("key.pem"));
+    let fixture = resolve_url("https://localhost:8443/mod.ts").unwrap();
+    assert_eq!(client_certs.get(&fixture).unwrap().0, 1);
+    let fixture = resolve_url("https://localhost/mod.ts").unwrap();
+    assert!(client_certs.get(&fixture).is_none());
+    let fixture = resolve_url("https://example.com/mod.ts").unwrap();
+    assert!(client_certs.get(&fixture).is_none());
+  }
 }
@@ -132,6 +132,24 @@ impl DenoDir {
     self.root.join("location_data")
   }
 
+  /// Path to the folder of the sockets used to relay `BroadcastChannel`
+  /// messages between processes.
+  pub fn broadcast_channel_folder_path(&self) -> PathBuf {
+    self.root.join("broadcast_channel")
+  }
+
+  /// File of the permissions granted to projects with the "always allow"
+  /// option of permission prompts.
+  pub fn permission_grants_file_path(&self) -> PathBuf {
+    self.root.join("permission_grants.json")
+  }
+
+  /// File of the lockfiles of the projects that use this DENO_DIR, which
+  /// `deno cache --prune` keeps the entries of.
+  pub fn registered_projects_file_path(&self) -> PathBuf {
+    self.root.join("registered_projects.json")
+  }
+
   /// File used for the upgrade checker.
   pub fn upgrade_check_file_path(&self) -> PathBuf {
     self.root.join("latest.txt")
@@ -170,7 +188,7 @@ impl DenoDir {
 
 /// To avoid the poorly managed dirs crate
 #[cfg(not(windows))]
-mod dirs {
+pub mod dirs {
   use std::path::PathBuf;
 
   pub fn cache_dir() -> Option<PathBuf> {
@@ -228,7 +246,7 @@ mod dirs {
 // https://github.com/dirs-dev/dirs-sys-rs/blob/ec7cee0b3e8685573d847f0a0f60aae3d9e07fa2/src/lib.rs#L140-L164
 // MIT license. Copyright (c) 2018-2019 dirs-rs contributors
 #[cfg(windows)]
-mod dirs {
+pub mod dirs {
   use std::ffi::OsString;
   use std::os::windows::ffi::OsStringExt;
   use std::path::PathBuf;
@@ -1,6 +1,8 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::args::check_frozen_remote;
 use crate::args::CacheSetting;
+use crate::args::Lockfile;
 use crate::errors::get_error_class_name;
 use crate::file_fetcher::FetchNoFollowOptions;
 use crate::file_fetcher::FetchOptions;
@@ -12,6 +14,7 @@ use crate::util::fs::atomic_write_file;
 use deno_ast::MediaType;
 use deno_core::futures;
 use deno_core::futures::FutureExt;
+use deno_core::parking_lot::Mutex;
 use deno_core::ModuleSpecifier;
 use deno_graph::source::CacheInfo;
 use deno_graph::source::LoadFuture;
@@ -37,11 +40,13 @@ mod incremental;
 mod module_info;
 mod node;
 mod parsed_source;
+mod registered_projects;
 
 pub use caches::Caches;
 pub use check::TypeCheckCache;
 pub use code_cache::CodeCache;
 pub use common::FastInsecureHasher;
+pub use deno_dir::dirs;
 pub use deno_dir::DenoDir;
 pub use deno_dir::DenoDirProvider;
 pub use disk_cache::DiskCache;
@@ -52,6 +57,7 @@ pub use module_info::ModuleInfoCache;
 pub use node::NodeAnalysisCache;
 pub use parsed_source::LazyGraphSourceParser;
 pub use parsed_source::ParsedSourceCache;
+pub use registered_projects::RegisteredProjects;
 
 /// Permissions used to save a file in the disk caches.
 pub const CACHE_PERM: u32 = 0o644;
@@ -114,6 +120,7 @@ pub struct FetchCacher {
   module_info_cache: Arc<ModuleInfoCache>,
   permissions: PermissionsContainer,
   cache_info_enabled: bool,
+  maybe_frozen_lockfile: Option<Arc<Mutex<Lockfile>>>,
 }
 
 impl FetchCacher {
@@ -135,6 +142,7 @@ impl FetchCacher {
       module_info_cache,
       permissions,
       cache_info_enabled: false,
+      maybe_frozen_lockfile: None,
     }
   }
 
@@ -144,6 +152,12 @@ impl FetchCacher {
     self.cache_info_enabled = true;
   }
 
+  /// Error on loading remote modules that aren't in the lockfile, which is
+  /// used with `--frozen-lockfile`.
+  pub fn set_frozen_lockfile(&mut self, lockfile: Arc<Mutex<Lockfile>>) {
+    self.maybe_frozen_lockfile = Some(lockfile);
+  }
+
   // DEPRECATED: Where the file is stored and how it's stored should be an implementation
   // detail of the cache.
   //
@@ -217,6 +231,12 @@ impl Loader for FetchCacher {
       }
     }
 
+    if let Some(lockfile) = &self.maybe_frozen_lockfile {
+      if let Err(err) = check_frozen_remote(&lockfile.lock(), specifier) {
+        return Box::pin(futures::future::ready(Err(err)));
+      }
+    }
+
     let file_fetcher = self.file_fetcher.clone();
     let file_header_overrides = self.file_header_overrides.clone();
     let permissions = self.permissions.clone();
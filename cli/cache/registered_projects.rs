@@ -0,0 +1,101 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeSet;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+
+use crate::util::fs::atomic_write_file;
+
+/// The lockfiles of the projects that use a DENO_DIR. Since the DENO_DIR is
+/// shared by every project, `deno cache --prune` keeps everything referenced
+/// by any of them rather than only what the current project references.
+#[derive(Debug, Clone)]
+pub struct RegisteredProjects {
+  file_path: PathBuf,
+}
+
+impl RegisteredProjects {
+  pub fn new(file_path: PathBuf) -> Self {
+    Self { file_path }
+  }
+
+  /// Add a lockfile to the registered projects if it isn't already.
+  pub fn register(&self, lockfile_path: &Path) -> Result<(), AnyError> {
+    let mut lockfiles = self.lockfiles()?;
+    if lockfiles.insert(lockfile_path.to_path_buf()) {
+      self.write(&lockfiles)?;
+    }
+    Ok(())
+  }
+
+  /// The registered lockfiles. Lockfiles that no longer exist are removed
+  /// from the registered projects.
+  pub fn existing_lockfiles(&self) -> Result<Vec<PathBuf>, AnyError> {
+    let lockfiles = self.lockfiles()?;
+    let existing = lockfiles
+      .iter()
+      .filter(|path| path.is_file())
+      .cloned()
+      .collect::<BTreeSet<_>>();
+    if existing.len() != lockfiles.len() {
+      self.write(&existing)?;
+    }
+    Ok(existing.into_iter().collect())
+  }
+
+  fn lockfiles(&self) -> Result<BTreeSet<PathBuf>, AnyError> {
+    let text = match std::fs::read_to_string(&self.file_path) {
+      Ok(text) => text,
+      Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+        return Ok(Default::default())
+      }
+      Err(err) => {
+        return Err(err).with_context(|| {
+          format!("Failed reading {}", self.file_path.display())
+        })
+      }
+    };
+    serde_json::from_str(&text)
+      .with_context(|| format!("Failed parsing {}", self.file_path.display()))
+  }
+
+  fn write(&self, lockfiles: &BTreeSet<PathBuf>) -> Result<(), AnyError> {
+    let text = serde_json::to_string_pretty(lockfiles)?;
+    atomic_write_file(&self.file_path, text, 0o644)
+      .with_context(|| format!("Failed writing {}", self.file_path.display()))
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use test_util::TempDir;
+
+  use super::*;
+
+  #[test]
+  fn test_registered_projects() {
+    let temp_dir = TempDir::new();
+    let projects = RegisteredProjects::new(
+      temp_dir.path().join("projects.json").to_path_buf(),
+    );
+    assert!(projects.existing_lockfiles().unwrap().is_empty());
+
+    let lockfile_a = temp_dir.path().join("a/deno.lock");
+    let lockfile_b = temp_dir.path().join("b/deno.lock");
+    lockfile_a.parent().create_dir_all();
+    lockfile_a.write("{}");
+    projects.register(lockfile_a.as_path()).unwrap();
+    projects.register(lockfile_a.as_path()).unwrap();
+    projects.register(lockfile_b.as_path()).unwrap();
+    assert_eq!(
+      projects.existing_lockfiles().unwrap(),
+      vec![lockfile_a.to_path_buf()]
+    );
+    // the missing lockfile was removed
+    assert_eq!(projects.lockfiles().unwrap().len(), 1);
+  }
+}
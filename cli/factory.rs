@@ -19,6 +19,7 @@ use crate::cache::LocalHttpCache;
 use crate::cache::ModuleInfoCache;
 use crate::cache::NodeAnalysisCache;
 use crate::cache::ParsedSourceCache;
+use crate::cache::RegisteredProjects;
 use crate::emit::Emitter;
 use crate::file_fetcher::FileFetcher;
 use crate::graph_util::FileWatcherReporter;
@@ -51,6 +52,7 @@ use crate::util::file_watcher::WatcherCommunicator;
 use crate::util::fs::canonicalize_path_maybe_not_exists;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::progress_bar::ProgressBarStyle;
+use crate::util::watched_fs::WatchedFileSystem;
 use crate::worker::CliMainWorkerFactory;
 use crate::worker::CliMainWorkerOptions;
 use std::path::PathBuf;
@@ -394,6 +396,14 @@ impl CliFactory {
             config,
           },
         );
+        // remember the project so that pruning the cache keeps its entries
+        if let Ok(deno_dir) = self.deno_dir() {
+          let registered_projects =
+            RegisteredProjects::new(deno_dir.registered_projects_file_path());
+          if let Err(err) = registered_projects.register(&lockfile.filename) {
+            log::debug!("Failed registering the project's lockfile: {:#}", err);
+          }
+        }
       }
 
       maybe_lockfile
@@ -781,6 +791,15 @@ impl CliFactory {
     } else {
       None
     };
+    // files read by the program restart it when they change, except with
+    // HMR which only replaces modules
+    let runtime_fs: Arc<dyn deno_fs::FileSystem> =
+      match &self.watcher_communicator {
+        Some(watcher_communicator) if !self.options.has_hmr() => Arc::new(
+          WatchedFileSystem::new(fs.clone(), watcher_communicator.clone()),
+        ),
+        _ => fs.clone(),
+      };
 
     Ok(CliMainWorkerFactory::new(
       StorageKeyResolver::from_options(&self.options),
@@ -810,7 +829,7 @@ impl CliFactory {
         self.module_info_cache()?.clone(),
       )),
       self.root_cert_store_provider().clone(),
-      self.fs().clone(),
+      runtime_fs,
       maybe_file_watcher_communicator,
       self.maybe_inspector_server()?.clone(),
       self.maybe_lockfile().clone(),
@@ -887,15 +906,26 @@ impl CliFactory {
         .or(std::env::args().next()),
       node_debug: std::env::var("NODE_DEBUG").ok(),
       origin_data_folder_path: Some(self.deno_dir()?.origin_data_folder_path()),
+      broadcast_channel_folder_path: Some(
+        self.deno_dir()?.broadcast_channel_folder_path(),
+      ),
+      local_storage_quota: self.options.local_storage_quota(),
       seed: self.options.seed(),
       unsafely_ignore_certificate_errors: self
         .options
         .unsafely_ignore_certificate_errors()
         .clone(),
+      http1_only: self.options.http1_only(),
       unstable: self.options.legacy_unstable_flag(),
       maybe_root_package_json_deps: self.options.maybe_package_json_deps(),
       create_hmr_runner,
       create_coverage_collector,
+      fs_fetch_read_file: None,
+      env_var_overrides: self
+        .watcher_communicator
+        .as_ref()
+        .map(|communicator| communicator.env_var_overrides().clone()),
+      permission_policy: self.options.permission_policy()?,
     })
   }
 }
@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use crate::args::CacheSetting;
+use crate::auth_tokens;
 use crate::auth_tokens::AuthToken;
 use crate::auth_tokens::AuthTokens;
 use crate::cache::HttpCache;
@@ -176,6 +177,9 @@ pub struct FileFetcher {
   blob_store: Arc<BlobStore>,
   download_log_level: log::Level,
   progress_bar: Option<ProgressBar>,
+  /// Remote modules that were served from the cache while stale because
+  /// `--cached-only` is specified.
+  stale_specifiers: Arc<Mutex<Vec<ModuleSpecifier>>>,
 }
 
 impl FileFetcher {
@@ -188,7 +192,7 @@ impl FileFetcher {
     progress_bar: Option<ProgressBar>,
   ) -> Self {
     Self {
-      auth_tokens: AuthTokens::new(env::var("DENO_AUTH_TOKENS").ok()),
+      auth_tokens: resolve_auth_tokens(),
       allow_remote,
       memory_files: Default::default(),
       cache_setting,
@@ -197,6 +201,7 @@ impl FileFetcher {
       blob_store,
       download_log_level: log::Level::Info,
       progress_bar,
+      stale_specifiers: Default::default(),
     }
   }
 
@@ -330,6 +335,13 @@ impl FileFetcher {
       if let Some(file_or_redirect) =
         self.fetch_cached_no_follow(specifier, maybe_checksum)?
       {
+        if self.cache_setting == CacheSetting::Only && self.is_stale(specifier)
+        {
+          let mut stale_specifiers = self.stale_specifiers.lock();
+          if !stale_specifiers.contains(specifier) {
+            stale_specifiers.push(specifier.clone());
+          }
+        }
         return Ok(file_or_redirect);
       }
     }
@@ -453,6 +465,76 @@ impl FileFetcher {
     result
   }
 
+  /// Returns whether the cached version of a remote specifier should be
+  /// refreshed according to its cache headers.
+  pub fn is_stale(&self, specifier: &ModuleSpecifier) -> bool {
+    !self.should_use_cache(specifier, &CacheSetting::RespectHeaders)
+  }
+
+  /// Takes the remote specifiers that were served from the cache while stale
+  /// since the last call.
+  pub fn take_stale_specifiers(&self) -> Vec<ModuleSpecifier> {
+    std::mem::take(&mut *self.stale_specifiers.lock())
+  }
+
+  /// Refreshes the cached version of a stale remote specifier without
+  /// following redirects or reporting progress. The refreshed version is
+  /// only picked up by subsequent fetches.
+  pub async fn revalidate(
+    &self,
+    specifier: &ModuleSpecifier,
+  ) -> Result<(), AnyError> {
+    debug!("FileFetcher::revalidate - specifier: {}", specifier);
+    if !self.allow_remote || !self.is_stale(specifier) {
+      return Ok(());
+    }
+    let cache_key = self.http_cache.cache_item_key(specifier)?;
+    let maybe_headers = self.http_cache.read_headers(&cache_key)?;
+    let maybe_etag = maybe_headers
+      .as_ref()
+      .and_then(|headers| headers.get("etag").cloned());
+    let result = fetch_no_follow(
+      &self.http_client,
+      FetchOnceArgs {
+        url: specifier.clone(),
+        maybe_accept: None,
+        maybe_etag,
+        maybe_auth_token: self.auth_tokens.get(specifier),
+        maybe_progress_guard: None,
+      },
+    )
+    .await?;
+    match result {
+      FetchOnceResult::NotModified => {
+        // store the body again so the download time is updated
+        let (Some(headers), Some(bytes)) = (
+          maybe_headers,
+          self.http_cache.read_file_bytes(
+            &cache_key,
+            None,
+            deno_cache_dir::GlobalToLocalCopy::Disallow,
+          )?,
+        ) else {
+          return Ok(());
+        };
+        self.http_cache.set(specifier, headers, &bytes)?;
+      }
+      FetchOnceResult::Redirect(_, headers) => {
+        self.http_cache.set(specifier, headers, &[])?;
+      }
+      FetchOnceResult::Code(bytes, headers) => {
+        self.http_cache.set(specifier, headers, &bytes)?;
+      }
+      FetchOnceResult::RequestError(err) => {
+        bail!("Revalidating '{}' failed: {}", specifier, err);
+      }
+      FetchOnceResult::ServerError(status) => {
+        bail!("Revalidating '{}' failed: {}", specifier, status);
+      }
+    }
+    Ok(())
+  }
+
   /// Returns if the cache should be used for a given specifier.
   fn should_use_cache(
     &self,
@@ -626,6 +708,22 @@ impl FileFetcher {
   }
 }
 
+/// The tokens of `DENO_AUTH_TOKENS`, followed by the credentials of the netrc
+/// file when one is provided with `DENO_NETRC`.
+fn resolve_auth_tokens() -> AuthTokens {
+  let auth_tokens = AuthTokens::new(env::var("DENO_AUTH_TOKENS").ok());
+  let Some(netrc_path) = auth_tokens::netrc_path() else {
+    return auth_tokens;
+  };
+  match std::fs::read_to_string(&netrc_path) {
+    Ok(netrc) => auth_tokens.with_netrc(&netrc),
+    Err(err) => {
+      debug!("Not reading {}: {:#}", netrc_path.display(), err);
+      auth_tokens
+    }
+  }
+}
+
 #[derive(Debug, Eq, PartialEq)]
 enum FetchOnceResult {
   Code(Vec<u8>, HeadersMap),
@@ -659,7 +757,7 @@ async fn fetch_no_follow<'a>(
     let if_none_match_val = HeaderValue::from_str(&etag)?;
     request = request.header(IF_NONE_MATCH, if_none_match_val);
   }
-  if let Some(auth_token) = args.maybe_auth_token {
+  if let Some(auth_token) = &args.maybe_auth_token {
     let authorization_val = HeaderValue::from_str(&auth_token.to_string())?;
     request = request.header(AUTHORIZATION, authorization_val);
   }
@@ -720,6 +818,19 @@ async fn fetch_no_follow<'a>(
         "NotFound",
         format!("Import '{}' failed, not found.", args.url),
       )
+    } else if status == StatusCode::UNAUTHORIZED
+      || status == StatusCode::FORBIDDEN
+    {
+      let host = args.url.host_str().unwrap_or_default();
+      let hint = if args.maybe_auth_token.is_some() {
+        format!("The credentials for \"{host}\" were rejected, check the matching entry of DENO_AUTH_TOKENS or of the netrc file.")
+      } else {
+        format!("No credentials were found for \"{host}\", add them to DENO_AUTH_TOKENS or to the netrc file.")
+      };
+      generic_error(format!(
+        "Import '{}' failed: {}\n{}",
+        args.url, status, hint
+      ))
     } else {
       generic_error(format!(
         "Import '{}' failed: {}",
@@ -1543,6 +1654,57 @@ mod tests {
     assert_eq!(first, second);
   }
 
+  #[tokio::test]
+  async fn test_cached_only_records_stale() {
+    let _g = test_util::http_server();
+    let temp_dir = TempDir::new();
+    let (file_fetcher, _) = setup(CacheSetting::Use, Some(temp_dir.clone()));
+    let stale =
+      ModuleSpecifier::parse("http://localhost:4545/dynamic").unwrap();
+    let fresh =
+      ModuleSpecifier::parse("http://localhost:4545/dynamic_cache").unwrap();
+    for specifier in [&stale, &fresh] {
+      let result = file_fetcher
+        .fetch(specifier, &PermissionsContainer::allow_all())
+        .await;
+      assert!(result.is_ok());
+    }
+    assert!(file_fetcher.take_stale_specifiers().is_empty());
+
+    let (file_fetcher, _) = setup(CacheSetting::Only, Some(temp_dir.clone()));
+    for specifier in [&stale, &fresh, &stale] {
+      let result = file_fetcher
+        .fetch(specifier, &PermissionsContainer::allow_all())
+        .await;
+      assert!(result.is_ok());
+    }
+    assert_eq!(file_fetcher.take_stale_specifiers(), vec![stale]);
+    assert!(file_fetcher.take_stale_specifiers().is_empty());
+  }
+
+  #[tokio::test]
+  async fn test_revalidate() {
+    let _g = test_util::http_server();
+    let temp_dir = TempDir::new();
+    let (file_fetcher, _) = setup(CacheSetting::Use, Some(temp_dir.clone()));
+    let specifier =
+      ModuleSpecifier::parse("http://localhost:4545/dynamic").unwrap();
+    let first = file_fetcher
+      .fetch(&specifier, &PermissionsContainer::allow_all())
+      .await
+      .unwrap()
+      .source;
+    assert!(file_fetcher.is_stale(&specifier));
+
+    file_fetcher.revalidate(&specifier).await.unwrap();
+    let second = file_fetcher
+      .fetch(&specifier, &PermissionsContainer::allow_all())
+      .await
+      .unwrap()
+      .source;
+    assert_ne!(first, second);
+  }
+
   #[tokio::test]
   async fn test_fetch_local_utf_16be() {
     let expected =
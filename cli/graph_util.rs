@@ -318,7 +318,14 @@ impl ModuleGraphCreator {
         loader: None,
       })
       .await?;
+    self.validate_and_maybe_check_graph(graph).await
+  }
 
+  /// Validates the graph and type checks it when type checking is enabled.
+  pub async fn validate_and_maybe_check_graph(
+    &self,
+    graph: ModuleGraph,
+  ) -> Result<Arc<deno_graph::ModuleGraph>, AnyError> {
     self.graph_valid(&graph)?;
     if let Some(lockfile) = &self.lockfile {
       graph_lock_or_exit(&graph, &mut lockfile.lock());
@@ -337,6 +344,36 @@ impl ModuleGraphCreator {
     self.module_graph_builder.graph_valid(graph)
   }
 
+  pub fn graph_roots_valid(
+    &self,
+    graph: &ModuleGraph,
+    roots: &[ModuleSpecifier],
+  ) -> Result<(), AnyError> {
+    self.module_graph_builder.graph_roots_valid(graph, roots)
+  }
+
+  /// Adds `roots` to an existing graph, e.g. for entrypoints that are found
+  /// while analyzing the graph.
+  pub async fn add_graph_roots(
+    &self,
+    graph: &mut ModuleGraph,
+    graph_kind: GraphKind,
+    roots: Vec<ModuleSpecifier>,
+  ) -> Result<(), AnyError> {
+    self
+      .module_graph_builder
+      .build_graph_with_npm_resolution(
+        graph,
+        CreateGraphOptions {
+          is_dynamic: false,
+          graph_kind,
+          roots,
+          loader: None,
+        },
+      )
+      .await
+  }
+
   async fn type_check_graph(
     &self,
     graph: ModuleGraph,
@@ -464,7 +501,49 @@ impl ModuleGraphBuilder {
           resolver: Some(graph_resolver),
         },
       )
-      .await
+      .await?;
+    self.warn_stale_modules();
+    Ok(())
+  }
+
+  fn warn_stale_modules(&self) {
+    let stale_specifiers = self.file_fetcher.take_stale_specifiers();
+    if stale_specifiers.is_empty() {
+      return;
+    }
+    let mut message = format!(
+      "{} Using {} stale cached module{} because --cached-only is specified:",
+      colors::yellow("Warning"),
+      stale_specifiers.len(),
+      if stale_specifiers.len() == 1 { "" } else { "s" },
+    );
+    for specifier in &stale_specifiers {
+      message.push_str(&format!("\n  - {}", specifier));
+    }
+    log::warn!("{}", message);
+  }
+
+  /// Refreshes the stale remote modules of the graph in a background task so
+  /// that startup isn't blocked on the network.
+  pub fn revalidate_in_background(&self, graph: &ModuleGraph) {
+    let specifiers = graph
+      .modules()
+      .map(|module| module.specifier())
+      .filter(|specifier| matches!(specifier.scheme(), "http" | "https"))
+      .filter(|specifier| self.file_fetcher.is_stale(specifier))
+      .cloned()
+      .collect::<Vec<_>>();
+    if specifiers.is_empty() {
+      return;
+    }
+    let file_fetcher = self.file_fetcher.clone();
+    deno_core::unsync::spawn(async move {
+      for specifier in specifiers {
+        if let Err(err) = file_fetcher.revalidate(&specifier).await {
+          log::debug!("{:#}", err);
+        }
+      }
+    });
   }
 
   async fn build_graph_with_npm_resolution_and_build_options<'a>(
@@ -636,7 +715,7 @@ impl ModuleGraphBuilder {
     &self,
     permissions: PermissionsContainer,
   ) -> cache::FetchCacher {
-    cache::FetchCacher::new(
+    let mut cache = cache::FetchCacher::new(
       self.emit_cache.clone(),
       self.file_fetcher.clone(),
       self.options.resolve_file_header_overrides(),
@@ -644,7 +723,13 @@ impl ModuleGraphBuilder {
       self.npm_resolver.clone(),
       self.module_info_cache.clone(),
       permissions,
-    )
+    );
+    if self.options.frozen_lockfile() {
+      if let Some(lockfile) = &self.lockfile {
+        cache.set_frozen_lockfile(lockfile.clone());
+      }
+    }
+    cache
   }
 
   /// Check if `roots` and their deps are available. Returns `Ok(())` if
@@ -1,4 +1,5 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+use crate::auth_tokens::ClientCertificates;
 use crate::util::progress_bar::UpdateGuard;
 use crate::version::get_user_agent;
 
@@ -17,6 +18,7 @@ use deno_runtime::deno_fetch::reqwest::header::LOCATION;
 use deno_runtime::deno_fetch::reqwest::Response;
 use deno_runtime::deno_fetch::CreateHttpClientOptions;
 use deno_runtime::deno_tls::RootCertStoreProvider;
+use deno_runtime::deno_tls::TlsKey;
 use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
@@ -223,6 +225,9 @@ pub struct HttpClient {
   options: CreateHttpClientOptions,
   root_cert_store_provider: Option<Arc<dyn RootCertStoreProvider>>,
   cell: once_cell::sync::OnceCell<reqwest::Client>,
+  client_certificates: ClientCertificates,
+  /// Clients presenting each of the `client_certificates`.
+  client_certificate_cells: Vec<once_cell::sync::OnceCell<reqwest::Client>>,
 }
 
 impl std::fmt::Debug for HttpClient {
@@ -238,6 +243,8 @@ impl HttpClient {
     root_cert_store_provider: Option<Arc<dyn RootCertStoreProvider>>,
     unsafely_ignore_certificate_errors: Option<Vec<String>>,
   ) -> Self {
+    let client_certificates =
+      ClientCertificates::new(std::env::var("DENO_CLIENT_CERTS").ok());
     Self {
       options: CreateHttpClientOptions {
         unsafely_ignore_certificate_errors,
@@ -245,6 +252,10 @@ impl HttpClient {
       },
       root_cert_store_provider,
       cell: Default::default(),
+      client_certificate_cells: (0..client_certificates.len())
+        .map(|_| Default::default())
+        .collect(),
+      client_certificates,
     }
   }
 
@@ -254,24 +265,48 @@ impl HttpClient {
       options: Default::default(),
       root_cert_store_provider: Default::default(),
       cell: Default::default(),
+      client_certificates: Default::default(),
+      client_certificate_cells: Default::default(),
     };
     result.cell.set(client).unwrap();
     result
   }
 
   pub(crate) fn client(&self) -> Result<&reqwest::Client, AnyError> {
-    self.cell.get_or_try_init(|| {
-      create_http_client(
-        get_user_agent(),
-        CreateHttpClientOptions {
-          root_cert_store: match &self.root_cert_store_provider {
-            Some(provider) => Some(provider.get_or_try_init()?.clone()),
-            None => None,
-          },
-          ..self.options.clone()
+    self.cell.get_or_try_init(|| self.create_client(None))
+  }
+
+  /// The client to use for a request to the provided url, which presents the
+  /// client certificate of the url's host from `DENO_CLIENT_CERTS`, if any.
+  ///
+  /// The client is chosen for each request, so a certificate is never
+  /// presented to another host after a redirect.
+  fn client_for_url(&self, url: &Url) -> Result<&reqwest::Client, AnyError> {
+    match self.client_certificates.get(url) {
+      Some((index, client_certificate)) => self.client_certificate_cells[index]
+        .get_or_try_init(|| {
+          let key = client_certificate.load()?;
+          self.create_client(Some(key))
+        }),
+      None => self.client(),
+    }
+  }
+
+  fn create_client(
+    &self,
+    client_cert_chain_and_key: Option<TlsKey>,
+  ) -> Result<reqwest::Client, AnyError> {
+    create_http_client(
+      get_user_agent(),
+      CreateHttpClientOptions {
+        root_cert_store: match &self.root_cert_store_provider {
+          Some(provider) => Some(provider.get_or_try_init()?.clone()),
+          None => None,
         },
-      )
-    })
+        client_cert_chain_and_key,
+        ..self.options.clone()
+      },
+    )
   }
 
   /// Do a GET request without following redirects.
@@ -279,7 +314,8 @@ impl HttpClient {
     &self,
     url: U,
   ) -> Result<reqwest::RequestBuilder, AnyError> {
-    Ok(self.client()?.get(url))
+    let url = url.into_url()?;
+    Ok(self.client_for_url(&url)?.get(url))
   }
 
   pub async fn download_text<U: reqwest::IntoUrl>(
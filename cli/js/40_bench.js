@@ -19,6 +19,7 @@ const {
   ArrayPrototypePush,
   Error,
   MathCeil,
+  MathSqrt,
   SymbolToStringTag,
   TypeError,
 } = primordials;
@@ -200,6 +201,15 @@ function benchStats(
   max,
   all,
 ) {
+  // The relative margin of error of the mean at a 95% confidence level, using
+  // the normal approximation since there are always at least 10 samples.
+  const mean = avg / n;
+  let variance = 0;
+  for (let i = 0; i < n; i++) {
+    variance += (all[i] - mean) ** 2;
+  }
+  variance /= n > 1 ? n - 1 : 1;
+  const rme = mean > 0 ? (1.96 * MathSqrt(variance / n)) / mean * 100 : 0;
   return {
     n,
     min,
@@ -209,6 +219,7 @@ function benchStats(
     p995: all[MathCeil(n * (99.5 / 100)) - 1],
     p999: all[MathCeil(n * (99.9 / 100)) - 1],
     avg: !highPrecision ? (avg / n) : MathCeil(avg / n),
+    rme,
     highPrecision,
     usedExplicitTimers,
   };
@@ -1,27 +1,33 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
-import { core, primordials } from "ext:core/mod.js";
+import { core, internals, primordials } from "ext:core/mod.js";
 import { escapeName, withPermissions } from "ext:cli/40_test_common.js";
+import { inspect } from "ext:deno_console/01_console.js";
 
 // TODO(mmastrac): We cannot import these from "ext:core/ops" yet
 const {
   op_register_test_step,
   op_register_test,
+  op_test_assert_snapshot,
   op_test_event_step_result_failed,
   op_test_event_step_result_ignored,
   op_test_event_step_result_ok,
   op_test_event_step_wait,
   op_test_get_origin,
+  op_test_trace_resource_creation,
 } = core.ops;
 const {
   ArrayPrototypeFilter,
   ArrayPrototypePush,
   DateNow,
   Error,
+  ErrorCaptureStackTrace,
   Map,
   MapPrototypeGet,
   MapPrototypeSet,
   SafeArrayIterator,
+  StringPrototypeReplaceAll,
+  StringPrototypeSlice,
   SymbolToStringTag,
   TypeError,
 } = primordials;
@@ -40,6 +46,8 @@ import { setExitHandler } from "ext:runtime/30_os.js";
  *   sanitizeOps: boolean,
  *   sanitizeResources: boolean,
  *   sanitizeExit: boolean,
+ *   retries: number | null,
+ *   timeout: number | null,
  *   permissions: PermissionOptions,
  * }} TestDescription
  *
@@ -110,6 +118,11 @@ function assertExit(fn, isTest) {
 
 function wrapOuter(fn, desc) {
   return async function outerWrapped() {
+    // Start from a clean state, the test may be run again if it's retried.
+    const testState = MapPrototypeGet(testStates, desc.id);
+    testState.children = [];
+    testState.completed = false;
+    testState.snapshotCount = 0;
     try {
       if (desc.ignore) {
         return "ignored";
@@ -210,6 +223,8 @@ function testInner(
     sanitizeOps: true,
     sanitizeResources: true,
     sanitizeExit: true,
+    retries: null,
+    timeout: null,
     permissions: null,
   };
 
@@ -303,6 +318,8 @@ function testInner(
     testDesc.only,
     testDesc.sanitizeOps,
     testDesc.sanitizeResources,
+    testDesc.retries,
+    testDesc.timeout,
     testDesc.location.fileName,
     testDesc.location.lineNumber,
     testDesc.location.columnNumber,
@@ -314,6 +331,7 @@ function testInner(
     context: createTestContext(testDesc),
     children: [],
     completed: false,
+    snapshotCount: 0,
   });
 }
 
@@ -463,6 +481,7 @@ function createTestContext(desc) {
         children: [],
         failed: false,
         completed: false,
+        snapshotCount: 0,
       };
       MapPrototypeSet(testStates, stepDesc.id, state);
       ArrayPrototypePush(
@@ -478,9 +497,45 @@ function createTestContext(desc) {
       stepReportResult(stepDesc, result, elapsed);
       return result == "ok";
     },
+    /**
+     * @param actual {unknown}
+     * @param options {{ serializer?: (value: unknown) => string } | undefined}
+     */
+    assertSnapshot(actual, options = {}) {
+      const state = MapPrototypeGet(testStates, desc.id);
+      const name = `${getFullName(desc)} ${++state.snapshotCount}`;
+      const serializer = options.serializer ?? serializeSnapshot;
+      const message = op_test_assert_snapshot(name, serializer(actual));
+      if (message !== null) {
+        const error = new Error(message);
+        error.name = "AssertionError";
+        throw error;
+      }
+    },
   };
 }
 
+/**
+ * The default serializer of `TestContext.assertSnapshot()`, produces a stable
+ * and complete representation of the value.
+ * @param value {unknown}
+ * @returns {string}
+ */
+function serializeSnapshot(value) {
+  const serialized = inspect(value, {
+    depth: Infinity,
+    sorted: true,
+    trailingComma: true,
+    compact: false,
+    iterableLimit: Infinity,
+    strAbbreviateSize: Infinity,
+    breakLength: Infinity,
+    escapeSequences: false,
+    colors: false,
+  });
+  return StringPrototypeReplaceAll(serialized, "\r", "\\r");
+}
+
 /**
  * Wrap a user test function in one which returns a structured result.
  * @template T {Function}
@@ -499,4 +554,23 @@ function wrapTest(desc) {
   return wrapOuter(testFn, desc);
 }
 
+/**
+ * Records the stack that created a resource, so that the resource sanitizer
+ * can point at the call that leaked it. Only called with `--trace-leaks`.
+ *
+ * The stack is captured like the traces of the op sanitizer, which are
+ * source mapped when formatted by the runtime.
+ * @param {number} rid
+ */
+function traceResourceCreation(rid) {
+  const error = new Error();
+  ErrorCaptureStackTrace(error, traceResourceCreation);
+  // "Error\n".length == 6
+  op_test_trace_resource_creation(rid, StringPrototypeSlice(error.stack, 6));
+}
+
+internals.enableResourceCreationTracing = () => {
+  internals.traceResourceCreation = traceResourceCreation;
+};
+
 globalThis.Deno.test = test;
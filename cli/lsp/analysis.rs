@@ -4,11 +4,16 @@ use super::diagnostics::DenoDiagnostic;
 use super::diagnostics::DiagnosticSource;
 use super::documents::Documents;
 use super::language_server;
+use super::logging::lsp_warn;
 use super::resolver::LspResolver;
 use super::tsc;
 
 use crate::args::jsr_url;
+use crate::tools::lint::apply_suppressions;
 use crate::tools::lint::create_linter;
+use crate::tools::lint::lint_with_suppression_reasons;
+use crate::tools::lint::LintPlugins;
+use crate::tools::lint::LintSuppressionOptions;
 use deno_runtime::fs_util::specifier_to_file_path;
 
 use deno_ast::SourceRange;
@@ -169,9 +174,16 @@ fn as_lsp_range(
 pub fn get_lint_references(
   parsed_source: &deno_ast::ParsedSource,
   lint_rules: Vec<&'static dyn LintRule>,
+  lint_plugins: &LintPlugins,
+  suppression_options: &LintSuppressionOptions,
 ) -> Result<Vec<Reference>, AnyError> {
   let linter = create_linter(lint_rules);
-  let lint_diagnostics = linter.lint_with_ast(parsed_source);
+  let mut lint_diagnostics =
+    lint_with_suppression_reasons(&linter, parsed_source)?;
+  if let Err(err) = lint_plugins.lint(parsed_source, &mut lint_diagnostics) {
+    lsp_warn!("Failed running lint plugins: {:#}", err);
+  }
+  apply_suppressions(parsed_source, &mut lint_diagnostics, suppression_options);
 
   Ok(
     lint_diagnostics
@@ -1,6 +1,12 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use super::logging::lsp_log;
+use crate::args::deno_json::apply_config_overrides;
+use crate::args::deno_json::resolve_import_map_extends;
+use crate::args::deno_json::ConfigFileExtras;
+use crate::args::merge_import_maps;
+use crate::args::resolve_fmt_config;
+use crate::args::resolve_lint_config;
 use crate::args::ConfigFile;
 use crate::args::FmtOptions;
 use crate::args::LintOptions;
@@ -10,6 +16,7 @@ use crate::file_fetcher::FileFetcher;
 use crate::lsp::logging::lsp_warn;
 use crate::tools::lint::get_configured_rules;
 use crate::tools::lint::ConfiguredRules;
+use crate::tools::lint::LintPlugins;
 use crate::util::fs::canonicalize_path_maybe_not_exists;
 use deno_ast::MediaType;
 use deno_config::FmtOptionsConfig;
@@ -1081,9 +1088,11 @@ pub enum ConfigWatchedFileType {
 pub struct ConfigData {
   pub scope: ModuleSpecifier,
   pub config_file: Option<Arc<ConfigFile>>,
+  pub config_file_extras: Option<Arc<ConfigFileExtras>>,
   pub fmt_options: Arc<FmtOptions>,
   pub lint_options: Arc<LintOptions>,
   pub lint_rules: Arc<ConfiguredRules>,
+  pub lint_plugins: LintPlugins,
   pub ts_config: Arc<LspTsConfig>,
   pub byonm: bool,
   pub node_modules_dir: Option<PathBuf>,
@@ -1108,13 +1117,28 @@ impl ConfigData {
         specifier.clone(),
         &deno_config::ParseOptions::default(),
       ) {
-        Ok(config_file) => {
+        Ok(mut config_file) => {
           lsp_log!(
             "  Resolved Deno configuration file: \"{}\"",
             config_file.specifier.as_str()
           );
+          let config_file_extras = match ConfigFileExtras::read(&config_file) {
+            Ok(extras) => {
+              if let Err(err) =
+                apply_config_overrides(&mut config_file, &extras)
+              {
+                lsp_warn!("  Couldn't apply \"overrides\": {}", err);
+              }
+              Some(extras)
+            }
+            Err(err) => {
+              lsp_warn!("  Couldn't read the configuration file: {}", err);
+              None
+            }
+          };
           Self::load_inner(
             Some(config_file),
+            config_file_extras,
             scope,
             parent,
             settings,
@@ -1129,7 +1153,8 @@ impl ConfigData {
             err
           );
           let mut data =
-            Self::load_inner(None, scope, parent, settings, file_fetcher).await;
+            Self::load_inner(None, None, scope, parent, settings, file_fetcher)
+              .await;
           data
             .watched_files
             .insert(specifier.clone(), ConfigWatchedFileType::DenoJson);
@@ -1147,12 +1172,13 @@ impl ConfigData {
         }
       }
     } else {
-      Self::load_inner(None, scope, parent, settings, file_fetcher).await
+      Self::load_inner(None, None, scope, parent, settings, file_fetcher).await
     }
   }
 
   async fn load_inner(
     config_file: Option<ConfigFile>,
+    config_file_extras: Option<ConfigFileExtras>,
     scope: &ModuleSpecifier,
     parent: Option<(&ModuleSpecifier, &ConfigData)>,
     settings: &Settings,
@@ -1189,14 +1215,13 @@ impl ConfigData {
       config_file
         .as_ref()
         .and_then(|config_file| {
-          config_file
-            .to_fmt_config()
-            .and_then(|o| {
+          resolve_fmt_config(config_file)
+            .and_then(|(o, cli_fmt_config)| {
               let base_path = config_file
                 .specifier
                 .to_file_path()
                 .map_err(|_| anyhow!("Invalid base path."))?;
-              FmtOptions::resolve(o, None, &base_path)
+              FmtOptions::resolve(o, cli_fmt_config, None, &base_path)
             })
             .inspect_err(|err| {
               lsp_warn!("  Couldn't read formatter configuration: {}", err)
@@ -1216,35 +1241,42 @@ impl ConfigData {
         lint_options_rules = Some((
           parent_data.lint_options.clone(),
           parent_data.lint_rules.clone(),
+          parent_data.lint_plugins.clone(),
         ))
       }
     }
-    let (lint_options, lint_rules) = lint_options_rules.unwrap_or_else(|| {
-      let lint_options = config_file
-        .as_ref()
-        .and_then(|config_file| {
-          config_file
-            .to_lint_config()
-            .and_then(|o| {
-              let base_path = config_file
-                .specifier
-                .to_file_path()
-                .map_err(|_| anyhow!("Invalid base path."))?;
-              LintOptions::resolve(o, None, &base_path)
-            })
+    let (lint_options, lint_rules, lint_plugins) = lint_options_rules
+      .unwrap_or_else(|| {
+        let lint_options = config_file
+          .as_ref()
+          .and_then(|config_file| {
+            resolve_lint_config(config_file)
+              .and_then(|(o, cli_lint_config)| {
+                let base_path = config_file
+                  .specifier
+                  .to_file_path()
+                  .map_err(|_| anyhow!("Invalid base path."))?;
+                LintOptions::resolve(o, cli_lint_config, None, &base_path)
+              })
+              .inspect_err(|err| {
+                lsp_warn!("  Couldn't read lint configuration: {}", err)
+              })
+              .ok()
+          })
+          .map(Arc::new)
+          .unwrap_or_default();
+        let lint_rules = Arc::new(get_configured_rules(
+          lint_options.rules.clone(),
+          config_file.as_ref(),
+        ));
+        let lint_plugins =
+          LintPlugins::load(&lint_options.plugins, &lint_rules.selection)
             .inspect_err(|err| {
-              lsp_warn!("  Couldn't read lint configuration: {}", err)
+              lsp_warn!("  Couldn't load lint plugins: {}", err)
             })
-            .ok()
-        })
-        .map(Arc::new)
-        .unwrap_or_default();
-      let lint_rules = Arc::new(get_configured_rules(
-        lint_options.rules.clone(),
-        config_file.as_ref(),
-      ));
-      (lint_options, lint_rules)
-    });
+            .unwrap_or_default();
+        (lint_options, lint_rules, lint_plugins)
+      });
 
     let ts_config = LspTsConfig::new(config_file.as_ref());
 
@@ -1384,6 +1416,76 @@ impl ConfigData {
         }
       }
     }
+    // the import maps extended by the configuration file are merged beneath
+    // its own import map, unless the import map is set in the settings
+    let extends = match config_file_extras.as_ref() {
+      Some(extras) if !import_map_from_settings => {
+        resolve_import_map_extends(extras).unwrap_or_else(|err| {
+          lsp_warn!("  Couldn't resolve \"extends\": {}", err);
+          Vec::new()
+        })
+      }
+      _ => Vec::new(),
+    };
+    if !extends.is_empty() {
+      let mut layers = Vec::with_capacity(extends.len() + 1);
+      for specifier in extends {
+        if let Ok(path) = specifier_to_file_path(&specifier) {
+          watched_files
+            .entry(specifier.clone())
+            .or_insert(ConfigWatchedFileType::ImportMap);
+          if let Some(specifier) = canonicalize_path_maybe_not_exists(&path)
+            .ok()
+            .and_then(|p| ModuleSpecifier::from_file_path(p).ok())
+          {
+            watched_files
+              .entry(specifier)
+              .or_insert(ConfigWatchedFileType::ImportMap);
+          }
+        }
+        let Some(file_fetcher) = file_fetcher else {
+          continue;
+        };
+        let value_result = file_fetcher
+          .fetch(&specifier, &PermissionsContainer::allow_all())
+          .await
+          .and_then(|f| {
+            serde_json::from_slice::<Value>(&f.source).map_err(|e| e.into())
+          });
+        match value_result {
+          Ok(value) => layers.push((specifier, value)),
+          Err(err) => {
+            lsp_warn!(
+              "  Couldn't read import map \"{}\": {}",
+              specifier.as_str(),
+              err
+            );
+          }
+        }
+      }
+      let config_import_map_url = import_map_specifier.clone();
+      if let (Some(value), Some(specifier)) =
+        (import_map_value.take(), import_map_specifier.take())
+      {
+        layers.push((specifier, value));
+      }
+      if !layers.is_empty() {
+        let (url, value, conflicts) =
+          merge_import_maps(layers, config_import_map_url.as_ref());
+        if !conflicts.is_empty() {
+          lsp_warn!(
+            "  Import map conflicts:\n{}",
+            conflicts
+              .iter()
+              .map(|c| format!("    - {c}"))
+              .collect::<Vec<_>>()
+              .join("\n")
+          );
+        }
+        import_map_value = Some(value);
+        import_map_specifier = Some(url);
+      }
+    }
     if let (Some(value), Some(specifier)) =
       (import_map_value, import_map_specifier)
     {
@@ -1420,9 +1522,11 @@ impl ConfigData {
     ConfigData {
       scope: scope.clone(),
       config_file: config_file.map(Arc::new),
+      config_file_extras: config_file_extras.map(Arc::new),
       fmt_options,
       lint_options,
       lint_rules,
+      lint_plugins,
       ts_config: Arc::new(ts_config),
       byonm,
       node_modules_dir,
@@ -1634,6 +1738,7 @@ impl ConfigTree {
     let scope = config_file.specifier.join(".").unwrap();
     let data = ConfigData::load_inner(
       Some(config_file),
+      None,
       &scope,
       None,
       &Default::default(),
@@ -21,6 +21,8 @@ use crate::graph_util::enhanced_resolution_error_message;
 use crate::lsp::lsp_custom::DiagnosticBatchNotificationParams;
 use crate::resolver::SloppyImportsResolution;
 use crate::resolver::SloppyImportsResolver;
+use crate::tools::lint::LintPlugins;
+use crate::tools::lint::LintSuppressionOptions;
 use crate::util::path::to_percent_decoded_str;
 
 use deno_ast::MediaType;
@@ -807,11 +809,17 @@ fn generate_lint_diagnostics(
       continue;
     }
     let version = document.maybe_lsp_version();
-    let (lint_options, lint_rules) = config
+    let (lint_options, lint_rules, lint_plugins) = config
       .tree
       .scope_for_specifier(specifier)
       .and_then(|s| config_data_by_scope.get(s))
-      .map(|d| (d.lint_options.clone(), d.lint_rules.clone()))
+      .map(|d| {
+        (
+          d.lint_options.clone(),
+          d.lint_rules.clone(),
+          d.lint_plugins.clone(),
+        )
+      })
       .unwrap_or_default();
     diagnostics_vec.push(DiagnosticRecord {
       specifier: specifier.clone(),
@@ -821,6 +829,7 @@ fn generate_lint_diagnostics(
           &document,
           &lint_options,
           lint_rules.rules.clone(),
+          &lint_plugins,
         ),
       },
     });
@@ -832,15 +841,23 @@ fn generate_document_lint_diagnostics(
   document: &Document,
   lint_options: &LintOptions,
   lint_rules: Vec<&'static dyn LintRule>,
+  lint_plugins: &LintPlugins,
 ) -> Vec<lsp::Diagnostic> {
   if !lint_options.files.matches_specifier(document.specifier()) {
     return Vec::new();
   }
   match document.maybe_parsed_source() {
     Some(Ok(parsed_source)) => {
-      if let Ok(references) =
-        analysis::get_lint_references(&parsed_source, lint_rules)
-      {
+      let suppression_options = LintSuppressionOptions::new(
+        &lint_rules,
+        lint_options.require_suppression_reason,
+      );
+      if let Ok(references) = analysis::get_lint_references(
+        &parsed_source,
+        lint_rules,
+        lint_plugins,
+        &suppression_options,
+      ) {
         references
           .into_iter()
           .map(|r| r.to_diagnostic())
@@ -4,6 +4,7 @@ use base64::Engine;
 use deno_ast::MediaType;
 use deno_core::anyhow::anyhow;
 use deno_core::error::AnyError;
+use deno_core::parking_lot::Mutex;
 use deno_core::resolve_url;
 use deno_core::serde_json;
 use deno_core::serde_json::json;
@@ -88,6 +89,7 @@ use crate::args::CaData;
 use crate::args::CacheSetting;
 use crate::args::CliOptions;
 use crate::args::Flags;
+use crate::args::FmtOptions;
 use crate::cache::DenoDir;
 use crate::cache::GlobalHttpCache;
 use crate::cache::HttpCache;
@@ -100,8 +102,9 @@ use crate::lsp::config::ConfigWatchedFileType;
 use crate::lsp::logging::init_log_file;
 use crate::lsp::tsc::file_text_changes_to_workspace_edit;
 use crate::lsp::urls::LspUrlKind;
-use crate::tools::fmt::format_file;
+use crate::tools::fmt::format_file_with_plugins;
 use crate::tools::fmt::format_parsed_source;
+use crate::tools::fmt::FmtPlugins;
 use crate::tools::upgrade::check_for_upgrades_for_lsp;
 use crate::tools::upgrade::upgrade_check_enabled;
 use crate::util::fs::remove_dir_all_if_exists;
@@ -186,6 +189,9 @@ pub struct Inner {
   pub config: Config,
   diagnostics_state: Arc<diagnostics::DiagnosticsState>,
   diagnostics_server: diagnostics::DiagnosticsServer,
+  /// Formatter plugins loaded for the formatting options they were
+  /// configured in.
+  fmt_plugins: Mutex<Vec<(Arc<FmtOptions>, FmtPlugins)>>,
   /// The collection of documents that the server is currently handling, either
   /// on disk or "open" within the client.
   pub documents: Documents,
@@ -490,6 +496,7 @@ impl Inner {
       diagnostics_state,
       diagnostics_server,
       documents,
+      fmt_plugins: Default::default(),
       global_cache,
       http_client,
       initial_cwd: initial_cwd.clone(),
@@ -1335,20 +1342,16 @@ impl Inner {
       LspError::invalid_request()
     })?;
     let mark = self.performance.mark_with_args("lsp.formatting", &params);
+    let fmt_options = self.config.tree.fmt_options_for_specifier(&specifier);
+    let fmt_plugins = self.fmt_plugins(&fmt_options).await;
 
     // spawn a blocking task to allow doing other work while this is occurring
     let text_edits = deno_core::unsync::spawn_blocking({
-      let fmt_options = self
-        .config
-        .tree
-        .fmt_options_for_specifier(&specifier)
-        .options
-        .clone();
       let document = document.clone();
       move || {
         let format_result = match document.maybe_parsed_source() {
           Some(Ok(parsed_source)) => {
-            format_parsed_source(&parsed_source, &fmt_options)
+            format_parsed_source(&parsed_source, &fmt_options.options)
           }
           Some(Err(err)) => Err(anyhow!("{:#}", err)),
           None => {
@@ -1361,7 +1364,13 @@ impl Inner {
               .map(|ext| file_path.with_extension(ext))
               .unwrap_or(file_path);
             // it's not a js/ts file, so attempt to format its contents
-            format_file(&file_path, &document.content(), &fmt_options)
+            format_file_with_plugins(
+              &file_path,
+              &document.content(),
+              &fmt_options.options,
+              &fmt_options.markdown,
+              &fmt_plugins,
+            )
           }
         };
         match format_result {
@@ -1393,6 +1402,49 @@ impl Inner {
     }
   }
 
+  /// Gets the formatter plugins configured in `fmt_options`, loading them
+  /// the first time they are used.
+  async fn fmt_plugins(&self, fmt_options: &Arc<FmtOptions>) -> FmtPlugins {
+    if fmt_options.plugins.is_empty() {
+      return FmtPlugins::default();
+    }
+    let loaded_plugins = self
+      .fmt_plugins
+      .lock()
+      .iter()
+      .find(|(options, _)| Arc::ptr_eq(options, fmt_options))
+      .map(|(_, plugins)| plugins.clone());
+    if let Some(plugins) = loaded_plugins {
+      return plugins;
+    }
+    let plugins = match FmtPlugins::load(
+      &self.module_registry.file_fetcher,
+      &fmt_options.plugins,
+      &fmt_options.options,
+      self
+        .config
+        .tree
+        .root_lockfile()
+        .map(|lockfile| lockfile.as_ref()),
+      // documents are formatted one at a time
+      1,
+    )
+    .await
+    {
+      Ok(plugins) => plugins,
+      Err(err) => {
+        lsp_warn!("Couldn't load formatter plugins: {:#}", err);
+        FmtPlugins::default()
+      }
+    };
+    let mut fmt_plugins = self.fmt_plugins.lock();
+    // drop the plugins of formatting options that were replaced by a config
+    // change
+    fmt_plugins.retain(|(options, _)| Arc::strong_count(options) > 1);
+    fmt_plugins.push((fmt_options.clone(), plugins.clone()));
+    plugins
+  }
+
   async fn hover(&self, params: HoverParams) -> LspResult<Option<Hover>> {
     let specifier = self.url_map.normalize_url(
       &params.text_document_position_params.text_document.uri,
@@ -3350,6 +3402,7 @@ impl Inner {
       },
       self.initial_cwd.clone(),
       config_data.and_then(|d| d.config_file.as_deref().cloned()),
+      config_data.and_then(|d| d.config_file_extras.as_deref().cloned()),
       config_data.and_then(|d| d.lockfile.clone()),
       config_data.and_then(|d| d.package_json.as_deref().cloned()),
       force_global_cache,
@@ -3374,7 +3427,10 @@ impl Inner {
     for config_file in self.config.tree.config_files() {
       if let Some(tasks) = json!(&config_file.json.tasks).as_object() {
         for (name, value) in tasks {
-          let Some(command) = value.as_str() else {
+          let Some(command) = value
+            .as_str()
+            .or_else(|| value.get("cmd").and_then(|cmd| cmd.as_str()))
+          else {
             continue;
           };
           result.push(TaskDefinition {
@@ -18,8 +18,11 @@ use tower_lsp::lsp_types::CompletionTriggerKind;
 use tower_lsp::lsp_types::DidChangeTextDocumentParams;
 use tower_lsp::lsp_types::DidCloseTextDocumentParams;
 use tower_lsp::lsp_types::DidOpenTextDocumentParams;
+use tower_lsp::lsp_types::HoverContents;
+use tower_lsp::lsp_types::HoverParams;
 use tower_lsp::lsp_types::InitializeParams;
 use tower_lsp::lsp_types::InitializedParams;
+use tower_lsp::lsp_types::MarkedString;
 use tower_lsp::lsp_types::PartialResultParams;
 use tower_lsp::lsp_types::Position;
 use tower_lsp::lsp_types::Range;
@@ -192,6 +195,41 @@ impl ReplLanguageServer {
       .collect()
   }
 
+  /// Gets the type of an expression inferred by the TypeScript compiler in
+  /// the context of the code evaluated so far.
+  pub async fn type_of_expression(&mut self, expr: &str) -> Option<String> {
+    const NAME: &str = "$deno$type";
+    let declaration = format!("const {NAME} = ({expr});");
+    self.did_change(&declaration).await;
+    let line = self.document_text.chars().filter(|c| *c == '\n').count();
+    let response = self
+      .language_server
+      .hover(HoverParams {
+        text_document_position_params: TextDocumentPositionParams {
+          text_document: TextDocumentIdentifier {
+            uri: self.get_document_specifier(),
+          },
+          position: Position::new(line as u32, "const ".len() as u32),
+        },
+        work_done_progress_params: WorkDoneProgressParams {
+          work_done_token: None,
+        },
+      })
+      .await;
+    // remove the declaration from the document
+    self.did_change("").await;
+    let HoverContents::Array(parts) = response.ok()??.contents else {
+      return None;
+    };
+    parts.into_iter().find_map(|part| match part {
+      MarkedString::LanguageString(code) => code
+        .value
+        .strip_prefix(&format!("const {NAME}: "))
+        .map(|ty| ty.to_string()),
+      MarkedString::String(_) => None,
+    })
+  }
+
   async fn did_change(&mut self, new_text: &str) {
     self.check_cwd_change().await;
     let new_text = if new_text.ends_with('\n') {
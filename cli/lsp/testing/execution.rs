@@ -303,6 +303,10 @@ impl TestRun {
             test::TestSpecifierOptions {
               filter,
               shuffle: None,
+              shard: None,
+              retries: 0,
+              timeout: None,
+              update_snapshots: false,
               trace_leaks: false,
             },
           ))
@@ -372,6 +376,9 @@ impl TestRun {
                 reporter.report_result(&description, &result, elapsed);
               }
             }
+            test::TestEvent::Retry(..) => {
+              // Only the result of the last run of a test is reported.
+            }
             test::TestEvent::UncaughtError(origin, error) => {
               reporter.report_uncaught_error(&origin, &error);
               summary.failed += 1;
@@ -669,7 +676,7 @@ impl LspTestReporter {
     let err_string = format!(
       "Uncaught error from {}: {}\nThis error was not caught from a test and caused the test runner to fail on the referenced module.\nIt most likely originated from a dangling promise, event/timeout handler or top-level code.",
       origin,
-      test::fmt::format_test_error(js_error)
+      test::fmt::format_test_error(js_error, false)
     );
     let messages = as_test_messages(err_string, false);
     for desc in self.tests.values().filter(|d| d.origin() == origin) {
@@ -28,6 +28,7 @@ mod worker;
 
 use crate::args::flags_from_vec;
 use crate::args::DenoSubcommand;
+use crate::args::ErrorFormat;
 use crate::args::Flags;
 use crate::args::DENO_FUTURE;
 use crate::util::display;
@@ -44,9 +45,16 @@ use deno_core::futures::FutureExt;
 use deno_core::unsync::JoinHandle;
 use deno_npm::resolution::SnapshotFromLockfileError;
 use deno_runtime::fmt_errors::format_js_error;
+use deno_runtime::fmt_errors::format_js_error_json;
+use deno_runtime::fmt_errors::set_format_js_error_options;
+use deno_runtime::fmt_errors::supports_hyperlinks;
+use deno_runtime::fmt_errors::ErrorTheme;
+use deno_runtime::fmt_errors::FormatJsErrorOptions;
+use deno_runtime::fmt_errors::DEFAULT_MAX_CAUSE_DEPTH;
 use deno_runtime::tokio_util::create_and_run_current_thread_with_maybe_metrics;
 use deno_terminal::colors;
 use factory::CliFactory;
+use once_cell::sync::OnceCell;
 use std::borrow::Cow;
 use std::env;
 use std::env::current_exe;
@@ -111,14 +119,34 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
       tools::run::eval_command(flags, eval_flags).await
     }),
     DenoSubcommand::Cache(cache_flags) => spawn_subcommand(async move {
+      if cache_flags.prune {
+        return tools::cache::prune(flags, cache_flags).await;
+      }
       let factory = CliFactory::from_flags(flags)?;
+      let initial_cwd = factory.cli_options().initial_cwd();
+      if let Some(import) = &cache_flags.import {
+        tools::cache::import_archive(&factory, &initial_cwd.join(import))?;
+        if cache_flags.files.is_empty() {
+          return Ok(());
+        }
+      }
       let module_load_preparer = factory.module_load_preparer().await?;
       let emitter = factory.emitter()?;
       let graph_container = factory.graph_container();
       module_load_preparer
         .load_and_type_check_files(&cache_flags.files)
         .await?;
-      emitter.cache_module_emits(&graph_container.graph())
+      let graph = graph_container.graph();
+      emitter.cache_module_emits(&graph)?;
+      if let Some(export) = &cache_flags.export {
+        tools::cache::export_archive(
+          &factory,
+          &graph,
+          &initial_cwd.join(export),
+        )
+        .await?;
+      }
+      Ok(())
     }),
     DenoSubcommand::Check(check_flags) => spawn_subcommand(async move {
       let factory = CliFactory::from_flags(flags)?;
@@ -126,6 +154,7 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
       module_load_preparer
         .load_and_type_check_files(&check_flags.files)
         .await
+        .map_err(|err| tools::check::format_check_error(err, &check_flags))
     }),
     DenoSubcommand::Compile(compile_flags) => spawn_subcommand(async {
       tools::compile::compile(flags, compile_flags).await
@@ -133,6 +162,9 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
     DenoSubcommand::Coverage(coverage_flags) => spawn_subcommand(async {
       tools::coverage::cover_files(flags, coverage_flags).await
     }),
+    DenoSubcommand::CoverageMerge(merge_flags) => spawn_subcommand(async {
+      tools::coverage::merge_coverage_profiles(flags, merge_flags)
+    }),
     DenoSubcommand::Fmt(fmt_flags) => {
       spawn_subcommand(
         async move { tools::fmt::format(flags, fmt_flags).await },
@@ -158,6 +190,14 @@ async fn run_subcommand(flags: Flags) -> Result<i32, AnyError> {
       tools::installer::uninstall(uninstall_flags)
     }),
     DenoSubcommand::Lsp => spawn_subcommand(async { lsp::start().await }),
+    DenoSubcommand::Permissions(permissions_flags) => {
+      spawn_subcommand(async {
+        tools::permissions::permissions(flags, permissions_flags.subcommand)
+      })
+    }
+    DenoSubcommand::Storage(storage_flags) => spawn_subcommand(async {
+      tools::storage::storage(flags, storage_flags)
+    }),
     DenoSubcommand::Lint(lint_flags) => spawn_subcommand(async {
       if lint_flags.rules {
         tools::lint::print_rules_list(
@@ -268,11 +308,19 @@ fn exit_with_message(message: &str, code: i32) -> ! {
   std::process::exit(code);
 }
 
+/// The `--error-format` of the uncaught error that exits the process.
+static ERROR_FORMAT: OnceCell<ErrorFormat> = OnceCell::new();
+
 fn exit_for_error(error: AnyError) -> ! {
   let mut error_string = format!("{error:?}");
   let mut error_code = 1;
 
   if let Some(e) = error.downcast_ref::<JsError>() {
+    if ERROR_FORMAT.get() == Some(&ErrorFormat::Json) {
+      // JSON output is meant to be machine readable, so don't prefix it
+      eprintln!("{}", format_js_error_json(e));
+      std::process::exit(error_code);
+    }
     error_string = format_js_error(e);
   } else if let Some(args::LockfileError::IntegrityCheckFailed(e)) =
     error.downcast_ref::<args::LockfileError>()
@@ -405,5 +453,27 @@ fn resolve_flags_and_init(
   deno_core::JsRuntime::init_platform(None);
   util::logger::init(flags.log_level);
 
+  let theme = match env::var("DENO_ERROR_THEME") {
+    Ok(value) => value.parse::<ErrorTheme>().unwrap_or_else(|err| {
+      log::warn!(
+        "{} Ignoring invalid DENO_ERROR_THEME: {}",
+        colors::yellow("Warning"),
+        err
+      );
+      ErrorTheme::default()
+    }),
+    Err(_) => ErrorTheme::default(),
+  };
+  let _ = ERROR_FORMAT.set(flags.error_format);
+  set_format_js_error_options(FormatJsErrorOptions {
+    context_lines: flags.error_context_lines,
+    theme,
+    hyperlinks: supports_hyperlinks(&std::io::stderr()),
+    hidden_frames: flags.hide_stack_frames.clone(),
+    max_cause_depth: flags
+      .error_max_cause_depth
+      .unwrap_or(DEFAULT_MAX_CAUSE_DEPTH),
+  });
+
   Ok(flags)
 }
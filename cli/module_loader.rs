@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use crate::args::jsr_url;
+use crate::args::write_lockfile;
 use crate::args::CliOptions;
 use crate::args::DenoSubcommand;
 use crate::args::TsTypeLib;
@@ -19,6 +20,8 @@ use crate::resolver::ModuleCodeStringSource;
 use crate::resolver::NpmModuleLoader;
 use crate::tools::check;
 use crate::tools::check::TypeChecker;
+use crate::util::fs::collect_specifiers;
+use crate::util::path::is_script_ext;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::text_encoding::code_without_source_map;
 use crate::util::text_encoding::source_map_from_code;
@@ -136,7 +139,7 @@ impl ModuleLoadPreparer {
       // validate the integrity of all the modules
       graph_lock_or_exit(graph, &mut lockfile);
       // update it with anything new
-      lockfile.write().context("Failed writing lockfile.")?;
+      write_lockfile(&lockfile, self.options.frozen_lockfile())?;
     }
 
     // save the graph and get a reference to the new graph
@@ -144,6 +147,10 @@ impl ModuleLoadPreparer {
 
     drop(_pb_clear_guard);
 
+    if self.options.revalidate_in_background() {
+      self.module_graph_builder.revalidate_in_background(&graph);
+    }
+
     // type check if necessary
     if self.options.type_check_mode().is_true() && !has_type_checked {
       self
@@ -198,6 +205,13 @@ impl ModuleLoadPreparer {
     &self,
     files: &[String],
   ) -> Result<Vec<ModuleSpecifier>, AnyError> {
+    if files.is_empty() {
+      return collect_specifiers(
+        self.options.resolve_check_files()?,
+        self.options.vendor_dir_path().map(ToOwned::to_owned),
+        |e| is_script_ext(e.path),
+      );
+    }
     let excludes = self.options.resolve_config_excludes()?;
     Ok(
       files
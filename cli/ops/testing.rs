@@ -1,5 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::tools::test::resource_trace::ResourceCreationTracer;
+use crate::tools::test::snapshot::SnapshotState;
 use crate::tools::test::TestContainer;
 use crate::tools::test::TestDescription;
 use crate::tools::test::TestEvent;
@@ -16,6 +18,7 @@ use deno_core::op2;
 use deno_core::v8;
 use deno_core::ModuleSpecifier;
 use deno_core::OpState;
+use deno_core::ResourceId;
 use deno_runtime::permissions::create_child_permissions;
 use deno_runtime::permissions::ChildPermissionsArg;
 use deno_runtime::permissions::PermissionsContainer;
@@ -31,6 +34,8 @@ deno_core::extension!(deno_test,
     op_register_test,
     op_register_test_step,
     op_test_get_origin,
+    op_test_assert_snapshot,
+    op_test_trace_resource_creation,
     op_test_event_step_wait,
     op_test_event_step_result_ok,
     op_test_event_step_result_ignored,
@@ -113,6 +118,8 @@ fn op_register_test(
   only: bool,
   sanitize_ops: bool,
   sanitize_resources: bool,
+  #[serde] retries: Option<u32>,
+  #[serde] timeout: Option<u64>,
   #[string] file_name: String,
   #[smi] line_number: u32,
   #[smi] column_number: u32,
@@ -133,6 +140,8 @@ fn op_register_test(
     only,
     sanitize_ops,
     sanitize_resources,
+    retries,
+    timeout,
     origin: origin.clone(),
     location: TestLocation {
       file_name,
@@ -152,6 +161,36 @@ fn op_test_get_origin(state: &mut OpState) -> String {
   state.borrow::<ModuleSpecifier>().to_string()
 }
 
+/// Returns a description of the mismatch if `actual` differs from the stored
+/// snapshot called `name`.
+#[op2]
+#[string]
+fn op_test_assert_snapshot(
+  state: &mut OpState,
+  #[string] name: String,
+  #[string] actual: String,
+) -> Result<Option<String>, AnyError> {
+  let Some(snapshots) = state.try_borrow_mut::<SnapshotState>() else {
+    return Err(generic_error(
+      "Snapshot testing is only supported when running \"deno test\".",
+    ));
+  };
+  snapshots.assert(name, actual)
+}
+
+#[op2]
+fn op_test_trace_resource_creation(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  #[string] stack: &str,
+) {
+  if !state.has::<ResourceCreationTracer>() {
+    state.put(ResourceCreationTracer::default());
+  }
+  let tracer = state.borrow::<ResourceCreationTracer>();
+  tracer.record(state, rid, stack);
+}
+
 #[op2(fast)]
 #[smi]
 #[allow(clippy::too_many_arguments)]
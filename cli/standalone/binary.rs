@@ -39,17 +39,28 @@ use crate::args::PackageJsonDepsProvider;
 use crate::args::PermissionFlags;
 use crate::args::UnstableConfig;
 use crate::cache::DenoDir;
+use crate::cache::CACHE_PERM;
 use crate::file_fetcher::FileFetcher;
 use crate::http_util::HttpClient;
 use crate::npm::CliNpmResolver;
 use crate::npm::InnerCliNpmResolverRef;
+use crate::util::fs::atomic_write_file;
+use crate::util::fs::normalize_path;
 use crate::util::progress_bar::ProgressBar;
 use crate::util::progress_bar::ProgressBarStyle;
 
+use super::macos_resources::set_macos_binary_resources;
+use super::macos_resources::MacosResourceOptions;
+use super::signing::include_appended_data_in_image;
+use super::signing::remove_binary_signature;
+use super::signing::signature_offset;
+use super::signing::SIGNATURE_ALIGNMENT;
 use super::virtual_fs::FileBackedVfs;
 use super::virtual_fs::VfsBuilder;
 use super::virtual_fs::VfsRoot;
 use super::virtual_fs::VirtualDirectory;
+use super::windows_resources::set_windows_binary_resources;
+use super::windows_resources::WindowsResourceOptions;
 
 const MAGIC_TRAILER: &[u8; 8] = b"d3n0l4nd";
 
@@ -130,20 +141,33 @@ pub enum NodeModules {
   },
 }
 
+/// A directory embedded with `--include`, which is served from a virtual
+/// file system at the path it had when the binary was compiled.
+#[derive(Deserialize, Serialize)]
+pub struct EmbeddedDirectory {
+  pub root_path: PathBuf,
+  pub dir: VirtualDirectory,
+  /// Offset of the directory's files from the start of the npm files.
+  pub files_offset: u64,
+}
+
 #[derive(Deserialize, Serialize)]
 pub struct Metadata {
   pub argv: Vec<String>,
   pub seed: Option<u64>,
   pub permissions: PermissionFlags,
   pub location: Option<Url>,
+  pub local_storage_quota: Option<usize>,
   pub v8_flags: Vec<String>,
   pub log_level: Option<Level>,
   pub ca_stores: Option<Vec<String>>,
   pub ca_data: Option<Vec<u8>>,
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
+  pub http1_only: bool,
   pub maybe_import_map: Option<(Url, String)>,
   pub entrypoint: ModuleSpecifier,
   pub node_modules: Option<NodeModules>,
+  pub embedded_dirs: Vec<EmbeddedDirectory>,
   pub disable_deprecated_api_warning: bool,
   pub unstable_config: UnstableConfig,
 }
@@ -151,10 +175,7 @@ pub struct Metadata {
 pub fn load_npm_vfs(root_dir_path: PathBuf) -> Result<FileBackedVfs, AnyError> {
   let file_path = current_exe().unwrap();
   let mut file = std::fs::File::open(file_path)?;
-  file.seek(SeekFrom::End(-(TRAILER_SIZE as i64)))?;
-  let mut trailer = [0; TRAILER_SIZE];
-  file.read_exact(&mut trailer)?;
-  let trailer = Trailer::parse(&trailer)?.unwrap();
+  let trailer = read_trailer(&mut file)?;
   file.seek(SeekFrom::Start(trailer.npm_vfs_pos))?;
   let mut vfs_data = vec![0; trailer.npm_vfs_len() as usize];
   file.read_exact(&mut vfs_data)?;
@@ -175,18 +196,85 @@ pub fn load_npm_vfs(root_dir_path: PathBuf) -> Result<FileBackedVfs, AnyError> {
   Ok(FileBackedVfs::new(file, fs_root))
 }
 
+pub fn load_embedded_dir_vfs(
+  embedded_dir: EmbeddedDirectory,
+) -> Result<FileBackedVfs, AnyError> {
+  let file_path = current_exe().unwrap();
+  let mut file = std::fs::File::open(file_path)?;
+  let trailer = read_trailer(&mut file)?;
+  let mut dir = embedded_dir.dir;
+  if let Some(name) = embedded_dir.root_path.file_name() {
+    dir.name = name.to_string_lossy().to_string();
+  }
+  let fs_root = VfsRoot {
+    dir,
+    root_path: embedded_dir.root_path,
+    start_file_offset: trailer.npm_files_pos + embedded_dir.files_offset,
+  };
+  Ok(FileBackedVfs::new(file, fs_root))
+}
+
+fn read_trailer(file: &mut std::fs::File) -> Result<Trailer, AnyError> {
+  Ok(find_trailer(file)?.unwrap())
+}
+
+/// Finds the trailer, which is either at the end of the file or, for signed
+/// executables, directly in front of the signature.
+fn find_trailer(file: &mut std::fs::File) -> Result<Option<Trailer>, AnyError> {
+  fn read_trailer_at(
+    file: &mut std::fs::File,
+    end: SeekFrom,
+  ) -> Result<Option<Trailer>, AnyError> {
+    let end = file.seek(end)?;
+    let Some(start) = end.checked_sub(TRAILER_SIZE as u64) else {
+      // the file is too small to possibly be `deno compile` output
+      return Ok(None);
+    };
+    file.seek(SeekFrom::Start(start))?;
+    let mut trailer = [0; TRAILER_SIZE];
+    file.read_exact(&mut trailer)?;
+    Trailer::parse(&trailer)
+  }
+
+  if let Some(trailer) = read_trailer_at(file, SeekFrom::End(0))? {
+    return Ok(Some(trailer));
+  }
+  match signature_offset(file)? {
+    Some(offset) => read_trailer_at(file, SeekFrom::Start(offset)),
+    None => Ok(None),
+  }
+}
+
 fn write_binary_bytes(
   writer: &mut impl Write,
-  original_bin: Vec<u8>,
+  mut original_bin: Vec<u8>,
   metadata: &Metadata,
   eszip: eszip::EszipV2,
   npm_vfs: Option<&VirtualDirectory>,
   npm_files: &Vec<Vec<u8>>,
+  prepare_for_signing: bool,
 ) -> Result<(), AnyError> {
   let metadata = serde_json::to_string(metadata)?.as_bytes().to_vec();
   let npm_vfs = serde_json::to_string(&npm_vfs)?.as_bytes().to_vec();
   let eszip_archive = eszip.into_bytes();
 
+  if prepare_for_signing {
+    remove_binary_signature(&mut original_bin)?;
+  }
+  let data_len = (eszip_archive.len() + metadata.len() + npm_vfs.len()) as u64
+    + npm_files.iter().map(|file| file.len() as u64).sum::<u64>();
+  // end on a boundary so that a signature appended by a code signing tool
+  // directly follows the trailer
+  let unpadded_len = original_bin.len() as u64 + data_len + TRAILER_SIZE as u64;
+  let padding_len =
+    unpadded_len.next_multiple_of(SIGNATURE_ALIGNMENT) - unpadded_len;
+  if prepare_for_signing {
+    include_appended_data_in_image(
+      &mut original_bin,
+      data_len + padding_len + TRAILER_SIZE as u64,
+    )?;
+  }
+
   writer.write_all(&original_bin)?;
   writer.write_all(&eszip_archive)?;
   writer.write_all(&metadata)?;
@@ -194,6 +282,7 @@ fn write_binary_bytes(
   for file in npm_files {
     writer.write_all(file)?;
   }
+  writer.write_all(&vec![0; padding_len as usize])?;
 
   // write the trailer, which includes the positions
   // of the data blocks in the file
@@ -218,27 +307,15 @@ pub fn is_standalone_binary(exe_path: &Path) -> bool {
   let Ok(mut output_file) = std::fs::File::open(exe_path) else {
     return false;
   };
-  if output_file
-    .seek(SeekFrom::End(-(TRAILER_SIZE as i64)))
-    .is_err()
-  {
-    // This seek may fail because the file is too small to possibly be
-    // `deno compile` output.
-    return false;
-  }
-  let mut trailer = [0; TRAILER_SIZE];
-  if output_file.read_exact(&mut trailer).is_err() {
-    return false;
-  };
-  let (magic_trailer, _) = trailer.split_at(8);
-  magic_trailer == MAGIC_TRAILER
+  matches!(find_trailer(&mut output_file), Ok(Some(_)))
 }
 
 /// This function will try to run this binary as a standalone binary
 /// produced by `deno compile`. It determines if this is a standalone
-/// binary by skipping over the trailer width at the end of the file,
-/// then checking for the magic trailer string `d3n0l4nd`. If found,
-/// the bundle is executed. If not, this function exits with `Ok(None)`.
+/// binary by skipping over the trailer width at the end of the file (or in
+/// front of the signature of a signed binary), then checking for the magic
+/// trailer string `d3n0l4nd`. If found, the bundle is executed. If not, this
+/// function exits with `Ok(None)`.
 pub fn extract_standalone(
   exe_path: &Path,
   cli_args: Cow<Vec<OsString>>,
@@ -248,10 +325,7 @@ pub fn extract_standalone(
 > {
   // We do the first part sync so it can complete quickly
   let mut file = std::fs::File::open(exe_path)?;
-  file.seek(SeekFrom::End(-(TRAILER_SIZE as i64)))?;
-  let mut trailer = [0; TRAILER_SIZE];
-  file.read_exact(&mut trailer)?;
-  let trailer = match Trailer::parse(&trailer)? {
+  let trailer = match find_trailer(&mut file)? {
     None => return Ok(None),
     Some(trailer) => trailer,
   };
@@ -467,6 +541,49 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       set_windows_binary_to_gui(&mut original_binary)?;
     }
 
+    let has_resources = compile_flags.icon.is_some()
+      || compile_flags.product_name.is_some()
+      || compile_flags.version.is_some();
+    if has_resources {
+      let target = compile_flags.resolve_target();
+      if target.contains("windows") {
+        let resource_options = WindowsResourceOptions {
+          icon: match &compile_flags.icon {
+            Some(icon) => {
+              let icon_path = cli_options.initial_cwd().join(icon);
+              Some(std::fs::read(&icon_path).with_context(|| {
+                format!("Failed reading icon '{}'", icon_path.display())
+              })?)
+            }
+            None => None,
+          },
+          product_name: compile_flags.product_name.clone(),
+          version: compile_flags.version.clone(),
+        };
+        set_windows_binary_resources(&mut original_binary, &resource_options)
+          .context("Failed setting the resources of the executable")?;
+      } else if target.contains("apple-darwin") {
+        // executables outside of an app bundle can't have an icon
+        if compile_flags.icon.is_some() {
+          bail!(
+            "The `--icon` flag is only available when targeting Windows (current: {})",
+            target,
+          )
+        }
+        let resource_options = MacosResourceOptions {
+          product_name: compile_flags.product_name.clone(),
+          version: compile_flags.version.clone(),
+        };
+        set_macos_binary_resources(&mut original_binary, &resource_options)
+          .context("Failed setting the Info.plist of the executable")?;
+      } else {
+        bail!(
+          "The `--icon`, `--product-name` and `--version` flags are only available when targeting Windows or macOS (current: {})",
+          target,
+        )
+      }
+    }
+
     self
       .write_standalone_binary(
         writer,
@@ -487,14 +604,32 @@ impl<'a> DenoCompileBinaryWriter<'a> {
     //
     // Phase 2 of the 'min sized' deno compile RFC talks
     // about adding this as a flag.
-    if let Some(path) = std::env::var_os("DENORT_BIN") {
+    //
+    // The lite binary is given with `DENORT_LITE_BIN` instead, so that
+    // `--no-web-apis` never silently uses the full binary.
+    let env_var_name = if compile_flags.no_web_apis {
+      "DENORT_LITE_BIN"
+    } else {
+      "DENORT_BIN"
+    };
+    if let Some(path) = std::env::var_os(env_var_name) {
       return std::fs::read(&path).with_context(|| {
         format!("Could not find denort at '{}'", path.to_string_lossy())
       });
     }
+    if compile_flags.no_web_apis && std::env::var_os("DENORT_BIN").is_some() {
+      bail!(
+        "DENORT_LITE_BIN must be set to use --no-web-apis with DENORT_BIN."
+      );
+    }
 
     let target = compile_flags.resolve_target();
-    let binary_name = format!("denort-{target}.zip");
+    // the lite binary is built with the `lite` feature
+    let binary_name = if compile_flags.no_web_apis {
+      format!("denort-lite-{target}.zip")
+    } else {
+      format!("denort-{target}.zip")
+    };
 
     let binary_path_suffix = if crate::version::is_canary() {
       format!("canary/{}/{}", crate::version::GIT_COMMIT_HASH, binary_name)
@@ -507,7 +642,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
 
     if !binary_path.exists() {
       self
-        .download_base_binary(&download_directory, &binary_path_suffix)
+        .download_base_binary(&target, &binary_path_suffix, &binary_path)
         .await?;
     }
 
@@ -527,8 +662,9 @@ impl<'a> DenoCompileBinaryWriter<'a> {
 
   async fn download_base_binary(
     &self,
-    output_directory: &Path,
+    target: &str,
     binary_path_suffix: &str,
+    output_path: &Path,
   ) -> Result<(), AnyError> {
     let download_url = format!("https://dl.deno.land/{binary_path_suffix}");
     let maybe_bytes = {
@@ -537,21 +673,21 @@ impl<'a> DenoCompileBinaryWriter<'a> {
 
       self
         .client
-        .download_with_progress(download_url, &progress)
+        .download_with_progress(&download_url, &progress)
         .await?
     };
-    let bytes = match maybe_bytes {
-      Some(bytes) => bytes,
-      None => {
-        log::info!("Download could not be found, aborting");
-        std::process::exit(1)
-      }
+    let Some(bytes) = maybe_bytes else {
+      bail!(
+        "Could not find the base binary for target '{}' at {}. The binary may not be published for this version of Deno.",
+        target,
+        download_url,
+      );
     };
 
-    std::fs::create_dir_all(output_directory)?;
-    let output_path = output_directory.join(binary_path_suffix);
-    std::fs::create_dir_all(output_path.parent().unwrap())?;
-    tokio::fs::write(output_path, bytes).await?;
+    // write atomically so an interrupted download is never picked up
+    // from the cache in a later compilation
+    atomic_write_file(output_path, bytes, CACHE_PERM)
+      .with_context(|| format!("Writing {}", output_path.display()))?;
     Ok(())
   }
 
@@ -578,7 +714,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       .resolve_import_map(self.file_fetcher)
       .await?
       .map(|import_map| (import_map.base_url().clone(), import_map.to_json()));
-    let (npm_vfs, npm_files, node_modules) =
+    let (npm_vfs, mut npm_files, node_modules) =
       match self.npm_resolver.as_inner() {
         InnerCliNpmResolverRef::Managed(managed) => {
           let snapshot =
@@ -617,21 +753,41 @@ impl<'a> DenoCompileBinaryWriter<'a> {
         }
       };
 
+    // the files of the embedded directories are stored after the npm files
+    let mut files_offset =
+      npm_files.iter().map(|file| file.len() as u64).sum::<u64>();
+    let mut embedded_dirs = Vec::new();
+    for root_path in resolve_include_dirs(compile_flags, cli_options) {
+      let mut builder = VfsBuilder::new(root_path.clone())?;
+      builder.add_dir_recursive(&root_path)?;
+      let (dir, files) = builder.into_dir_and_files();
+      embedded_dirs.push(EmbeddedDirectory {
+        root_path,
+        dir,
+        files_offset,
+      });
+      files_offset += files.iter().map(|file| file.len() as u64).sum::<u64>();
+      npm_files.extend(files);
+    }
+
     let metadata = Metadata {
       argv: compile_flags.args.clone(),
       seed: cli_options.seed(),
       location: cli_options.location_flag().clone(),
+      local_storage_quota: cli_options.local_storage_quota(),
       permissions: cli_options.permission_flags().clone(),
       v8_flags: cli_options.v8_flags().clone(),
       unsafely_ignore_certificate_errors: cli_options
         .unsafely_ignore_certificate_errors()
         .clone(),
+      http1_only: cli_options.http1_only(),
       log_level: cli_options.log_level(),
       ca_stores: cli_options.ca_stores().clone(),
       ca_data,
       entrypoint: entrypoint.clone(),
       maybe_import_map,
       node_modules,
+      embedded_dirs,
       disable_deprecated_api_warning: cli_options
         .disable_deprecated_api_warning,
       unstable_config: UnstableConfig {
@@ -650,6 +806,7 @@ impl<'a> DenoCompileBinaryWriter<'a> {
       eszip,
       npm_vfs.as_ref(),
       &npm_files,
+      compile_flags.sign_cmd.is_some(),
     )
   }
 
@@ -704,6 +861,20 @@ impl<'a> DenoCompileBinaryWriter<'a> {
   }
 }
 
+/// Gets the directories passed to `--include`, which are embedded in the
+/// binary instead of being added to the module graph.
+pub fn resolve_include_dirs(
+  compile_flags: &CompileFlags,
+  cli_options: &CliOptions,
+) -> Vec<PathBuf> {
+  compile_flags
+    .include
+    .iter()
+    .map(|include| normalize_path(cli_options.initial_cwd().join(include)))
+    .filter(|path| path.is_dir())
+    .collect()
+}
+
 /// This function sets the subsystem field in the PE header to 2 (GUI subsystem)
 /// For more information about the PE header: https://learn.microsoft.com/en-us/windows/win32/debug/pe-format
 fn set_windows_binary_to_gui(bin: &mut [u8]) -> Result<(), AnyError> {
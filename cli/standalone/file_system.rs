@@ -19,24 +19,33 @@ use deno_runtime::deno_io::fs::FsStat;
 use super::virtual_fs::FileBackedVfs;
 
 #[derive(Debug, Clone)]
-pub struct DenoCompileFileSystem(Arc<FileBackedVfs>);
+pub struct DenoCompileFileSystem(Arc<Vec<Arc<FileBackedVfs>>>);
 
 impl DenoCompileFileSystem {
-  pub fn new(vfs: FileBackedVfs) -> Self {
+  pub fn new(vfs: Vec<Arc<FileBackedVfs>>) -> Self {
     Self(Arc::new(vfs))
   }
 
+  fn find_vfs(&self, path: &Path) -> Option<&Arc<FileBackedVfs>> {
+    self.0.iter().find(|vfs| vfs.is_path_within(path))
+  }
+
   fn error_if_in_vfs(&self, path: &Path) -> FsResult<()> {
-    if self.0.is_path_within(path) {
+    if self.find_vfs(path).is_some() {
       Err(FsError::NotSupported)
     } else {
       Ok(())
     }
   }
 
-  fn copy_to_real_path(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
-    let old_file = self.0.file_entry(oldpath)?;
-    let old_file_bytes = self.0.read_file_all(old_file)?;
+  fn copy_to_real_path(
+    &self,
+    vfs: &FileBackedVfs,
+    oldpath: &Path,
+    newpath: &Path,
+  ) -> FsResult<()> {
+    let old_file = vfs.file_entry(oldpath)?;
+    let old_file_bytes = vfs.read_file_all(old_file)?;
     RealFs.write_file_sync(
       newpath,
       OpenOptions {
@@ -79,8 +88,8 @@ impl FileSystem for DenoCompileFileSystem {
     options: OpenOptions,
     access_check: Option<AccessCheckCb>,
   ) -> FsResult<Rc<dyn File>> {
-    if self.0.is_path_within(path) {
-      Ok(self.0.open_file(path)?)
+    if let Some(vfs) = self.find_vfs(path) {
+      Ok(vfs.open_file(path)?)
     } else {
       RealFs.open_sync(path, options, access_check)
     }
@@ -91,8 +100,8 @@ impl FileSystem for DenoCompileFileSystem {
     options: OpenOptions,
     access_check: Option<AccessCheckCb<'a>>,
   ) -> FsResult<Rc<dyn File>> {
-    if self.0.is_path_within(&path) {
-      Ok(self.0.open_file(&path)?)
+    if let Some(vfs) = self.find_vfs(&path) {
+      Ok(vfs.open_file(&path)?)
     } else {
       RealFs.open_async(path, options, access_check).await
     }
@@ -156,8 +165,8 @@ impl FileSystem for DenoCompileFileSystem {
 
   fn copy_file_sync(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
     self.error_if_in_vfs(newpath)?;
-    if self.0.is_path_within(oldpath) {
-      self.copy_to_real_path(oldpath, newpath)
+    if let Some(vfs) = self.find_vfs(oldpath) {
+      self.copy_to_real_path(vfs, oldpath, newpath)
     } else {
       RealFs.copy_file_sync(oldpath, newpath)
     }
@@ -168,10 +177,11 @@ impl FileSystem for DenoCompileFileSystem {
     newpath: PathBuf,
   ) -> FsResult<()> {
     self.error_if_in_vfs(&newpath)?;
-    if self.0.is_path_within(&oldpath) {
+    if let Some(vfs) = self.find_vfs(&oldpath) {
       let fs = self.clone();
+      let vfs = vfs.clone();
       tokio::task::spawn_blocking(move || {
-        fs.copy_to_real_path(&oldpath, &newpath)
+        fs.copy_to_real_path(&vfs, &oldpath, &newpath)
       })
       .await?
     } else {
@@ -191,60 +201,60 @@ impl FileSystem for DenoCompileFileSystem {
   }
 
   fn stat_sync(&self, path: &Path) -> FsResult<FsStat> {
-    if self.0.is_path_within(path) {
-      Ok(self.0.stat(path)?)
+    if let Some(vfs) = self.find_vfs(path) {
+      Ok(vfs.stat(path)?)
     } else {
       RealFs.stat_sync(path)
     }
   }
   async fn stat_async(&self, path: PathBuf) -> FsResult<FsStat> {
-    if self.0.is_path_within(&path) {
-      Ok(self.0.stat(&path)?)
+    if let Some(vfs) = self.find_vfs(&path) {
+      Ok(vfs.stat(&path)?)
     } else {
       RealFs.stat_async(path).await
     }
   }
 
   fn lstat_sync(&self, path: &Path) -> FsResult<FsStat> {
-    if self.0.is_path_within(path) {
-      Ok(self.0.lstat(path)?)
+    if let Some(vfs) = self.find_vfs(path) {
+      Ok(vfs.lstat(path)?)
     } else {
       RealFs.lstat_sync(path)
     }
   }
   async fn lstat_async(&self, path: PathBuf) -> FsResult<FsStat> {
-    if self.0.is_path_within(&path) {
-      Ok(self.0.lstat(&path)?)
+    if let Some(vfs) = self.find_vfs(&path) {
+      Ok(vfs.lstat(&path)?)
     } else {
       RealFs.lstat_async(path).await
     }
   }
 
   fn realpath_sync(&self, path: &Path) -> FsResult<PathBuf> {
-    if self.0.is_path_within(path) {
-      Ok(self.0.canonicalize(path)?)
+    if let Some(vfs) = self.find_vfs(path) {
+      Ok(vfs.canonicalize(path)?)
     } else {
       RealFs.realpath_sync(path)
     }
   }
   async fn realpath_async(&self, path: PathBuf) -> FsResult<PathBuf> {
-    if self.0.is_path_within(&path) {
-      Ok(self.0.canonicalize(&path)?)
+    if let Some(vfs) = self.find_vfs(&path) {
+      Ok(vfs.canonicalize(&path)?)
     } else {
       RealFs.realpath_async(path).await
     }
   }
 
   fn read_dir_sync(&self, path: &Path) -> FsResult<Vec<FsDirEntry>> {
-    if self.0.is_path_within(path) {
-      Ok(self.0.read_dir(path)?)
+    if let Some(vfs) = self.find_vfs(path) {
+      Ok(vfs.read_dir(path)?)
     } else {
       RealFs.read_dir_sync(path)
     }
   }
   async fn read_dir_async(&self, path: PathBuf) -> FsResult<Vec<FsDirEntry>> {
-    if self.0.is_path_within(&path) {
-      Ok(self.0.read_dir(&path)?)
+    if let Some(vfs) = self.find_vfs(&path) {
+      Ok(vfs.read_dir(&path)?)
     } else {
       RealFs.read_dir_async(path).await
     }
@@ -302,15 +312,15 @@ impl FileSystem for DenoCompileFileSystem {
   }
 
   fn read_link_sync(&self, path: &Path) -> FsResult<PathBuf> {
-    if self.0.is_path_within(path) {
-      Ok(self.0.read_link(path)?)
+    if let Some(vfs) = self.find_vfs(path) {
+      Ok(vfs.read_link(path)?)
     } else {
       RealFs.read_link_sync(path)
     }
   }
   async fn read_link_async(&self, path: PathBuf) -> FsResult<PathBuf> {
-    if self.0.is_path_within(&path) {
-      Ok(self.0.read_link(&path)?)
+    if let Some(vfs) = self.find_vfs(&path) {
+      Ok(vfs.read_link(&path)?)
     } else {
       RealFs.read_link_async(path).await
     }
@@ -349,4 +359,75 @@ impl FileSystem for DenoCompileFileSystem {
       .utime_async(path, atime_secs, atime_nanos, mtime_secs, mtime_nanos)
       .await
   }
+
+  fn get_xattr_sync(
+    &self,
+    path: &Path,
+    name: &str,
+  ) -> FsResult<Option<Vec<u8>>> {
+    self.error_if_in_vfs(path)?;
+    RealFs.get_xattr_sync(path, name)
+  }
+  async fn get_xattr_async(
+    &self,
+    path: PathBuf,
+    name: String,
+  ) -> FsResult<Option<Vec<u8>>> {
+    self.error_if_in_vfs(&path)?;
+    RealFs.get_xattr_async(path, name).await
+  }
+
+  fn set_xattr_sync(
+    &self,
+    path: &Path,
+    name: &str,
+    value: &[u8],
+  ) -> FsResult<()> {
+    self.error_if_in_vfs(path)?;
+    RealFs.set_xattr_sync(path, name, value)
+  }
+  async fn set_xattr_async(
+    &self,
+    path: PathBuf,
+    name: String,
+    value: Vec<u8>,
+  ) -> FsResult<()> {
+    self.error_if_in_vfs(&path)?;
+    RealFs.set_xattr_async(path, name, value).await
+  }
+
+  fn remove_xattr_sync(&self, path: &Path, name: &str) -> FsResult<()> {
+    self.error_if_in_vfs(path)?;
+    RealFs.remove_xattr_sync(path, name)
+  }
+  async fn remove_xattr_async(
+    &self,
+    path: PathBuf,
+    name: String,
+  ) -> FsResult<()> {
+    self.error_if_in_vfs(&path)?;
+    RealFs.remove_xattr_async(path, name).await
+  }
+
+  fn list_xattrs_sync(&self, path: &Path) -> FsResult<Vec<String>> {
+    self.error_if_in_vfs(path)?;
+    RealFs.list_xattrs_sync(path)
+  }
+  async fn list_xattrs_async(&self, path: PathBuf) -> FsResult<Vec<String>> {
+    self.error_if_in_vfs(&path)?;
+    RealFs.list_xattrs_async(path).await
+  }
+
+  fn set_immutable_sync(&self, path: &Path, immutable: bool) -> FsResult<()> {
+    self.error_if_in_vfs(path)?;
+    RealFs.set_immutable_sync(path, immutable)
+  }
+  async fn set_immutable_async(
+    &self,
+    path: PathBuf,
+    immutable: bool,
+  ) -> FsResult<()> {
+    self.error_if_in_vfs(&path)?;
+    RealFs.set_immutable_async(path, immutable).await
+  }
 }
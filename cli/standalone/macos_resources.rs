@@ -0,0 +1,293 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Writes the product name and version of a macOS executable produced by
+//! `deno compile`.
+//!
+//! Executables that aren't part of an app bundle describe themselves with an
+//! `Info.plist` embedded in the `__TEXT,__info_plist` section, which is what
+//! `ld -sectcreate __TEXT __info_plist` produces. The linker places the
+//! sections of the `__TEXT` segment at its end, so the space between the load
+//! commands and the first section is used for the new section header and the
+//! property list, which keeps the offsets of everything else unchanged.
+
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+
+use super::windows_resources::parse_version;
+
+const MH_MAGIC_64: u32 = 0xfeedfacf;
+const MACH_HEADER_64_SIZE: usize = 32;
+const LC_SEGMENT_64: u32 = 0x19;
+const SEGMENT_COMMAND_64_SIZE: usize = 72;
+const SECTION_64_SIZE: usize = 80;
+const TEXT_SEGMENT_NAME: &[u8; 16] = b"__TEXT\0\0\0\0\0\0\0\0\0\0";
+const INFO_PLIST_SECTION_NAME: &[u8; 16] = b"__info_plist\0\0\0\0";
+
+#[derive(Debug, Default)]
+pub struct MacosResourceOptions {
+  pub product_name: Option<String>,
+  pub version: Option<String>,
+}
+
+/// Sets the product name and version of a macOS executable.
+pub fn set_macos_binary_resources(
+  bin: &mut [u8],
+  options: &MacosResourceOptions,
+) -> Result<(), AnyError> {
+  if options.product_name.is_none() && options.version.is_none() {
+    return Ok(());
+  }
+  if let Some(version) = &options.version {
+    parse_version(version)?;
+  }
+  let plist = build_info_plist(
+    options.product_name.as_deref(),
+    options.version.as_deref(),
+  );
+
+  if read_u32(bin, 0)? != MH_MAGIC_64 {
+    bail!("The base binary is not a 64-bit macOS executable.");
+  }
+  let count = read_u32(bin, 16)? as usize;
+  let commands_size = read_u32(bin, 20)? as usize;
+  let commands_end = MACH_HEADER_64_SIZE + commands_size;
+
+  let mut text_segment = None;
+  // the file offset of the first section, which is where the padding ends
+  let mut first_section_offset = bin.len();
+  let mut offset = MACH_HEADER_64_SIZE;
+  for _ in 0..count {
+    let cmd = read_u32(bin, offset)?;
+    let cmd_size = read_u32(bin, offset + 4)? as usize;
+    if cmd_size < 8 || offset + cmd_size > commands_end {
+      bail!("Invalid Mach-O load command.");
+    }
+    if cmd == LC_SEGMENT_64 {
+      let number_of_sections = read_u32(bin, offset + 64)? as usize;
+      for i in 0..number_of_sections {
+        let section = offset + SEGMENT_COMMAND_64_SIZE + i * SECTION_64_SIZE;
+        if bin.get(section..section + 16)
+          == Some(INFO_PLIST_SECTION_NAME.as_slice())
+        {
+          bail!("The base binary already contains an Info.plist.");
+        }
+        let section_offset = read_u32(bin, section + 48)? as usize;
+        // zero fill sections have no contents in the file
+        if section_offset != 0 {
+          first_section_offset = first_section_offset.min(section_offset);
+        }
+      }
+      if bin.get(offset + 8..offset + 24) == Some(TEXT_SEGMENT_NAME.as_slice())
+      {
+        text_segment = Some((offset, cmd_size));
+      }
+    }
+    offset += cmd_size;
+  }
+  let Some((text_offset, text_size)) = text_segment else {
+    bail!("Could not find the __TEXT segment of the base binary.");
+  };
+
+  let plist_offset = commands_end + SECTION_64_SIZE;
+  if plist_offset + plist.len() > first_section_offset {
+    bail!(
+      "The base binary doesn't have enough space after its load commands for an Info.plist ({} bytes needed, {} available).",
+      SECTION_64_SIZE + plist.len(),
+      first_section_offset.saturating_sub(commands_end),
+    );
+  }
+
+  // insert the section header after the last section of the __TEXT segment
+  // by moving the load commands after it
+  let section = text_offset + text_size;
+  bin.copy_within(section..commands_end, section + SECTION_64_SIZE);
+  let text_vm_address = read_u64(bin, text_offset + 24)?;
+  let text_file_offset = read_u64(bin, text_offset + 40)?;
+  let header = &mut bin[section..section + SECTION_64_SIZE];
+  header.fill(0);
+  header[0..16].copy_from_slice(INFO_PLIST_SECTION_NAME);
+  header[16..32].copy_from_slice(TEXT_SEGMENT_NAME);
+  let address = text_vm_address + plist_offset as u64 - text_file_offset;
+  header[32..40].copy_from_slice(&address.to_le_bytes());
+  header[40..48].copy_from_slice(&(plist.len() as u64).to_le_bytes());
+  header[48..52].copy_from_slice(&(plist_offset as u32).to_le_bytes());
+  bin[plist_offset..plist_offset + plist.len()].copy_from_slice(&plist);
+
+  let number_of_sections = read_u32(bin, text_offset + 64)? + 1;
+  write_u32(bin, text_offset + 64, number_of_sections);
+  write_u32(bin, text_offset + 4, (text_size + SECTION_64_SIZE) as u32);
+  write_u32(bin, 20, (commands_size + SECTION_64_SIZE) as u32);
+  Ok(())
+}
+
+/// Builds the XML property list with the bundle name and version.
+fn build_info_plist(
+  product_name: Option<&str>,
+  version: Option<&str>,
+) -> Vec<u8> {
+  let mut entries = vec![("CFBundleInfoDictionaryVersion", "6.0")];
+  if let Some(product_name) = product_name {
+    entries.push(("CFBundleName", product_name));
+  }
+  if let Some(version) = version {
+    entries.push(("CFBundleShortVersionString", version));
+    entries.push(("CFBundleVersion", version));
+  }
+  let mut plist = String::from(concat!(
+    "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+    "<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" ",
+    "\"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">\n",
+    "<plist version=\"1.0\">\n<dict>\n",
+  ));
+  for (key, value) in entries {
+    plist.push_str(&format!(
+      "  <key>{key}</key>\n  <string>{}</string>\n",
+      escape_xml(value)
+    ));
+  }
+  plist.push_str("</dict>\n</plist>\n");
+  plist.into_bytes()
+}
+
+fn escape_xml(text: &str) -> String {
+  let mut out = String::with_capacity(text.len());
+  for c in text.chars() {
+    match c {
+      '&' => out.push_str("&amp;"),
+      '<' => out.push_str("&lt;"),
+      '>' => out.push_str("&gt;"),
+      '"' => out.push_str("&quot;"),
+      '\'' => out.push_str("&apos;"),
+      c => out.push(c),
+    }
+  }
+  out
+}
+
+fn read_u32(bin: &[u8], offset: usize) -> Result<u32, AnyError> {
+  match bin.get(offset..offset + 4) {
+    Some(bytes) => Ok(u32::from_le_bytes(bytes.try_into().unwrap())),
+    None => bail!("Unexpected end of the executable at offset {offset}."),
+  }
+}
+
+fn read_u64(bin: &[u8], offset: usize) -> Result<u64, AnyError> {
+  match bin.get(offset..offset + 8) {
+    Some(bytes) => Ok(u64::from_le_bytes(bytes.try_into().unwrap())),
+    None => bail!("Unexpected end of the executable at offset {offset}."),
+  }
+}
+
+fn write_u32(bin: &mut [u8], offset: usize, value: u32) {
+  bin[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  /// Creates a Mach-O binary with a __TEXT segment that contains a single
+  /// section at `text_section_offset`, followed by a __LINKEDIT segment.
+  fn create_macho_binary(text_section_offset: u32) -> Vec<u8> {
+    let mut bin = vec![0; 0x2000];
+    write_u32(&mut bin, 0, MH_MAGIC_64);
+    write_u32(&mut bin, 16, 2);
+    let text_size = SEGMENT_COMMAND_64_SIZE + SECTION_64_SIZE;
+    let commands_size = text_size + SEGMENT_COMMAND_64_SIZE;
+    write_u32(&mut bin, 20, commands_size as u32);
+    // LC_SEGMENT_64 __TEXT
+    let text = MACH_HEADER_64_SIZE;
+    write_u32(&mut bin, text, LC_SEGMENT_64);
+    write_u32(&mut bin, text + 4, text_size as u32);
+    bin[text + 8..text + 24].copy_from_slice(TEXT_SEGMENT_NAME);
+    bin[text + 24..text + 32].copy_from_slice(&0x100000000u64.to_le_bytes());
+    write_u32(&mut bin, text + 64, 1);
+    let section = text + SEGMENT_COMMAND_64_SIZE;
+    bin[section..section + 6].copy_from_slice(b"__text");
+    bin[section + 16..section + 32].copy_from_slice(TEXT_SEGMENT_NAME);
+    write_u32(&mut bin, section + 48, text_section_offset);
+    // LC_SEGMENT_64 __LINKEDIT
+    let linkedit = text + text_size;
+    write_u32(&mut bin, linkedit, LC_SEGMENT_64);
+    write_u32(&mut bin, linkedit + 4, SEGMENT_COMMAND_64_SIZE as u32);
+    bin[linkedit + 8..linkedit + 18].copy_from_slice(b"__LINKEDIT");
+    bin[linkedit + 40..linkedit + 48].copy_from_slice(&0x1800u64.to_le_bytes());
+    bin
+  }
+
+  #[test]
+  fn sets_info_plist() {
+    let mut bin = create_macho_binary(0x1000);
+    let original = bin.clone();
+    set_macos_binary_resources(
+      &mut bin,
+      &MacosResourceOptions {
+        product_name: Some("My <App>".to_string()),
+        version: Some("1.2.3".to_string()),
+      },
+    )
+    .unwrap();
+
+    assert_eq!(bin.len(), original.len());
+    assert_eq!(read_u32(&bin, 16).unwrap(), 2);
+    let text = MACH_HEADER_64_SIZE;
+    assert_eq!(read_u32(&bin, text + 64).unwrap(), 2);
+    assert_eq!(
+      read_u32(&bin, text + 4).unwrap() as usize,
+      SEGMENT_COMMAND_64_SIZE + 2 * SECTION_64_SIZE
+    );
+    // the __LINKEDIT segment was moved after the new section header
+    let linkedit = text + SEGMENT_COMMAND_64_SIZE + 2 * SECTION_64_SIZE;
+    assert_eq!(&bin[linkedit + 8..linkedit + 18], b"__LINKEDIT");
+    assert_eq!(read_u64(&bin, linkedit + 40).unwrap(), 0x1800);
+    let commands_end =
+      MACH_HEADER_64_SIZE + read_u32(&bin, 20).unwrap() as usize;
+    assert_eq!(commands_end, linkedit + SEGMENT_COMMAND_64_SIZE);
+
+    let section = text + SEGMENT_COMMAND_64_SIZE + SECTION_64_SIZE;
+    assert_eq!(&bin[section..section + 16], INFO_PLIST_SECTION_NAME);
+    assert_eq!(&bin[section + 16..section + 32], TEXT_SEGMENT_NAME);
+    let offset = read_u32(&bin, section + 48).unwrap() as usize;
+    let size = read_u64(&bin, section + 40).unwrap() as usize;
+    assert_eq!(
+      read_u64(&bin, section + 32).unwrap(),
+      0x100000000 + offset as u64
+    );
+    let plist = std::str::from_utf8(&bin[offset..offset + size]).unwrap();
+    assert!(plist
+      .contains("<key>CFBundleName</key>\n  <string>My &lt;App&gt;</string>"));
+    assert!(plist.contains(
+      "<key>CFBundleShortVersionString</key>\n  <string>1.2.3</string>"
+    ));
+    // everything after the padding is unchanged
+    assert_eq!(bin[0x1000..], original[0x1000..]);
+  }
+
+  #[test]
+  fn errors_without_space_for_info_plist() {
+    let mut bin = create_macho_binary(0x200);
+    let err = set_macos_binary_resources(
+      &mut bin,
+      &MacosResourceOptions {
+        product_name: Some("My App".to_string()),
+        version: None,
+      },
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("doesn't have enough space"));
+  }
+
+  #[test]
+  fn errors_for_invalid_version() {
+    let mut bin = create_macho_binary(0x1000);
+    let err = set_macos_binary_resources(
+      &mut bin,
+      &MacosResourceOptions {
+        product_name: None,
+        version: Some("latest".to_string()),
+      },
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("Invalid version 'latest'"));
+  }
+}
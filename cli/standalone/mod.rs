@@ -42,6 +42,7 @@ use deno_core::ModuleSpecifier;
 use deno_core::ModuleType;
 use deno_core::RequestedModuleType;
 use deno_core::ResolutionKind;
+use deno_runtime::deno_fetch;
 use deno_runtime::deno_fs;
 use deno_runtime::deno_node::analyze::NodeCodeTranslator;
 use deno_runtime::deno_node::NodeResolutionMode;
@@ -54,17 +55,24 @@ use deno_runtime::WorkerExecutionMode;
 use deno_runtime::WorkerLogLevel;
 use deno_semver::npm::NpmPackageReqReference;
 use import_map::parse_from_json;
+use std::path::Path;
 use std::rc::Rc;
 use std::sync::Arc;
 
 pub mod binary;
 mod file_system;
+mod macos_resources;
+mod signing;
 mod virtual_fs;
+mod windows_resources;
 
 pub use binary::extract_standalone;
 pub use binary::is_standalone_binary;
+pub use binary::resolve_include_dirs;
 pub use binary::DenoCompileBinaryWriter;
+pub use signing::run_sign_cmd;
 
+use self::binary::load_embedded_dir_vfs;
 use self::binary::load_npm_vfs;
 use self::binary::Metadata;
 use self::file_system::DenoCompileFileSystem;
@@ -351,6 +359,30 @@ pub async fn run(
   let npm_cache_dir = NpmCacheDir::new(root_path.clone());
   let npm_global_cache_dir = npm_cache_dir.get_cache_location();
   let cache_setting = CacheSetting::Only;
+  let mut embedded_vfs = Vec::with_capacity(metadata.embedded_dirs.len());
+  for embedded_dir in metadata.embedded_dirs {
+    let vfs = load_embedded_dir_vfs(embedded_dir)
+      .context("Failed to load embedded directory vfs.")?;
+    embedded_vfs.push(Arc::new(vfs));
+  }
+  let embedded_vfs_roots = embedded_vfs
+    .iter()
+    .map(|vfs| vfs.root().to_path_buf())
+    .collect::<Vec<_>>();
+  let fs_fetch_read_file: Option<deno_fetch::FsFetchReadFileFn> =
+    if embedded_vfs.is_empty() {
+      None
+    } else {
+      let embedded_vfs = embedded_vfs.clone();
+      Some(Arc::new(move |path: &Path| {
+        let vfs = embedded_vfs.iter().find(|vfs| vfs.is_path_within(path))?;
+        Some(
+          vfs
+            .file_entry(path)
+            .and_then(|file| vfs.read_file_all(file)),
+        )
+      }))
+    };
   let (package_json_deps_provider, fs, npm_resolver, maybe_vfs_root) =
     match metadata.node_modules {
       Some(binary::NodeModules::Managed {
@@ -375,8 +407,9 @@ pub async fn run(
           Arc::new(PackageJsonDepsProvider::new(
             package_json_deps.map(|serialized| serialized.into_deps()),
           ));
-        let fs = Arc::new(DenoCompileFileSystem::new(vfs))
-          as Arc<dyn deno_fs::FileSystem>;
+        let fs = Arc::new(DenoCompileFileSystem::new(
+          std::iter::once(Arc::new(vfs)).chain(embedded_vfs).collect(),
+        )) as Arc<dyn deno_fs::FileSystem>;
         let npm_resolver = create_cli_npm_resolver(
           CliNpmResolverCreateOptions::Managed(CliNpmResolverManagedCreateOptions {
             snapshot: CliNpmResolverManagedSnapshotOption::Specified(Some(snapshot)),
@@ -412,8 +445,9 @@ pub async fn run(
           Arc::new(PackageJsonDepsProvider::new(
             package_json_deps.map(|serialized| serialized.into_deps()),
           ));
-        let fs = Arc::new(DenoCompileFileSystem::new(vfs))
-          as Arc<dyn deno_fs::FileSystem>;
+        let fs = Arc::new(DenoCompileFileSystem::new(
+          std::iter::once(Arc::new(vfs)).chain(embedded_vfs).collect(),
+        )) as Arc<dyn deno_fs::FileSystem>;
         let npm_resolver =
           create_cli_npm_resolver(CliNpmResolverCreateOptions::Byonm(
             CliNpmResolverByonmCreateOptions {
@@ -432,7 +466,12 @@ pub async fn run(
       None => {
         let package_json_deps_provider =
           Arc::new(PackageJsonDepsProvider::new(None));
-        let fs = Arc::new(deno_fs::RealFs) as Arc<dyn deno_fs::FileSystem>;
+        let fs = if embedded_vfs.is_empty() {
+          Arc::new(deno_fs::RealFs) as Arc<dyn deno_fs::FileSystem>
+        } else {
+          Arc::new(DenoCompileFileSystem::new(embedded_vfs))
+            as Arc<dyn deno_fs::FileSystem>
+        };
         let npm_resolver = create_cli_npm_resolver(
           CliNpmResolverCreateOptions::Managed(CliNpmResolverManagedCreateOptions {
             snapshot: CliNpmResolverManagedSnapshotOption::Specified(None),
@@ -502,8 +541,9 @@ pub async fn run(
     let maybe_cwd = std::env::current_dir().ok();
     let mut permissions =
       metadata.permissions.to_options(maybe_cwd.as_deref())?;
-    // if running with an npm vfs, grant read access to it
-    if let Some(vfs_root) = maybe_vfs_root {
+    // if running with an npm vfs or embedded directories, grant read
+    // access to them
+    for vfs_root in maybe_vfs_root.into_iter().chain(embedded_vfs_roots) {
       match &mut permissions.allow_read {
         Some(vec) if vec.is_empty() => {
           // do nothing, already granted
@@ -569,13 +609,21 @@ pub async fn run(
         .or(std::env::args().next()),
       node_debug: std::env::var("NODE_DEBUG").ok(),
       origin_data_folder_path: None,
+      // compiled programs have no storage origin, so their BroadcastChannel
+      // messages only reach the workers of the same process
+      broadcast_channel_folder_path: None,
+      local_storage_quota: metadata.local_storage_quota,
       seed: metadata.seed,
       unsafely_ignore_certificate_errors: metadata
         .unsafely_ignore_certificate_errors,
+      http1_only: metadata.http1_only,
       unstable: metadata.unstable_config.legacy_flag_enabled,
       maybe_root_package_json_deps: package_json_deps_provider.deps().cloned(),
       create_hmr_runner: None,
       create_coverage_collector: None,
+      fs_fetch_read_file,
+      env_var_overrides: None,
+      permission_policy: None,
     },
     None,
     None,
@@ -0,0 +1,269 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Support for signing the executables produced by `deno compile`.
+//!
+//! The code signing tools append the signature to the end of the file, which
+//! is where the trailer describing the embedded data is stored. In order for
+//! the trailer to still be found, the data written by `deno compile` ends on a
+//! 16 byte boundary, so that the signature is placed directly after it, and
+//! the trailer is then searched for in front of the signature.
+
+use std::collections::HashMap;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+use std::path::Path;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use tokio::task::LocalSet;
+
+use super::windows_resources::remove_windows_signature;
+use super::windows_resources::windows_signature_offset;
+
+/// The alignment of the end of the data written by `deno compile`. Authenticode
+/// signatures are aligned to 8 bytes and Mach-O code signatures to 16 bytes.
+pub const SIGNATURE_ALIGNMENT: u64 = 16;
+
+const MH_MAGIC_64: u32 = 0xfeedfacf;
+const MACH_HEADER_64_SIZE: usize = 32;
+const LC_SEGMENT_64: u32 = 0x19;
+const LC_CODE_SIGNATURE: u32 = 0x1d;
+const LINKEDIT_SEGMENT_NAME: &[u8; 16] = b"__LINKEDIT\0\0\0\0\0\0";
+const MACHO_PAGE_SIZE: u64 = 0x4000;
+
+/// Removes the existing signature of the base binary, because the signing
+/// tools require the signature to be at the end of the file.
+pub fn remove_binary_signature(bin: &mut Vec<u8>) -> Result<(), AnyError> {
+  if bin.starts_with(b"MZ") {
+    remove_windows_signature(bin)
+  } else if read_u32(bin, 0) == Some(MH_MAGIC_64) {
+    remove_macho_signature(bin)
+  } else {
+    // other formats, such as ELF, have no embedded signature
+    Ok(())
+  }
+}
+
+/// Makes the `appended_len` bytes written after the base binary part of the
+/// executable image. This is necessary for Mach-O binaries, because `codesign`
+/// refuses to sign files with data after the `__LINKEDIT` segment.
+pub fn include_appended_data_in_image(
+  bin: &mut [u8],
+  appended_len: u64,
+) -> Result<(), AnyError> {
+  if read_u32(bin, 0) != Some(MH_MAGIC_64) {
+    return Ok(());
+  }
+  let Some(linkedit_offset) =
+    MachOLoadCommands::parse(bin)?.linkedit_segment(bin)
+  else {
+    bail!("Could not find the __LINKEDIT segment of the base binary.");
+  };
+  let file_offset = read_u64(bin, linkedit_offset + 40).unwrap();
+  let file_size = bin.len() as u64 + appended_len - file_offset;
+  let vm_size = file_size.div_ceil(MACHO_PAGE_SIZE) * MACHO_PAGE_SIZE;
+  bin[linkedit_offset + 32..linkedit_offset + 40]
+    .copy_from_slice(&vm_size.to_le_bytes());
+  bin[linkedit_offset + 48..linkedit_offset + 56]
+    .copy_from_slice(&file_size.to_le_bytes());
+  Ok(())
+}
+
+/// Gets the file offset of the embedded signature of a signed executable.
+pub fn signature_offset(
+  file: &mut std::fs::File,
+) -> Result<Option<u64>, AnyError> {
+  // the headers of deno executables fit in this size
+  const HEADERS_SIZE: u64 = 16 * 1024;
+  file.seek(SeekFrom::Start(0))?;
+  let mut headers = Vec::new();
+  file.take(HEADERS_SIZE).read_to_end(&mut headers)?;
+  if headers.starts_with(b"MZ") {
+    Ok(windows_signature_offset(&headers))
+  } else if read_u32(&headers, 0) == Some(MH_MAGIC_64) {
+    Ok(
+      MachOLoadCommands::parse(&headers)
+        .ok()
+        .and_then(|commands| commands.code_signature(&headers))
+        .map(|(offset, _)| offset as u64),
+    )
+  } else {
+    Ok(None)
+  }
+}
+
+/// Runs the command provided via `--sign-cmd` with the path of the
+/// executable appended as the last argument.
+pub async fn run_sign_cmd(
+  sign_cmd: &str,
+  executable_path: &Path,
+  cwd: &Path,
+) -> Result<(), AnyError> {
+  let script = format!(
+    "{} \"{}\"",
+    sign_cmd,
+    executable_path
+      .to_string_lossy()
+      .replace('"', "\\\"")
+      .replace('$', "\\$")
+  );
+  let seq_list = deno_task_shell::parser::parse(&script)
+    .with_context(|| format!("Error parsing sign command '{}'.", sign_cmd))?;
+  let env_vars = std::env::vars().collect::<HashMap<String, String>>();
+  let local = LocalSet::new();
+  let future =
+    deno_task_shell::execute(seq_list, env_vars, cwd, Default::default());
+  let exit_code = local.run_until(future).await;
+  if exit_code != 0 {
+    bail!("Sign command failed with exit code {}.", exit_code);
+  }
+  Ok(())
+}
+
+fn read_u32(bin: &[u8], offset: usize) -> Option<u32> {
+  let bytes = bin.get(offset..offset + 4)?;
+  Some(u32::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+fn read_u64(bin: &[u8], offset: usize) -> Option<u64> {
+  let bytes = bin.get(offset..offset + 8)?;
+  Some(u64::from_le_bytes(bytes.try_into().unwrap()))
+}
+
+/// The offsets of the load commands of a 64-bit Mach-O binary.
+struct MachOLoadCommands {
+  commands: Vec<(u32, usize, usize)>,
+}
+
+impl MachOLoadCommands {
+  fn parse(bin: &[u8]) -> Result<Self, AnyError> {
+    let (Some(count), Some(size)) = (read_u32(bin, 16), read_u32(bin, 20))
+    else {
+      bail!("Invalid Mach-O header.");
+    };
+    if MACH_HEADER_64_SIZE + size as usize > bin.len() {
+      bail!("Invalid Mach-O header.");
+    }
+    let mut commands = Vec::with_capacity(count as usize);
+    let mut offset = MACH_HEADER_64_SIZE;
+    for _ in 0..count {
+      let (Some(cmd), Some(cmd_size)) =
+        (read_u32(bin, offset), read_u32(bin, offset + 4))
+      else {
+        bail!("Invalid Mach-O load command.");
+      };
+      if cmd_size < 8 {
+        bail!("Invalid Mach-O load command.");
+      }
+      commands.push((cmd, offset, cmd_size as usize));
+      offset += cmd_size as usize;
+    }
+    Ok(Self { commands })
+  }
+
+  fn code_signature(&self, bin: &[u8]) -> Option<(usize, usize)> {
+    let (_, offset, _) = self
+      .commands
+      .iter()
+      .find(|(cmd, _, _)| *cmd == LC_CODE_SIGNATURE)?;
+    let data_offset = read_u32(bin, offset + 8)? as usize;
+    let data_size = read_u32(bin, offset + 12)? as usize;
+    Some((data_offset, data_size))
+  }
+
+  fn linkedit_segment(&self, bin: &[u8]) -> Option<usize> {
+    self
+      .commands
+      .iter()
+      .find(|(cmd, offset, _)| {
+        *cmd == LC_SEGMENT_64
+          && bin.get(offset + 8..offset + 24)
+            == Some(LINKEDIT_SEGMENT_NAME.as_slice())
+      })
+      .map(|(_, offset, _)| *offset)
+  }
+}
+
+fn remove_macho_signature(bin: &mut Vec<u8>) -> Result<(), AnyError> {
+  let commands = MachOLoadCommands::parse(bin)?;
+  let Some((data_offset, _)) = commands.code_signature(bin) else {
+    return Ok(());
+  };
+  let (_, cmd_offset, cmd_size) = *commands
+    .commands
+    .iter()
+    .find(|(cmd, _, _)| *cmd == LC_CODE_SIGNATURE)
+    .unwrap();
+  // remove the load command by moving the ones after it to its position
+  let commands_end = MACH_HEADER_64_SIZE + read_u32(bin, 20).unwrap() as usize;
+  bin.copy_within(cmd_offset + cmd_size..commands_end, cmd_offset);
+  bin[commands_end - cmd_size..commands_end].fill(0);
+  let count = read_u32(bin, 16).unwrap() - 1;
+  bin[16..20].copy_from_slice(&count.to_le_bytes());
+  let size = (commands_end - MACH_HEADER_64_SIZE - cmd_size) as u32;
+  bin[20..24].copy_from_slice(&size.to_le_bytes());
+  // the signature is the last data in the file
+  bin.truncate(data_offset);
+  Ok(())
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  /// Creates a Mach-O binary with a __LINKEDIT segment and a code signature.
+  fn create_macho_binary() -> Vec<u8> {
+    let mut bin = vec![0; 0x1000];
+    bin[0..4].copy_from_slice(&MH_MAGIC_64.to_le_bytes());
+    bin[16..20].copy_from_slice(&2u32.to_le_bytes());
+    bin[20..24].copy_from_slice(&(72u32 + 16).to_le_bytes());
+    // LC_SEGMENT_64 __LINKEDIT
+    let segment = MACH_HEADER_64_SIZE;
+    bin[segment..segment + 4].copy_from_slice(&LC_SEGMENT_64.to_le_bytes());
+    bin[segment + 4..segment + 8].copy_from_slice(&72u32.to_le_bytes());
+    bin[segment + 8..segment + 24].copy_from_slice(LINKEDIT_SEGMENT_NAME);
+    bin[segment + 40..segment + 48].copy_from_slice(&0x800u64.to_le_bytes());
+    bin[segment + 48..segment + 56].copy_from_slice(&0x900u64.to_le_bytes());
+    // LC_CODE_SIGNATURE
+    let signature = segment + 72;
+    bin[signature..signature + 4]
+      .copy_from_slice(&LC_CODE_SIGNATURE.to_le_bytes());
+    bin[signature + 4..signature + 8].copy_from_slice(&16u32.to_le_bytes());
+    bin[signature + 8..signature + 12].copy_from_slice(&0xf00u32.to_le_bytes());
+    bin[signature + 12..signature + 16]
+      .copy_from_slice(&0x100u32.to_le_bytes());
+    bin
+  }
+
+  #[test]
+  fn prepares_macho_binary() {
+    let mut bin = create_macho_binary();
+    remove_binary_signature(&mut bin).unwrap();
+    include_appended_data_in_image(&mut bin, 0x2000).unwrap();
+    // the code signature is removed
+    assert_eq!(bin.len(), 0xf00);
+    assert_eq!(read_u32(&bin, 16), Some(1));
+    assert_eq!(read_u32(&bin, 20), Some(72));
+    let commands = MachOLoadCommands::parse(&bin).unwrap();
+    assert_eq!(commands.code_signature(&bin), None);
+    // the __LINKEDIT segment contains the appended data
+    let linkedit = commands.linkedit_segment(&bin).unwrap();
+    assert_eq!(read_u64(&bin, linkedit + 48), Some(0xf00 + 0x2000 - 0x800));
+    assert_eq!(read_u64(&bin, linkedit + 32), Some(0x4000));
+  }
+
+  #[test]
+  fn finds_macho_signature_offset() {
+    let temp_dir = test_util::TempDir::new();
+    let path = temp_dir.path().join("bin");
+    path.write(create_macho_binary());
+    let mut file = std::fs::File::open(&path).unwrap();
+    assert_eq!(signature_offset(&mut file).unwrap(), Some(0xf00));
+
+    path.write("#!/bin/sh");
+    let mut file = std::fs::File::open(&path).unwrap();
+    assert_eq!(signature_offset(&mut file).unwrap(), None);
+  }
+}
@@ -294,6 +294,7 @@ impl<'a> VfsEntryRef<'a> {
         is_char_device: false,
         is_fifo: false,
         is_socket: false,
+        is_immutable: None,
       },
       VfsEntryRef::File(file) => FsStat {
         is_directory: false,
@@ -316,6 +317,7 @@ impl<'a> VfsEntryRef<'a> {
         is_char_device: false,
         is_fifo: false,
         is_socket: false,
+        is_immutable: None,
       },
       VfsEntryRef::Symlink(_) => FsStat {
         is_directory: false,
@@ -338,6 +340,7 @@ impl<'a> VfsEntryRef<'a> {
         is_char_device: false,
         is_fifo: false,
         is_socket: false,
+        is_immutable: None,
       },
     }
   }
@@ -692,6 +695,28 @@ impl deno_io::fs::File for FileBackedVfsFile {
     Err(FsError::NotSupported)
   }
 
+  fn allocate_sync(self: Rc<Self>, _len: u64) -> FsResult<()> {
+    Err(FsError::NotSupported)
+  }
+  async fn allocate_async(self: Rc<Self>, _len: u64) -> FsResult<()> {
+    Err(FsError::NotSupported)
+  }
+
+  fn copy_to_sync(
+    self: Rc<Self>,
+    _dest: ResourceHandleFd,
+    _len: Option<u64>,
+  ) -> FsResult<u64> {
+    Err(FsError::NotSupported)
+  }
+  async fn copy_to_async(
+    self: Rc<Self>,
+    _dest: ResourceHandleFd,
+    _len: Option<u64>,
+  ) -> FsResult<u64> {
+    Err(FsError::NotSupported)
+  }
+
   fn utime_sync(
     self: Rc<Self>,
     _atime_secs: i64,
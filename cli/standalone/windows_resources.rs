@@ -0,0 +1,922 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Writes the icon and version information resources of a Windows
+//! executable produced by `deno compile`.
+//!
+//! The resources of the base binary are read, the requested ones replaced
+//! and the result is written to a new section at the end of the image, which
+//! the resource data directory is then pointed at. For more information about
+//! the format see: https://learn.microsoft.com/en-us/windows/win32/debug/pe-format#the-rsrc-section
+
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+
+const RT_ICON: u16 = 3;
+const RT_GROUP_ICON: u16 = 14;
+const RT_VERSION: u16 = 16;
+/// English (United States)
+const LANGUAGE_ID: u16 = 0x0409;
+/// Unicode (UTF-16LE)
+const CODE_PAGE: u16 = 1200;
+
+const RESOURCE_DATA_DIRECTORY_INDEX: usize = 2;
+const SECURITY_DATA_DIRECTORY_INDEX: usize = 4;
+const SECTION_HEADER_SIZE: usize = 40;
+const SECTION_NAME: &[u8; 8] = b".rsrc2\0\0";
+const IMAGE_SCN_CNT_INITIALIZED_DATA: u32 = 0x00000040;
+const IMAGE_SCN_MEM_READ: u32 = 0x40000000;
+
+#[derive(Debug, Default)]
+pub struct WindowsResourceOptions {
+  /// The contents of an `.ico` file.
+  pub icon: Option<Vec<u8>>,
+  pub product_name: Option<String>,
+  pub version: Option<String>,
+}
+
+impl WindowsResourceOptions {
+  pub fn is_empty(&self) -> bool {
+    self.icon.is_none() && self.product_name.is_none() && self.version.is_none()
+  }
+}
+
+/// Sets the icon, product name and version of a Windows executable.
+pub fn set_windows_binary_resources(
+  bin: &mut Vec<u8>,
+  options: &WindowsResourceOptions,
+) -> Result<(), AnyError> {
+  if options.is_empty() {
+    return Ok(());
+  }
+
+  let mut image = PeImage::parse(bin)?;
+  let mut resources =
+    match image.data_directory(bin, RESOURCE_DATA_DIRECTORY_INDEX) {
+      Some((rva, _)) if rva != 0 => read_resource_directory(bin, &image, rva)?,
+      _ => Vec::new(),
+    };
+
+  if let Some(icon) = &options.icon {
+    let icon = IconFile::parse(icon)?;
+    set_resource_type(&mut resources, RT_ICON, icon.icon_resources());
+    set_resource_type(
+      &mut resources,
+      RT_GROUP_ICON,
+      vec![(
+        ResourceName::Id(1),
+        ResourceNode::Data(icon.group_icon_resource()),
+      )],
+    );
+  }
+  if options.product_name.is_some() || options.version.is_some() {
+    let version_info = build_version_info(
+      options.product_name.as_deref(),
+      options.version.as_deref(),
+    )?;
+    set_resource_type(
+      &mut resources,
+      RT_VERSION,
+      vec![(ResourceName::Id(1), ResourceNode::Data(version_info))],
+    );
+  }
+
+  // the signature is no longer valid once the image changes
+  remove_signature(bin, &image);
+
+  let section_rva = image.next_section_rva(bin);
+  let section_data = write_resource_directory(&resources, section_rva);
+  image.add_section(bin, section_rva, &section_data)?;
+  image.set_data_directory(
+    bin,
+    RESOURCE_DATA_DIRECTORY_INDEX,
+    section_rva,
+    section_data.len() as u32,
+  );
+  Ok(())
+}
+
+/// Removes the Authenticode signature of a Windows executable, which is
+/// stored at the end of the file.
+pub fn remove_windows_signature(bin: &mut Vec<u8>) -> Result<(), AnyError> {
+  let image = PeImage::parse(bin)?;
+  remove_signature(bin, &image);
+  Ok(())
+}
+
+/// Gets the file offset of the signature of a Windows executable from
+/// its headers.
+pub fn windows_signature_offset(headers: &[u8]) -> Option<u64> {
+  let image = PeImage::parse(headers).ok()?;
+  match image.data_directory(headers, SECURITY_DATA_DIRECTORY_INDEX) {
+    Some((offset, size)) if offset != 0 && size != 0 => Some(offset as u64),
+    _ => None,
+  }
+}
+
+fn remove_signature(bin: &mut Vec<u8>, image: &PeImage) {
+  if let Some((offset, size)) =
+    image.data_directory(bin, SECURITY_DATA_DIRECTORY_INDEX)
+  {
+    // the security directory contains a file offset rather than an address
+    if offset != 0 && size != 0 && offset as usize <= bin.len() {
+      bin.truncate(offset as usize);
+      image.set_data_directory(bin, SECURITY_DATA_DIRECTORY_INDEX, 0, 0);
+    }
+  }
+}
+
+fn read_u16(bin: &[u8], offset: usize) -> Result<u16, AnyError> {
+  match bin.get(offset..offset + 2) {
+    Some(bytes) => Ok(u16::from_le_bytes(bytes.try_into().unwrap())),
+    None => bail!("Unexpected end of the executable at offset {offset}."),
+  }
+}
+
+fn read_u32(bin: &[u8], offset: usize) -> Result<u32, AnyError> {
+  match bin.get(offset..offset + 4) {
+    Some(bytes) => Ok(u32::from_le_bytes(bytes.try_into().unwrap())),
+    None => bail!("Unexpected end of the executable at offset {offset}."),
+  }
+}
+
+fn write_u16(bin: &mut [u8], offset: usize, value: u16) {
+  bin[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+}
+
+fn write_u32(bin: &mut [u8], offset: usize, value: u32) {
+  bin[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+}
+
+fn align_up(value: usize, alignment: usize) -> usize {
+  if alignment == 0 {
+    value
+  } else {
+    value.div_ceil(alignment) * alignment
+  }
+}
+
+struct Section {
+  virtual_address: u32,
+  virtual_size: u32,
+  raw_size: u32,
+  raw_offset: u32,
+}
+
+/// The offsets of the headers of a PE image that are read or updated.
+struct PeImage {
+  number_of_sections_offset: usize,
+  optional_header_offset: usize,
+  data_directories_offset: usize,
+  number_of_data_directories: usize,
+  section_table_offset: usize,
+  sections: Vec<Section>,
+}
+
+impl PeImage {
+  fn parse(bin: &[u8]) -> Result<Self, AnyError> {
+    if bin.get(0..2) != Some(b"MZ") {
+      bail!("The base binary is not a Windows executable.");
+    }
+    let pe_offset = read_u32(bin, 60)? as usize;
+    if bin.get(pe_offset..pe_offset + 4) != Some(b"PE\0\0") {
+      bail!("The base binary is not a Windows executable.");
+    }
+    let coff_header_offset = pe_offset + 4;
+    let number_of_sections = read_u16(bin, coff_header_offset + 2)? as usize;
+    let size_of_optional_header =
+      read_u16(bin, coff_header_offset + 16)? as usize;
+    let optional_header_offset = coff_header_offset + 20;
+    let (data_directories_offset, number_of_data_directories_offset) =
+      match read_u16(bin, optional_header_offset)? {
+        // PE32
+        0x10b => (optional_header_offset + 96, optional_header_offset + 92),
+        // PE32+
+        0x20b => (optional_header_offset + 112, optional_header_offset + 108),
+        magic => bail!("Unsupported optional header magic: {magic:#x}"),
+      };
+    let number_of_data_directories =
+      read_u32(bin, number_of_data_directories_offset)? as usize;
+    let section_table_offset = optional_header_offset + size_of_optional_header;
+    let mut sections = Vec::with_capacity(number_of_sections);
+    for i in 0..number_of_sections {
+      let offset = section_table_offset + i * SECTION_HEADER_SIZE;
+      sections.push(Section {
+        virtual_size: read_u32(bin, offset + 8)?,
+        virtual_address: read_u32(bin, offset + 12)?,
+        raw_size: read_u32(bin, offset + 16)?,
+        raw_offset: read_u32(bin, offset + 20)?,
+      });
+    }
+    Ok(Self {
+      number_of_sections_offset: coff_header_offset + 2,
+      optional_header_offset,
+      data_directories_offset,
+      number_of_data_directories,
+      section_table_offset,
+      sections,
+    })
+  }
+
+  fn section_alignment(&self, bin: &[u8]) -> usize {
+    read_u32(bin, self.optional_header_offset + 32).unwrap() as usize
+  }
+
+  fn file_alignment(&self, bin: &[u8]) -> usize {
+    read_u32(bin, self.optional_header_offset + 36).unwrap() as usize
+  }
+
+  fn data_directory(&self, bin: &[u8], index: usize) -> Option<(u32, u32)> {
+    if index >= self.number_of_data_directories {
+      return None;
+    }
+    let offset = self.data_directories_offset + index * 8;
+    Some((read_u32(bin, offset).ok()?, read_u32(bin, offset + 4).ok()?))
+  }
+
+  fn set_data_directory(
+    &self,
+    bin: &mut [u8],
+    index: usize,
+    rva: u32,
+    size: u32,
+  ) {
+    let offset = self.data_directories_offset + index * 8;
+    write_u32(bin, offset, rva);
+    write_u32(bin, offset + 4, size);
+  }
+
+  /// Gets the file offset of a relative virtual address.
+  fn rva_to_offset(&self, rva: u32) -> Result<usize, AnyError> {
+    for section in &self.sections {
+      let size = section.virtual_size.max(section.raw_size);
+      if rva >= section.virtual_address && rva < section.virtual_address + size
+      {
+        return Ok(
+          (section.raw_offset + (rva - section.virtual_address)) as usize,
+        );
+      }
+    }
+    bail!("Address {rva:#x} is not in a section of the executable.")
+  }
+
+  fn next_section_rva(&self, bin: &[u8]) -> u32 {
+    let end = self
+      .sections
+      .iter()
+      .map(|s| s.virtual_address + s.virtual_size.max(s.raw_size))
+      .max()
+      .unwrap_or(0);
+    align_up(end as usize, self.section_alignment(bin)) as u32
+  }
+
+  fn add_section(
+    &mut self,
+    bin: &mut Vec<u8>,
+    rva: u32,
+    data: &[u8],
+  ) -> Result<(), AnyError> {
+    let header_offset =
+      self.section_table_offset + self.sections.len() * SECTION_HEADER_SIZE;
+    let size_of_headers =
+      read_u32(bin, self.optional_header_offset + 60)? as usize;
+    let first_section_offset = self
+      .sections
+      .iter()
+      .filter(|s| s.raw_size > 0)
+      .map(|s| s.raw_offset as usize)
+      .min()
+      .unwrap_or(size_of_headers);
+    if header_offset + SECTION_HEADER_SIZE
+      > size_of_headers.min(first_section_offset)
+    {
+      bail!("There is no space for another section in the executable.");
+    }
+
+    let file_alignment = self.file_alignment(bin);
+    let raw_offset = align_up(bin.len(), file_alignment);
+    let raw_size = align_up(data.len(), file_alignment);
+    bin.resize(raw_offset, 0);
+    bin.extend_from_slice(data);
+    bin.resize(raw_offset + raw_size, 0);
+
+    let header = &mut bin[header_offset..header_offset + SECTION_HEADER_SIZE];
+    header.fill(0);
+    header[0..8].copy_from_slice(SECTION_NAME);
+    write_u32(header, 8, data.len() as u32);
+    write_u32(header, 12, rva);
+    write_u32(header, 16, raw_size as u32);
+    write_u32(header, 20, raw_offset as u32);
+    write_u32(
+      header,
+      36,
+      IMAGE_SCN_CNT_INITIALIZED_DATA | IMAGE_SCN_MEM_READ,
+    );
+    self.sections.push(Section {
+      virtual_address: rva,
+      virtual_size: data.len() as u32,
+      raw_size: raw_size as u32,
+      raw_offset: raw_offset as u32,
+    });
+    write_u16(
+      bin,
+      self.number_of_sections_offset,
+      self.sections.len() as u16,
+    );
+    // size of image
+    let size_of_image =
+      align_up(rva as usize + data.len(), self.section_alignment(bin));
+    write_u32(bin, self.optional_header_offset + 56, size_of_image as u32);
+    // the checksum is only verified for drivers, so clear it
+    write_u32(bin, self.optional_header_offset + 64, 0);
+    Ok(())
+  }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ResourceName {
+  Id(u16),
+  Name(Vec<u16>),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ResourceNode {
+  Directory(Vec<(ResourceName, ResourceNode)>),
+  Data(Vec<u8>),
+}
+
+/// Replaces all the resources of a type with the provided ones.
+fn set_resource_type(
+  resources: &mut Vec<(ResourceName, ResourceNode)>,
+  type_id: u16,
+  entries: Vec<(ResourceName, ResourceNode)>,
+) {
+  let entries = entries
+    .into_iter()
+    .map(|(name, node)| {
+      let languages = vec![(ResourceName::Id(LANGUAGE_ID), node)];
+      (name, ResourceNode::Directory(languages))
+    })
+    .collect();
+  resources.retain(|(name, _)| *name != ResourceName::Id(type_id));
+  resources.push((ResourceName::Id(type_id), ResourceNode::Directory(entries)));
+}
+
+fn read_resource_directory(
+  bin: &[u8],
+  image: &PeImage,
+  root_rva: u32,
+) -> Result<Vec<(ResourceName, ResourceNode)>, AnyError> {
+  fn read_directory(
+    bin: &[u8],
+    image: &PeImage,
+    root_offset: usize,
+    directory_offset: usize,
+    depth: usize,
+  ) -> Result<Vec<(ResourceName, ResourceNode)>, AnyError> {
+    // the resource tree has three levels (type, name and language), so
+    // anything deeper is malformed
+    if depth > 3 {
+      bail!("Invalid resource directory in the executable.");
+    }
+    let offset = root_offset + directory_offset;
+    let count = read_u16(bin, offset + 12)? as usize
+      + read_u16(bin, offset + 14)? as usize;
+    let mut entries = Vec::with_capacity(count);
+    for i in 0..count {
+      let entry_offset = offset + 16 + i * 8;
+      let name = read_u32(bin, entry_offset)?;
+      let name = if name & 0x8000_0000 != 0 {
+        let string_offset = root_offset + (name & 0x7fff_ffff) as usize;
+        let len = read_u16(bin, string_offset)? as usize;
+        let chars = (0..len)
+          .map(|i| read_u16(bin, string_offset + 2 + i * 2))
+          .collect::<Result<Vec<_>, _>>()?;
+        ResourceName::Name(chars)
+      } else {
+        ResourceName::Id(name as u16)
+      };
+      let data = read_u32(bin, entry_offset + 4)?;
+      let node = if data & 0x8000_0000 != 0 {
+        ResourceNode::Directory(read_directory(
+          bin,
+          image,
+          root_offset,
+          (data & 0x7fff_ffff) as usize,
+          depth + 1,
+        )?)
+      } else {
+        let data_entry_offset = root_offset + data as usize;
+        let data_rva = read_u32(bin, data_entry_offset)?;
+        let size = read_u32(bin, data_entry_offset + 4)? as usize;
+        let data_offset = image.rva_to_offset(data_rva)?;
+        match bin.get(data_offset..data_offset + size) {
+          Some(data) => ResourceNode::Data(data.to_vec()),
+          None => bail!("Invalid resource data in the executable."),
+        }
+      };
+      entries.push((name, node));
+    }
+    Ok(entries)
+  }
+
+  let root_offset = image.rva_to_offset(root_rva)?;
+  read_directory(bin, image, root_offset, 0, 1)
+}
+
+/// Writes the resource directory tree, which is laid out as the directory
+/// tables, followed by the names, the data entries and the data.
+fn write_resource_directory(
+  resources: &[(ResourceName, ResourceNode)],
+  section_rva: u32,
+) -> Vec<u8> {
+  enum Child {
+    Directory(usize),
+    Data(usize),
+  }
+
+  struct Table<'a> {
+    entries: Vec<(&'a ResourceName, Child)>,
+  }
+
+  fn sorted(
+    entries: &[(ResourceName, ResourceNode)],
+  ) -> Vec<&(ResourceName, ResourceNode)> {
+    // named entries come first and both kinds are sorted
+    let mut entries = entries.iter().collect::<Vec<_>>();
+    entries.sort_by(|(a, _), (b, _)| match (a, b) {
+      (ResourceName::Name(a), ResourceName::Name(b)) => a.cmp(b),
+      (ResourceName::Name(_), ResourceName::Id(_)) => std::cmp::Ordering::Less,
+      (ResourceName::Id(_), ResourceName::Name(_)) => {
+        std::cmp::Ordering::Greater
+      }
+      (ResourceName::Id(a), ResourceName::Id(b)) => a.cmp(b),
+    });
+    entries
+  }
+
+  // collect the tables breadth first
+  let mut tables: Vec<Table> = Vec::new();
+  let mut data: Vec<&[u8]> = Vec::new();
+  let mut pending = std::collections::VecDeque::from([resources]);
+  while let Some(entries) = pending.pop_front() {
+    let mut table = Table {
+      entries: Vec::with_capacity(entries.len()),
+    };
+    for (name, node) in sorted(entries) {
+      let child = match node {
+        ResourceNode::Directory(entries) => {
+          pending.push_back(entries);
+          Child::Directory(tables.len() + pending.len())
+        }
+        ResourceNode::Data(bytes) => {
+          data.push(bytes);
+          Child::Data(data.len() - 1)
+        }
+      };
+      table.entries.push((name, child));
+    }
+    tables.push(table);
+  }
+
+  let mut table_offsets = Vec::with_capacity(tables.len());
+  let mut offset = 0;
+  for table in &tables {
+    table_offsets.push(offset);
+    offset += 16 + table.entries.len() * 8;
+  }
+  let mut name_offsets = Vec::new();
+  for table in &tables {
+    for (name, _) in &table.entries {
+      if let ResourceName::Name(chars) = name {
+        name_offsets.push(offset);
+        offset += 2 + chars.len() * 2;
+      }
+    }
+  }
+  let data_entries_offset = align_up(offset, 4);
+  let mut data_offsets = Vec::with_capacity(data.len());
+  offset = data_entries_offset + data.len() * 16;
+  for bytes in &data {
+    offset = align_up(offset, 8);
+    data_offsets.push(offset);
+    offset += bytes.len();
+  }
+
+  let mut out = vec![0; offset];
+  let mut names = name_offsets.into_iter();
+  for (table, table_offset) in tables.iter().zip(&table_offsets) {
+    let named_count = table
+      .entries
+      .iter()
+      .filter(|(name, _)| matches!(name, ResourceName::Name(_)))
+      .count();
+    write_u16(&mut out, table_offset + 12, named_count as u16);
+    write_u16(
+      &mut out,
+      table_offset + 14,
+      (table.entries.len() - named_count) as u16,
+    );
+    for (i, (name, child)) in table.entries.iter().enumerate() {
+      let entry_offset = table_offset + 16 + i * 8;
+      match name {
+        ResourceName::Id(id) => write_u32(&mut out, entry_offset, *id as u32),
+        ResourceName::Name(chars) => {
+          let name_offset = names.next().unwrap();
+          write_u16(&mut out, name_offset, chars.len() as u16);
+          for (i, c) in chars.iter().enumerate() {
+            write_u16(&mut out, name_offset + 2 + i * 2, *c);
+          }
+          write_u32(&mut out, entry_offset, 0x8000_0000 | name_offset as u32);
+        }
+      }
+      let child_offset = match child {
+        Child::Directory(index) => 0x8000_0000 | table_offsets[*index] as u32,
+        Child::Data(index) => (data_entries_offset + index * 16) as u32,
+      };
+      write_u32(&mut out, entry_offset + 4, child_offset);
+    }
+  }
+  for (i, (bytes, data_offset)) in data.iter().zip(&data_offsets).enumerate() {
+    let entry_offset = data_entries_offset + i * 16;
+    write_u32(&mut out, entry_offset, section_rva + *data_offset as u32);
+    write_u32(&mut out, entry_offset + 4, bytes.len() as u32);
+    out[*data_offset..*data_offset + bytes.len()].copy_from_slice(bytes);
+  }
+  out
+}
+
+struct IconImage<'a> {
+  /// The width, height, color count, reserved, planes and bit count fields,
+  /// which are the same in the icon file and the group icon resource.
+  header: &'a [u8],
+  data: &'a [u8],
+}
+
+struct IconFile<'a> {
+  images: Vec<IconImage<'a>>,
+}
+
+impl<'a> IconFile<'a> {
+  fn parse(bytes: &'a [u8]) -> Result<Self, AnyError> {
+    let is_icon =
+      bytes.len() >= 6 && read_u16(bytes, 0)? == 0 && read_u16(bytes, 2)? == 1;
+    if !is_icon {
+      bail!("The icon is not a valid .ico file.");
+    }
+    let count = read_u16(bytes, 4)? as usize;
+    if count == 0 {
+      bail!("The icon does not contain any images.");
+    }
+    let mut images = Vec::with_capacity(count);
+    for i in 0..count {
+      let entry_offset = 6 + i * 16;
+      let size = read_u32(bytes, entry_offset + 8)? as usize;
+      let offset = read_u32(bytes, entry_offset + 12)? as usize;
+      let (Some(header), Some(data)) = (
+        bytes.get(entry_offset..entry_offset + 8),
+        bytes.get(offset..offset + size),
+      ) else {
+        bail!("The icon is not a valid .ico file.");
+      };
+      images.push(IconImage { header, data });
+    }
+    Ok(Self { images })
+  }
+
+  fn icon_resources(&self) -> Vec<(ResourceName, ResourceNode)> {
+    self
+      .images
+      .iter()
+      .enumerate()
+      .map(|(i, image)| {
+        (
+          ResourceName::Id(i as u16 + 1),
+          ResourceNode::Data(image.data.to_vec()),
+        )
+      })
+      .collect()
+  }
+
+  fn group_icon_resource(&self) -> Vec<u8> {
+    let mut out = Vec::with_capacity(6 + self.images.len() * 14);
+    out.extend_from_slice(&0u16.to_le_bytes());
+    out.extend_from_slice(&1u16.to_le_bytes());
+    out.extend_from_slice(&(self.images.len() as u16).to_le_bytes());
+    for (i, image) in self.images.iter().enumerate() {
+      out.extend_from_slice(image.header);
+      out.extend_from_slice(&(image.data.len() as u32).to_le_bytes());
+      out.extend_from_slice(&(i as u16 + 1).to_le_bytes());
+    }
+    out
+  }
+}
+
+/// Parses a version like `1.2.3` into the four parts of a Windows version.
+pub fn parse_version(version: &str) -> Result<[u16; 4], AnyError> {
+  // prerelease and build metadata can't be represented
+  let numbers = version.split(['-', '+']).next().unwrap();
+  let mut parts = [0; 4];
+  for (i, part) in numbers.split('.').enumerate() {
+    if i == parts.len() {
+      bail!("Invalid version '{version}'. Expected up to four numbers.");
+    }
+    match part.parse::<u16>() {
+      Ok(number) => parts[i] = number,
+      Err(_) => {
+        bail!("Invalid version '{version}'. Expected a version like 1.2.3.")
+      }
+    }
+  }
+  Ok(parts)
+}
+
+/// Builds a `VS_VERSIONINFO` resource.
+/// See: https://learn.microsoft.com/en-us/windows/win32/menurc/vs-versioninfo
+fn build_version_info(
+  product_name: Option<&str>,
+  version: Option<&str>,
+) -> Result<Vec<u8>, AnyError> {
+  enum Value<'a> {
+    None,
+    Binary(&'a [u8]),
+    Text(&'a str),
+  }
+
+  fn utf16(text: &str) -> Vec<u8> {
+    text
+      .encode_utf16()
+      .chain([0])
+      .flat_map(|c| c.to_le_bytes())
+      .collect()
+  }
+
+  fn pad(out: &mut Vec<u8>) {
+    out.resize(align_up(out.len(), 4), 0);
+  }
+
+  fn block(key: &str, value: Value, children: Vec<Vec<u8>>) -> Vec<u8> {
+    let mut out = vec![0; 6];
+    out.extend(utf16(key));
+    pad(&mut out);
+    let (value_len, value_type) = match value {
+      Value::None => (0, 1),
+      Value::Binary(bytes) => {
+        out.extend_from_slice(bytes);
+        (bytes.len(), 0)
+      }
+      Value::Text(text) => {
+        let text = utf16(text);
+        out.extend_from_slice(&text);
+        // the length of text values is in words
+        (text.len() / 2, 1)
+      }
+    };
+    for child in children {
+      pad(&mut out);
+      out.extend(child);
+    }
+    let len = out.len() as u16;
+    write_u16(&mut out, 0, len);
+    write_u16(&mut out, 2, value_len as u16);
+    write_u16(&mut out, 4, value_type);
+    out
+  }
+
+  let parts = match version {
+    Some(version) => parse_version(version)?,
+    None => [0; 4],
+  };
+  let version_ms = (parts[0] as u32) << 16 | parts[1] as u32;
+  let version_ls = (parts[2] as u32) << 16 | parts[3] as u32;
+  let fixed_file_info = [
+    0xfeef04bd, // signature
+    0x00010000, // structure version
+    version_ms, // file version
+    version_ls, version_ms, // product version
+    version_ls, 0x3f,       // file flags mask
+    0,          // file flags
+    0x00040004, // VOS_NT_WINDOWS32
+    1,          // VFT_APP
+    0,          // file subtype
+    0,          // file date
+    0,
+  ]
+  .into_iter()
+  .flat_map(u32::to_le_bytes)
+  .collect::<Vec<_>>();
+
+  let mut strings = Vec::new();
+  if let Some(product_name) = product_name {
+    strings.push(block("ProductName", Value::Text(product_name), vec![]));
+    strings.push(block("FileDescription", Value::Text(product_name), vec![]));
+  }
+  if let Some(version) = version {
+    strings.push(block("ProductVersion", Value::Text(version), vec![]));
+    strings.push(block("FileVersion", Value::Text(version), vec![]));
+  }
+  let string_table_key = format!("{LANGUAGE_ID:04x}{CODE_PAGE:04x}");
+  let string_file_info = block(
+    "StringFileInfo",
+    Value::None,
+    vec![block(&string_table_key, Value::None, strings)],
+  );
+  let translation = [LANGUAGE_ID, CODE_PAGE]
+    .into_iter()
+    .flat_map(u16::to_le_bytes)
+    .collect::<Vec<_>>();
+  let var_file_info = block(
+    "VarFileInfo",
+    Value::None,
+    vec![block("Translation", Value::Binary(&translation), vec![])],
+  );
+  Ok(block(
+    "VS_VERSION_INFO",
+    Value::Binary(&fixed_file_info),
+    vec![string_file_info, var_file_info],
+  ))
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  /// Creates a PE32+ image with a single code section.
+  fn create_image() -> Vec<u8> {
+    let mut bin = vec![0; 0x600];
+    bin[0..2].copy_from_slice(b"MZ");
+    write_u32(&mut bin, 60, 0x80);
+    bin[0x80..0x84].copy_from_slice(b"PE\0\0");
+    let coff = 0x84;
+    write_u16(&mut bin, coff, 0x8664);
+    write_u16(&mut bin, coff + 2, 1);
+    write_u16(&mut bin, coff + 16, 240);
+    let optional = coff + 20;
+    write_u16(&mut bin, optional, 0x20b);
+    write_u32(&mut bin, optional + 32, 0x1000);
+    write_u32(&mut bin, optional + 36, 0x200);
+    write_u32(&mut bin, optional + 56, 0x2000);
+    write_u32(&mut bin, optional + 60, 0x400);
+    write_u32(&mut bin, optional + 108, 16);
+    let section = optional + 240;
+    bin[section..section + 5].copy_from_slice(b".text");
+    write_u32(&mut bin, section + 8, 0x10);
+    write_u32(&mut bin, section + 12, 0x1000);
+    write_u32(&mut bin, section + 16, 0x200);
+    write_u32(&mut bin, section + 20, 0x400);
+    bin
+  }
+
+  fn create_icon() -> Vec<u8> {
+    let image = [1u8, 2, 3, 4, 5];
+    let mut icon = vec![0, 0, 1, 0, 1, 0];
+    icon.extend_from_slice(&[16, 16, 0, 0, 1, 0, 32, 0]);
+    icon.extend_from_slice(&(image.len() as u32).to_le_bytes());
+    icon.extend_from_slice(&22u32.to_le_bytes());
+    icon.extend_from_slice(&image);
+    icon
+  }
+
+  fn read_resources(bin: &[u8]) -> Vec<(ResourceName, ResourceNode)> {
+    let image = PeImage::parse(bin).unwrap();
+    let (rva, _) = image
+      .data_directory(bin, RESOURCE_DATA_DIRECTORY_INDEX)
+      .unwrap();
+    read_resource_directory(bin, &image, rva).unwrap()
+  }
+
+  fn find_data(
+    resources: &[(ResourceName, ResourceNode)],
+    type_id: u16,
+  ) -> Vec<Vec<u8>> {
+    let Some((_, ResourceNode::Directory(names))) = resources
+      .iter()
+      .find(|(name, _)| *name == ResourceName::Id(type_id))
+    else {
+      return Vec::new();
+    };
+    names
+      .iter()
+      .flat_map(|(_, node)| match node {
+        ResourceNode::Directory(languages) => languages
+          .iter()
+          .filter_map(|(_, node)| match node {
+            ResourceNode::Data(data) => Some(data.clone()),
+            ResourceNode::Directory(_) => None,
+          })
+          .collect::<Vec<_>>(),
+        ResourceNode::Data(data) => vec![data.clone()],
+      })
+      .collect()
+  }
+
+  #[test]
+  fn sets_icon_and_version_resources() {
+    let mut bin = create_image();
+    set_windows_binary_resources(
+      &mut bin,
+      &WindowsResourceOptions {
+        icon: Some(create_icon()),
+        product_name: Some("My App".to_string()),
+        version: Some("1.2.3".to_string()),
+      },
+    )
+    .unwrap();
+
+    let image = PeImage::parse(&bin).unwrap();
+    assert_eq!(image.sections.len(), 2);
+    assert_eq!(image.sections[1].virtual_address, 0x2000);
+    assert_eq!(image.sections[1].raw_offset, 0x600);
+    assert_eq!(bin.len() % 0x200, 0);
+
+    let resources = read_resources(&bin);
+    assert_eq!(find_data(&resources, RT_ICON), vec![vec![1, 2, 3, 4, 5]]);
+    let group_icon = &find_data(&resources, RT_GROUP_ICON)[0];
+    assert_eq!(group_icon.len(), 6 + 14);
+    assert_eq!(&group_icon[6..14], &[16, 16, 0, 0, 1, 0, 32, 0]);
+    assert_eq!(read_u16(group_icon, 18).unwrap(), 1);
+
+    let version_info = &find_data(&resources, RT_VERSION)[0];
+    assert_eq!(
+      read_u16(version_info, 0).unwrap() as usize,
+      version_info.len()
+    );
+    // VS_FIXEDFILEINFO follows the key
+    assert_eq!(read_u32(version_info, 40).unwrap(), 0xfeef04bd);
+    assert_eq!(read_u32(version_info, 48).unwrap(), 1 << 16 | 2);
+    assert_eq!(read_u32(version_info, 52).unwrap(), 3 << 16);
+    let text = String::from_utf16_lossy(
+      &version_info
+        .chunks_exact(2)
+        .map(|c| u16::from_le_bytes([c[0], c[1]]))
+        .collect::<Vec<_>>(),
+    );
+    assert!(text.contains("ProductName\0\0My App\0"));
+    assert!(text.contains("FileVersion\0\x001.2.3\0"));
+  }
+
+  #[test]
+  fn keeps_existing_resources() {
+    let mut bin = create_image();
+    set_windows_binary_resources(
+      &mut bin,
+      &WindowsResourceOptions {
+        icon: Some(create_icon()),
+        ..Default::default()
+      },
+    )
+    .unwrap();
+    set_windows_binary_resources(
+      &mut bin,
+      &WindowsResourceOptions {
+        version: Some("2.0.0-beta.1".to_string()),
+        ..Default::default()
+      },
+    )
+    .unwrap();
+
+    let resources = read_resources(&bin);
+    assert_eq!(find_data(&resources, RT_ICON), vec![vec![1, 2, 3, 4, 5]]);
+    let version_info = &find_data(&resources, RT_VERSION)[0];
+    assert_eq!(read_u32(version_info, 48).unwrap(), 2 << 16);
+  }
+
+  #[test]
+  fn removes_signature() {
+    let mut bin = create_image();
+    assert_eq!(windows_signature_offset(&bin), None);
+    let image = PeImage::parse(&bin).unwrap();
+    image.set_data_directory(&mut bin, SECURITY_DATA_DIRECTORY_INDEX, 0x600, 8);
+    bin.extend_from_slice(&[1; 8]);
+    assert_eq!(windows_signature_offset(&bin), Some(0x600));
+
+    remove_windows_signature(&mut bin).unwrap();
+    assert_eq!(bin.len(), 0x600);
+    assert_eq!(windows_signature_offset(&bin), None);
+  }
+
+  #[test]
+  fn errors_for_invalid_input() {
+    let mut bin = b"not an executable".to_vec();
+    assert!(set_windows_binary_resources(
+      &mut bin,
+      &WindowsResourceOptions {
+        version: Some("1.0.0".to_string()),
+        ..Default::default()
+      },
+    )
+    .is_err());
+
+    let mut bin = create_image();
+    assert!(set_windows_binary_resources(
+      &mut bin,
+      &WindowsResourceOptions {
+        icon: Some(b"not an icon".to_vec()),
+        ..Default::default()
+      },
+    )
+    .is_err());
+    assert!(parse_version("1.x").is_err());
+    assert!(parse_version("1.2.3.4.5").is_err());
+    assert_eq!(parse_version("1.2").unwrap(), [1, 2, 0, 0]);
+  }
+}
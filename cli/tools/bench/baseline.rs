@@ -0,0 +1,160 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use serde::Deserialize;
+use std::path::Path;
+
+use super::BenchDescription;
+use super::BenchResult;
+use super::BenchStats;
+
+/// Results of a previous run, as written by `deno bench --json`, that new
+/// measurements are compared against.
+#[derive(Debug, Default, Deserialize)]
+pub struct BenchBaseline {
+  benches: Vec<BaselineBench>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BaselineBench {
+  origin: String,
+  group: Option<String>,
+  name: String,
+  results: Vec<BenchResult>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct BenchComparison {
+  pub baseline_avg: f64,
+  /// Change of the average time per iteration relative to the baseline, as a
+  /// percentage. Positive values mean the benchmark got slower.
+  pub change: f64,
+  /// Whether the benchmark got slower by more than the combined margin of
+  /// error of both measurements.
+  pub regressed: bool,
+}
+
+impl BenchBaseline {
+  pub fn load(path: &Path) -> Result<Self, AnyError> {
+    let text = std::fs::read_to_string(path).with_context(|| {
+      format!("Failed reading bench baseline {}", path.display())
+    })?;
+    serde_json::from_str(&text).with_context(|| {
+      format!("Failed parsing bench baseline {}", path.display())
+    })
+  }
+
+  pub fn compare(
+    &self,
+    desc: &BenchDescription,
+    stats: &BenchStats,
+  ) -> Option<BenchComparison> {
+    let baseline = self.benches.iter().find(|bench| {
+      bench.origin == desc.origin
+        && bench.group == desc.group
+        && bench.name == desc.name
+    })?;
+    let baseline = baseline.results.iter().find_map(|result| match result {
+      BenchResult::Ok(stats) => Some(stats),
+      BenchResult::Failed(_) => None,
+    })?;
+    if baseline.avg <= 0.0 {
+      return None;
+    }
+    let change = (stats.avg - baseline.avg) / baseline.avg * 100.0;
+    let margin = baseline.rme + stats.rme;
+    Some(BenchComparison {
+      baseline_avg: baseline.avg,
+      change,
+      regressed: change > margin,
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn stats(avg: f64, rme: f64) -> BenchStats {
+    BenchStats {
+      n: 100,
+      min: avg,
+      max: avg,
+      avg,
+      p75: avg,
+      p99: avg,
+      p995: avg,
+      p999: avg,
+      rme,
+      high_precision: true,
+      used_explicit_timers: false,
+    }
+  }
+
+  fn desc(name: &str) -> BenchDescription {
+    BenchDescription {
+      id: 0,
+      name: name.to_string(),
+      origin: "file:///bench.ts".to_string(),
+      baseline: false,
+      group: None,
+      ignore: false,
+      only: false,
+      warmup: false,
+    }
+  }
+
+  #[test]
+  fn compare_flags_regressions_outside_margin_of_error() {
+    let baseline = BenchBaseline {
+      benches: vec![BaselineBench {
+        origin: "file:///bench.ts".to_string(),
+        group: None,
+        name: "parse".to_string(),
+        results: vec![BenchResult::Ok(stats(100.0, 2.0))],
+      }],
+    };
+    let comparison = baseline.compare(&desc("parse"), &stats(103.0, 2.0));
+    assert_eq!(
+      comparison,
+      Some(BenchComparison {
+        baseline_avg: 100.0,
+        change: 3.0,
+        regressed: false,
+      })
+    );
+    let comparison = baseline.compare(&desc("parse"), &stats(110.0, 2.0));
+    assert!(comparison.unwrap().regressed);
+    let comparison = baseline.compare(&desc("parse"), &stats(50.0, 2.0));
+    assert!(!comparison.unwrap().regressed);
+    assert_eq!(baseline.compare(&desc("format"), &stats(50.0, 2.0)), None);
+  }
+
+  #[test]
+  fn load_json_output() {
+    let baseline: BenchBaseline = serde_json::from_str(
+      r#"{
+        "runtime": "Deno/1.43.0 x86_64-unknown-linux-gnu",
+        "cpu": "test",
+        "benches": [{
+          "origin": "file:///bench.ts",
+          "group": null,
+          "name": "parse",
+          "baseline": false,
+          "results": [{
+            "ok": {
+              "n": 10, "min": 1, "max": 3, "avg": 2, "p75": 2, "p99": 3,
+              "p995": 3, "p999": 3, "highPrecision": true,
+              "usedExplicitTimers": false
+            }
+          }]
+        }]
+      }"#,
+    )
+    .unwrap();
+    let comparison = baseline.compare(&desc("parse"), &stats(2.0, 0.0));
+    assert_eq!(comparison.unwrap().change, 0.0);
+  }
+}
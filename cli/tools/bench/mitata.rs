@@ -187,6 +187,9 @@ pub mod reporter {
     pub p75: f64,
     pub p99: f64,
     pub p995: f64,
+    /// Relative margin of error of `avg` at a 95% confidence level, as a
+    /// percentage.
+    pub rme: f64,
   }
 
   #[derive(Clone, PartialEq)]
@@ -235,6 +238,7 @@ pub mod reporter {
       options.size
         + 14 * options.avg as usize
         + 14 * options.avg as usize
+        + 10 * options.avg as usize
         + 24 * options.min_max as usize,
     ));
 
@@ -270,6 +274,7 @@ pub mod reporter {
     if options.avg {
       s.push_str(&format!("{:>14}", "time (avg)"));
       s.push_str(&format!("{:>14}", "iter/s"));
+      s.push_str(&format!("{:>10}", "rme"));
     }
     if options.min_max {
       s.push_str(&format!("{:>24}", "(min … max)"));
@@ -297,6 +302,7 @@ pub mod reporter {
         format!("{}/iter", colors::yellow(fmt_duration(stats.avg)))
       ));
       s.push_str(&format!("{:>14}", avg_to_iter_per_s(stats.avg)));
+      s.push_str(&format!("{:>10}", format!("±{:.2}%", stats.rme)));
     }
     if options.min_max {
       s.push_str(&format!(
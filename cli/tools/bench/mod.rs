@@ -32,6 +32,7 @@ use deno_core::unsync::spawn_blocking;
 use deno_core::v8;
 use deno_core::ModuleSpecifier;
 use deno_core::PollEventLoopOptions;
+use deno_runtime::fmt_errors::supports_hyperlinks;
 use deno_runtime::permissions::Permissions;
 use deno_runtime::permissions::PermissionsContainer;
 use deno_runtime::tokio_util::create_and_run_current_thread;
@@ -48,9 +49,11 @@ use std::time::Duration;
 use tokio::sync::mpsc::unbounded_channel;
 use tokio::sync::mpsc::UnboundedSender;
 
+mod baseline;
 mod mitata;
 mod reporters;
 
+use baseline::BenchBaseline;
 use reporters::BenchReporter;
 use reporters::ConsoleReporter;
 use reporters::JsonReporter;
@@ -59,6 +62,7 @@ use reporters::JsonReporter;
 struct BenchSpecifierOptions {
   filter: TestFilter,
   json: bool,
+  baseline: Option<Arc<BenchBaseline>>,
   log_level: Option<log::Level>,
 }
 
@@ -120,6 +124,10 @@ pub struct BenchStats {
   pub p99: f64,
   pub p995: f64,
   pub p999: f64,
+  /// Relative margin of error of `avg` at a 95% confidence level, as a
+  /// percentage.
+  #[serde(default)]
+  pub rme: f64,
   pub high_precision: bool,
   pub used_explicit_timers: bool,
 }
@@ -138,11 +146,12 @@ impl BenchReport {
 fn create_reporter(
   show_output: bool,
   json: bool,
+  baseline: Option<Arc<BenchBaseline>>,
 ) -> Box<dyn BenchReporter + Send> {
   if json {
     return Box::new(JsonReporter::new());
   }
-  Box::new(ConsoleReporter::new(show_output))
+  Box::new(ConsoleReporter::new(show_output, baseline))
 }
 
 /// Type check a collection of module and document specifiers.
@@ -317,9 +326,13 @@ async fn bench_specifiers(
   let handler = {
     spawn(async move {
       let mut used_only = false;
+      let mut regressed = 0;
       let mut report = BenchReport::new();
-      let mut reporter =
-        create_reporter(log_level != Some(Level::Error), options.json);
+      let mut reporter = create_reporter(
+        log_level != Some(Level::Error),
+        options.json,
+        options.baseline.clone(),
+      );
       let mut benches = IndexMap::new();
 
       while let Some(event) = receiver.recv().await {
@@ -351,6 +364,15 @@ async fn bench_specifiers(
             reporter.report_result(desc, &result);
             match result {
               BenchResult::Ok(stats) => {
+                if !desc.warmup
+                  && options
+                    .baseline
+                    .as_ref()
+                    .and_then(|baseline| baseline.compare(desc, &stats))
+                    .is_some_and(|comparison| comparison.regressed)
+                {
+                  regressed += 1;
+                }
                 report.measurements.push((desc.clone(), stats));
               }
 
@@ -380,6 +402,18 @@ async fn bench_specifiers(
         return Err(generic_error("Bench failed"));
       }
 
+      if regressed > 0 {
+        return Err(generic_error(format!(
+          "Bench failed because {} {} regressed compared to the baseline",
+          regressed,
+          if regressed == 1 {
+            "benchmark"
+          } else {
+            "benchmarks"
+          },
+        )));
+      }
+
       Ok(())
     })
   };
@@ -421,6 +455,15 @@ fn has_supported_bench_path_name(path: &Path) -> bool {
   }
 }
 
+fn load_baseline(
+  path: Option<&Path>,
+) -> Result<Option<Arc<BenchBaseline>>, AnyError> {
+  let Some(path) = path else {
+    return Ok(None);
+  };
+  Ok(Some(Arc::new(BenchBaseline::load(path)?)))
+}
+
 pub async fn run_benchmarks(
   flags: Flags,
   bench_flags: BenchFlags,
@@ -457,6 +500,7 @@ pub async fn run_benchmarks(
   }
 
   let log_level = cli_options.log_level();
+  let baseline = load_baseline(bench_options.compare.as_deref())?;
   let worker_factory =
     Arc::new(factory.create_cli_main_worker_factory().await?);
   bench_specifiers(
@@ -466,6 +510,7 @@ pub async fn run_benchmarks(
     BenchSpecifierOptions {
       filter: TestFilter::from_flag(&bench_options.filter),
       json: bench_options.json,
+      baseline,
       log_level,
     },
   )
@@ -567,6 +612,7 @@ pub async fn run_benchmarks_with_watch(
         }
 
         let log_level = cli_options.log_level();
+        let baseline = load_baseline(bench_options.compare.as_deref())?;
         bench_specifiers(
           worker_factory,
           &permissions,
@@ -574,6 +620,7 @@ pub async fn run_benchmarks_with_watch(
           BenchSpecifierOptions {
             filter: TestFilter::from_flag(&bench_options.filter),
             json: bench_options.json,
+            baseline,
             log_level,
           },
         )
@@ -103,14 +103,19 @@ pub struct ConsoleReporter {
   baseline: bool,
   group_measurements: Vec<(BenchDescription, BenchStats)>,
   options: Option<mitata::reporter::Options>,
+  compare_baseline: Option<Arc<BenchBaseline>>,
 }
 
 impl ConsoleReporter {
-  pub fn new(show_output: bool) -> Self {
+  pub fn new(
+    show_output: bool,
+    compare_baseline: Option<Arc<BenchBaseline>>,
+  ) -> Self {
     Self {
       show_output,
       group: None,
       options: None,
+      compare_baseline,
       baseline: false,
       name: String::new(),
       group_measurements: Vec::new(),
@@ -216,11 +221,29 @@ impl BenchReporter for ConsoleReporter {
               p75: stats.p75,
               p99: stats.p99,
               p995: stats.p995,
+              rme: stats.rme,
             },
             options
           )
         );
 
+        if let Some(comparison) = self
+          .compare_baseline
+          .as_ref()
+          .and_then(|baseline| baseline.compare(&desc, stats))
+        {
+          let change = format!(
+            "{:+.2}% compared to {}/iter",
+            comparison.change,
+            mitata::fmt_duration(comparison.baseline_avg)
+          );
+          if comparison.regressed {
+            println!("  {} {}", colors::red_bold("regression"), change);
+          } else {
+            println!("  {}", colors::gray(change));
+          }
+        }
+
         if !stats.high_precision && stats.used_explicit_timers {
           println!("{}", colors::yellow(format!("Warning: start() and end() calls in \"{}\" are ignored because it averages less\nthan 10µs per iteration. Remove them for better results.", &desc.name)));
         }
@@ -235,7 +258,10 @@ impl BenchReporter for ConsoleReporter {
             &desc.name,
             &mitata::reporter::Error {
               stack: None,
-              message: format_test_error(js_error),
+              message: format_test_error(
+                js_error,
+                supports_hyperlinks(&std::io::stdout()),
+              ),
             },
             options
           )
@@ -270,6 +296,7 @@ impl BenchReporter for ConsoleReporter {
                 p75: s.p75,
                 p99: s.p99,
                 p995: s.p995,
+                rme: s.rme,
               },
             })
             .collect::<Vec<mitata::reporter::GroupBenchmark>>(),
@@ -290,7 +317,7 @@ impl BenchReporter for ConsoleReporter {
     println!(
       "{}: {}",
       colors::red_bold("error"),
-      format_test_error(&error)
+      format_test_error(&error, supports_hyperlinks(&std::io::stdout()))
     );
     println!("This error was not caught from a benchmark and caused the bench runner to fail on the referenced module.");
     println!("It most likely originated from a dangling promise, event/timeout handler or top-level code.");
@@ -0,0 +1,375 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Emits the chunks of a [`ChunkGraph`] using the swc bundler. Imports of
+//! modules in other chunks are rewritten to import the other chunk's file and
+//! are kept as external imports.
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use deno_ast::swc::ast;
+use deno_ast::swc::bundler::BundleKind;
+use deno_ast::swc::bundler::Bundler;
+use deno_ast::swc::bundler::Config;
+use deno_ast::swc::bundler::Hook;
+use deno_ast::swc::bundler::Load;
+use deno_ast::swc::bundler::ModuleData;
+use deno_ast::swc::bundler::ModuleRecord;
+use deno_ast::swc::bundler::ModuleType;
+use deno_ast::swc::bundler::Resolve;
+use deno_ast::swc::common::sync::Lrc;
+use deno_ast::swc::common::FileName;
+use deno_ast::swc::common::FilePathMapping;
+use deno_ast::swc::common::Globals;
+use deno_ast::swc::common::SourceMap;
+use deno_ast::swc::common::Span;
+use deno_ast::swc::common::GLOBALS;
+use deno_ast::swc::loader::resolve::Resolution;
+use deno_ast::swc::parser::parse_file_as_module;
+use deno_ast::swc::parser::Syntax;
+use deno_ast::swc::visit::noop_visit_mut_type;
+use deno_ast::swc::visit::VisitMut;
+use deno_ast::swc::visit::VisitMutWith;
+use deno_ast::EmitOptions;
+use deno_ast::SourceMapOption;
+use deno_ast::TranspileOptions;
+use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::sourcemap;
+use deno_core::ModuleSpecifier;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+
+use super::chunk_graph::chunk_import_specifier;
+use super::chunk_graph::ChunkGraph;
+use super::codegen::compose_source_map;
+use super::codegen::emit_module;
+use super::minify::mangle_identifiers;
+
+pub struct EmittedChunk {
+  pub file_name: String,
+  pub code: String,
+  pub maybe_source_map: Option<String>,
+}
+
+pub struct ChunkEmitOptions<'a> {
+  pub transpile_options: &'a TranspileOptions,
+  pub emit_options: &'a EmitOptions,
+  pub minify: bool,
+  pub source_maps: bool,
+}
+
+pub fn emit_chunks(
+  graph: &ModuleGraph,
+  chunk_graph: &ChunkGraph,
+  options: ChunkEmitOptions,
+) -> Result<Vec<EmittedChunk>, AnyError> {
+  // the source maps of the transpiled modules are used to map the chunks to
+  // the original sources
+  let emit_options = EmitOptions {
+    source_map: if options.source_maps {
+      SourceMapOption::Separate
+    } else {
+      SourceMapOption::None
+    },
+    inline_sources: true,
+    ..options.emit_options.clone()
+  };
+  let module_source_maps = RefCell::new(HashMap::new());
+  let external_modules = chunk_graph
+    .chunks
+    .iter()
+    .map(|chunk| chunk_import_specifier(&chunk.file_name).into())
+    .collect::<Vec<_>>();
+  let globals = Globals::new();
+  GLOBALS.set(&globals, || {
+    chunk_graph
+      .chunks
+      .iter()
+      .map(|chunk| {
+        let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+        let loader = ChunkLoader {
+          graph,
+          chunk_graph,
+          cm: cm.clone(),
+          transpile_options: options.transpile_options,
+          emit_options: &emit_options,
+          module_source_maps: &module_source_maps,
+        };
+        let config = Config {
+          module: ModuleType::Es,
+          external_modules: external_modules.clone(),
+          ..Default::default()
+        };
+        let mut bundler = Bundler::new(
+          &globals,
+          cm.clone(),
+          loader,
+          ChunkResolver(graph),
+          config,
+          Box::new(ImportMetaHook),
+        );
+        let mut entries = HashMap::new();
+        entries
+          .insert(chunk.file_name.clone(), FileName::Url(chunk.root.clone()));
+        let output = bundler
+          .bundle(entries)
+          .with_context(|| format!("Unable to bundle {}.", chunk.file_name))?;
+        let mut bundle = output
+          .into_iter()
+          .find(|bundle| match &bundle.kind {
+            BundleKind::Named { name } => *name == chunk.file_name,
+            _ => false,
+          })
+          .unwrap();
+        if options.minify {
+          mangle_identifiers(&mut bundle.module);
+        }
+        let emitted = emit_module(&cm, &bundle.module, options.minify)?;
+        let maybe_source_map = if options.source_maps {
+          let module_source_maps = module_source_maps.borrow();
+          Some(compose_source_map(
+            &cm,
+            &emitted.mappings,
+            Some(&chunk.file_name),
+            |file_name| match file_name {
+              FileName::Url(specifier) => module_source_maps.get(specifier),
+              _ => None,
+            },
+          )?)
+        } else {
+          None
+        };
+        Ok(EmittedChunk {
+          file_name: chunk.file_name.clone(),
+          code: emitted.code,
+          maybe_source_map,
+        })
+      })
+      .collect()
+  })
+}
+
+struct ChunkLoader<'a> {
+  graph: &'a ModuleGraph,
+  chunk_graph: &'a ChunkGraph,
+  cm: Lrc<SourceMap>,
+  transpile_options: &'a TranspileOptions,
+  emit_options: &'a EmitOptions,
+  module_source_maps:
+    &'a RefCell<HashMap<ModuleSpecifier, sourcemap::SourceMap>>,
+}
+
+impl Load for ChunkLoader<'_> {
+  fn load(&self, file_name: &FileName) -> Result<ModuleData, AnyError> {
+    let FileName::Url(specifier) = file_name else {
+      unreachable!("Received a non-URL file name from the bundler.");
+    };
+    let (specifier, source, rewrites) = match self.graph.get(specifier) {
+      Some(Module::Js(module)) => {
+        let parsed_source = deno_ast::parse_module(deno_ast::ParseParams {
+          specifier: module.specifier.clone(),
+          text_info: deno_ast::SourceTextInfo::new(module.source.clone()),
+          media_type: module.media_type,
+          capture_tokens: false,
+          maybe_syntax: None,
+          scope_analysis: false,
+        })?;
+        let transpiled = parsed_source
+          .transpile(self.transpile_options, self.emit_options)?
+          .into_source();
+        if let Some(source_map) = transpiled.source_map {
+          self.module_source_maps.borrow_mut().insert(
+            module.specifier.clone(),
+            sourcemap::SourceMap::from_slice(source_map.as_bytes())?,
+          );
+        }
+        let source = transpiled.text;
+        let rewrites = module
+          .dependencies
+          .iter()
+          .filter_map(|(raw_specifier, dependency)| {
+            let resolved = dependency.maybe_code.maybe_specifier()?;
+            let resolved = self.graph.get(resolved)?.specifier();
+            let specifier = self
+              .chunk_graph
+              .cross_chunk_specifier(&module.specifier, resolved)?;
+            Some((raw_specifier.clone(), specifier))
+          })
+          .collect::<HashMap<_, _>>();
+        (&module.specifier, source, rewrites)
+      }
+      Some(Module::Json(module)) => (
+        &module.specifier,
+        format!("export default {};", module.source),
+        HashMap::new(),
+      ),
+      _ => bail!(
+        "Module \"{}\" unexpectedly missing when bundling.",
+        specifier
+      ),
+    };
+    let fm = self
+      .cm
+      .new_source_file(FileName::Url(specifier.clone()), source);
+    let mut module = parse_file_as_module(
+      &fm,
+      Syntax::Es(Default::default()),
+      ast::EsVersion::latest(),
+      None,
+      &mut Vec::new(),
+    )
+    .map_err(|err| {
+      anyhow!(
+        "Unable to parse \"{}\" when bundling: {}",
+        specifier,
+        err.into_kind().msg()
+      )
+    })?;
+    module.visit_mut_with(&mut CrossChunkSpecifierRewriter { rewrites });
+    Ok(ModuleData {
+      fm,
+      module,
+      helpers: Default::default(),
+    })
+  }
+}
+
+/// Replaces the specifiers of imports of modules in other chunks with the
+/// specifier of the other chunk's file.
+struct CrossChunkSpecifierRewriter {
+  rewrites: HashMap<String, String>,
+}
+
+impl CrossChunkSpecifierRewriter {
+  fn rewrite(&self, src: &mut ast::Str) -> bool {
+    match self.rewrites.get(src.value.as_ref()) {
+      Some(specifier) => {
+        *src = ast::Str {
+          span: src.span,
+          value: specifier.as_str().into(),
+          raw: None,
+        };
+        true
+      }
+      None => false,
+    }
+  }
+}
+
+impl VisitMut for CrossChunkSpecifierRewriter {
+  noop_visit_mut_type!();
+
+  fn visit_mut_import_decl(&mut self, n: &mut ast::ImportDecl) {
+    if self.rewrite(&mut n.src) {
+      // a chunk is always JavaScript, even when its root is a JSON module
+      n.with = None;
+    }
+  }
+
+  fn visit_mut_named_export(&mut self, n: &mut ast::NamedExport) {
+    if let Some(src) = &mut n.src {
+      if self.rewrite(src) {
+        n.with = None;
+      }
+    }
+  }
+
+  fn visit_mut_export_all(&mut self, n: &mut ast::ExportAll) {
+    if self.rewrite(&mut n.src) {
+      n.with = None;
+    }
+  }
+
+  fn visit_mut_call_expr(&mut self, n: &mut ast::CallExpr) {
+    n.visit_mut_children_with(self);
+    if !matches!(n.callee, ast::Callee::Import(_)) {
+      return;
+    }
+    if let Some(ast::ExprOrSpread { expr, .. }) = n.args.first_mut() {
+      if let ast::Expr::Lit(ast::Lit::Str(src)) = &mut **expr {
+        if self.rewrite(src) {
+          n.args.truncate(1);
+        }
+      }
+    }
+  }
+}
+
+struct ChunkResolver<'a>(&'a ModuleGraph);
+
+impl Resolve for ChunkResolver<'_> {
+  fn resolve(
+    &self,
+    referrer: &FileName,
+    specifier: &str,
+  ) -> Result<Resolution, AnyError> {
+    let FileName::Url(referrer) = referrer else {
+      unreachable!("Received a non-URL file name from the bundler.");
+    };
+    let resolved = match self.0.get(referrer) {
+      Some(Module::Js(module)) => module
+        .dependencies
+        .get(specifier)
+        .and_then(|dependency| dependency.maybe_code.maybe_specifier())
+        .and_then(|specifier| self.0.get(specifier)),
+      _ => None,
+    };
+    match resolved {
+      Some(module) => Ok(Resolution {
+        filename: FileName::Url(module.specifier().clone()),
+        slug: None,
+      }),
+      None => bail!(
+        "Cannot resolve \"{}\" from \"{}\" when bundling.",
+        specifier,
+        referrer
+      ),
+    }
+  }
+}
+
+/// Rewrites `import.meta` of the bundled modules, so it behaves the same as in
+/// the unbundled code.
+struct ImportMetaHook;
+
+impl Hook for ImportMetaHook {
+  fn get_import_meta_props(
+    &self,
+    span: Span,
+    module_record: &ModuleRecord,
+  ) -> Result<Vec<ast::KeyValueProp>, AnyError> {
+    let url = match &module_record.file_name {
+      FileName::Url(url) => url.to_string(),
+      file_name => file_name.to_string(),
+    };
+    let main = if module_record.is_entry {
+      ast::Expr::Member(ast::MemberExpr {
+        span,
+        obj: Box::new(ast::Expr::MetaProp(ast::MetaPropExpr {
+          span,
+          kind: ast::MetaPropKind::ImportMeta,
+        })),
+        prop: ast::MemberProp::Ident(ast::Ident::new("main".into(), span)),
+      })
+    } else {
+      ast::Expr::Lit(ast::Lit::Bool(ast::Bool { span, value: false }))
+    };
+    Ok(vec![
+      ast::KeyValueProp {
+        key: ast::PropName::Ident(ast::Ident::new("url".into(), span)),
+        value: Box::new(ast::Expr::Lit(ast::Lit::Str(ast::Str {
+          span,
+          value: url.into(),
+          raw: None,
+        }))),
+      },
+      ast::KeyValueProp {
+        key: ast::PropName::Ident(ast::Ident::new("main".into(), span)),
+        value: Box::new(main),
+      },
+    ])
+  }
+}
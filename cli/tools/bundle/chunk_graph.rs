@@ -0,0 +1,525 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Splits the modules of a module graph into chunks.
+//!
+//! Every entry point and every dynamically imported module is the root of a
+//! chunk. Any other module is placed in the chunk of its importers when all of
+//! them are in the same chunk, otherwise it becomes the root of a new shared
+//! chunk. This ensures that modules are only ever imported across chunks via
+//! the root of a chunk, so every module is emitted exactly once.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::collections::VecDeque;
+
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_core::ModuleSpecifier;
+use deno_graph::Module;
+use deno_graph::ModuleGraph;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Chunk {
+  /// The name of the file the chunk is emitted to.
+  pub file_name: String,
+  pub root: ModuleSpecifier,
+  pub is_entry: bool,
+  pub modules: Vec<ModuleSpecifier>,
+  /// The file names of the chunks statically imported by this chunk.
+  pub imports: Vec<String>,
+  /// The file names of the chunks dynamically imported by this chunk.
+  pub dynamic_imports: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct ChunkGraph {
+  pub chunks: Vec<Chunk>,
+  chunk_by_module: HashMap<ModuleSpecifier, usize>,
+}
+
+impl ChunkGraph {
+  pub fn build(graph: &ModuleGraph, entries: &[ModuleSpecifier]) -> Self {
+    let entries = entries
+      .iter()
+      .filter_map(|entry| graph.get(entry).map(|m| m.specifier().clone()))
+      .collect::<Vec<_>>();
+    Self::from_dependencies(&entries, |specifier| {
+      code_dependencies(graph, specifier)
+    })
+  }
+
+  fn from_dependencies(
+    entries: &[ModuleSpecifier],
+    dependencies: impl Fn(&ModuleSpecifier) -> Vec<(ModuleSpecifier, bool)>,
+  ) -> Self {
+    let modules = ModuleEdges::collect(entries, dependencies);
+    let mut roots = entries.to_vec();
+    for specifier in &modules.order {
+      if modules.dynamic_roots.contains(specifier) && !roots.contains(specifier)
+      {
+        roots.push(specifier.clone());
+      }
+    }
+
+    // assigning modules that are part of a cycle to a chunk is a guess, so
+    // repeat until no module is imported from another chunk without being
+    // the root of its own chunk
+    let chunk_by_module = loop {
+      let chunk_by_module = assign_chunks(&modules, &mut roots);
+      let invalid = modules.order.iter().find(|specifier| {
+        let chunk = chunk_by_module[*specifier];
+        roots[chunk] != **specifier
+          && modules
+            .importers(specifier)
+            .any(|i| chunk_by_module[i] != chunk)
+      });
+      match invalid {
+        Some(specifier) => roots.push(specifier.clone()),
+        None => break chunk_by_module,
+      }
+    };
+
+    let mut file_names = HashSet::new();
+    let mut chunks = roots
+      .iter()
+      .enumerate()
+      .map(|(index, root)| {
+        let is_entry = index < entries.len();
+        let stem = file_stem(root);
+        let stem = if is_entry {
+          stem
+        } else {
+          format!("chunk-{}", stem)
+        };
+        let mut file_name = format!("{}.js", stem);
+        let mut suffix = 2;
+        while !file_names.insert(file_name.clone()) {
+          file_name = format!("{}-{}.js", stem, suffix);
+          suffix += 1;
+        }
+        Chunk {
+          file_name,
+          root: root.clone(),
+          is_entry,
+          modules: Vec::new(),
+          imports: Vec::new(),
+          dynamic_imports: Vec::new(),
+        }
+      })
+      .collect::<Vec<_>>();
+
+    for specifier in &modules.order {
+      let chunk = chunk_by_module[specifier];
+      chunks[chunk].modules.push(specifier.clone());
+      for (dependency, is_dynamic) in &modules.dependencies[specifier] {
+        let dependency_chunk = chunk_by_module[dependency];
+        if dependency_chunk == chunk {
+          continue;
+        }
+        let file_name = chunks[dependency_chunk].file_name.clone();
+        let imports = if *is_dynamic {
+          &mut chunks[chunk].dynamic_imports
+        } else {
+          &mut chunks[chunk].imports
+        };
+        if !imports.contains(&file_name) {
+          imports.push(file_name);
+        }
+      }
+    }
+
+    Self {
+      chunks,
+      chunk_by_module,
+    }
+  }
+
+  /// Gets the specifier to import `specifier` with from `referrer` when the
+  /// modules are in different chunks.
+  pub fn cross_chunk_specifier(
+    &self,
+    referrer: &ModuleSpecifier,
+    specifier: &ModuleSpecifier,
+  ) -> Option<String> {
+    let referrer_chunk = self.chunk_by_module.get(referrer)?;
+    let chunk = self.chunk_by_module.get(specifier)?;
+    if referrer_chunk == chunk {
+      None
+    } else {
+      Some(chunk_import_specifier(&self.chunks[*chunk].file_name))
+    }
+  }
+
+  /// The manifest describing which files belong to the entry points, which
+  /// is written next to the chunks.
+  pub fn manifest(&self) -> serde_json::Value {
+    let entries = self
+      .chunks
+      .iter()
+      .filter(|chunk| chunk.is_entry)
+      .map(|chunk| (chunk.root.to_string(), json!(chunk.file_name)))
+      .collect::<serde_json::Map<_, _>>();
+    let chunks = self
+      .chunks
+      .iter()
+      .map(|chunk| {
+        (
+          chunk.file_name.clone(),
+          json!({
+            "root": chunk.root,
+            "isEntry": chunk.is_entry,
+            "modules": chunk.modules,
+            "imports": chunk.imports,
+            "dynamicImports": chunk.dynamic_imports,
+          }),
+        )
+      })
+      .collect::<serde_json::Map<_, _>>();
+    json!({
+      "entries": entries,
+      "chunks": chunks,
+    })
+  }
+}
+
+/// The specifier used to import another chunk from within a chunk.
+pub fn chunk_import_specifier(file_name: &str) -> String {
+  format!("./{}", file_name)
+}
+
+/// The modules reachable from the entries with their static and dynamic
+/// dependencies.
+struct ModuleEdges {
+  order: Vec<ModuleSpecifier>,
+  dependencies: HashMap<ModuleSpecifier, Vec<(ModuleSpecifier, bool)>>,
+  importers: HashMap<ModuleSpecifier, Vec<ModuleSpecifier>>,
+  dynamic_roots: HashSet<ModuleSpecifier>,
+}
+
+impl ModuleEdges {
+  fn collect(
+    entries: &[ModuleSpecifier],
+    dependencies: impl Fn(&ModuleSpecifier) -> Vec<(ModuleSpecifier, bool)>,
+  ) -> Self {
+    let mut edges = Self {
+      order: Vec::new(),
+      dependencies: HashMap::new(),
+      importers: HashMap::new(),
+      dynamic_roots: HashSet::new(),
+    };
+    let mut pending = entries.iter().cloned().collect::<VecDeque<_>>();
+    while let Some(specifier) = pending.pop_front() {
+      if edges.dependencies.contains_key(&specifier) {
+        continue;
+      }
+      let module_dependencies = dependencies(&specifier);
+      for (dependency, is_dynamic) in &module_dependencies {
+        if *is_dynamic {
+          edges.dynamic_roots.insert(dependency.clone());
+        } else {
+          let importers =
+            edges.importers.entry(dependency.clone()).or_default();
+          if !importers.contains(&specifier) {
+            importers.push(specifier.clone());
+          }
+        }
+        pending.push_back(dependency.clone());
+      }
+      edges.order.push(specifier.clone());
+      edges.dependencies.insert(specifier, module_dependencies);
+    }
+    edges
+  }
+
+  fn importers<'a>(
+    &'a self,
+    specifier: &ModuleSpecifier,
+  ) -> impl Iterator<Item = &'a ModuleSpecifier> {
+    self.importers.get(specifier).into_iter().flatten()
+  }
+}
+
+fn assign_chunks(
+  modules: &ModuleEdges,
+  roots: &mut Vec<ModuleSpecifier>,
+) -> HashMap<ModuleSpecifier, usize> {
+  let mut chunk_by_module = roots
+    .iter()
+    .enumerate()
+    .map(|(index, root)| (root.clone(), index))
+    .collect::<HashMap<_, _>>();
+  while chunk_by_module.len() < modules.order.len() {
+    let mut changed = false;
+    for specifier in &modules.order {
+      if chunk_by_module.contains_key(specifier) {
+        continue;
+      }
+      let chunks = modules
+        .importers(specifier)
+        .map(|importer| chunk_by_module.get(importer).copied())
+        .collect::<Option<HashSet<_>>>();
+      let Some(chunks) = chunks else {
+        // not all importers are assigned yet
+        continue;
+      };
+      let chunk = if chunks.len() == 1 {
+        chunks.into_iter().next().unwrap()
+      } else {
+        roots.push(specifier.clone());
+        roots.len() - 1
+      };
+      chunk_by_module.insert(specifier.clone(), chunk);
+      changed = true;
+    }
+    if !changed {
+      // the remaining modules are part of a cycle, so place the first one in
+      // the chunk of one of its importers
+      let (specifier, chunk) = modules
+        .order
+        .iter()
+        .filter(|specifier| !chunk_by_module.contains_key(*specifier))
+        .find_map(|specifier| {
+          modules
+            .importers(specifier)
+            .find_map(|importer| chunk_by_module.get(importer))
+            .map(|chunk| (specifier.clone(), *chunk))
+        })
+        .unwrap();
+      chunk_by_module.insert(specifier, chunk);
+    }
+  }
+  chunk_by_module
+}
+
+/// Gets the JavaScript and JSON modules the module imports and whether the
+/// import is dynamic.
+fn code_dependencies(
+  graph: &ModuleGraph,
+  specifier: &ModuleSpecifier,
+) -> Vec<(ModuleSpecifier, bool)> {
+  let Some(Module::Js(module)) = graph.get(specifier) else {
+    return Vec::new();
+  };
+  module
+    .dependencies
+    .values()
+    .filter_map(|dependency| {
+      let specifier = dependency.maybe_code.maybe_specifier()?;
+      match graph.get(specifier)? {
+        Module::Js(module) => Some(module.specifier.clone()),
+        Module::Json(module) => Some(module.specifier.clone()),
+        Module::Npm(_) | Module::Node(_) | Module::External(_) => None,
+      }
+      .map(|specifier| (specifier, dependency.is_dynamic))
+    })
+    .collect()
+}
+
+fn file_stem(specifier: &ModuleSpecifier) -> String {
+  let name = specifier
+    .path_segments()
+    .and_then(|mut segments| segments.next_back())
+    .filter(|name| !name.is_empty())
+    .unwrap_or("mod");
+  let stem = match name.find('.') {
+    Some(index) if index > 0 => &name[..index],
+    _ => name,
+  };
+  stem
+    .chars()
+    .map(|c| {
+      if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+        c
+      } else {
+        '_'
+      }
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn build(
+    entries: &[&str],
+    dependencies: &[(&str, &[(&str, bool)])],
+  ) -> ChunkGraph {
+    let specifier = |name: &str| {
+      ModuleSpecifier::parse(&format!("file:///{}", name)).unwrap()
+    };
+    let dependencies = dependencies
+      .iter()
+      .map(|(name, deps)| {
+        (
+          specifier(name),
+          deps
+            .iter()
+            .map(|(dep, is_dynamic)| (specifier(dep), *is_dynamic))
+            .collect::<Vec<_>>(),
+        )
+      })
+      .collect::<HashMap<_, _>>();
+    let entries = entries.iter().map(|e| specifier(e)).collect::<Vec<_>>();
+    ChunkGraph::from_dependencies(&entries, |specifier| {
+      dependencies.get(specifier).cloned().unwrap_or_default()
+    })
+  }
+
+  fn chunk_modules(graph: &ChunkGraph) -> Vec<(String, Vec<String>)> {
+    graph
+      .chunks
+      .iter()
+      .map(|chunk| {
+        (
+          chunk.file_name.clone(),
+          chunk
+            .modules
+            .iter()
+            .map(|m| m.path().trim_start_matches('/').to_string())
+            .collect(),
+        )
+      })
+      .collect()
+  }
+
+  #[test]
+  fn single_entry() {
+    let graph = build(
+      &["main.ts"],
+      &[
+        ("main.ts", &[("a.ts", false)]),
+        ("a.ts", &[("b.json", false)]),
+      ],
+    );
+    assert_eq!(
+      chunk_modules(&graph),
+      vec![(
+        "main.js".to_string(),
+        vec![
+          "main.ts".to_string(),
+          "a.ts".to_string(),
+          "b.json".to_string()
+        ]
+      )]
+    );
+  }
+
+  #[test]
+  fn shared_dependencies() {
+    let graph = build(
+      &["a.ts", "b.ts"],
+      &[
+        ("a.ts", &[("shared.ts", false), ("only_a.ts", false)]),
+        ("b.ts", &[("shared.ts", false)]),
+        ("shared.ts", &[("util.ts", false)]),
+      ],
+    );
+    assert_eq!(
+      chunk_modules(&graph),
+      vec![
+        (
+          "a.js".to_string(),
+          vec!["a.ts".to_string(), "only_a.ts".to_string()]
+        ),
+        ("b.js".to_string(), vec!["b.ts".to_string()]),
+        (
+          "chunk-shared.js".to_string(),
+          vec!["shared.ts".to_string(), "util.ts".to_string()]
+        ),
+      ]
+    );
+    assert_eq!(graph.chunks[0].imports, vec!["chunk-shared.js"]);
+    assert_eq!(graph.chunks[1].imports, vec!["chunk-shared.js"]);
+    let a = ModuleSpecifier::parse("file:///a.ts").unwrap();
+    let shared = ModuleSpecifier::parse("file:///shared.ts").unwrap();
+    let only_a = ModuleSpecifier::parse("file:///only_a.ts").unwrap();
+    assert_eq!(
+      graph.cross_chunk_specifier(&a, &shared),
+      Some("./chunk-shared.js".to_string())
+    );
+    assert_eq!(graph.cross_chunk_specifier(&a, &only_a), None);
+  }
+
+  #[test]
+  fn dynamic_imports() {
+    let graph = build(
+      &["main.ts"],
+      &[
+        ("main.ts", &[("lazy.ts", true), ("dep.ts", false)]),
+        ("lazy.ts", &[("dep.ts", false), ("lazy_dep.ts", false)]),
+      ],
+    );
+    assert_eq!(
+      chunk_modules(&graph),
+      vec![
+        ("main.js".to_string(), vec!["main.ts".to_string()]),
+        (
+          "chunk-lazy.js".to_string(),
+          vec!["lazy.ts".to_string(), "lazy_dep.ts".to_string()]
+        ),
+        ("chunk-dep.js".to_string(), vec!["dep.ts".to_string()]),
+      ]
+    );
+    assert_eq!(graph.chunks[0].imports, vec!["chunk-dep.js"]);
+    assert_eq!(graph.chunks[0].dynamic_imports, vec!["chunk-lazy.js"]);
+    assert_eq!(graph.chunks[1].imports, vec!["chunk-dep.js"]);
+  }
+
+  #[test]
+  fn cycles() {
+    let graph = build(
+      &["main.ts"],
+      &[
+        ("main.ts", &[("a.ts", false)]),
+        ("a.ts", &[("b.ts", false)]),
+        ("b.ts", &[("a.ts", false)]),
+      ],
+    );
+    assert_eq!(
+      chunk_modules(&graph),
+      vec![(
+        "main.js".to_string(),
+        vec![
+          "main.ts".to_string(),
+          "a.ts".to_string(),
+          "b.ts".to_string()
+        ]
+      )]
+    );
+
+    let graph = build(
+      &["x.ts", "y.ts"],
+      &[
+        ("x.ts", &[("a.ts", false)]),
+        ("y.ts", &[("b.ts", false)]),
+        ("a.ts", &[("b.ts", false)]),
+        ("b.ts", &[("a.ts", false)]),
+      ],
+    );
+    // both modules of the cycle are imported from different chunks
+    assert_eq!(
+      chunk_modules(&graph),
+      vec![
+        ("x.js".to_string(), vec!["x.ts".to_string()]),
+        ("y.js".to_string(), vec!["y.ts".to_string()]),
+        ("chunk-b.js".to_string(), vec!["b.ts".to_string()]),
+        ("chunk-a.js".to_string(), vec!["a.ts".to_string()]),
+      ]
+    );
+  }
+
+  #[test]
+  fn unique_file_names() {
+    let graph = build(
+      &["a/mod.ts", "b/mod.ts"],
+      &[("a/mod.ts", &[("c/mod.ts", true)])],
+    );
+    let names = graph
+      .chunks
+      .iter()
+      .map(|c| c.file_name.as_str())
+      .collect::<Vec<_>>();
+    assert_eq!(names, vec!["mod.js", "mod-2.js", "chunk-mod.js"]);
+  }
+}
@@ -0,0 +1,160 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashSet;
+
+use deno_ast::swc::ast;
+use deno_ast::swc::codegen::text_writer::JsWriter;
+use deno_ast::swc::codegen::Emitter;
+use deno_ast::swc::common::sync::Lrc;
+use deno_ast::swc::common::BytePos;
+use deno_ast::swc::common::FileName;
+use deno_ast::swc::common::LineCol;
+use deno_ast::swc::common::SourceMap;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::sourcemap;
+
+pub struct EmittedModule {
+  pub code: String,
+  /// The generated positions of the spans of the emitted module.
+  pub mappings: Vec<(BytePos, LineCol)>,
+}
+
+pub fn emit_module(
+  cm: &Lrc<SourceMap>,
+  module: &ast::Module,
+  minify: bool,
+) -> Result<EmittedModule, AnyError> {
+  let mut buf = Vec::new();
+  let mut mappings = Vec::new();
+  {
+    let writer = JsWriter::new(cm.clone(), "\n", &mut buf, Some(&mut mappings));
+    let mut cfg = deno_ast::swc::codegen::Config::default();
+    cfg.minify = minify;
+    let mut emitter = Emitter {
+      cfg,
+      cm: cm.clone(),
+      comments: None,
+      wr: writer,
+    };
+    emitter
+      .emit_module(module)
+      .context("Unable to emit during bundling.")?;
+  }
+  Ok(EmittedModule {
+    code: String::from_utf8(buf)?,
+    mappings,
+  })
+}
+
+/// Creates the source map of a module emitted from the files in `cm`. When
+/// one of the files was generated itself, `input_source_map` provides its
+/// source map, so the positions are mapped to the original sources.
+pub fn compose_source_map<'a>(
+  cm: &SourceMap,
+  mappings: &[(BytePos, LineCol)],
+  file: Option<&str>,
+  input_source_map: impl Fn(&FileName) -> Option<&'a sourcemap::SourceMap>,
+) -> Result<String, AnyError> {
+  let mut builder = sourcemap::SourceMapBuilder::new(file);
+  let mut sources_with_contents = HashSet::new();
+  let mut tokens = Vec::with_capacity(mappings.len());
+  for (pos, generated) in mappings {
+    if pos.0 == 0 {
+      // dummy span of generated code
+      continue;
+    }
+    let loc = cm.lookup_char_pos(*pos);
+    let line = loc.line as u32 - 1;
+    let col = loc.col.0 as u32;
+    match input_source_map(&loc.file.name) {
+      Some(input) => {
+        let Some(token) = input.lookup_token(line, col) else {
+          continue;
+        };
+        let Some(source) = token.get_source() else {
+          continue;
+        };
+        let source = builder.add_source(source);
+        if sources_with_contents.insert(source) {
+          builder.set_source_contents(
+            source,
+            input.get_source_contents(token.get_src_id()),
+          );
+        }
+        tokens.push((
+          generated.line,
+          generated.col,
+          source,
+          token.get_src_line(),
+          token.get_src_col(),
+        ));
+      }
+      None => {
+        let source = builder.add_source(&loc.file.name.to_string());
+        if sources_with_contents.insert(source) {
+          builder.set_source_contents(source, Some(loc.file.src.as_str()));
+        }
+        tokens.push((generated.line, generated.col, source, line, col));
+      }
+    }
+  }
+  // the mappings are encoded in the order of their generated positions
+  tokens.sort();
+  tokens.dedup_by_key(|(generated_line, generated_col, ..)| {
+    (*generated_line, *generated_col)
+  });
+  for (generated_line, generated_col, source, line, col) in tokens {
+    builder.add_raw(
+      generated_line,
+      generated_col,
+      line,
+      col,
+      Some(source),
+      None,
+    );
+  }
+
+  let mut buf = Vec::new();
+  builder.into_sourcemap().to_writer(&mut buf)?;
+  Ok(String::from_utf8(buf)?)
+}
+
+#[cfg(test)]
+mod test {
+  use deno_core::ModuleSpecifier;
+
+  use super::*;
+
+  #[test]
+  fn compose_source_map_of_emitted_file() {
+    let cm = SourceMap::default();
+    let specifier = ModuleSpecifier::parse("file:///a.ts").unwrap();
+    let file = cm.new_source_file(
+      FileName::Url(specifier),
+      "const a = 1;\nconst b = 2;\n".to_string(),
+    );
+    let mappings = vec![
+      (file.start_pos + BytePos(19), LineCol { line: 2, col: 4 }),
+      (file.start_pos, LineCol { line: 0, col: 0 }),
+      (file.start_pos + BytePos(6), LineCol { line: 0, col: 6 }),
+      // dummy span
+      (BytePos(0), LineCol { line: 1, col: 0 }),
+    ];
+    let json =
+      compose_source_map(&cm, &mappings, Some("bundle.js"), |_| None).unwrap();
+
+    let map = sourcemap::SourceMap::from_slice(json.as_bytes()).unwrap();
+    assert_eq!(map.get_file(), Some("bundle.js"));
+    assert_eq!(map.get_source_count(), 1);
+    assert_eq!(
+      map.get_source_contents(0),
+      Some("const a = 1;\nconst b = 2;\n")
+    );
+    let token = map.lookup_token(0, 7).unwrap();
+    assert_eq!(token.get_source(), Some("file:///a.ts"));
+    assert_eq!((token.get_src_line(), token.get_src_col()), (0, 6));
+    let token = map.lookup_token(2, 4).unwrap();
+    assert_eq!((token.get_src_line(), token.get_src_col()), (1, 6));
+  }
+}
@@ -0,0 +1,462 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Renames the variables of a bundle to short names.
+//!
+//! The bindings are resolved with swc's resolver, so every binding and its
+//! references share the same syntax context. Each renamed binding gets a name
+//! that is unique within the module, which keeps the renaming independent of
+//! the scopes the bindings are used in.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+
+use deno_ast::swc::ast;
+use deno_ast::swc::atoms::Atom;
+use deno_ast::swc::common::sync::Lrc;
+use deno_ast::swc::common::FileName;
+use deno_ast::swc::common::FilePathMapping;
+use deno_ast::swc::common::Globals;
+use deno_ast::swc::common::Mark;
+use deno_ast::swc::common::SourceMap;
+use deno_ast::swc::common::Span;
+use deno_ast::swc::common::SyntaxContext;
+use deno_ast::swc::common::DUMMY_SP;
+use deno_ast::swc::common::GLOBALS;
+use deno_ast::swc::parser::parse_file_as_module;
+use deno_ast::swc::parser::Syntax;
+use deno_ast::swc::transforms::resolver;
+use deno_ast::swc::visit::noop_visit_mut_type;
+use deno_ast::swc::visit::noop_visit_type;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitMut;
+use deno_ast::swc::visit::VisitMutWith;
+use deno_ast::swc::visit::VisitWith;
+use deno_core::anyhow::anyhow;
+use deno_core::error::AnyError;
+use deno_core::sourcemap;
+use deno_core::ModuleSpecifier;
+
+use super::codegen::compose_source_map;
+use super::codegen::emit_module;
+
+/// Minifies a bundle emitted as a single file, mapping the source map of the
+/// bundle to the minified code when there is one.
+pub fn minify_code(
+  specifier: &ModuleSpecifier,
+  code: &str,
+  maybe_source_map: Option<&str>,
+) -> Result<(String, Option<String>), AnyError> {
+  let input_source_map = maybe_source_map
+    .map(|map| sourcemap::SourceMap::from_slice(map.as_bytes()))
+    .transpose()?;
+  let globals = Globals::new();
+  GLOBALS.set(&globals, || {
+    let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+    let fm =
+      cm.new_source_file(FileName::Url(specifier.clone()), code.to_string());
+    let mut module = parse_file_as_module(
+      &fm,
+      Syntax::Es(Default::default()),
+      ast::EsVersion::latest(),
+      None,
+      &mut Vec::new(),
+    )
+    .map_err(|err| {
+      anyhow!("Unable to parse bundle: {}", err.into_kind().msg())
+    })?;
+    mangle_identifiers(&mut module);
+    let emitted = emit_module(&cm, &module, true)?;
+    let maybe_source_map = input_source_map
+      .as_ref()
+      .map(|input| {
+        compose_source_map(&cm, &emitted.mappings, None, |_| Some(input))
+      })
+      .transpose()?;
+    Ok((emitted.code, maybe_source_map))
+  })
+}
+
+/// Gives the variables of the module short names. This must be called while
+/// `GLOBALS` is set.
+pub fn mangle_identifiers(module: &mut ast::Module) {
+  // the module might have been resolved before, for example by the bundler,
+  // in which case the names are already unique
+  module.visit_mut_with(&mut ClearSyntaxContexts);
+  let unresolved_mark = Mark::new();
+  let top_level_mark = Mark::new();
+  module.visit_mut_with(&mut resolver(unresolved_mark, top_level_mark, false));
+
+  let mut collector = BindingCollector {
+    unresolved_ctxt: SyntaxContext::empty().apply_mark(unresolved_mark),
+    bindings: Vec::new(),
+    binding_indexes: HashMap::new(),
+    used_names: HashSet::new(),
+    exported: HashSet::new(),
+    has_direct_eval: false,
+  };
+  module.visit_with(&mut collector);
+  if collector.has_direct_eval {
+    // the evaluated code might reference any variable in scope
+    return;
+  }
+
+  let mut bindings = collector
+    .bindings
+    .into_iter()
+    .filter(|(id, _)| !collector.exported.contains(id))
+    .collect::<Vec<_>>();
+  // the most referenced bindings get the shortest names
+  bindings.sort_by(|(_, a), (_, b)| b.cmp(a));
+  let mut names = (0..)
+    .map(mangled_name)
+    .filter(|name| {
+      !RESERVED_NAMES.contains(&name.as_str())
+        && !collector.used_names.contains(name)
+    })
+    .map(Atom::from);
+  let renames = bindings
+    .into_iter()
+    .map(|(id, _)| (id, names.next().unwrap()))
+    .collect::<HashMap<_, _>>();
+  module.visit_mut_with(&mut Renamer { renames });
+}
+
+/// Names that can't be used for variables in strict mode or that would
+/// shadow commonly used globals.
+const RESERVED_NAMES: &[&str] = &[
+  "arguments",
+  "as",
+  "await",
+  "break",
+  "case",
+  "catch",
+  "class",
+  "const",
+  "continue",
+  "debugger",
+  "default",
+  "delete",
+  "do",
+  "else",
+  "enum",
+  "eval",
+  "export",
+  "extends",
+  "false",
+  "finally",
+  "for",
+  "function",
+  "if",
+  "implements",
+  "import",
+  "in",
+  "instanceof",
+  "interface",
+  "let",
+  "new",
+  "null",
+  "of",
+  "package",
+  "private",
+  "protected",
+  "public",
+  "return",
+  "static",
+  "super",
+  "switch",
+  "this",
+  "throw",
+  "true",
+  "try",
+  "typeof",
+  "undefined",
+  "var",
+  "void",
+  "while",
+  "with",
+  "yield",
+  "NaN",
+  "Infinity",
+];
+
+/// Gets the `n`th name of the sequence `a`, `b`, ..., `$`, `aa`, `ba`, ...
+fn mangled_name(mut n: usize) -> String {
+  const FIRST_CHARS: &[u8] =
+    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_$";
+  const CHARS: &[u8] =
+    b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ_$0123456789";
+  let mut name = String::new();
+  name.push(FIRST_CHARS[n % FIRST_CHARS.len()] as char);
+  n /= FIRST_CHARS.len();
+  while n > 0 {
+    n -= 1;
+    name.push(CHARS[n % CHARS.len()] as char);
+    n /= CHARS.len();
+  }
+  name
+}
+
+struct ClearSyntaxContexts;
+
+impl VisitMut for ClearSyntaxContexts {
+  noop_visit_mut_type!();
+
+  fn visit_mut_span(&mut self, span: &mut Span) {
+    span.ctxt = SyntaxContext::empty();
+  }
+}
+
+struct BindingCollector {
+  unresolved_ctxt: SyntaxContext,
+  /// The bindings with the number of references in order of appearance.
+  bindings: Vec<(ast::Id, usize)>,
+  binding_indexes: HashMap<ast::Id, usize>,
+  used_names: HashSet<String>,
+  /// The top level bindings that are exported by their declaration.
+  exported: HashSet<ast::Id>,
+  has_direct_eval: bool,
+}
+
+impl Visit for BindingCollector {
+  noop_visit_type!();
+
+  fn visit_ident(&mut self, ident: &ast::Ident) {
+    self.used_names.insert(ident.sym.to_string());
+    // property names and labels have no syntax context and unresolved
+    // identifiers are globals
+    let ctxt = ident.span.ctxt;
+    if ctxt == SyntaxContext::empty()
+      || ctxt == self.unresolved_ctxt
+      || &*ident.sym == "arguments"
+    {
+      return;
+    }
+    let id = ident.to_id();
+    match self.binding_indexes.get(&id) {
+      Some(index) => self.bindings[*index].1 += 1,
+      None => {
+        self.binding_indexes.insert(id.clone(), self.bindings.len());
+        self.bindings.push((id, 1));
+      }
+    }
+  }
+
+  fn visit_call_expr(&mut self, call: &ast::CallExpr) {
+    if let ast::Callee::Expr(callee) = &call.callee {
+      if let ast::Expr::Ident(ident) = &**callee {
+        if &*ident.sym == "eval" && ident.span.ctxt == self.unresolved_ctxt {
+          self.has_direct_eval = true;
+        }
+      }
+    }
+    call.visit_children_with(self);
+  }
+
+  fn visit_export_decl(&mut self, export: &ast::ExportDecl) {
+    match &export.decl {
+      ast::Decl::Class(decl) => {
+        self.exported.insert(decl.ident.to_id());
+      }
+      ast::Decl::Fn(decl) => {
+        self.exported.insert(decl.ident.to_id());
+      }
+      ast::Decl::Var(decl) => {
+        let mut collector = PatBindingCollector {
+          bindings: &mut self.exported,
+        };
+        for declarator in &decl.decls {
+          declarator.name.visit_with(&mut collector);
+        }
+      }
+      _ => {}
+    }
+    export.visit_children_with(self);
+  }
+}
+
+/// Collects the bindings declared by a pattern.
+struct PatBindingCollector<'a> {
+  bindings: &'a mut HashSet<ast::Id>,
+}
+
+impl Visit for PatBindingCollector<'_> {
+  noop_visit_type!();
+
+  fn visit_binding_ident(&mut self, ident: &ast::BindingIdent) {
+    self.bindings.insert(ident.id.to_id());
+  }
+
+  fn visit_expr(&mut self, _: &ast::Expr) {
+    // default values don't declare bindings of the pattern
+  }
+
+  fn visit_prop_name(&mut self, _: &ast::PropName) {}
+}
+
+struct Renamer {
+  renames: HashMap<ast::Id, Atom>,
+}
+
+impl VisitMut for Renamer {
+  noop_visit_mut_type!();
+
+  fn visit_mut_ident(&mut self, ident: &mut ast::Ident) {
+    if let Some(name) = self.renames.get(&ident.to_id()) {
+      ident.sym = name.clone();
+    }
+  }
+
+  fn visit_mut_prop(&mut self, prop: &mut ast::Prop) {
+    let ast::Prop::Shorthand(ident) = prop else {
+      prop.visit_mut_children_with(self);
+      return;
+    };
+    let name = ident.sym.clone();
+    self.visit_mut_ident(ident);
+    if ident.sym != name {
+      // `{ a }` becomes `{ a: b }`
+      *prop = ast::Prop::KeyValue(ast::KeyValueProp {
+        key: ast::PropName::Ident(ast::Ident::new(name, DUMMY_SP)),
+        value: Box::new(ast::Expr::Ident(ident.clone())),
+      });
+    }
+  }
+
+  fn visit_mut_object_pat_prop(&mut self, prop: &mut ast::ObjectPatProp) {
+    let ast::ObjectPatProp::Assign(assign) = prop else {
+      prop.visit_mut_children_with(self);
+      return;
+    };
+    let name = assign.key.id.sym.clone();
+    assign.visit_mut_children_with(self);
+    if assign.key.id.sym != name {
+      // `{ a = 1 }` becomes `{ a: b = 1 }`
+      let binding = ast::Pat::Ident(assign.key.clone());
+      let value = match assign.value.take() {
+        Some(default) => ast::Pat::Assign(ast::AssignPat {
+          span: assign.span,
+          left: Box::new(binding),
+          right: default,
+        }),
+        None => binding,
+      };
+      *prop = ast::ObjectPatProp::KeyValue(ast::KeyValuePatProp {
+        key: ast::PropName::Ident(ast::Ident::new(name, DUMMY_SP)),
+        value: Box::new(value),
+      });
+    }
+  }
+
+  fn visit_mut_export_named_specifier(
+    &mut self,
+    specifier: &mut ast::ExportNamedSpecifier,
+  ) {
+    let ast::ModuleExportName::Ident(orig) = &mut specifier.orig else {
+      return;
+    };
+    let name = orig.sym.clone();
+    self.visit_mut_ident(orig);
+    if orig.sym != name && specifier.exported.is_none() {
+      // `export { a }` becomes `export { b as a }`
+      specifier.exported = Some(ast::ModuleExportName::Ident(ast::Ident::new(
+        name, DUMMY_SP,
+      )));
+    }
+  }
+
+  fn visit_mut_import_named_specifier(
+    &mut self,
+    specifier: &mut ast::ImportNamedSpecifier,
+  ) {
+    let name = specifier.local.sym.clone();
+    self.visit_mut_ident(&mut specifier.local);
+    if specifier.local.sym != name && specifier.imported.is_none() {
+      // `import { a }` becomes `import { a as b }`
+      specifier.imported = Some(ast::ModuleExportName::Ident(ast::Ident::new(
+        name, DUMMY_SP,
+      )));
+    }
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  fn mangle(source: &str) -> String {
+    let globals = Globals::new();
+    GLOBALS.set(&globals, || {
+      let cm = Lrc::new(SourceMap::new(FilePathMapping::empty()));
+      let fm = cm.new_source_file(
+        FileName::Url(ModuleSpecifier::parse("file:///mod.js").unwrap()),
+        source.to_string(),
+      );
+      let mut module = parse_file_as_module(
+        &fm,
+        Syntax::Es(Default::default()),
+        ast::EsVersion::latest(),
+        None,
+        &mut Vec::new(),
+      )
+      .unwrap();
+      mangle_identifiers(&mut module);
+      emit_module(&cm, &module, true).unwrap().code
+    })
+  }
+
+  #[test]
+  fn mangled_names() {
+    assert_eq!(mangled_name(0), "a");
+    assert_eq!(mangled_name(53), "$");
+    assert_eq!(mangled_name(54), "aa");
+    assert_eq!(mangled_name(55), "ba");
+    assert_eq!(mangled_name(108), "ab");
+  }
+
+  #[test]
+  fn renames_bindings() {
+    let code = mangle(
+      "const message = 'hi';
+function greet(person) { return message + person; }
+console.log(greet('you'));",
+    );
+    assert!(!code.contains("message"));
+    assert!(!code.contains("greet"));
+    assert!(!code.contains("person"));
+    assert!(code.contains("console.log("));
+    assert!(code.contains("function b(c)"));
+  }
+
+  #[test]
+  fn keeps_exports_and_properties() {
+    let code = mangle(
+      "const value = { count: 1 };
+const { count } = value;
+export const total = count + 1;
+export { value, count as amount };",
+    );
+    assert!(code.contains("count:1"));
+    assert!(code.contains("{count:b}=a"));
+    assert!(code.contains("export const total=b+1"));
+    assert!(code.contains("a as value"));
+    assert!(code.contains("b as amount"));
+  }
+
+  #[test]
+  fn shorthand_properties_and_imports() {
+    let code = mangle(
+      "import { helper } from './chunk.js';
+const local = helper();
+export default { local };",
+    );
+    assert!(code.contains("helper as a"));
+    assert!(code.contains("b=a()"));
+    assert!(code.contains("local:b"));
+  }
+
+  #[test]
+  fn skips_direct_eval() {
+    let code = mangle("const value = 1; eval('value');");
+    assert!(code.contains("const value=1"));
+  }
+}
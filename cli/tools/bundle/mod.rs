@@ -0,0 +1,280 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_ast::EmitOptions;
+use deno_ast::SourceMapOption;
+use deno_ast::TranspileOptions;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::resolve_url_or_path;
+use deno_core::serde_json;
+use deno_graph::Module;
+use deno_terminal::colors;
+
+use crate::args::BundleFlags;
+use crate::args::BundleSourceMap;
+use crate::args::CliOptions;
+use crate::args::Flags;
+use crate::args::TsConfigType;
+use crate::factory::CliFactory;
+use crate::factory::CliFactoryBuilder;
+use crate::graph_util::error_for_any_npm_specifier;
+use crate::util;
+use crate::util::display;
+use crate::util::text_encoding::append_inline_source_map;
+
+use self::chunk_emit::ChunkEmitOptions;
+use self::chunk_graph::ChunkGraph;
+
+mod chunk_emit;
+mod chunk_graph;
+mod codegen;
+mod minify;
+
+pub async fn bundle(
+  flags: Flags,
+  bundle_flags: BundleFlags,
+) -> Result<(), AnyError> {
+  log::info!(
+    "{}",
+    colors::yellow("⚠️ Warning: `deno bundle` is deprecated and will be removed in Deno 2.0.\nUse an alternative bundler like \"deno_emit\", \"esbuild\" or \"rollup\" instead."),
+  );
+
+  if let Some(watch_flags) = &bundle_flags.watch {
+    util::file_watcher::watch_func(
+      flags,
+      util::file_watcher::PrintConfig::new(
+        "Bundle",
+        !watch_flags.no_clear_screen,
+      ),
+      move |flags, watcher_communicator, _changed_paths| {
+        let bundle_flags = bundle_flags.clone();
+        Ok(async move {
+          let factory = CliFactoryBuilder::new().build_from_flags_for_watcher(
+            flags,
+            watcher_communicator.clone(),
+          )?;
+          let cli_options = factory.cli_options();
+          let _ = watcher_communicator.watch_paths(cli_options.watch_paths());
+          bundle_action(factory, &bundle_flags).await?;
+
+          Ok(())
+        })
+      },
+    )
+    .await?;
+  } else {
+    let factory = CliFactory::from_flags(flags)?;
+    bundle_action(factory, &bundle_flags).await?;
+  }
+
+  Ok(())
+}
+
+async fn bundle_action(
+  factory: CliFactory,
+  bundle_flags: &BundleFlags,
+) -> Result<(), AnyError> {
+  if bundle_flags.source_map == Some(BundleSourceMap::External)
+    && bundle_flags.out_file.is_none()
+    && bundle_flags.out_dir.is_none()
+  {
+    bail!(
+      "An external source map requires an output file. Use \
+       --sourcemap=inline when writing the bundle to stdout."
+    );
+  }
+  let cli_options = factory.cli_options();
+  let mut roots = vec![cli_options.resolve_main_module()?];
+  for source_file in &bundle_flags.additional_source_files {
+    roots.push(resolve_url_or_path(source_file, cli_options.initial_cwd())?);
+  }
+  log::debug!(">>>>> bundle START");
+  let module_graph_creator = factory.module_graph_creator().await?;
+  let cli_options = factory.cli_options();
+
+  let graph = module_graph_creator
+    .create_graph_and_maybe_check(roots.clone())
+    .await?;
+
+  let mut paths_to_watch: Vec<PathBuf> = graph
+    .specifiers()
+    .filter_map(|(_, r)| {
+      r.ok().and_then(|module| match module {
+        Module::Js(m) => m.specifier.to_file_path().ok(),
+        Module::Json(m) => m.specifier.to_file_path().ok(),
+        // nothing to watch
+        Module::Node(_) | Module::Npm(_) | Module::External(_) => None,
+      })
+    })
+    .collect();
+
+  if let Ok(Some(import_map_path)) = cli_options
+    .resolve_specified_import_map_specifier()
+    .map(|ms| ms.and_then(|ref s| s.to_file_path().ok()))
+  {
+    paths_to_watch.push(import_map_path);
+  }
+
+  // at the moment, we don't support npm specifiers in deno bundle, so show an error
+  error_for_any_npm_specifier(&graph)?;
+
+  if let Some(out_dir) = &bundle_flags.out_dir {
+    let out_dir = cli_options.initial_cwd().join(out_dir);
+    for root in &roots {
+      log::info!("{} {}", colors::green("Bundle"), root);
+    }
+    let chunk_graph = ChunkGraph::build(&graph, &roots);
+    let (transpile_options, emit_options) =
+      resolve_transpile_and_emit_options(cli_options)?;
+    let chunks = chunk_emit::emit_chunks(
+      &graph,
+      &chunk_graph,
+      ChunkEmitOptions {
+        transpile_options: &transpile_options,
+        emit_options: &emit_options,
+        minify: bundle_flags.minify,
+        source_maps: bundle_flags.source_map.is_some(),
+      },
+    )?;
+    log::debug!(">>>>> bundle END");
+    std::fs::create_dir_all(&out_dir).with_context(|| {
+      format!("Failed creating output directory {}", out_dir.display())
+    })?;
+    for chunk in chunks {
+      let map_file_name = format!("{}.map", chunk.file_name);
+      let (code, maybe_map) = link_source_map(
+        chunk.code,
+        chunk.maybe_source_map,
+        bundle_flags.source_map,
+        &map_file_name,
+      );
+      write_output(&out_dir.join(&chunk.file_name), code.as_bytes())?;
+      if let Some(map) = maybe_map {
+        write_output(&out_dir.join(&map_file_name), map.as_bytes())?;
+      }
+    }
+    let manifest = serde_json::to_string_pretty(&chunk_graph.manifest())?;
+    write_output(&out_dir.join("manifest.json"), manifest.as_bytes())?;
+    return Ok(());
+  }
+
+  let bundle_output =
+    bundle_module_graph(graph.as_ref(), cli_options, bundle_flags)?;
+  let (code, maybe_map) = if bundle_flags.minify {
+    minify::minify_code(
+      &graph.roots[0],
+      &bundle_output.code,
+      bundle_output.maybe_map.as_deref(),
+    )?
+  } else {
+    (bundle_output.code, bundle_output.maybe_map)
+  };
+  log::debug!(">>>>> bundle END");
+  let out_file = &bundle_flags.out_file;
+
+  if let Some(out_file) = out_file {
+    let out_file = cli_options.initial_cwd().join(out_file);
+    let ext = if let Some(curr_ext) = out_file.extension() {
+      format!("{}.map", curr_ext.to_string_lossy())
+    } else {
+      "map".to_string()
+    };
+    let map_out_file = out_file.with_extension(ext);
+    let map_file_name = map_out_file
+      .file_name()
+      .map(|name| name.to_string_lossy().to_string())
+      .unwrap_or_default();
+    let (code, maybe_map) =
+      link_source_map(code, maybe_map, bundle_flags.source_map, &map_file_name);
+    write_output(&out_file, code.as_bytes())?;
+    if let Some(bundle_map) = maybe_map {
+      write_output(&map_out_file, bundle_map.as_bytes())?;
+    }
+  } else {
+    let (code, _) =
+      link_source_map(code, maybe_map, bundle_flags.source_map, "");
+    println!("{}", code);
+  }
+  Ok(())
+}
+
+/// Adds the reference to the source map requested with `--sourcemap` to the
+/// code. Returns the source map when it is written to a separate file.
+fn link_source_map(
+  mut code: String,
+  maybe_source_map: Option<String>,
+  kind: Option<BundleSourceMap>,
+  map_file_name: &str,
+) -> (String, Option<String>) {
+  match (maybe_source_map, kind) {
+    (Some(source_map), Some(BundleSourceMap::Inline)) => {
+      append_inline_source_map(&mut code, source_map.as_bytes());
+      (code, None)
+    }
+    (Some(source_map), Some(BundleSourceMap::External)) => {
+      if !code.ends_with('\n') {
+        code.push('\n');
+      }
+      code.push_str(&format!("//# sourceMappingURL={}", map_file_name));
+      (code, Some(source_map))
+    }
+    (maybe_source_map, _) => (code, maybe_source_map),
+  }
+}
+
+fn write_output(path: &Path, bytes: &[u8]) -> Result<(), AnyError> {
+  util::fs::write_file(path, bytes, 0o644)?;
+  log::info!(
+    "{} {:?} ({})",
+    colors::green("Emit"),
+    path,
+    colors::gray(display::human_size(bytes.len() as f64))
+  );
+  Ok(())
+}
+
+fn resolve_transpile_and_emit_options(
+  cli_options: &CliOptions,
+) -> Result<(TranspileOptions, EmitOptions), AnyError> {
+  let ts_config_result =
+    cli_options.resolve_ts_config_for_emit(TsConfigType::Bundle)?;
+  if !cli_options.type_check_mode().is_true() {
+    if let Some(ignored_options) = ts_config_result.maybe_ignored_options {
+      log::warn!("{}", ignored_options);
+    }
+  }
+
+  crate::args::ts_config_to_transpile_and_emit_options(
+    ts_config_result.ts_config,
+  )
+}
+
+fn bundle_module_graph(
+  graph: &deno_graph::ModuleGraph,
+  cli_options: &CliOptions,
+  bundle_flags: &BundleFlags,
+) -> Result<deno_emit::BundleEmit, AnyError> {
+  log::info!("{} {}", colors::green("Bundle"), graph.roots[0]);
+
+  let (transpile_options, mut emit_options) =
+    resolve_transpile_and_emit_options(cli_options)?;
+  if bundle_flags.source_map.is_some() {
+    // the source map is linked to the code after minifying
+    emit_options.source_map = SourceMapOption::Separate;
+    emit_options.inline_sources = true;
+  }
+  deno_emit::bundle_graph(
+    graph,
+    deno_emit::BundleOptions {
+      minify: false,
+      bundle_type: deno_emit::BundleType::Module,
+      emit_options,
+      emit_ignore_directives: true,
+      transpile_options,
+    },
+  )
+}
@@ -0,0 +1,852 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+use std::ffi::OsStr;
+use std::io::Read;
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_cache_dir::url_to_filename;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::url::Url;
+use deno_graph::ModuleGraph;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::args::jsr_url;
+use crate::args::npm_registry_url;
+use crate::args::CacheFlags;
+use crate::args::Flags;
+use crate::args::Lockfile;
+use crate::cache::HttpCache;
+use crate::cache::RegisteredProjects;
+use crate::colors;
+use crate::factory::CliFactory;
+use crate::http_util::HeadersMap;
+use crate::npm::CliNpmResolver;
+use crate::npm::NpmCacheDir;
+use crate::util::checksum;
+use crate::util::display::human_size;
+use crate::util::fs::atomic_write_file;
+use crate::util::fs::dir_size;
+
+/// Bump this when the layout of the archive changes.
+const ARCHIVE_VERSION: u32 = 1;
+const ARCHIVE_MANIFEST_PATH: &str = "manifest.json";
+/// Written to an npm package folder while it's being extracted.
+const NPM_PACKAGE_SYNC_LOCK_FILENAME: &str = ".deno_sync_lock";
+
+/// Describes the remote modules and npm packages stored in an exported cache
+/// archive. The bodies are stored next to it at `modules/<sha256 checksum>`.
+#[derive(Debug, Serialize, Deserialize)]
+struct ArchiveManifest {
+  version: u32,
+  modules: Vec<ArchiveModule>,
+  #[serde(default)]
+  npm_packages: Vec<ArchiveNpmPackage>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ArchiveModule {
+  specifier: Url,
+  headers: HeadersMap,
+  /// The checksum of the body, which is absent for redirects.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  checksum: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ArchiveNpmPackage {
+  /// Ex. `chalk@5.0.1`
+  id: String,
+  /// The files of the package folder, along with the registry information
+  /// of the package name for the first package of each name.
+  files: Vec<ArchiveFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ArchiveFile {
+  /// The path relative to the npm cache directory, separated by `/`.
+  path: String,
+  checksum: String,
+}
+
+fn archive_module_path(checksum: &str) -> String {
+  format!("modules/{checksum}")
+}
+
+/// Collects the bodies of an archive, writing each of them once.
+struct ArchiveBuilder {
+  builder: tar::Builder<Vec<u8>>,
+  written_checksums: HashSet<String>,
+}
+
+impl ArchiveBuilder {
+  fn new() -> Self {
+    Self {
+      builder: tar::Builder::new(Vec::new()),
+      written_checksums: HashSet::new(),
+    }
+  }
+
+  /// Adds the body and returns its checksum.
+  fn add_body(&mut self, bytes: &[u8]) -> Result<String, AnyError> {
+    let checksum = checksum::gen(&[bytes]);
+    if self.written_checksums.insert(checksum.clone()) {
+      self.append(&archive_module_path(&checksum), bytes)?;
+    }
+    Ok(checksum)
+  }
+
+  fn append(&mut self, path: &str, bytes: &[u8]) -> Result<(), AnyError> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(bytes.len() as u64);
+    header.set_mode(0o644);
+    self.builder.append_data(&mut header, path, bytes)?;
+    Ok(())
+  }
+
+  fn finish(mut self, manifest: &ArchiveManifest) -> Result<Vec<u8>, AnyError> {
+    let manifest_bytes = serde_json::to_vec_pretty(manifest)?;
+    self.append(ARCHIVE_MANIFEST_PATH, &manifest_bytes)?;
+    Ok(zstd::stream::encode_all(
+      self.builder.into_inner()?.as_slice(),
+      0,
+    )?)
+  }
+}
+
+/// Errors when the lockfile has a different checksum for the remote module.
+fn verify_lockfile_checksum(
+  maybe_lockfile: Option<&Lockfile>,
+  specifier: &Url,
+  bytes: &[u8],
+) -> Result<(), AnyError> {
+  let Some(lockfile) = maybe_lockfile else {
+    return Ok(());
+  };
+  let Some(expected) = lockfile.content.remote.get(specifier.as_str()) else {
+    return Ok(());
+  };
+  // the lockfile stores the checksum of the source without a byte order mark
+  let source = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(bytes);
+  let actual = checksum::gen(&[source]);
+  if &actual != expected {
+    bail!(
+      concat!(
+        "Integrity check failed for {}, which doesn't match the lockfile.\n\n",
+        "Actual: {}\n",
+        "Expected: {}\n",
+        "Lock file: {}",
+      ),
+      specifier,
+      actual,
+      expected,
+      lockfile.filename.display()
+    );
+  }
+  Ok(())
+}
+
+/// Writes the cached remote modules and npm packages of the graph, along with
+/// the checksum of their bodies, to a zstd compressed tarball. The remote
+/// modules are verified against the lockfile.
+pub async fn export_archive(
+  factory: &CliFactory,
+  graph: &ModuleGraph,
+  path: &Path,
+) -> Result<(), AnyError> {
+  let http_cache = factory.global_http_cache()?;
+  let mut specifiers = graph
+    .modules()
+    .map(|module| module.specifier().clone())
+    .chain(graph.redirects.keys().cloned())
+    .filter(|specifier| matches!(specifier.scheme(), "http" | "https"))
+    .collect::<Vec<_>>();
+  // the package metadata is necessary to resolve jsr specifiers
+  for nv in graph.packages.mappings().values() {
+    for file in [
+      format!("{}/meta.json", nv.name),
+      format!("{}/{}_meta.json", nv.name, nv.version),
+    ] {
+      if let Ok(specifier) = jsr_url().join(&file) {
+        specifiers.push(specifier);
+      }
+    }
+  }
+  specifiers.sort();
+  specifiers.dedup();
+
+  let lockfile = factory.maybe_lockfile().as_ref().map(|l| l.lock());
+  let mut manifest = ArchiveManifest {
+    version: ARCHIVE_VERSION,
+    modules: Vec::with_capacity(specifiers.len()),
+    npm_packages: Vec::new(),
+  };
+  let mut builder = ArchiveBuilder::new();
+  for specifier in specifiers {
+    let key = http_cache.cache_item_key(&specifier)?;
+    let Some(headers) = http_cache.read_headers(&key)? else {
+      continue;
+    };
+    let mut checksum = None;
+    if !headers.contains_key("location") {
+      let Some(bytes) = http_cache.read_file_bytes(
+        &key,
+        None,
+        deno_cache_dir::GlobalToLocalCopy::Disallow,
+      )?
+      else {
+        continue;
+      };
+      verify_lockfile_checksum(lockfile.as_deref(), &specifier, &bytes)?;
+      checksum = Some(builder.add_body(&bytes)?);
+    }
+    manifest.modules.push(ArchiveModule {
+      specifier,
+      headers,
+      checksum,
+    });
+  }
+  drop(lockfile);
+
+  if let Some(npm_resolver) = factory.npm_resolver().await?.as_managed() {
+    let npm_cache_dir = NpmCacheDir::new(factory.deno_dir()?.npm_folder_path());
+    let root = npm_cache_dir.get_cache_location();
+    let mut packages = npm_resolver
+      .snapshot()
+      .all_packages_for_every_system()
+      .cloned()
+      .collect::<Vec<_>>();
+    packages.sort_by_cached_key(|package| package.id.as_serialized());
+    let mut names = HashSet::new();
+    for package in packages {
+      let mut paths = Vec::new();
+      collect_files(
+        &npm_cache_dir.package_folder_for_id(
+          &package.get_package_cache_folder_id(),
+          npm_registry_url(),
+        ),
+        &mut paths,
+      )?;
+      if names.insert(package.id.nv.name.clone()) {
+        let registry_info = npm_cache_dir
+          .package_name_folder(&package.id.nv.name, npm_registry_url())
+          .join("registry.json");
+        if registry_info.is_file() {
+          paths.push(registry_info);
+        }
+      }
+      let mut files = Vec::with_capacity(paths.len());
+      for path in paths {
+        let bytes = std::fs::read(&path)
+          .with_context(|| format!("Failed reading {}", path.display()))?;
+        let relative_path = path
+          .strip_prefix(&root)?
+          .components()
+          .map(|c| c.as_os_str().to_string_lossy())
+          .collect::<Vec<_>>()
+          .join("/");
+        files.push(ArchiveFile {
+          path: relative_path,
+          checksum: builder.add_body(&bytes)?,
+        });
+      }
+      manifest.npm_packages.push(ArchiveNpmPackage {
+        id: package.id.as_serialized(),
+        files,
+      });
+    }
+  }
+
+  let bytes = builder.finish(&manifest)?;
+  atomic_write_file(path, bytes, 0o644)
+    .with_context(|| format!("Failed writing {}", path.display()))?;
+  log::info!(
+    "{} {} to {}",
+    colors::green("Exported"),
+    archive_summary(&manifest),
+    path.display()
+  );
+  Ok(())
+}
+
+/// Populates the cache with the remote modules and npm packages of an
+/// archive written by `export_archive`, verifying the checksum of each of
+/// them against the archive and the remote modules against the lockfile.
+pub fn import_archive(
+  factory: &CliFactory,
+  path: &Path,
+) -> Result<(), AnyError> {
+  let http_cache = factory.global_http_cache()?;
+  let bytes = std::fs::read(path)
+    .with_context(|| format!("Failed reading {}", path.display()))?;
+  let tar_bytes = zstd::stream::decode_all(bytes.as_slice())
+    .with_context(|| format!("Failed decompressing {}", path.display()))?;
+  let mut manifest = None;
+  let mut bodies = BTreeMap::new();
+  let mut archive = tar::Archive::new(tar_bytes.as_slice());
+  for entry in archive.entries()? {
+    let mut entry = entry?;
+    let entry_path = entry.path()?.to_string_lossy().to_string();
+    let mut data = Vec::with_capacity(entry.size() as usize);
+    entry.read_to_end(&mut data)?;
+    if entry_path == ARCHIVE_MANIFEST_PATH {
+      manifest = Some(serde_json::from_slice::<ArchiveManifest>(&data)?);
+    } else {
+      bodies.insert(entry_path, data);
+    }
+  }
+  let Some(manifest) = manifest else {
+    bail!("{} is not an exported cache archive.", path.display());
+  };
+  if manifest.version != ARCHIVE_VERSION {
+    bail!(
+      "Unsupported cache archive version {} in {}.",
+      manifest.version,
+      path.display()
+    );
+  }
+  let get_body = |name: &dyn std::fmt::Display, expected: &str| {
+    let Some(bytes) = bodies.get(&archive_module_path(expected)) else {
+      bail!("Missing the body of {} in the archive.", name);
+    };
+    let actual = checksum::gen(&[bytes]);
+    if actual != expected {
+      bail!(
+        concat!(
+          "Integrity check failed for {}.\n\n",
+          "Actual: {}\n",
+          "Expected: {}",
+        ),
+        name,
+        actual,
+        expected
+      );
+    }
+    Ok(bytes.as_slice())
+  };
+
+  let lockfile = factory.maybe_lockfile().as_ref().map(|l| l.lock());
+  for module in &manifest.modules {
+    let bytes = match &module.checksum {
+      Some(expected) => {
+        let bytes = get_body(&module.specifier, expected)?;
+        verify_lockfile_checksum(
+          lockfile.as_deref(),
+          &module.specifier,
+          bytes,
+        )?;
+        bytes
+      }
+      None => &[],
+    };
+    http_cache.set(&module.specifier, module.headers.clone(), bytes)?;
+  }
+  drop(lockfile);
+
+  let npm_root = factory.deno_dir()?.npm_folder_path();
+  for package in &manifest.npm_packages {
+    for file in &package.files {
+      let relative_path = Path::new(&file.path);
+      if !relative_path
+        .components()
+        .all(|c| matches!(c, std::path::Component::Normal(_)))
+      {
+        bail!(
+          "Invalid path {} of npm package {} in the archive.",
+          file.path,
+          package.id
+        );
+      }
+      let bytes = get_body(&format!("npm:{}", package.id), &file.checksum)?;
+      let file_path = npm_root.join(relative_path);
+      if let Some(parent) = file_path.parent() {
+        std::fs::create_dir_all(parent)
+          .with_context(|| format!("Failed creating {}", parent.display()))?;
+      }
+      atomic_write_file(&file_path, bytes, 0o644)
+        .with_context(|| format!("Failed writing {}", file_path.display()))?;
+    }
+  }
+  log::info!(
+    "{} {} from {}",
+    colors::green("Imported"),
+    archive_summary(&manifest),
+    path.display()
+  );
+  Ok(())
+}
+
+/// Ex. `2 remote modules and 1 npm package`
+fn archive_summary(manifest: &ArchiveManifest) -> String {
+  let mut summary = format!("{} remote modules", manifest.modules.len());
+  match manifest.npm_packages.len() {
+    0 => {}
+    1 => summary.push_str(" and 1 npm package"),
+    count => summary.push_str(&format!(" and {count} npm packages")),
+  }
+  summary
+}
+
+/// Collects the files of a directory recursively, skipping symlinks and the
+/// lock file of a package folder that's being extracted.
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), AnyError> {
+  for path in read_dir_entries(dir)? {
+    let file_type = path.symlink_metadata()?.file_type();
+    if file_type.is_dir() {
+      collect_files(&path, files)?;
+    } else if file_type.is_file()
+      && path.file_name() != Some(OsStr::new(NPM_PACKAGE_SYNC_LOCK_FILENAME))
+    {
+      files.push(path);
+    }
+  }
+  Ok(())
+}
+
+/// The entries of the DENO_DIR that are still in use.
+#[derive(Default)]
+struct ReferencedEntries {
+  /// Cache keys of the remote modules, without extension.
+  /// Ex. `https/deno.land/<hash>`
+  remote_keys: HashSet<PathBuf>,
+  npm_package_folders: HashSet<PathBuf>,
+}
+
+impl ReferencedEntries {
+  fn add_remote(&mut self, specifier: &Url) {
+    if matches!(specifier.scheme(), "http" | "https") {
+      if let Ok(key) = url_to_filename(specifier) {
+        self.remote_keys.insert(key);
+      }
+    }
+  }
+
+  fn add_lockfile(&mut self, lockfile: &Lockfile, npm_cache_dir: &NpmCacheDir) {
+    let content = &lockfile.content;
+    for url in content
+      .remote
+      .keys()
+      .chain(content.redirects.keys())
+      .chain(content.redirects.values())
+    {
+      if let Ok(url) = Url::parse(url) {
+        self.add_remote(&url);
+      }
+    }
+    for id in content.packages.npm.keys() {
+      if let Some((name, version)) = npm_package_id_name_and_version(id) {
+        self.npm_package_folders.insert(
+          npm_cache_dir
+            .package_name_folder(name, npm_registry_url())
+            .join(version),
+        );
+      }
+    }
+  }
+}
+
+/// Gets the name and version of an npm package id of a lockfile.
+/// Ex. `@scope/name@1.0.0_peer@2.0.0` -> (`@scope/name`, `1.0.0`)
+fn npm_package_id_name_and_version(id: &str) -> Option<(&str, &str)> {
+  let (name, rest) = match id.strip_prefix('@') {
+    Some(scoped) => {
+      let index = scoped.find('@')? + 1;
+      (&id[..index], &id[index + 1..])
+    }
+    None => id.split_once('@')?,
+  };
+  let version = rest.split('_').next().unwrap();
+  Some((name, version))
+}
+
+#[derive(Default)]
+struct OriginUsage {
+  total: u64,
+  unreferenced: u64,
+}
+
+enum RemovalKind {
+  File,
+  NpmPackage,
+}
+
+struct Removal {
+  path: PathBuf,
+  size: u64,
+  kind: RemovalKind,
+}
+
+#[derive(Default)]
+struct PruneReport {
+  usage: BTreeMap<String, OriginUsage>,
+  removals: Vec<Removal>,
+}
+
+impl PruneReport {
+  fn add(&mut self, origin: String, path: PathBuf, size: u64, keep: bool) {
+    let usage = self.usage.entry(origin).or_default();
+    usage.total += size;
+    if !keep {
+      usage.unreferenced += size;
+      self.removals.push(Removal {
+        path,
+        size,
+        kind: RemovalKind::File,
+      });
+    }
+  }
+}
+
+/// Removes the cached remote modules, emits and npm packages that aren't
+/// referenced by the graph of the provided modules, the lockfile or the
+/// lockfile of any other project registered in the DENO_DIR. Only lists them
+/// unless `--force` is provided.
+pub async fn prune(
+  flags: Flags,
+  cache_flags: CacheFlags,
+) -> Result<(), AnyError> {
+  let factory = CliFactory::from_flags(flags)?;
+  if !cache_flags.dry_run
+    && cache_flags.files.is_empty()
+    && factory.maybe_lockfile().is_none()
+  {
+    bail!(concat!(
+      "Refusing to prune the cache without a lockfile or modules to keep, ",
+      "since the cache is shared by every project.\n",
+      "Provide the modules of the project or run without --force to only ",
+      "list what would be removed."
+    ));
+  }
+  if !cache_flags.files.is_empty() {
+    let module_load_preparer = factory.module_load_preparer().await?;
+    module_load_preparer
+      .load_and_type_check_files(&cache_flags.files)
+      .await?;
+  }
+
+  let mut referenced = ReferencedEntries::default();
+  let graph = factory.graph_container().graph();
+  for module in graph.modules() {
+    referenced.add_remote(module.specifier());
+  }
+  for (from, to) in &graph.redirects {
+    referenced.add_remote(from);
+    referenced.add_remote(to);
+  }
+  let deno_dir = factory.deno_dir()?;
+  let npm_cache_dir = NpmCacheDir::new(deno_dir.npm_folder_path());
+  if let Some(lockfile) = factory.maybe_lockfile() {
+    referenced.add_lockfile(&lockfile.lock(), &npm_cache_dir);
+  }
+  let registered_projects =
+    RegisteredProjects::new(deno_dir.registered_projects_file_path());
+  for path in registered_projects.existing_lockfiles()? {
+    match Lockfile::new(path.clone(), false) {
+      Ok(lockfile) => referenced.add_lockfile(&lockfile, &npm_cache_dir),
+      Err(err) => bail!(
+        "Failed reading the lockfile of a registered project {}: {:#}",
+        path.display(),
+        err
+      ),
+    }
+  }
+  if let Some(npm_resolver) = factory.npm_resolver().await?.as_managed() {
+    for package in npm_resolver.snapshot().all_packages_for_every_system() {
+      referenced.npm_package_folders.insert(
+        npm_cache_dir.package_folder_for_id(
+          &package.get_package_cache_folder_id(),
+          npm_registry_url(),
+        ),
+      );
+    }
+  }
+
+  let mut report = PruneReport::default();
+  collect_remote_files(&deno_dir.deps_folder_path(), &referenced, &mut report)?;
+  collect_remote_files(&deno_dir.gen_cache.location, &referenced, &mut report)?;
+  collect_npm_packages(
+    &npm_cache_dir.get_cache_location(),
+    &referenced,
+    &mut report,
+  )?;
+
+  log::info!(
+    "{} {}",
+    colors::green("Cache"),
+    deno_dir.root_path_for_display()
+  );
+  for (origin, usage) in &report.usage {
+    log::info!(
+      "  {} {} ({} unreferenced)",
+      origin,
+      human_size(usage.total as f64),
+      human_size(usage.unreferenced as f64),
+    );
+  }
+
+  let mut file_count = 0;
+  let mut package_count = 0;
+  let mut removed_size = 0;
+  for removal in &report.removals {
+    if cache_flags.dry_run {
+      log::info!("Would remove {}", removal.path.display());
+    } else {
+      let result = match removal.kind {
+        RemovalKind::File => std::fs::remove_file(&removal.path),
+        RemovalKind::NpmPackage => std::fs::remove_dir_all(&removal.path),
+      };
+      if let Err(err) = result {
+        log::warn!(
+          "{} Failed removing {}: {:#}",
+          colors::yellow("Warning"),
+          removal.path.display(),
+          err
+        );
+        continue;
+      }
+    }
+    match removal.kind {
+      RemovalKind::File => file_count += 1,
+      RemovalKind::NpmPackage => package_count += 1,
+    }
+    removed_size += removal.size;
+  }
+  log::info!(
+    "{} {} {} and {} npm {} ({}).",
+    if cache_flags.dry_run {
+      "Would remove"
+    } else {
+      "Removed"
+    },
+    file_count,
+    if file_count == 1 { "file" } else { "files" },
+    package_count,
+    if package_count == 1 {
+      "package"
+    } else {
+      "packages"
+    },
+    human_size(removed_size as f64),
+  );
+  Ok(())
+}
+
+/// Collects the files of a directory laid out like the remote module cache.
+/// Ex. `<root>/https/deno.land/<hash>.<ext>`
+fn collect_remote_files(
+  root: &Path,
+  referenced: &ReferencedEntries,
+  report: &mut PruneReport,
+) -> Result<(), AnyError> {
+  for scheme in ["http", "https"] {
+    for host_dir in read_dir_entries(&root.join(scheme))? {
+      if !host_dir.is_dir() {
+        continue;
+      }
+      let host = host_dir.file_name().unwrap().to_string_lossy().to_string();
+      let origin = origin_from_host_dir_name(scheme, &host);
+      for path in read_dir_entries(&host_dir)? {
+        let metadata = path.metadata()?;
+        if !metadata.is_file() {
+          continue;
+        }
+        let file_name = path.file_name().unwrap().to_string_lossy();
+        let key = Path::new(scheme)
+          .join(&host)
+          .join(cache_key_stem(&file_name));
+        let keep = referenced.remote_keys.contains(&key);
+        report.add(origin.clone(), path, metadata.len(), keep);
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Collects the package folders of the npm cache.
+/// Ex. `<root>/registry.npmjs.org/@scope/name/<version>`
+fn collect_npm_packages(
+  root: &Path,
+  referenced: &ReferencedEntries,
+  report: &mut PruneReport,
+) -> Result<(), AnyError> {
+  for registry_dir in read_dir_entries(root)? {
+    if !registry_dir.is_dir() {
+      continue;
+    }
+    let origin = format!(
+      "npm:{}",
+      registry_dir.file_name().unwrap().to_string_lossy()
+    );
+    let mut package_dirs = Vec::new();
+    for path in read_dir_entries(&registry_dir)? {
+      if !path.is_dir() {
+        continue;
+      }
+      if path.file_name().unwrap().to_string_lossy().starts_with('@') {
+        package_dirs
+          .extend(read_dir_entries(&path)?.into_iter().filter(|p| p.is_dir()));
+      } else {
+        package_dirs.push(path);
+      }
+    }
+    for package_dir in package_dirs {
+      for path in read_dir_entries(&package_dir)? {
+        let metadata = path.metadata()?;
+        let usage = report.usage.entry(origin.clone()).or_default();
+        if !metadata.is_dir() {
+          // ex. registry.json
+          usage.total += metadata.len();
+          continue;
+        }
+        let size = dir_size(&path)?;
+        usage.total += size;
+        // copies of a package for different peer dependencies are stored
+        // as `<version>_<copy index>`
+        let file_name = path.file_name().unwrap().to_string_lossy();
+        let version_path =
+          path.with_file_name(file_name.split('_').next().unwrap());
+        if !referenced.npm_package_folders.contains(&path)
+          && !referenced.npm_package_folders.contains(&version_path)
+        {
+          usage.unreferenced += size;
+          report.removals.push(Removal {
+            path,
+            size,
+            kind: RemovalKind::NpmPackage,
+          });
+        }
+      }
+    }
+  }
+  Ok(())
+}
+
+fn read_dir_entries(dir: &Path) -> Result<Vec<PathBuf>, AnyError> {
+  match std::fs::read_dir(dir) {
+    Ok(entries) => {
+      let mut paths = entries
+        .map(|entry| entry.map(|e| e.path()))
+        .collect::<Result<Vec<_>, _>>()?;
+      paths.sort();
+      Ok(paths)
+    }
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+    Err(err) => Err(err.into()),
+  }
+}
+
+/// Gets the cache key of a file in the remote module cache, which is its
+/// file name without any of the extensions added for metadata or emits.
+fn cache_key_stem(file_name: &str) -> &str {
+  file_name.split('.').next().unwrap()
+}
+
+fn origin_from_host_dir_name(scheme: &str, host: &str) -> String {
+  // ports are stored as `_PORT<port>` since ":" isn't allowed on Windows
+  match host.rsplit_once("_PORT") {
+    Some((host, port)) => format!("{scheme}://{host}:{port}"),
+    None => format!("{scheme}://{host}"),
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_archive_manifest_serialization() {
+    let manifest = ArchiveManifest {
+      version: ARCHIVE_VERSION,
+      modules: vec![
+        ArchiveModule {
+          specifier: Url::parse("https://deno.land/x/mod.ts").unwrap(),
+          headers: HeadersMap::from([(
+            "content-type".to_string(),
+            "application/typescript".to_string(),
+          )]),
+          checksum: Some(checksum::gen(&[b"export {};"])),
+        },
+        ArchiveModule {
+          specifier: Url::parse("https://deno.land/x/redirect.ts").unwrap(),
+          headers: HeadersMap::from([(
+            "location".to_string(),
+            "https://deno.land/x/mod.ts".to_string(),
+          )]),
+          checksum: None,
+        },
+      ],
+      npm_packages: vec![ArchiveNpmPackage {
+        id: "chalk@5.0.1".to_string(),
+        files: vec![ArchiveFile {
+          path: "registry.npmjs.org/chalk/5.0.1/package.json".to_string(),
+          checksum: checksum::gen(&[b"{}"]),
+        }],
+      }],
+    };
+    let value = serde_json::to_value(&manifest).unwrap();
+    assert!(value["modules"][1].get("checksum").is_none());
+    let manifest: ArchiveManifest = serde_json::from_value(value).unwrap();
+    assert_eq!(manifest.version, ARCHIVE_VERSION);
+    assert_eq!(manifest.modules.len(), 2);
+    assert_eq!(manifest.npm_packages[0].files.len(), 1);
+    // archives without npm packages
+    let manifest: ArchiveManifest =
+      serde_json::from_str(r#"{ "version": 1, "modules": [] }"#).unwrap();
+    assert!(manifest.npm_packages.is_empty());
+    assert_eq!(
+      manifest.modules[0].checksum.as_deref(),
+      Some(checksum::gen(&[b"export {};"]).as_str())
+    );
+  }
+
+  #[test]
+  fn test_cache_key_stem() {
+    assert_eq!(cache_key_stem("abc123"), "abc123");
+    assert_eq!(cache_key_stem("abc123.metadata.json"), "abc123");
+    assert_eq!(cache_key_stem("abc123.js"), "abc123");
+    assert_eq!(cache_key_stem("abc123.meta"), "abc123");
+  }
+
+  #[test]
+  fn test_origin_from_host_dir_name() {
+    assert_eq!(
+      origin_from_host_dir_name("https", "deno.land"),
+      "https://deno.land"
+    );
+    assert_eq!(
+      origin_from_host_dir_name("http", "localhost_PORT4545"),
+      "http://localhost:4545"
+    );
+  }
+
+  #[test]
+  fn test_npm_package_id_name_and_version() {
+    assert_eq!(
+      npm_package_id_name_and_version("chalk@5.0.1"),
+      Some(("chalk", "5.0.1"))
+    );
+    assert_eq!(
+      npm_package_id_name_and_version("@types/node@18.0.0_peer@1.0.0"),
+      Some(("@types/node", "18.0.0"))
+    );
+    assert_eq!(npm_package_id_name_and_version("invalid"), None);
+  }
+
+  #[test]
+  fn test_referenced_remote_keys() {
+    let mut referenced = ReferencedEntries::default();
+    let specifier = Url::parse("https://deno.land/std/mod.ts").unwrap();
+    referenced.add_remote(&specifier);
+    referenced.add_remote(&Url::parse("file:///mod.ts").unwrap());
+    assert_eq!(referenced.remote_keys.len(), 1);
+    assert!(referenced
+      .remote_keys
+      .contains(&url_to_filename(&specifier).unwrap()));
+  }
+}
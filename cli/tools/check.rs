@@ -14,6 +14,7 @@ use deno_terminal::colors;
 use once_cell::sync::Lazy;
 use regex::Regex;
 
+use crate::args::CheckFlags;
 use crate::args::CliOptions;
 use crate::args::TsConfig;
 use crate::args::TsConfigType;
@@ -27,6 +28,8 @@ use crate::graph_util::ModuleGraphBuilder;
 use crate::npm::CliNpmResolver;
 use crate::tsc;
 use crate::tsc::Diagnostics;
+use crate::tsc::DiagnosticsSummary;
+use crate::tsc::LimitedDiagnostics;
 use crate::version;
 
 /// Options for performing a check of a module graph. Note that the decision to
@@ -240,6 +243,25 @@ impl TypeChecker {
   }
 }
 
+/// Applies the `--error-limit` and `--summary` flags of `deno check` to the
+/// diagnostics of a failed type check. Other errors are returned as-is.
+pub fn format_check_error(err: AnyError, check_flags: &CheckFlags) -> AnyError {
+  if !check_flags.summary && check_flags.error_limit.is_none() {
+    return err;
+  }
+  match err.downcast::<Diagnostics>() {
+    Ok(diagnostics) if check_flags.summary => {
+      DiagnosticsSummary(diagnostics).into()
+    }
+    Ok(diagnostics) => LimitedDiagnostics {
+      diagnostics,
+      limit: check_flags.error_limit.unwrap(),
+    }
+    .into(),
+    Err(err) => err,
+  }
+}
+
 enum CheckHashResult {
   Hash(u64),
   NoFiles,
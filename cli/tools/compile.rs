@@ -2,15 +2,28 @@
 
 use crate::args::CompileFlags;
 use crate::args::Flags;
+use crate::cache::ParsedSourceCache;
 use crate::factory::CliFactory;
+use crate::graph_util::ModuleGraphCreator;
 use crate::standalone::is_standalone_binary;
+use crate::standalone::resolve_include_dirs;
+use crate::standalone::run_sign_cmd;
+use crate::util::fs::normalize_path;
+use deno_ast::swc::ast;
+use deno_ast::swc::visit::noop_visit_type;
+use deno_ast::swc::visit::Visit;
+use deno_ast::swc::visit::VisitWith;
+use deno_ast::ModuleSpecifier;
+use deno_ast::ParsedSource;
 use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::generic_error;
 use deno_core::error::AnyError;
 use deno_core::resolve_url_or_path;
 use deno_graph::GraphKind;
+use deno_graph::ModuleGraph;
 use deno_terminal::colors;
+use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
 use std::sync::Arc;
@@ -27,14 +40,35 @@ pub async fn compile(
   let parsed_source_cache = factory.parsed_source_cache();
   let binary_writer = factory.create_compile_binary_writer().await?;
   let module_specifier = cli_options.resolve_main_module()?;
+  let include_dirs = resolve_include_dirs(&compile_flags, cli_options);
   let module_roots = {
     let mut vec = Vec::with_capacity(compile_flags.include.len() + 1);
     vec.push(module_specifier.clone());
     for side_module in &compile_flags.include {
-      vec.push(resolve_url_or_path(side_module, cli_options.initial_cwd())?);
+      let specifier =
+        resolve_url_or_path(side_module, cli_options.initial_cwd())?;
+      // directories are embedded as files rather than added to the graph
+      let is_dir = specifier
+        .to_file_path()
+        .map(|path| include_dirs.contains(&normalize_path(path)))
+        .unwrap_or(false);
+      if !is_dir {
+        vec.push(specifier);
+      }
     }
     vec
   };
+  let graph_kind = cli_options.type_check_mode().as_graph_kind();
+  let mut graph = module_graph_creator
+    .create_graph(graph_kind, module_roots)
+    .await?;
+  add_worker_entrypoints(
+    &mut graph,
+    graph_kind,
+    module_graph_creator,
+    parsed_source_cache,
+  )
+  .await?;
 
   // this is not supported, so show a warning about it, but don't error in order
   // to allow someone to still run `deno compile` when this is in a deno.json
@@ -56,16 +90,16 @@ pub async fn compile(
 
   let graph = Arc::try_unwrap(
     module_graph_creator
-      .create_graph_and_maybe_check(module_roots.clone())
+      .validate_and_maybe_check_graph(graph)
       .await?,
   )
   .unwrap();
   let graph = if cli_options.type_check_mode().is_true() {
-    // In this case, the previous graph creation did type checking, which will
-    // create a module graph with types information in it. We don't want to
-    // store that in the eszip so create a code only module graph from scratch.
+    // In this case, the graph was type checked, so it has types information
+    // in it. We don't want to store that in the eszip so create a code only
+    // module graph from scratch.
     module_graph_creator
-      .create_graph(GraphKind::CodeOnly, module_roots)
+      .create_graph(GraphKind::CodeOnly, graph.roots.clone())
       .await?
   } else {
     graph
@@ -117,12 +151,192 @@ pub async fn compile(
   {
     use std::os::unix::fs::PermissionsExt;
     let perms = std::fs::Permissions::from_mode(0o777);
-    std::fs::set_permissions(output_path, perms)?;
+    std::fs::set_permissions(&output_path, perms)?;
+  }
+
+  if let Some(sign_cmd) = &compile_flags.sign_cmd {
+    log::info!("{} {}", colors::green("Sign"), output_path.display());
+    let sign_result =
+      run_sign_cmd(sign_cmd, &output_path, cli_options.initial_cwd())
+        .await
+        .and_then(|()| {
+          if !is_standalone_binary(&output_path) {
+            bail!(concat!(
+              "The sign command modified the executable in a way that ",
+              "prevents it from being run. The signature must be appended ",
+              "to the end of the file."
+            ));
+          }
+          Ok(())
+        })
+        .with_context(|| format!("Signing {}", output_path.display()));
+    if let Err(err) = sign_result {
+      // don't leave an unsigned executable behind
+      let _ = std::fs::remove_file(output_path);
+      return Err(err);
+    }
   }
 
   Ok(())
 }
 
+/// Adds the worker entrypoints that are statically referenced by the modules
+/// in the graph to its roots, because they aren't imported by other modules
+/// and would otherwise be missing from the executable. Workers that can't be
+/// loaded are left out with a warning, since they might never be created.
+async fn add_worker_entrypoints(
+  graph: &mut ModuleGraph,
+  graph_kind: GraphKind,
+  module_graph_creator: &ModuleGraphCreator,
+  parsed_source_cache: &ParsedSourceCache,
+) -> Result<(), AnyError> {
+  let mut analyzed_modules = HashSet::new();
+  let mut found_entrypoints = HashSet::new();
+  loop {
+    let mut entrypoints = Vec::new();
+    for module in graph.modules() {
+      let deno_graph::Module::Js(module) = module else {
+        continue;
+      };
+      if !analyzed_modules.insert(module.specifier.clone()) {
+        continue;
+      }
+      let parsed_source =
+        parsed_source_cache.get_parsed_source_from_js_module(module)?;
+      for specifier in analyze_worker_entrypoints(&parsed_source) {
+        let Ok(specifier) = module.specifier.join(&specifier) else {
+          continue;
+        };
+        if graph.get(&specifier).is_none()
+          && found_entrypoints.insert(specifier.clone())
+        {
+          log::debug!("Found worker entrypoint {}", specifier);
+          entrypoints.push(specifier);
+        }
+      }
+    }
+    if entrypoints.is_empty() {
+      return Ok(());
+    }
+
+    // the new entrypoints may reference other workers, which are found in
+    // the next iteration
+    module_graph_creator
+      .add_graph_roots(graph, graph_kind, entrypoints.clone())
+      .await?;
+    for specifier in entrypoints {
+      let Err(err) = module_graph_creator
+        .graph_roots_valid(graph, std::slice::from_ref(&specifier))
+      else {
+        continue;
+      };
+      log::warn!(
+        "{} Worker {} won't be included in the executable: {:#}",
+        colors::yellow("Warning"),
+        specifier,
+        err
+      );
+      graph.roots.retain(|root| *root != specifier);
+    }
+  }
+}
+
+/// Gets the specifiers of workers created relative to the module, which is
+/// done with `new Worker(new URL("./worker.ts", import.meta.url))` or
+/// `new Worker(import.meta.resolve("./worker.ts"))`.
+fn analyze_worker_entrypoints(parsed_source: &ParsedSource) -> Vec<String> {
+  let mut collector = WorkerEntrypointCollector {
+    specifiers: Vec::new(),
+  };
+  parsed_source.module().visit_with(&mut collector);
+  collector.specifiers
+}
+
+struct WorkerEntrypointCollector {
+  specifiers: Vec<String>,
+}
+
+impl WorkerEntrypointCollector {
+  fn is_ident(expr: &ast::Expr, name: &str) -> bool {
+    matches!(expr, ast::Expr::Ident(ident) if &*ident.sym == name)
+  }
+
+  fn is_import_meta_prop(expr: &ast::Expr, name: &str) -> bool {
+    let ast::Expr::Member(member_expr) = expr else {
+      return false;
+    };
+    let is_import_meta = matches!(
+      &*member_expr.obj,
+      ast::Expr::MetaProp(ast::MetaPropExpr {
+        kind: ast::MetaPropKind::ImportMeta,
+        ..
+      })
+    );
+    let is_prop = match &member_expr.prop {
+      ast::MemberProp::Ident(ident) => &*ident.sym == name,
+      _ => false,
+    };
+    is_import_meta && is_prop
+  }
+
+  fn str_arg(args: &[ast::ExprOrSpread]) -> Option<String> {
+    match args.first() {
+      Some(ast::ExprOrSpread { spread: None, expr }) => match &**expr {
+        ast::Expr::Lit(ast::Lit::Str(str_lit)) => {
+          Some(str_lit.value.to_string())
+        }
+        _ => None,
+      },
+      _ => None,
+    }
+  }
+
+  fn worker_specifier(expr: &ast::Expr) -> Option<String> {
+    match expr {
+      // new URL("./worker.ts", import.meta.url)
+      ast::Expr::New(new_expr) => {
+        let is_url = Self::is_ident(&new_expr.callee, "URL");
+        let args = new_expr.args.as_deref()?;
+        let is_relative_to_module =
+          args.len() == 2 && Self::is_import_meta_prop(&args[1].expr, "url");
+        if is_url && is_relative_to_module {
+          Self::str_arg(args)
+        } else {
+          None
+        }
+      }
+      // import.meta.resolve("./worker.ts")
+      ast::Expr::Call(call_expr) => match &call_expr.callee {
+        ast::Callee::Expr(callee)
+          if Self::is_import_meta_prop(callee, "resolve") =>
+        {
+          Self::str_arg(&call_expr.args)
+        }
+        _ => None,
+      },
+      _ => None,
+    }
+  }
+}
+
+impl Visit for WorkerEntrypointCollector {
+  noop_visit_type!();
+
+  fn visit_new_expr(&mut self, new_expr: &ast::NewExpr) {
+    if Self::is_ident(&new_expr.callee, "Worker") {
+      if let Some(specifier) = new_expr
+        .args
+        .as_deref()
+        .and_then(|args| args.first())
+        .and_then(|arg| Self::worker_specifier(&arg.expr))
+      {
+        self.specifiers.push(specifier);
+      }
+    }
+    new_expr.visit_children_with(self);
+  }
+}
+
 /// This function writes out a final binary to specified path. If output path
 /// is not already standalone binary it will return error instead.
 fn validate_output_path(output_path: &Path) -> Result<(), AnyError> {
@@ -245,6 +459,11 @@ mod test {
         target: Some("x86_64-unknown-linux-gnu".to_string()),
         no_terminal: false,
         include: vec![],
+        icon: None,
+        product_name: None,
+        version: None,
+        sign_cmd: None,
+        no_web_apis: false,
       },
       &std::env::current_dir().unwrap(),
     )
@@ -267,6 +486,11 @@ mod test {
         target: Some("x86_64-pc-windows-msvc".to_string()),
         include: vec![],
         no_terminal: false,
+        icon: None,
+        product_name: None,
+        version: None,
+        sign_cmd: None,
+        no_web_apis: false,
       },
       &std::env::current_dir().unwrap(),
     )
@@ -301,4 +525,35 @@ mod test {
     run_test("C:\\my-exe.0.1.2", Some("windows"), "C:\\my-exe.0.1.2.exe");
     run_test("my-exe-0.1.2", Some("linux"), "my-exe-0.1.2");
   }
+
+  #[test]
+  fn test_analyze_worker_entrypoints() {
+    let parsed_source = deno_ast::parse_module(deno_ast::ParseParams {
+      specifier: ModuleSpecifier::parse("file:///mod.ts").unwrap(),
+      capture_tokens: false,
+      maybe_syntax: None,
+      media_type: deno_ast::MediaType::TypeScript,
+      scope_analysis: false,
+      text_info: deno_ast::SourceTextInfo::from_string(
+        r#"
+const a = new Worker(new URL("./a.ts", import.meta.url), { type: "module" });
+function create() {
+  return new Worker(import.meta.resolve("./b.ts"), { type: "module" });
+}
+// not relative to the module
+new Worker(new URL("./c.ts", location.href));
+new Worker("./d.ts");
+// not a literal
+new Worker(new URL(`./${name}.ts`, import.meta.url));
+new SharedWorker(new URL("./e.ts", import.meta.url));
+"#
+        .to_string(),
+      ),
+    })
+    .unwrap();
+    assert_eq!(
+      analyze_worker_entrypoints(&parsed_source),
+      vec!["./a.ts".to_string(), "./b.ts".to_string()]
+    );
+  }
 }
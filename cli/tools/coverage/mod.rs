@@ -2,6 +2,8 @@
 
 use crate::args::CliOptions;
 use crate::args::CoverageFlags;
+use crate::args::CoverageMergeFlags;
+use crate::args::CoverageType;
 use crate::args::FileFlags;
 use crate::args::Flags;
 use crate::cdp;
@@ -76,19 +78,7 @@ impl crate::worker::CoverageCollector for CoverageCollector {
         continue;
       }
 
-      let filename = format!("{}.json", Uuid::new_v4());
-      let filepath = self.dir.join(filename);
-
-      let mut out = BufWriter::new(File::create(&filepath)?);
-      let coverage = serde_json::to_string(&script_coverage)?;
-      let formatted_coverage =
-        format_json(&filepath, &coverage, &Default::default())
-          .ok()
-          .flatten()
-          .unwrap_or(coverage);
-
-      out.write_all(formatted_coverage.as_bytes())?;
-      out.flush()?;
+      write_script_coverage(&self.dir, &script_coverage)?;
     }
 
     self.disable_debugger().await?;
@@ -98,6 +88,26 @@ impl crate::worker::CoverageCollector for CoverageCollector {
   }
 }
 
+fn write_script_coverage(
+  dir: &Path,
+  script_coverage: &cdp::ScriptCoverage,
+) -> Result<(), AnyError> {
+  let filename = format!("{}.json", Uuid::new_v4());
+  let filepath = dir.join(filename);
+
+  let mut out = BufWriter::new(File::create(&filepath)?);
+  let coverage = serde_json::to_string(script_coverage)?;
+  let formatted_coverage =
+    format_json(&filepath, &coverage, &Default::default())
+      .ok()
+      .flatten()
+      .unwrap_or(coverage);
+
+  out.write_all(formatted_coverage.as_bytes())?;
+  out.flush()?;
+  Ok(())
+}
+
 impl CoverageCollector {
   pub fn new(dir: PathBuf, session: LocalInspectorSession) -> Self {
     Self { dir, session }
@@ -189,6 +199,58 @@ pub struct CoverageReport {
   output: Option<PathBuf>,
 }
 
+/// Line and branch hit counts of all the files in a coverage report.
+#[derive(Debug, Default, PartialEq)]
+struct CoverageTotals {
+  line_hit: usize,
+  line_miss: usize,
+  branch_hit: usize,
+  branch_miss: usize,
+}
+
+impl CoverageTotals {
+  fn add(&mut self, report: &CoverageReport) {
+    self.line_hit += report.found_lines.iter().filter(|(_, c)| *c > 0).count();
+    self.line_miss +=
+      report.found_lines.iter().filter(|(_, c)| *c == 0).count();
+    self.branch_hit += report.branches.iter().filter(|b| b.is_hit).count();
+    self.branch_miss += report.branches.iter().filter(|b| !b.is_hit).count();
+  }
+
+  /// Returns an error describing every `--fail-under-*` threshold that the
+  /// coverage doesn't meet.
+  fn check_thresholds(
+    &self,
+    fail_under_lines: Option<u8>,
+    fail_under_branches: Option<u8>,
+  ) -> Result<(), AnyError> {
+    let mut failures = Vec::new();
+    let mut check = |kind: &str, hit: usize, miss: usize, min: Option<u8>| {
+      let Some(min) = min else {
+        return;
+      };
+      let (_, percent, _) = util::calc_coverage_display_info(hit, miss);
+      if percent < min as f32 {
+        failures.push(format!(
+          "{kind} coverage of {percent:.1}% is below the threshold of {min}%"
+        ));
+      }
+    };
+    check("Line", self.line_hit, self.line_miss, fail_under_lines);
+    check(
+      "Branch",
+      self.branch_hit,
+      self.branch_miss,
+      fail_under_branches,
+    );
+    if failures.is_empty() {
+      Ok(())
+    } else {
+      Err(generic_error(failures.join("\n")))
+    }
+  }
+}
+
 fn generate_coverage_report(
   script_coverage: &cdp::ScriptCoverage,
   script_source: String,
@@ -457,6 +519,58 @@ fn filter_coverages(
     .collect::<Vec<cdp::ScriptCoverage>>()
 }
 
+/// Combines the coverage profiles of several runs into a single profile,
+/// summing the counts of scripts that were covered by more than one run.
+pub fn merge_coverage_profiles(
+  flags: Flags,
+  merge_flags: CoverageMergeFlags,
+) -> Result<(), AnyError> {
+  let factory = CliFactory::from_flags(flags)?;
+  let cli_options = factory.cli_options();
+
+  let output_dir = cli_options.initial_cwd().join(&merge_flags.output);
+  // Merging into a directory that already holds a profile would count the
+  // existing scripts twice in later reports.
+  if fs::read_dir(&output_dir).is_ok_and(|mut entries| entries.next().is_some())
+  {
+    return Err(anyhow!(
+      "Output directory {} is not empty",
+      output_dir.display()
+    ));
+  }
+
+  let script_coverages = collect_coverages(
+    cli_options,
+    merge_flags.files,
+    cli_options.initial_cwd(),
+  )?;
+  if script_coverages.is_empty() {
+    return Err(generic_error("No coverage files found"));
+  }
+  let profile_count = script_coverages.len();
+
+  let proc_coverages: Vec<_> = script_coverages
+    .into_iter()
+    .map(|cov| ProcessCoverage { result: vec![cov] })
+    .collect();
+  let script_coverages = merge::merge_processes(proc_coverages)
+    .map(|c| c.result)
+    .unwrap_or_default();
+
+  fs::create_dir_all(&output_dir)?;
+  for script_coverage in &script_coverages {
+    write_script_coverage(&output_dir, script_coverage)?;
+  }
+
+  log::info!(
+    "Merged {} coverage files into {} scripts at {}",
+    profile_count,
+    script_coverages.len(),
+    output_dir.display()
+  );
+  Ok(())
+}
+
 pub async fn cover_files(
   flags: Flags,
   coverage_flags: CoverageFlags,
@@ -507,9 +621,16 @@ pub async fn cover_files(
     vec![]
   };
 
-  let mut reporter = reporter::create(coverage_flags.r#type);
+  let mut output = coverage_flags.output;
+  let html_dir = match coverage_flags.r#type {
+    CoverageType::Html => {
+      output.take().map(|dir| cli_options.initial_cwd().join(dir))
+    }
+    _ => None,
+  };
+  let mut reporter = reporter::create(coverage_flags.r#type, html_dir);
 
-  let out_mode = match coverage_flags.output {
+  let out_mode = match output {
     Some(ref path) => match File::create(path) {
       Ok(_) => Some(PathBuf::from(path)),
       Err(e) => {
@@ -519,6 +640,7 @@ pub async fn cover_files(
     None => None,
   };
 
+  let mut totals = CoverageTotals::default();
   for script_coverage in script_coverages {
     let module_specifier = deno_core::resolve_url_or_path(
       &script_coverage.url,
@@ -583,11 +705,49 @@ pub async fn cover_files(
     );
 
     if !coverage_report.found_lines.is_empty() {
+      totals.add(&coverage_report);
       reporter.report(&coverage_report, &original_source)?;
     }
   }
 
   reporter.done(&coverage_root);
 
-  Ok(())
+  totals.check_thresholds(
+    coverage_flags.fail_under_lines,
+    coverage_flags.fail_under_branches,
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_check_thresholds() {
+    let totals = CoverageTotals {
+      line_hit: 3,
+      line_miss: 1,
+      branch_hit: 1,
+      branch_miss: 1,
+    };
+    assert!(totals.check_thresholds(None, None).is_ok());
+    assert!(totals.check_thresholds(Some(75), Some(50)).is_ok());
+    assert_eq!(
+      totals
+        .check_thresholds(Some(80), None)
+        .unwrap_err()
+        .to_string(),
+      "Line coverage of 75.0% is below the threshold of 80%"
+    );
+    assert_eq!(
+      totals
+        .check_thresholds(Some(80), Some(60))
+        .unwrap_err()
+        .to_string(),
+      concat!(
+        "Line coverage of 75.0% is below the threshold of 80%\n",
+        "Branch coverage of 50.0% is below the threshold of 60%",
+      )
+    );
+  }
 }
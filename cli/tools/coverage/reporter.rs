@@ -28,12 +28,15 @@ pub struct CoverageStats<'a> {
 
 type CoverageSummary<'a> = HashMap<String, CoverageStats<'a>>;
 
-pub fn create(kind: CoverageType) -> Box<dyn CoverageReporter + Send> {
+pub fn create(
+  kind: CoverageType,
+  html_dir: Option<PathBuf>,
+) -> Box<dyn CoverageReporter + Send> {
   match kind {
     CoverageType::Summary => Box::new(SummaryCoverageReporter::new()),
     CoverageType::Lcov => Box::new(LcovCoverageReporter::new()),
     CoverageType::Detailed => Box::new(DetailedCoverageReporter::new()),
-    CoverageType::Html => Box::new(HtmlCoverageReporter::new()),
+    CoverageType::Html => Box::new(HtmlCoverageReporter::new(html_dir)),
   }
 }
 
@@ -182,12 +185,26 @@ impl CoverageReporter for SummaryCoverageReporter {
     let summary = self.collect_summary(&self.file_reports);
     let root_stats = summary.get("").unwrap();
 
+    // Group the files by directory, each directory being followed by the
+    // files and directories it contains.
     let mut entries = summary
       .iter()
-      .filter(|(_, stats)| stats.file_text.is_some())
+      .filter(|(node, _)| !node.is_empty())
+      .map(|(node, stats)| {
+        let label = if stats.file_text.is_some() {
+          node.to_string()
+        } else {
+          format!("{node}/")
+        };
+        (Path::new(node), label, stats)
+      })
       .collect::<Vec<_>>();
-    entries.sort_by_key(|(node, _)| node.to_owned());
-    let node_max = entries.iter().map(|(node, _)| node.len()).max().unwrap();
+    entries.sort_by(|(a, _, _), (b, _, _)| a.cmp(b));
+    let node_max = entries
+      .iter()
+      .map(|(_, label, _)| label.len())
+      .max()
+      .unwrap();
 
     let header =
       format!("{node:node_max$}  | Branch % | Line % |", node = "File");
@@ -195,8 +212,8 @@ impl CoverageReporter for SummaryCoverageReporter {
     println!("{}", separator);
     println!("{}", header);
     println!("{}", separator);
-    entries.iter().for_each(|(node, stats)| {
-      self.print_coverage_line(node, node_max, stats);
+    entries.iter().for_each(|(_, label, stats)| {
+      self.print_coverage_line(label, node_max, stats);
     });
     println!("{}", separator);
     self.print_coverage_line("All files", node_max, root_stats);
@@ -377,6 +394,8 @@ impl CoverageReporter for DetailedCoverageReporter {
 
 struct HtmlCoverageReporter {
   file_reports: Vec<(CoverageReport, String)>,
+  /// Where to write the report, defaults to `html/` in the coverage root.
+  output_dir: Option<PathBuf>,
 }
 
 impl CoverageReporter for HtmlCoverageReporter {
@@ -392,10 +411,14 @@ impl CoverageReporter for HtmlCoverageReporter {
   fn done(&mut self, coverage_root: &Path) {
     let summary = self.collect_summary(&self.file_reports);
     let now = crate::util::time::utc_now().to_rfc2822();
+    let output_dir = self
+      .output_dir
+      .clone()
+      .unwrap_or_else(|| coverage_root.join("html"));
 
     for (node, stats) in &summary {
       let report_path =
-        self.get_report_path(coverage_root, node, stats.file_text.is_none());
+        self.get_report_path(&output_dir, node, stats.file_text.is_none());
       let main_content = if let Some(file_text) = &stats.file_text {
         self.create_html_code_table(file_text, stats.report.unwrap())
       } else {
@@ -408,11 +431,7 @@ impl CoverageReporter for HtmlCoverageReporter {
     }
 
     let root_report = Url::from_file_path(
-      coverage_root
-        .join("html")
-        .join("index.html")
-        .canonicalize()
-        .unwrap(),
+      output_dir.join("index.html").canonicalize().unwrap(),
     )
     .unwrap();
 
@@ -421,29 +440,27 @@ impl CoverageReporter for HtmlCoverageReporter {
 }
 
 impl HtmlCoverageReporter {
-  pub fn new() -> HtmlCoverageReporter {
+  pub fn new(output_dir: Option<PathBuf>) -> HtmlCoverageReporter {
     HtmlCoverageReporter {
       file_reports: Vec::new(),
+      output_dir,
     }
   }
 
   /// Gets the report path for a single file
   pub fn get_report_path(
     &self,
-    coverage_root: &Path,
+    output_dir: &Path,
     node: &str,
     is_dir: bool,
   ) -> PathBuf {
     if is_dir {
       // e.g. /path/to/coverage/html/src/index.html
-      coverage_root.join("html").join(node).join("index.html")
+      output_dir.join(node).join("index.html")
     } else {
       // e.g. /path/to/coverage/html/src/main.ts.html
-      Path::new(&format!(
-        "{}.html",
-        coverage_root.join("html").join(node).to_str().unwrap()
-      ))
-      .to_path_buf()
+      Path::new(&format!("{}.html", output_dir.join(node).to_str().unwrap()))
+        .to_path_buf()
     }
   }
 
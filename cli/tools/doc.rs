@@ -17,6 +17,7 @@ use deno_config::glob::PathOrPatternSet;
 use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
+use deno_core::serde_json;
 use deno_doc as doc;
 use deno_doc::html::UrlResolveKind;
 use deno_graph::source::NullFileSystem;
@@ -27,7 +28,13 @@ use deno_graph::ModuleSpecifier;
 use doc::html::ShortPath;
 use doc::DocDiagnostic;
 use indexmap::IndexMap;
+use lazy_regex::lazy_regex;
+use once_cell::sync::Lazy;
+use regex::Regex;
+use serde::Deserialize;
+use serde::Serialize;
 use std::collections::BTreeMap;
+use std::collections::HashSet;
 use std::rc::Rc;
 
 async fn generate_doc_nodes_for_builtin_types(
@@ -90,6 +97,27 @@ pub async fn doc(flags: Flags, doc_flags: DocFlags) -> Result<(), AnyError> {
   let capturing_parser = parsed_source_cache.as_capturing_parser();
   let analyzer = module_info_cache.as_module_analyzer(&capturing_parser);
 
+  // the builtin types are used to resolve links when linting and for the
+  // `Deno` namespace of the HTML output, so only generate them once
+  let builtin_doc_nodes = if doc_flags.source_files
+    != DocSourceFileFlag::Builtin
+    && (doc_flags.lint || doc_flags.html.is_some())
+  {
+    let (_, nodes) = generate_doc_nodes_for_builtin_types(
+      doc_flags.clone(),
+      &capturing_parser,
+      &analyzer,
+    )
+    .await?
+    .into_iter()
+    .next()
+    .unwrap();
+    nodes
+  } else {
+    Vec::new()
+  };
+  let mut json_diagnostics = None;
+
   let doc_nodes_by_url = match doc_flags.source_files {
     DocSourceFileFlag::Builtin => {
       generate_doc_nodes_for_builtin_types(
@@ -144,7 +172,22 @@ pub async fn doc(flags: Flags, doc_flags: DocFlags) -> Result<(), AnyError> {
 
       if doc_flags.lint {
         let diagnostics = doc_parser.take_diagnostics();
-        check_diagnostics(&diagnostics)?;
+        let builtin_names = builtin_doc_nodes
+          .iter()
+          .map(|node| node.name.clone())
+          .collect::<HashSet<_>>();
+        let symbols = DocSymbols::new(&doc_nodes_by_url);
+        let unresolved_links = symbols.unresolved_links(&builtin_names);
+        if doc_flags.json_version.is_some() {
+          // reported together with the documentation
+          json_diagnostics = Some(to_json_diagnostics(
+            &diagnostics,
+            &unresolved_links,
+            &symbols,
+          ));
+        } else {
+          check_diagnostics(&diagnostics, &unresolved_links)?;
+        }
       }
 
       doc_nodes_by_url
@@ -153,14 +196,6 @@ pub async fn doc(flags: Flags, doc_flags: DocFlags) -> Result<(), AnyError> {
 
   if let Some(html_options) = &doc_flags.html {
     let deno_ns = if doc_flags.source_files != DocSourceFileFlag::Builtin {
-      let deno_ns = generate_doc_nodes_for_builtin_types(
-        doc_flags.clone(),
-        &capturing_parser,
-        &analyzer,
-      )
-      .await?;
-      let (_, deno_ns) = deno_ns.into_iter().next().unwrap();
-
       let short_path = Rc::new(ShortPath::new(
         ModuleSpecifier::parse("file:///lib.deno.d.ts").unwrap(),
         None,
@@ -169,7 +204,7 @@ pub async fn doc(flags: Flags, doc_flags: DocFlags) -> Result<(), AnyError> {
       ));
 
       deno_doc::html::compute_namespaced_symbols(
-        &deno_ns
+        &builtin_doc_nodes
           .into_iter()
           .map(|node| deno_doc::html::DocNodeWithContext {
             origin: short_path.clone(),
@@ -192,7 +227,23 @@ pub async fn doc(flags: Flags, doc_flags: DocFlags) -> Result<(), AnyError> {
       doc_nodes_by_url.into_values().flatten().collect::<Vec<_>>();
 
     if doc_flags.json {
-      write_json_to_stdout(&doc_nodes)
+      let doc_nodes = match &doc_flags.filter {
+        Some(filter) => filter_doc_nodes(doc_nodes, filter)?,
+        None => doc_nodes,
+      };
+      match doc_flags.json_version {
+        Some(version) => {
+          let lint_error_count = json_diagnostics.as_ref().map_or(0, Vec::len);
+          write_json_to_stdout(&JsonDocOutput {
+            version,
+            nodes: doc_nodes,
+            diagnostics: json_diagnostics,
+          })?;
+          check_lint_error_count(lint_error_count)
+        }
+        // the array of nodes that `deno doc --json` has always printed
+        None => write_json_to_stdout(&doc_nodes),
+      }
     } else if doc_flags.lint {
       // don't output docs if running with only the --lint flag
       log::info!(
@@ -256,6 +307,27 @@ impl deno_doc::html::HrefResolver for DocResolver {
   }
 }
 
+const SEARCH_INDEX_JS_FILE_NAME: &str = "search_index.js";
+const SEARCH_INDEX_JSON_FILE_NAME: &str = "search_index.json";
+
+/// Extracts the JSON of the search index from the script that assigns it to
+/// `window.DENO_DOC_SEARCH_INDEX` inside of an IIFE, like
+/// `(function () { window.DENO_DOC_SEARCH_INDEX = {...}; })()`.
+fn search_index_json_from_script(script: &str) -> Option<String> {
+  let (_, assignment) = script.split_once("DENO_DOC_SEARCH_INDEX")?;
+  let start = assignment.find('{')?;
+  // the end of the assignment rather than the closing brace of the IIFE
+  let end = assignment.rfind("};")?;
+  let json = assignment.get(start..=end)?;
+  match serde_json::from_str::<serde_json::Value>(json) {
+    Ok(_) => Some(json.to_string()),
+    Err(err) => {
+      log::debug!("Failed to parse the search index: {:#}", err);
+      None
+    }
+  }
+}
+
 fn generate_docs_directory(
   doc_nodes_by_url: IndexMap<ModuleSpecifier, Vec<doc::DocNode>>,
   html_options: &DocHtmlFlag,
@@ -273,9 +345,17 @@ fn generate_docs_directory(
     composable_output: false,
   };
 
-  let files = deno_doc::html::generate(options, doc_nodes_by_url)
+  let mut files = deno_doc::html::generate(options, doc_nodes_by_url)
     .context("Failed to generate HTML documentation")?;
 
+  // the search index is also provided as JSON for tools that index the site
+  if let Some(search_index) = files
+    .get(SEARCH_INDEX_JS_FILE_NAME)
+    .and_then(|script| search_index_json_from_script(script))
+  {
+    files.insert(SEARCH_INDEX_JSON_FILE_NAME.to_string(), search_index);
+  }
+
   let path = &output_dir_resolved;
   let _ = std::fs::remove_dir_all(path);
   std::fs::create_dir(path)
@@ -303,16 +383,38 @@ fn generate_docs_directory(
   Ok(())
 }
 
+/// The output of `deno doc --json --json-version=<version>`, which is
+/// described by `cli/schemas/doc-output.v<version>.json`. Breaking changes
+/// need a new version.
+// WARNING: Ensure doesn't change because it's used in the JSON output
+#[derive(Serialize)]
+struct JsonDocOutput {
+  version: u32,
+  nodes: Vec<doc::DocNode>,
+  /// The documentation diagnostics when running with `--lint`.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  diagnostics: Option<Vec<JsonDocLintDiagnostic>>,
+}
+
+fn filter_doc_nodes(
+  doc_nodes: Vec<doc::DocNode>,
+  filter: &str,
+) -> Result<Vec<doc::DocNode>, AnyError> {
+  let nodes =
+    doc::find_nodes_by_name_recursively(doc_nodes, filter.to_string());
+  if nodes.is_empty() {
+    bail!("Node {} was not found!", filter);
+  }
+  Ok(nodes)
+}
+
 fn print_docs_to_stdout(
   doc_flags: DocFlags,
   mut doc_nodes: Vec<deno_doc::DocNode>,
 ) -> Result<(), AnyError> {
   doc_nodes.retain(|doc_node| doc_node.kind != doc::DocNodeKind::Import);
   let details = if let Some(filter) = doc_flags.filter {
-    let nodes = doc::find_nodes_by_name_recursively(doc_nodes, filter.clone());
-    if nodes.is_empty() {
-      bail!("Node {} was not found!", filter);
-    }
+    let nodes = filter_doc_nodes(doc_nodes, &filter)?;
     format!(
       "{}",
       doc::DocPrinter::new(&nodes, colors::use_color(), doc_flags.private)
@@ -327,11 +429,285 @@ fn print_docs_to_stdout(
   write_to_stdout_ignore_sigpipe(details.as_bytes()).map_err(AnyError::from)
 }
 
-fn check_diagnostics(diagnostics: &[DocDiagnostic]) -> Result<(), AnyError> {
-  if diagnostics.is_empty() {
-    return Ok(());
+/// Global names of the JavaScript language that `{@link}` tags can refer to.
+const ECMASCRIPT_GLOBALS: &[&str] = &[
+  "AggregateError",
+  "Array",
+  "ArrayBuffer",
+  "AsyncGenerator",
+  "AsyncIterable",
+  "AsyncIterator",
+  "Atomics",
+  "BigInt",
+  "BigInt64Array",
+  "BigUint64Array",
+  "Boolean",
+  "DataView",
+  "Date",
+  "Error",
+  "EvalError",
+  "FinalizationRegistry",
+  "Float32Array",
+  "Float64Array",
+  "Function",
+  "Generator",
+  "Infinity",
+  "Int16Array",
+  "Int32Array",
+  "Int8Array",
+  "Intl",
+  "Iterable",
+  "Iterator",
+  "JSON",
+  "Map",
+  "Math",
+  "NaN",
+  "Number",
+  "Object",
+  "Promise",
+  "Proxy",
+  "RangeError",
+  "ReferenceError",
+  "Reflect",
+  "RegExp",
+  "Set",
+  "SharedArrayBuffer",
+  "String",
+  "Symbol",
+  "SyntaxError",
+  "TypeError",
+  "URIError",
+  "Uint16Array",
+  "Uint32Array",
+  "Uint8Array",
+  "Uint8ClampedArray",
+  "WeakMap",
+  "WeakRef",
+  "WeakSet",
+  "globalThis",
+];
+
+static JSDOC_LINK_RE: Lazy<Regex> =
+  lazy_regex!(r"\{@link(?:code|plain)?\s+([^\s|}]+)");
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+struct DocLintLocation {
+  filename: String,
+  line: usize,
+  col: usize,
+}
+
+/// A documented symbol, including the members of classes, interfaces and
+/// namespaces, e.g. `MyClass.prop`.
+#[derive(Debug)]
+struct DocSymbol {
+  name: String,
+  location: DocLintLocation,
+  /// The targets of the `{@link}` tags in its JSDoc.
+  links: Vec<String>,
+}
+
+struct DocSymbols {
+  /// The names of the top level symbols of all modules.
+  names: HashSet<String>,
+  symbols: Vec<DocSymbol>,
+}
+
+impl DocSymbols {
+  fn new(
+    doc_nodes_by_url: &IndexMap<ModuleSpecifier, Vec<doc::DocNode>>,
+  ) -> Self {
+    let mut names = HashSet::new();
+    let mut symbols = Vec::new();
+    for node in doc_nodes_by_url.values().flatten() {
+      names.insert(node.name.clone());
+      if node.kind == doc::DocNodeKind::Import {
+        continue;
+      }
+      if let Ok(value) = serde_json::to_value(node) {
+        collect_doc_symbols(&value, &[], &mut symbols);
+      }
+    }
+    Self { names, symbols }
   }
 
+  fn find_by_location(&self, location: &doc::Location) -> Option<&DocSymbol> {
+    self.symbols.iter().find(|symbol| {
+      symbol.location.filename == location.filename
+        && symbol.location.line == location.line
+        && symbol.location.col == location.col
+    })
+  }
+
+  fn unresolved_links(
+    &self,
+    builtin_names: &HashSet<String>,
+  ) -> Vec<UnresolvedLink> {
+    let is_resolvable = |target: &str| {
+      if target.contains("://") {
+        return true;
+      }
+      let name = target.split(['.', '#']).next().unwrap_or(target);
+      self.names.contains(name)
+        || builtin_names.contains(name)
+        || ECMASCRIPT_GLOBALS.contains(&name)
+    };
+    self
+      .symbols
+      .iter()
+      .flat_map(|symbol| {
+        symbol
+          .links
+          .iter()
+          .filter(|target| !is_resolvable(target))
+          .map(|target| UnresolvedLink {
+            symbol: symbol.name.clone(),
+            location: symbol.location.clone(),
+            target: target.clone(),
+          })
+      })
+      .collect()
+  }
+}
+
+/// Collects the symbols in the JSON of a doc node, which are the objects
+/// that have a name and a location.
+fn collect_doc_symbols(
+  value: &serde_json::Value,
+  parent_path: &[String],
+  symbols: &mut Vec<DocSymbol>,
+) {
+  match value {
+    serde_json::Value::Object(map) => {
+      let mut path = parent_path.to_vec();
+      let name = map.get("name").and_then(|name| name.as_str());
+      let location = map
+        .get("location")
+        .and_then(|location| serde_json::from_value(location.clone()).ok());
+      if let (Some(name), Some(location)) = (name, location) {
+        path.push(name.to_string());
+        let mut links = Vec::new();
+        if let Some(js_doc) = map.get("jsDoc") {
+          collect_jsdoc_links(js_doc, &mut links);
+        }
+        symbols.push(DocSymbol {
+          name: path.join("."),
+          location,
+          links,
+        });
+      }
+      for (key, value) in map {
+        if key != "jsDoc" && key != "location" {
+          collect_doc_symbols(value, &path, symbols);
+        }
+      }
+    }
+    serde_json::Value::Array(items) => {
+      for item in items {
+        collect_doc_symbols(item, parent_path, symbols);
+      }
+    }
+    _ => {}
+  }
+}
+
+fn collect_jsdoc_links(value: &serde_json::Value, links: &mut Vec<String>) {
+  match value {
+    serde_json::Value::String(text) => {
+      for captures in JSDOC_LINK_RE.captures_iter(text) {
+        links.push(captures[1].to_string());
+      }
+    }
+    serde_json::Value::Object(map) => {
+      for value in map.values() {
+        collect_jsdoc_links(value, links);
+      }
+    }
+    serde_json::Value::Array(items) => {
+      for item in items {
+        collect_jsdoc_links(item, links);
+      }
+    }
+    _ => {}
+  }
+}
+
+/// A `{@link}` tag whose target isn't a symbol of the documented modules or
+/// a global.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct UnresolvedLink {
+  symbol: String,
+  location: DocLintLocation,
+  target: String,
+}
+
+const UNRESOLVED_LINK_CODE: &str = "unresolved-jsdoc-link";
+
+impl UnresolvedLink {
+  fn message(&self) -> String {
+    format!(
+      "JSDoc of '{}' links to '{}', which can't be resolved",
+      self.symbol, self.target
+    )
+  }
+
+  fn hint(&self) -> &'static str {
+    "fix the name of the linked symbol or make it part of the documented modules"
+  }
+}
+
+// WARNING: Ensure doesn't change because it's used in the JSON output
+#[derive(Serialize)]
+struct JsonDocLintDiagnostic {
+  code: String,
+  message: String,
+  symbol: Option<String>,
+  location: DocLintLocation,
+  hint: Option<String>,
+}
+
+fn to_json_diagnostics(
+  diagnostics: &[DocDiagnostic],
+  unresolved_links: &[UnresolvedLink],
+  symbols: &DocSymbols,
+) -> Vec<JsonDocLintDiagnostic> {
+  let mut json_diagnostics = diagnostics
+    .iter()
+    .map(|diagnostic| JsonDocLintDiagnostic {
+      code: diagnostic.code().to_string(),
+      message: diagnostic.message().to_string(),
+      symbol: symbols
+        .find_by_location(&diagnostic.location)
+        .map(|symbol| symbol.name.clone()),
+      location: DocLintLocation {
+        filename: diagnostic.location.filename.clone(),
+        line: diagnostic.location.line,
+        col: diagnostic.location.col,
+      },
+      hint: diagnostic.hint().map(|hint| hint.to_string()),
+    })
+    .chain(unresolved_links.iter().map(|link| JsonDocLintDiagnostic {
+      code: UNRESOLVED_LINK_CODE.to_string(),
+      message: link.message(),
+      symbol: Some(link.symbol.clone()),
+      location: link.location.clone(),
+      hint: Some(link.hint().to_string()),
+    }))
+    .collect::<Vec<_>>();
+  json_diagnostics.sort_by(|a, b| {
+    (&a.location.filename, a.location.line, a.location.col).cmp(&(
+      &b.location.filename,
+      b.location.line,
+      b.location.col,
+    ))
+  });
+  json_diagnostics
+}
+
+fn check_diagnostics(
+  diagnostics: &[DocDiagnostic],
+  unresolved_links: &[UnresolvedLink],
+) -> Result<(), AnyError> {
   // group by location then by line (sorted) then column (sorted)
   let mut diagnostic_groups = IndexMap::new();
   for diagnostic in diagnostics {
@@ -342,21 +718,129 @@ fn check_diagnostics(diagnostics: &[DocDiagnostic]) -> Result<(), AnyError> {
       .or_insert_with(BTreeMap::new)
       .entry(diagnostic.location.col)
       .or_insert_with(Vec::new)
-      .push(diagnostic);
+      .push(diagnostic.display().to_string());
+  }
+  for link in unresolved_links {
+    diagnostic_groups
+      .entry(link.location.filename.clone())
+      .or_insert_with(BTreeMap::new)
+      .entry(link.location.line)
+      .or_insert_with(BTreeMap::new)
+      .entry(link.location.col)
+      .or_insert_with(Vec::new)
+      .push(format!(
+        "{}: {}\n --> {}:{}:{}\n  = hint: {}\n",
+        colors::red_bold(format!("error[{}]", UNRESOLVED_LINK_CODE)),
+        link.message(),
+        link.location.filename,
+        link.location.line,
+        link.location.col + 1,
+        link.hint(),
+      ));
   }
 
   for (_, diagnostics_by_lc) in diagnostic_groups {
     for (_, diagnostics_by_col) in diagnostics_by_lc {
       for (_, diagnostics) in diagnostics_by_col {
         for diagnostic in diagnostics {
-          log::error!("{}", diagnostic.display());
+          log::error!("{}", diagnostic);
         }
       }
     }
   }
+  check_lint_error_count(diagnostics.len() + unresolved_links.len())
+}
+
+fn check_lint_error_count(count: usize) -> Result<(), AnyError> {
+  if count == 0 {
+    return Ok(());
+  }
   bail!(
     "Found {} documentation lint error{}.",
-    colors::bold(diagnostics.len().to_string()),
-    if diagnostics.len() == 1 { "" } else { "s" }
+    colors::bold(count.to_string()),
+    if count == 1 { "" } else { "s" }
   );
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn extracts_search_index_json() {
+    let script = r#"(function () {
+  window.DENO_DOC_SEARCH_INDEX = {"nodes":[{"kind":["class"],"name":"MyClass","file":"."}]};
+})()"#;
+    assert_eq!(
+      search_index_json_from_script(script).unwrap(),
+      r#"{"nodes":[{"kind":["class"],"name":"MyClass","file":"."}]}"#
+    );
+    // braces in the strings of the index
+    let script = r#"(function () {
+  window.DENO_DOC_SEARCH_INDEX = {"nodes":[{"name":"a};"}]};
+})()"#;
+    assert_eq!(
+      search_index_json_from_script(script).unwrap(),
+      r#"{"nodes":[{"name":"a};"}]}"#
+    );
+    assert_eq!(search_index_json_from_script("window.foo = {};"), None);
+    assert_eq!(
+      search_index_json_from_script("window.DENO_DOC_SEARCH_INDEX = {;"),
+      None
+    );
+  }
+
+  #[test]
+  fn finds_unresolved_jsdoc_links() {
+    let node = serde_json::json!({
+      "kind": "class",
+      "name": "MyClass",
+      "location": { "filename": "file:///mod.ts", "line": 2, "col": 0, "byteIndex": 20 },
+      "jsDoc": { "doc": "Wraps a {@link Foo} in a {@linkcode Promise}." },
+      "classDef": {
+        "methods": [{
+          "name": "run",
+          "location": { "filename": "file:///mod.ts", "line": 4, "col": 2, "byteIndex": 50 },
+          "jsDoc": {
+            "doc": "Runs it.",
+            "tags": [{ "kind": "see", "doc": "{@link MyClass.stop | stop} and {@link Bar}" }]
+          }
+        }]
+      }
+    });
+    let mut symbols = Vec::new();
+    collect_doc_symbols(&node, &[], &mut symbols);
+    let symbols = DocSymbols {
+      names: HashSet::from(["MyClass".to_string()]),
+      symbols,
+    };
+    assert_eq!(
+      symbols
+        .symbols
+        .iter()
+        .map(|s| (s.name.as_str(), s.links.clone()))
+        .collect::<Vec<_>>(),
+      vec![
+        ("MyClass", vec!["Foo".to_string(), "Promise".to_string()]),
+        (
+          "MyClass.run",
+          vec!["MyClass.stop".to_string(), "Bar".to_string()]
+        ),
+      ]
+    );
+    let unresolved =
+      symbols.unresolved_links(&HashSet::from(["Bar".to_string()]));
+    assert_eq!(
+      unresolved,
+      vec![UnresolvedLink {
+        symbol: "MyClass".to_string(),
+        location: DocLintLocation {
+          filename: "file:///mod.ts".to_string(),
+          line: 2,
+          col: 0,
+        },
+        target: "Foo".to_string(),
+      }]
+    );
+  }
+}
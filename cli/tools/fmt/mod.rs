@@ -7,15 +7,16 @@
 //! the future it can be easily extended to provide
 //! the same functions as ops available in JS runtime.
 
+use crate::args::write_lockfile;
 use crate::args::CliOptions;
 use crate::args::Flags;
 use crate::args::FmtFlags;
 use crate::args::FmtOptions;
 use crate::args::FmtOptionsConfig;
+use crate::args::MarkdownFmtOptions;
 use crate::args::ProseWrap;
-use crate::colors;
 use crate::factory::CliFactory;
-use crate::util::diff::diff;
+use crate::util::diff::unified_diff;
 use crate::util::file_watcher;
 use crate::util::fs::canonicalize_path;
 use crate::util::fs::FileCollector;
@@ -30,6 +31,7 @@ use deno_core::error::AnyError;
 use deno_core::futures;
 use deno_core::parking_lot::Mutex;
 use deno_core::unsync::spawn_blocking;
+use deno_core::ModuleSpecifier;
 use log::debug;
 use log::info;
 use log::warn;
@@ -46,19 +48,39 @@ use std::sync::Arc;
 
 use crate::cache::IncrementalCache;
 
+mod plugins;
+
+pub use plugins::FmtPlugins;
+
 /// Format JavaScript/TypeScript files.
 pub async fn format(flags: Flags, fmt_flags: FmtFlags) -> Result<(), AnyError> {
   if fmt_flags.is_stdin() {
-    let cli_options = CliOptions::from_flags(flags)?;
+    let factory = CliFactory::from_flags(flags)?;
+    let cli_options = factory.cli_options();
+    let stdin_filepath = fmt_flags
+      .stdin_filepath
+      .as_ref()
+      .map(|path| cli_options.initial_cwd().join(path));
     let fmt_options = cli_options.resolve_fmt_options(fmt_flags)?;
-    return format_stdin(
-      fmt_options,
-      cli_options
-        .ext_flag()
-        .as_ref()
-        .map(|s| s.as_str())
-        .unwrap_or("ts"),
-    );
+    let plugins = load_plugins(&factory, &fmt_options).await?;
+    let file_path = match stdin_filepath {
+      Some(file_path) => {
+        if !is_fmt_path_included(&fmt_options, &file_path) {
+          // echo the text back so editors can always use the output
+          return copy_stdin_to_stdout(fmt_options.check);
+        }
+        file_path
+      }
+      None => {
+        let ext = cli_options
+          .ext_flag()
+          .as_ref()
+          .map(|s| s.as_str())
+          .unwrap_or("ts");
+        PathBuf::from(format!("_stdin.{ext}"))
+      }
+    };
+    return format_stdin(&fmt_options, &plugins, &file_path);
   }
 
   if let Some(watch_flags) = &fmt_flags.watch {
@@ -71,14 +93,16 @@ pub async fn format(flags: Flags, fmt_flags: FmtFlags) -> Result<(), AnyError> {
           let factory = CliFactory::from_flags(flags)?;
           let cli_options = factory.cli_options();
           let fmt_options = cli_options.resolve_fmt_options(fmt_flags)?;
-          let files = collect_fmt_files(cli_options, fmt_options.files.clone())
-            .and_then(|files| {
-              if files.is_empty() {
-                Err(generic_error("No target files found."))
-              } else {
-                Ok(files)
-              }
-            })?;
+          let plugins = load_plugins(&factory, &fmt_options).await?;
+          let files =
+            collect_fmt_files(cli_options, fmt_options.files.clone(), &plugins)
+              .and_then(|files| {
+                if files.is_empty() {
+                  Err(generic_error("No target files found."))
+                } else {
+                  Ok(files)
+                }
+              })?;
           let _ = watcher_communicator.watch_paths(files.clone());
           let refmt_files = if let Some(paths) = changed_paths {
             if fmt_options.check {
@@ -105,7 +129,7 @@ pub async fn format(flags: Flags, fmt_flags: FmtFlags) -> Result<(), AnyError> {
           } else {
             files
           };
-          format_files(factory, fmt_options, refmt_files).await?;
+          format_files(factory, fmt_options, plugins, refmt_files).await?;
 
           Ok(())
         })
@@ -116,37 +140,71 @@ pub async fn format(flags: Flags, fmt_flags: FmtFlags) -> Result<(), AnyError> {
     let factory = CliFactory::from_flags(flags)?;
     let cli_options = factory.cli_options();
     let fmt_options = cli_options.resolve_fmt_options(fmt_flags)?;
-    let files = collect_fmt_files(cli_options, fmt_options.files.clone())
-      .and_then(|files| {
-        if files.is_empty() {
-          Err(generic_error("No target files found."))
-        } else {
-          Ok(files)
-        }
-      })?;
-    format_files(factory, fmt_options, files).await?;
+    let plugins = load_plugins(&factory, &fmt_options).await?;
+    let files =
+      collect_fmt_files(cli_options, fmt_options.files.clone(), &plugins)
+        .and_then(|files| {
+          if files.is_empty() {
+            Err(generic_error("No target files found."))
+          } else {
+            Ok(files)
+          }
+        })?;
+    format_files(factory, fmt_options, plugins, files).await?;
   }
 
   Ok(())
 }
 
+async fn load_plugins(
+  factory: &CliFactory,
+  fmt_options: &FmtOptions,
+) -> Result<FmtPlugins, AnyError> {
+  if fmt_options.plugins.is_empty() {
+    return Ok(FmtPlugins::default());
+  }
+  let maybe_lockfile = factory.maybe_lockfile();
+  let plugins = FmtPlugins::load(
+    factory.file_fetcher()?,
+    &fmt_options.plugins,
+    &fmt_options.options,
+    maybe_lockfile.as_deref(),
+    std::thread::available_parallelism()
+      .map(|n| n.get())
+      .unwrap_or(1),
+  )
+  .await?;
+  if let Some(lockfile) = maybe_lockfile {
+    write_lockfile(&lockfile.lock(), factory.cli_options().frozen_lockfile())?;
+  }
+  Ok(plugins)
+}
+
 async fn format_files(
   factory: CliFactory,
   fmt_options: FmtOptions,
+  plugins: FmtPlugins,
   paths: Vec<PathBuf>,
 ) -> Result<(), AnyError> {
   let caches = factory.caches()?;
   let check = fmt_options.check;
   let incremental_cache = Arc::new(IncrementalCache::new(
     caches.fmt_incremental_cache_db(),
-    &fmt_options.options,
+    &(
+      &fmt_options.options,
+      fmt_options.markdown.format_code_blocks,
+      // keyed by content, so that changes of a plugin at the same url
+      // invalidate the cache
+      plugins.checksums(),
+      &fmt_options.member_options,
+    ),
     &paths,
   ));
   if check {
-    check_source_files(paths, fmt_options.options, incremental_cache.clone())
+    check_source_files(paths, fmt_options, plugins, incremental_cache.clone())
       .await?;
   } else {
-    format_source_files(paths, fmt_options.options, incremental_cache.clone())
+    format_source_files(paths, fmt_options, plugins, incremental_cache.clone())
       .await?;
   }
   incremental_cache.wait_completion().await;
@@ -156,41 +214,48 @@ async fn format_files(
 fn collect_fmt_files(
   cli_options: &CliOptions,
   files: FilePatterns,
+  plugins: &FmtPlugins,
 ) -> Result<Vec<PathBuf>, AnyError> {
-  FileCollector::new(|e| is_supported_ext_fmt(e.path))
-    .ignore_git_folder()
-    .ignore_node_modules()
-    .set_vendor_folder(cli_options.vendor_dir_path().map(ToOwned::to_owned))
-    .collect_file_patterns(files)
+  FileCollector::new(|e| {
+    is_supported_ext_fmt(e.path) || plugins.matches(e.path)
+  })
+  .ignore_git_folder()
+  .ignore_node_modules()
+  .set_vendor_folder(cli_options.vendor_dir_path().map(ToOwned::to_owned))
+  .collect_file_patterns(files)
 }
 
 /// Formats markdown (using <https://github.com/dprint/dprint-plugin-markdown>) and its code blocks
-/// (ts/tsx, js/jsx).
+/// (ts/tsx, js/jsx, json/jsonc), unless disabled in `markdown_options`.
 fn format_markdown(
   file_text: &str,
   fmt_options: &FmtOptionsConfig,
+  markdown_options: &MarkdownFmtOptions,
 ) -> Result<Option<String>, AnyError> {
   let markdown_config = get_resolved_markdown_config(fmt_options);
+  let format_code_blocks = markdown_options.format_code_blocks;
   dprint_plugin_markdown::format_text(
     file_text,
     &markdown_config,
     move |tag, text, line_width| {
       let tag = tag.to_lowercase();
-      if matches!(
-        tag.as_str(),
-        "ts"
-          | "tsx"
-          | "js"
-          | "jsx"
-          | "cjs"
-          | "cts"
-          | "mjs"
-          | "mts"
-          | "javascript"
-          | "typescript"
-          | "json"
-          | "jsonc"
-      ) {
+      if format_code_blocks
+        && matches!(
+          tag.as_str(),
+          "ts"
+            | "tsx"
+            | "js"
+            | "jsx"
+            | "cjs"
+            | "cts"
+            | "mjs"
+            | "mts"
+            | "javascript"
+            | "typescript"
+            | "json"
+            | "jsonc"
+        )
+      {
         // It's important to tell dprint proper file extension, otherwise
         // it might parse the file twice.
         let extension = match tag.as_str() {
@@ -239,18 +304,19 @@ pub fn format_file(
   file_path: &Path,
   file_text: &str,
   fmt_options: &FmtOptionsConfig,
+  markdown_options: &MarkdownFmtOptions,
 ) -> Result<Option<String>, AnyError> {
   let ext = get_extension(file_path).unwrap_or_default();
 
   match ext.as_str() {
     "md" | "mkd" | "mkdn" | "mdwn" | "mdown" | "markdown" => {
-      format_markdown(file_text, fmt_options)
+      format_markdown(file_text, fmt_options, markdown_options)
     }
     "json" | "jsonc" => format_json(file_path, file_text, fmt_options),
     "ipynb" => dprint_plugin_jupyter::format_text(
       file_text,
       |file_path: &Path, file_text: String| {
-        format_file(file_path, &file_text, fmt_options)
+        format_file(file_path, &file_text, fmt_options, markdown_options)
       },
     ),
     _ => {
@@ -260,6 +326,23 @@ pub fn format_file(
   }
 }
 
+/// Formats a file with the built-in formatters, or the formatter plugin
+/// handling it if the built-in formatters don't support it.
+pub fn format_file_with_plugins(
+  file_path: &Path,
+  file_text: &str,
+  fmt_options: &FmtOptionsConfig,
+  markdown_options: &MarkdownFmtOptions,
+  plugins: &FmtPlugins,
+) -> Result<Option<String>, AnyError> {
+  if !is_supported_ext_fmt(file_path) {
+    if let Some(result) = plugins.format(file_path, file_text) {
+      return result;
+    }
+  }
+  format_file(file_path, file_text, fmt_options, markdown_options)
+}
+
 pub fn format_parsed_source(
   parsed_source: &ParsedSource,
   fmt_options: &FmtOptionsConfig,
@@ -272,7 +355,8 @@ pub fn format_parsed_source(
 
 async fn check_source_files(
   paths: Vec<PathBuf>,
-  fmt_options: FmtOptionsConfig,
+  fmt_options: FmtOptions,
+  plugins: FmtPlugins,
   incremental_cache: Arc<IncrementalCache>,
 ) -> Result<(), AnyError> {
   let not_formatted_files_count = Arc::new(AtomicUsize::new(0));
@@ -293,13 +377,21 @@ async fn check_source_files(
         return Ok(());
       }
 
-      match format_file(&file_path, &file_text, &fmt_options) {
+      match format_file_with_plugins(
+        &file_path,
+        &file_text,
+        fmt_options.options_for_path(&file_path),
+        &fmt_options.markdown,
+        &plugins,
+      ) {
         Ok(Some(formatted_text)) => {
           not_formatted_files_count.fetch_add(1, Ordering::Relaxed);
           let _g = output_lock.lock();
-          let diff = diff(&file_text, &formatted_text);
-          info!("");
-          info!("{} {}:", colors::bold("from"), file_path.display());
+          let diff = unified_diff(
+            &file_path.to_string_lossy(),
+            &file_text,
+            &formatted_text,
+          );
           info!("{}", diff);
         }
         Ok(None) => {
@@ -353,7 +445,8 @@ async fn check_source_files(
 
 async fn format_source_files(
   paths: Vec<PathBuf>,
-  fmt_options: FmtOptionsConfig,
+  fmt_options: FmtOptions,
+  plugins: FmtPlugins,
   incremental_cache: Arc<IncrementalCache>,
 ) -> Result<(), AnyError> {
   let formatted_files_count = Arc::new(AtomicUsize::new(0));
@@ -375,8 +468,16 @@ async fn format_source_files(
       match format_ensure_stable(
         &file_path,
         &file_contents.text,
-        &fmt_options,
-        format_file,
+        fmt_options.options_for_path(&file_path),
+        |file_path, file_text, options| {
+          format_file_with_plugins(
+            file_path,
+            file_text,
+            options,
+            &fmt_options.markdown,
+            &plugins,
+          )
+        },
       ) {
         Ok(Some(formatted_text)) => {
           incremental_cache.update_file(&file_path, &formatted_text);
@@ -484,16 +585,47 @@ fn format_ensure_stable(
   }
 }
 
-/// Format stdin and write result to stdout.
-/// Treats input as set by `--ext` flag.
-/// Compatible with `--check` flag.
-fn format_stdin(fmt_options: FmtOptions, ext: &str) -> Result<(), AnyError> {
+/// Whether the file at `file_path` isn't excluded from formatting by the
+/// `--ignore` flag or the configuration file.
+fn is_fmt_path_included(fmt_options: &FmtOptions, file_path: &Path) -> bool {
+  ModuleSpecifier::from_file_path(file_path)
+    .map(|specifier| fmt_options.files.matches_specifier(&specifier))
+    .unwrap_or(true)
+}
+
+fn read_stdin() -> Result<String, AnyError> {
   let mut source = String::new();
   if stdin().read_to_string(&mut source).is_err() {
     bail!("Failed to read from stdin");
   }
-  let file_path = PathBuf::from(format!("_stdin.{ext}"));
-  let formatted_text = format_file(&file_path, &source, &fmt_options.options)?;
+  Ok(source)
+}
+
+fn copy_stdin_to_stdout(check: bool) -> Result<(), AnyError> {
+  let source = read_stdin()?;
+  if !check {
+    stdout().write_all(source.as_bytes())?;
+  }
+  Ok(())
+}
+
+/// Format stdin and write result to stdout.
+/// Treats input as the contents of the file at `file_path`, which is set by
+/// the `--stdin-filepath` or `--ext` flag.
+/// Compatible with `--check` flag.
+fn format_stdin(
+  fmt_options: &FmtOptions,
+  plugins: &FmtPlugins,
+  file_path: &Path,
+) -> Result<(), AnyError> {
+  let source = read_stdin()?;
+  let formatted_text = format_file_with_plugins(
+    file_path,
+    &source,
+    fmt_options.options_for_path(file_path),
+    &fmt_options.markdown,
+    plugins,
+  )?;
   if fmt_options.check {
     if formatted_text.is_some() {
       println!("Not formatted stdin");
@@ -809,6 +941,7 @@ mod test {
         single_quote: Some(true),
         ..Default::default()
       },
+      &Default::default(),
     )
     .unwrap()
     .unwrap();
@@ -0,0 +1,111 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// Host side of the dprint Wasm plugin interface (schema version 4), used by
+// `deno fmt` to format file types that aren't supported out of the box.
+//
+// Data is exchanged with a plugin through its "shared bytes" buffer: the host
+// asks the plugin to allocate it with `clear_shared_bytes(length)` before
+// writing to it, and plugin functions that produce data return its length.
+
+// deno-lint-ignore-file no-undef
+
+(() => {
+  // Only one configuration is ever registered with each plugin.
+  const CONFIG_ID = 1;
+
+  const FORMAT_RESULT_NO_CHANGE = 0;
+  const FORMAT_RESULT_CHANGE = 1;
+  const FORMAT_RESULT_ERROR = 2;
+
+  /** @type {{ writeBytes: (bytes: Uint8Array) => void, readBytes: (length: number) => Uint8Array, exports: any }[]} */
+  const plugins = [];
+
+  function createImports(module) {
+    const dprintImports = {
+      // Formatting code embedded in another language through the host isn't
+      // supported, so leave it unchanged.
+      host_format: () => FORMAT_RESULT_NO_CHANGE,
+      host_get_formatted_text: () => 0,
+      host_get_error_text: () => 0,
+      host_has_cancelled: () => 0,
+      host_write_buffer: () => {},
+    };
+    const imports = {};
+    for (const { module: moduleName, name, kind } of WebAssembly.Module.imports(
+      module,
+    )) {
+      if (kind !== "function") {
+        continue;
+      }
+      imports[moduleName] ??= {};
+      // WASI and other environment functions are stubbed out, plugins can't
+      // access the outside world.
+      imports[moduleName][name] = (moduleName === "dprint" &&
+        dprintImports[name]) || (() => 0);
+    }
+    return imports;
+  }
+
+  /**
+   * @param {Uint8Array} wasmBytes
+   * @param {Uint8Array} configBytes JSON of the plugin and global config
+   * @returns {[Uint8Array, Uint8Array]} JSON of the file matching info and
+   * the config diagnostics
+   */
+  function loadPlugin(wasmBytes, configBytes) {
+    const module = new WebAssembly.Module(wasmBytes);
+    const instance = new WebAssembly.Instance(module, createImports(module));
+    const exports = instance.exports;
+    if (typeof exports.dprint_plugin_version_4 !== "function") {
+      throw new Error(
+        "Unsupported plugin, only dprint Wasm plugins using schema version 4 are supported.",
+      );
+    }
+    exports._initialize?.();
+
+    const writeBytes = (bytes) => {
+      const pointer = exports.clear_shared_bytes(bytes.length);
+      new Uint8Array(exports.memory.buffer, pointer, bytes.length).set(bytes);
+    };
+    const readBytes = (length) => {
+      const pointer = exports.get_shared_bytes_ptr();
+      return new Uint8Array(exports.memory.buffer, pointer, length).slice();
+    };
+
+    writeBytes(configBytes);
+    exports.register_config(CONFIG_ID);
+    const diagnostics = readBytes(exports.get_config_diagnostics(CONFIG_ID));
+    const fileMatching = readBytes(
+      exports.get_config_file_matching(CONFIG_ID),
+    );
+    plugins.push({ writeBytes, readBytes, exports });
+    return [fileMatching, diagnostics];
+  }
+
+  /**
+   * @param {number} index
+   * @param {Uint8Array} filePathBytes
+   * @param {Uint8Array} fileTextBytes
+   * @returns {[number, Uint8Array | null]} the format result and either the
+   * formatted text or the error message
+   */
+  function formatText(index, filePathBytes, fileTextBytes) {
+    const { writeBytes, readBytes, exports } = plugins[index];
+    writeBytes(filePathBytes);
+    exports.set_file_path();
+    writeBytes(fileTextBytes);
+    const result = exports.format(CONFIG_ID);
+    switch (result) {
+      case FORMAT_RESULT_NO_CHANGE:
+        return [result, null];
+      case FORMAT_RESULT_CHANGE:
+        return [result, readBytes(exports.get_formatted_text())];
+      case FORMAT_RESULT_ERROR:
+        return [result, readBytes(exports.get_error_text())];
+      default:
+        throw new Error(`Unknown format result: ${result}`);
+    }
+  }
+
+  return [loadPlugin, formatText];
+})();
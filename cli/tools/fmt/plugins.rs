@@ -0,0 +1,444 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Support for formatting additional file types with
+//! [dprint](https://dprint.dev/plugins/) Wasm plugins declared in the `fmt`
+//! config, e.g. `"plugins": ["https://plugins.dprint.dev/toml-0.6.1.wasm"]`.
+//!
+//! Remote plugins must be pinned to their content, either with the SHA-256
+//! checksum appended to the url (`https://.../toml-0.6.1.wasm@<checksum>`) or
+//! with the lockfile.
+
+use crate::args::FmtOptionsConfig;
+use crate::args::Lockfile;
+use crate::file_fetcher::FileFetcher;
+use crate::util::checksum;
+use crate::util::path::get_extension;
+use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::error::JsError;
+use deno_core::parking_lot::Mutex;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_core::serde_v8;
+use deno_core::url::Url;
+use deno_core::v8;
+use deno_core::JsBuffer;
+use deno_core::JsRuntime;
+use deno_core::ModuleCodeString;
+use deno_core::RuntimeOptions;
+use deno_core::ToJsBuffer;
+use deno_runtime::permissions::PermissionsContainer;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::mpsc;
+use std::sync::Arc;
+
+const FORMAT_RESULT_CHANGE: u8 = 1;
+const FORMAT_RESULT_ERROR: u8 = 2;
+
+/// The loaded formatter plugins. The plugins are instantiated in V8 isolates
+/// that live on dedicated threads, which format one file at a time each.
+#[derive(Clone, Debug, Default)]
+pub struct FmtPlugins(Option<Arc<FmtPluginsInner>>);
+
+#[derive(Debug)]
+struct FmtPluginsInner {
+  /// The checksums of the plugins' content, in the order they are declared.
+  checksums: Vec<String>,
+  /// Maps lowercase file extensions to the index of the plugin formatting
+  /// them. Plugins declared first take precedence.
+  file_extensions: HashMap<String, usize>,
+  file_names: HashMap<String, usize>,
+  requests: mpsc::Sender<FormatRequest>,
+}
+
+#[derive(Debug)]
+struct FormatRequest {
+  plugin_index: usize,
+  file_path: PathBuf,
+  file_text: String,
+  response: mpsc::Sender<Result<Option<String>, AnyError>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileMatchingInfo {
+  #[serde(default)]
+  file_extensions: Vec<String>,
+  #[serde(default)]
+  file_names: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ConfigurationDiagnostic {
+  property_name: String,
+  message: String,
+}
+
+impl FmtPlugins {
+  /// Downloads (or loads from the cache) and instantiates the plugins at
+  /// `urls`, configured with the global formatting options. The plugins are
+  /// instantiated on `threads` threads, so that as many files can be
+  /// formatted at the same time.
+  pub async fn load(
+    file_fetcher: &FileFetcher,
+    urls: &[Url],
+    fmt_options: &FmtOptionsConfig,
+    maybe_lockfile: Option<&Mutex<Lockfile>>,
+    threads: usize,
+  ) -> Result<Self, AnyError> {
+    if urls.is_empty() {
+      return Ok(Self::default());
+    }
+    let mut plugins = Vec::with_capacity(urls.len());
+    let mut checksums = Vec::with_capacity(urls.len());
+    for url in urls {
+      let (url, expected_checksum) = split_checksum(url);
+      let file = file_fetcher
+        .fetch(&url, &PermissionsContainer::allow_all())
+        .await
+        .with_context(|| format!("Failed loading formatter plugin {url}"))?;
+      let checksum = checksum::gen(&[&file.source]);
+      verify_checksum(&url, &checksum, expected_checksum, maybe_lockfile)?;
+      plugins.push((url, file.source));
+      checksums.push(checksum);
+    }
+    let config = serde_json::to_vec(&json!({
+      "plugin": {},
+      "global": {
+        "lineWidth": fmt_options.line_width,
+        "indentWidth": fmt_options.indent_width,
+        "useTabs": fmt_options.use_tabs,
+        "newLineKind": "lf",
+      },
+    }))?;
+
+    let plugins = Arc::new(plugins);
+    let config = Arc::new(config);
+    let (requests, requests_rx) = mpsc::channel::<FormatRequest>();
+    let requests_rx = Arc::new(Mutex::new(requests_rx));
+    let (loaded_tx, loaded_rx) = mpsc::channel();
+    let threads = threads.max(1);
+    for _ in 0..threads {
+      let plugins = plugins.clone();
+      let config = config.clone();
+      let requests_rx = requests_rx.clone();
+      let loaded_tx = loaded_tx.clone();
+      std::thread::spawn(move || {
+        let mut runtime = match PluginRuntime::new() {
+          Ok(runtime) => runtime,
+          Err(err) => {
+            let _ = loaded_tx.send(Err(err));
+            return;
+          }
+        };
+        let file_matching = plugins
+          .iter()
+          .map(|(url, wasm_bytes)| {
+            runtime
+              .load_plugin(wasm_bytes, &config)
+              .with_context(|| format!("Failed loading formatter plugin {url}"))
+          })
+          .collect::<Result<Vec<_>, _>>();
+        let failed = file_matching.is_err();
+        let _ = loaded_tx.send(file_matching);
+        if failed {
+          return;
+        }
+        // runs until all handles to the plugins are dropped
+        loop {
+          let Ok(request) = requests_rx.lock().recv() else {
+            break;
+          };
+          let result = runtime.format(
+            request.plugin_index,
+            &request.file_path,
+            &request.file_text,
+          );
+          let _ = request.response.send(result);
+        }
+      });
+    }
+    drop(loaded_tx);
+    // every thread loads the same plugins, so their file matching is equal
+    let mut file_matching = Vec::new();
+    for _ in 0..threads {
+      file_matching = loaded_rx.recv().map_err(|_| {
+        anyhow!("Formatter plugin thread exited unexpectedly")
+      })??;
+    }
+
+    let mut file_extensions = HashMap::new();
+    let mut file_names = HashMap::new();
+    for (index, info) in file_matching.into_iter().enumerate() {
+      for ext in info.file_extensions {
+        file_extensions.entry(ext.to_lowercase()).or_insert(index);
+      }
+      for name in info.file_names {
+        file_names.entry(name).or_insert(index);
+      }
+    }
+    Ok(Self(Some(Arc::new(FmtPluginsInner {
+      checksums,
+      file_extensions,
+      file_names,
+      requests,
+    }))))
+  }
+
+  /// The checksums of the plugins' content, which identify the plugins
+  /// regardless of where they were loaded from.
+  pub fn checksums(&self) -> &[String] {
+    match &self.0 {
+      Some(inner) => &inner.checksums,
+      None => &[],
+    }
+  }
+
+  /// Whether one of the plugins formats the file at `file_path`.
+  pub fn matches(&self, file_path: &Path) -> bool {
+    self.plugin_index(file_path).is_some()
+  }
+
+  /// Formats the file with the plugin matching it, returning `None` if there
+  /// is no such plugin.
+  pub fn format(
+    &self,
+    file_path: &Path,
+    file_text: &str,
+  ) -> Option<Result<Option<String>, AnyError>> {
+    let inner = self.0.as_ref()?;
+    let plugin_index = self.plugin_index(file_path)?;
+    let (response, response_rx) = mpsc::channel();
+    let request = FormatRequest {
+      plugin_index,
+      file_path: file_path.to_path_buf(),
+      file_text: file_text.to_string(),
+      response,
+    };
+    if inner.requests.send(request).is_err() {
+      return Some(Err(anyhow!("Formatter plugin thread has exited")));
+    }
+    Some(response_rx.recv().unwrap_or_else(|_| {
+      Err(anyhow!("Formatter plugin thread exited unexpectedly"))
+    }))
+  }
+
+  fn plugin_index(&self, file_path: &Path) -> Option<usize> {
+    let inner = self.0.as_ref()?;
+    let file_name = file_path.file_name().and_then(|name| name.to_str());
+    if let Some(index) = file_name.and_then(|name| inner.file_names.get(name)) {
+      return Some(*index);
+    }
+    let ext = get_extension(file_path)?;
+    inner.file_extensions.get(&ext).copied()
+  }
+}
+
+/// Splits the checksum appended to the url of a plugin, if any, e.g.
+/// `https://plugins.dprint.dev/toml-0.6.1.wasm@<checksum>`.
+fn split_checksum(url: &Url) -> (Url, Option<String>) {
+  if let Some((path, checksum)) = url.path().rsplit_once('@') {
+    if checksum.len() == 64 && checksum.chars().all(|c| c.is_ascii_hexdigit()) {
+      let checksum = checksum.to_ascii_lowercase();
+      let mut url = url.clone();
+      url.set_path(path);
+      return (url, Some(checksum));
+    }
+  }
+  (url.clone(), None)
+}
+
+/// Verifies the checksum of a plugin against the checksum appended to its
+/// url or, for remote plugins without one, against the lockfile.
+fn verify_checksum(
+  url: &Url,
+  checksum: &str,
+  expected_checksum: Option<String>,
+  maybe_lockfile: Option<&Mutex<Lockfile>>,
+) -> Result<(), AnyError> {
+  if let Some(expected_checksum) = expected_checksum {
+    if checksum != expected_checksum {
+      bail!(
+        concat!(
+          "The checksum of formatter plugin {} doesn't match.\n",
+          "  Expected: {}\n",
+          "  Actual: {}",
+        ),
+        url,
+        expected_checksum,
+        checksum
+      );
+    }
+    return Ok(());
+  }
+  if !matches!(url.scheme(), "http" | "https") {
+    return Ok(());
+  }
+  let Some(lockfile) = maybe_lockfile else {
+    bail!(
+      concat!(
+        "The remote formatter plugin {} must be pinned with a checksum or a ",
+        "lockfile, e.g. \"{}@{}\"",
+      ),
+      url,
+      url,
+      checksum
+    );
+  };
+  let mut lockfile = lockfile.lock();
+  // the lockfile hashes the source code of modules, so it stores the hash of
+  // the plugin's checksum rather than of its binary content
+  if !lockfile.check_or_insert_remote(url.as_str(), checksum) {
+    bail!(
+      concat!(
+        "The formatter plugin {} doesn't match the expected hash in the ",
+        "lock file.\n  Lock file: {}",
+      ),
+      url,
+      lockfile.filename.display()
+    );
+  }
+  Ok(())
+}
+
+/// The isolate running the plugins, see `plugins.js`.
+struct PluginRuntime {
+  js_runtime: JsRuntime,
+  load_plugin_fn: v8::Global<v8::Function>,
+  format_fn: v8::Global<v8::Function>,
+}
+
+impl PluginRuntime {
+  fn new() -> Result<Self, AnyError> {
+    let mut js_runtime = JsRuntime::new(RuntimeOptions::default());
+    let functions = js_runtime.execute_script(
+      "ext:cli/fmt_plugins.js",
+      ModuleCodeString::from_static(include_str!("plugins.js")),
+    )?;
+    let (load_plugin_fn, format_fn) = {
+      let scope = &mut js_runtime.handle_scope();
+      let functions = v8::Local::new(scope, functions);
+      let functions = v8::Local::<v8::Array>::try_from(functions)?;
+      let mut get_fn = |index| -> Result<_, AnyError> {
+        let function = functions.get_index(scope, index).unwrap();
+        let function = v8::Local::<v8::Function>::try_from(function)?;
+        Ok(v8::Global::new(scope, function))
+      };
+      (get_fn(0)?, get_fn(1)?)
+    };
+    Ok(Self {
+      js_runtime,
+      load_plugin_fn,
+      format_fn,
+    })
+  }
+
+  fn load_plugin(
+    &mut self,
+    wasm_bytes: &[u8],
+    config: &[u8],
+  ) -> Result<FileMatchingInfo, AnyError> {
+    let load_plugin_fn = self.load_plugin_fn.clone();
+    let (file_matching, diagnostics): (JsBuffer, JsBuffer) = self.call(
+      &load_plugin_fn,
+      vec![
+        ToJsBuffer::from(wasm_bytes.to_vec()),
+        ToJsBuffer::from(config.to_vec()),
+      ],
+      None,
+    )?;
+    let diagnostics: Vec<ConfigurationDiagnostic> =
+      serde_json::from_slice(&diagnostics)?;
+    if let Some(diagnostic) = diagnostics.first() {
+      bail!(
+        "Invalid configuration for \"{}\": {}",
+        diagnostic.property_name,
+        diagnostic.message
+      );
+    }
+    Ok(serde_json::from_slice(&file_matching)?)
+  }
+
+  fn format(
+    &mut self,
+    plugin_index: usize,
+    file_path: &Path,
+    file_text: &str,
+  ) -> Result<Option<String>, AnyError> {
+    let format_fn = self.format_fn.clone();
+    let (result, text): (u8, Option<JsBuffer>) = self.call(
+      &format_fn,
+      vec![
+        ToJsBuffer::from(file_path.to_string_lossy().as_bytes().to_vec()),
+        ToJsBuffer::from(file_text.as_bytes().to_vec()),
+      ],
+      Some(plugin_index),
+    )?;
+    let text = text
+      .map(|text| String::from_utf8_lossy(&text).into_owned())
+      .unwrap_or_default();
+    match result {
+      FORMAT_RESULT_CHANGE => Ok(Some(text)),
+      FORMAT_RESULT_ERROR => Err(anyhow!("{}", text)),
+      _ => Ok(None),
+    }
+  }
+
+  fn call<T: DeserializeOwned>(
+    &mut self,
+    function: &v8::Global<v8::Function>,
+    args: Vec<ToJsBuffer>,
+    plugin_index: Option<usize>,
+  ) -> Result<T, AnyError> {
+    let scope = &mut self.js_runtime.handle_scope();
+    let tc_scope = &mut v8::TryCatch::new(scope);
+    let function = v8::Local::new(tc_scope, function);
+    let undefined = v8::undefined(tc_scope).into();
+    let mut v8_args = Vec::with_capacity(args.len() + 1);
+    if let Some(plugin_index) = plugin_index {
+      v8_args.push(v8::Integer::new(tc_scope, plugin_index as i32).into());
+    }
+    for arg in args {
+      v8_args.push(serde_v8::to_v8(tc_scope, arg)?);
+    }
+    let result = function.call(tc_scope, undefined, &v8_args);
+    if let Some(exception) = tc_scope.exception() {
+      return Err(JsError::from_v8_exception(tc_scope, exception).into());
+    }
+    let result = result.ok_or_else(|| anyhow!("Formatter plugin failed"))?;
+    Ok(serde_v8::from_v8(tc_scope, result)?)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn splits_checksum_from_url() {
+    let checksum =
+      "ae4310af8c38d40eae4bb5089e28f92d5b880c4232bf021277575f7fc146c17f";
+    let url = Url::parse(&format!(
+      "https://plugins.dprint.dev/toml-0.6.1.wasm@{}",
+      checksum.to_uppercase()
+    ))
+    .unwrap();
+    assert_eq!(
+      split_checksum(&url),
+      (
+        Url::parse("https://plugins.dprint.dev/toml-0.6.1.wasm").unwrap(),
+        Some(checksum.to_string())
+      )
+    );
+
+    let url = Url::parse("https://example.com/@scope/plugin.wasm").unwrap();
+    assert_eq!(split_checksum(&url), (url.clone(), None));
+  }
+}
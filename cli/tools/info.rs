@@ -4,10 +4,11 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fmt;
 use std::fmt::Write;
+use std::rc::Rc;
 
+use deno_ast::MediaType;
 use deno_ast::ModuleSpecifier;
 use deno_core::anyhow::bail;
-use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::resolve_url_or_path;
 use deno_core::serde_json;
@@ -26,14 +27,18 @@ use deno_semver::npm::NpmPackageReqReference;
 use deno_semver::package::PackageNv;
 use deno_terminal::colors;
 
+use crate::args::write_lockfile;
 use crate::args::Flags;
 use crate::args::InfoFlags;
+use crate::args::InfoGraphFormat;
 use crate::display;
+use crate::emit::Emitter;
 use crate::factory::CliFactory;
 use crate::graph_util::graph_lock_or_exit;
 use crate::npm::CliNpmResolver;
 use crate::npm::ManagedCliNpmResolver;
 use crate::util::checksum;
+use crate::util::path::relative_specifier;
 
 pub async fn info(flags: Flags, info_flags: InfoFlags) -> Result<(), AnyError> {
   let factory = CliFactory::from_flags(flags)?;
@@ -74,12 +79,25 @@ pub async fn info(flags: Flags, info_flags: InfoFlags) -> Result<(), AnyError> {
       // validate the integrity of all the modules
       graph_lock_or_exit(&graph, &mut lockfile);
       // update it with anything new
-      lockfile.write().context("Failed writing lockfile.")?;
+      write_lockfile(&lockfile, cli_options.frozen_lockfile())?;
     }
 
-    if info_flags.json {
+    if let Some(format) = info_flags.graph {
+      let mut output = String::new();
+      let cwd_specifier =
+        ModuleSpecifier::from_directory_path(cli_options.initial_cwd())
+          .unwrap();
+      write_graph(&graph, format, &cwd_specifier, &mut output)?;
+      display::write_to_stdout_ignore_sigpipe(output.as_bytes())?;
+    } else if info_flags.json {
       let mut json_graph = json!(graph);
       add_npm_packages_to_json(&mut json_graph, npm_resolver.as_ref());
+      add_sizes_and_emit_cache_to_json(
+        &mut json_graph,
+        &graph,
+        npm_resolver.as_ref(),
+        factory.emitter()?,
+      );
       display::write_json_to_stdout(&json_graph)?;
     } else {
       let mut output = String::new();
@@ -270,6 +288,468 @@ fn add_npm_packages_to_json(
   json.insert("npmPackages".to_string(), json_packages.into());
 }
 
+/// Adds the emit cache status and the transitive sizes of the modules, the
+/// sizes of the npm packages and the total size of the graph to the json.
+fn add_sizes_and_emit_cache_to_json(
+  json: &mut serde_json::Value,
+  graph: &ModuleGraph,
+  npm_resolver: &dyn CliNpmResolver,
+  emitter: &Emitter,
+) {
+  let npm_info = match npm_resolver.as_managed() {
+    Some(npm_resolver) => {
+      let npm_snapshot = npm_resolver.snapshot();
+      NpmInfo::build(graph, npm_resolver, &npm_snapshot)
+    }
+    None => NpmInfo::default(),
+  };
+  let transitive_sizes = TransitiveSizes::build(graph, &npm_info);
+  let json = json.as_object_mut().unwrap();
+
+  let modules = json.get_mut("modules").and_then(|m| m.as_array_mut());
+  if let Some(modules) = modules {
+    for module_json in modules.iter_mut() {
+      let Some(module_json) = module_json.as_object_mut() else {
+        continue;
+      };
+      let maybe_module = module_json
+        .get("specifier")
+        .and_then(|s| s.as_str())
+        .and_then(|s| ModuleSpecifier::parse(s).ok())
+        .and_then(|specifier| graph.get(&graph.resolve(&specifier)));
+      let Some(module) = maybe_module else {
+        continue;
+      };
+      if let Some(status) = emit_cache_status(module, emitter) {
+        module_json.insert("emitCache".to_string(), status.into());
+      }
+      module_json.insert(
+        "totalSize".to_string(),
+        transitive_sizes.get([module]).into(),
+      );
+    }
+  }
+
+  let packages = json.get_mut("npmPackages").and_then(|p| p.as_object_mut());
+  if let Some(packages) = packages {
+    for (id, size) in &npm_info.package_sizes {
+      let package = packages
+        .get_mut(&id.as_serialized())
+        .and_then(|p| p.as_object_mut());
+      if let Some(package) = package {
+        package.insert("size".to_string(), (*size).into());
+      }
+    }
+  }
+
+  let roots = graph
+    .roots
+    .iter()
+    .filter_map(|root| graph.get(&graph.resolve(root)));
+  json.insert("size".to_string(), transitive_sizes.get(roots).into());
+}
+
+/// Gets whether the emit of a module that requires transpilation is in the
+/// emit cache and up to date with its source.
+fn emit_cache_status(
+  module: &Module,
+  emitter: &Emitter,
+) -> Option<&'static str> {
+  let module = module.js()?;
+  let is_emittable = matches!(
+    module.media_type,
+    MediaType::TypeScript
+      | MediaType::Mts
+      | MediaType::Cts
+      | MediaType::Jsx
+      | MediaType::Tsx
+  );
+  if !is_emittable {
+    return None;
+  }
+  if emitter
+    .maybe_cached_emit(&module.specifier, &module.source)
+    .is_some()
+  {
+    Some("cached")
+  } else if module
+    .maybe_cache_info
+    .as_ref()
+    .and_then(|info| info.emit.as_ref())
+    .is_some()
+  {
+    Some("stale")
+  } else {
+    Some("missing")
+  }
+}
+
+/// The size in bytes of each module along with all the modules and npm
+/// packages it depends on, counting each of them once. The nodes reachable
+/// from each module are memoized, so the graph is walked once rather than
+/// once per module.
+struct TransitiveSizes<'a> {
+  module_indexes: HashMap<&'a ModuleSpecifier, usize>,
+  /// The size of each module or npm package.
+  sizes: Vec<u64>,
+  /// The nodes reachable from each node as a bit set.
+  reachable: Vec<Rc<Vec<u64>>>,
+}
+
+impl<'a> TransitiveSizes<'a> {
+  pub fn build(graph: &'a ModuleGraph, npm_info: &'a NpmInfo) -> Self {
+    let modules = graph.modules().collect::<Vec<_>>();
+    let module_indexes = modules
+      .iter()
+      .enumerate()
+      .map(|(i, module)| (module.specifier(), i))
+      .collect::<HashMap<_, _>>();
+    let package_indexes = npm_info
+      .packages
+      .keys()
+      .enumerate()
+      .map(|(i, id)| (id, modules.len() + i))
+      .collect::<HashMap<_, _>>();
+
+    let mut sizes = Vec::with_capacity(modules.len() + package_indexes.len());
+    let mut edges = Vec::with_capacity(sizes.capacity());
+    for module in &modules {
+      let mut module_edges = Vec::new();
+      let size = match module {
+        Module::Js(module) => {
+          let resolutions = module
+            .maybe_types_dependency
+            .iter()
+            .map(|dep| &dep.dependency)
+            .chain(
+              module
+                .dependencies
+                .values()
+                .flat_map(|dep| [&dep.maybe_code, &dep.maybe_type]),
+            );
+          for resolution in resolutions {
+            let maybe_module = resolution
+              .maybe_specifier()
+              .and_then(|specifier| graph.get(&graph.resolve(specifier)));
+            if let Some(module) = maybe_module {
+              module_edges.push(module_indexes[module.specifier()]);
+            }
+          }
+          module.size() as u64
+        }
+        Module::Json(module) => module.size() as u64,
+        Module::Npm(module) => {
+          if let Some(package) =
+            npm_info.resolve_package(module.nv_reference.nv())
+          {
+            module_edges.push(package_indexes[&package.id]);
+          }
+          0
+        }
+        Module::Node(_) | Module::External(_) => 0,
+      };
+      sizes.push(size);
+      edges.push(module_edges);
+    }
+    for (id, package) in &npm_info.packages {
+      sizes.push(npm_info.package_sizes.get(id).copied().unwrap_or(0));
+      edges.push(
+        package
+          .dependencies
+          .values()
+          .filter_map(|id| package_indexes.get(id).copied())
+          .collect(),
+      );
+    }
+
+    Self {
+      module_indexes,
+      sizes,
+      reachable: reachable_sets(&edges),
+    }
+  }
+
+  /// Gets the size of the provided modules along with all the modules and
+  /// npm packages they depend on, counting each of them once.
+  pub fn get<'b>(&self, modules: impl IntoIterator<Item = &'b Module>) -> u64 {
+    let mut nodes = vec![0u64; self.sizes.len().div_ceil(64)];
+    for module in modules {
+      if let Some(index) = self.module_indexes.get(module.specifier()) {
+        for (word, reachable_word) in
+          nodes.iter_mut().zip(self.reachable[*index].iter())
+        {
+          *word |= reachable_word;
+        }
+      }
+    }
+    let mut size = 0;
+    for (i, word) in nodes.iter().enumerate() {
+      let mut word = *word;
+      while word != 0 {
+        size += self.sizes[i * 64 + word.trailing_zeros() as usize];
+        word &= word - 1;
+      }
+    }
+    size
+  }
+}
+
+/// Gets the nodes reachable from each node of a graph, including itself, as
+/// bit sets. The strongly connected components are found with Tarjan's
+/// algorithm, which completes a component only after every component it can
+/// reach, so the set of each component is computed once from the sets of the
+/// components it links to and shared by its nodes.
+fn reachable_sets(edges: &[Vec<usize>]) -> Vec<Rc<Vec<u64>>> {
+  const UNVISITED: usize = usize::MAX;
+  let words = edges.len().div_ceil(64);
+  let mut indexes = vec![UNVISITED; edges.len()];
+  let mut low_links = vec![0; edges.len()];
+  let mut on_stack = vec![false; edges.len()];
+  let mut stack = Vec::new();
+  let mut next_index = 0;
+  let mut reachable: Vec<Option<Rc<Vec<u64>>>> = vec![None; edges.len()];
+  for root in 0..edges.len() {
+    if indexes[root] != UNVISITED {
+      continue;
+    }
+    // the nodes being visited along with the position of the next edge
+    let mut visiting = vec![(root, 0)];
+    indexes[root] = next_index;
+    low_links[root] = next_index;
+    next_index += 1;
+    stack.push(root);
+    on_stack[root] = true;
+    while let Some((node, edge_position)) = visiting.last_mut() {
+      let node = *node;
+      if let Some(&next) = edges[node].get(*edge_position) {
+        *edge_position += 1;
+        if indexes[next] == UNVISITED {
+          indexes[next] = next_index;
+          low_links[next] = next_index;
+          next_index += 1;
+          stack.push(next);
+          on_stack[next] = true;
+          visiting.push((next, 0));
+        } else if on_stack[next] {
+          low_links[node] = low_links[node].min(indexes[next]);
+        }
+        continue;
+      }
+      visiting.pop();
+      if let Some((parent, _)) = visiting.last() {
+        low_links[*parent] = low_links[*parent].min(low_links[node]);
+      }
+      if low_links[node] != indexes[node] {
+        continue;
+      }
+      // the node is the root of a component
+      let mut members = Vec::new();
+      loop {
+        let member = stack.pop().unwrap();
+        on_stack[member] = false;
+        members.push(member);
+        if member == node {
+          break;
+        }
+      }
+      let mut set = vec![0u64; words];
+      for member in &members {
+        set[member / 64] |= 1 << (member % 64);
+        for next in &edges[*member] {
+          // the members of this component don't have a set yet
+          if let Some(next_set) = &reachable[*next] {
+            for (word, next_word) in set.iter_mut().zip(next_set.iter()) {
+              *word |= next_word;
+            }
+          }
+        }
+      }
+      let set = Rc::new(set);
+      for member in members {
+        reachable[member] = Some(set.clone());
+      }
+    }
+  }
+  reachable.into_iter().map(|set| set.unwrap()).collect()
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+enum ModuleOrigin {
+  Local,
+  Remote,
+  Npm,
+  Data,
+  Other,
+}
+
+impl ModuleOrigin {
+  pub fn from_specifier(specifier: &ModuleSpecifier) -> Self {
+    match specifier.scheme() {
+      "file" => Self::Local,
+      "http" | "https" => Self::Remote,
+      "npm" => Self::Npm,
+      "data" => Self::Data,
+      _ => Self::Other,
+    }
+  }
+
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      Self::Local => "local",
+      Self::Remote => "remote",
+      Self::Npm => "npm",
+      Self::Data => "data",
+      Self::Other => "other",
+    }
+  }
+
+  pub fn color(&self) -> &'static str {
+    match self {
+      Self::Local => "#c8e6c9",
+      Self::Remote => "#bbdefb",
+      Self::Npm => "#ffcdd2",
+      Self::Data => "#fff9c4",
+      Self::Other => "#eeeeee",
+    }
+  }
+}
+
+/// Writes the module graph in Graphviz DOT or Mermaid syntax. Dynamic
+/// imports and type only dependencies are drawn as dashed edges.
+fn write_graph<TWrite: Write>(
+  graph: &ModuleGraph,
+  format: InfoGraphFormat,
+  cwd_specifier: &ModuleSpecifier,
+  writer: &mut TWrite,
+) -> fmt::Result {
+  let modules = graph.modules().collect::<Vec<_>>();
+  let ids = modules
+    .iter()
+    .enumerate()
+    .map(|(i, module)| (module.specifier(), i))
+    .collect::<HashMap<_, _>>();
+  let mut all_edges = HashSet::new();
+  // prefer drawing a solid edge when a module is imported statically
+  let mut solid_edges = HashSet::new();
+  for module in &modules {
+    let Some(js_module) = module.js() else {
+      continue;
+    };
+    let mut resolutions = Vec::new();
+    if let Some(types_dep) = &js_module.maybe_types_dependency {
+      resolutions.push((&types_dep.dependency, true));
+    }
+    for dep in js_module.dependencies.values() {
+      resolutions.push((&dep.maybe_code, dep.is_dynamic));
+      resolutions.push((&dep.maybe_type, true));
+    }
+    for (resolution, dashed) in resolutions {
+      let Some(specifier) = resolution.maybe_specifier() else {
+        continue;
+      };
+      let Some(to_module) = graph.get(&graph.resolve(specifier)) else {
+        continue;
+      };
+      let edge = (ids[module.specifier()], ids[to_module.specifier()]);
+      all_edges.insert(edge);
+      if !dashed {
+        solid_edges.insert(edge);
+      }
+    }
+  }
+
+  let mut edges = all_edges
+    .into_iter()
+    .map(|edge| (edge, !solid_edges.contains(&edge)))
+    .collect::<Vec<_>>();
+  edges.sort();
+
+  match format {
+    InfoGraphFormat::Dot => {
+      writeln!(writer, "digraph {{")?;
+      writeln!(writer, "  node [shape=box, style=filled];")?;
+      for module in &modules {
+        let specifier = module.specifier();
+        writeln!(
+          writer,
+          "  n{} [label=\"{}\", fillcolor=\"{}\"];",
+          ids[specifier],
+          graph_label(specifier, cwd_specifier)
+            .replace('\\', "\\\\")
+            .replace('"', "\\\""),
+          ModuleOrigin::from_specifier(specifier).color(),
+        )?;
+      }
+      for ((from, to), dashed) in &edges {
+        if *dashed {
+          writeln!(writer, "  n{from} -> n{to} [style=dashed];")?;
+        } else {
+          writeln!(writer, "  n{from} -> n{to};")?;
+        }
+      }
+      writeln!(writer, "}}")?;
+    }
+    InfoGraphFormat::Mermaid => {
+      writeln!(writer, "graph LR")?;
+      let mut ids_by_origin = Vec::<(ModuleOrigin, Vec<String>)>::new();
+      for module in &modules {
+        let specifier = module.specifier();
+        let id = format!("n{}", ids[specifier]);
+        writeln!(
+          writer,
+          "  {}[\"{}\"]",
+          id,
+          graph_label(specifier, cwd_specifier).replace('"', "#quot;"),
+        )?;
+        let origin = ModuleOrigin::from_specifier(specifier);
+        match ids_by_origin.iter_mut().find(|(o, _)| *o == origin) {
+          Some((_, origin_ids)) => origin_ids.push(id),
+          None => ids_by_origin.push((origin, vec![id])),
+        }
+      }
+      for ((from, to), dashed) in &edges {
+        let arrow = if *dashed { "-.->" } else { "-->" };
+        writeln!(writer, "  n{from} {arrow} n{to}")?;
+      }
+      ids_by_origin.sort_by_key(|(origin, _)| *origin);
+      for (origin, origin_ids) in &ids_by_origin {
+        writeln!(
+          writer,
+          "  classDef {} fill:{}",
+          origin.as_str(),
+          origin.color()
+        )?;
+        writeln!(
+          writer,
+          "  class {} {}",
+          origin_ids.join(","),
+          origin.as_str()
+        )?;
+      }
+    }
+  }
+  Ok(())
+}
+
+fn graph_label(
+  specifier: &ModuleSpecifier,
+  cwd_specifier: &ModuleSpecifier,
+) -> String {
+  match ModuleOrigin::from_specifier(specifier) {
+    ModuleOrigin::Local => relative_specifier(cwd_specifier, specifier)
+      .unwrap_or_else(|| specifier.to_string()),
+    // data urls can be very long, so only display their media type
+    ModuleOrigin::Data => {
+      let text = specifier.as_str();
+      let end = text.find([',', ';']).unwrap_or(text.len());
+      text[..end].to_string()
+    }
+    ModuleOrigin::Remote | ModuleOrigin::Npm | ModuleOrigin::Other => {
+      specifier.to_string()
+    }
+  }
+}
+
 struct TreeNode {
   text: String,
   children: Vec<TreeNode>,
@@ -734,3 +1214,25 @@ fn maybe_size_to_text(maybe_size: Option<u64>) -> String {
   ))
   .to_string()
 }
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_reachable_sets() {
+    fn nodes(set: &[u64]) -> Vec<usize> {
+      (0..64).filter(|i| set[0] & (1 << i) != 0).collect()
+    }
+
+    // 3 -> 0 -> 1 <-> 2, and 4 without any edges
+    let edges = vec![vec![1], vec![2], vec![1], vec![0], vec![]];
+    let reachable = reachable_sets(&edges);
+    assert_eq!(nodes(&reachable[0]), vec![0, 1, 2]);
+    assert_eq!(nodes(&reachable[1]), vec![1, 2]);
+    assert_eq!(nodes(&reachable[2]), vec![1, 2]);
+    assert!(Rc::ptr_eq(&reachable[1], &reachable[2]));
+    assert_eq!(nodes(&reachable[3]), vec![0, 1, 2, 3]);
+    assert_eq!(nodes(&reachable[4]), vec![4]);
+  }
+}
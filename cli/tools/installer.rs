@@ -374,6 +374,9 @@ async fn resolve_shim_data(
     executable_args.push("--location".to_string());
     executable_args.push(url.to_string());
   }
+  if let Some(quota) = flags.local_storage_quota {
+    executable_args.push(format!("--local-storage-quota={quota}"));
+  }
   if let Some(CaData::File(ca_file)) = &flags.ca_data {
     executable_args.push("--cert".to_string());
     executable_args.push(ca_file.to_owned())
@@ -426,6 +429,10 @@ async fn resolve_shim_data(
     executable_args.push("--cached-only".to_string());
   }
 
+  if flags.http1_only {
+    executable_args.push("--http1-only".to_string());
+  }
+
   if resolve_no_prompt(&flags.permissions) {
     executable_args.push("--no-prompt".to_string());
   }
@@ -447,7 +454,11 @@ async fn resolve_shim_data(
     executable_args.push(format!("--inspect-brk={inspect_brk}"));
   }
 
-  if let Some(import_map_path) = &flags.import_map_path {
+  for import_map_path in flags
+    .import_map_path
+    .iter()
+    .chain(&flags.additional_import_map_paths)
+  {
     let import_map_url = resolve_url_or_path(import_map_path, &cwd)?;
     executable_args.push("--import-map".to_string());
     executable_args.push(import_map_url.to_string());
@@ -946,6 +957,35 @@ mod tests {
     );
   }
 
+  #[tokio::test]
+  async fn install_http1_only() {
+    let shim_data = resolve_shim_data(
+      &Flags {
+        http1_only: true,
+        ..Flags::default()
+      },
+      &InstallFlagsGlobal {
+        module_url: "http://localhost:4545/echo_server.ts".to_string(),
+        args: vec![],
+        name: Some("echo_test".to_string()),
+        root: Some(env::temp_dir().to_string_lossy().to_string()),
+        force: false,
+      },
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+      shim_data.args,
+      vec![
+        "run",
+        "--http1-only",
+        "--no-config",
+        "http://localhost:4545/echo_server.ts",
+      ]
+    );
+  }
+
   #[tokio::test]
   async fn install_allow_all() {
     let shim_data = resolve_shim_data(
@@ -141,7 +141,7 @@ pub async fn kernel(
     })?;
   repl_session.set_test_reporter_factory(Box::new(move || {
     Box::new(
-      PrettyTestReporter::new(false, true, false, true, cwd_url.clone())
+      PrettyTestReporter::new(true, false, true, cwd_url.clone())
         .with_writer(Box::new(TestWriter(stdio_tx.clone()))),
     )
   }));
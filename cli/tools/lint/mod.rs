@@ -17,7 +17,6 @@ use deno_core::parking_lot::Mutex;
 use deno_core::serde_json;
 use deno_graph::FastCheckDiagnostic;
 use deno_lint::diagnostic::LintDiagnostic;
-use deno_lint::linter::LintFileOptions;
 use deno_lint::linter::Linter;
 use deno_lint::linter::LinterBuilder;
 use deno_lint::rules;
@@ -26,20 +25,25 @@ use log::debug;
 use log::info;
 use serde::Serialize;
 use std::borrow::Cow;
+use std::collections::HashMap;
 use std::collections::HashSet;
 use std::fs;
 use std::io::stdin;
 use std::io::Read;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
+use crate::args::resolve_workspace_member_value;
 use crate::args::CliOptions;
 use crate::args::Flags;
 use crate::args::LintFlags;
 use crate::args::LintOptions;
 use crate::args::LintReporterKind;
 use crate::args::LintRulesConfig;
+use crate::args::LintSeverity;
 use crate::cache::IncrementalCache;
 use crate::colors;
 use crate::factory::CliFactory;
@@ -52,14 +56,29 @@ use crate::util::path::is_script_ext;
 use crate::util::sync::AtomicFlag;
 
 pub mod no_slow_types;
+mod plugins;
+mod sarif;
+mod suppressions;
+
+pub use plugins::LintPlugins;
+use sarif::SarifLintReporter;
+pub use suppressions::apply_suppressions;
+pub use suppressions::lint_with_suppression_reasons;
+pub use suppressions::LintSuppressionOptions;
 
 static STDIN_FILE_NAME: &str = "$deno$stdin.ts";
 
-fn create_reporter(kind: LintReporterKind) -> Box<dyn LintReporter + Send> {
+fn create_reporter(
+  kind: LintReporterKind,
+  initial_cwd: &Path,
+) -> Box<dyn LintReporter + Send> {
   match kind {
     LintReporterKind::Pretty => Box::new(PrettyLintReporter::new()),
     LintReporterKind::Json => Box::new(JsonLintReporter::new()),
     LintReporterKind::Compact => Box::new(CompactLintReporter::new()),
+    LintReporterKind::Sarif => Box::new(SarifLintReporter::new(
+      ModuleSpecifier::from_directory_path(initial_cwd).unwrap(),
+    )),
   }
 }
 
@@ -119,20 +138,31 @@ pub async fn lint(flags: Flags, lint_flags: LintFlags) -> Result<(), AnyError> {
     let files = &lint_options.files;
     let success = if is_stdin {
       let reporter_kind = lint_options.reporter_kind;
-      let reporter_lock = Arc::new(Mutex::new(create_reporter(reporter_kind)));
+      let reporter_lock = Arc::new(Mutex::new(create_reporter(
+        reporter_kind,
+        cli_options.initial_cwd(),
+      )));
       let lint_rules = get_config_rules_err_empty(
         lint_options.rules,
         cli_options.maybe_config_file().as_ref(),
       )?;
+      let plugins =
+        LintPlugins::load(&lint_options.plugins, &lint_rules.selection)?;
+      let linter = CliLinter::new(
+        lint_rules.rules,
+        plugins,
+        lint_options.require_suppression_reason,
+      );
       let file_path = cli_options.initial_cwd().join(STDIN_FILE_NAME);
-      let r = lint_stdin(&file_path, lint_rules.rules);
-      let success = handle_lint_result(
+      let r = lint_stdin(&file_path, &linter);
+      let summary = handle_lint_result(
         &file_path.to_string_lossy(),
         r,
+        &lint_options.severity,
         reporter_lock.clone(),
       );
       reporter_lock.lock().close(1);
-      success
+      summary.is_success(lint_options.max_warnings)
     } else {
       let target_files = collect_lint_files(cli_options, files.clone())
         .and_then(|files| {
@@ -162,17 +192,37 @@ async fn lint_files(
   let maybe_config_file = factory.cli_options().maybe_config_file().as_ref();
   let lint_rules =
     get_config_rules_err_empty(lint_options.rules, maybe_config_file)?;
+  let plugins =
+    LintPlugins::load(&lint_options.plugins, &lint_rules.selection)?;
+  let member_lint_rules = lint_options
+    .member_rules
+    .into_iter()
+    .map(|(dir, rules)| {
+      Ok((dir, get_config_rules_err_empty(rules, maybe_config_file)?))
+    })
+    .collect::<Result<Vec<_>, AnyError>>()?;
   let incremental_cache = Arc::new(IncrementalCache::new(
     caches.lint_incremental_cache_db(),
-    &lint_rules.incremental_cache_state(),
+    &(
+      lint_rules.incremental_cache_state(),
+      plugins.incremental_cache_state(),
+      lint_options.require_suppression_reason,
+      member_lint_rules
+        .iter()
+        .map(|(dir, rules)| (dir, rules.incremental_cache_state()))
+        .collect::<Vec<_>>(),
+    ),
     &paths,
   ));
   let target_files_len = paths.len();
   let reporter_kind = lint_options.reporter_kind;
   // todo(dsherret): abstract away this lock behind a performant interface
-  let reporter_lock =
-    Arc::new(Mutex::new(create_reporter(reporter_kind.clone())));
+  let reporter_lock = Arc::new(Mutex::new(create_reporter(
+    reporter_kind.clone(),
+    factory.cli_options().initial_cwd(),
+  )));
   let has_error = Arc::new(AtomicFlag::default());
+  let warning_count = Arc::new(AtomicUsize::new(0));
 
   let mut futures = Vec::with_capacity(2);
   if lint_rules.no_slow_types {
@@ -218,7 +268,24 @@ async fn lint_files(
 
   futures.push({
     let has_error = has_error.clone();
-    let linter = create_linter(lint_rules.rules);
+    let warning_count = warning_count.clone();
+    let severity = Arc::new(lint_options.severity.clone());
+    let member_linters = member_lint_rules
+      .into_iter()
+      .map(|(dir, rules)| {
+        let linter = CliLinter::new(
+          rules.rules,
+          plugins.clone(),
+          lint_options.require_suppression_reason,
+        );
+        (dir, linter)
+      })
+      .collect::<Vec<_>>();
+    let linter = CliLinter::new(
+      lint_rules.rules,
+      plugins,
+      lint_options.require_suppression_reason,
+    );
     let reporter_lock = reporter_lock.clone();
     let incremental_cache = incremental_cache.clone();
     let fix = lint_options.fix;
@@ -232,7 +299,17 @@ async fn lint_files(
             return Ok(());
           }
 
-          let r = lint_file(&linter, &file_path, file_text, fix);
+          let linter =
+            resolve_workspace_member_value(&member_linters, &file_path)
+              .unwrap_or(&linter);
+          let r = lint_file(linter, &file_path, file_text, fix).map(
+            |(file_source, file_diagnostics, fixed_count)| {
+              if fixed_count > 0 {
+                reporter_lock.lock().visit_fixes(fixed_count);
+              }
+              (file_source, file_diagnostics)
+            },
+          );
           if let Ok((file_source, file_diagnostics)) = &r {
             if file_diagnostics.is_empty() {
               // update the incremental cache if there were no diagnostics
@@ -244,14 +321,16 @@ async fn lint_files(
             }
           }
 
-          let success = handle_lint_result(
+          let summary = handle_lint_result(
             &file_path.to_string_lossy(),
             r,
+            &severity,
             reporter_lock.clone(),
           );
-          if !success {
+          if summary.has_errors {
             has_error.raise();
           }
+          warning_count.fetch_add(summary.warning_count, Ordering::Relaxed);
 
           Ok(())
         }
@@ -265,7 +344,11 @@ async fn lint_files(
   incremental_cache.wait_completion().await;
   reporter_lock.lock().close(target_files_len);
 
-  Ok(!has_error.is_raised())
+  let summary = LintSummary {
+    has_errors: has_error.is_raised(),
+    warning_count: warning_count.load(Ordering::Relaxed),
+  };
+  Ok(summary.is_success(lint_options.max_warnings))
 }
 
 fn collect_lint_files(
@@ -331,40 +414,80 @@ pub fn create_linter(rules: Vec<&'static dyn LintRule>) -> Linter {
 }
 
 fn lint_file(
-  linter: &Linter,
+  linter: &CliLinter,
   file_path: &Path,
   source_code: String,
   fix: bool,
-) -> Result<(ParsedSource, Vec<LintDiagnostic>), AnyError> {
+) -> Result<(ParsedSource, Vec<LintDiagnostic>, usize), AnyError> {
   let specifier = specifier_from_file_path(file_path)?;
   let media_type = MediaType::from_specifier(&specifier);
 
   if fix {
     lint_file_and_fix(linter, &specifier, media_type, source_code, file_path)
   } else {
-    linter
-      .lint_file(LintFileOptions {
-        specifier,
-        media_type,
-        source_code,
-      })
-      .map_err(AnyError::from)
+    let (source, diagnostics) =
+      linter.lint_source(specifier, media_type, source_code)?;
+    Ok((source, diagnostics, 0))
+  }
+}
+
+/// Runs the built-in and plugin rules and applies the suppression comments
+/// that deno_lint doesn't handle.
+#[derive(Clone)]
+struct CliLinter {
+  linter: Linter,
+  plugins: LintPlugins,
+  suppression_options: LintSuppressionOptions,
+}
+
+impl CliLinter {
+  fn new(
+    rules: Vec<&'static dyn LintRule>,
+    plugins: LintPlugins,
+    require_suppression_reason: bool,
+  ) -> Self {
+    let suppression_options =
+      LintSuppressionOptions::new(&rules, require_suppression_reason);
+    Self {
+      linter: create_linter(rules),
+      plugins,
+      suppression_options,
+    }
+  }
+
+  fn lint_source(
+    &self,
+    specifier: ModuleSpecifier,
+    media_type: MediaType,
+    source_code: String,
+  ) -> Result<(ParsedSource, Vec<LintDiagnostic>), AnyError> {
+    let source = deno_ast::parse_program(deno_ast::ParseParams {
+      specifier,
+      text_info: SourceTextInfo::from_string(source_code),
+      media_type,
+      capture_tokens: true,
+      scope_analysis: true,
+      maybe_syntax: None,
+    })?;
+    let mut diagnostics = lint_with_suppression_reasons(&self.linter, &source)?;
+    self.plugins.lint(&source, &mut diagnostics)?;
+    apply_suppressions(&source, &mut diagnostics, &self.suppression_options);
+    Ok((source, diagnostics))
   }
 }
 
+/// Lints the file and applies the fixes of the diagnostics, returning the
+/// number of problems that were fixed.
 fn lint_file_and_fix(
-  linter: &Linter,
+  linter: &CliLinter,
   specifier: &ModuleSpecifier,
   media_type: MediaType,
   source_code: String,
   file_path: &Path,
-) -> Result<(ParsedSource, Vec<LintDiagnostic>), deno_core::anyhow::Error> {
+) -> Result<(ParsedSource, Vec<LintDiagnostic>, usize), AnyError> {
   // initial lint
-  let (source, diagnostics) = linter.lint_file(LintFileOptions {
-    specifier: specifier.clone(),
-    media_type,
-    source_code,
-  })?;
+  let (source, diagnostics) =
+    linter.lint_source(specifier.clone(), media_type, source_code)?;
 
   // Try applying fixes repeatedly until the file has none left or
   // a maximum number of iterations is reached. This is necessary
@@ -373,6 +496,7 @@ fn lint_file_and_fix(
   let mut source = source;
   let mut diagnostics = diagnostics;
   let mut fix_iterations = 0;
+  let mut fixed_count = 0;
   loop {
     let change = apply_lint_fixes_and_relint(
       specifier,
@@ -385,6 +509,7 @@ fn lint_file_and_fix(
       Some(change) => {
         source = change.0;
         diagnostics = change.1;
+        fixed_count += change.2;
       }
       None => {
         break;
@@ -409,67 +534,82 @@ fn lint_file_and_fix(
       .context("Failed writing fix to file.")?;
   }
 
-  Ok((source, diagnostics))
+  Ok((source, diagnostics, fixed_count))
 }
 
 fn apply_lint_fixes_and_relint(
   specifier: &ModuleSpecifier,
   media_type: MediaType,
-  linter: &Linter,
+  linter: &CliLinter,
   text_info: &SourceTextInfo,
   diagnostics: &[LintDiagnostic],
-) -> Result<Option<(ParsedSource, Vec<LintDiagnostic>)>, AnyError> {
-  let Some(new_text) = apply_lint_fixes(text_info, diagnostics) else {
+) -> Result<Option<(ParsedSource, Vec<LintDiagnostic>, usize)>, AnyError> {
+  let Some((new_text, fixed_count)) = apply_lint_fixes(text_info, diagnostics)
+  else {
     return Ok(None);
   };
-  linter
-    .lint_file(LintFileOptions {
-      specifier: specifier.clone(),
-      source_code: new_text,
-      media_type,
-    })
-    .map(Some)
+  let (source, diagnostics) = linter
+    .lint_source(specifier.clone(), media_type, new_text)
     .context(
       "An applied lint fix caused a syntax error. Please report this bug.",
-    )
+    )?;
+  Ok(Some((source, diagnostics, fixed_count)))
 }
 
+/// Applies the first fix of each diagnostic that doesn't overlap with the
+/// fixes applied before it, returning the new text and the number of fixes
+/// that were applied.
 fn apply_lint_fixes(
   text_info: &SourceTextInfo,
   diagnostics: &[LintDiagnostic],
-) -> Option<String> {
+) -> Option<(String, usize)> {
   if diagnostics.is_empty() {
     return None;
   }
 
   let file_start = text_info.range().start;
-  let mut quick_fixes = diagnostics
+  let mut fixes = diagnostics
     .iter()
     // use the first quick fix
     .filter_map(|d| d.fixes.first())
-    .flat_map(|fix| fix.changes.iter())
-    .map(|change| deno_ast::TextChange {
-      range: change.range.as_byte_range(file_start),
-      new_text: change.new_text.to_string(),
+    .map(|fix| {
+      let mut changes = fix
+        .changes
+        .iter()
+        .map(|change| deno_ast::TextChange {
+          range: change.range.as_byte_range(file_start),
+          new_text: change.new_text.to_string(),
+        })
+        .collect::<Vec<_>>();
+      changes.sort_by_key(|change| change.range.start);
+      changes
     })
+    .filter(|changes| !changes.is_empty())
     .collect::<Vec<_>>();
-  if quick_fixes.is_empty() {
+  if fixes.is_empty() {
     return None;
   }
-  // remove any overlapping text changes, we'll circle
-  // back for another pass to fix the remaining
-  quick_fixes.sort_by_key(|change| change.range.start);
-  for i in (1..quick_fixes.len()).rev() {
-    let cur = &quick_fixes[i];
-    let previous = &quick_fixes[i - 1];
-    let is_overlapping = cur.range.start < previous.range.end;
-    if is_overlapping {
-      quick_fixes.remove(i);
+  // skip the fixes that overlap with one that was already accepted, we'll
+  // circle back for another pass to fix the remaining
+  fixes.sort_by_key(|changes| changes[0].range.start);
+  let mut accepted_changes: Vec<deno_ast::TextChange> = Vec::new();
+  let mut fixed_count = 0;
+  for changes in fixes {
+    let is_overlapping = changes.iter().any(|change| {
+      accepted_changes.iter().any(|accepted| {
+        change.range.start < accepted.range.end
+          && accepted.range.start < change.range.end
+      })
+    });
+    if !is_overlapping {
+      accepted_changes.extend(changes);
+      fixed_count += 1;
     }
   }
+  accepted_changes.sort_by_key(|change| change.range.start);
   let new_text =
-    deno_ast::apply_text_changes(text_info.text_str(), quick_fixes);
-  Some(new_text)
+    deno_ast::apply_text_changes(text_info.text_str(), accepted_changes);
+  Some((new_text, fixed_count))
 }
 
 /// Lint stdin and write result to stdout.
@@ -477,29 +617,54 @@ fn apply_lint_fixes(
 /// Compatible with `--json` flag.
 fn lint_stdin(
   file_path: &Path,
-  lint_rules: Vec<&'static dyn LintRule>,
+  linter: &CliLinter,
 ) -> Result<(ParsedSource, Vec<LintDiagnostic>), AnyError> {
   let mut source_code = String::new();
   if stdin().read_to_string(&mut source_code).is_err() {
     return Err(generic_error("Failed to read from stdin"));
   }
 
-  let linter = create_linter(lint_rules);
+  linter.lint_source(
+    specifier_from_file_path(file_path)?,
+    MediaType::TypeScript,
+    source_code,
+  )
+}
 
-  linter
-    .lint_file(LintFileOptions {
-      specifier: specifier_from_file_path(file_path)?,
-      source_code: source_code.clone(),
-      media_type: MediaType::TypeScript,
-    })
-    .map_err(AnyError::from)
+/// The outcome of linting one or more files.
+struct LintSummary {
+  has_errors: bool,
+  warning_count: usize,
+}
+
+impl LintSummary {
+  /// Whether linting succeeded. Reports when the warnings exceed
+  /// `--max-warnings`.
+  fn is_success(&self, max_warnings: Option<usize>) -> bool {
+    let exceeds_max_warnings = match max_warnings {
+      Some(max_warnings) if self.warning_count > max_warnings => {
+        match self.warning_count {
+          1 => {
+            info!("Found 1 warning, exceeding --max-warnings {max_warnings}")
+          }
+          n => {
+            info!("Found {n} warnings, exceeding --max-warnings {max_warnings}")
+          }
+        }
+        true
+      }
+      _ => false,
+    };
+    !self.has_errors && !exceeds_max_warnings
+  }
 }
 
 fn handle_lint_result(
   file_path: &str,
   result: Result<(ParsedSource, Vec<LintDiagnostic>), AnyError>,
+  severity: &HashMap<String, LintSeverity>,
   reporter_lock: Arc<Mutex<Box<dyn LintReporter + Send>>>,
-) -> bool {
+) -> LintSummary {
   let mut reporter = reporter_lock.lock();
 
   match result {
@@ -508,14 +673,30 @@ fn handle_lint_result(
         std::cmp::Ordering::Equal => a.range.start.cmp(&b.range.start),
         file_order => file_order,
       });
+      let mut summary = LintSummary {
+        has_errors: false,
+        warning_count: 0,
+      };
       for d in &file_diagnostics {
-        reporter.visit_diagnostic(LintOrCliDiagnostic::Lint(d));
+        match severity.get(&d.code) {
+          Some(LintSeverity::Warn) => {
+            summary.warning_count += 1;
+            reporter.visit_diagnostic(LintOrCliDiagnostic::LintWarning(d));
+          }
+          _ => {
+            summary.has_errors = true;
+            reporter.visit_diagnostic(LintOrCliDiagnostic::Lint(d));
+          }
+        }
       }
-      file_diagnostics.is_empty()
+      summary
     }
     Err(err) => {
       reporter.visit_error(file_path, &err);
-      false
+      LintSummary {
+        has_errors: true,
+        warning_count: 0,
+      }
     }
   }
 }
@@ -523,20 +704,26 @@ fn handle_lint_result(
 #[derive(Clone, Copy)]
 pub enum LintOrCliDiagnostic<'a> {
   Lint(&'a LintDiagnostic),
+  /// A diagnostic of a rule with its severity set to "warn".
+  LintWarning(&'a LintDiagnostic),
   FastCheck(&'a FastCheckDiagnostic),
 }
 
 impl<'a> LintOrCliDiagnostic<'a> {
   pub fn specifier(&self) -> &ModuleSpecifier {
     match self {
-      LintOrCliDiagnostic::Lint(d) => &d.specifier,
+      LintOrCliDiagnostic::Lint(d) | LintOrCliDiagnostic::LintWarning(d) => {
+        &d.specifier
+      }
       LintOrCliDiagnostic::FastCheck(d) => d.specifier(),
     }
   }
 
   pub fn range(&self) -> Option<(&SourceTextInfo, SourceRange)> {
     match self {
-      LintOrCliDiagnostic::Lint(d) => Some((&d.text_info, d.range)),
+      LintOrCliDiagnostic::Lint(d) | LintOrCliDiagnostic::LintWarning(d) => {
+        Some((&d.text_info, d.range))
+      }
       LintOrCliDiagnostic::FastCheck(d) => {
         d.range().map(|r| (&r.text_info, r.range))
       }
@@ -548,41 +735,54 @@ impl<'a> deno_ast::diagnostics::Diagnostic for LintOrCliDiagnostic<'a> {
   fn level(&self) -> deno_ast::diagnostics::DiagnosticLevel {
     match self {
       LintOrCliDiagnostic::Lint(d) => d.level(),
+      LintOrCliDiagnostic::LintWarning(_) => {
+        deno_ast::diagnostics::DiagnosticLevel::Warning
+      }
       LintOrCliDiagnostic::FastCheck(d) => d.level(),
     }
   }
 
   fn code(&self) -> Cow<'_, str> {
     match self {
-      LintOrCliDiagnostic::Lint(d) => d.code(),
+      LintOrCliDiagnostic::Lint(d) | LintOrCliDiagnostic::LintWarning(d) => {
+        d.code()
+      }
       LintOrCliDiagnostic::FastCheck(_) => Cow::Borrowed("no-slow-types"),
     }
   }
 
   fn message(&self) -> Cow<'_, str> {
     match self {
-      LintOrCliDiagnostic::Lint(d) => d.message(),
+      LintOrCliDiagnostic::Lint(d) | LintOrCliDiagnostic::LintWarning(d) => {
+        d.message()
+      }
       LintOrCliDiagnostic::FastCheck(d) => d.message(),
     }
   }
 
   fn location(&self) -> deno_ast::diagnostics::DiagnosticLocation {
     match self {
-      LintOrCliDiagnostic::Lint(d) => d.location(),
+      LintOrCliDiagnostic::Lint(d) | LintOrCliDiagnostic::LintWarning(d) => {
+        d.location()
+      }
       LintOrCliDiagnostic::FastCheck(d) => d.location(),
     }
   }
 
   fn snippet(&self) -> Option<deno_ast::diagnostics::DiagnosticSnippet<'_>> {
     match self {
-      LintOrCliDiagnostic::Lint(d) => d.snippet(),
+      LintOrCliDiagnostic::Lint(d) | LintOrCliDiagnostic::LintWarning(d) => {
+        d.snippet()
+      }
       LintOrCliDiagnostic::FastCheck(d) => d.snippet(),
     }
   }
 
   fn hint(&self) -> Option<Cow<'_, str>> {
     match self {
-      LintOrCliDiagnostic::Lint(d) => d.hint(),
+      LintOrCliDiagnostic::Lint(d) | LintOrCliDiagnostic::LintWarning(d) => {
+        d.hint()
+      }
       LintOrCliDiagnostic::FastCheck(d) => d.hint(),
     }
   }
@@ -591,21 +791,27 @@ impl<'a> deno_ast::diagnostics::Diagnostic for LintOrCliDiagnostic<'a> {
     &self,
   ) -> Option<deno_ast::diagnostics::DiagnosticSnippet<'_>> {
     match self {
-      LintOrCliDiagnostic::Lint(d) => d.snippet_fixed(),
+      LintOrCliDiagnostic::Lint(d) | LintOrCliDiagnostic::LintWarning(d) => {
+        d.snippet_fixed()
+      }
       LintOrCliDiagnostic::FastCheck(d) => d.snippet_fixed(),
     }
   }
 
   fn info(&self) -> Cow<'_, [Cow<'_, str>]> {
     match self {
-      LintOrCliDiagnostic::Lint(d) => d.info(),
+      LintOrCliDiagnostic::Lint(d) | LintOrCliDiagnostic::LintWarning(d) => {
+        d.info()
+      }
       LintOrCliDiagnostic::FastCheck(d) => d.info(),
     }
   }
 
   fn docs_url(&self) -> Option<Cow<'_, str>> {
     match self {
-      LintOrCliDiagnostic::Lint(d) => d.docs_url(),
+      LintOrCliDiagnostic::Lint(d) | LintOrCliDiagnostic::LintWarning(d) => {
+        d.docs_url()
+      }
       LintOrCliDiagnostic::FastCheck(d) => d.docs_url(),
     }
   }
@@ -614,6 +820,8 @@ impl<'a> deno_ast::diagnostics::Diagnostic for LintOrCliDiagnostic<'a> {
 trait LintReporter {
   fn visit_diagnostic(&mut self, d: LintOrCliDiagnostic);
   fn visit_error(&mut self, file_path: &str, err: &AnyError);
+  /// Called with the number of problems fixed in a file via `--fix`.
+  fn visit_fixes(&mut self, count: usize);
   fn close(&mut self, check_count: usize);
 }
 
@@ -625,14 +833,18 @@ struct LintError {
 
 struct PrettyLintReporter {
   lint_count: u32,
+  warning_count: u32,
   fixable_diagnostics: u32,
+  fixed_count: usize,
 }
 
 impl PrettyLintReporter {
   fn new() -> PrettyLintReporter {
     PrettyLintReporter {
       lint_count: 0,
+      warning_count: 0,
       fixable_diagnostics: 0,
+      fixed_count: 0,
     }
   }
 }
@@ -640,10 +852,16 @@ impl PrettyLintReporter {
 impl LintReporter for PrettyLintReporter {
   fn visit_diagnostic(&mut self, d: LintOrCliDiagnostic) {
     self.lint_count += 1;
-    if let LintOrCliDiagnostic::Lint(d) = d {
-      if !d.fixes.is_empty() {
-        self.fixable_diagnostics += 1;
+    match d {
+      LintOrCliDiagnostic::Lint(d) | LintOrCliDiagnostic::LintWarning(d) => {
+        if !d.fixes.is_empty() {
+          self.fixable_diagnostics += 1;
+        }
       }
+      LintOrCliDiagnostic::FastCheck(_) => {}
+    }
+    if matches!(d, LintOrCliDiagnostic::LintWarning(_)) {
+      self.warning_count += 1;
     }
 
     eprintln!("{}", d.display());
@@ -654,12 +872,25 @@ impl LintReporter for PrettyLintReporter {
     eprintln!("   {err}");
   }
 
+  fn visit_fixes(&mut self, count: usize) {
+    self.fixed_count += count;
+  }
+
   fn close(&mut self, check_count: usize) {
-    let fixable_suffix = if self.fixable_diagnostics > 0 {
-      colors::gray(format!(" ({} fixable via --fix)", self.fixable_diagnostics))
-        .to_string()
-    } else {
+    print_fixed_count(self.fixed_count);
+    let mut details = Vec::new();
+    match self.warning_count {
+      0 => (),
+      1 => details.push("1 warning".to_string()),
+      n => details.push(format!("{n} warnings")),
+    }
+    if self.fixable_diagnostics > 0 {
+      details.push(format!("{} fixable via --fix", self.fixable_diagnostics));
+    }
+    let fixable_suffix = if details.is_empty() {
       "".to_string()
+    } else {
+      colors::gray(format!(" ({})", details.join(", "))).to_string()
     };
     match self.lint_count {
       1 => info!("Found 1 problem{}", fixable_suffix),
@@ -679,11 +910,15 @@ impl LintReporter for PrettyLintReporter {
 
 struct CompactLintReporter {
   lint_count: u32,
+  fixed_count: usize,
 }
 
 impl CompactLintReporter {
   fn new() -> CompactLintReporter {
-    CompactLintReporter { lint_count: 0 }
+    CompactLintReporter {
+      lint_count: 0,
+      fixed_count: 0,
+    }
   }
 }
 
@@ -691,20 +926,32 @@ impl LintReporter for CompactLintReporter {
   fn visit_diagnostic(&mut self, d: LintOrCliDiagnostic) {
     self.lint_count += 1;
 
+    // errors are the default, so only warnings are marked
+    let severity = match d {
+      LintOrCliDiagnostic::LintWarning(_) => "warning: ",
+      _ => "",
+    };
     match d.range() {
       Some((text_info, range)) => {
         let line_and_column = text_info.line_and_column_display(range.start);
         eprintln!(
-          "{}: line {}, col {} - {} ({})",
+          "{}: line {}, col {} - {}{} ({})",
           d.specifier(),
           line_and_column.line_number,
           line_and_column.column_number,
+          severity,
           d.message(),
           d.code(),
         )
       }
       None => {
-        eprintln!("{}: {} ({})", d.specifier(), d.message(), d.code())
+        eprintln!(
+          "{}: {}{} ({})",
+          d.specifier(),
+          severity,
+          d.message(),
+          d.code()
+        )
       }
     }
   }
@@ -714,7 +961,12 @@ impl LintReporter for CompactLintReporter {
     eprintln!("   {err}");
   }
 
+  fn visit_fixes(&mut self, count: usize) {
+    self.fixed_count += count;
+  }
+
   fn close(&mut self, check_count: usize) {
+    print_fixed_count(self.fixed_count);
     match self.lint_count {
       1 => info!("Found 1 problem"),
       n if n > 1 => info!("Found {} problems", self.lint_count),
@@ -729,6 +981,14 @@ impl LintReporter for CompactLintReporter {
   }
 }
 
+fn print_fixed_count(fixed_count: usize) {
+  match fixed_count {
+    0 => (),
+    1 => info!("Fixed 1 problem"),
+    n => info!("Fixed {} problems", n),
+  }
+}
+
 // WARNING: Ensure doesn't change because it's used in the JSON output
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -765,6 +1025,8 @@ struct JsonLintDiagnostic {
   pub message: String,
   pub code: String,
   pub hint: Option<String>,
+  /// Either "error" or "warning".
+  pub severity: &'static str,
 }
 
 #[derive(Serialize)]
@@ -799,6 +1061,10 @@ impl LintReporter for JsonLintReporter {
       message: d.message().to_string(),
       code: d.code().to_string(),
       hint: d.hint().map(|h| h.to_string()),
+      severity: match d {
+        LintOrCliDiagnostic::LintWarning(_) => "warning",
+        _ => "error",
+      },
     });
   }
 
@@ -809,6 +1075,10 @@ impl LintReporter for JsonLintReporter {
     });
   }
 
+  fn visit_fixes(&mut self, _count: usize) {
+    // not part of the JSON output, the remaining diagnostics are
+  }
+
   fn close(&mut self, _check_count: usize) {
     sort_diagnostics(&mut self.diagnostics);
     let json = serde_json::to_string_pretty(&self);
@@ -859,6 +1129,27 @@ pub struct ConfiguredRules {
   pub rules: Vec<&'static dyn LintRule>,
   // cli specific rules
   pub no_slow_types: bool,
+  /// Selects the plugin rules the same way as the built-in rules were.
+  pub selection: LintRuleSelection,
+}
+
+/// The rules selected by the `tags`, `include` and `exclude` of the `rules`
+/// config.
+#[derive(Debug, Clone, Default)]
+pub struct LintRuleSelection {
+  tags: Vec<String>,
+  include: Vec<String>,
+  exclude: Vec<String>,
+}
+
+impl LintRuleSelection {
+  /// Whether the rule with `code` and `tags` is selected, following
+  /// `deno_lint::rules::get_filtered_rules()`.
+  pub fn includes(&self, code: &str, tags: &[String]) -> bool {
+    (tags.iter().any(|tag| self.tags.contains(tag))
+      || self.include.iter().any(|c| c == code))
+      && !self.exclude.iter().any(|c| c == code)
+  }
 }
 
 impl Default for ConfiguredRules {
@@ -894,26 +1185,32 @@ pub fn get_configured_rules(
       .as_ref()
       .map(|exclude| exclude.iter().any(|i| i == NO_SLOW_TYPES_NAME))
       .unwrap_or(false);
-  let rules = rules::get_filtered_rules(
-    rules
+  let selection = LintRuleSelection {
+    tags: rules
       .tags
-      .or_else(|| Some(get_default_tags(maybe_config_file))),
-    rules.exclude.map(|exclude| {
-      exclude
-        .into_iter()
-        .filter(|c| c != NO_SLOW_TYPES_NAME)
-        .collect()
-    }),
-    rules.include.map(|include| {
-      include
-        .into_iter()
-        .filter(|c| c != NO_SLOW_TYPES_NAME)
-        .collect()
-    }),
+      .unwrap_or_else(|| get_default_tags(maybe_config_file)),
+    include: rules
+      .include
+      .unwrap_or_default()
+      .into_iter()
+      .filter(|c| c != NO_SLOW_TYPES_NAME)
+      .collect(),
+    exclude: rules
+      .exclude
+      .unwrap_or_default()
+      .into_iter()
+      .filter(|c| c != NO_SLOW_TYPES_NAME)
+      .collect(),
+  };
+  let rules = rules::get_filtered_rules(
+    Some(selection.tags.clone()),
+    Some(selection.exclude.clone()),
+    Some(selection.include.clone()),
   );
   ConfiguredRules {
     rules,
     no_slow_types,
+    selection,
   }
 }
 
@@ -0,0 +1,163 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// Runs the lint rules of the plugins declared under "lint.plugins" in the
+// config file. A plugin module default exports an object like:
+//
+//   export default {
+//     name: "my-plugin",
+//     rules: {
+//       "no-foo": {
+//         create(context) {
+//           return {
+//             Ident(node) {
+//               if (node.text === "foo") {
+//                 context.report({ node, message: "Don't use foo." });
+//               }
+//             },
+//           };
+//         },
+//       },
+//     },
+//   };
+//
+// Like the built-in rules, the rules run are selected with the "tags",
+// "include" and "exclude" of the "rules" config. A rule can list its `tags`
+// next to `create`, which default to `["recommended"]`.
+//
+// Visitors are called in source order with the nodes of the matching type,
+// which are named after the SWC AST (e.g. "Module", "CallExpr", "Ident").
+// Each node has a `type`, a `range` of UTF-8 byte offsets into the file, its
+// `parent` and either its `children` or, for leaf nodes, its `text`.
+//
+// A reported problem can include a `fix`, one or more `{ range, text }`
+// changes that replace the text in `range`, which `deno lint --fix` and the
+// LSP's quick fixes apply.
+
+(() => {
+  /** @type {{ code: string, tags: string[], create: (context: object) => object }[]} */
+  let rules = [];
+
+  /** @type {string[]} */
+  let pendingSpecifiers = [];
+
+  /** @param {string[]} specifiers */
+  function preparePlugins(specifiers) {
+    pendingSpecifiers = specifiers;
+  }
+
+  /** @returns {Promise<{ code: string, tags: string[] }[]>} the registered rules */
+  async function loadPlugins() {
+    for (const specifier of pendingSpecifiers) {
+      const module = await import(specifier);
+      registerPlugin(module.default, specifier);
+    }
+    return rules.map(({ code, tags }) => ({ code, tags }));
+  }
+
+  /** @param {string[]} codes codes of the rules selected by the config */
+  function selectRules(codes) {
+    rules = rules.filter((rule) => codes.includes(rule.code));
+  }
+
+  /**
+   * @param {any} plugin the default export of the plugin module
+   * @param {string} specifier
+   */
+  function registerPlugin(plugin, specifier) {
+    if (plugin === null || typeof plugin !== "object") {
+      throw new TypeError(
+        `Lint plugin ${specifier} must default export a plugin object.`,
+      );
+    }
+    const { name } = plugin;
+    if (typeof name !== "string" || name.length === 0) {
+      throw new TypeError(`Lint plugin ${specifier} must have a "name".`);
+    }
+    for (const [ruleName, rule] of Object.entries(plugin.rules ?? {})) {
+      const code = `${name}/${ruleName}`;
+      if (typeof rule?.create !== "function") {
+        throw new TypeError(`Lint rule "${code}" must have a "create" method.`);
+      }
+      const tags = rule.tags ?? ["recommended"];
+      if (
+        !Array.isArray(tags) || !tags.every((tag) => typeof tag === "string")
+      ) {
+        throw new TypeError(
+          `Lint rule "${code}" must have an array of strings as "tags".`,
+        );
+      }
+      rules.push({ code, tags, create: rule.create });
+    }
+  }
+
+  function isRange(range) {
+    return Array.isArray(range) && range.length === 2 &&
+      Number.isInteger(range[0]) && Number.isInteger(range[1]);
+  }
+
+  /**
+   * @param {string} fileName
+   * @param {string} astJson
+   * @returns {{ code: string, message: string, hint: string | null, range: [number, number], fix: { range: [number, number], text: string }[] }[]}
+   */
+  function lintFile(fileName, astJson) {
+    const program = JSON.parse(astJson);
+    program.parent = null;
+    // flatten the tree in source order, linking each node to its parent
+    const nodes = [];
+    const stack = [program];
+    while (stack.length > 0) {
+      const node = stack.pop();
+      nodes.push(node);
+      const children = node.children ?? [];
+      for (let i = children.length - 1; i >= 0; i--) {
+        children[i].parent = node;
+        stack.push(children[i]);
+      }
+    }
+
+    const diagnostics = [];
+    for (const { code, create } of rules) {
+      const context = {
+        id: code,
+        fileName,
+        report({ node, range, message, hint, fix }) {
+          range ??= node?.range;
+          if (!isRange(range)) {
+            throw new TypeError(
+              `Lint rule "${code}" reported a problem without a node or range.`,
+            );
+          }
+          const changes = fix == null ? [] : [fix].flat();
+          for (const change of changes) {
+            if (!isRange(change?.range) || typeof change.text !== "string") {
+              throw new TypeError(
+                `Lint rule "${code}" reported a fix without a range and text.`,
+              );
+            }
+          }
+          diagnostics.push({
+            code,
+            message: String(message),
+            hint: hint == null ? null : String(hint),
+            range: [range[0], range[1]],
+            fix: changes.map((change) => ({
+              range: [change.range[0], change.range[1]],
+              text: change.text,
+            })),
+          });
+        },
+      };
+      const visitors = create(context) ?? {};
+      for (const node of nodes) {
+        const visitor = visitors[node.type];
+        if (typeof visitor === "function") {
+          visitor(node);
+        }
+      }
+    }
+    return diagnostics;
+  }
+
+  return [preparePlugins, loadPlugins, selectRules, lintFile];
+})();
@@ -0,0 +1,492 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Support for lint rules written in JavaScript or TypeScript, declared in
+//! the `lint` config, e.g. `"plugins": ["./lint/my_plugin.ts"]`. See
+//! `plugins.js` for the plugin interface.
+
+use super::suppressions::LintSuppressions;
+use super::LintRuleSelection;
+use crate::cache::FastInsecureHasher;
+use deno_ast::view::NodeTrait;
+use deno_ast::EmitOptions;
+use deno_ast::MediaType;
+use deno_ast::ModuleSpecifier;
+use deno_ast::ParseParams;
+use deno_ast::ParsedSource;
+use deno_ast::SourceRange;
+use deno_ast::SourceRanged;
+use deno_ast::SourceTextInfo;
+use deno_ast::TranspileOptions;
+use deno_core::anyhow::anyhow;
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::error::JsError;
+use deno_core::serde_json;
+use deno_core::serde_json::json;
+use deno_core::serde_v8;
+use deno_core::v8;
+use deno_core::JsRuntime;
+use deno_core::ModuleCodeString;
+use deno_core::PollEventLoopOptions;
+use deno_core::RuntimeOptions;
+use deno_core::StaticModuleLoader;
+use deno_lint::diagnostic::LintDiagnostic;
+use deno_lint::diagnostic::LintFix;
+use deno_lint::diagnostic::LintFixChange;
+use deno_runtime::tokio_util::create_basic_runtime;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use std::fmt::Write;
+use std::rc::Rc;
+use std::sync::mpsc;
+use std::sync::Arc;
+
+/// The loaded lint plugins. Their rules run in a V8 isolate that lives on a
+/// dedicated thread, which lints one file at a time.
+#[derive(Clone, Debug, Default)]
+pub struct LintPlugins(Option<Arc<LintPluginsInner>>);
+
+#[derive(Debug)]
+struct LintPluginsInner {
+  /// The codes of all plugin rules, including the ones that aren't selected.
+  codes: Vec<String>,
+  selected_codes: Vec<String>,
+  /// Hash of the plugin sources and the selected rules, used to bust the
+  /// incremental cache.
+  source_hash: u64,
+  requests: mpsc::Sender<LintRequest>,
+}
+
+#[derive(Debug)]
+struct LintRequest {
+  specifier: ModuleSpecifier,
+  ast_json: String,
+  response: mpsc::Sender<Result<Vec<PluginDiagnostic>, AnyError>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginDiagnostic {
+  code: String,
+  message: String,
+  hint: Option<String>,
+  /// Byte offsets into the file.
+  range: (usize, usize),
+  #[serde(default)]
+  fix: Vec<PluginFixChange>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginRule {
+  code: String,
+  tags: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginFixChange {
+  range: (usize, usize),
+  text: String,
+}
+
+impl LintPlugins {
+  /// Loads the plugin modules at `urls`. Only their rules included in
+  /// `selection` are run.
+  pub fn load(
+    urls: &[ModuleSpecifier],
+    selection: &LintRuleSelection,
+  ) -> Result<Self, AnyError> {
+    if urls.is_empty() {
+      return Ok(Self::default());
+    }
+    let mut hasher = FastInsecureHasher::new();
+    let mut modules = Vec::with_capacity(urls.len());
+    for url in urls {
+      let code = load_plugin_module(url)
+        .with_context(|| format!("Failed loading lint plugin {url}"))?;
+      hasher.write_str(url.as_str()).write_str(&code);
+      modules.push((url.clone(), code));
+    }
+    let selection = selection.clone();
+
+    let (requests, requests_rx) = mpsc::channel::<LintRequest>();
+    let (loaded_tx, loaded_rx) = mpsc::channel();
+    std::thread::spawn(move || {
+      let tokio_runtime = create_basic_runtime();
+      let result =
+        tokio_runtime.block_on(PluginRuntime::new(modules, &selection));
+      let mut runtime = match result {
+        Ok((runtime, codes, selected_codes)) => {
+          let _ = loaded_tx.send(Ok((codes, selected_codes)));
+          runtime
+        }
+        Err(err) => {
+          let _ = loaded_tx.send(Err(err));
+          return;
+        }
+      };
+      // runs until all handles to the plugins are dropped
+      for request in requests_rx {
+        let result = runtime.lint(&request.specifier, request.ast_json);
+        let _ = request.response.send(result);
+      }
+    });
+    let (codes, selected_codes) = loaded_rx
+      .recv()
+      .map_err(|_| anyhow!("Lint plugin thread exited unexpectedly"))??;
+    for code in &selected_codes {
+      hasher.write_str(code);
+    }
+    Ok(Self(Some(Arc::new(LintPluginsInner {
+      codes,
+      selected_codes,
+      source_hash: hasher.finish(),
+      requests,
+    }))))
+  }
+
+  /// State that changes whenever the plugins do, for the incremental cache.
+  pub fn incremental_cache_state(&self) -> Option<u64> {
+    self.0.as_ref().map(|inner| inner.source_hash)
+  }
+
+  /// Runs the plugin rules on `parsed_source` and adds their diagnostics to
+  /// the ones of the built-in rules, leaving out those suppressed with
+  /// `deno-lint-ignore` comments.
+  pub fn lint(
+    &self,
+    parsed_source: &ParsedSource,
+    diagnostics: &mut Vec<LintDiagnostic>,
+  ) -> Result<(), AnyError> {
+    let Some(inner) = &self.0 else {
+      return Ok(());
+    };
+    // deno_lint doesn't know about the plugin rules, so don't report ignore
+    // comments for them as unknown or unused
+    diagnostics.retain(|d| {
+      !ignore_comment_code(d)
+        .is_some_and(|code| inner.codes.iter().any(|c| c == code))
+    });
+    if inner.selected_codes.is_empty() {
+      return Ok(());
+    }
+    let text_info = parsed_source.text_info();
+    let ignores = LintSuppressions::new(parsed_source);
+    if ignores.is_file_ignored_for_all() {
+      return Ok(());
+    }
+    let ast_json = parsed_source
+      .with_view(|program| serialize_node(program.as_node(), text_info));
+    let (response, response_rx) = mpsc::channel();
+    let request = LintRequest {
+      specifier: parsed_source.specifier().clone(),
+      ast_json,
+      response,
+    };
+    if inner.requests.send(request).is_err() {
+      bail!("Lint plugin thread has exited");
+    }
+    let plugin_diagnostics = response_rx
+      .recv()
+      .map_err(|_| anyhow!("Lint plugin thread exited unexpectedly"))??;
+
+    for diagnostic in plugin_diagnostics {
+      let range =
+        to_source_range(diagnostic.range, &diagnostic.code, text_info)?;
+      if ignores.is_ignored(&diagnostic.code, range, text_info) {
+        continue;
+      }
+      let mut fixes = Vec::new();
+      if !diagnostic.fix.is_empty() {
+        let changes = diagnostic
+          .fix
+          .into_iter()
+          .map(|change| {
+            Ok(LintFixChange {
+              new_text: change.text.into(),
+              range: to_source_range(
+                change.range,
+                &diagnostic.code,
+                text_info,
+              )?,
+            })
+          })
+          .collect::<Result<Vec<_>, AnyError>>()?;
+        fixes.push(LintFix {
+          description: format!("Fix this {} problem", diagnostic.code).into(),
+          changes,
+        });
+      }
+      diagnostics.push(LintDiagnostic {
+        specifier: parsed_source.specifier().clone(),
+        range,
+        text_info: text_info.clone(),
+        message: diagnostic.message,
+        code: diagnostic.code,
+        hint: diagnostic.hint,
+        fixes,
+        custom_docs_url: None,
+        info: Vec::new(),
+      });
+    }
+    Ok(())
+  }
+}
+
+/// The code of the ignore comment that a `ban-unknown-rule-code` or
+/// `ban-unused-ignore` diagnostic of deno_lint is about.
+fn ignore_comment_code(diagnostic: &LintDiagnostic) -> Option<&str> {
+  let message = diagnostic.message.as_str();
+  match diagnostic.code.as_str() {
+    "ban-unknown-rule-code" => message
+      .strip_prefix("Unknown rule for code \"")?
+      .strip_suffix('"'),
+    "ban-unused-ignore" => message
+      .strip_prefix("Ignore for code \"")?
+      .strip_suffix("\" was not used."),
+    _ => None,
+  }
+}
+
+fn to_source_range(
+  (start, end): (usize, usize),
+  code: &str,
+  text_info: &SourceTextInfo,
+) -> Result<SourceRange, AnyError> {
+  let text = text_info.text_str();
+  if start > end
+    || end > text.len()
+    || !text.is_char_boundary(start)
+    || !text.is_char_boundary(end)
+  {
+    bail!(
+      "Lint rule \"{}\" reported an invalid range: [{}, {}]",
+      code,
+      start,
+      end
+    );
+  }
+  let file_start = text_info.range().start;
+  Ok(SourceRange::new(file_start + start, file_start + end))
+}
+
+/// Reads and transpiles a plugin module. Plugins are loaded from disk and
+/// must not import other modules.
+fn load_plugin_module(url: &ModuleSpecifier) -> Result<String, AnyError> {
+  let Ok(path) = url.to_file_path() else {
+    bail!("Lint plugins must be local files");
+  };
+  let media_type = MediaType::from_specifier(url);
+  let text = std::fs::read_to_string(path)?;
+  match media_type {
+    MediaType::JavaScript | MediaType::Mjs => Ok(text),
+    MediaType::TypeScript | MediaType::Mts => {
+      let parsed_source = deno_ast::parse_module(ParseParams {
+        specifier: url.clone(),
+        text_info: SourceTextInfo::new(text.into()),
+        media_type,
+        capture_tokens: false,
+        scope_analysis: false,
+        maybe_syntax: None,
+      })?;
+      let transpiled_source = parsed_source
+        .transpile(
+          &TranspileOptions::default(),
+          &EmitOptions {
+            source_map: deno_ast::SourceMapOption::None,
+            ..Default::default()
+          },
+        )?
+        .into_source();
+      Ok(transpiled_source.text)
+    }
+    _ => bail!("Unsupported media type {}", media_type),
+  }
+}
+
+/// Serializes the AST to the JSON format described in `plugins.js`. The
+/// nodes are visited with an explicit stack rather than recursively, since
+/// deeply nested code would overflow the stack otherwise.
+fn serialize_node(
+  node: deno_ast::view::Node,
+  text_info: &SourceTextInfo,
+) -> String {
+  enum Step<'a> {
+    /// Writes the node, which is preceded by a comma unless it's the first
+    /// child of its parent.
+    Open(deno_ast::view::Node<'a>, bool),
+    /// Closes the children of a node.
+    Close,
+  }
+
+  let file_start = text_info.range().start;
+  let mut output = String::new();
+  let mut stack = vec![Step::Open(node, true)];
+  while let Some(step) = stack.pop() {
+    let (node, is_first) = match step {
+      Step::Open(node, is_first) => (node, is_first),
+      Step::Close => {
+        output.push_str("]}");
+        continue;
+      }
+    };
+    if !is_first {
+      output.push(',');
+    }
+    let start = node.start().as_byte_index(file_start);
+    let end = node.end().as_byte_index(file_start);
+    // the strings can't fail to serialize
+    let kind = serde_json::to_string(&node.kind().to_string()).unwrap();
+    write!(output, r#"{{"type":{kind},"range":[{start},{end}]"#).unwrap();
+    let children = node.children();
+    if children.is_empty() {
+      let text = serde_json::to_string(&text_info.text_str()[start..end]);
+      write!(output, r#","text":{}}}"#, text.unwrap()).unwrap();
+    } else {
+      output.push_str(r#","children":["#);
+      stack.push(Step::Close);
+      stack.extend(
+        children
+          .into_iter()
+          .enumerate()
+          .rev()
+          .map(|(i, child)| Step::Open(child, i == 0)),
+      );
+    }
+  }
+  output
+}
+
+/// The isolate running the plugins, see `plugins.js`.
+struct PluginRuntime {
+  js_runtime: JsRuntime,
+  lint_fn: v8::Global<v8::Function>,
+}
+
+impl PluginRuntime {
+  /// Loads the plugins, returning the codes of all their rules and of the
+  /// ones selected to run.
+  async fn new(
+    modules: Vec<(ModuleSpecifier, String)>,
+    selection: &LintRuleSelection,
+  ) -> Result<(Self, Vec<String>, Vec<String>), AnyError> {
+    let specifiers = modules
+      .iter()
+      .map(|(specifier, _)| specifier.to_string())
+      .collect::<Vec<_>>();
+    let module_loader = StaticModuleLoader::new(
+      modules
+        .into_iter()
+        .map(|(specifier, code)| (specifier, ModuleCodeString::from(code))),
+    );
+    let mut js_runtime = JsRuntime::new(RuntimeOptions {
+      module_loader: Some(Rc::new(module_loader)),
+      ..Default::default()
+    });
+    let functions = js_runtime.execute_script(
+      "ext:cli/lint_plugins.js",
+      ModuleCodeString::from_static(include_str!("plugins.js")),
+    )?;
+    let (prepare_fn, load_fn, select_fn, lint_fn) = {
+      let scope = &mut js_runtime.handle_scope();
+      let functions = v8::Local::new(scope, functions);
+      let functions = v8::Local::<v8::Array>::try_from(functions)?;
+      let mut get_fn = |index| -> Result<_, AnyError> {
+        let function = functions.get_index(scope, index).unwrap();
+        let function = v8::Local::<v8::Function>::try_from(function)?;
+        Ok(v8::Global::new(scope, function))
+      };
+      (get_fn(0)?, get_fn(1)?, get_fn(2)?, get_fn(3)?)
+    };
+    let mut runtime = Self {
+      js_runtime,
+      lint_fn,
+    };
+    runtime.call::<()>(&prepare_fn, vec![json!(specifiers)])?;
+
+    let call = runtime.js_runtime.call(&load_fn);
+    let rules = runtime
+      .js_runtime
+      .with_event_loop_promise(call, PollEventLoopOptions::default())
+      .await?;
+    let rules = {
+      let scope = &mut runtime.js_runtime.handle_scope();
+      let rules = v8::Local::new(scope, rules);
+      serde_v8::from_v8::<Vec<PluginRule>>(scope, rules)?
+    };
+    let selected_codes = rules
+      .iter()
+      .filter(|rule| selection.includes(&rule.code, &rule.tags))
+      .map(|rule| rule.code.clone())
+      .collect::<Vec<_>>();
+    runtime.call::<()>(&select_fn, vec![json!(selected_codes)])?;
+    let codes = rules.into_iter().map(|rule| rule.code).collect();
+    Ok((runtime, codes, selected_codes))
+  }
+
+  fn lint(
+    &mut self,
+    specifier: &ModuleSpecifier,
+    ast_json: String,
+  ) -> Result<Vec<PluginDiagnostic>, AnyError> {
+    let lint_fn = self.lint_fn.clone();
+    self.call(
+      &lint_fn,
+      vec![
+        json!(specifier.as_str()),
+        serde_json::Value::String(ast_json),
+      ],
+    )
+  }
+
+  fn call<T: DeserializeOwned>(
+    &mut self,
+    function: &v8::Global<v8::Function>,
+    args: Vec<serde_json::Value>,
+  ) -> Result<T, AnyError> {
+    let scope = &mut self.js_runtime.handle_scope();
+    let tc_scope = &mut v8::TryCatch::new(scope);
+    let function = v8::Local::new(tc_scope, function);
+    let undefined = v8::undefined(tc_scope).into();
+    let mut v8_args = Vec::with_capacity(args.len());
+    for arg in args {
+      v8_args.push(serde_v8::to_v8(tc_scope, arg)?);
+    }
+    let result = function.call(tc_scope, undefined, &v8_args);
+    if let Some(exception) = tc_scope.exception() {
+      return Err(JsError::from_v8_exception(tc_scope, exception).into());
+    }
+    let result = result.ok_or_else(|| anyhow!("Lint plugin failed"))?;
+    Ok(serde_v8::from_v8(tc_scope, result)?)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn test_serialize_node() {
+    let parsed_source = deno_ast::parse_module(ParseParams {
+      specifier: ModuleSpecifier::parse("file:///mod.ts").unwrap(),
+      text_info: SourceTextInfo::from_string("f(\"a\");".to_string()),
+      media_type: MediaType::TypeScript,
+      capture_tokens: true,
+      scope_analysis: false,
+      maybe_syntax: None,
+    })
+    .unwrap();
+    let ast_json = parsed_source.with_view(|program| {
+      serialize_node(program.as_node(), parsed_source.text_info())
+    });
+    let ast: serde_json::Value = serde_json::from_str(&ast_json).unwrap();
+    assert_eq!(ast["type"], "Module");
+    assert_eq!(ast["range"], json!([0, 7]));
+    let call = &ast["children"][0]["children"][0];
+    assert_eq!(call["type"], "CallExpr");
+    assert_eq!(
+      call["children"][0],
+      json!({ "type": "Ident", "range": [0, 1], "text": "f" })
+    );
+    assert_eq!(call["children"][1]["children"][0]["text"], "\"a\"");
+  }
+}
@@ -0,0 +1,461 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Reports lint diagnostics in the [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! format, which is understood by GitHub code scanning and other static
+//! analysis dashboards.
+
+use std::collections::HashMap;
+
+use deno_ast::diagnostics::Diagnostic;
+use deno_ast::diagnostics::DiagnosticLevel;
+use deno_ast::ModuleSpecifier;
+use deno_ast::SourceRange;
+use deno_ast::SourceTextInfo;
+use deno_core::error::AnyError;
+use deno_core::serde_json;
+use deno_core::url::Url;
+use deno_lint::rules;
+use serde::Serialize;
+
+use super::LintOrCliDiagnostic;
+use super::LintReporter;
+use crate::cache::FastInsecureHasher;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SRC_ROOT: &str = "%SRCROOT%";
+
+// WARNING: Ensure the structs below don't change other than to follow the
+// SARIF specification because they're used in the SARIF output.
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLog {
+  #[serde(rename = "$schema")]
+  schema: &'static str,
+  version: &'static str,
+  runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRun {
+  tool: SarifTool,
+  invocations: Vec<SarifInvocation>,
+  original_uri_base_ids: HashMap<&'static str, SarifArtifactLocation>,
+  column_kind: &'static str,
+  results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct SarifTool {
+  driver: SarifToolComponent,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifToolComponent {
+  name: &'static str,
+  semantic_version: &'static str,
+  information_uri: &'static str,
+  rules: Vec<SarifRule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+  id: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  short_description: Option<SarifMessage>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  help_uri: Option<String>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  properties: Option<SarifRuleProperties>,
+}
+
+#[derive(Serialize)]
+struct SarifRuleProperties {
+  tags: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifInvocation {
+  execution_successful: bool,
+  tool_execution_notifications: Vec<SarifNotification>,
+}
+
+#[derive(Serialize)]
+struct SarifNotification {
+  level: &'static str,
+  message: SarifMessage,
+  locations: Vec<SarifLocation>,
+}
+
+#[derive(Serialize)]
+struct SarifMessage {
+  text: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+  rule_id: String,
+  rule_index: usize,
+  level: &'static str,
+  message: SarifMessage,
+  locations: Vec<SarifLocation>,
+  #[serde(skip_serializing_if = "HashMap::is_empty")]
+  partial_fingerprints: HashMap<&'static str, String>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  fixes: Vec<SarifFix>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+  physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+  artifact_location: SarifArtifactLocation,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  region: Option<SarifRegion>,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifArtifactLocation {
+  uri: String,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  uri_base_id: Option<&'static str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+  start_line: usize,
+  start_column: usize,
+  end_line: usize,
+  end_column: usize,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  snippet: Option<SarifMessage>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifFix {
+  description: SarifMessage,
+  artifact_changes: Vec<SarifArtifactChange>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifArtifactChange {
+  artifact_location: SarifArtifactLocation,
+  replacements: Vec<SarifReplacement>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifReplacement {
+  deleted_region: SarifRegion,
+  inserted_content: SarifMessage,
+}
+
+pub struct SarifLintReporter {
+  root_url: Url,
+  rules: Vec<SarifRule>,
+  rule_indexes: HashMap<String, usize>,
+  results: Vec<(ModuleSpecifier, SourceRange, SarifResult)>,
+  /// The number of results with the same fingerprint hash in a file, used to
+  /// tell apart problems on identical lines.
+  fingerprint_counts: HashMap<(ModuleSpecifier, u64), usize>,
+  notifications: Vec<SarifNotification>,
+}
+
+impl SarifLintReporter {
+  /// Creates a reporter that reports the locations of files relative to
+  /// the directory of `root_url`.
+  pub fn new(root_url: Url) -> Self {
+    Self {
+      root_url,
+      rules: Vec::new(),
+      rule_indexes: HashMap::new(),
+      results: Vec::new(),
+      fingerprint_counts: HashMap::new(),
+      notifications: Vec::new(),
+    }
+  }
+
+  fn artifact_location(&self, specifier: &Url) -> SarifArtifactLocation {
+    if specifier.scheme() == "file" {
+      if let Some(relative) = self.root_url.make_relative(specifier) {
+        if !relative.starts_with("../") {
+          return SarifArtifactLocation {
+            uri: relative,
+            uri_base_id: Some(SRC_ROOT),
+          };
+        }
+      }
+    }
+    SarifArtifactLocation {
+      uri: specifier.to_string(),
+      uri_base_id: None,
+    }
+  }
+
+  fn rule_index(&mut self, d: &LintOrCliDiagnostic) -> usize {
+    let code = d.code();
+    if let Some(index) = self.rule_indexes.get(code.as_ref()) {
+      return *index;
+    }
+    let builtin_rule = rules::get_all_rules()
+      .into_iter()
+      .find(|rule| rule.code() == code);
+    let index = self.rules.len();
+    self.rules.push(SarifRule {
+      id: code.to_string(),
+      short_description: builtin_rule
+        .and_then(|rule| docs_summary(rule.docs()))
+        .map(|text| SarifMessage { text }),
+      help_uri: d.docs_url().map(|url| url.to_string()),
+      properties: builtin_rule.filter(|rule| !rule.tags().is_empty()).map(
+        |rule| SarifRuleProperties {
+          tags: rule.tags().to_vec(),
+        },
+      ),
+    });
+    self.rule_indexes.insert(code.to_string(), index);
+    index
+  }
+
+  fn fingerprint(
+    &mut self,
+    specifier: &ModuleSpecifier,
+    code: &str,
+    text_info: &SourceTextInfo,
+    range: SourceRange,
+  ) -> String {
+    let line_index = text_info.line_index(range.start);
+    let hash = FastInsecureHasher::new()
+      .write_str(code)
+      .write_str(text_info.line_text(line_index).trim())
+      .finish();
+    let count = self
+      .fingerprint_counts
+      .entry((specifier.clone(), hash))
+      .or_default();
+    *count += 1;
+    format!("{:016x}:{}", hash, count)
+  }
+}
+
+impl LintReporter for SarifLintReporter {
+  fn visit_diagnostic(&mut self, d: LintOrCliDiagnostic) {
+    let rule_index = self.rule_index(&d);
+    let specifier = d.specifier().clone();
+    let artifact_location = self.artifact_location(&specifier);
+    let code = d.code().to_string();
+    let mut message = d.message().to_string();
+    if let Some(hint) = d.hint() {
+      message.push_str("\n\n");
+      message.push_str(&hint);
+    }
+    let mut partial_fingerprints = HashMap::new();
+    let mut region = None;
+    let mut source_range = None;
+    if let Some((text_info, range)) = d.range() {
+      partial_fingerprints.insert(
+        "primaryLocationLineHash",
+        self.fingerprint(&specifier, &code, text_info, range),
+      );
+      region = Some(to_region(text_info, range, true));
+      source_range = Some(range);
+    }
+    let fixes = match d {
+      LintOrCliDiagnostic::Lint(lint_diagnostic)
+      | LintOrCliDiagnostic::LintWarning(lint_diagnostic) => lint_diagnostic
+        .fixes
+        .iter()
+        .map(|fix| SarifFix {
+          description: SarifMessage {
+            text: fix.description.to_string(),
+          },
+          artifact_changes: vec![SarifArtifactChange {
+            artifact_location: artifact_location.clone(),
+            replacements: fix
+              .changes
+              .iter()
+              .map(|change| SarifReplacement {
+                deleted_region: to_region(
+                  &lint_diagnostic.text_info,
+                  change.range,
+                  false,
+                ),
+                inserted_content: SarifMessage {
+                  text: change.new_text.to_string(),
+                },
+              })
+              .collect(),
+          }],
+        })
+        .collect(),
+      LintOrCliDiagnostic::FastCheck(_) => Vec::new(),
+    };
+    let result = SarifResult {
+      rule_id: code,
+      rule_index,
+      level: match d.level() {
+        DiagnosticLevel::Error => "error",
+        DiagnosticLevel::Warning => "warning",
+      },
+      message: SarifMessage { text: message },
+      locations: vec![SarifLocation {
+        physical_location: SarifPhysicalLocation {
+          artifact_location,
+          region,
+        },
+      }],
+      partial_fingerprints,
+      fixes,
+    };
+    self.results.push((
+      specifier,
+      source_range.unwrap_or_else(|| SourceRange::new(0.into(), 0.into())),
+      result,
+    ));
+  }
+
+  fn visit_error(&mut self, file_path: &str, err: &AnyError) {
+    let locations = ModuleSpecifier::from_file_path(file_path)
+      .map(|specifier| {
+        vec![SarifLocation {
+          physical_location: SarifPhysicalLocation {
+            artifact_location: self.artifact_location(&specifier),
+            region: None,
+          },
+        }]
+      })
+      .unwrap_or_default();
+    self.notifications.push(SarifNotification {
+      level: "error",
+      message: SarifMessage {
+        text: err.to_string(),
+      },
+      locations,
+    });
+  }
+
+  fn visit_fixes(&mut self, _count: usize) {
+    // not part of the SARIF output, the remaining problems are
+  }
+
+  fn close(&mut self, _check_count: usize) {
+    // sort so that the output is deterministic
+    self.results.sort_by(
+      |(a_specifier, a_range, _), (b_specifier, b_range, _)| {
+        a_specifier
+          .cmp(b_specifier)
+          .then_with(|| a_range.start.cmp(&b_range.start))
+      },
+    );
+    let log = SarifLog {
+      schema: SARIF_SCHEMA,
+      version: "2.1.0",
+      runs: vec![SarifRun {
+        tool: SarifTool {
+          driver: SarifToolComponent {
+            name: "deno lint",
+            semantic_version: crate::version::deno(),
+            information_uri: "https://lint.deno.land",
+            rules: std::mem::take(&mut self.rules),
+          },
+        },
+        invocations: vec![SarifInvocation {
+          execution_successful: self.notifications.is_empty(),
+          tool_execution_notifications: std::mem::take(&mut self.notifications),
+        }],
+        original_uri_base_ids: HashMap::from([(
+          SRC_ROOT,
+          SarifArtifactLocation {
+            uri: self.root_url.to_string(),
+            uri_base_id: None,
+          },
+        )]),
+        column_kind: "unicodeCodePoints",
+        results: std::mem::take(&mut self.results)
+          .into_iter()
+          .map(|(_, _, result)| result)
+          .collect(),
+      }],
+    };
+    let json = serde_json::to_string_pretty(&log);
+    println!("{}", json.unwrap());
+  }
+}
+
+/// Converts a range to a SARIF region, which has one based lines and columns
+/// and an exclusive end column.
+fn to_region(
+  text_info: &SourceTextInfo,
+  range: SourceRange,
+  include_snippet: bool,
+) -> SarifRegion {
+  let start = text_info.line_and_column_display(range.start);
+  let end = text_info.line_and_column_display(range.end);
+  SarifRegion {
+    start_line: start.line_number,
+    start_column: start.column_number,
+    end_line: end.line_number,
+    end_column: end.column_number,
+    snippet: include_snippet.then(|| SarifMessage {
+      text: text_info.range_text(&range).to_string(),
+    }),
+  }
+}
+
+/// Gets the first paragraph of the markdown docs of a rule.
+fn docs_summary(docs: &str) -> Option<String> {
+  let summary = docs
+    .trim()
+    .lines()
+    .take_while(|line| !line.trim().is_empty())
+    .map(|line| line.trim())
+    .collect::<Vec<_>>()
+    .join(" ");
+  if summary.is_empty() {
+    None
+  } else {
+    Some(summary)
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn docs_summary_first_paragraph() {
+    assert_eq!(
+      docs_summary("\nDisallows `var`.\nUse `let`.\n\n### Invalid:\n"),
+      Some("Disallows `var`. Use `let`.".to_string())
+    );
+    assert_eq!(docs_summary("  \n"), None);
+  }
+
+  #[test]
+  fn artifact_location_relative_to_root() {
+    let reporter =
+      SarifLintReporter::new(Url::parse("file:///project/").unwrap());
+    let location = reporter
+      .artifact_location(&Url::parse("file:///project/src/main.ts").unwrap());
+    assert_eq!(location.uri, "src/main.ts");
+    assert_eq!(location.uri_base_id, Some(SRC_ROOT));
+    let location =
+      reporter.artifact_location(&Url::parse("file:///other/main.ts").unwrap());
+    assert_eq!(location.uri, "file:///other/main.ts");
+    assert_eq!(location.uri_base_id, None);
+  }
+}
@@ -0,0 +1,463 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Handling of the lint suppression comments that deno_lint doesn't know
+//! about:
+//!
+//! - `// deno-lint-ignore-next-line <codes> -- <reason>` suppresses the
+//!   problems of the given rules on the next line, for both the built-in and
+//!   the plugin rules.
+//! - The `-- <reason>` explaining a `deno-lint-ignore-file` or
+//!   `deno-lint-ignore` comment, which is required when the
+//!   `"requireSuppressionReason"` lint option is enabled.
+//!
+//! deno_lint applies `deno-lint-ignore-file` and `deno-lint-ignore` comments
+//! to the built-in rules itself, see [`lint_with_suppression_reasons`].
+
+use deno_ast::swc::common::comments::CommentKind;
+use deno_ast::ParsedSource;
+use deno_ast::SourceRange;
+use deno_ast::SourceRangedForSpanned;
+use deno_ast::SourceTextInfo;
+use deno_core::error::AnyError;
+use deno_lint::diagnostic::LintDiagnostic;
+use deno_lint::linter::LintFileOptions;
+use deno_lint::linter::Linter;
+use deno_lint::rules::LintRule;
+
+const IGNORE_FILE_DIRECTIVE: &str = "deno-lint-ignore-file";
+const IGNORE_NEXT_LINE_DIRECTIVE: &str = "deno-lint-ignore-next-line";
+const IGNORE_DIRECTIVE: &str = "deno-lint-ignore";
+
+const REQUIRE_SUPPRESSION_REASON_CODE: &str = "require-suppression-reason";
+const BAN_UNUSED_IGNORE_CODE: &str = "ban-unused-ignore";
+const BAN_UNKNOWN_RULE_CODE_CODE: &str = "ban-unknown-rule-code";
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct LintSuppressionOptions {
+  /// Report suppression comments without a `-- <reason>`.
+  pub require_reason: bool,
+  /// Report codes of `deno-lint-ignore-next-line` comments that didn't
+  /// suppress anything, like the `ban-unused-ignore` rule does for
+  /// `deno-lint-ignore` comments.
+  pub report_unused: bool,
+}
+
+impl LintSuppressionOptions {
+  pub fn new(rules: &[&'static dyn LintRule], require_reason: bool) -> Self {
+    Self {
+      require_reason,
+      report_unused: rules
+        .iter()
+        .any(|rule| rule.code() == BAN_UNUSED_IGNORE_CODE),
+    }
+  }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SuppressionKind {
+  File,
+  /// A `deno-lint-ignore` comment.
+  Line,
+  NextLine,
+}
+
+#[derive(Debug)]
+struct Suppression {
+  kind: SuppressionKind,
+  range: SourceRange,
+  /// The line index of the comment.
+  comment_line: usize,
+  codes: Vec<String>,
+  reason: Option<String>,
+}
+
+impl Suppression {
+  /// The line index of the code that a line suppression applies to.
+  fn target_line(&self) -> usize {
+    self.comment_line + 1
+  }
+
+  fn suppresses(&self, code: &str, line_index: usize) -> bool {
+    self.target_line() == line_index && self.codes.iter().any(|c| c == code)
+  }
+}
+
+/// The suppression comments of a file.
+pub struct LintSuppressions {
+  suppressions: Vec<Suppression>,
+}
+
+impl LintSuppressions {
+  pub fn new(parsed_source: &ParsedSource) -> Self {
+    let text_info = parsed_source.text_info();
+    let mut suppressions = Vec::new();
+    for comment in parsed_source.comments().get_vec() {
+      if !matches!(comment.kind, CommentKind::Line) {
+        continue;
+      }
+      let Some((kind, args)) = parse_directive(&comment.text) else {
+        continue;
+      };
+      let Some((codes, reason)) = parse_directive_args(args) else {
+        continue;
+      };
+      suppressions.push(Suppression {
+        kind,
+        range: comment.range(),
+        comment_line: text_info.line_index(comment.start()),
+        codes,
+        reason,
+      });
+    }
+    Self { suppressions }
+  }
+
+  pub fn is_file_ignored_for_all(&self) -> bool {
+    self
+      .suppressions
+      .iter()
+      .any(|s| s.kind == SuppressionKind::File && s.codes.is_empty())
+  }
+
+  /// Whether a problem is suppressed by a `deno-lint-ignore-file` or
+  /// `deno-lint-ignore` comment. Used for the plugin rules, which deno_lint
+  /// doesn't know about.
+  pub fn is_ignored(
+    &self,
+    code: &str,
+    range: SourceRange,
+    text_info: &SourceTextInfo,
+  ) -> bool {
+    let line_index = text_info.line_index(range.start);
+    self.suppressions.iter().any(|s| match s.kind {
+      SuppressionKind::File => {
+        s.codes.is_empty() || s.codes.iter().any(|c| c == code)
+      }
+      SuppressionKind::Line => s.suppresses(code, line_index),
+      SuppressionKind::NextLine => false,
+    })
+  }
+}
+
+/// Runs the built-in rules of `linter`. deno_lint would take the words of a
+/// `-- <reason>` for rule codes, so the reasons are blanked out of the text
+/// it lints. This keeps the positions of the diagnostics as they are.
+pub fn lint_with_suppression_reasons(
+  linter: &Linter,
+  parsed_source: &ParsedSource,
+) -> Result<Vec<LintDiagnostic>, AnyError> {
+  let text_info = parsed_source.text_info();
+  let start = text_info.range().start;
+  let reason_ranges = parsed_source
+    .comments()
+    .get_vec()
+    .iter()
+    .filter(|comment| matches!(comment.kind, CommentKind::Line))
+    .filter(|comment| parse_directive(&comment.text).is_some())
+    .filter_map(|comment| {
+      let index = comment.text.find("--")?;
+      // the text of a line comment follows the `//`
+      let reason_start = comment.start().as_byte_index(start) + 2 + index;
+      Some(reason_start..comment.end().as_byte_index(start))
+    })
+    .collect::<Vec<_>>();
+  if reason_ranges.is_empty() {
+    return Ok(linter.lint_with_ast(parsed_source));
+  }
+  let mut text = text_info.text_str().to_string();
+  for range in reason_ranges {
+    let blank = " ".repeat(range.len());
+    text.replace_range(range, &blank);
+  }
+  let (_, mut diagnostics) = linter.lint_file(LintFileOptions {
+    specifier: parsed_source.specifier().clone(),
+    media_type: parsed_source.media_type(),
+    source_code: text,
+  })?;
+  for diagnostic in &mut diagnostics {
+    diagnostic.text_info = text_info.clone();
+  }
+  Ok(diagnostics)
+}
+
+/// Applies the suppression comments that deno_lint doesn't handle to the
+/// diagnostics of the built-in and plugin rules, and reports unused and
+/// unexplained suppressions.
+pub fn apply_suppressions(
+  parsed_source: &ParsedSource,
+  diagnostics: &mut Vec<LintDiagnostic>,
+  options: &LintSuppressionOptions,
+) {
+  let suppressions = LintSuppressions::new(parsed_source);
+  if suppressions.suppressions.is_empty() {
+    return;
+  }
+  let text_info = parsed_source.text_info();
+
+  let mut used = suppressions
+    .suppressions
+    .iter()
+    .map(|s| vec![false; s.codes.len()])
+    .collect::<Vec<_>>();
+  diagnostics.retain(|d| {
+    let line_index = text_info.line_index(d.range.start);
+    if matches!(
+      d.code.as_str(),
+      BAN_UNKNOWN_RULE_CODE_CODE | BAN_UNUSED_IGNORE_CODE
+    ) {
+      // drop the problems that deno_lint reports for the
+      // `deno-lint-ignore-next-line` comments it doesn't know
+      return !suppressions.suppressions.iter().any(|s| {
+        s.kind == SuppressionKind::NextLine && s.comment_line == line_index
+      });
+    }
+    let mut is_suppressed = false;
+    for (s, used) in suppressions.suppressions.iter().zip(used.iter_mut()) {
+      if s.kind != SuppressionKind::NextLine || s.target_line() != line_index {
+        continue;
+      }
+      if let Some(index) = s.codes.iter().position(|c| *c == d.code) {
+        used[index] = true;
+        is_suppressed = true;
+      }
+    }
+    !is_suppressed
+  });
+
+  for (s, used) in suppressions.suppressions.iter().zip(used) {
+    if options.report_unused && s.kind == SuppressionKind::NextLine {
+      for (code, _) in s.codes.iter().zip(used).filter(|(_, used)| !used) {
+        diagnostics.push(create_diagnostic(
+          parsed_source,
+          s.range,
+          BAN_UNUSED_IGNORE_CODE,
+          format!("Ignore for code \"{code}\" was not used."),
+          None,
+        ));
+      }
+    }
+    if options.require_reason && s.reason.is_none() {
+      diagnostics.push(create_diagnostic(
+        parsed_source,
+        s.range,
+        REQUIRE_SUPPRESSION_REASON_CODE,
+        "Lint suppression comment doesn't explain why the problem is suppressed"
+          .to_string(),
+        Some(
+          "Add a reason after \"--\", e.g. `// deno-lint-ignore-next-line no-explicit-any -- the type comes from JSON`"
+            .to_string(),
+        ),
+      ));
+    }
+  }
+}
+
+fn create_diagnostic(
+  parsed_source: &ParsedSource,
+  range: SourceRange,
+  code: &str,
+  message: String,
+  hint: Option<String>,
+) -> LintDiagnostic {
+  LintDiagnostic {
+    specifier: parsed_source.specifier().clone(),
+    range,
+    text_info: parsed_source.text_info().clone(),
+    message,
+    code: code.to_string(),
+    hint,
+    fixes: Vec::new(),
+    custom_docs_url: None,
+    info: Vec::new(),
+  }
+}
+
+/// Parses the directive a suppression comment starts with, returning the
+/// text following it.
+fn parse_directive(comment_text: &str) -> Option<(SuppressionKind, &str)> {
+  let text = comment_text.trim();
+  if let Some(args) = text.strip_prefix(IGNORE_FILE_DIRECTIVE) {
+    Some((SuppressionKind::File, args))
+  } else if let Some(args) = text.strip_prefix(IGNORE_NEXT_LINE_DIRECTIVE) {
+    Some((SuppressionKind::NextLine, args))
+  } else {
+    text
+      .strip_prefix(IGNORE_DIRECTIVE)
+      .map(|args| (SuppressionKind::Line, args))
+  }
+}
+
+/// Parses the codes and the reason following a directive, e.g.
+/// ` no-var prefer-const -- generated code`.
+fn parse_directive_args(text: &str) -> Option<(Vec<String>, Option<String>)> {
+  if !text.is_empty() && !text.starts_with(char::is_whitespace) {
+    // e.g. `deno-lint-ignore-filefoo`
+    return None;
+  }
+  let (codes, reason) = match text.split_once("--") {
+    Some((codes, reason)) => (codes, Some(reason.trim())),
+    None => (text, None),
+  };
+  Some((
+    codes
+      .split_whitespace()
+      .map(|code| code.to_string())
+      .collect(),
+    reason
+      .filter(|reason| !reason.is_empty())
+      .map(|reason| reason.to_string()),
+  ))
+}
+
+#[cfg(test)]
+mod tests {
+  use deno_ast::MediaType;
+  use deno_ast::ModuleSpecifier;
+
+  use super::*;
+
+  fn parse(text: &str) -> ParsedSource {
+    deno_ast::parse_module(deno_ast::ParseParams {
+      specifier: ModuleSpecifier::parse("file:///mod.ts").unwrap(),
+      text_info: SourceTextInfo::from_string(text.to_string()),
+      media_type: MediaType::TypeScript,
+      capture_tokens: true,
+      scope_analysis: false,
+      maybe_syntax: None,
+    })
+    .unwrap()
+  }
+
+  fn diagnostic(
+    parsed_source: &ParsedSource,
+    code: &str,
+    line: usize,
+  ) -> LintDiagnostic {
+    let start = parsed_source.text_info().line_start(line);
+    create_diagnostic(
+      parsed_source,
+      SourceRange::new(start, start),
+      code,
+      "problem".to_string(),
+      None,
+    )
+  }
+
+  #[test]
+  fn parses_directive_args() {
+    assert_eq!(parse_directive_args(""), Some((Vec::new(), None)));
+    assert_eq!(
+      parse_directive_args(" my-plugin/no-foo no-explicit-any"),
+      Some((
+        vec![
+          "my-plugin/no-foo".to_string(),
+          "no-explicit-any".to_string()
+        ],
+        None
+      ))
+    );
+    assert_eq!(
+      parse_directive_args(" my-plugin/no-foo -- generated code "),
+      Some((
+        vec!["my-plugin/no-foo".to_string()],
+        Some("generated code".to_string())
+      ))
+    );
+    assert_eq!(
+      parse_directive_args(" -- generated"),
+      Some((Vec::new(), Some("generated".to_string())))
+    );
+    assert_eq!(
+      parse_directive_args(" no-var --"),
+      Some((vec!["no-var".to_string()], None))
+    );
+    assert_eq!(parse_directive_args("foo"), None);
+  }
+
+  #[test]
+  fn applies_next_line_suppressions() {
+    let parsed_source = parse(concat!(
+      "// deno-lint-ignore-next-line no-var no-debugger -- legacy\n",
+      "var a = 1;\n",
+      "// deno-lint-ignore-next-line no-var\n",
+      "var b = 2;\n",
+      "var c = 3;\n",
+    ));
+    let mut diagnostics = vec![
+      diagnostic(&parsed_source, "no-var", 1),
+      diagnostic(&parsed_source, "no-var", 3),
+      diagnostic(&parsed_source, "no-var", 4),
+    ];
+    apply_suppressions(
+      &parsed_source,
+      &mut diagnostics,
+      &LintSuppressionOptions {
+        require_reason: true,
+        report_unused: true,
+      },
+    );
+    let text_info = parsed_source.text_info();
+    let actual = diagnostics
+      .iter()
+      .map(|d| {
+        (
+          d.code.as_str(),
+          text_info.line_index(d.range.start),
+          d.message.as_str(),
+        )
+      })
+      .collect::<Vec<_>>();
+    assert_eq!(
+      actual,
+      vec![
+        ("no-var", 4, "problem"),
+        (
+          "ban-unused-ignore",
+          0,
+          "Ignore for code \"no-debugger\" was not used."
+        ),
+        (
+          "require-suppression-reason",
+          2,
+          "Lint suppression comment doesn't explain why the problem is suppressed"
+        ),
+      ]
+    );
+  }
+
+  #[test]
+  fn lints_without_suppression_reasons() {
+    let parsed_source = parse(concat!(
+      "// deno-lint-ignore no-var -- \"legacy\" code\n",
+      "var a = 1;\n",
+      "// deno-lint-ignore-next-line no-var -- legacy\n",
+      "var b = 2;\n",
+      "var c = 3;\n",
+    ));
+    let linter = crate::tools::lint::create_linter(
+      deno_lint::rules::get_all_rules()
+        .into_iter()
+        .filter(|rule| {
+          matches!(
+            rule.code(),
+            "no-var" | BAN_UNKNOWN_RULE_CODE_CODE | BAN_UNUSED_IGNORE_CODE
+          )
+        })
+        .collect(),
+    );
+    let diagnostics =
+      lint_with_suppression_reasons(&linter, &parsed_source).unwrap();
+    let text_info = parsed_source.text_info();
+    let actual = diagnostics
+      .iter()
+      .map(|d| (d.code.as_str(), text_info.line_index(d.range.start)))
+      // the `deno-lint-ignore-next-line` comment is left to
+      // `apply_suppressions`
+      .filter(|(_, line_index)| *line_index != 2)
+      .collect::<Vec<_>>();
+    assert_eq!(actual, vec![("no-var", 3), ("no-var", 4)]);
+    assert!(diagnostics
+      .iter()
+      .all(|d| d.text_info.text_str() == text_info.text_str()));
+  }
+}
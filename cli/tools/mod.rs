@@ -2,6 +2,7 @@
 
 pub mod bench;
 pub mod bundle;
+pub mod cache;
 pub mod check;
 pub mod compile;
 pub mod coverage;
@@ -12,9 +13,11 @@ pub mod init;
 pub mod installer;
 pub mod jupyter;
 pub mod lint;
+pub mod permissions;
 pub mod registry;
 pub mod repl;
 pub mod run;
+pub mod storage;
 pub mod task;
 pub mod test;
 pub mod upgrade;
@@ -0,0 +1,60 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::anyhow::bail;
+use deno_core::error::AnyError;
+
+use crate::args::CliOptions;
+use crate::args::Flags;
+use crate::args::PermissionGrantsFlags;
+use crate::colors;
+
+pub fn permissions(
+  flags: Flags,
+  grants_flags: PermissionGrantsFlags,
+) -> Result<(), AnyError> {
+  let cli_options = CliOptions::from_flags(flags)?;
+  let Some(store) = cli_options.permission_grant_store()? else {
+    bail!(
+      "No configuration file found. Specify the script whose permissions to manage with --script."
+    );
+  };
+  match grants_flags {
+    PermissionGrantsFlags::List => {
+      let grants = store.grants()?;
+      if grants.is_empty() {
+        log::info!("No permissions stored for {}", store.project());
+        return Ok(());
+      }
+      log::info!(
+        "{} {}",
+        colors::bold("Permissions stored for"),
+        store.project()
+      );
+      for (name, values) in grants {
+        if values.is_empty() {
+          log::info!("  {} {}", colors::green(&name), colors::gray("(all)"));
+        }
+        for value in values {
+          log::info!("  {} {}", colors::green(&name), value);
+        }
+      }
+    }
+    PermissionGrantsFlags::Revoke { name, value } => {
+      if store.revoke(name.as_deref(), value.as_deref())? {
+        let revoked = match (&name, &value) {
+          (Some(name), Some(value)) => format!("{name} access to {value}"),
+          (Some(name), None) => format!("all {name} access"),
+          (None, _) => "all permissions".to_string(),
+        };
+        log::info!(
+          "{} {revoked} for {}",
+          colors::green("Revoked"),
+          store.project()
+        );
+      } else {
+        log::info!("No matching permissions stored for {}", store.project());
+      }
+    }
+  }
+  Ok(())
+}
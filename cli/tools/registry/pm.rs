@@ -17,6 +17,7 @@ use deno_semver::npm::NpmPackageReqReference;
 use jsonc_parser::ast::ObjectProp;
 use jsonc_parser::ast::Value;
 
+use crate::args::resolve_fmt_config;
 use crate::args::AddFlags;
 use crate::args::CacheSetting;
 use crate::args::Flags;
@@ -159,10 +160,9 @@ pub async fn add(flags: Flags, add_flags: AddFlags) -> Result<(), AnyError> {
   import_list.sort_by(|(k1, _), (k2, _)| k1.cmp(k2));
   let generated_imports = generate_imports(import_list);
 
-  let fmt_config_options = config_file
-    .to_fmt_config()
+  let fmt_config_options = resolve_fmt_config(config_file)
     .ok()
-    .flatten()
+    .and_then(|(config, _)| config)
     .map(|config| config.options)
     .unwrap_or_default();
 
@@ -11,6 +11,9 @@ use deno_core::anyhow::Context as _;
 use deno_core::error::AnyError;
 use deno_core::parking_lot::Mutex;
 use deno_core::serde_json;
+use lazy_regex::lazy_regex;
+use once_cell::sync::Lazy;
+use regex::Regex;
 use rustyline::completion::Completer;
 use rustyline::error::ReadlineError;
 use rustyline::highlight::Highlighter;
@@ -181,6 +184,30 @@ fn get_expr_from_line_at_pos(line: &str, cursor_pos: usize) -> &str {
   word
 }
 
+/// An import specifier that is still being typed, for example
+/// `import("./mod` or `import { a } from "./mod`.
+static UNTERMINATED_SPECIFIER_RE: Lazy<Regex> = lazy_regex!(
+  r#"(?:(?P<dynamic>\bimport\s*\(\s*)|\bfrom\s*|\bimport\s+)(["'])[^"']*$"#
+);
+
+/// The language server only provides completions for import specifiers that
+/// are part of the module graph, which requires the string of the specifier
+/// to be closed. Returns the line with the specifier at the cursor closed.
+fn close_unterminated_specifier(line: &str, pos: usize) -> Option<String> {
+  let captures = UNTERMINATED_SPECIFIER_RE.captures(&line[..pos])?;
+  let quote = captures.get(2)?.as_str();
+  let rest = &line[pos..];
+  if rest.contains(quote) {
+    return None;
+  }
+  let closing = if captures.name("dynamic").is_some() {
+    ")"
+  } else {
+    ""
+  };
+  Some(format!("{}{quote}{closing}{rest}", &line[..pos]))
+}
+
 impl Completer for EditorHelper {
   type Candidate = String;
 
@@ -190,7 +217,10 @@ impl Completer for EditorHelper {
     pos: usize,
     _ctx: &Context<'_>,
   ) -> Result<(usize, Vec<String>), ReadlineError> {
-    let lsp_completions = self.sync_sender.lsp_completions(line, pos);
+    let lsp_completions = match close_unterminated_specifier(line, pos) {
+      Some(line) => self.sync_sender.lsp_completions(&line, pos),
+      None => self.sync_sender.lsp_completions(line, pos),
+    };
     if !lsp_completions.is_empty() {
       // assumes all lsp completions have the same start position
       return Ok((
@@ -432,9 +462,14 @@ impl ReplEditor {
   pub fn new(
     helper: EditorHelper,
     history_file_path: Option<PathBuf>,
+    max_history_size: usize,
   ) -> Result<Self, AnyError> {
+    // lines starting with a space are not saved, like in most shells
     let editor_config = Config::builder()
       .completion_type(CompletionType::List)
+      .max_history_size(max_history_size)?
+      .history_ignore_dups(true)?
+      .history_ignore_space(true)
       .build();
 
     let mut editor =
@@ -563,6 +598,7 @@ impl ConditionalEventHandler for TabEventHandler {
 mod test {
   use rustyline::validate::ValidationResult;
 
+  use super::close_unterminated_specifier;
   use super::validate;
 
   #[test]
@@ -578,4 +614,24 @@ let left = test( arr.slice( 0 , arr.length/2 ) )"#;
     let code = r#"/testing/;"#;
     assert!(matches!(validate(code), ValidationResult::Valid(_)));
   }
+
+  #[test]
+  fn closes_unterminated_specifier() {
+    let close = |line: &str| close_unterminated_specifier(line, line.len());
+    assert_eq!(
+      close("await import(\"./mod").as_deref(),
+      Some("await import(\"./mod\")")
+    );
+    assert_eq!(
+      close("import { a } from './mod").as_deref(),
+      Some("import { a } from './mod'")
+    );
+    assert_eq!(close("import 'npm:ch").as_deref(), Some("import 'npm:ch'"));
+    assert_eq!(close("import(\"./mod\")"), None);
+    assert_eq!(close("const from = \"text"), None);
+    assert_eq!(close("Deno.readTextFile('./data"), None);
+    assert_eq!(close("import './a(b").as_deref(), Some("import './a(b'"));
+    let line = "import(\"./mod\")";
+    assert_eq!(close_unterminated_specifier(line, 12), None);
+  }
 }
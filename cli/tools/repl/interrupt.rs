@@ -0,0 +1,70 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+
+use deno_core::v8;
+use deno_runtime::deno_fs::temp_cleanup::SigintListener;
+use deno_runtime::tokio_util::create_basic_runtime;
+use tokio::sync::mpsc::unbounded_channel;
+use tokio::sync::mpsc::UnboundedReceiver;
+
+/// Interrupts the code evaluated in the REPL when ctrl+c is pressed.
+///
+/// Rustyline handles ctrl+c while reading a line by clearing it, so a signal
+/// received between evaluations, e.g. right after the code finished running,
+/// is ignored rather than exiting the REPL. The signal is listened for on a
+/// separate thread, because code stuck in a loop blocks the thread of the
+/// REPL.
+/// Execution is terminated through the isolate handle, which keeps the state
+/// of the session, and the REPL stops waiting for pending promises.
+pub struct InterruptHandler {
+  evaluating: Arc<AtomicBool>,
+  interrupt_rx: UnboundedReceiver<()>,
+}
+
+impl InterruptHandler {
+  pub fn new(isolate_handle: v8::IsolateHandle) -> Self {
+    let evaluating = Arc::new(AtomicBool::new(false));
+    let (interrupt_tx, interrupt_rx) = unbounded_channel();
+    let evaluating_ = evaluating.clone();
+    std::thread::spawn(move || {
+      create_basic_runtime().block_on(async move {
+        // keeps ctrl+c from terminating the REPL when temporary paths are
+        // removed on SIGINT
+        let _sigint_listener = SigintListener::new();
+        while tokio::signal::ctrl_c().await.is_ok() {
+          if !evaluating_.load(Ordering::SeqCst) {
+            continue;
+          }
+          isolate_handle.terminate_execution();
+          if interrupt_tx.send(()).is_err() {
+            break;
+          }
+        }
+      });
+    });
+    Self {
+      evaluating,
+      interrupt_rx,
+    }
+  }
+
+  pub fn start_evaluation(&mut self) {
+    // discard interrupts of a previous evaluation
+    while self.interrupt_rx.try_recv().is_ok() {}
+    self.evaluating.store(true, Ordering::SeqCst);
+  }
+
+  /// Resolves when the current evaluation is interrupted.
+  pub async fn interrupted(&mut self) {
+    self.interrupt_rx.recv().await;
+  }
+
+  /// Returns if the evaluation was interrupted.
+  pub fn finish_evaluation(&mut self) -> bool {
+    self.evaluating.store(false, Ordering::SeqCst);
+    self.interrupt_rx.try_recv().is_ok()
+  }
+}
@@ -2,7 +2,6 @@
 
 use std::sync::Arc;
 
-use crate::args::CliOptions;
 use crate::args::Flags;
 use crate::args::ReplFlags;
 use crate::cdp;
@@ -13,6 +12,7 @@ use deno_core::error::AnyError;
 use deno_core::futures::StreamExt;
 use deno_core::serde_json;
 use deno_core::unsync::spawn_blocking;
+use deno_core::ModuleSpecifier;
 use deno_runtime::permissions::Permissions;
 use deno_runtime::permissions::PermissionsContainer;
 use deno_runtime::WorkerExecutionMode;
@@ -20,6 +20,7 @@ use rustyline::error::ReadlineError;
 
 mod channel;
 mod editor;
+mod interrupt;
 mod session;
 
 use channel::rustyline_channel;
@@ -28,6 +29,7 @@ use channel::RustylineSyncMessageHandler;
 use channel::RustylineSyncResponse;
 use editor::EditorHelper;
 use editor::ReplEditor;
+use interrupt::InterruptHandler;
 pub use session::EvaluationOutput;
 pub use session::ReplSession;
 pub use session::REPL_INTERNALS_NAME;
@@ -38,6 +40,7 @@ struct Repl {
   session: ReplSession,
   editor: ReplEditor,
   message_handler: RustylineSyncMessageHandler,
+  interrupt_handler: InterruptHandler,
 }
 
 impl Repl {
@@ -53,7 +56,41 @@ impl Repl {
         Ok(line) => {
           self.editor.set_should_exit_on_interrupt(false);
           self.editor.update_history(line.clone());
-          let output = self.session.evaluate_line_and_get_output(&line).await;
+          if let Some(expr) = line.trim().strip_prefix(".type ") {
+            match self.session.language_server.type_of_expression(expr).await {
+              Some(ty) => println!("{}", colors::cyan(ty)),
+              None => println!(
+                "{}",
+                colors::red("Unable to infer the type of the expression.")
+              ),
+            }
+            continue;
+          }
+          // an interrupt might have terminated execution right after the
+          // previous evaluation finished
+          self
+            .session
+            .worker
+            .js_runtime
+            .v8_isolate()
+            .cancel_terminate_execution();
+          self.interrupt_handler.start_evaluation();
+          let output = tokio::select! {
+            output = self.session.evaluate_line_and_get_output(&line) => {
+              Some(output)
+            }
+            _ = self.interrupt_handler.interrupted() => None,
+          };
+          if self.interrupt_handler.finish_evaluation() || output.is_none() {
+            self
+              .session
+              .worker
+              .js_runtime
+              .v8_isolate()
+              .cancel_terminate_execution();
+            println!("{}", colors::red("Interrupted"));
+            continue;
+          }
 
           // We check for close and break here instead of making it a loop condition to get
           // consistent behavior in when the user evaluates a call to close().
@@ -61,7 +98,7 @@ impl Repl {
             break;
           }
 
-          println!("{output}");
+          println!("{}", output.unwrap());
         }
         Err(ReadlineError::Interrupted) => {
           if self.editor.should_exit_on_interrupt() {
@@ -138,20 +175,37 @@ async fn read_line_and_poll(
 }
 
 async fn read_eval_file(
-  cli_options: &CliOptions,
   file_fetcher: &FileFetcher,
-  eval_file: &str,
+  specifier: &ModuleSpecifier,
 ) -> Result<Arc<str>, AnyError> {
-  let specifier =
-    deno_core::resolve_url_or_path(eval_file, cli_options.initial_cwd())?;
-
   let file = file_fetcher
-    .fetch(&specifier, &PermissionsContainer::allow_all())
+    .fetch(specifier, &PermissionsContainer::allow_all())
     .await?;
 
   Ok(file.into_text_decoded()?.source)
 }
 
+const DEFAULT_HISTORY_SIZE: usize = 1000;
+
+/// The maximum number of entries kept in the history, which can be set with
+/// the `DENO_REPL_HISTORY_SIZE` environment variable.
+fn repl_history_size() -> usize {
+  let Some(value) = std::env::var_os("DENO_REPL_HISTORY_SIZE") else {
+    return DEFAULT_HISTORY_SIZE;
+  };
+  match value.to_str().and_then(|v| v.parse::<usize>().ok()) {
+    Some(size) if size > 0 => size,
+    _ => {
+      log::warn!(
+        "{} Ignoring invalid DENO_REPL_HISTORY_SIZE {:?}, expected a positive number.",
+        colors::yellow("Warning"),
+        value
+      );
+      DEFAULT_HISTORY_SIZE
+    }
+  }
+}
+
 pub async fn run(flags: Flags, repl_flags: ReplFlags) -> Result<i32, AnyError> {
   let factory = CliFactory::from_flags(flags)?;
   let cli_options = factory.cli_options();
@@ -196,17 +250,46 @@ pub async fn run(flags: Flags, repl_flags: ReplFlags) -> Result<i32, AnyError> {
     sync_sender: rustyline_channel.0,
   };
 
-  let editor = ReplEditor::new(helper, history_file_path)?;
+  let editor = ReplEditor::new(helper, history_file_path, repl_history_size())?;
 
+  let interrupt_handler = InterruptHandler::new(
+    session.worker.js_runtime.v8_isolate().thread_safe_handle(),
+  );
   let mut repl = Repl {
     session,
     editor,
     message_handler: rustyline_channel.1,
+    interrupt_handler,
   };
 
+  for specifier in cli_options.resolve_repl_init()? {
+    match read_eval_file(file_fetcher, &specifier).await {
+      Ok(eval_source) => {
+        let output = repl
+          .session
+          .evaluate_line_and_get_output(&eval_source)
+          .await;
+        // only output errors
+        if let EvaluationOutput::Error(error_text) = output {
+          println!("Error in \"repl.init\" file \"{specifier}\": {error_text}");
+        }
+      }
+      Err(e) => {
+        println!("Error in \"repl.init\" file \"{specifier}\": {e}");
+      }
+    }
+  }
+
   if let Some(eval_files) = repl_flags.eval_files {
     for eval_file in eval_files {
-      match read_eval_file(cli_options, file_fetcher, &eval_file).await {
+      let result = match deno_core::resolve_url_or_path(
+        &eval_file,
+        cli_options.initial_cwd(),
+      ) {
+        Ok(specifier) => read_eval_file(file_fetcher, &specifier).await,
+        Err(e) => Err(e.into()),
+      };
+      match result {
         Ok(eval_source) => {
           let output = repl
             .session
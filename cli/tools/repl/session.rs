@@ -267,13 +267,7 @@ impl ReplSession {
       referrer,
       notifications: Arc::new(Mutex::new(notification_rx)),
       test_reporter_factory: Box::new(move || {
-        Box::new(PrettyTestReporter::new(
-          false,
-          true,
-          false,
-          true,
-          cwd_url.clone(),
-        ))
+        Box::new(PrettyTestReporter::new(true, false, true, cwd_url.clone()))
       }),
       main_module,
       test_event_receiver: Some(test_event_receiver),
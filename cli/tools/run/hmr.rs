@@ -117,7 +117,7 @@ impl crate::worker::HmrRunner for HmrRunner {
 
           // If after filtering there are no paths it means it's either a file
           // we can't HMR or an external file that was passed explicitly to
-          // `--unstable-hmr=<file>` path.
+          // `--watch-hmr=<file>` path.
           if filtered_paths.is_empty() {
             let _ = self.watcher_communicator.force_restart();
             continue;
@@ -236,9 +236,11 @@ impl HmrRunner {
     &mut self,
     script_id: &str,
   ) -> Result<(), AnyError> {
+    // "hmr" is kept for programs written before the event was namespaced
+    let detail = json!({ "path": script_id });
     let expr = format!(
-      "dispatchEvent(new CustomEvent(\"hmr\", {{ detail: {{ path: \"{}\" }} }}));",
-      script_id
+      "dispatchEvent(new CustomEvent(\"deno:hmr\", {{ detail: {detail} }}));\
+       dispatchEvent(new CustomEvent(\"hmr\", {{ detail: {detail} }}));"
     );
 
     let _result = self
@@ -0,0 +1,123 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use deno_core::error::AnyError;
+use deno_runtime::deno_webstorage;
+
+use crate::args::Flags;
+use crate::args::StorageFlags;
+use crate::colors;
+use crate::factory::CliFactory;
+use crate::util::display::human_size;
+
+pub fn storage(
+  flags: Flags,
+  storage_flags: StorageFlags,
+) -> Result<(), AnyError> {
+  let factory = CliFactory::from_flags(flags)?;
+  let origin_data_dir = factory.deno_dir()?.origin_data_folder_path();
+  let dirs = origin_storage_dirs(&origin_data_dir)?;
+  match storage_flags {
+    StorageFlags::List => {
+      let mut found = false;
+      for dir in dirs {
+        match deno_webstorage::read_local_storage_info(&dir) {
+          Ok(None) => {}
+          Ok(Some(info)) => {
+            found = true;
+            let origin = info.origin.as_deref().unwrap_or("(unknown origin)");
+            log::info!("{}", colors::bold(origin));
+            log::info!("  {} {}", colors::gray("Path:"), dir.display());
+            log::info!("  {} {}", colors::gray("Entries:"), info.entries);
+            log::info!(
+              "  {} {}",
+              colors::gray("Size:"),
+              human_size(info.size as f64)
+            );
+          }
+          Err(err) => {
+            found = true;
+            log::info!("{}", colors::bold("(unknown origin)"));
+            log::info!("  {} {}", colors::gray("Path:"), dir.display());
+            log::info!("  {} {err}", colors::red("Error:"));
+          }
+        }
+      }
+      if !found {
+        log::info!(
+          "No localStorage data stored in {}",
+          origin_data_dir.display()
+        );
+      }
+    }
+    StorageFlags::Clear { origin } => {
+      let mut cleared = 0;
+      for dir in dirs {
+        if let Some(origin) = &origin {
+          // databases without a recorded origin can be cleared by the name
+          // of their directory, as shown by `deno storage ls`
+          let recorded_origin = deno_webstorage::read_local_storage_info(&dir)
+            .ok()
+            .flatten()
+            .and_then(|info| info.origin);
+          if recorded_origin.as_ref() != Some(origin)
+            && dir.file_name().and_then(|name| name.to_str())
+              != Some(origin.as_str())
+          {
+            continue;
+          }
+        }
+        if deno_webstorage::remove_local_storage(&dir)? {
+          cleared += 1;
+          // the directory is shared with other origin bound APIs, so it is
+          // only removed when nothing else is left in it
+          let _ = std::fs::remove_dir(&dir);
+        }
+      }
+      match (origin, cleared) {
+        (Some(origin), 0) => {
+          log::info!("No localStorage data stored for {origin}")
+        }
+        (Some(origin), _) => log::info!(
+          "{} localStorage data of {origin}",
+          colors::green("Cleared")
+        ),
+        (None, 0) => log::info!("No localStorage data stored"),
+        (None, 1) => {
+          log::info!(
+            "{} localStorage data of 1 origin",
+            colors::green("Cleared")
+          )
+        }
+        (None, cleared) => log::info!(
+          "{} localStorage data of {cleared} origins",
+          colors::green("Cleared")
+        ),
+      }
+    }
+  }
+  Ok(())
+}
+
+fn origin_storage_dirs(
+  origin_data_dir: &Path,
+) -> Result<Vec<PathBuf>, AnyError> {
+  let entries = match std::fs::read_dir(origin_data_dir) {
+    Ok(entries) => entries,
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+      return Ok(Vec::new())
+    }
+    Err(err) => return Err(err.into()),
+  };
+  let mut dirs = Vec::new();
+  for entry in entries {
+    let entry = entry?;
+    if entry.file_type()?.is_dir() {
+      dirs.push(entry.path());
+    }
+  }
+  dirs.sort();
+  Ok(dirs)
+}
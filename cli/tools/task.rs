@@ -1,6 +1,8 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use crate::args::CliOptions;
+use crate::args::CliTaskOptions;
+use crate::args::CliTasksConfig;
 use crate::args::Flags;
 use crate::args::TaskFlags;
 use crate::colors;
@@ -8,12 +10,19 @@ use crate::factory::CliFactory;
 use crate::npm::CliNpmResolver;
 use crate::npm::InnerCliNpmResolverRef;
 use crate::npm::ManagedCliNpmResolver;
+use crate::util::file_watcher;
+use crate::util::file_watcher::WatcherCommunicator;
 use crate::util::fs::canonicalize_path;
+use deno_config::glob::PathOrPattern;
+use deno_config::glob::PathOrPatternSet;
 use deno_core::anyhow::bail;
 use deno_core::anyhow::Context;
 use deno_core::error::AnyError;
 use deno_core::futures;
 use deno_core::futures::future::LocalBoxFuture;
+use deno_core::futures::stream::FuturesUnordered;
+use deno_core::futures::FutureExt;
+use deno_core::futures::StreamExt;
 use deno_runtime::deno_node::NodeResolver;
 use deno_semver::package::PackageNv;
 use deno_task_shell::ExecuteResult;
@@ -23,6 +32,7 @@ use indexmap::IndexMap;
 use lazy_regex::Lazy;
 use regex::Regex;
 use std::collections::HashMap;
+use std::collections::HashSet;
 use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
@@ -32,9 +42,56 @@ pub async fn execute_script(
   flags: Flags,
   task_flags: TaskFlags,
 ) -> Result<i32, AnyError> {
-  let factory = CliFactory::from_flags(flags)?;
+  if let Some(watch_flags) = &task_flags.watch {
+    // check the task can be watched before starting the watcher, which would
+    // wait for changes after an error otherwise
+    if let Some(task_name) = &task_flags.task {
+      let cli_options = CliOptions::from_flags(flags.clone())?;
+      let (tasks_config, cli_tasks_config) =
+        cli_options.resolve_tasks_config()?;
+      let has_watch_files = cli_tasks_config
+        .tasks
+        .get(task_name)
+        .is_some_and(|options| !options.watch.is_empty());
+      if tasks_config.contains_key(task_name) && !has_watch_files {
+        bail!(
+          "Task \"{}\" has no files to watch. Add a \"watch\" option with the files or globs to watch to the task in the configuration file.",
+          task_name
+        );
+      }
+    }
+    file_watcher::watch_func(
+      flags,
+      file_watcher::PrintConfig::new("Task", !watch_flags.no_clear_screen),
+      move |flags, watcher_communicator, _changed_paths| {
+        let task_flags = task_flags.clone();
+        Ok(async move {
+          let factory = CliFactory::from_flags(flags)?;
+          let exit_code =
+            execute_task(factory, task_flags, Some(&watcher_communicator))
+              .await?;
+          if exit_code > 0 {
+            bail!("Task exited with code {}.", exit_code);
+          }
+          Ok(())
+        })
+      },
+    )
+    .await?;
+    Ok(0)
+  } else {
+    let factory = CliFactory::from_flags(flags)?;
+    execute_task(factory, task_flags, None).await
+  }
+}
+
+async fn execute_task(
+  factory: CliFactory,
+  task_flags: TaskFlags,
+  maybe_watcher_communicator: Option<&WatcherCommunicator>,
+) -> Result<i32, AnyError> {
   let cli_options = factory.cli_options();
-  let tasks_config = cli_options.resolve_tasks_config()?;
+  let (tasks_config, cli_tasks_config) = cli_options.resolve_tasks_config()?;
   let maybe_package_json = cli_options.maybe_package_json();
   let package_json_scripts = maybe_package_json
     .as_ref()
@@ -44,42 +101,90 @@ pub async fn execute_script(
   let task_name = match &task_flags.task {
     Some(task) => task,
     None => {
-      print_available_tasks(&tasks_config, &package_json_scripts);
+      print_available_tasks(
+        &tasks_config,
+        &cli_tasks_config,
+        &package_json_scripts,
+      );
       return Ok(1);
     }
   };
   let npm_resolver = factory.npm_resolver().await?;
   let node_resolver = factory.node_resolver().await?;
 
-  if let Some(
-    deno_config::Task::Definition(script)
-    | deno_config::Task::Commented {
-      definition: script, ..
-    },
-  ) = tasks_config.get(task_name)
-  {
+  if tasks_config.contains_key(task_name) {
     let config_file_url = cli_options.maybe_config_file_specifier().unwrap();
     let config_file_path = if config_file_url.scheme() == "file" {
       config_file_url.to_file_path().unwrap()
     } else {
       bail!("Only local configuration files are supported")
     };
-    let cwd = match task_flags.cwd {
-      Some(path) => canonicalize_path(&PathBuf::from(path))?,
-      None => config_file_path.parent().unwrap().to_owned(),
+    let config_dir = config_file_path.parent().unwrap();
+    let maybe_flags_cwd = match &task_flags.cwd {
+      Some(path) => Some(canonicalize_path(&PathBuf::from(path))?),
+      None => None,
     };
+    let empty_options = CliTaskOptions::default();
+    let task_options =
+      |name: &str| cli_tasks_config.tasks.get(name).unwrap_or(&empty_options);
+    // --cwd takes precedence over the cwd of the task
+    let task_cwd =
+      |name: &str| match (&maybe_flags_cwd, &task_options(name).cwd) {
+        (Some(cwd), _) => cwd.clone(),
+        (None, Some(cwd)) => config_dir.join(cwd),
+        (None, None) => config_dir.to_owned(),
+      };
+
+    let task_graph = TaskGraph::build(task_name, &cli_tasks_config, |name| {
+      tasks_config.contains_key(name)
+    })?;
+
+    if let Some(watcher_communicator) = maybe_watcher_communicator {
+      // watch the base paths of the globs, so that the files created after
+      // starting are picked up, but only restart for the files matching them
+      let watch_set = PathOrPatternSet::from_include_relative_path_or_patterns(
+        &task_cwd(task_name),
+        &task_options(task_name).watch,
+      )?;
+      let mut paths_to_watch = cli_options.watch_paths();
+      let restart_set = paths_to_watch
+        .iter()
+        .cloned()
+        .map(PathOrPattern::Path)
+        .chain(watch_set.inner().iter().cloned())
+        .collect();
+      paths_to_watch.extend(watch_set.base_paths());
+      watcher_communicator
+        .set_restart_filter(PathOrPatternSet::new(restart_set));
+      let _ = watcher_communicator.watch_paths(paths_to_watch);
+    }
 
     let npm_commands =
       resolve_npm_commands(npm_resolver.as_ref(), node_resolver)?;
-    run_task(
-      task_name,
-      script,
-      &cwd,
-      cli_options,
-      npm_commands,
-      npm_resolver.as_ref(),
-    )
-    .await
+    task_graph
+      .run(|name| {
+        let script = match &tasks_config[name] {
+          deno_config::Task::Definition(script)
+          | deno_config::Task::Commented {
+            definition: script, ..
+          } => script,
+        };
+        // only the task that was requested receives the additional arguments
+        let script = if name == task_name {
+          get_script_with_args(script, cli_options)
+        } else {
+          script.clone()
+        };
+        let cwd = task_cwd(name);
+        let env = &task_options(name).env;
+        let npm_commands = npm_commands.clone();
+        let npm_resolver = npm_resolver.as_ref();
+        async move {
+          run_task(name, script, &cwd, env, npm_commands, npm_resolver).await
+        }
+        .boxed_local()
+      })
+      .await
   } else if package_json_scripts.contains_key(task_name) {
     let package_json_deps_provider = factory.package_json_deps_provider();
 
@@ -130,9 +235,9 @@ pub async fn execute_script(
       if let Some(script) = package_json_scripts.get(&task_name) {
         let exit_code = run_task(
           &task_name,
-          script,
+          get_script_with_args(script, cli_options),
           &cwd,
-          cli_options,
+          &HashMap::new(),
           npm_commands.clone(),
           npm_resolver.as_ref(),
         )
@@ -146,32 +251,139 @@ pub async fn execute_script(
     Ok(0)
   } else {
     eprintln!("Task not found: {task_name}");
-    print_available_tasks(&tasks_config, &package_json_scripts);
+    print_available_tasks(
+      &tasks_config,
+      &cli_tasks_config,
+      &package_json_scripts,
+    );
     Ok(1)
   }
 }
 
 async fn run_task(
   task_name: &str,
-  script: &str,
+  script: String,
   cwd: &Path,
-  cli_options: &CliOptions,
+  env: &HashMap<String, String>,
   npm_commands: HashMap<String, Rc<dyn ShellCommand>>,
   npm_resolver: &dyn CliNpmResolver,
 ) -> Result<i32, AnyError> {
-  let script = get_script_with_args(script, cli_options);
   output_task(task_name, &script);
   let seq_list = deno_task_shell::parser::parse(&script)
     .with_context(|| format!("Error parsing script '{}'.", task_name))?;
-  let env_vars = match npm_resolver.root_node_modules_path() {
+  let mut env_vars = match npm_resolver.root_node_modules_path() {
     Some(dir_path) => collect_env_vars_with_node_modules_dir(dir_path),
     None => collect_env_vars(),
   };
+  env_vars.extend(env.iter().map(|(k, v)| (k.clone(), v.clone())));
   let local = LocalSet::new();
   let future = deno_task_shell::execute(seq_list, env_vars, cwd, npm_commands);
   Ok(local.run_until(future).await)
 }
 
+/// The tasks that need to run for a task, in the order they can be started,
+/// along with the tasks each of them depends on.
+struct TaskGraph<'a> {
+  tasks: IndexMap<&'a str, &'a [String]>,
+}
+
+impl<'a> TaskGraph<'a> {
+  fn build(
+    task_name: &'a str,
+    config: &'a CliTasksConfig,
+    task_exists: impl Fn(&str) -> bool,
+  ) -> Result<Self, AnyError> {
+    let mut graph = TaskGraph {
+      tasks: IndexMap::new(),
+    };
+    graph.visit(task_name, config, &task_exists, &mut Vec::new())?;
+    Ok(graph)
+  }
+
+  fn visit(
+    &mut self,
+    task_name: &'a str,
+    config: &'a CliTasksConfig,
+    task_exists: &impl Fn(&str) -> bool,
+    path: &mut Vec<&'a str>,
+  ) -> Result<(), AnyError> {
+    if self.tasks.contains_key(task_name) {
+      return Ok(());
+    }
+    if let Some(index) = path.iter().position(|name| *name == task_name) {
+      let cycle = path[index..]
+        .iter()
+        .chain(std::iter::once(&task_name))
+        .copied()
+        .collect::<Vec<_>>();
+      bail!("Task dependency cycle detected: {}", cycle.join(" -> "));
+    }
+    let dependencies = config
+      .tasks
+      .get(task_name)
+      .map(|options| options.depends_on.as_slice())
+      .unwrap_or_default();
+    path.push(task_name);
+    for dependency in dependencies {
+      if !task_exists(dependency) {
+        bail!(
+          "Task \"{}\" depends on \"{}\", which is not defined in the configuration file",
+          task_name,
+          dependency
+        );
+      }
+      self.visit(dependency, config, task_exists, path)?;
+    }
+    path.pop();
+    self.tasks.insert(task_name, dependencies);
+    Ok(())
+  }
+
+  /// Runs each task once the tasks it depends on succeeded. Tasks that don't
+  /// depend on each other run in parallel. Returns the exit code of the first
+  /// task that fails.
+  async fn run(
+    &self,
+    run_task: impl Fn(&'a str) -> LocalBoxFuture<'a, Result<i32, AnyError>>,
+  ) -> Result<i32, AnyError> {
+    let mut pending = self
+      .tasks
+      .iter()
+      .map(|(name, dependencies)| {
+        let dependencies = dependencies
+          .iter()
+          .map(|dependency| dependency.as_str())
+          .collect::<HashSet<_>>();
+        (*name, dependencies)
+      })
+      .collect::<IndexMap<_, _>>();
+    let mut running = FuturesUnordered::new();
+    loop {
+      let ready = pending
+        .iter()
+        .filter(|(_, dependencies)| dependencies.is_empty())
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>();
+      for name in ready {
+        pending.shift_remove(name);
+        let future = run_task(name);
+        running.push(async move { (name, future.await) });
+      }
+      let Some((name, result)) = running.next().await else {
+        break;
+      };
+      let exit_code = result?;
+      if exit_code != 0 {
+        return Ok(exit_code);
+      }
+      for dependencies in pending.values_mut() {
+        dependencies.remove(name);
+      }
+    }
+    Ok(0)
+  }
+}
+
 fn get_script_with_args(script: &str, options: &CliOptions) -> String {
   let additional_args = options
     .argv()
@@ -241,6 +453,7 @@ fn collect_env_vars() -> HashMap<String, String> {
 fn print_available_tasks(
   // order can be important, so these use an index map
   tasks_config: &IndexMap<String, deno_config::Task>,
+  cli_tasks_config: &CliTasksConfig,
   package_json_scripts: &IndexMap<String, String>,
 ) {
   eprintln!("{}", colors::green("Available tasks:"));
@@ -276,6 +489,22 @@ fn print_available_tasks(
       }
     }
     eprintln!("    {definition}");
+    if let Some(dependencies) = cli_tasks_config
+      .tasks
+      .get(key)
+      .map(|options| &options.depends_on)
+      .filter(|dependencies| is_deno && !dependencies.is_empty())
+    {
+      eprintln!(
+        "    {} {}",
+        colors::gray("depends on:"),
+        dependencies
+          .iter()
+          .map(|name| colors::cyan(name).to_string())
+          .collect::<Vec<_>>()
+          .join(", ")
+      );
+    }
     had_task = true;
   }
   if !had_task {
@@ -491,8 +720,124 @@ fn resolve_managed_npm_commands(
 
 #[cfg(test)]
 mod test {
+  use std::cell::RefCell;
+
   use super::*;
 
+  fn tasks_config(dependencies: &[(&str, &[&str])]) -> CliTasksConfig {
+    CliTasksConfig {
+      tasks: dependencies
+        .iter()
+        .map(|(name, dependencies)| {
+          let options = CliTaskOptions {
+            depends_on: dependencies.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+          };
+          (name.to_string(), options)
+        })
+        .collect(),
+    }
+  }
+
+  #[test]
+  fn task_graph_order() {
+    let config = tasks_config(&[
+      ("build", &["codegen", "check"]),
+      ("check", &["codegen"]),
+      ("codegen", &[]),
+    ]);
+    let graph = TaskGraph::build("build", &config, |_| true).unwrap();
+    assert_eq!(
+      graph.tasks.keys().copied().collect::<Vec<_>>(),
+      vec!["codegen", "check", "build"]
+    );
+
+    let graph = TaskGraph::build("lint", &config, |_| true).unwrap();
+    assert_eq!(
+      graph.tasks.keys().copied().collect::<Vec<_>>(),
+      vec!["lint"]
+    );
+  }
+
+  #[test]
+  fn task_graph_errors() {
+    let config = tasks_config(&[
+      ("build", &["codegen"]),
+      ("codegen", &["prepare"]),
+      ("prepare", &["build"]),
+      ("lint", &["lint"]),
+    ]);
+    let err = TaskGraph::build("build", &config, |_| true).err().unwrap();
+    assert_eq!(
+      err.to_string(),
+      "Task dependency cycle detected: build -> codegen -> prepare -> build"
+    );
+    let err = TaskGraph::build("lint", &config, |_| true).err().unwrap();
+    assert_eq!(
+      err.to_string(),
+      "Task dependency cycle detected: lint -> lint"
+    );
+    let err = TaskGraph::build("build", &config, |name| name != "prepare")
+      .err()
+      .unwrap();
+    assert_eq!(
+      err.to_string(),
+      "Task \"codegen\" depends on \"prepare\", which is not defined in the configuration file"
+    );
+  }
+
+  #[tokio::test]
+  async fn task_graph_run() {
+    let config = tasks_config(&[
+      ("build", &["a", "b"]),
+      ("a", &["prepare"]),
+      ("b", &["prepare"]),
+    ]);
+    let graph = TaskGraph::build("build", &config, |_| true).unwrap();
+    let events = RefCell::new(Vec::new());
+    let exit_code = graph
+      .run(|name| {
+        let events = &events;
+        async move {
+          events.borrow_mut().push(format!("start {name}"));
+          tokio::task::yield_now().await;
+          events.borrow_mut().push(format!("end {name}"));
+          Ok(0)
+        }
+        .boxed_local()
+      })
+      .await
+      .unwrap();
+    assert_eq!(exit_code, 0);
+    let events = events.take();
+    let position =
+      |event: &str| events.iter().position(|e| e == event).unwrap();
+    assert_eq!(events.len(), 8);
+    assert!(position("end prepare") < position("start a"));
+    assert!(position("end prepare") < position("start b"));
+    // the independent tasks run in parallel
+    assert!(position("start a") < position("end b"));
+    assert!(position("start b") < position("end a"));
+    assert!(position("end a") < position("start build"));
+    assert!(position("end b") < position("start build"));
+
+    // stops once a task fails
+    let events = RefCell::new(Vec::new());
+    let exit_code = graph
+      .run(|name| {
+        let events = &events;
+        async move {
+          events.borrow_mut().push(name.to_string());
+          Ok(if name == "prepare" { 3 } else { 0 })
+        }
+        .boxed_local()
+      })
+      .await
+      .unwrap();
+    assert_eq!(exit_code, 3);
+    assert_eq!(events.take(), vec!["prepare"]);
+  }
+
   #[test]
   fn test_prepend_to_path() {
     let mut env_vars = HashMap::new();
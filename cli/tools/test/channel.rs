@@ -10,7 +10,10 @@ use deno_runtime::deno_io::pipe;
 use deno_runtime::deno_io::AsyncPipeRead;
 use deno_runtime::deno_io::PipeRead;
 use deno_runtime::deno_io::PipeWrite;
+use indexmap::IndexMap;
 use memmem::Searcher;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::fmt::Display;
 use std::future::Future;
 use std::io::Write;
@@ -78,7 +81,10 @@ pub fn create_test_event_channel() -> (TestEventSenderFactory, TestEventReceiver
       sender,
       worker_id: Default::default(),
     },
-    TestEventReceiver { receiver },
+    TestEventReceiver {
+      receiver,
+      grouping: None,
+    },
   )
 }
 
@@ -94,12 +100,112 @@ pub fn create_single_test_event_channel(
 /// streams may be interleaved.
 pub struct TestEventReceiver {
   receiver: UnboundedReceiver<(usize, TestEvent)>,
+  grouping: Option<WorkerEventGrouping>,
 }
 
 impl TestEventReceiver {
+  /// Instead of interleaving them, receive the events of each worker as one
+  /// contiguous group, ending with [`TestEvent::Completed`]. Events of other
+  /// workers are held back until the current worker has completed.
+  pub fn group_by_worker(&mut self) {
+    self.grouping.get_or_insert_with(Default::default);
+  }
+
   /// Receive a single test event, or `None` if no workers are alive.
   pub async fn recv(&mut self) -> Option<(usize, TestEvent)> {
-    self.receiver.recv().await
+    let Some(grouping) = &mut self.grouping else {
+      return self.receiver.recv().await;
+    };
+    loop {
+      if let Some(event) = grouping.ready.pop_front() {
+        return Some(event);
+      }
+      match self.receiver.recv().await {
+        Some((id, event)) => grouping.push(id, event),
+        // Release whatever is left of the workers that never completed.
+        None => {
+          if !grouping.activate_next() {
+            return None;
+          }
+        }
+      }
+    }
+  }
+}
+
+#[derive(Default)]
+struct WorkerEventGrouping {
+  /// The worker whose events are currently passed through.
+  active: Option<usize>,
+  /// Events held back per worker, in the order the workers started sending,
+  /// and whether the worker has completed.
+  pending: IndexMap<usize, (Vec<TestEvent>, bool)>,
+  /// Workers whose groups have been released.
+  completed: HashSet<usize>,
+  /// Events ready to be received.
+  ready: VecDeque<(usize, TestEvent)>,
+  /// Whether the held back events were flushed because the run is ending,
+  /// after which events aren't grouped anymore.
+  flushed: bool,
+}
+
+impl WorkerEventGrouping {
+  fn push(&mut self, id: usize, event: TestEvent) {
+    // These are not sent by a worker and end the whole run, so everything
+    // held back is released first for it to be reported.
+    if matches!(event, TestEvent::Sigint | TestEvent::ForceEndReport) {
+      self.flush();
+    }
+    // Output sent after a worker completed can't be grouped anymore.
+    if self.flushed || self.completed.contains(&id) {
+      self.ready.push_back((id, event));
+      return;
+    }
+    if self.active.is_none() && !self.pending.contains_key(&id) {
+      self.active = Some(id);
+    }
+    let completed = matches!(event, TestEvent::Completed);
+    if self.active == Some(id) {
+      self.ready.push_back((id, event));
+      if completed {
+        self.completed.insert(id);
+        self.active = None;
+        while self.activate_next() && self.active.is_none() {}
+      }
+    } else {
+      let (events, is_completed) = self.pending.entry(id).or_default();
+      *is_completed |= completed;
+      events.push(event);
+    }
+  }
+
+  /// Releases the held back events of all the workers and stops grouping.
+  fn flush(&mut self) {
+    for (id, (events, _)) in self.pending.drain(..) {
+      self
+        .ready
+        .extend(events.into_iter().map(|event| (id, event)));
+    }
+    self.active = None;
+    self.flushed = true;
+  }
+
+  /// Releases the held back events of the next worker, making it the active
+  /// one if it hasn't completed yet. Returns `false` if there are none.
+  fn activate_next(&mut self) -> bool {
+    let Some((id, (events, completed))) = self.pending.shift_remove_index(0)
+    else {
+      return false;
+    };
+    self
+      .ready
+      .extend(events.into_iter().map(|event| (id, event)));
+    if completed {
+      self.completed.insert(id);
+    } else {
+      self.active = Some(id);
+    }
+    true
   }
 }
 
@@ -697,4 +803,65 @@ mod tests {
       });
     });
   }
+
+  /// Test that events of concurrently running workers are received per worker.
+  #[test]
+  fn group_by_worker() {
+    let mut grouping = WorkerEventGrouping::default();
+    grouping.push(1, TestEvent::Wait(1));
+    grouping.push(2, TestEvent::Wait(2));
+    grouping.push(3, TestEvent::Wait(3));
+    grouping.push(3, TestEvent::Completed);
+    grouping.push(1, TestEvent::Result(1, TestResult::Ok, 0));
+    grouping.push(2, TestEvent::Result(2, TestResult::Ok, 0));
+    grouping.push(1, TestEvent::Completed);
+    grouping.push(2, TestEvent::Completed);
+    grouping.push(3, TestEvent::Output(TestStdioStream::Stdout, vec![]));
+
+    let events = grouping
+      .ready
+      .into_iter()
+      .map(|(id, event)| format!("{id} {event:?}"))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      events,
+      vec![
+        "1 Wait(1)",
+        "1 Result(1, Ok, 0)",
+        "1 Completed",
+        "2 Wait(2)",
+        "2 Result(2, Ok, 0)",
+        "2 Completed",
+        "3 Wait(3)",
+        "3 Completed",
+        "3 Output(Stdout, [])",
+      ]
+    );
+  }
+  /// Test that the held back events are released when the run is cancelled.
+  #[test]
+  fn group_by_worker_flushes_on_sigint() {
+    let mut grouping = WorkerEventGrouping::default();
+    grouping.push(1, TestEvent::Wait(1));
+    grouping.push(2, TestEvent::Wait(2));
+    grouping.push(2, TestEvent::Output(TestStdioStream::Stdout, vec![]));
+    grouping.push(0, TestEvent::Sigint);
+    grouping.push(2, TestEvent::Result(2, TestResult::Ok, 0));
+
+    let events = grouping
+      .ready
+      .into_iter()
+      .map(|(id, event)| format!("{id} {event:?}"))
+      .collect::<Vec<_>>();
+    assert_eq!(
+      events,
+      vec![
+        "1 Wait(1)",
+        "2 Wait(2)",
+        "2 Output(Stdout, [])",
+        "0 Sigint",
+        "2 Result(2, Ok, 0)",
+      ]
+    );
+  }
 }
@@ -8,6 +8,7 @@ use phf::phf_map;
 use std::borrow::Cow;
 use std::ops::AddAssign;
 
+use super::resource_trace::ResourceCreationTracer;
 use super::*;
 
 pub fn to_relative_path_or_remote_url(cwd: &Url, path_or_url: &str) -> String {
@@ -74,20 +75,26 @@ fn abbreviate_test_error(js_error: &JsError) -> JsError {
 //   - if stack trace consists of mixed user and internal code, the frames
 //     below the first user code frame are filtered out
 //   - if stack trace consists only of internal code it is preserved as is
-pub fn format_test_error(js_error: &JsError) -> String {
+//
+// File locations are wrapped in OSC 8 hyperlinks if `hyperlinks` is set, which
+// is only the case for console output written to a terminal supporting them.
+pub fn format_test_error(js_error: &JsError, hyperlinks: bool) -> String {
   let mut js_error = abbreviate_test_error(js_error);
   js_error.exception_message = js_error
     .exception_message
     .trim_start_matches("Uncaught ")
     .to_string();
-  format_js_error(&js_error)
+  format_js_error_with_hyperlinks(&js_error, hyperlinks)
 }
 
 pub fn format_sanitizer_diff(
   diff: RuntimeActivityDiff,
+  resource_tracer: Option<&ResourceCreationTracer>,
 ) -> (Vec<String>, Vec<String>) {
-  let (mut messages, trailers) = format_sanitizer_accum(diff.appeared, true);
-  let disappeared = format_sanitizer_accum(diff.disappeared, false);
+  let (mut messages, trailers) =
+    format_sanitizer_accum(diff.appeared, true, resource_tracer);
+  let disappeared =
+    format_sanitizer_accum(diff.disappeared, false, resource_tracer);
   messages.extend(disappeared.0);
   messages.sort();
   let mut trailers = BTreeSet::from_iter(trailers);
@@ -98,11 +105,12 @@ pub fn format_sanitizer_diff(
 fn format_sanitizer_accum(
   activities: Vec<RuntimeActivity>,
   appeared: bool,
+  resource_tracer: Option<&ResourceCreationTracer>,
 ) -> (Vec<String>, Vec<String>) {
   // Aggregate the sanitizer information
   let mut accum = HashMap::new();
   for activity in activities {
-    let item = format_sanitizer_accum_item(activity);
+    let item = format_sanitizer_accum_item(activity, resource_tracer);
     accum.entry(item).or_insert(0).add_assign(1);
   }
 
@@ -114,7 +122,13 @@ fn format_sanitizer_accum(
       let hint = resource_close_hint(&item_name);
 
       let value = if appeared {
-        format!("{name} was {action1} during the test, but not {action2} during the test. {hint}")
+        let mut value = format!("{name} was {action1} during the test, but not {action2} during the test. {hint}");
+        if let Some(trace) = trace {
+          value += &format!(" The resource was created here:\n{trace}");
+        } else {
+          needs_trace_leaks = true;
+        }
+        value
       } else {
         format!("{name} was {action1} before the test started, but was {action2} during the test. \
           Do not close resources in a test that were not created during that test.")
@@ -198,19 +212,26 @@ fn format_sanitizer_accum(
 
 fn format_sanitizer_accum_item(
   activity: RuntimeActivity,
-) -> (
-  RuntimeActivityType,
-  Cow<'static, str>,
-  Option<RuntimeActivityTrace>,
-) {
+  resource_tracer: Option<&ResourceCreationTracer>,
+) -> (RuntimeActivityType, Cow<'static, str>, Option<String>) {
   let activity_type = activity.activity();
+  let format_trace =
+    |trace: Option<RuntimeActivityTrace>| trace.map(|trace| trace.to_string());
   match activity {
     RuntimeActivity::AsyncOp(_, trace, name) => {
+      (activity_type, name.into(), format_trace(trace))
+    }
+    RuntimeActivity::Resource(rid, _, name) => {
+      let trace =
+        resource_tracer.and_then(|tracer| tracer.format_resource_trace(rid));
       (activity_type, name.into(), trace)
     }
-    RuntimeActivity::Resource(_, _, name) => (activity_type, name.into(), None),
-    RuntimeActivity::Interval(_, trace) => (activity_type, "".into(), trace),
-    RuntimeActivity::Timer(_, trace) => (activity_type, "".into(), trace),
+    RuntimeActivity::Interval(_, trace) => {
+      (activity_type, "".into(), format_trace(trace))
+    }
+    RuntimeActivity::Timer(_, trace) => {
+      (activity_type, "".into(), format_trace(trace))
+    }
   }
 }
 
@@ -380,7 +401,7 @@ mod tests {
     ($name:ident, $appeared:literal, [$($activity:expr),*], $expected:literal) => {
       #[test]
       fn $name() {
-        let (leaks, trailer_notes) = super::format_sanitizer_accum(vec![$($activity),*], $appeared);
+        let (leaks, trailer_notes) = super::format_sanitizer_accum(vec![$($activity),*], $appeared, None);
         let mut output = String::new();
         for leak in leaks {
           output += &format!(" - {leak}\n");
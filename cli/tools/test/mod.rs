@@ -4,6 +4,8 @@ use crate::args::CliOptions;
 use crate::args::Flags;
 use crate::args::TestFlags;
 use crate::args::TestReporterConfig;
+use crate::args::TestShard;
+use crate::cache::FastInsecureHasher;
 use crate::colors;
 use crate::display;
 use crate::factory::CliFactory;
@@ -14,6 +16,7 @@ use crate::graph_util::has_graph_root_local_dependent_changed;
 use crate::module_loader::ModuleLoadPreparer;
 use crate::ops;
 use crate::util::file_watcher;
+use crate::util::fs::canonicalize_path;
 use crate::util::fs::collect_specifiers;
 use crate::util::fs::WalkEntry;
 use crate::util::path::get_extension;
@@ -38,6 +41,7 @@ use deno_core::futures::stream;
 use deno_core::futures::FutureExt;
 use deno_core::futures::StreamExt;
 use deno_core::located_script_name;
+use deno_core::parking_lot::Mutex;
 use deno_core::serde_v8;
 use deno_core::stats::RuntimeActivity;
 use deno_core::stats::RuntimeActivityDiff;
@@ -52,9 +56,13 @@ use deno_core::v8;
 use deno_core::ModuleSpecifier;
 use deno_core::OpState;
 use deno_core::PollEventLoopOptions;
+use deno_graph::ModuleGraph;
+use deno_runtime::deno_fs::temp_cleanup::SigintListener;
 use deno_runtime::deno_io::Stdio;
 use deno_runtime::deno_io::StdioPipe;
-use deno_runtime::fmt_errors::format_js_error;
+use deno_runtime::fmt_errors::format_js_error_with_hyperlinks;
+use deno_runtime::fmt_errors::supports_hyperlinks;
+use deno_runtime::fs_util::specifier_to_file_path;
 use deno_runtime::permissions::Permissions;
 use deno_runtime::permissions::PermissionsContainer;
 use deno_runtime::tokio_util::create_and_run_current_thread;
@@ -74,23 +82,29 @@ use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::HashMap;
 use std::collections::HashSet;
+use std::ffi::c_void;
 use std::fmt::Write as _;
 use std::future::poll_fn;
 use std::io::Write;
 use std::num::NonZeroUsize;
 use std::path::Path;
+use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
+use std::sync::mpsc::RecvTimeoutError;
 use std::sync::Arc;
 use std::task::Poll;
 use std::time::Duration;
 use std::time::Instant;
 use tokio::signal;
+use tokio_util::sync::CancellationToken;
 
 mod channel;
 pub mod fmt;
 pub mod reporters;
+pub mod resource_trace;
+pub mod snapshot;
 
 pub use channel::create_single_test_event_channel;
 pub use channel::create_test_event_channel;
@@ -99,12 +113,16 @@ pub use channel::TestEventSender;
 pub use channel::TestEventWorkerSender;
 use fmt::format_sanitizer_diff;
 pub use fmt::format_test_error;
+use fmt::to_relative_path_or_remote_url;
 use reporters::CompoundTestReporter;
 use reporters::DotTestReporter;
+use reporters::JsonTestReporter;
 use reporters::JunitTestReporter;
 use reporters::PrettyTestReporter;
 use reporters::TapTestReporter;
 use reporters::TestReporter;
+use resource_trace::ResourceCreationTracer;
+use snapshot::SnapshotState;
 
 /// How many times we're allowed to spin the event loop before considering something a leak.
 const MAX_SANITIZER_LOOP_SPINS: usize = 16;
@@ -201,6 +219,33 @@ impl TestFilter {
   }
 }
 
+/// Selects the tests of a file that belong to a shard of the test run.
+///
+/// Files are spread over the shards by the hash of their path relative to the
+/// current directory and the tests of a file by their index, so that every
+/// machine makes the same assignment and large files are split up.
+#[derive(Debug, Clone)]
+pub struct TestShardFilter {
+  shard: TestShard,
+  file_hash: u64,
+}
+
+impl TestShardFilter {
+  pub fn new(shard: TestShard, cwd: &Url, specifier: &ModuleSpecifier) -> Self {
+    let path = to_relative_path_or_remote_url(cwd, specifier.as_str());
+    Self {
+      shard,
+      file_hash: FastInsecureHasher::hash(path),
+    }
+  }
+
+  pub fn includes(&self, test_index: usize) -> bool {
+    let count = self.shard.count.get() as u64;
+    let shard_index = self.file_hash.wrapping_add(test_index as u64) % count;
+    shard_index == self.shard.index.get() as u64 - 1
+  }
+}
+
 #[derive(Debug, Clone, PartialEq, Deserialize, Eq, Hash)]
 #[serde(rename_all = "camelCase")]
 pub struct TestLocation {
@@ -265,6 +310,10 @@ pub struct TestDescription {
   pub location: TestLocation,
   pub sanitize_ops: bool,
   pub sanitize_resources: bool,
+  /// Overrides the `--retries` flag for this test.
+  pub retries: Option<u32>,
+  /// Overrides the `--timeout` flag for this test, in milliseconds.
+  pub timeout: Option<u64>,
 }
 
 /// May represent a failure of a test or test step.
@@ -296,6 +345,7 @@ pub enum TestFailure {
   FailedSteps(usize),
   IncompleteSteps,
   Leaked(Vec<String>, Vec<String>), // Details, trailer notes
+  TimedOut(u64, Option<String>, Vec<String>, Vec<String>), // Timeout in ms, stack it was stuck at, pending activity, trailer notes
   // The rest are for steps only.
   Incomplete,
   OverlapsWithSanitizers(IndexSet<String>), // Long names of overlapped tests
@@ -306,7 +356,7 @@ impl std::fmt::Display for TestFailure {
   fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
     match self {
       TestFailure::JsError(js_error) => {
-        write!(f, "{}", format_test_error(js_error))
+        write!(f, "{}", format_test_error(js_error, false))
       }
       TestFailure::FailedSteps(1) => write!(f, "1 test step failed."),
       TestFailure::FailedSteps(n) => write!(f, "{n} test steps failed."),
@@ -329,6 +379,23 @@ impl std::fmt::Display for TestFailure {
         }
         Ok(())
       }
+      TestFailure::TimedOut(timeout, stack, pending, trailer_notes) => {
+        write!(f, "Test timed out after {timeout}ms")?;
+        if let Some(stack) = stack {
+          write!(f, " while running:\n{}", stack.trim_end())?;
+        } else if !pending.is_empty() {
+          write!(f, " while waiting for:")?;
+          for detail in pending {
+            write!(f, "\n  - {}", detail)?;
+          }
+          for trailer in trailer_notes {
+            write!(f, "\n{}", trailer)?;
+          }
+        } else {
+          write!(f, ".")?;
+        }
+        Ok(())
+      }
       TestFailure::OverlapsWithSanitizers(long_names) => {
         write!(f, "Started test step while another test step with sanitizers was running:")?;
         for long_name in long_names {
@@ -358,6 +425,9 @@ impl TestFailure {
       }
       TestFailure::Incomplete => "Didn't complete before parent".to_string(),
       TestFailure::Leaked(_, _) => "Leaks detected".to_string(),
+      TestFailure::TimedOut(timeout, _, _, _) => {
+        format!("Timed out after {timeout}ms")
+      }
       TestFailure::OverlapsWithSanitizers(_) => {
         "Started test step while another test step with sanitizers was running"
           .to_string()
@@ -455,6 +525,8 @@ pub enum TestEvent {
   Wait(usize),
   Output(TestStdioStream, Vec<u8>),
   Result(usize, TestResult, u64),
+  /// A test failed and is run again.
+  Retry(usize, TestFailure, u64),
   UncaughtError(String, Box<JsError>),
   StepRegister(TestStepDescription),
   StepWait(usize),
@@ -477,6 +549,7 @@ impl TestEvent {
       self,
       TestEvent::Plan(..)
         | TestEvent::Result(..)
+        | TestEvent::Retry(..)
         | TestEvent::StepWait(..)
         | TestEvent::StepResult(..)
         | TestEvent::UncaughtError(..)
@@ -499,6 +572,14 @@ pub struct TestSummary {
   pub measured: usize,
   pub failures: Vec<(TestFailureDescription, TestFailure)>,
   pub uncaught_errors: Vec<(String, Box<JsError>)>,
+  /// Tests that passed after being retried, with the number of retries.
+  pub flaky: Vec<(TestFailureDescription, usize)>,
+  /// Number of retries of the tests that are still running.
+  retries: HashMap<usize, usize>,
+  /// Step results of the current attempt of the tests that are still
+  /// running, so they can be discarded when the test is retried.
+  #[serde(skip)]
+  attempt_steps: HashMap<usize, Vec<(usize, TestStepResult)>>,
 }
 
 #[derive(Debug, Clone)]
@@ -511,12 +592,20 @@ struct TestSpecifiersOptions {
   specifier: TestSpecifierOptions,
   reporter: TestReporterConfig,
   junit_path: Option<String>,
+  shard: Option<TestShard>,
 }
 
 #[derive(Debug, Default, Clone)]
 pub struct TestSpecifierOptions {
   pub shuffle: Option<u64>,
   pub filter: TestFilter,
+  pub shard: Option<TestShardFilter>,
+  /// Number of times a failing test is run again.
+  pub retries: u32,
+  /// Timeout of each test in milliseconds.
+  pub timeout: Option<u64>,
+  /// Whether `TestContext.assertSnapshot()` overwrites mismatching snapshots.
+  pub update_snapshots: bool,
   pub trace_leaks: bool,
 }
 
@@ -534,6 +623,80 @@ impl TestSummary {
       measured: 0,
       failures: Vec::new(),
       uncaught_errors: Vec::new(),
+      flaky: Vec::new(),
+      retries: HashMap::new(),
+      attempt_steps: HashMap::new(),
+    }
+  }
+
+  /// Record the result of a test step.
+  fn add_step_result(
+    &mut self,
+    description: &TestStepDescription,
+    result: &TestStepResult,
+    tests: &IndexMap<usize, TestDescription>,
+    test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    match result {
+      TestStepResult::Ok => {
+        self.passed_steps += 1;
+      }
+      TestStepResult::Ignored => {
+        self.ignored_steps += 1;
+      }
+      TestStepResult::Failed(failure) => {
+        self.failed_steps += 1;
+        self.failures.push((
+          TestFailureDescription {
+            id: description.id,
+            name: reporters::format_test_step_ancestry(
+              description,
+              tests,
+              test_steps,
+            ),
+            origin: description.origin.clone(),
+            location: description.location.clone(),
+          },
+          failure.clone(),
+        ))
+      }
+    }
+    self
+      .attempt_steps
+      .entry(description.root_id)
+      .or_default()
+      .push((description.id, result.clone()));
+  }
+
+  /// Record a failed run of a test that is going to be retried. The results
+  /// of the steps of the failed run are discarded.
+  fn add_retry(&mut self, id: usize) {
+    *self.retries.entry(id).or_default() += 1;
+    for (step_id, result) in self.attempt_steps.remove(&id).unwrap_or_default()
+    {
+      match result {
+        TestStepResult::Ok => self.passed_steps -= 1,
+        TestStepResult::Ignored => self.ignored_steps -= 1,
+        TestStepResult::Failed(_) => {
+          self.failed_steps -= 1;
+          self.failures.retain(|(d, _)| d.id != step_id);
+        }
+      }
+    }
+  }
+
+  /// Record the test as flaky if it passed after being retried.
+  fn add_retried_result(
+    &mut self,
+    description: &TestDescription,
+    result: &TestResult,
+  ) {
+    self.attempt_steps.remove(&description.id);
+    let Some(retries) = self.retries.remove(&description.id) else {
+      return;
+    };
+    if matches!(result, TestResult::Ok) {
+      self.flaky.push((description.into(), retries));
     }
   }
 
@@ -543,13 +706,11 @@ impl TestSummary {
 }
 
 fn get_test_reporter(options: &TestSpecifiersOptions) -> Box<dyn TestReporter> {
-  let parallel = options.concurrent_jobs.get() > 1;
   let reporter: Box<dyn TestReporter> = match &options.reporter {
     TestReporterConfig::Dot => {
       Box::new(DotTestReporter::new(options.cwd.clone()))
     }
     TestReporterConfig::Pretty => Box::new(PrettyTestReporter::new(
-      parallel,
       options.log_level != Some(Level::Error),
       options.filter,
       false,
@@ -562,6 +723,9 @@ fn get_test_reporter(options: &TestSpecifiersOptions) -> Box<dyn TestReporter> {
       options.cwd.clone(),
       options.concurrent_jobs > NonZeroUsize::new(1).unwrap(),
     )),
+    TestReporterConfig::Json => {
+      Box::new(JsonTestReporter::new(options.cwd.clone()))
+    }
   };
 
   if let Some(junit_path) = &options.junit_path {
@@ -599,11 +763,29 @@ async fn configure_main_worker(
   if options.trace_leaks {
     worker.execute_script_static(
       located_script_name!(),
-      "Deno[Deno.internal].core.setLeakTracingEnabled(true);",
+      concat!(
+        "Deno[Deno.internal].core.setLeakTracingEnabled(true);",
+        "Deno[Deno.internal].enableResourceCreationTracing();",
+      ),
     )?;
   }
   let res = worker.execute_side_module_possibly_with_npm().await;
   let mut worker = worker.into_main_worker();
+  if options.trace_leaks {
+    let op_state = worker.js_runtime.op_state();
+    let mut op_state = op_state.borrow_mut();
+    if !op_state.has::<ResourceCreationTracer>() {
+      op_state.put(ResourceCreationTracer::default());
+    }
+  }
+  worker
+    .js_runtime
+    .op_state()
+    .borrow_mut()
+    .put(SnapshotState::new(
+      specifier.clone(),
+      options.update_snapshots,
+    ));
   match res {
     Ok(()) => Ok(()),
     Err(error) => {
@@ -693,6 +875,14 @@ async fn test_specifier_inner(
   run_tests_for_worker(worker, &specifier, &options, &fail_fast_tracker)
     .await?;
 
+  // Write any snapshots that were created or updated by the tests.
+  worker
+    .js_runtime
+    .op_state()
+    .borrow_mut()
+    .borrow_mut::<SnapshotState>()
+    .save()?;
+
   // Ignore `defaultPrevented` of the `beforeunload` event. We don't allow the
   // event loop to continue beyond what's needed to await results.
   worker.dispatch_beforeunload_event()?;
@@ -800,7 +990,7 @@ async fn run_tests_for_worker_inner(
   // Build the test plan in a single pass
   let mut tests_to_run = Vec::with_capacity(tests.len());
   let mut used_only = false;
-  for ((_, d), f) in tests.tests.iter().zip(test_functions) {
+  for (i, ((_, d), f)) in tests.tests.iter().zip(test_functions).enumerate() {
     if !options.filter.includes(&d.name) {
       continue;
     }
@@ -816,9 +1006,22 @@ async fn run_tests_for_worker_inner(
       used_only = true;
       tests_to_run.clear();
     }
-    tests_to_run.push((d, f));
+    tests_to_run.push((i, d, f));
   }
 
+  // Shard after the "only" filtering, so each shard runs its part of the
+  // tests which actually run.
+  let mut tests_to_run = tests_to_run
+    .into_iter()
+    .filter(|(i, _, _)| {
+      options
+        .shard
+        .as_ref()
+        .map_or(true, |shard| shard.includes(*i))
+    })
+    .map(|(_, d, f)| (d, f))
+    .collect::<Vec<_>>();
+
   if let Some(seed) = options.shuffle {
     tests_to_run.shuffle(&mut SmallRng::seed_from_u64(seed));
   }
@@ -836,6 +1039,12 @@ async fn run_tests_for_worker_inner(
   let mut had_uncaught_error = false;
   let stats = worker.js_runtime.runtime_activity_stats_factory();
   let ops = worker.js_runtime.op_names();
+  let resource_tracer = worker
+    .js_runtime
+    .op_state()
+    .borrow()
+    .try_borrow::<ResourceCreationTracer>()
+    .cloned();
 
   // These particular ops may start and stop independently of tests, so we just filter them out
   // completely.
@@ -870,7 +1079,7 @@ async fn run_tests_for_worker_inner(
       .or_insert(1);
   }
 
-  for (desc, function) in tests_to_run.into_iter() {
+  'tests: for (desc, function) in tests_to_run.into_iter() {
     if fail_fast_tracker.should_stop() {
       break;
     }
@@ -901,95 +1110,146 @@ async fn run_tests_for_worker_inner(
     }
     send_test_event(&state_rc, TestEvent::Wait(desc.id))?;
 
-    // Poll event loop once, to allow all ops that are already resolved, but haven't
-    // responded to settle.
-    // TODO(mmastrac): we should provide an API to poll the event loop until no further
-    // progress is made.
-    poll_event_loop(worker).await?;
+    let max_retries = desc.retries.unwrap_or(options.retries);
+    let timeout = desc.timeout.or(options.timeout);
+    let mut attempt = 0;
+    loop {
+      // Poll event loop once, to allow all ops that are already resolved, but haven't
+      // responded to settle.
+      // TODO(mmastrac): we should provide an API to poll the event loop until no further
+      // progress is made.
+      poll_event_loop(worker).await?;
+
+      // We always capture stats, regardless of sanitization state
+      let before = stats.clone().capture(&filter);
+
+      let earlier = Instant::now();
+      let watchdog = timeout.map(|timeout| {
+        TestTimeoutWatchdog::start(
+          worker.js_runtime.v8_isolate().thread_safe_handle(),
+          Duration::from_millis(timeout),
+        )
+      });
+      let terminator = fail_fast_tracker
+        .terminate_on_stop(worker.js_runtime.v8_isolate().thread_safe_handle());
+      let call = worker.js_runtime.call(&function);
+      let run = worker
+        .js_runtime
+        .with_event_loop_promise(call, PollEventLoopOptions::default());
+      let run = async {
+        match timeout {
+          Some(timeout) => {
+            tokio::time::timeout(Duration::from_millis(timeout), run)
+              .await
+              .ok()
+          }
+          None => Some(run.await),
+        }
+      };
+      // Stop the test as soon as tests in other modules have failed enough
+      // times, rather than waiting for it to complete.
+      let (outcome, cancelled) = tokio::select! {
+        outcome = run => (outcome, false),
+        _ = fail_fast_tracker.stopped() => (None, true),
+      };
+      drop(terminator);
+      // A test that was running synchronous code when the run stopped ends
+      // with the error of its terminated execution.
+      let cancelled = cancelled || fail_fast_tracker.should_stop();
+      let stuck_stack = watchdog.and_then(|watchdog| watchdog.finish());
+      if stuck_stack.is_some() || cancelled {
+        worker.js_runtime.v8_isolate().cancel_terminate_execution();
+      }
+      if cancelled {
+        // The test was abandoned mid-way, so its activity isn't sanitized.
+        let elapsed = earlier.elapsed().as_millis() as u64;
+        send_test_event(
+          &state_rc,
+          TestEvent::Result(desc.id, TestResult::Cancelled, elapsed),
+        )?;
+        break 'tests;
+      }
+      let mut result = match outcome {
+        Some(Ok(result)) if stuck_stack.is_none() => {
+          let scope = &mut worker.js_runtime.handle_scope();
+          let result = v8::Local::new(scope, result);
+          serde_v8::from_v8::<TestResult>(scope, result)?
+        }
+        Some(Err(error)) if stuck_stack.is_none() => {
+          if error.is::<JsError>() {
+            send_test_event(
+              &state_rc,
+              TestEvent::UncaughtError(
+                specifier.to_string(),
+                Box::new(error.downcast::<JsError>().unwrap()),
+              ),
+            )?;
+            fail_fast_tracker.add_failure();
+            send_test_event(
+              &state_rc,
+              TestEvent::Result(desc.id, TestResult::Cancelled, 0),
+            )?;
+            had_uncaught_error = true;
+            break;
+          } else {
+            return Err(error);
+          }
+        }
+        // The test timed out, either while waiting on async work or after
+        // being interrupted running synchronous code.
+        _ => {
+          let after = stats.clone().capture(&filter);
+          let (pending, trailer_notes) = format_sanitizer_diff(
+            RuntimeActivityStats::diff(&before, &after),
+            resource_tracer.as_ref(),
+          );
+          TestResult::Failed(TestFailure::TimedOut(
+            timeout.unwrap_or_default(),
+            stuck_stack.filter(|stack| !stack.is_empty()),
+            pending,
+            trailer_notes,
+          ))
+        }
+      };
 
-    // We always capture stats, regardless of sanitization state
-    let before = stats.clone().capture(&filter);
+      // Check the result before we check for leaks
+      if !matches!(result, TestResult::Failed(_)) {
+        // Await activity stabilization
+        if let Some(diff) = wait_for_activity_to_stabilize(
+          worker,
+          &stats,
+          &filter,
+          &top_level,
+          before,
+          desc.sanitize_ops,
+          desc.sanitize_resources,
+        )
+        .await?
+        {
+          let (formatted, trailer_notes) =
+            format_sanitizer_diff(diff, resource_tracer.as_ref());
+          if !formatted.is_empty() {
+            result =
+              TestResult::Failed(TestFailure::Leaked(formatted, trailer_notes));
+          }
+        }
+      }
 
-    let earlier = Instant::now();
-    let call = worker.js_runtime.call(&function);
-    let result = match worker
-      .js_runtime
-      .with_event_loop_promise(call, PollEventLoopOptions::default())
-      .await
-    {
-      Ok(r) => r,
-      Err(error) => {
-        if error.is::<JsError>() {
-          send_test_event(
-            &state_rc,
-            TestEvent::UncaughtError(
-              specifier.to_string(),
-              Box::new(error.downcast::<JsError>().unwrap()),
-            ),
-          )?;
-          fail_fast_tracker.add_failure();
+      let elapsed = earlier.elapsed().as_millis() as u64;
+      if let TestResult::Failed(failure) = &result {
+        if attempt < max_retries {
+          attempt += 1;
           send_test_event(
             &state_rc,
-            TestEvent::Result(desc.id, TestResult::Cancelled, 0),
+            TestEvent::Retry(desc.id, failure.clone(), elapsed),
           )?;
-          had_uncaught_error = true;
           continue;
-        } else {
-          return Err(error);
         }
-      }
-    };
-
-    // Check the result before we check for leaks
-    let result = {
-      let scope = &mut worker.js_runtime.handle_scope();
-      let result = v8::Local::new(scope, result);
-      serde_v8::from_v8::<TestResult>(scope, result)?
-    };
-    if matches!(result, TestResult::Failed(_)) {
-      fail_fast_tracker.add_failure();
-      let elapsed = earlier.elapsed().as_millis();
-      send_test_event(
-        &state_rc,
-        TestEvent::Result(desc.id, result, elapsed as u64),
-      )?;
-      continue;
-    }
-
-    // Await activity stabilization
-    if let Some(diff) = wait_for_activity_to_stabilize(
-      worker,
-      &stats,
-      &filter,
-      &top_level,
-      before,
-      desc.sanitize_ops,
-      desc.sanitize_resources,
-    )
-    .await?
-    {
-      let (formatted, trailer_notes) = format_sanitizer_diff(diff);
-      if !formatted.is_empty() {
-        let failure = TestFailure::Leaked(formatted, trailer_notes);
         fail_fast_tracker.add_failure();
-        let elapsed = earlier.elapsed().as_millis();
-        send_test_event(
-          &state_rc,
-          TestEvent::Result(
-            desc.id,
-            TestResult::Failed(failure),
-            elapsed as u64,
-          ),
-        )?;
-        continue;
       }
+      send_test_event(&state_rc, TestEvent::Result(desc.id, result, elapsed))?;
+      break;
     }
-
-    let elapsed = earlier.elapsed().as_millis();
-    send_test_event(
-      &state_rc,
-      TestEvent::Result(desc.id, result, elapsed as u64),
-    )?;
   }
   Ok(())
 }
@@ -1050,6 +1310,100 @@ fn is_empty(
   map.is_empty()
 }
 
+/// Stops a test that is still running when its timeout elapses. A test that
+/// is waiting on async work is simply no longer polled, but one that is stuck
+/// running synchronous code has to be interrupted: the stack it is running is
+/// sampled and its execution is terminated.
+struct TestTimeoutWatchdog {
+  state: Arc<TestTimeoutState>,
+  cancel: Option<std::sync::mpsc::Sender<()>>,
+}
+
+struct TestTimeoutState {
+  running: AtomicBool,
+  stack: Mutex<Option<String>>,
+}
+
+impl TestTimeoutWatchdog {
+  fn start(isolate_handle: v8::IsolateHandle, timeout: Duration) -> Self {
+    let state = Arc::new(TestTimeoutState {
+      running: AtomicBool::new(true),
+      stack: Mutex::new(None),
+    });
+    let (cancel, cancelled) = std::sync::mpsc::channel::<()>();
+    let interrupt_state = state.clone();
+    std::thread::spawn(move || {
+      if let Err(RecvTimeoutError::Timeout) = cancelled.recv_timeout(timeout) {
+        let data = Arc::into_raw(interrupt_state) as *mut c_void;
+        if !isolate_handle.request_interrupt(interrupt_timed_out_test, data) {
+          // SAFETY: The isolate is gone, so the callback will never take
+          // ownership of `data`.
+          drop(unsafe { Arc::from_raw(data as *const TestTimeoutState) });
+        }
+      }
+    });
+    Self {
+      state,
+      cancel: Some(cancel),
+    }
+  }
+
+  /// Stops the watchdog, returning the sampled stack if the test had to be
+  /// interrupted.
+  fn finish(mut self) -> Option<String> {
+    self.state.running.store(false, Ordering::SeqCst);
+    self.cancel.take();
+    self.state.stack.lock().take()
+  }
+}
+
+extern "C" fn interrupt_timed_out_test(
+  isolate: &mut v8::Isolate,
+  data: *mut c_void,
+) {
+  // SAFETY: `data` was created with `Arc::into_raw()` by the watchdog.
+  let state = unsafe { Arc::from_raw(data as *const TestTimeoutState) };
+  // The interrupt only runs once JS is executing again, by then the test
+  // may have completed.
+  if !state.running.load(Ordering::SeqCst) {
+    return;
+  }
+  let scope = &mut v8::HandleScope::new(isolate);
+  let mut stack = String::new();
+  if let Some(trace) = v8::StackTrace::current_stack_trace(scope, 10) {
+    for i in 0..trace.get_frame_count() {
+      let Some(frame) = trace.get_frame(scope, i) else {
+        continue;
+      };
+      let file_name = frame
+        .get_script_name_or_source_url(scope)
+        .map(|name| name.to_rust_string_lossy(scope))
+        .unwrap_or_default();
+      if file_name.starts_with("ext:") {
+        continue;
+      }
+      let location = format!(
+        "{}:{}:{}",
+        file_name,
+        frame.get_line_number(),
+        frame.get_column()
+      );
+      let function_name = frame
+        .get_function_name(scope)
+        .map(|name| name.to_rust_string_lossy(scope))
+        .filter(|name| !name.is_empty());
+      _ = match function_name {
+        Some(function_name) => {
+          writeln!(stack, "    at {function_name} ({location})")
+        }
+        None => writeln!(stack, "    at {location}"),
+      };
+    }
+  }
+  *state.stack.lock() = Some(stack);
+  scope.terminate_execution();
+}
+
 async fn wait_for_activity_to_stabilize(
   worker: &mut MainWorker,
   stats: &RuntimeActivityStatsFactory,
@@ -1377,11 +1731,18 @@ async fn test_specifiers(
     specifiers
   };
 
-  let (test_event_sender_factory, receiver) = create_test_event_channel();
+  let (test_event_sender_factory, mut receiver) = create_test_event_channel();
   let concurrent_jobs = options.concurrent_jobs;
+  // Report the output of each test module in one piece, rather than
+  // interleaving it with the output of the modules running in parallel.
+  if concurrent_jobs.get() > 1 {
+    receiver.group_by_worker();
+  }
 
   let mut cancel_sender = test_event_sender_factory.weak_sender();
+  let sigint_listener = SigintListener::new();
   let sigint_handler_handle = spawn(async move {
+    let _sigint_listener = sigint_listener;
     signal::ctrl_c().await.unwrap();
     cancel_sender.send(TestEvent::Sigint).ok();
   });
@@ -1394,7 +1755,10 @@ async fn test_specifiers(
     let permissions = permissions.clone();
     let worker_sender = test_event_sender_factory.worker();
     let fail_fast_tracker = fail_fast_tracker.clone();
-    let specifier_options = options.specifier.clone();
+    let mut specifier_options = options.specifier.clone();
+    specifier_options.shard = options
+      .shard
+      .map(|shard| TestShardFilter::new(shard, &options.cwd, &specifier));
     spawn_blocking(move || {
       create_and_run_current_thread(test_specifier(
         worker_factory,
@@ -1476,6 +1840,9 @@ pub async fn report_tests(
           reporter.report_result(tests.get(&id).unwrap(), &result, elapsed);
         }
       }
+      TestEvent::Retry(id, failure, elapsed) => {
+        reporter.report_retry(tests.get(&id).unwrap(), &failure, elapsed);
+      }
       TestEvent::UncaughtError(origin, error) => {
         failed = true;
         reporter.report_uncaught_error(&origin, error);
@@ -1691,6 +2058,73 @@ async fn fetch_specifiers_with_test_mode(
   Ok(specifiers_with_mode)
 }
 
+/// Runs git in `cwd`, returning its standard output.
+fn run_git(cwd: &Path, args: &[&str]) -> Result<String, AnyError> {
+  let output = std::process::Command::new("git")
+    .args(args)
+    .current_dir(cwd)
+    .output()
+    .context("Failed to run git, which is required by --changed")?;
+  if !output.status.success() {
+    bail!(
+      "git {} failed: {}",
+      args.join(" "),
+      String::from_utf8_lossy(&output.stderr).trim()
+    );
+  }
+  Ok(String::from_utf8(output.stdout)?)
+}
+
+/// Collects the canonicalized paths of the files that were modified since
+/// `base_ref`, including uncommitted and untracked ones. Deleted files are
+/// left out as any module depending on them is affected in other ways.
+fn get_git_changed_paths(
+  cwd: &Path,
+  base_ref: &str,
+) -> Result<HashSet<PathBuf>, AnyError> {
+  let root =
+    PathBuf::from(run_git(cwd, &["rev-parse", "--show-toplevel"])?.trim_end());
+  let changed = run_git(cwd, &["diff", "--name-only", "-z", base_ref, "--"])?;
+  let untracked = run_git(
+    cwd,
+    &[
+      "ls-files",
+      "--others",
+      "--exclude-standard",
+      "--full-name",
+      "-z",
+    ],
+  )?;
+  Ok(
+    changed
+      .split('\0')
+      .chain(untracked.split('\0'))
+      .filter(|path| !path.is_empty())
+      .filter_map(|path| canonicalize_path(&root.join(path)).ok())
+      .collect(),
+  )
+}
+
+/// Keeps the test modules for `--changed` that depend on one of
+/// `changed_paths`. `graph` must have the test modules, other than the
+/// documentation ones, as roots.
+fn retain_changed_specifiers(
+  specifiers_with_mode: &mut Vec<(ModuleSpecifier, TestMode)>,
+  graph: &ModuleGraph,
+  changed_paths: &HashSet<PathBuf>,
+) {
+  specifiers_with_mode.retain(|(specifier, mode)| {
+    if *mode == TestMode::Documentation {
+      specifier_to_file_path(specifier)
+        .ok()
+        .and_then(|path| canonicalize_path(&path).ok())
+        .is_some_and(|path| changed_paths.contains(&path))
+    } else {
+      has_graph_root_local_dependent_changed(graph, specifier, changed_paths)
+    }
+  });
+}
+
 pub async fn run_tests(
   flags: Flags,
   test_flags: TestFlags,
@@ -1707,7 +2141,7 @@ pub async fn run_tests(
     Permissions::from_options(&cli_options.permissions_options()?)?;
   let log_level = cli_options.log_level();
 
-  let specifiers_with_mode = fetch_specifiers_with_test_mode(
+  let mut specifiers_with_mode = fetch_specifiers_with_test_mode(
     cli_options,
     file_fetcher,
     test_options.files.clone(),
@@ -1719,6 +2153,31 @@ pub async fn run_tests(
     return Err(generic_error("No test modules found"));
   }
 
+  if let Some(base_ref) = &test_options.changed {
+    let changed_paths =
+      get_git_changed_paths(cli_options.initial_cwd(), base_ref)?;
+    let module_graph_creator = factory.module_graph_creator().await?;
+    let graph = module_graph_creator
+      .create_graph(
+        cli_options.type_check_mode().as_graph_kind(),
+        specifiers_with_mode
+          .iter()
+          .filter(|(_, mode)| *mode != TestMode::Documentation)
+          .map(|(specifier, _)| specifier.clone())
+          .collect(),
+      )
+      .await?;
+    retain_changed_specifiers(
+      &mut specifiers_with_mode,
+      &graph,
+      &changed_paths,
+    );
+    if specifiers_with_mode.is_empty() {
+      log::info!("No test modules are affected by changes since {base_ref}");
+      return Ok(());
+    }
+  }
+
   check_specifiers(
     cli_options,
     file_fetcher,
@@ -1759,9 +2218,14 @@ pub async fn run_tests(
       filter: test_options.filter.is_some(),
       reporter: test_options.reporter,
       junit_path: test_options.junit_path,
+      shard: test_options.shard,
       specifier: TestSpecifierOptions {
         filter: TestFilter::from_flag(&test_options.filter),
         shuffle: test_options.shuffle,
+        shard: None,
+        retries: test_options.retries,
+        timeout: test_options.timeout,
+        update_snapshots: test_options.update_snapshots,
         trace_leaks: test_options.trace_leaks,
       },
     },
@@ -1780,6 +2244,7 @@ pub async fn run_tests_with_watch(
   // limitation where it doesn't unbind its own handler for the entire process
   // once a user adds one.
   spawn(async move {
+    let _sigint_listener = SigintListener::new();
     loop {
       signal::ctrl_c().await.unwrap();
       if !HAS_TEST_RUN_SIGINT_HANDLER.load(Ordering::Relaxed) {
@@ -1862,7 +2327,7 @@ pub async fn run_tests_with_watch(
         let worker_factory =
           Arc::new(factory.create_cli_main_worker_factory().await?);
         let module_load_preparer = factory.module_load_preparer().await?;
-        let specifiers_with_mode = fetch_specifiers_with_test_mode(
+        let mut specifiers_with_mode = fetch_specifiers_with_test_mode(
           &cli_options,
           file_fetcher,
           test_options.files.clone(),
@@ -1873,6 +2338,22 @@ pub async fn run_tests_with_watch(
         .filter(|(specifier, _)| test_modules_to_reload.contains(specifier))
         .collect::<Vec<(ModuleSpecifier, TestMode)>>();
 
+        if let Some(base_ref) = &test_options.changed {
+          let changed_paths =
+            get_git_changed_paths(cli_options.initial_cwd(), base_ref)?;
+          retain_changed_specifiers(
+            &mut specifiers_with_mode,
+            &graph,
+            &changed_paths,
+          );
+          if specifiers_with_mode.is_empty() {
+            log::info!(
+              "No test modules are affected by changes since {base_ref}"
+            );
+            return Ok(());
+          }
+        }
+
         check_specifiers(
           &cli_options,
           file_fetcher,
@@ -1910,9 +2391,14 @@ pub async fn run_tests_with_watch(
             filter: test_options.filter.is_some(),
             reporter: test_options.reporter,
             junit_path: test_options.junit_path,
+            shard: test_options.shard,
             specifier: TestSpecifierOptions {
               filter: TestFilter::from_flag(&test_options.filter),
               shuffle: test_options.shuffle,
+              shard: None,
+              retries: test_options.retries,
+              timeout: test_options.timeout,
+              update_snapshots: test_options.update_snapshots,
               trace_leaks: test_options.trace_leaks,
             },
           },
@@ -1934,6 +2420,7 @@ pub async fn run_tests_with_watch(
 pub struct FailFastTracker {
   max_count: Option<usize>,
   failure_count: Arc<AtomicUsize>,
+  stop_token: CancellationToken,
 }
 
 impl FailFastTracker {
@@ -1941,20 +2428,65 @@ impl FailFastTracker {
     Self {
       max_count: fail_fast.map(|v| v.into()),
       failure_count: Default::default(),
+      stop_token: Default::default(),
     }
   }
 
   pub fn add_failure(&self) -> bool {
     if let Some(max_count) = &self.max_count {
-      self
+      let failure_count = self
         .failure_count
         .fetch_add(1, std::sync::atomic::Ordering::SeqCst)
-        >= *max_count
+        + 1;
+      if failure_count == *max_count {
+        self.stop_token.cancel();
+      }
+      failure_count > *max_count
     } else {
       false
     }
   }
 
+  /// Resolves once enough tests have failed that the run should stop, which
+  /// never happens without `--fail-fast`.
+  pub async fn stopped(&self) {
+    if self.max_count.is_none() {
+      return std::future::pending().await;
+    }
+    self.stop_token.cancelled().await
+  }
+
+  /// Terminates the execution of the isolate behind `isolate_handle` once
+  /// the run is stopped, so that a test stuck in synchronous code is
+  /// cancelled as well. Returns `None` without `--fail-fast`.
+  fn terminate_on_stop(
+    &self,
+    isolate_handle: v8::IsolateHandle,
+  ) -> Option<FailFastTerminator> {
+    self.max_count?;
+    let stop_token = self.stop_token.clone();
+    let done = CancellationToken::new();
+    let thread = std::thread::spawn({
+      let done = done.clone();
+      move || {
+        let stopped = deno_core::futures::executor::block_on(async {
+          tokio::select! {
+            biased;
+            _ = done.cancelled() => false,
+            _ = stop_token.cancelled() => true,
+          }
+        });
+        if stopped {
+          isolate_handle.terminate_execution();
+        }
+      }
+    });
+    Some(FailFastTerminator {
+      done,
+      thread: Some(thread),
+    })
+  }
+
   pub fn should_stop(&self) -> bool {
     if let Some(max_count) = &self.max_count {
       self.failure_count.load(std::sync::atomic::Ordering::SeqCst) >= *max_count
@@ -1964,6 +2496,23 @@ impl FailFastTracker {
   }
 }
 
+/// Returned by [`FailFastTracker::terminate_on_stop`]. Once dropped, the
+/// isolate is no longer terminated.
+struct FailFastTerminator {
+  done: CancellationToken,
+  thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl Drop for FailFastTerminator {
+  fn drop(&mut self) {
+    self.done.cancel();
+    // Wait for the thread, so the isolate can't be terminated afterwards.
+    if let Some(thread) = self.thread.take() {
+      let _ = thread.join();
+    }
+  }
+}
+
 #[cfg(test)]
 mod inner_test {
   use std::path::Path;
@@ -2026,4 +2575,48 @@ mod inner_test {
     assert!(!is_supported_test_path(Path::new("notatest.js")));
     assert!(!is_supported_test_path(Path::new("NotAtest.ts")));
   }
+
+  #[test]
+  fn test_shard_filter_assigns_each_test_once() {
+    let cwd = Url::parse("file:///project/").unwrap();
+    let specifier = Url::parse("file:///project/foo_test.ts").unwrap();
+    let count = NonZeroUsize::new(3).unwrap();
+    let filters = (1..=3)
+      .map(|index| {
+        let shard = TestShard {
+          index: NonZeroUsize::new(index).unwrap(),
+          count,
+        };
+        TestShardFilter::new(shard, &cwd, &specifier)
+      })
+      .collect::<Vec<_>>();
+    for test_index in 0..10 {
+      let shards = filters.iter().filter(|f| f.includes(test_index)).count();
+      assert_eq!(shards, 1);
+    }
+
+    // The assignment doesn't depend on where the project is located.
+    let other_cwd = Url::parse("file:///other/").unwrap();
+    let other_specifier = Url::parse("file:///other/foo_test.ts").unwrap();
+    let other =
+      TestShardFilter::new(filters[0].shard, &other_cwd, &other_specifier);
+    assert_eq!(other.file_hash, filters[0].file_hash);
+  }
+
+  #[tokio::test]
+  async fn test_fail_fast_tracker_stops_at_threshold() {
+    let tracker = FailFastTracker::new(NonZeroUsize::new(2));
+    let stopped = tokio::spawn({
+      let tracker = tracker.clone();
+      async move { tracker.stopped().await }
+    });
+    tracker.add_failure();
+    assert!(!tracker.should_stop());
+    tracker.add_failure();
+    assert!(tracker.should_stop());
+    tokio::time::timeout(Duration::from_secs(10), stopped)
+      .await
+      .unwrap()
+      .unwrap();
+  }
 }
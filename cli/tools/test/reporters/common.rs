@@ -4,7 +4,7 @@ use super::fmt::format_test_error;
 use super::fmt::to_relative_path_or_remote_url;
 use super::*;
 
-pub(super) fn format_test_step_ancestry(
+pub(crate) fn format_test_step_ancestry(
   desc: &TestStepDescription,
   tests: &IndexMap<usize, TestDescription>,
   test_steps: &IndexMap<usize, TestStepDescription>,
@@ -100,11 +100,14 @@ pub(super) fn report_sigint(
   writeln!(writer).unwrap();
 }
 
+/// Write the summary of the test run. `hyperlinks` should only be set if
+/// `writer` is a terminal supporting OSC 8 hyperlinks.
 pub(super) fn report_summary(
   writer: &mut dyn std::io::Write,
   cwd: &Url,
   summary: &TestSummary,
   elapsed: &Duration,
+  hyperlinks: bool,
 ) {
   if !summary.failures.is_empty() || !summary.uncaught_errors.is_empty() {
     #[allow(clippy::type_complexity)] // Type alias doesn't look better here
@@ -136,6 +139,12 @@ pub(super) fn report_summary(
         if !failure.hide_in_summary() {
           let failure_title = format_test_for_summary(cwd, description);
           writeln!(writer, "{}", &failure_title).unwrap();
+          let failure = match failure {
+            TestFailure::JsError(js_error) => {
+              format_test_error(js_error, hyperlinks)
+            }
+            _ => failure.to_string(),
+          };
           writeln!(writer, "{}: {}", colors::red_bold("error"), failure)
             .unwrap();
           writeln!(writer).unwrap();
@@ -152,7 +161,7 @@ pub(super) fn report_summary(
           writer,
           "{}: {}",
           colors::red_bold("error"),
-          format_test_error(js_error)
+          format_test_error(js_error, hyperlinks)
         )
         .unwrap();
         writeln!(writer, "This error was not caught from a test and caused the test runner to fail on the referenced module.").unwrap();
@@ -168,6 +177,24 @@ pub(super) fn report_summary(
     }
   }
 
+  if !summary.flaky.is_empty() {
+    writeln!(writer, "\n{}\n", colors::yellow("FLAKY")).unwrap();
+    for (description, retries) in &summary.flaky {
+      let retries = if *retries == 1 {
+        "1 retry".to_string()
+      } else {
+        format!("{retries} retries")
+      };
+      writeln!(
+        writer,
+        "{} {}",
+        format_test_for_summary(cwd, description),
+        colors::yellow(format!("(passed after {retries})"))
+      )
+      .unwrap();
+    }
+  }
+
   let status = if summary.has_failed() {
     colors::red("FAILED").to_string()
   } else {
@@ -206,6 +233,10 @@ pub(super) fn report_summary(
     .unwrap()
   }
 
+  if !summary.flaky.is_empty() {
+    write!(summary_result, " | {} flaky", summary.flaky.len()).unwrap();
+  }
+
   if summary.measured > 0 {
     write!(summary_result, " | {} measured", summary.measured,).unwrap();
   }
@@ -48,6 +48,17 @@ impl TestReporter for CompoundTestReporter {
     }
   }
 
+  fn report_retry(
+    &mut self,
+    description: &TestDescription,
+    failure: &TestFailure,
+    elapsed: u64,
+  ) {
+    for reporter in &mut self.test_reporters {
+      reporter.report_retry(description, failure, elapsed);
+    }
+  }
+
   fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
     for reporter in &mut self.test_reporters {
       reporter.report_uncaught_error(origin, error.clone());
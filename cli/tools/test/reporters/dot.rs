@@ -76,6 +76,10 @@ fn fmt_failed() -> String {
   colors::red_bold("!").to_string()
 }
 
+fn fmt_retried() -> String {
+  colors::yellow("!").to_string()
+}
+
 fn fmt_cancelled() -> String {
   colors::gray("!").to_string()
 }
@@ -101,6 +105,7 @@ impl TestReporter for DotTestReporter {
     result: &TestResult,
     _elapsed: u64,
   ) {
+    self.summary.add_retried_result(description, result);
     match &result {
       TestResult::Ok => {
         self.summary.passed += 1;
@@ -123,6 +128,16 @@ impl TestReporter for DotTestReporter {
     self.print_test_result(result);
   }
 
+  fn report_retry(
+    &mut self,
+    description: &TestDescription,
+    _failure: &TestFailure,
+    _elapsed: u64,
+  ) {
+    self.summary.add_retry(description.id);
+    self.print_status(fmt_retried());
+  }
+
   fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
     self.summary.failed += 1;
     self
@@ -152,26 +167,9 @@ impl TestReporter for DotTestReporter {
     tests: &IndexMap<usize, TestDescription>,
     test_steps: &IndexMap<usize, TestStepDescription>,
   ) {
-    match &result {
-      TestStepResult::Ok => {
-        self.summary.passed_steps += 1;
-      }
-      TestStepResult::Ignored => {
-        self.summary.ignored_steps += 1;
-      }
-      TestStepResult::Failed(failure) => {
-        self.summary.failed_steps += 1;
-        self.summary.failures.push((
-          TestFailureDescription {
-            id: desc.id,
-            name: common::format_test_step_ancestry(desc, tests, test_steps),
-            origin: desc.origin.clone(),
-            location: desc.location.clone(),
-          },
-          failure.clone(),
-        ))
-      }
-    }
+    self
+      .summary
+      .add_step_result(desc, result, tests, test_steps);
 
     self.print_test_step_result(result);
   }
@@ -187,6 +185,7 @@ impl TestReporter for DotTestReporter {
       &self.cwd,
       &self.summary,
       elapsed,
+      supports_hyperlinks(&std::io::stdout()),
     );
     println!();
   }
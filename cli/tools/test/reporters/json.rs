@@ -0,0 +1,253 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use console_static_text::ansi::strip_ansi_codes;
+use deno_core::serde_json;
+use serde::Serialize;
+
+use super::fmt::format_test_error;
+use super::fmt::to_relative_path_or_remote_url;
+use super::*;
+
+/// A test reporter that prints every test event as a single line of JSON
+/// (newline delimited JSON), to be consumed by other tools.
+pub struct JsonTestReporter {
+  cwd: Url,
+  summary: TestSummary,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+enum JsonTestStatus {
+  Ok,
+  Ignored,
+  Failed,
+  Cancelled,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum JsonTestEvent<'a> {
+  #[serde(rename_all = "camelCase")]
+  Plan {
+    origin: String,
+    total: usize,
+    filtered_out: usize,
+    used_only: bool,
+  },
+  #[serde(rename_all = "camelCase")]
+  Result {
+    id: usize,
+    name: &'a str,
+    file: String,
+    line: u32,
+    column: u32,
+    status: JsonTestStatus,
+    duration: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+    /// Whether the test passed after being retried.
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    flaky: bool,
+  },
+  #[serde(rename_all = "camelCase")]
+  StepResult {
+    id: usize,
+    parent_id: usize,
+    name: &'a str,
+    file: String,
+    line: u32,
+    column: u32,
+    status: JsonTestStatus,
+    duration: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+  },
+  #[serde(rename_all = "camelCase")]
+  Retry {
+    id: usize,
+    name: &'a str,
+    duration: u64,
+    error: String,
+  },
+  #[serde(rename_all = "camelCase")]
+  UncaughtError {
+    origin: String,
+    error: String,
+  },
+  #[serde(rename_all = "camelCase")]
+  Summary {
+    passed: usize,
+    failed: usize,
+    ignored: usize,
+    passed_steps: usize,
+    failed_steps: usize,
+    ignored_steps: usize,
+    filtered_out: usize,
+    #[serde(skip_serializing_if = "is_zero")]
+    flaky: usize,
+    duration: u128,
+  },
+  Interrupted,
+}
+
+fn is_zero(value: &usize) -> bool {
+  *value == 0
+}
+
+impl JsonTestReporter {
+  pub fn new(cwd: Url) -> JsonTestReporter {
+    JsonTestReporter {
+      cwd,
+      summary: TestSummary::new(),
+    }
+  }
+
+  fn print_event(event: &JsonTestEvent) {
+    println!(
+      "{}",
+      serde_json::to_string(event).expect("failed to serialize test event")
+    );
+  }
+
+  fn format_failure(failure: &TestFailure) -> String {
+    strip_ansi_codes(&failure.to_string()).into_owned()
+  }
+}
+
+impl TestReporter for JsonTestReporter {
+  fn report_plan(&mut self, plan: &TestPlan) {
+    self.summary.total += plan.total;
+    self.summary.filtered_out += plan.filtered_out;
+    Self::print_event(&JsonTestEvent::Plan {
+      origin: to_relative_path_or_remote_url(&self.cwd, &plan.origin),
+      total: plan.total,
+      filtered_out: plan.filtered_out,
+      used_only: plan.used_only,
+    });
+  }
+
+  fn report_result(
+    &mut self,
+    description: &TestDescription,
+    result: &TestResult,
+    elapsed: u64,
+  ) {
+    let flaky_before = self.summary.flaky.len();
+    self.summary.add_retried_result(description, result);
+    let flaky = self.summary.flaky.len() > flaky_before;
+    let (status, error) = match result {
+      TestResult::Ok => {
+        self.summary.passed += 1;
+        (JsonTestStatus::Ok, None)
+      }
+      TestResult::Ignored => {
+        self.summary.ignored += 1;
+        (JsonTestStatus::Ignored, None)
+      }
+      TestResult::Failed(failure) => {
+        self.summary.failed += 1;
+        (JsonTestStatus::Failed, Some(Self::format_failure(failure)))
+      }
+      TestResult::Cancelled => {
+        self.summary.failed += 1;
+        (JsonTestStatus::Cancelled, None)
+      }
+    };
+    Self::print_event(&JsonTestEvent::Result {
+      id: description.id,
+      name: &description.name,
+      file: to_relative_path_or_remote_url(
+        &self.cwd,
+        &description.location.file_name,
+      ),
+      line: description.location.line_number,
+      column: description.location.column_number,
+      status,
+      duration: elapsed,
+      error,
+      flaky,
+    });
+  }
+
+  fn report_retry(
+    &mut self,
+    description: &TestDescription,
+    failure: &TestFailure,
+    elapsed: u64,
+  ) {
+    self.summary.add_retry(description.id);
+    Self::print_event(&JsonTestEvent::Retry {
+      id: description.id,
+      name: &description.name,
+      duration: elapsed,
+      error: Self::format_failure(failure),
+    });
+  }
+
+  fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
+    self.summary.failed += 1;
+    Self::print_event(&JsonTestEvent::UncaughtError {
+      origin: to_relative_path_or_remote_url(&self.cwd, origin),
+      error: strip_ansi_codes(&format_test_error(&error, false)).into_owned(),
+    });
+  }
+
+  fn report_step_result(
+    &mut self,
+    desc: &TestStepDescription,
+    result: &TestStepResult,
+    elapsed: u64,
+    tests: &IndexMap<usize, TestDescription>,
+    test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    self
+      .summary
+      .add_step_result(desc, result, tests, test_steps);
+    let (status, error) = match result {
+      TestStepResult::Ok => (JsonTestStatus::Ok, None),
+      TestStepResult::Ignored => (JsonTestStatus::Ignored, None),
+      TestStepResult::Failed(failure) => {
+        (JsonTestStatus::Failed, Some(Self::format_failure(failure)))
+      }
+    };
+    Self::print_event(&JsonTestEvent::StepResult {
+      id: desc.id,
+      parent_id: desc.parent_id,
+      name: &desc.name,
+      file: to_relative_path_or_remote_url(&self.cwd, &desc.location.file_name),
+      line: desc.location.line_number,
+      column: desc.location.column_number,
+      status,
+      duration: elapsed,
+      error,
+    });
+  }
+
+  fn report_summary(
+    &mut self,
+    elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    Self::print_event(&JsonTestEvent::Summary {
+      passed: self.summary.passed,
+      failed: self.summary.failed,
+      ignored: self.summary.ignored,
+      passed_steps: self.summary.passed_steps,
+      failed_steps: self.summary.failed_steps,
+      ignored_steps: self.summary.ignored_steps,
+      filtered_out: self.summary.filtered_out,
+      flaky: self.summary.flaky.len(),
+      duration: elapsed.as_millis(),
+    });
+  }
+
+  fn report_sigint(
+    &mut self,
+    _tests_pending: &HashSet<usize>,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+    Self::print_event(&JsonTestEvent::Interrupted);
+  }
+}
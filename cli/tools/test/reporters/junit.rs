@@ -3,6 +3,9 @@
 use std::collections::VecDeque;
 use std::path::PathBuf;
 
+use console_static_text::ansi::strip_ansi_codes;
+
+use super::fmt::format_test_error;
 use super::fmt::to_relative_path_or_remote_url;
 use super::*;
 
@@ -15,6 +18,13 @@ pub struct JunitTestReporter {
   // from child to parent to build the full test name that reflects the test
   // hierarchy.
   test_name_tree: TestNameTree,
+  // Errors thrown outside of any test, by their origin.
+  uncaught_errors: Vec<(String, Box<JsError>)>,
+  // Failed runs of the tests that are retried, by the test ID.
+  reruns: HashMap<usize, Vec<quick_junit::TestRerun>>,
+  // Steps of the current run of each test, by the test ID, so the steps of a
+  // run that is retried can be removed.
+  attempt_steps: HashMap<usize, Vec<usize>>,
 }
 
 impl JunitTestReporter {
@@ -24,20 +34,77 @@ impl JunitTestReporter {
       output_path,
       cases: IndexMap::new(),
       test_name_tree: TestNameTree::new(),
+      uncaught_errors: Vec::new(),
+      reruns: HashMap::new(),
+      attempt_steps: HashMap::new(),
+    }
+  }
+
+  fn new_case(
+    &self,
+    name: String,
+    location: &TestLocation,
+  ) -> quick_junit::TestCase {
+    let mut case =
+      quick_junit::TestCase::new(name, quick_junit::TestCaseStatus::skipped());
+    let file_name =
+      to_relative_path_or_remote_url(&self.cwd, &location.file_name);
+    case.classname = Some(file_name.clone());
+    case.extra.insert(String::from("file"), file_name);
+    case
+      .extra
+      .insert(String::from("line"), location.line_number.to_string());
+    case
+      .extra
+      .insert(String::from("col"), location.column_number.to_string());
+    case
+  }
+
+  /// Returns the location an error was thrown from, relative to the current
+  /// directory. Frames of Deno's internal modules are skipped.
+  fn js_error_location(&self, js_error: &JsError) -> Option<String> {
+    js_error.frames.iter().find_map(|frame| {
+      let file_name = frame.file_name.as_ref()?;
+      if file_name.starts_with("ext:") || file_name.starts_with("node:") {
+        return None;
+      }
+      Some(format!(
+        "{}:{}:{}",
+        to_relative_path_or_remote_url(&self.cwd, file_name),
+        frame.line_number?,
+        frame.column_number?,
+      ))
+    })
+  }
+
+  fn convert_failure(
+    &self,
+    failure: &TestFailure,
+  ) -> quick_junit::TestCaseStatus {
+    let (message, ty) = match failure {
+      TestFailure::JsError(js_error) => {
+        let message = match self.js_error_location(js_error) {
+          Some(location) => format!("{} at {}", failure.overview(), location),
+          None => failure.overview(),
+        };
+        (message, js_error.name.clone())
+      }
+      _ => (failure.overview(), None),
+    };
+    quick_junit::TestCaseStatus::NonSuccess {
+      kind: quick_junit::NonSuccessKind::Failure,
+      message: Some(message),
+      ty,
+      description: Some(strip_ansi_codes(&failure.detail()).into_owned()),
+      reruns: vec![],
     }
   }
 
-  fn convert_status(status: &TestResult) -> quick_junit::TestCaseStatus {
+  fn convert_status(&self, status: &TestResult) -> quick_junit::TestCaseStatus {
     match status {
       TestResult::Ok => quick_junit::TestCaseStatus::success(),
       TestResult::Ignored => quick_junit::TestCaseStatus::skipped(),
-      TestResult::Failed(failure) => quick_junit::TestCaseStatus::NonSuccess {
-        kind: quick_junit::NonSuccessKind::Failure,
-        message: Some(failure.overview()),
-        ty: None,
-        description: Some(failure.detail()),
-        reruns: vec![],
-      },
+      TestResult::Failed(failure) => self.convert_failure(failure),
       TestResult::Cancelled => quick_junit::TestCaseStatus::NonSuccess {
         kind: quick_junit::NonSuccessKind::Error,
         message: Some("Cancelled".to_string()),
@@ -48,43 +115,70 @@ impl JunitTestReporter {
     }
   }
 
+  fn convert_rerun(
+    &self,
+    failure: &TestFailure,
+    elapsed: u64,
+  ) -> quick_junit::TestRerun {
+    let mut rerun =
+      quick_junit::TestRerun::new(quick_junit::NonSuccessKind::Failure);
+    if let quick_junit::TestCaseStatus::NonSuccess {
+      message,
+      ty,
+      description,
+      ..
+    } = self.convert_failure(failure)
+    {
+      if let Some(message) = message {
+        rerun.set_message(message);
+      }
+      if let Some(ty) = ty {
+        rerun.set_type(ty);
+      }
+      if let Some(description) = description {
+        rerun.set_description(description);
+      }
+    }
+    rerun.set_time(Duration::from_millis(elapsed));
+    rerun
+  }
+
   fn convert_step_status(
+    &self,
     status: &TestStepResult,
   ) -> quick_junit::TestCaseStatus {
     match status {
       TestStepResult::Ok => quick_junit::TestCaseStatus::success(),
       TestStepResult::Ignored => quick_junit::TestCaseStatus::skipped(),
-      TestStepResult::Failed(failure) => {
-        quick_junit::TestCaseStatus::NonSuccess {
-          kind: quick_junit::NonSuccessKind::Failure,
-          message: Some(failure.overview()),
-          ty: None,
-          description: Some(failure.detail()),
-          reruns: vec![],
-        }
+      TestStepResult::Failed(failure) => self.convert_failure(failure),
+    }
+  }
+
+  fn convert_uncaught_error(
+    &self,
+    js_error: &JsError,
+  ) -> quick_junit::TestCaseStatus {
+    let message = match self.js_error_location(js_error) {
+      Some(location) => {
+        format!("{} at {}", js_error.exception_message, location)
       }
+      None => js_error.exception_message.clone(),
+    };
+    quick_junit::TestCaseStatus::NonSuccess {
+      kind: quick_junit::NonSuccessKind::Error,
+      message: Some(message),
+      ty: js_error.name.clone(),
+      description: Some(
+        strip_ansi_codes(&format_test_error(js_error, false)).into_owned(),
+      ),
+      reruns: vec![],
     }
   }
 }
 
 impl TestReporter for JunitTestReporter {
   fn report_register(&mut self, description: &TestDescription) {
-    let mut case = quick_junit::TestCase::new(
-      description.name.clone(),
-      quick_junit::TestCaseStatus::skipped(),
-    );
-    case.classname = Some(to_relative_path_or_remote_url(
-      &self.cwd,
-      &description.location.file_name,
-    ));
-    case.extra.insert(
-      String::from("line"),
-      description.location.line_number.to_string(),
-    );
-    case.extra.insert(
-      String::from("col"),
-      description.location.column_number.to_string(),
-    );
+    let case = self.new_case(description.name.clone(), &description.location);
     self.cases.insert(description.id, case);
 
     self.test_name_tree.add_node(description.clone().into());
@@ -109,35 +203,54 @@ impl TestReporter for JunitTestReporter {
     result: &TestResult,
     elapsed: u64,
   ) {
+    let mut status = self.convert_status(result);
+    self.attempt_steps.remove(&description.id);
+    let reruns = self.reruns.remove(&description.id).unwrap_or_default();
+    match &mut status {
+      quick_junit::TestCaseStatus::Success { flaky_runs } => {
+        *flaky_runs = reruns;
+      }
+      quick_junit::TestCaseStatus::NonSuccess { reruns: runs, .. } => {
+        *runs = reruns;
+      }
+      quick_junit::TestCaseStatus::Skipped { .. } => {}
+    }
     if let Some(case) = self.cases.get_mut(&description.id) {
-      case.status = Self::convert_status(result);
+      case.status = status;
       case.set_time(Duration::from_millis(elapsed));
     }
   }
 
-  fn report_uncaught_error(&mut self, _origin: &str, _error: Box<JsError>) {}
+  fn report_retry(
+    &mut self,
+    description: &TestDescription,
+    failure: &TestFailure,
+    elapsed: u64,
+  ) {
+    let rerun = self.convert_rerun(failure, elapsed);
+    self.reruns.entry(description.id).or_default().push(rerun);
+    // the steps of the failed run are run again
+    let steps = self.attempt_steps.remove(&description.id);
+    for id in steps.unwrap_or_default() {
+      self.cases.shift_remove(&id);
+    }
+  }
+
+  fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
+    self.uncaught_errors.push((origin.to_string(), error));
+  }
 
   fn report_step_register(&mut self, description: &TestStepDescription) {
+    self
+      .attempt_steps
+      .entry(description.root_id)
+      .or_default()
+      .push(description.id);
     self.test_name_tree.add_node(description.clone().into());
     let test_case_name =
       self.test_name_tree.construct_full_test_name(description.id);
 
-    let mut case = quick_junit::TestCase::new(
-      test_case_name,
-      quick_junit::TestCaseStatus::skipped(),
-    );
-    case.classname = Some(to_relative_path_or_remote_url(
-      &self.cwd,
-      &description.location.file_name,
-    ));
-    case.extra.insert(
-      String::from("line"),
-      description.location.line_number.to_string(),
-    );
-    case.extra.insert(
-      String::from("col"),
-      description.location.column_number.to_string(),
-    );
+    let case = self.new_case(test_case_name, &description.location);
     self.cases.insert(description.id, case);
   }
 
@@ -151,8 +264,9 @@ impl TestReporter for JunitTestReporter {
     _tests: &IndexMap<usize, TestDescription>,
     _test_steps: &IndexMap<usize, TestStepDescription>,
   ) {
+    let status = self.convert_step_status(result);
     if let Some(case) = self.cases.get_mut(&description.id) {
-      case.status = Self::convert_step_status(result);
+      case.status = status;
       case.set_time(Duration::from_millis(elapsed));
     }
   }
@@ -213,6 +327,20 @@ impl TestReporter for JunitTestReporter {
         });
     }
 
+    for (origin, js_error) in &self.uncaught_errors {
+      let filename = to_relative_path_or_remote_url(&self.cwd, origin);
+      let mut case = quick_junit::TestCase::new(
+        "uncaught error",
+        self.convert_uncaught_error(js_error),
+      );
+      case.classname = Some(filename.clone());
+      case.extra.insert(String::from("file"), filename.clone());
+      suites
+        .entry(filename.clone())
+        .or_insert_with(|| quick_junit::TestSuite::new(filename))
+        .add_test_case(case);
+    }
+
     let mut report = quick_junit::Report::new("deno test");
     report
       .set_time(*elapsed)
@@ -312,6 +440,41 @@ impl From<TestStepDescription> for TestNameTreeNode {
 #[cfg(test)]
 mod tests {
   use super::*;
+  use deno_core::error::JsStackFrame;
+
+  #[test]
+  fn js_error_location_skips_internal_frames() {
+    let reporter = JunitTestReporter::new(
+      Url::parse("file:///project/").unwrap(),
+      "-".to_string(),
+    );
+    let js_error = JsError {
+      name: Some("Error".to_string()),
+      message: Some("boom".to_string()),
+      stack: None,
+      cause: None,
+      exception_message: "Uncaught Error: boom".to_string(),
+      frames: vec![
+        JsStackFrame::from_location(
+          Some("ext:deno_web/00_infra.js".to_string()),
+          Some(10),
+          Some(3),
+        ),
+        JsStackFrame::from_location(
+          Some("file:///project/test.ts".to_string()),
+          Some(4),
+          Some(9),
+        ),
+      ],
+      source_line: None,
+      source_line_frame_index: None,
+      aggregated: None,
+    };
+    assert_eq!(
+      reporter.js_error_location(&js_error),
+      Some("./test.ts:4:9".to_string())
+    );
+  }
 
   #[test]
   fn construct_full_test_name_one_node() {
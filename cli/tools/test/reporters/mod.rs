@@ -5,30 +5,58 @@ use super::*;
 mod common;
 mod compound;
 mod dot;
+mod json;
 mod junit;
 mod pretty;
 mod tap;
 
+pub(crate) use common::format_test_step_ancestry;
 pub use compound::CompoundTestReporter;
 pub use dot::DotTestReporter;
+pub use json::JsonTestReporter;
 pub use junit::JunitTestReporter;
 pub use pretty::PrettyTestReporter;
 pub use tap::TapTestReporter;
 
+/// Receives the events of a test run and reports them, e.g. by printing
+/// them to the terminal or writing a report file.
+///
+/// Events arrive in the order they happen. When tests run concurrently,
+/// events of different test files may be interleaved. Only the result
+/// methods are required, the others default to doing nothing so that a
+/// reporter only needs to implement the events it is interested in.
 pub trait TestReporter {
-  fn report_register(&mut self, description: &TestDescription);
-  fn report_plan(&mut self, plan: &TestPlan);
-  fn report_wait(&mut self, description: &TestDescription);
-  fn report_output(&mut self, output: &[u8]);
+  /// A test was discovered. Called before the test file's plan is reported.
+  fn report_register(&mut self, _description: &TestDescription) {}
+  /// The tests of a test file are about to run.
+  fn report_plan(&mut self, _plan: &TestPlan) {}
+  /// A test started running.
+  fn report_wait(&mut self, _description: &TestDescription) {}
+  /// A test, or the module it is declared in, wrote to stdout or stderr.
+  fn report_output(&mut self, _output: &[u8]) {}
+  /// A test finished, `elapsed` is its duration in milliseconds.
   fn report_result(
     &mut self,
     description: &TestDescription,
     result: &TestResult,
     elapsed: u64,
   );
-  fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>);
-  fn report_step_register(&mut self, description: &TestStepDescription);
-  fn report_step_wait(&mut self, description: &TestStepDescription);
+  /// A test failed and is run again, `elapsed` is the duration of the failed
+  /// run in milliseconds.
+  fn report_retry(
+    &mut self,
+    _description: &TestDescription,
+    _failure: &TestFailure,
+    _elapsed: u64,
+  ) {
+  }
+  /// An error was thrown outside of any test in the file `origin`.
+  fn report_uncaught_error(&mut self, _origin: &str, _error: Box<JsError>) {}
+  /// A test step was discovered.
+  fn report_step_register(&mut self, _description: &TestStepDescription) {}
+  /// A test step started running.
+  fn report_step_wait(&mut self, _description: &TestStepDescription) {}
+  /// A test step finished, `elapsed` is its duration in milliseconds.
   fn report_step_result(
     &mut self,
     desc: &TestStepDescription,
@@ -37,23 +65,31 @@ pub trait TestReporter {
     tests: &IndexMap<usize, TestDescription>,
     test_steps: &IndexMap<usize, TestStepDescription>,
   );
+  /// All test files have run.
   fn report_summary(
     &mut self,
     elapsed: &Duration,
     tests: &IndexMap<usize, TestDescription>,
     test_steps: &IndexMap<usize, TestStepDescription>,
   );
+  /// The run was interrupted with SIGINT while `tests_pending` were running.
   fn report_sigint(
     &mut self,
-    tests_pending: &HashSet<usize>,
-    tests: &IndexMap<usize, TestDescription>,
-    test_steps: &IndexMap<usize, TestStepDescription>,
-  );
-  fn report_completed(&mut self);
+    _tests_pending: &HashSet<usize>,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) {
+  }
+  /// No more events will be reported.
+  fn report_completed(&mut self) {}
+  /// Write out the report, for reporters that don't print events as they
+  /// happen.
   fn flush_report(
     &mut self,
-    elapsed: &Duration,
-    tests: &IndexMap<usize, TestDescription>,
-    test_steps: &IndexMap<usize, TestStepDescription>,
-  ) -> anyhow::Result<()>;
+    _elapsed: &Duration,
+    _tests: &IndexMap<usize, TestDescription>,
+    _test_steps: &IndexMap<usize, TestStepDescription>,
+  ) -> anyhow::Result<()> {
+    Ok(())
+  }
 }
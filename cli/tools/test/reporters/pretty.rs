@@ -5,7 +5,6 @@ use super::fmt::to_relative_path_or_remote_url;
 use super::*;
 
 pub struct PrettyTestReporter {
-  parallel: bool,
   echo_output: bool,
   in_new_line: bool,
   phase: &'static str,
@@ -20,18 +19,17 @@ pub struct PrettyTestReporter {
     HashMap<usize, IndexMap<usize, (TestStepDescription, TestStepResult, u64)>>,
   summary: TestSummary,
   writer: Box<dyn std::io::Write>,
+  hyperlinks: bool,
 }
 
 impl PrettyTestReporter {
   pub fn new(
-    parallel: bool,
     echo_output: bool,
     filter: bool,
     repl: bool,
     cwd: Url,
   ) -> PrettyTestReporter {
     PrettyTestReporter {
-      parallel,
       echo_output,
       in_new_line: true,
       phase: "",
@@ -45,28 +43,22 @@ impl PrettyTestReporter {
       child_results_buffer: Default::default(),
       summary: TestSummary::new(),
       writer: Box::new(std::io::stdout()),
+      hyperlinks: supports_hyperlinks(&std::io::stdout()),
     }
   }
 
   pub fn with_writer(self, writer: Box<dyn std::io::Write>) -> Self {
-    Self { writer, ..self }
+    Self {
+      writer,
+      hyperlinks: false,
+      ..self
+    }
   }
 
   fn force_report_wait(&mut self, description: &TestDescription) {
     if !self.in_new_line {
       writeln!(&mut self.writer).unwrap();
     }
-    if self.parallel {
-      write!(
-        &mut self.writer,
-        "{}",
-        colors::gray(format!(
-          "{} => ",
-          to_relative_path_or_remote_url(&self.cwd, &description.origin)
-        ))
-      )
-      .unwrap();
-    }
     write!(&mut self.writer, "{} ...", description.name).unwrap();
     self.in_new_line = false;
     // flush for faster feedback when line buffered
@@ -103,17 +95,15 @@ impl PrettyTestReporter {
       self.force_report_step_wait(description);
     }
 
-    if !self.parallel {
-      let child_results = self
-        .child_results_buffer
-        .remove(&description.id)
-        .unwrap_or_default();
-      for (desc, result, elapsed) in child_results.values() {
-        self.force_report_step_result(desc, result, *elapsed);
-      }
-      if !child_results.is_empty() {
-        self.force_report_step_wait(description);
-      }
+    let child_results = self
+      .child_results_buffer
+      .remove(&description.id)
+      .unwrap_or_default();
+    for (desc, result, elapsed) in child_results.values() {
+      self.force_report_step_result(desc, result, *elapsed);
+    }
+    if !child_results.is_empty() {
+      self.force_report_step_wait(description);
     }
 
     let status = match &result {
@@ -137,11 +127,7 @@ impl PrettyTestReporter {
     }
     writeln!(&mut self.writer).unwrap();
     self.in_new_line = true;
-    if self.parallel {
-      self.scope_test_id = None;
-    } else {
-      self.scope_test_id = Some(description.parent_id);
-    }
+    self.scope_test_id = Some(description.parent_id);
     self
       .child_results_buffer
       .entry(description.parent_id)
@@ -172,7 +158,7 @@ impl TestReporter for PrettyTestReporter {
     if self.repl {
       return;
     }
-    if self.parallel || (self.filter && plan.total == 0) {
+    if self.filter && plan.total == 0 {
       return;
     }
     let inflection = if plan.total == 1 { "test" } else { "tests" };
@@ -191,9 +177,7 @@ impl TestReporter for PrettyTestReporter {
   }
 
   fn report_wait(&mut self, description: &TestDescription) {
-    if !self.parallel {
-      self.force_report_wait(description);
-    }
+    self.force_report_wait(description);
     self.started_tests = true;
   }
 
@@ -234,6 +218,8 @@ impl TestReporter for PrettyTestReporter {
     result: &TestResult,
     elapsed: u64,
   ) {
+    let retried = self.summary.retries.contains_key(&description.id);
+    self.summary.add_retried_result(description, result);
     match &result {
       TestResult::Ok => {
         self.summary.passed += 1;
@@ -253,16 +239,15 @@ impl TestReporter for PrettyTestReporter {
       }
     }
 
-    if self.parallel {
-      self.force_report_wait(description);
-    }
-
     self.write_output_end();
     if self.in_new_line || self.scope_test_id != Some(description.id) {
       self.force_report_wait(description);
     }
 
     let status = match result {
+      TestResult::Ok if retried => {
+        format!("{} {}", colors::green("ok"), colors::yellow("(flaky)"))
+      }
       TestResult::Ok => colors::green("ok").to_string(),
       TestResult::Ignored => colors::yellow("ignored").to_string(),
       TestResult::Failed(failure) => failure.format_label(),
@@ -284,6 +269,34 @@ impl TestReporter for PrettyTestReporter {
     self.scope_test_id = None;
   }
 
+  fn report_retry(
+    &mut self,
+    description: &TestDescription,
+    failure: &TestFailure,
+    elapsed: u64,
+  ) {
+    self.summary.add_retry(description.id);
+    // the step results of the failed run which weren't printed yet
+    self.child_results_buffer.remove(&description.id);
+
+    self.write_output_end();
+    if self.in_new_line || self.scope_test_id != Some(description.id) {
+      self.force_report_wait(description);
+    }
+
+    writeln!(
+      &mut self.writer,
+      " {} {} {}",
+      failure.format_label(),
+      colors::yellow("(retrying)"),
+      colors::gray(format!("({})", display::human_elapsed(elapsed.into())))
+    )
+    .unwrap();
+    self.in_new_line = true;
+    // the steps of the next run are reported under it
+    self.force_report_wait(description);
+  }
+
   fn report_uncaught_error(&mut self, origin: &str, error: Box<JsError>) {
     self.summary.failed += 1;
     self
@@ -308,7 +321,7 @@ impl TestReporter for PrettyTestReporter {
   fn report_step_register(&mut self, _description: &TestStepDescription) {}
 
   fn report_step_wait(&mut self, description: &TestStepDescription) {
-    if !self.parallel && self.scope_test_id == Some(description.parent_id) {
+    if self.scope_test_id == Some(description.parent_id) {
       self.force_report_step_wait(description);
     }
   }
@@ -321,58 +334,23 @@ impl TestReporter for PrettyTestReporter {
     tests: &IndexMap<usize, TestDescription>,
     test_steps: &IndexMap<usize, TestStepDescription>,
   ) {
-    match &result {
-      TestStepResult::Ok => {
-        self.summary.passed_steps += 1;
-      }
-      TestStepResult::Ignored => {
-        self.summary.ignored_steps += 1;
-      }
-      TestStepResult::Failed(failure) => {
-        self.summary.failed_steps += 1;
-        self.summary.failures.push((
-          TestFailureDescription {
-            id: desc.id,
-            name: common::format_test_step_ancestry(desc, tests, test_steps),
-            origin: desc.origin.clone(),
-            location: desc.location.clone(),
-          },
-          failure.clone(),
-        ))
-      }
-    }
+    self
+      .summary
+      .add_step_result(desc, result, tests, test_steps);
 
-    if self.parallel {
-      self.write_output_end();
-      write!(
-        &mut self.writer,
-        "{} {} ...",
-        colors::gray(format!(
-          "{} =>",
-          to_relative_path_or_remote_url(&self.cwd, &desc.origin)
-        )),
-        common::format_test_step_ancestry(desc, tests, test_steps)
-      )
-      .unwrap();
-      self.in_new_line = false;
-      self.scope_test_id = Some(desc.id);
+    let sibling_results =
+      self.child_results_buffer.entry(desc.parent_id).or_default();
+    if self.scope_test_id == Some(desc.id)
+      || self.scope_test_id == Some(desc.parent_id)
+    {
+      let sibling_results = std::mem::take(sibling_results);
       self.force_report_step_result(desc, result, elapsed);
-    } else {
-      let sibling_results =
-        self.child_results_buffer.entry(desc.parent_id).or_default();
-      if self.scope_test_id == Some(desc.id)
-        || self.scope_test_id == Some(desc.parent_id)
-      {
-        let sibling_results = std::mem::take(sibling_results);
-        self.force_report_step_result(desc, result, elapsed);
-        // Flush buffered sibling results.
-        for (desc, result, elapsed) in sibling_results.values() {
-          self.force_report_step_result(desc, result, *elapsed);
-        }
-      } else {
-        sibling_results
-          .insert(desc.id, (desc.clone(), result.clone(), elapsed));
+      // Flush buffered sibling results.
+      for (desc, result, elapsed) in sibling_results.values() {
+        self.force_report_step_result(desc, result, *elapsed);
       }
+    } else {
+      sibling_results.insert(desc.id, (desc.clone(), result.clone(), elapsed));
     }
   }
 
@@ -383,7 +361,13 @@ impl TestReporter for PrettyTestReporter {
     _test_steps: &IndexMap<usize, TestStepDescription>,
   ) {
     self.write_output_end();
-    common::report_summary(&mut self.writer, &self.cwd, &self.summary, elapsed);
+    common::report_summary(
+      &mut self.writer,
+      &self.cwd,
+      &self.summary,
+      elapsed,
+      self.hyperlinks,
+    );
     if !self.repl {
       writeln!(&mut self.writer).unwrap();
     }
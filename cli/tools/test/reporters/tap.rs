@@ -20,6 +20,7 @@ pub struct TapTestReporter {
   n: usize,
   step_n: usize,
   step_results: HashMap<usize, Vec<(TestStepDescription, TestStepResult)>>,
+  retries: HashMap<usize, usize>,
 }
 
 impl TapTestReporter {
@@ -32,6 +33,7 @@ impl TapTestReporter {
       n: 0,
       step_n: 0,
       step_results: HashMap::new(),
+      retries: HashMap::new(),
     }
   }
 
@@ -146,11 +148,9 @@ impl TestReporter for TapTestReporter {
     result: &TestResult,
     _elapsed: u64,
   ) {
-    if self.is_concurrent {
-      let results = self.step_results.remove(&description.id);
-      for (desc, result) in results.iter().flat_map(|v| v.iter()) {
-        self.print_step_result(desc, result);
-      }
+    let results = self.step_results.remove(&description.id);
+    for (desc, result) in results.iter().flat_map(|v| v.iter()) {
+      self.print_step_result(desc, result);
     }
 
     if self.step_n != 0 {
@@ -166,6 +166,12 @@ impl TestReporter for TapTestReporter {
     };
     self.n += 1;
     Self::print_line(0, status, self.n, &description.name, directive);
+    let retries = self.retries.remove(&description.id).unwrap_or(0);
+    if retries > 0 && matches!(result, TestResult::Ok) {
+      // Unspecified behavior: TAP has no directive for flaky tests.
+      let inflection = if retries == 1 { "retry" } else { "retries" };
+      println!("# flaky: passed after {} {}", retries, inflection);
+    }
 
     if let TestResult::Failed(failure) = result {
       Self::print_diagnostic(
@@ -179,6 +185,17 @@ impl TestReporter for TapTestReporter {
     }
   }
 
+  fn report_retry(
+    &mut self,
+    description: &TestDescription,
+    _failure: &TestFailure,
+    _elapsed: u64,
+  ) {
+    *self.retries.entry(description.id).or_default() += 1;
+    // only the steps of the last run are reported
+    self.step_results.remove(&description.id);
+  }
+
   fn report_uncaught_error(&mut self, _origin: &str, _errorr: Box<JsError>) {}
 
   fn report_step_register(&mut self, _description: &TestStepDescription) {}
@@ -196,19 +213,15 @@ impl TestReporter for TapTestReporter {
     _tests: &IndexMap<usize, TestDescription>,
     _test_steps: &IndexMap<usize, TestStepDescription>,
   ) {
-    if self.is_concurrent {
-      // All subtests must be reported immediately before the parent test.
-      // So during concurrent execution we need to defer printing the results.
-      // TODO(SyrupThinker) This only outputs one level of subtests, it could support multiple.
-      self
-        .step_results
-        .entry(desc.root_id)
-        .or_default()
-        .push((desc.clone(), result.clone()));
-      return;
-    }
-
-    self.print_step_result(desc, result);
+    // All subtests must be reported immediately before the parent test.
+    // So during concurrent execution, or when the test is retried, we need to
+    // defer printing the results.
+    // TODO(SyrupThinker) This only outputs one level of subtests, it could support multiple.
+    self
+      .step_results
+      .entry(desc.root_id)
+      .or_default()
+      .push((desc.clone(), result.clone()));
   }
 
   fn report_summary(
@@ -0,0 +1,30 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use deno_core::OpState;
+use deno_core::ResourceId;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+/// Records the stack that created each resource while leak tracing is
+/// enabled, so the resource sanitizer can point at the call that leaked it.
+///
+/// The stacks are captured on the JS side by the classes wrapping resources,
+/// like the traces of the op sanitizer, and start at the first frame.
+#[derive(Clone, Default)]
+pub struct ResourceCreationTracer(Rc<RefCell<HashMap<ResourceId, String>>>);
+
+impl ResourceCreationTracer {
+  /// Stores the stack that created the resource `rid`. Traces of resources
+  /// that were closed since are dropped.
+  pub fn record(&self, op_state: &OpState, rid: ResourceId, stack: &str) {
+    let mut traces = self.0.borrow_mut();
+    traces.retain(|rid, _| op_state.resource_table.has(*rid));
+    traces.insert(rid, stack.to_string());
+  }
+
+  /// Formats the stack that created the resource `rid`, if it is known.
+  pub fn format_resource_trace(&self, rid: ResourceId) -> Option<String> {
+    self.0.borrow().get(&rid).cloned()
+  }
+}
@@ -0,0 +1,215 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use crate::util::diff::diff;
+use crate::util::fs::atomic_write_file;
+
+use deno_core::anyhow::bail;
+use deno_core::anyhow::Context;
+use deno_core::error::AnyError;
+use deno_core::ModuleSpecifier;
+use indexmap::IndexMap;
+use std::path::Path;
+use std::path::PathBuf;
+
+const SNAPSHOT_FILE_HEADER: &str =
+  "// Snapshot file generated by `deno test --update-snapshots`.\n\nexport const snapshot = {};\n";
+
+/// The state backing `TestContext.assertSnapshot()` for a single test module.
+pub struct SnapshotState {
+  update: bool,
+  file: Option<SnapshotFile>,
+  specifier: ModuleSpecifier,
+}
+
+impl SnapshotState {
+  pub fn new(specifier: ModuleSpecifier, update: bool) -> Self {
+    Self {
+      update,
+      file: None,
+      specifier,
+    }
+  }
+
+  /// Compares `actual` against the snapshot stored under `name`, returning a
+  /// message describing the mismatch if they differ. When updating snapshots
+  /// the stored value is replaced instead.
+  pub fn assert(
+    &mut self,
+    name: String,
+    actual: String,
+  ) -> Result<Option<String>, AnyError> {
+    let update = self.update;
+    let file = self.file()?;
+    match file.snapshots.get(&name) {
+      Some(expected) if *expected == actual => Ok(None),
+      _ if update => {
+        file.snapshots.insert(name, actual);
+        file.changed = true;
+        Ok(None)
+      }
+      Some(expected) => Ok(Some(format!(
+        "Snapshot \"{}\" does not match:\n\n{}\nTo update snapshots, run `deno test --update-snapshots`.",
+        name,
+        diff(expected, &actual),
+      ))),
+      None => Ok(Some(format!(
+        "Missing snapshot \"{}\" in {}.\n\nTo create it, run `deno test --update-snapshots`.",
+        name,
+        file.path.display(),
+      ))),
+    }
+  }
+
+  /// Writes any snapshots that were changed to disk.
+  pub fn save(&mut self) -> Result<(), AnyError> {
+    let Some(file) = &mut self.file else {
+      return Ok(());
+    };
+    if !file.changed {
+      return Ok(());
+    }
+    if let Some(parent) = file.path.parent() {
+      std::fs::create_dir_all(parent)?;
+    }
+    atomic_write_file(&file.path, serialize_snapshots(&file.snapshots), 0o644)?;
+    file.changed = false;
+    Ok(())
+  }
+
+  fn file(&mut self) -> Result<&mut SnapshotFile, AnyError> {
+    if self.file.is_none() {
+      let Ok(module_path) = self.specifier.to_file_path() else {
+        bail!(
+          "Snapshot testing is only supported for local test modules: {}",
+          self.specifier
+        );
+      };
+      let path = snapshot_path(&module_path);
+      let snapshots = match std::fs::read_to_string(&path) {
+        Ok(text) => parse_snapshots(&text).with_context(|| {
+          format!("Failed parsing snapshot file {}", path.display())
+        })?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {
+          IndexMap::new()
+        }
+        Err(err) => return Err(err.into()),
+      };
+      self.file = Some(SnapshotFile {
+        path,
+        snapshots,
+        changed: false,
+      });
+    }
+    Ok(self.file.as_mut().unwrap())
+  }
+}
+
+struct SnapshotFile {
+  path: PathBuf,
+  snapshots: IndexMap<String, String>,
+  changed: bool,
+}
+
+/// The snapshots of `dir/mod_test.ts` are stored in
+/// `dir/__snapshots__/mod_test.ts.snap`.
+fn snapshot_path(module_path: &Path) -> PathBuf {
+  let file_name = module_path
+    .file_name()
+    .map(|name| name.to_string_lossy().into_owned())
+    .unwrap_or_default();
+  module_path
+    .with_file_name("__snapshots__")
+    .join(format!("{file_name}.snap"))
+}
+
+fn escape_template(text: &str) -> String {
+  text
+    .replace('\\', "\\\\")
+    .replace('`', "\\`")
+    .replace("${", "\\${")
+}
+
+fn serialize_snapshots(snapshots: &IndexMap<String, String>) -> String {
+  let mut text = SNAPSHOT_FILE_HEADER.to_string();
+  let mut names = snapshots.keys().collect::<Vec<_>>();
+  names.sort();
+  for name in names {
+    text.push_str(&format!(
+      "\nsnapshot[`{}`] = `\n{}\n`;\n",
+      escape_template(name),
+      escape_template(&snapshots[name]),
+    ));
+  }
+  text
+}
+
+/// Reads the template literal starting right after an opening backtick,
+/// returning its unescaped contents and the remaining text.
+fn parse_template(text: &str) -> Result<(String, &str), AnyError> {
+  let mut value = String::new();
+  let mut chars = text.char_indices();
+  while let Some((i, c)) = chars.next() {
+    match c {
+      '\\' => match chars.next() {
+        Some((_, c)) => value.push(c),
+        None => break,
+      },
+      '`' => return Ok((value, &text[i + 1..])),
+      c => value.push(c),
+    }
+  }
+  bail!("unterminated template literal")
+}
+
+fn parse_snapshots(text: &str) -> Result<IndexMap<String, String>, AnyError> {
+  const ENTRY_START: &str = "snapshot[`";
+  let mut snapshots = IndexMap::new();
+  let mut rest = text;
+  while let Some(index) = rest.find(ENTRY_START) {
+    let (name, after_name) =
+      parse_template(&rest[index + ENTRY_START.len()..])?;
+    let Some(after_assign) = after_name.trim_start().strip_prefix("] = `")
+    else {
+      bail!("expected snapshot value for \"{}\"", name);
+    };
+    let (value, after_value) = parse_template(after_assign)?;
+    let value = value
+      .strip_prefix('\n')
+      .and_then(|value| value.strip_suffix('\n'))
+      .unwrap_or(&value)
+      .to_string();
+    snapshots.insert(name, value);
+    rest = after_value;
+  }
+  Ok(snapshots)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn snapshots_roundtrip() {
+    let mut snapshots = IndexMap::new();
+    snapshots.insert("b 1".to_string(), "{\n  a: `${x}` \\ 1,\n}".to_string());
+    snapshots.insert("a 1".to_string(), "\"hello\"".to_string());
+    snapshots.insert("name with ` tick 1".to_string(), "".to_string());
+    let text = serialize_snapshots(&snapshots);
+    assert!(text.starts_with(SNAPSHOT_FILE_HEADER));
+    assert!(text.contains("snapshot[`a 1`] = `\n\"hello\"\n`;\n"));
+    let parsed = parse_snapshots(&text).unwrap();
+    assert_eq!(parsed.len(), 3);
+    for (name, value) in &snapshots {
+      assert_eq!(parsed.get(name), Some(value));
+    }
+    assert!(parse_snapshots("snapshot[`a 1`] = `unterminated").is_err());
+  }
+
+  #[test]
+  fn snapshot_path_is_next_to_module() {
+    assert_eq!(
+      snapshot_path(Path::new("/project/tests/mod_test.ts")),
+      PathBuf::from("/project/tests/__snapshots__/mod_test.ts.snap")
+    );
+  }
+}
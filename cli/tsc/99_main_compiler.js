@@ -50,10 +50,21 @@ delete Object.prototype.__proto__;
     "flockSync",
     "funlock",
     "funlockSync",
-    "listen",
-    "listenDatagram",
+    "getXattr",
+    "getXattrSync",
+    "listXattrs",
+    "listXattrsSync",
     "openKv",
+    "openReadableStream",
+    "openWritableStream",
+    "removeXattr",
+    "removeXattrSync",
+    "setImmutable",
+    "setImmutableSync",
+    "setXattr",
+    "setXattrSync",
     "umask",
+    "withTempDir",
   ]);
   const unstableMsgSuggestion =
     "If not, try changing the 'lib' compiler option to include 'deno.unstable' " +
@@ -241,11 +241,11 @@ impl Diagnostic {
 
   fn fmt_related_information(&self, f: &mut fmt::Formatter) -> fmt::Result {
     if let Some(related_information) = self.related_information.as_ref() {
-      if !related_information.is_empty() {
+      // each related span (ex. "the expected type comes from here") gets its
+      // own code frame so it can be read independently of the error
+      for info in related_information {
         write!(f, "\n\n")?;
-        for info in related_information {
-          info.fmt_stack(f, 4)?;
-        }
+        info.fmt_stack(f, 4)?;
       }
     }
 
@@ -359,27 +359,169 @@ impl Serialize for Diagnostics {
   }
 }
 
-impl fmt::Display for Diagnostics {
-  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-    let mut i = 0;
-    for item in &self.0 {
-      if i > 0 {
+/// Groups the diagnostics by file, keeping the order in which each file was
+/// first reported. Diagnostics without a file (ex. compiler options) don't
+/// belong to any file, so they are grouped first.
+fn group_by_file(
+  diagnostics: &[Diagnostic],
+) -> Vec<(Option<&str>, Vec<&Diagnostic>)> {
+  let mut groups: Vec<(Option<&str>, Vec<&Diagnostic>)> = Vec::new();
+  for item in diagnostics {
+    let file_name = item.file_name.as_deref();
+    match groups.iter_mut().find(|(name, _)| *name == file_name) {
+      Some((_, items)) => items.push(item),
+      None => groups.push((file_name, vec![item])),
+    }
+  }
+  groups.sort_by_key(|(file_name, _)| file_name.is_some());
+  groups
+}
+
+/// Formats the number of diagnostics of each category, for example
+/// `3 errors, 1 warning`.
+fn fmt_category_counts<'a>(
+  diagnostics: impl IntoIterator<Item = &'a Diagnostic>,
+) -> String {
+  let mut counts = [0usize; 4];
+  for diagnostic in diagnostics {
+    let index = match diagnostic.category {
+      DiagnosticCategory::Error => 0,
+      DiagnosticCategory::Warning => 1,
+      DiagnosticCategory::Suggestion => 2,
+      DiagnosticCategory::Message => 3,
+    };
+    counts[index] += 1;
+  }
+  let names = [
+    ("error", "errors"),
+    ("warning", "warnings"),
+    ("suggestion", "suggestions"),
+    ("message", "messages"),
+  ];
+  counts
+    .iter()
+    .zip(names)
+    .filter(|(count, _)| **count > 0)
+    .map(|(count, (singular, plural))| {
+      format!("{} {}", count, if *count == 1 { singular } else { plural })
+    })
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
+/// Writes the diagnostics grouped by file, with a header per file when there
+/// is more than one diagnostic.
+fn fmt_grouped(
+  f: &mut fmt::Formatter,
+  diagnostics: &[Diagnostic],
+) -> fmt::Result {
+  let show_headers = diagnostics.len() > 1;
+  let mut i = 0;
+  for (file_name, items) in group_by_file(diagnostics) {
+    if show_headers {
+      if let Some(file_name) = file_name {
+        if i > 0 {
+          write!(f, "\n\n")?;
+        }
+        write!(
+          f,
+          "{} ({})",
+          colors::cyan(file_name),
+          fmt_category_counts(items.iter().copied())
+        )?;
+      }
+    }
+    for item in items {
+      if i > 0 || (show_headers && file_name.is_some()) {
         write!(f, "\n\n")?;
       }
       write!(f, "{item}")?;
       i += 1;
     }
+  }
+  Ok(())
+}
 
-    if i > 1 {
-      write!(f, "\n\nFound {i} errors.")?;
-    }
+/// Writes the total number of diagnostics of each category when there is more
+/// than one diagnostic.
+fn fmt_found(
+  f: &mut fmt::Formatter,
+  diagnostics: &[Diagnostic],
+) -> fmt::Result {
+  if diagnostics.len() > 1 {
+    write!(f, "\n\nFound {}.", fmt_category_counts(diagnostics))?;
+  }
+  Ok(())
+}
 
-    Ok(())
+impl fmt::Display for Diagnostics {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    fmt_grouped(f, &self.0)?;
+    fmt_found(f, &self.0)
   }
 }
 
 impl Error for Diagnostics {}
 
+/// Diagnostics where only the first `limit` diagnostics are displayed and the
+/// remaining ones are counted by category.
+#[derive(Debug)]
+pub struct LimitedDiagnostics {
+  pub diagnostics: Diagnostics,
+  pub limit: usize,
+}
+
+impl fmt::Display for LimitedDiagnostics {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    let all = &self.diagnostics.0;
+    let (shown, hidden) = all.split_at(self.limit.min(all.len()));
+    fmt_grouped(f, shown)?;
+
+    if !hidden.is_empty() {
+      if !shown.is_empty() {
+        write!(f, "\n\n")?;
+      }
+      write!(
+        f,
+        "{} more not shown ({}).",
+        hidden.len(),
+        fmt_category_counts(hidden)
+      )?;
+    }
+
+    fmt_found(f, all)
+  }
+}
+
+impl Error for LimitedDiagnostics {}
+
+/// Diagnostics displayed as the number of diagnostics of each category per
+/// file, instead of displaying each diagnostic.
+#[derive(Debug)]
+pub struct DiagnosticsSummary(pub Diagnostics);
+
+impl fmt::Display for DiagnosticsSummary {
+  fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    for (i, (file_name, items)) in
+      group_by_file(&self.0 .0).into_iter().enumerate()
+    {
+      if i > 0 {
+        writeln!(f)?;
+      }
+      write!(
+        f,
+        "{}: {}",
+        colors::cyan(file_name.unwrap_or("(global)")),
+        fmt_category_counts(items)
+      )?;
+    }
+
+    fmt_found(f, &self.0 .0)
+  }
+}
+
+impl Error for DiagnosticsSummary {}
+
 #[cfg(test)]
 mod tests {
   use super::*;
@@ -583,4 +725,206 @@ mod tests {
     let actual = diagnostics.to_string();
     assert_eq!(strip_ansi_codes(&actual), "TS2552 [ERROR]: Cannot find name \'foo_Bar\'. Did you mean \'foo_bar\'?\nfoo_Bar();\n~~~~~~~\n    at test.ts:8:1\n\n    \'foo_bar\' is declared here.\n    function foo_bar() {\n             ~~~~~~~\n        at test.ts:4:10");
   }
+
+  #[test]
+  fn test_diagnostics_multiple_related_info() {
+    let value = json!([
+      {
+        "start": { "line": 2, "character": 6 },
+        "end": { "line": 2, "character": 7 },
+        "fileName": "test.ts",
+        "messageText": "Type 'string' is not assignable to type 'number'.",
+        "sourceLine": "const a: Foo = { b: \"\" };",
+        "relatedInformation": [
+          {
+            "start": { "line": 0, "character": 16 },
+            "end": { "line": 0, "character": 17 },
+            "fileName": "test.ts",
+            "messageText": "The expected type comes from property 'b' which is declared here on type 'Foo'",
+            "sourceLine": "interface Foo { b: number }",
+            "category": 3,
+            "code": 6500
+          },
+          {
+            "start": { "line": 1, "character": 9 },
+            "end": { "line": 1, "character": 12 },
+            "fileName": "other.ts",
+            "messageText": "'Foo' is also declared here.",
+            "sourceLine": "export { Foo };",
+            "category": 3,
+            "code": 6203
+          }
+        ],
+        "category": 1,
+        "code": 2322
+      }
+    ]);
+    let diagnostics: Diagnostics = serde_json::from_value(value).unwrap();
+    let actual = diagnostics.to_string();
+    assert_eq!(
+      strip_ansi_codes(&actual),
+      concat!(
+        "TS2322 [ERROR]: Type 'string' is not assignable to type 'number'.\n",
+        "const a: Foo = { b: \"\" };\n",
+        "      ^\n",
+        "    at test.ts:3:7\n",
+        "\n",
+        "    The expected type comes from property 'b' which is declared here on type 'Foo'\n",
+        "    interface Foo { b: number }\n",
+        "                    ^\n",
+        "        at test.ts:1:17\n",
+        "\n",
+        "    'Foo' is also declared here.\n",
+        "    export { Foo };\n",
+        "             ~~~\n",
+        "        at other.ts:2:10",
+      )
+    );
+  }
+
+  #[test]
+  fn test_diagnostics_grouped_by_file() {
+    let diagnostic = |file_name: &str, line: u64, message: &str| {
+      json!({
+        "start": { "line": line, "character": 0 },
+        "end": { "line": line, "character": 1 },
+        "fileName": file_name,
+        "messageText": message,
+        "sourceLine": "a;",
+        "category": 1,
+        "code": 2304
+      })
+    };
+    let value = json!([
+      diagnostic("a.ts", 0, "Cannot find name 'a'."),
+      diagnostic("b.ts", 0, "Cannot find name 'a'."),
+      diagnostic("a.ts", 4, "Cannot find name 'a'."),
+    ]);
+    let diagnostics: Diagnostics = serde_json::from_value(value).unwrap();
+    let actual = diagnostics.to_string();
+    assert_eq!(
+      strip_ansi_codes(&actual),
+      concat!(
+        "a.ts (2 errors)\n",
+        "\n",
+        "TS2304 [ERROR]: Cannot find name 'a'.\n",
+        "a;\n",
+        "^\n",
+        "    at a.ts:1:1\n",
+        "\n",
+        "TS2304 [ERROR]: Cannot find name 'a'.\n",
+        "a;\n",
+        "^\n",
+        "    at a.ts:5:1\n",
+        "\n",
+        "b.ts (1 error)\n",
+        "\n",
+        "TS2304 [ERROR]: Cannot find name 'a'.\n",
+        "a;\n",
+        "^\n",
+        "    at b.ts:1:1\n",
+        "\n",
+        "Found 3 errors.",
+      )
+    );
+  }
+
+  #[test]
+  fn test_diagnostics_count_warnings_separately() {
+    let value = json!([
+      {
+        "start": { "line": 0, "character": 0 },
+        "end": { "line": 0, "character": 1 },
+        "fileName": "a.ts",
+        "messageText": "Cannot find name 'a'.",
+        "sourceLine": "a;",
+        "category": 1,
+        "code": 2304
+      },
+      {
+        "start": { "line": 1, "character": 0 },
+        "end": { "line": 1, "character": 1 },
+        "fileName": "a.ts",
+        "messageText": "'b' is declared but its value is never read.",
+        "sourceLine": "b;",
+        "category": 0,
+        "code": 6133
+      }
+    ]);
+    let diagnostics: Diagnostics = serde_json::from_value(value).unwrap();
+    let actual = strip_ansi_codes(&diagnostics.to_string()).to_string();
+    assert!(
+      actual.starts_with("a.ts (1 error, 1 warning)\n"),
+      "{actual}"
+    );
+    assert!(
+      actual.ends_with("\n\nFound 1 error, 1 warning."),
+      "{actual}"
+    );
+  }
+
+  fn diagnostics_for_limit_tests() -> Diagnostics {
+    let diagnostic = |file_name: &str, category: u8| {
+      json!({
+        "start": { "line": 0, "character": 0 },
+        "end": { "line": 0, "character": 1 },
+        "fileName": file_name,
+        "messageText": "Cannot find name 'a'.",
+        "sourceLine": "a;",
+        "category": category,
+        "code": 2304
+      })
+    };
+    let value = json!([
+      diagnostic("a.ts", 1),
+      diagnostic("a.ts", 1),
+      diagnostic("b.ts", 0),
+      diagnostic("b.ts", 1),
+    ]);
+    serde_json::from_value(value).unwrap()
+  }
+
+  #[test]
+  fn test_limited_diagnostics() {
+    let diagnostics = LimitedDiagnostics {
+      diagnostics: diagnostics_for_limit_tests(),
+      limit: 1,
+    };
+    assert_eq!(
+      strip_ansi_codes(&diagnostics.to_string()),
+      concat!(
+        "TS2304 [ERROR]: Cannot find name 'a'.\n",
+        "a;\n",
+        "^\n",
+        "    at a.ts:1:1\n",
+        "\n",
+        "3 more not shown (2 errors, 1 warning).\n",
+        "\n",
+        "Found 3 errors, 1 warning.",
+      )
+    );
+
+    let diagnostics = LimitedDiagnostics {
+      diagnostics: diagnostics_for_limit_tests(),
+      limit: 0,
+    };
+    assert_eq!(
+      strip_ansi_codes(&diagnostics.to_string()),
+      "4 more not shown (3 errors, 1 warning).\n\nFound 3 errors, 1 warning."
+    );
+  }
+
+  #[test]
+  fn test_diagnostics_summary() {
+    let summary = DiagnosticsSummary(diagnostics_for_limit_tests());
+    assert_eq!(
+      strip_ansi_codes(&summary.to_string()),
+      concat!(
+        "a.ts: 2 errors\n",
+        "b.ts: 1 error, 1 warning\n",
+        "\n",
+        "Found 3 errors, 1 warning.",
+      )
+    );
+  }
 }
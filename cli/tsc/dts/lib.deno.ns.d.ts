@@ -674,6 +674,14 @@ declare namespace Deno {
      * @default {false}
      */
     write?: "inherit" | boolean | Array<string | URL>;
+
+    /** Specifies if permissions that weren't granted can be requested with a
+     * prompt. A context can only prompt when its parent can, so setting this
+     * to `true` has no effect when the parent can't prompt.
+     *
+     * @default {true}
+     */
+    prompt?: boolean;
   }
 
   /**
@@ -762,6 +770,30 @@ declare namespace Deno {
      * ```
      */
     step(fn: (t: TestContext) => void | Promise<void>): Promise<boolean>;
+
+    /** Assert that `actual` matches the snapshot stored for this assertion,
+     * throwing an `AssertionError` with a diff if it doesn't.
+     *
+     * Snapshots are stored in a `__snapshots__` directory next to the test
+     * module and are created or updated by running
+     * `deno test --update-snapshots`. They are named after the test and the
+     * number of the assertion within it.
+     *
+     * ```ts
+     * Deno.test("user", (t) => {
+     *   t.assertSnapshot({ name: "deno", roles: ["admin"] });
+     * });
+     * ```
+     */
+    assertSnapshot(
+      actual: unknown,
+      options?: {
+        /** Converts the value into the string stored in the snapshot file.
+         * Defaults to `Deno.inspect()` with options producing a stable and
+         * complete representation. */
+        serializer?: (value: unknown) => string;
+      },
+    ): void;
   }
 
   /** @category Testing */
@@ -832,6 +864,19 @@ declare namespace Deno {
      *
      * @default {true} */
     sanitizeExit?: boolean;
+    /** Run the test again, up to this many times, if it fails. A test that
+     * fails but then passes when it is retried is reported as flaky.
+     *
+     * Overrides the `--retries` flag of `deno test` for this test.
+     *
+     * @default {0} */
+    retries?: number;
+    /** Fail the test if it takes longer than this many milliseconds. The
+     * failure shows where the test was stuck: the code it was running or the
+     * operations it was waiting on.
+     *
+     * Overrides the `--timeout` flag of `deno test` for this test. */
+    timeout?: number;
     /** Specifies the permissions that should be used to run the test.
      *
      * Set this to "inherit" to keep the calling runtime permissions, set this
@@ -2426,6 +2471,68 @@ declare namespace Deno {
      * ```
      */
     truncateSync(len?: number): void;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Preallocates disk space for the file so that it is at least `len`
+     * bytes long, extending it if necessary. Subsequent writes within the
+     * allocated range won't fail for lack of disk space.
+     *
+     * ```ts
+     * using file = await Deno.open("my_file.txt", { write: true, create: true });
+     * await file.allocate(1024 * 1024);
+     * ```
+     *
+     * @category File System
+     */
+    allocate(len: number): Promise<void>;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Synchronously preallocates disk space for the file so that it is at
+     * least `len` bytes long, extending it if necessary.
+     *
+     * ```ts
+     * using file = Deno.openSync("my_file.txt", { write: true, create: true });
+     * file.allocateSync(1024 * 1024);
+     * ```
+     *
+     * @category File System
+     */
+    allocateSync(len: number): void;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Copies up to `len` bytes, or everything up to the end of the file, from
+     * the current position of this file to the current position of `dest`.
+     * Where the OS supports it (`copy_file_range` and `sendfile` on Linux),
+     * the data is copied in the kernel without passing through user space.
+     * `dest` can also be a TCP or Unix socket connection, except on Windows.
+     * Resolves to the number of bytes copied.
+     *
+     * ```ts
+     * using src = await Deno.open("my_file.txt");
+     * using dest = await Deno.open("copy.txt", { write: true, create: true });
+     * const copied = await src.copyTo(dest);
+     * ```
+     *
+     * @category File System
+     */
+    copyTo(dest: FsFile | Conn, len?: number): Promise<number>;
+    /** **UNSTABLE**: New API, yet to be vetted.
+     *
+     * Synchronously copies up to `len` bytes, or everything up to the end of
+     * the file, from the current position of this file to the current
+     * position of `dest`. Unlike {@linkcode Deno.FsFile.copyTo}, `dest` must
+     * be a file, since waiting for a socket would block the thread. Returns
+     * the number of bytes copied.
+     *
+     * ```ts
+     * using src = Deno.openSync("my_file.txt");
+     * using dest = Deno.openSync("copy.txt", { write: true, create: true });
+     * const copied = src.copyToSync(dest);
+     * ```
+     *
+     * @category File System
+     */
+    copyToSync(dest: FsFile, len?: number): number;
     /** Read the file into an array buffer (`p`).
      *
      * Resolves to either the number of bytes read during the operation or EOF
@@ -3175,6 +3282,17 @@ declare namespace Deno {
     /** String that should follow the random portion of the temporary
      * directory's name. */
     suffix?: string;
+    /** Permissions of the temporary file or directory, before the process
+     * umask is applied (ignored on Windows).
+     *
+     * @default {0o600 for files, 0o700 for directories} */
+    mode?: number;
+    /** Remove the temporary file or directory (with its contents) when the
+     * process exits, including through `Deno.exit()`, an uncaught exception,
+     * or SIGINT when the program doesn't listen for that signal.
+     *
+     * @default {false} */
+    cleanupAtExit?: boolean;
   }
 
   /** Creates a new temporary directory in the default directory for temporary
@@ -3625,6 +3743,11 @@ declare namespace Deno {
      *
      * _Linux/Mac OS only._ */
     isSocket: boolean | null;
+    /** True if the file has the immutable flag set, meaning it can't be
+     * modified, renamed or removed, see {@linkcode Deno.setImmutable}.
+     *
+     * _Linux/Mac OS only. `null` if the flag couldn't be read._ */
+    immutable: boolean | null;
   }
 
   /** Resolves to the absolute normalized path, with symbolic links resolved.
@@ -4145,6 +4268,33 @@ declare namespace Deno {
     paths: string[];
     /** Any additional flags associated with the event. */
     flag?: FsEventFlag;
+    /** Set when the event is a rename, with the old and the new path of the
+     * renamed file. On platforms that report the two sides of a rename as
+     * separate events, an additional `"modify"` event is yielded with both
+     * paths once the rename is complete. */
+    rename?: { from: string; to: string };
+  }
+
+  /**
+   * Options which can be set when calling {@linkcode Deno.watchFs}.
+   *
+   * @category File System
+   */
+  export interface WatchFsOptions {
+    /** For directories, also watch all of their sub directories.
+     *
+     * @default {true} */
+    recursive: boolean;
+    /** Only yield the events for a path matching one of these globs. Relative
+     * globs are resolved against the current working directory. */
+    include?: string[];
+    /** Don't yield the events for a path matching one of these globs.
+     * Relative globs are resolved against the current working directory. */
+    exclude?: string[];
+    /** Wait this many milliseconds after an event for more events, and yield
+     * the burst without duplicate events once the file system is quiet. A
+     * burst is held back for at most ten times this period. */
+    debounce?: number;
   }
 
   /**
@@ -4210,6 +4360,17 @@ declare namespace Deno {
    * }
    * ```
    *
+   * The events can be filtered with `include` and `exclude` globs, and rapid
+   * bursts of events can be coalesced with `debounce`.
+   *
+   * ```ts
+   * const watcher = Deno.watchFs(".", {
+   *   recursive: true,
+   *   exclude: ["node_modules/**", "dist/**"],
+   *   debounce: 50,
+   * });
+   * ```
+   *
    * Requires `allow-read` permission.
    *
    * @tags allow-read
@@ -4217,7 +4378,7 @@ declare namespace Deno {
    */
   export function watchFs(
     paths: string | string[],
-    options?: { recursive: boolean },
+    options?: WatchFsOptions,
   ): FsWatcher;
 
   /**
@@ -4638,9 +4799,12 @@ declare namespace Deno {
      *
      * Defaults to `SIGTERM` if no signal is provided.
      *
+     * With `processGroup: true`, the signal is sent to every process in the
+     * process group of a `detached` child. This is not supported on Windows.
+     *
      * @param [signo="SIGTERM"]
      */
-    kill(signo?: Signal): void;
+    kill(signo?: Signal, options?: { processGroup?: boolean }): void;
 
     /** Ensure that the status of the child process prevents the Deno process
      * from exiting. */
@@ -4714,6 +4878,15 @@ declare namespace Deno {
      *
      * @default {false} */
     windowsRawArguments?: boolean;
+
+    /** Starts the child in a new process group (`CREATE_NEW_PROCESS_GROUP`
+     * on Windows). The child then doesn't receive the signals sent to the
+     * process group of this process, such as `SIGINT` on Ctrl+C, and signals
+     * can be sent to the child along with its descendants with
+     * `kill(signo, { processGroup: true })`.
+     *
+     * @default {false} */
+    detached?: boolean;
   }
 
   /**
@@ -4723,9 +4896,11 @@ declare namespace Deno {
     /** If the child process exits with a 0 status code, `success` will be set
      * to `true`, otherwise `false`. */
     success: boolean;
-    /** The exit code of the child process. */
+    /** The exit code of the child process. When the child was terminated by a
+     * signal, this is `128` plus the number of the signal. */
     code: number;
-    /** The signal associated with the child process. */
+    /** The signal that terminated the child process, or `null` if it exited
+     * on its own. Always `null` on Windows. */
     signal: Signal | null;
   }
 
@@ -5180,6 +5355,11 @@ declare namespace Deno {
    * Requesting already granted permissions will not prompt the user and will
    * return that the permission was granted.
    *
+   * The permissions apply to all the code running in the same thread, so they
+   * can't be reduced for a single module, such as one that is imported
+   * dynamically. Run the module in a `Worker` with the `deno.permissions`
+   * option instead to give it fewer permissions.
+   *
    * ### Querying
    *
    * ```ts
@@ -5775,6 +5955,9 @@ declare namespace Deno {
    * This operation does not yet consume the request or open the websocket. This
    * only happens once the returned response has been passed to `respondWith()`.
    *
+   * No WebSocket extensions are negotiated. A `permessage-deflate` offer from
+   * the client is declined, so messages are always sent uncompressed.
+   *
    * @category Web Sockets
    */
   export function upgradeWebSocket(
@@ -5794,6 +5977,10 @@ declare namespace Deno {
    * identified by `pid`. An error will be thrown if a negative `pid` is used on
    * Windows.
    *
+   * On Windows only `"SIGTERM"` and `"SIGKILL"` are supported. Both terminate
+   * the process immediately with `TerminateProcess`, so the process can't
+   * handle them, and it exits with code 1.
+   *
    * ```ts
    * const p = Deno.run({
    *   cmd: ["sleep", "10000"]
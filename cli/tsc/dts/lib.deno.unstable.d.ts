@@ -984,6 +984,23 @@ declare namespace Deno {
      * @default {false}
      */
     allowHost?: boolean;
+    /** Whether the client stores the cookies set by responses and sends them
+     * along with later requests whose URL matches their domain, path and
+     * secure attributes.
+     *
+     * The cookies are only kept in memory for the lifetime of the client, so
+     * they are not shared with other clients or persisted across runs.
+     *
+     * @default {false}
+     */
+    cookieStore?: boolean;
+    /** Send the requests made with the client to the server listening on
+     * this unix domain socket, instead of connecting to the host of the URL.
+     * The URL still provides the request path and the `Host` header.
+     *
+     * Requires `allow-read` and `allow-write` permission for the socket.
+     * Not supported on Windows. */
+    unixSocketPath?: string;
   }
 
   /** **UNSTABLE**: New API, yet to be vetted.
@@ -1066,194 +1083,370 @@ declare namespace Deno {
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * Represents membership of a IPv4 multicast group.
+   * Acquire an advisory file-system lock for the provided file.
    *
-   * @category Network
+   * @param [exclusive=false]
+   * @category File System
    * @tags unstable
    */
-  export interface MulticastV4Membership {
-    /** Leaves the multicast group. */
-    leave: () => Promise<void>;
-    /** Sets the multicast loopback option. If enabled, multicast packets will be looped back to the local socket. */
-    setLoopback: (loopback: boolean) => Promise<void>;
-    /** Sets the time-to-live of outgoing multicast packets for this socket. */
-    setTTL: (ttl: number) => Promise<void>;
-  }
+  export function flock(rid: number, exclusive?: boolean): Promise<void>;
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * Represents membership of a IPv6 multicast group.
+   * Acquire an advisory file-system lock synchronously for the provided file.
    *
-   * @category Network
+   * @param [exclusive=false]
+   * @category File System
    * @tags unstable
    */
-  export interface MulticastV6Membership {
-    /** Leaves the multicast group. */
-    leave: () => Promise<void>;
-    /** Sets the multicast loopback option. If enabled, multicast packets will be looped back to the local socket. */
-    setLoopback: (loopback: boolean) => Promise<void>;
-  }
+  export function flockSync(rid: number, exclusive?: boolean): void;
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * A generic transport listener for message-oriented protocols.
+   * Release an advisory file-system lock for the provided file.
    *
-   * @category Network
+   * @category File System
    * @tags unstable
    */
-  export interface DatagramConn extends AsyncIterable<[Uint8Array, Addr]> {
-    /** Joins an IPv4 multicast group. */
-    joinMulticastV4(
-      address: string,
-      networkInterface: string,
-    ): Promise<MulticastV4Membership>;
-
-    /** Joins an IPv6 multicast group. */
-    joinMulticastV6(
-      address: string,
-      networkInterface: number,
-    ): Promise<MulticastV6Membership>;
+  export function funlock(rid: number): Promise<void>;
 
-    /** Waits for and resolves to the next message to the instance.
-     *
-     * Messages are received in the format of a tuple containing the data array
-     * and the address information.
-     */
-    receive(p?: Uint8Array): Promise<[Uint8Array, Addr]>;
-    /** Sends a message to the target via the connection. The method resolves
-     * with the number of bytes sent. */
-    send(p: Uint8Array, addr: Addr): Promise<number>;
-    /** Close closes the socket. Any pending message promises will be rejected
-     * with errors. */
-    close(): void;
-    /** Return the address of the instance. */
-    readonly addr: Addr;
-    [Symbol.asyncIterator](): AsyncIterableIterator<[Uint8Array, Addr]>;
-  }
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Release an advisory file-system lock for the provided file synchronously.
+   *
+   * @category File System
+   * @tags unstable
+   */
+  export function funlockSync(rid: number): void;
 
-  /**
-   * @category Network
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Options which can be set when calling
+   * {@linkcode Deno.openReadableStream}.
+   *
+   * @category File System
    * @tags unstable
    */
-  export interface TcpListenOptions extends ListenOptions {
-    /** When `true` the SO_REUSEPORT flag will be set on the listener. This
-     * allows multiple processes to listen on the same address and port.
-     *
-     * On Linux this will cause the kernel to distribute incoming connections
-     * across the different processes that are listening on the same address and
-     * port.
+  export interface OpenReadableStreamOptions {
+    /** The size in bytes of the chunks read from the file.
      *
-     * This flag is only supported on Linux. It is silently ignored on other
-     * platforms.
+     * @default {65536} */
+    chunkSize?: number;
+    /** The byte offset in the file to start reading from.
      *
-     * @default {false} */
-    reusePort?: boolean;
+     * @default {0} */
+    start?: number;
   }
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * Unstable options which can be set when opening a datagram listener via
-   * {@linkcode Deno.listenDatagram}.
+   * Opens the file at `path` for reading and resolves to a byte stream of its
+   * contents. The file is read in chunks of `chunkSize` bytes, directly into
+   * the buffers of the stream (or of a BYOB reader), so files of any size can
+   * be processed without reading them into memory. The file is closed once
+   * the stream is read to the end or cancelled.
+   *
+   * ```ts
+   * const stream = await Deno.openReadableStream("./large.bin", {
+   *   chunkSize: 1024 * 1024,
+   * });
+   * for await (const chunk of stream) {
+   *   console.log(chunk.byteLength);
+   * }
+   * ```
+   *
+   * Requires `allow-read` permission.
    *
-   * @category Network
+   * @tags allow-read, unstable
+   * @category File System
+   */
+  export function openReadableStream(
+    path: string | URL,
+    options?: OpenReadableStreamOptions,
+  ): Promise<ReadableStream<Uint8Array>>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Options which can be set when calling
+   * {@linkcode Deno.openWritableStream}.
+   *
+   * @category File System
    * @tags unstable
    */
-  export interface UdpListenOptions extends ListenOptions {
-    /** When `true` the specified address will be reused, even if another
-     * process has already bound a socket on it. This effectively steals the
-     * socket from the listener.
+  export interface OpenWritableStreamOptions {
+    /** Append to the file instead of truncating it.
      *
      * @default {false} */
-    reuseAddress?: boolean;
-
-    /** When `true`, sent multicast packets will be looped back to the local socket.
+    append?: boolean;
+    /** Create the file if it doesn't exist.
+     *
+     * @default {true} */
+    create?: boolean;
+    /** Fail if the file already exists.
      *
      * @default {false} */
-    loopback?: boolean;
+    createNew?: boolean;
+    /** Permissions to use if creating the file (ignored on Windows). */
+    mode?: number;
   }
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * Listen announces on the local transport address.
+   * Opens the file at `path` for writing and resolves to a stream which
+   * writes the chunks written to it to the file. The file is closed when
+   * the stream is closed or aborted.
    *
    * ```ts
-   * const listener1 = Deno.listenDatagram({
-   *   port: 80,
-   *   transport: "udp"
-   * });
-   * const listener2 = Deno.listenDatagram({
-   *   hostname: "golang.org",
-   *   port: 80,
-   *   transport: "udp"
-   * });
+   * const src = await Deno.openReadableStream("./large.bin");
+   * const dest = await Deno.openWritableStream("./copy.bin");
+   * await src.pipeTo(dest);
    * ```
    *
-   * Requires `allow-net` permission.
+   * Requires `allow-write` permission, and `allow-read` permission if
+   * `create` is `false`.
    *
-   * @tags allow-net, unstable
-   * @category Network
+   * @tags allow-read, allow-write, unstable
+   * @category File System
    */
-  export function listenDatagram(
-    options: UdpListenOptions & { transport: "udp" },
-  ): DatagramConn;
+  export function openWritableStream(
+    path: string | URL,
+    options?: OpenWritableStreamOptions,
+  ): Promise<WritableStream<Uint8Array>>;
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * Listen announces on the local transport address.
+   * Creates a temporary directory, calls `fn` with its path and removes the
+   * directory with its contents once the promise returned by `fn` settles.
+   * The directory is also removed if the process exits in the meantime, as
+   * with the `cleanupAtExit` option of {@linkcode Deno.makeTempDir}.
+   *
+   * Resolves to the value `fn` resolved to.
    *
    * ```ts
-   * const listener = Deno.listenDatagram({
-   *   path: "/foo/bar.sock",
-   *   transport: "unixpacket"
+   * const count = await Deno.withTempDir(async (dir) => {
+   *   await Deno.writeTextFile(`${dir}/data.txt`, "hello");
+   *   return Array.from(Deno.readDirSync(dir)).length;
    * });
    * ```
    *
-   * Requires `allow-read` and `allow-write` permission.
+   * Requires `allow-write` permission.
    *
-   * @tags allow-read, allow-write, unstable
-   * @category Network
+   * @tags allow-write, unstable
+   * @category File System
    */
-  export function listenDatagram(
-    options: UnixListenOptions & { transport: "unixpacket" },
-  ): DatagramConn;
+  export function withTempDir<T>(
+    fn: (path: string) => T | Promise<T>,
+    options?: Omit<MakeTempOptions, "cleanupAtExit">,
+  ): Promise<T>;
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * Acquire an advisory file-system lock for the provided file.
+   * Resolves to the value of the extended attribute `name` of the file at
+   * `path`, or `null` if the file doesn't have that attribute.
    *
-   * @param [exclusive=false]
+   * ```ts
+   * const value = await Deno.getXattr("file.txt", "user.origin");
+   * if (value) console.log(new TextDecoder().decode(value));
+   * ```
+   *
+   * _Linux/Mac OS only._
+   *
+   * Requires `allow-read` permission.
+   *
+   * @tags allow-read, unstable
    * @category File System
-   * @tags unstable
    */
-  export function flock(rid: number, exclusive?: boolean): Promise<void>;
+  export function getXattr(
+    path: string | URL,
+    name: string,
+  ): Promise<Uint8Array | null>;
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * Acquire an advisory file-system lock synchronously for the provided file.
+   * Synchronously returns the value of the extended attribute `name` of the
+   * file at `path`, or `null` if the file doesn't have that attribute.
    *
-   * @param [exclusive=false]
+   * ```ts
+   * const value = Deno.getXattrSync("file.txt", "user.origin");
+   * if (value) console.log(new TextDecoder().decode(value));
+   * ```
+   *
+   * _Linux/Mac OS only._
+   *
+   * Requires `allow-read` permission.
+   *
+   * @tags allow-read, unstable
    * @category File System
-   * @tags unstable
    */
-  export function flockSync(rid: number, exclusive?: boolean): void;
+  export function getXattrSync(
+    path: string | URL,
+    name: string,
+  ): Uint8Array | null;
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * Release an advisory file-system lock for the provided file.
+   * Sets the extended attribute `name` of the file at `path` to `value`,
+   * creating it if needed. Strings are stored UTF-8 encoded. On Linux, names
+   * set by unprivileged processes must be in the `user.` namespace.
    *
+   * ```ts
+   * await Deno.setXattr("file.txt", "user.origin", "https://deno.land");
+   * ```
+   *
+   * _Linux/Mac OS only._
+   *
+   * Requires `allow-write` permission.
+   *
+   * @tags allow-write, unstable
    * @category File System
-   * @tags unstable
    */
-  export function funlock(rid: number): Promise<void>;
+  export function setXattr(
+    path: string | URL,
+    name: string,
+    value: Uint8Array | string,
+  ): Promise<void>;
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
-   * Release an advisory file-system lock for the provided file synchronously.
+   * Synchronously sets the extended attribute `name` of the file at `path` to
+   * `value`, creating it if needed. Strings are stored UTF-8 encoded. On
+   * Linux, names set by unprivileged processes must be in the `user.`
+   * namespace.
+   *
+   * ```ts
+   * Deno.setXattrSync("file.txt", "user.origin", "https://deno.land");
+   * ```
    *
+   * _Linux/Mac OS only._
+   *
+   * Requires `allow-write` permission.
+   *
+   * @tags allow-write, unstable
    * @category File System
-   * @tags unstable
    */
-  export function funlockSync(rid: number): void;
+  export function setXattrSync(
+    path: string | URL,
+    name: string,
+    value: Uint8Array | string,
+  ): void;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Removes the extended attribute `name` from the file at `path`.
+   *
+   * ```ts
+   * await Deno.removeXattr("file.txt", "user.origin");
+   * ```
+   *
+   * _Linux/Mac OS only._
+   *
+   * Requires `allow-write` permission.
+   *
+   * @tags allow-write, unstable
+   * @category File System
+   */
+  export function removeXattr(path: string | URL, name: string): Promise<void>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Synchronously removes the extended attribute `name` from the file at
+   * `path`.
+   *
+   * ```ts
+   * Deno.removeXattrSync("file.txt", "user.origin");
+   * ```
+   *
+   * _Linux/Mac OS only._
+   *
+   * Requires `allow-write` permission.
+   *
+   * @tags allow-write, unstable
+   * @category File System
+   */
+  export function removeXattrSync(path: string | URL, name: string): void;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Resolves to the names of the extended attributes of the file at `path`.
+   *
+   * ```ts
+   * console.log(await Deno.listXattrs("file.txt")); // e.g. [ "user.origin" ]
+   * ```
+   *
+   * _Linux/Mac OS only._
+   *
+   * Requires `allow-read` permission.
+   *
+   * @tags allow-read, unstable
+   * @category File System
+   */
+  export function listXattrs(path: string | URL): Promise<string[]>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Synchronously returns the names of the extended attributes of the file at
+   * `path`.
+   *
+   * ```ts
+   * console.log(Deno.listXattrsSync("file.txt")); // e.g. [ "user.origin" ]
+   * ```
+   *
+   * _Linux/Mac OS only._
+   *
+   * Requires `allow-read` permission.
+   *
+   * @tags allow-read, unstable
+   * @category File System
+   */
+  export function listXattrsSync(path: string | URL): string[];
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Sets or clears the immutable flag of the file at `path`. An immutable file
+   * can't be modified, renamed or removed until the flag is cleared. The
+   * current state is reported by {@linkcode Deno.FileInfo.immutable}.
+   *
+   * Setting the flag on Linux requires the `CAP_LINUX_IMMUTABLE` capability.
+   * On Mac OS the user immutable flag (`uchg`) is used.
+   *
+   * ```ts
+   * await Deno.setImmutable("config.json", true);
+   * ```
+   *
+   * _Linux/Mac OS only._
+   *
+   * Requires `allow-write` permission.
+   *
+   * @tags allow-write, unstable
+   * @category File System
+   */
+  export function setImmutable(
+    path: string | URL,
+    immutable: boolean,
+  ): Promise<void>;
+
+  /** **UNSTABLE**: New API, yet to be vetted.
+   *
+   * Synchronously sets or clears the immutable flag of the file at `path`. An
+   * immutable file can't be modified, renamed or removed until the flag is
+   * cleared. The current state is reported by
+   * {@linkcode Deno.FileInfo.immutable}.
+   *
+   * Setting the flag on Linux requires the `CAP_LINUX_IMMUTABLE` capability.
+   * On Mac OS the user immutable flag (`uchg`) is used.
+   *
+   * ```ts
+   * Deno.setImmutableSync("config.json", true);
+   * ```
+   *
+   * _Linux/Mac OS only._
+   *
+   * Requires `allow-write` permission.
+   *
+   * @tags allow-write, unstable
+   * @category File System
+   */
+  export function setImmutableSync(path: string | URL, immutable: boolean): void;
 
   /** **UNSTABLE**: New API, yet to be vetted.
    *
@@ -2299,15 +2492,19 @@ declare interface WorkerOptions {
   /** **UNSTABLE**: New API, yet to be vetted.
    *
    * Configure permissions options to change the level of access the worker will
-   * have. By default it will have no permissions. Note that the permissions
-   * of a worker can't be extended beyond its parent's permissions reach.
+   * have. By default it will inherit the permissions of its parent. Note that
+   * the permissions of a worker can't be extended beyond its parent's
+   * permissions reach.
    *
    * - `"inherit"` will take the permissions of the thread the worker is created
    *   in.
-   * - `"none"` will use the default behavior and have no permission
+   * - `"none"` will have no permission
    * - A list of routes can be provided that are relative to the file the worker
    *   is created in to limit the access of the worker (read/write permissions
    *   only)
+   * - `prompt: false` prevents the worker from prompting for permissions it
+   *   wasn't granted. Otherwise the worker prompts whenever its parent would,
+   *   and permissions granted by those prompts only apply to the worker.
    *
    * Example:
    *
@@ -2323,6 +2520,20 @@ declare interface WorkerOptions {
    *     },
    *   }
    * );
+   *
+   * // runs a plugin with read access to its own directory only, without
+   * // prompting for anything else
+   * const plugin = new Worker(
+   *   new URL("plugins/plugin.ts", import.meta.url).href, {
+   *     type: "module",
+   *     deno: {
+   *       permissions: {
+   *         read: [new URL("plugins/", import.meta.url)],
+   *         prompt: false,
+   *       },
+   *     },
+   *   }
+   * );
    * ```
    */
   deno?: {
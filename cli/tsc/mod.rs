@@ -49,6 +49,8 @@ mod diagnostics;
 pub use self::diagnostics::Diagnostic;
 pub use self::diagnostics::DiagnosticCategory;
 pub use self::diagnostics::Diagnostics;
+pub use self::diagnostics::DiagnosticsSummary;
+pub use self::diagnostics::LimitedDiagnostics;
 pub use self::diagnostics::Position;
 
 pub static COMPILER_SNAPSHOT: Lazy<Box<[u8]>> = Lazy::new(
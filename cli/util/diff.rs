@@ -3,6 +3,7 @@
 use crate::colors;
 use dissimilar::diff as difference;
 use dissimilar::Chunk;
+use std::collections::HashMap;
 use std::fmt::Write as _;
 
 /// Print diff of the same file_path, before and after formatting.
@@ -24,6 +25,185 @@ pub fn diff(orig_text: &str, edit_text: &str) -> String {
   DiffBuilder::build(&orig_text, &edit_text)
 }
 
+/// Number of unchanged lines shown around each change of a unified diff.
+const UNIFIED_DIFF_CONTEXT: usize = 3;
+
+/// Print a unified diff (as produced by `diff -u`) of the same file_path,
+/// before and after formatting.
+pub fn unified_diff(
+  file_name: &str,
+  orig_text: &str,
+  edit_text: &str,
+) -> String {
+  if orig_text == edit_text {
+    return String::new();
+  }
+
+  let orig_normalized = orig_text.replace("\r\n", "\n");
+  let edit_normalized = edit_text.replace("\r\n", "\n");
+  if orig_normalized == edit_normalized {
+    return format!("{} | Text differed by line endings.\n", file_name);
+  }
+
+  let lines = diff_lines(&orig_normalized, &edit_normalized);
+  // line offsets into the original and edited text before each diff line
+  let mut orig_offsets = Vec::with_capacity(lines.len() + 1);
+  let mut edit_offsets = Vec::with_capacity(lines.len() + 1);
+  let (mut orig_offset, mut edit_offset) = (0, 0);
+  for line in &lines {
+    orig_offsets.push(orig_offset);
+    edit_offsets.push(edit_offset);
+    match line {
+      DiffLine::Equal(_) => {
+        orig_offset += 1;
+        edit_offset += 1;
+      }
+      DiffLine::Delete(_) => orig_offset += 1,
+      DiffLine::Insert(_) => edit_offset += 1,
+    }
+  }
+  orig_offsets.push(orig_offset);
+  edit_offsets.push(edit_offset);
+
+  // group changes that are close enough to share their context lines
+  let mut hunks: Vec<(usize, usize)> = Vec::new();
+  for (index, line) in lines.iter().enumerate() {
+    if matches!(line, DiffLine::Equal(_)) {
+      continue;
+    }
+    let start = index.saturating_sub(UNIFIED_DIFF_CONTEXT);
+    let end = std::cmp::min(index + 1 + UNIFIED_DIFF_CONTEXT, lines.len());
+    match hunks.last_mut() {
+      Some(hunk) if start <= hunk.1 => hunk.1 = end,
+      _ => hunks.push((start, end)),
+    }
+  }
+
+  let mut output = String::new();
+  writeln!(output, "{}", colors::bold(format!("--- {file_name}"))).unwrap();
+  writeln!(output, "{}", colors::bold(format!("+++ {file_name}"))).unwrap();
+  for (start, end) in hunks {
+    writeln!(
+      output,
+      "{}",
+      colors::cyan(format!(
+        "@@ -{} +{} @@",
+        hunk_range(orig_offsets[start], orig_offsets[end]),
+        hunk_range(edit_offsets[start], edit_offsets[end]),
+      ))
+    )
+    .unwrap();
+    for line in &lines[start..end] {
+      let (text, line_text) = match line {
+        DiffLine::Equal(text) => (*text, format!(" {}", trim_newline(text))),
+        DiffLine::Delete(text) => (
+          *text,
+          colors::red(format!("-{}", trim_newline(text))).to_string(),
+        ),
+        DiffLine::Insert(text) => (
+          *text,
+          colors::green(format!("+{}", trim_newline(text))).to_string(),
+        ),
+      };
+      output.push_str(&line_text);
+      output.push('\n');
+      if !text.ends_with('\n') {
+        output.push_str("\\ No newline at end of file\n");
+      }
+    }
+  }
+  output
+}
+
+enum DiffLine<'a> {
+  Equal(&'a str),
+  Delete(&'a str),
+  Insert(&'a str),
+}
+
+/// Diffs the texts line by line by mapping every distinct line to a single
+/// char and diffing the resulting strings. Texts with more distinct lines
+/// than chars are diffed by replacing every line.
+fn diff_lines<'a>(orig_text: &'a str, edit_text: &'a str) -> Vec<DiffLine<'a>> {
+  let mut line_ids: HashMap<&str, usize> = HashMap::new();
+  let mut lines: Vec<&str> = Vec::new();
+  let mut encode = |text: &'a str| {
+    text
+      .split_inclusive('\n')
+      .map(|line| {
+        let id = *line_ids.entry(line).or_insert_with(|| {
+          lines.push(line);
+          lines.len() - 1
+        });
+        id_to_char(id)
+      })
+      .collect::<Option<String>>()
+  };
+  let (Some(orig_chars), Some(edit_chars)) =
+    (encode(orig_text), encode(edit_text))
+  else {
+    // too many distinct lines to diff, so replace all of them
+    return orig_text
+      .split_inclusive('\n')
+      .map(DiffLine::Delete)
+      .chain(edit_text.split_inclusive('\n').map(DiffLine::Insert))
+      .collect();
+  };
+
+  let mut diff_lines = Vec::new();
+  for chunk in difference(&orig_chars, &edit_chars) {
+    let (chars, to_diff_line): (&str, fn(&'a str) -> DiffLine<'a>) = match chunk
+    {
+      Chunk::Equal(chars) => (chars, DiffLine::Equal),
+      Chunk::Delete(chars) => (chars, DiffLine::Delete),
+      Chunk::Insert(chars) => (chars, DiffLine::Insert),
+    };
+    diff_lines
+      .extend(chars.chars().map(|c| to_diff_line(lines[char_to_id(c)])));
+  }
+  diff_lines
+}
+
+const SURROGATES_START: u32 = 0xD800;
+const SURROGATES_LEN: u32 = 0x800;
+
+/// Gets the char of a line id, which is `None` when there are more distinct
+/// lines than chars.
+fn id_to_char(id: usize) -> Option<char> {
+  let code = u32::try_from(id).ok()?;
+  let code = if code >= SURROGATES_START {
+    code.checked_add(SURROGATES_LEN)?
+  } else {
+    code
+  };
+  char::from_u32(code)
+}
+
+fn char_to_id(c: char) -> usize {
+  let code = c as u32;
+  let code = if code >= SURROGATES_START + SURROGATES_LEN {
+    code - SURROGATES_LEN
+  } else {
+    code
+  };
+  code as usize
+}
+
+/// Formats the `start,count` range of a hunk header from 0-based offsets.
+fn hunk_range(start: usize, end: usize) -> String {
+  let count = end - start;
+  match count {
+    // an empty range refers to the line before it
+    0 => format!("{start},0"),
+    1 => format!("{}", start + 1),
+    _ => format!("{},{}", start + 1, count),
+  }
+}
+
+fn trim_newline(line: &str) -> &str {
+  line.strip_suffix('\n').unwrap_or(line)
+}
+
 struct DiffBuilder {
   output: String,
   line_number_width: usize,
@@ -219,6 +399,59 @@ mod tests {
     run_test("test\n", "test\r\n", " | Text differed by line endings.\n");
   }
 
+  #[test]
+  fn test_id_to_char() {
+    for id in [0, 0xD7FF, 0xD800, 0x10F7FF] {
+      assert_eq!(char_to_id(id_to_char(id).unwrap()), id);
+    }
+    assert_eq!(id_to_char(0x10F800), None);
+    assert_eq!(id_to_char(usize::MAX), None);
+  }
+
+  #[test]
+  fn test_diff_lines_too_many_lines() {
+    let orig = (0..0x10F801).map(|i| format!("{i}\n")).collect::<String>();
+    let lines = diff_lines(&orig, "a\n");
+    assert_eq!(lines.len(), 0x10F802);
+    assert!(matches!(lines[0], DiffLine::Delete("0\n")));
+    assert!(matches!(lines[0x10F801], DiffLine::Insert("a\n")));
+  }
+
+  #[test]
+  fn test_unified_diff() {
+    let orig = "a\nb\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\n";
+    let edit = "a\nB\nc\nd\ne\nf\ng\nh\ni\nj\nk\nl\nm";
+    assert_eq!(
+      test_util::strip_ansi_codes(&unified_diff("mod.ts", orig, edit)),
+      concat!(
+        "--- mod.ts\n",
+        "+++ mod.ts\n",
+        "@@ -1,5 +1,5 @@\n",
+        " a\n",
+        "-b\n",
+        "+B\n",
+        " c\n",
+        " d\n",
+        " e\n",
+        "@@ -10,3 +10,4 @@\n",
+        " j\n",
+        " k\n",
+        " l\n",
+        "+m\n",
+        "\\ No newline at end of file\n",
+      ),
+    );
+    assert_eq!(unified_diff("mod.ts", orig, orig), "");
+    assert_eq!(
+      unified_diff("mod.ts", "a\n", "a\r\n"),
+      "mod.ts | Text differed by line endings.\n"
+    );
+    assert_eq!(
+      test_util::strip_ansi_codes(&unified_diff("mod.ts", "", "a\n")),
+      "--- mod.ts\n+++ mod.ts\n@@ -0,0 +1 @@\n+a\n",
+    );
+  }
+
   fn run_test(diff_text1: &str, diff_text2: &str, expected_output: &str) {
     assert_eq!(
       test_util::strip_ansi_codes(&diff(diff_text1, diff_text2,)),
@@ -3,7 +3,11 @@
 use crate::args::Flags;
 use crate::colors;
 use crate::util::fs::canonicalize_path;
+use crate::util::fs::canonicalize_path_maybe_not_exists;
+use crate::util::fs::FileCollector;
 
+use deno_config::glob::FilePatterns;
+use deno_config::glob::PathOrPattern;
 use deno_config::glob::PathOrPatternSet;
 use deno_core::error::AnyError;
 use deno_core::error::JsError;
@@ -11,6 +15,7 @@ use deno_core::futures::Future;
 use deno_core::futures::FutureExt;
 use deno_core::parking_lot::Mutex;
 use deno_runtime::fmt_errors::format_js_error;
+use deno_runtime::ops::os::EnvVarOverrides;
 use log::info;
 use notify::event::Event as NotifyEvent;
 use notify::event::EventKind;
@@ -21,6 +26,7 @@ use notify::Watcher;
 use std::cell::RefCell;
 use std::collections::HashSet;
 use std::io::IsTerminal;
+use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
 use std::sync::Arc;
@@ -31,6 +37,8 @@ use tokio::sync::mpsc::UnboundedReceiver;
 use tokio::time::sleep;
 
 const CLEAR_SCREEN: &str = "\x1B[2J\x1B[1;1H";
+/// Environment variable with the paths whose change restarted the process.
+pub const CHANGED_PATHS_ENV_VAR_NAME: &str = "DENO_WATCH_CHANGED_PATHS";
 const DEBOUNCE_INTERVAL: Duration = Duration::from_millis(200);
 
 struct DebouncedReceiver {
@@ -156,14 +164,52 @@ pub struct WatcherCommunicator {
 
   restart_mode: Mutex<WatcherRestartMode>,
 
+  /// Paths whose changes don't restart the process.
+  ignored_paths: Mutex<HashSet<PathBuf>>,
+
+  /// When set, only the changes of paths that match it restart the process.
+  restart_filter: Mutex<Option<PathOrPatternSet>>,
+
+  /// Environment variables of the programs run by the watcher.
+  env_var_overrides: EnvVarOverrides,
+
   banner: String,
 }
 
 impl WatcherCommunicator {
+  pub fn env_var_overrides(&self) -> &EnvVarOverrides {
+    &self.env_var_overrides
+  }
+
   pub fn watch_paths(&self, paths: Vec<PathBuf>) -> Result<(), AnyError> {
     self.paths_to_watch_tx.send(paths).map_err(AnyError::from)
   }
 
+  /// Ignore the changes of `path` from now on, e.g. because the program
+  /// writes to it.
+  pub fn ignore_path(&self, path: &Path) {
+    let path = canonicalize_path_maybe_not_exists(path)
+      .unwrap_or_else(|_| path.to_path_buf());
+    self.ignored_paths.lock().insert(path);
+  }
+
+  /// Only restart for the changes of paths that match `set` from now on,
+  /// e.g. to watch the files matching a glob in a watched directory,
+  /// including the ones created later.
+  pub fn set_restart_filter(&self, set: PathOrPatternSet) {
+    *self.restart_filter.lock() = Some(set);
+  }
+
+  fn remove_ignored_paths(&self, paths: &mut Vec<PathBuf>) {
+    let ignored_paths = self.ignored_paths.lock();
+    if !ignored_paths.is_empty() {
+      paths.retain(|path| !ignored_paths.contains(path));
+    }
+    if let Some(restart_filter) = &*self.restart_filter.lock() {
+      paths.retain(|path| restart_filter.matches_path(path));
+    }
+  }
+
   pub fn force_restart(&self) -> Result<(), AnyError> {
     // Change back to automatic mode, so that HMR can set up watching
     // from scratch.
@@ -265,6 +311,9 @@ where
     changed_paths_rx: changed_paths_rx.resubscribe(),
     restart_tx: restart_tx.clone(),
     restart_mode: Mutex::new(restart_mode),
+    ignored_paths: Default::default(),
+    restart_filter: Default::default(),
+    env_var_overrides: Default::default(),
     banner: colors::intense_blue(banner).to_string(),
   });
   info!("{} {} started.", colors::intense_blue(banner), job_name);
@@ -275,8 +324,23 @@ where
 
   deno_core::unsync::spawn(async move {
     loop {
-      let received_changed_paths = watcher_receiver.recv().await;
+      let mut received_changed_paths = watcher_receiver.recv().await;
+      if let Some(paths) = &mut received_changed_paths {
+        watcher_.remove_ignored_paths(paths);
+        if paths.is_empty() {
+          continue;
+        }
+      }
       *changed_paths_.borrow_mut() = received_changed_paths.clone();
+      // set before restarting, so that the program can read it in its
+      // unload event listeners as well as after restarting
+      watcher_.env_var_overrides.set(
+        CHANGED_PATHS_ENV_VAR_NAME,
+        received_changed_paths
+          .as_deref()
+          .and_then(|paths| std::env::join_paths(paths).ok())
+          .map(|value| value.to_string_lossy().into_owned()),
+      );
 
       match *watcher_.restart_mode.lock() {
         WatcherRestartMode::Automatic => {
@@ -357,6 +421,47 @@ where
   }
 }
 
+/// Resolves paths and glob patterns to watch relative to `cwd`. Paths are
+/// watched as is, so files created in a watched directory are picked up,
+/// while glob patterns are expanded to the files that match them.
+pub fn resolve_watch_paths(
+  cwd: &Path,
+  path_or_patterns: &[String],
+) -> Result<Vec<PathBuf>, AnyError> {
+  let include = PathOrPatternSet::from_include_relative_path_or_patterns(
+    cwd,
+    path_or_patterns,
+  )?;
+  let mut paths = Vec::new();
+  let mut patterns = Vec::new();
+  for path_or_pattern in include.into_path_or_patterns() {
+    match path_or_pattern {
+      PathOrPattern::Path(path) => paths.push(path),
+      PathOrPattern::Pattern(_) | PathOrPattern::NegatedPath(_) => {
+        patterns.push(path_or_pattern)
+      }
+      // nothing to watch
+      PathOrPattern::RemoteUrl(_) => {}
+    }
+  }
+  if patterns
+    .iter()
+    .any(|p| matches!(p, PathOrPattern::Pattern(_)))
+  {
+    paths.extend(
+      FileCollector::new(|_| true)
+        .ignore_git_folder()
+        .ignore_node_modules()
+        .collect_file_patterns(FilePatterns {
+          base: cwd.to_path_buf(),
+          include: Some(PathOrPatternSet::new(patterns)),
+          exclude: Default::default(),
+        })?,
+    );
+  }
+  Ok(paths)
+}
+
 fn new_watcher(
   sender: Arc<mpsc::UnboundedSender<Vec<PathBuf>>>,
 ) -> Result<RecommendedWatcher, AnyError> {
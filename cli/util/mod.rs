@@ -17,4 +17,5 @@ pub mod text_encoding;
 pub mod time;
 pub mod unix;
 pub mod v8;
+pub mod watched_fs;
 pub mod windows;
@@ -21,6 +21,15 @@ pub fn source_map_from_code(code: &ModuleCodeString) -> Option<Vec<u8>> {
   }
 }
 
+/// Appends the source map to the code as a data URL.
+pub fn append_inline_source_map(code: &mut String, source_map: &[u8]) {
+  if !code.is_empty() && !code.ends_with('\n') {
+    code.push('\n');
+  }
+  code.push_str(std::str::from_utf8(SOURCE_MAP_PREFIX).unwrap());
+  code.push_str(&BASE64_STANDARD.encode(source_map));
+}
+
 /// Truncate the source code before the source map.
 pub fn code_without_source_map(mut code: ModuleCodeString) -> ModuleCodeString {
   let bytes = code.as_bytes();
@@ -72,4 +81,20 @@ mod tests {
       );
     }
   }
+
+  #[test]
+  fn test_append_inline_source_map() {
+    let mut code = "console.log(1);".to_string();
+    append_inline_source_map(&mut code, b"{\"version\":3}");
+    assert_eq!(
+      code,
+      "console.log(1);\n//# sourceMappingURL=data:application/json;base64,eyJ2ZXJzaW9uIjozfQ=="
+    );
+    let code = ModuleCodeString::from(code);
+    assert_eq!(
+      source_map_from_code(&code).unwrap(),
+      b"{\"version\":3}".to_vec()
+    );
+    assert_eq!(code_without_source_map(code).as_str(), "console.log(1);\n");
+  }
 }
@@ -0,0 +1,436 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::collections::HashSet;
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+
+use deno_core::parking_lot::Mutex;
+use deno_runtime::deno_fs::AccessCheckCb;
+use deno_runtime::deno_fs::FileSystem;
+use deno_runtime::deno_fs::FsDirEntry;
+use deno_runtime::deno_fs::FsFileType;
+use deno_runtime::deno_fs::OpenOptions;
+use deno_runtime::deno_io::fs::File;
+use deno_runtime::deno_io::fs::FsResult;
+use deno_runtime::deno_io::fs::FsStat;
+
+use super::file_watcher::WatcherCommunicator;
+
+/// A file system that reports the files the program opens for reading to
+/// the file watcher, so that changing them restarts the process. Files the
+/// program writes to are neither watched nor restart it, as a program that
+/// reads and then writes a file would otherwise restart itself forever.
+#[derive(Debug)]
+pub struct WatchedFileSystem {
+  fs: Arc<dyn FileSystem>,
+  watcher_communicator: Arc<WatcherCommunicator>,
+  watched_paths: Mutex<HashSet<PathBuf>>,
+  written_paths: Mutex<HashSet<PathBuf>>,
+}
+
+impl WatchedFileSystem {
+  pub fn new(
+    fs: Arc<dyn FileSystem>,
+    watcher_communicator: Arc<WatcherCommunicator>,
+  ) -> Self {
+    Self {
+      fs,
+      watcher_communicator,
+      watched_paths: Default::default(),
+      written_paths: Default::default(),
+    }
+  }
+
+  fn watch_path(&self, path: &Path) {
+    if !should_watch(path) || self.written_paths.lock().contains(path) {
+      return;
+    }
+    if self.watched_paths.lock().insert(path.to_path_buf()) {
+      let _ = self
+        .watcher_communicator
+        .watch_paths(vec![path.to_path_buf()]);
+    }
+  }
+
+  fn mark_written(&self, path: &Path) {
+    if self.written_paths.lock().insert(path.to_path_buf()) {
+      self.watcher_communicator.ignore_path(path);
+    }
+  }
+}
+
+fn is_read_only(options: &OpenOptions) -> bool {
+  options.read
+    && !options.write
+    && !options.append
+    && !options.create
+    && !options.truncate
+    && !options.create_new
+}
+
+/// Dependencies in `node_modules` are not expected to change while
+/// developing, so they are not watched.
+fn should_watch(path: &Path) -> bool {
+  !path
+    .components()
+    .any(|c| c == Component::Normal("node_modules".as_ref()))
+}
+
+#[async_trait::async_trait(?Send)]
+impl FileSystem for WatchedFileSystem {
+  fn cwd(&self) -> FsResult<PathBuf> {
+    self.fs.cwd()
+  }
+
+  fn tmp_dir(&self) -> FsResult<PathBuf> {
+    self.fs.tmp_dir()
+  }
+
+  fn chdir(&self, path: &Path) -> FsResult<()> {
+    self.fs.chdir(path)
+  }
+
+  fn umask(&self, mask: Option<u32>) -> FsResult<u32> {
+    self.fs.umask(mask)
+  }
+
+  fn open_sync(
+    &self,
+    path: &Path,
+    options: OpenOptions,
+    access_check: Option<AccessCheckCb>,
+  ) -> FsResult<Rc<dyn File>> {
+    let read_only = is_read_only(&options);
+    if !read_only {
+      self.mark_written(path);
+    }
+    let file = self.fs.open_sync(path, options, access_check)?;
+    if read_only {
+      self.watch_path(path);
+    }
+    Ok(file)
+  }
+
+  async fn open_async<'a>(
+    &'a self,
+    path: PathBuf,
+    options: OpenOptions,
+    access_check: Option<AccessCheckCb<'a>>,
+  ) -> FsResult<Rc<dyn File>> {
+    let read_only = is_read_only(&options);
+    if !read_only {
+      self.mark_written(&path);
+    }
+    let file = self
+      .fs
+      .open_async(path.clone(), options, access_check)
+      .await?;
+    if read_only {
+      self.watch_path(&path);
+    }
+    Ok(file)
+  }
+
+  fn mkdir_sync(
+    &self,
+    path: &Path,
+    recursive: bool,
+    mode: u32,
+  ) -> FsResult<()> {
+    self.mark_written(path);
+    self.fs.mkdir_sync(path, recursive, mode)
+  }
+
+  async fn mkdir_async(
+    &self,
+    path: PathBuf,
+    recursive: bool,
+    mode: u32,
+  ) -> FsResult<()> {
+    self.mark_written(&path);
+    self.fs.mkdir_async(path, recursive, mode).await
+  }
+
+  fn chmod_sync(&self, path: &Path, mode: u32) -> FsResult<()> {
+    self.fs.chmod_sync(path, mode)
+  }
+
+  async fn chmod_async(&self, path: PathBuf, mode: u32) -> FsResult<()> {
+    self.fs.chmod_async(path, mode).await
+  }
+
+  fn chown_sync(
+    &self,
+    path: &Path,
+    uid: Option<u32>,
+    gid: Option<u32>,
+  ) -> FsResult<()> {
+    self.fs.chown_sync(path, uid, gid)
+  }
+
+  async fn chown_async(
+    &self,
+    path: PathBuf,
+    uid: Option<u32>,
+    gid: Option<u32>,
+  ) -> FsResult<()> {
+    self.fs.chown_async(path, uid, gid).await
+  }
+
+  fn remove_sync(&self, path: &Path, recursive: bool) -> FsResult<()> {
+    self.mark_written(path);
+    self.fs.remove_sync(path, recursive)
+  }
+
+  async fn remove_async(&self, path: PathBuf, recursive: bool) -> FsResult<()> {
+    self.mark_written(&path);
+    self.fs.remove_async(path, recursive).await
+  }
+
+  fn copy_file_sync(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
+    self.mark_written(newpath);
+    self.fs.copy_file_sync(oldpath, newpath)
+  }
+
+  async fn copy_file_async(
+    &self,
+    oldpath: PathBuf,
+    newpath: PathBuf,
+  ) -> FsResult<()> {
+    self.mark_written(&newpath);
+    self.fs.copy_file_async(oldpath, newpath).await
+  }
+
+  fn cp_sync(&self, from: &Path, to: &Path) -> FsResult<()> {
+    self.mark_written(to);
+    self.fs.cp_sync(from, to)
+  }
+
+  async fn cp_async(&self, from: PathBuf, to: PathBuf) -> FsResult<()> {
+    self.mark_written(&to);
+    self.fs.cp_async(from, to).await
+  }
+
+  fn stat_sync(&self, path: &Path) -> FsResult<FsStat> {
+    self.fs.stat_sync(path)
+  }
+
+  async fn stat_async(&self, path: PathBuf) -> FsResult<FsStat> {
+    self.fs.stat_async(path).await
+  }
+
+  fn lstat_sync(&self, path: &Path) -> FsResult<FsStat> {
+    self.fs.lstat_sync(path)
+  }
+
+  async fn lstat_async(&self, path: PathBuf) -> FsResult<FsStat> {
+    self.fs.lstat_async(path).await
+  }
+
+  fn realpath_sync(&self, path: &Path) -> FsResult<PathBuf> {
+    self.fs.realpath_sync(path)
+  }
+
+  async fn realpath_async(&self, path: PathBuf) -> FsResult<PathBuf> {
+    self.fs.realpath_async(path).await
+  }
+
+  fn read_dir_sync(&self, path: &Path) -> FsResult<Vec<FsDirEntry>> {
+    self.fs.read_dir_sync(path)
+  }
+
+  async fn read_dir_async(&self, path: PathBuf) -> FsResult<Vec<FsDirEntry>> {
+    self.fs.read_dir_async(path).await
+  }
+
+  fn rename_sync(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
+    self.mark_written(oldpath);
+    self.mark_written(newpath);
+    self.fs.rename_sync(oldpath, newpath)
+  }
+
+  async fn rename_async(
+    &self,
+    oldpath: PathBuf,
+    newpath: PathBuf,
+  ) -> FsResult<()> {
+    self.mark_written(&oldpath);
+    self.mark_written(&newpath);
+    self.fs.rename_async(oldpath, newpath).await
+  }
+
+  fn link_sync(&self, oldpath: &Path, newpath: &Path) -> FsResult<()> {
+    self.mark_written(newpath);
+    self.fs.link_sync(oldpath, newpath)
+  }
+
+  async fn link_async(
+    &self,
+    oldpath: PathBuf,
+    newpath: PathBuf,
+  ) -> FsResult<()> {
+    self.mark_written(&newpath);
+    self.fs.link_async(oldpath, newpath).await
+  }
+
+  fn symlink_sync(
+    &self,
+    oldpath: &Path,
+    newpath: &Path,
+    file_type: Option<FsFileType>,
+  ) -> FsResult<()> {
+    self.mark_written(newpath);
+    self.fs.symlink_sync(oldpath, newpath, file_type)
+  }
+
+  async fn symlink_async(
+    &self,
+    oldpath: PathBuf,
+    newpath: PathBuf,
+    file_type: Option<FsFileType>,
+  ) -> FsResult<()> {
+    self.mark_written(&newpath);
+    self.fs.symlink_async(oldpath, newpath, file_type).await
+  }
+
+  fn read_link_sync(&self, path: &Path) -> FsResult<PathBuf> {
+    self.fs.read_link_sync(path)
+  }
+
+  async fn read_link_async(&self, path: PathBuf) -> FsResult<PathBuf> {
+    self.fs.read_link_async(path).await
+  }
+
+  fn truncate_sync(&self, path: &Path, len: u64) -> FsResult<()> {
+    self.mark_written(path);
+    self.fs.truncate_sync(path, len)
+  }
+
+  async fn truncate_async(&self, path: PathBuf, len: u64) -> FsResult<()> {
+    self.mark_written(&path);
+    self.fs.truncate_async(path, len).await
+  }
+
+  fn utime_sync(
+    &self,
+    path: &Path,
+    atime_secs: i64,
+    atime_nanos: u32,
+    mtime_secs: i64,
+    mtime_nanos: u32,
+  ) -> FsResult<()> {
+    self
+      .fs
+      .utime_sync(path, atime_secs, atime_nanos, mtime_secs, mtime_nanos)
+  }
+
+  async fn utime_async(
+    &self,
+    path: PathBuf,
+    atime_secs: i64,
+    atime_nanos: u32,
+    mtime_secs: i64,
+    mtime_nanos: u32,
+  ) -> FsResult<()> {
+    self
+      .fs
+      .utime_async(path, atime_secs, atime_nanos, mtime_secs, mtime_nanos)
+      .await
+  }
+
+  fn get_xattr_sync(
+    &self,
+    path: &Path,
+    name: &str,
+  ) -> FsResult<Option<Vec<u8>>> {
+    self.fs.get_xattr_sync(path, name)
+  }
+
+  async fn get_xattr_async(
+    &self,
+    path: PathBuf,
+    name: String,
+  ) -> FsResult<Option<Vec<u8>>> {
+    self.fs.get_xattr_async(path, name).await
+  }
+
+  fn set_xattr_sync(
+    &self,
+    path: &Path,
+    name: &str,
+    value: &[u8],
+  ) -> FsResult<()> {
+    self.fs.set_xattr_sync(path, name, value)
+  }
+
+  async fn set_xattr_async(
+    &self,
+    path: PathBuf,
+    name: String,
+    value: Vec<u8>,
+  ) -> FsResult<()> {
+    self.fs.set_xattr_async(path, name, value).await
+  }
+
+  fn remove_xattr_sync(&self, path: &Path, name: &str) -> FsResult<()> {
+    self.fs.remove_xattr_sync(path, name)
+  }
+
+  async fn remove_xattr_async(
+    &self,
+    path: PathBuf,
+    name: String,
+  ) -> FsResult<()> {
+    self.fs.remove_xattr_async(path, name).await
+  }
+
+  fn list_xattrs_sync(&self, path: &Path) -> FsResult<Vec<String>> {
+    self.fs.list_xattrs_sync(path)
+  }
+
+  async fn list_xattrs_async(&self, path: PathBuf) -> FsResult<Vec<String>> {
+    self.fs.list_xattrs_async(path).await
+  }
+
+  fn set_immutable_sync(&self, path: &Path, immutable: bool) -> FsResult<()> {
+    self.fs.set_immutable_sync(path, immutable)
+  }
+
+  async fn set_immutable_async(
+    &self,
+    path: PathBuf,
+    immutable: bool,
+  ) -> FsResult<()> {
+    self.fs.set_immutable_async(path, immutable).await
+  }
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  #[test]
+  fn should_watch_skips_node_modules() {
+    assert!(should_watch(Path::new("/project/data.json")));
+    assert!(should_watch(Path::new(
+      "/project/node_modules_backup/a.txt"
+    )));
+    assert!(!should_watch(Path::new(
+      "/project/node_modules/chalk/package.json"
+    )));
+  }
+
+  #[test]
+  fn is_read_only_options() {
+    assert!(is_read_only(&OpenOptions::read()));
+    assert!(!is_read_only(&OpenOptions {
+      read: true,
+      write: true,
+      ..OpenOptions::read()
+    }));
+    assert!(!is_read_only(&OpenOptions::write(true, false, false, None)));
+  }
+}
@@ -25,6 +25,7 @@ use deno_core::SourceMapGetter;
 use deno_lockfile::Lockfile;
 use deno_runtime::code_cache;
 use deno_runtime::deno_broadcast_channel::InMemoryBroadcastChannel;
+use deno_runtime::deno_fetch;
 use deno_runtime::deno_fs;
 use deno_runtime::deno_node;
 use deno_runtime::deno_node::NodeResolution;
@@ -32,8 +33,10 @@ use deno_runtime::deno_node::NodeResolutionMode;
 use deno_runtime::deno_node::NodeResolver;
 use deno_runtime::deno_tls::RootCertStoreProvider;
 use deno_runtime::deno_web::BlobStore;
+use deno_runtime::deno_webstorage::LocalStorageOptions;
 use deno_runtime::fmt_errors::format_js_error;
 use deno_runtime::inspector_server::InspectorServer;
+use deno_runtime::ops::os::EnvVarOverrides;
 use deno_runtime::ops::worker_host::CreateWebWorkerCb;
 use deno_runtime::permissions::PermissionsContainer;
 use deno_runtime::web_worker::WebWorker;
@@ -50,6 +53,7 @@ use tokio::select;
 
 use crate::args::package_json::PackageJsonDeps;
 use crate::args::DenoSubcommand;
+use crate::args::PermissionPolicy;
 use crate::args::StorageKeyResolver;
 use crate::errors;
 use crate::npm::CliNpmResolver;
@@ -114,13 +118,19 @@ pub struct CliMainWorkerOptions {
   pub argv0: Option<String>,
   pub node_debug: Option<String>,
   pub origin_data_folder_path: Option<PathBuf>,
+  pub broadcast_channel_folder_path: Option<PathBuf>,
+  pub local_storage_quota: Option<usize>,
   pub seed: Option<u64>,
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
+  pub http1_only: bool,
   pub unstable: bool,
   pub skip_op_registration: bool,
   pub maybe_root_package_json_deps: Option<PackageJsonDeps>,
   pub create_hmr_runner: Option<CreateHmrRunnerCb>,
   pub create_coverage_collector: Option<CreateCoverageCollectorCb>,
+  pub fs_fetch_read_file: Option<deno_fetch::FsFetchReadFileFn>,
+  pub env_var_overrides: Option<EnvVarOverrides>,
+  pub permission_policy: Option<PermissionPolicy>,
 }
 
 struct SharedWorkerState {
@@ -548,6 +558,10 @@ impl CliMainWorkerFactory {
     } else {
       (main_module, false)
     };
+    let permissions = match &shared.options.permission_policy {
+      Some(policy) => policy.restrict(&main_module, permissions),
+      None => permissions,
+    };
 
     let module_loader = shared
       .module_loader_factory
@@ -570,13 +584,20 @@ impl CliMainWorkerFactory {
         .unwrap() // must be set if storage key resolver returns a value
         .join(checksum::gen(&[key.as_bytes()]))
     });
-    let cache_storage_dir = maybe_storage_key.map(|key| {
-      // TODO(@satyarohith): storage quota management
-      // Note: we currently use temp_dir() to avoid managing storage size.
-      std::env::temp_dir()
-        .join("deno_cache")
-        .join(checksum::gen(&[key.as_bytes()]))
-    });
+    // TODO(@satyarohith): storage quota management
+    let cache_storage_dir = origin_storage_dir
+      .as_ref()
+      .map(|dir| dir.join("cache_storage"));
+    if let (Some(key), Some(folder)) = (
+      &maybe_storage_key,
+      &shared.options.broadcast_channel_folder_path,
+    ) {
+      // processes sharing the storage origin also share broadcast channels
+      let hash = checksum::gen(&[key.as_bytes()]);
+      shared
+        .broadcast_channel
+        .relay_to_processes(folder.join(format!("{}.sock", &hash[..16])));
+    }
 
     // TODO(bartlomieju): this is cruft, update FeatureChecker to spit out
     // list of enabled features.
@@ -624,6 +645,7 @@ impl CliMainWorkerFactory {
         .options
         .unsafely_ignore_certificate_errors
         .clone(),
+      http1_only: shared.options.http1_only,
       root_cert_store_provider: Some(shared.root_cert_store_provider.clone()),
       seed: shared.options.seed,
       source_map_getter: maybe_source_map_getter,
@@ -635,10 +657,15 @@ impl CliMainWorkerFactory {
       strace_ops: shared.options.strace_ops.clone(),
       module_loader,
       fs: shared.fs.clone(),
+      fs_fetch_read_file: shared.options.fs_fetch_read_file.clone(),
       npm_resolver: Some(shared.npm_resolver.clone().into_npm_resolver()),
       get_error_class_fn: Some(&errors::get_error_class_name),
       cache_storage_dir,
       origin_storage_dir,
+      local_storage: LocalStorageOptions {
+        origin: maybe_storage_key,
+        quota: shared.options.local_storage_quota,
+      },
       blob_store: shared.blob_store.clone(),
       broadcast_channel: shared.broadcast_channel.clone(),
       shared_array_buffer_store: Some(shared.shared_array_buffer_store.clone()),
@@ -656,6 +683,13 @@ impl CliMainWorkerFactory {
       permissions,
       options,
     );
+    if let Some(env_var_overrides) = &shared.options.env_var_overrides {
+      worker
+        .js_runtime
+        .op_state()
+        .borrow_mut()
+        .put(env_var_overrides.clone());
+    }
 
     if self.shared.subcommand.needs_test() {
       macro_rules! test_file {
@@ -765,7 +799,10 @@ fn create_web_worker_callback(
   shared: Arc<SharedWorkerState>,
   stdio: deno_runtime::deno_io::Stdio,
 ) -> Arc<CreateWebWorkerCb> {
-  Arc::new(move |args| {
+  Arc::new(move |mut args| {
+    if let Some(policy) = &shared.options.permission_policy {
+      args.permissions = policy.restrict(&args.main_module, args.permissions);
+    }
     let maybe_inspector_server = shared.maybe_inspector_server.clone();
 
     let module_loader = shared.module_loader_factory.create_for_worker(
@@ -780,12 +817,15 @@ fn create_web_worker_callback(
     let maybe_storage_key = shared
       .storage_key_resolver
       .resolve_storage_key(&args.main_module);
+    // TODO(@satyarohith): storage quota management
     let cache_storage_dir = maybe_storage_key.map(|key| {
-      // TODO(@satyarohith): storage quota management
-      // Note: we currently use temp_dir() to avoid managing storage size.
-      std::env::temp_dir()
-        .join("deno_cache")
+      shared
+        .options
+        .origin_data_folder_path
+        .as_ref()
+        .unwrap() // must be set if storage key resolver returns a value
         .join(checksum::gen(&[key.as_bytes()]))
+        .join("cache_storage")
     });
 
     // TODO(bartlomieju): this is cruft, update FeatureChecker to spit out
@@ -833,6 +873,7 @@ fn create_web_worker_callback(
         .options
         .unsafely_ignore_certificate_errors
         .clone(),
+      http1_only: shared.options.http1_only,
       root_cert_store_provider: Some(shared.root_cert_store_provider.clone()),
       seed: shared.options.seed,
       create_web_worker_cb,
@@ -840,6 +881,7 @@ fn create_web_worker_callback(
       source_map_getter: maybe_source_map_getter,
       module_loader,
       fs: shared.fs.clone(),
+      fs_fetch_read_file: shared.options.fs_fetch_read_file.clone(),
       npm_resolver: Some(shared.npm_resolver.clone().into_npm_resolver()),
       worker_type: args.worker_type,
       maybe_inspector_server,
@@ -858,13 +900,21 @@ fn create_web_worker_callback(
       maybe_worker_metadata: args.maybe_worker_metadata,
     };
 
-    WebWorker::bootstrap_from_options(
+    let (mut worker, handle) = WebWorker::bootstrap_from_options(
       args.name,
       args.permissions,
       args.main_module,
       args.worker_id,
       options,
-    )
+    );
+    if let Some(env_var_overrides) = &shared.options.env_var_overrides {
+      worker
+        .js_runtime
+        .op_state()
+        .borrow_mut()
+        .put(env_var_overrides.clone());
+    }
+    (worker, handle)
   })
 }
 
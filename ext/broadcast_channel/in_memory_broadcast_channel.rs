@@ -1,18 +1,30 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use deno_core::error::range_error;
 use deno_core::error::AnyError;
 use deno_core::parking_lot::Mutex;
 use tokio::sync::broadcast;
 use tokio::sync::mpsc;
 use uuid::Uuid;
 
+use crate::relay::MAX_CHUNK_SIZE;
 use crate::BroadcastChannel;
 
 #[derive(Clone)]
-pub struct InMemoryBroadcastChannel(Arc<Mutex<broadcast::Sender<Message>>>);
+pub struct InMemoryBroadcastChannel {
+  tx: Arc<Mutex<broadcast::Sender<Message>>>,
+  relay: Arc<Mutex<Relay>>,
+}
+
+enum Relay {
+  Disabled,
+  Pending(PathBuf),
+  Started,
+}
 
 pub struct InMemoryBroadcastChannelResource {
   rx: tokio::sync::Mutex<(
@@ -24,16 +36,57 @@ pub struct InMemoryBroadcastChannelResource {
 }
 
 #[derive(Clone, Debug)]
-struct Message {
-  name: Arc<String>,
-  data: Arc<Vec<u8>>,
+pub(crate) struct Message {
+  pub name: Arc<String>,
+  pub data: Arc<Vec<u8>>,
   uuid: Uuid,
 }
 
+impl Message {
+  #[cfg(test)]
+  pub fn new(name: &str, data: &[u8]) -> Self {
+    Self {
+      name: Arc::new(name.to_string()),
+      data: Arc::new(data.to_vec()),
+      uuid: Uuid::new_v4(),
+    }
+  }
+
+  /// A message received from another process. It isn't sent by any
+  /// resource of this process, so all of them receive it.
+  pub fn relayed(name: String, data: Vec<u8>) -> Self {
+    Self {
+      name: Arc::new(name),
+      data: Arc::new(data),
+      uuid: Uuid::nil(),
+    }
+  }
+
+  pub fn is_relayed(&self) -> bool {
+    self.uuid.is_nil()
+  }
+}
+
 impl Default for InMemoryBroadcastChannel {
   fn default() -> Self {
     let (tx, _) = broadcast::channel(256);
-    Self(Arc::new(Mutex::new(tx)))
+    Self {
+      tx: Arc::new(Mutex::new(tx)),
+      relay: Arc::new(Mutex::new(Relay::Disabled)),
+    }
+  }
+}
+
+impl InMemoryBroadcastChannel {
+  /// Also exchanges messages with the other processes relaying to the same
+  /// `address`, which is the path of a unix socket or names a pipe on
+  /// Windows. The relay starts when a channel is first subscribed to, and
+  /// only the first address is used.
+  pub fn relay_to_processes(&self, address: PathBuf) {
+    let mut relay = self.relay.lock();
+    if matches!(*relay, Relay::Disabled) {
+      *relay = Relay::Pending(address);
+    }
   }
 }
 
@@ -42,8 +95,15 @@ impl BroadcastChannel for InMemoryBroadcastChannel {
   type Resource = InMemoryBroadcastChannelResource;
 
   fn subscribe(&self) -> Result<Self::Resource, AnyError> {
+    {
+      let mut relay = self.relay.lock();
+      if let Relay::Pending(address) = &*relay {
+        crate::relay::spawn(address.clone(), self.tx.lock().clone());
+        *relay = Relay::Started;
+      }
+    }
     let (cancel_tx, cancel_rx) = mpsc::unbounded_channel();
-    let broadcast_rx = self.0.lock().subscribe();
+    let broadcast_rx = self.tx.lock().subscribe();
     let rx = tokio::sync::Mutex::new((broadcast_rx, cancel_rx));
     let uuid = Uuid::new_v4();
     Ok(Self::Resource {
@@ -63,10 +123,17 @@ impl BroadcastChannel for InMemoryBroadcastChannel {
     name: String,
     data: Vec<u8>,
   ) -> Result<(), AnyError> {
+    if matches!(*self.relay.lock(), Relay::Started)
+      && (name.len() > MAX_CHUNK_SIZE || data.len() > MAX_CHUNK_SIZE)
+    {
+      return Err(range_error(
+        "BroadcastChannel message is too large to be relayed to other processes",
+      ));
+    }
     let name = Arc::new(name);
     let data = Arc::new(data);
     let uuid = resource.uuid;
-    self.0.lock().send(Message { name, data, uuid })?;
+    self.tx.lock().send(Message { name, data, uuid })?;
     Ok(())
   }
 
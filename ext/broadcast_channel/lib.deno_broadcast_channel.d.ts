@@ -58,6 +58,13 @@ declare interface BroadcastChannel extends EventTarget {
 }
 
 /**
+ * Messages are delivered to the channels of the same name in all the workers
+ * of the process, and in the other Deno processes on the machine sharing its
+ * storage origin: the origin of `--location` when it is set, otherwise the
+ * configuration file or the main module. Programs compiled with
+ * `deno compile` have no storage origin, so their messages only reach the
+ * workers of the same process.
+ *
  * @category Messaging
  * @tags unstable
  */
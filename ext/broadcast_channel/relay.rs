@@ -0,0 +1,627 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Relays the messages of an [crate::InMemoryBroadcastChannel] between the
+//! Deno processes using the same relay address.
+//!
+//! The first process to take the lock next to the address becomes the
+//! broker: it listens on a unix socket (a named pipe on Windows) and forwards
+//! the messages of every process to all the others. The other processes
+//! connect to it. When the broker exits, one of them takes over.
+
+use std::collections::HashMap;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Once;
+use std::time::Duration;
+use std::time::Instant;
+
+use deno_core::parking_lot::Mutex;
+use tokio::io::AsyncRead;
+use tokio::io::AsyncReadExt;
+use tokio::io::AsyncWrite;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::mpsc;
+
+use crate::in_memory_broadcast_channel::Message;
+
+/// Bounds the memory a misbehaving peer can make a process allocate. Larger
+/// messages are rejected when they are sent.
+pub(crate) const MAX_CHUNK_SIZE: usize = 128 * 1024 * 1024;
+
+const MIN_RETRY_DELAY: Duration = Duration::from_millis(50);
+const MAX_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+type Peers = Arc<Mutex<HashMap<u64, mpsc::UnboundedSender<Arc<Vec<u8>>>>>>;
+
+/// Starts relaying the messages sent through `tx` on a dedicated thread, so
+/// the relay outlives the worker which first subscribed to a channel.
+pub(crate) fn spawn(address: PathBuf, tx: broadcast::Sender<Message>) {
+  // Subscribe right away so messages sent before the relay is connected
+  // are delivered once it is.
+  let rx = tx.subscribe();
+  let _ = std::thread::Builder::new()
+    .name("broadcast-channel-relay".to_string())
+    .spawn(move || {
+      let Ok(rt) = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+      else {
+        return;
+      };
+      rt.block_on(run(address, tx, rx));
+    });
+}
+
+async fn run(
+  address: PathBuf,
+  tx: broadcast::Sender<Message>,
+  mut rx: broadcast::Receiver<Message>,
+) {
+  let mut delay = MIN_RETRY_DELAY;
+  let mut warned = false;
+  loop {
+    let started = Instant::now();
+    if let Err(err) = run_once(&address, &tx, &mut rx).await {
+      // Keep retrying, as the address may become usable, e.g. once the
+      // broker's lock is released.
+      if !warned {
+        warned = true;
+        log::warn!(
+          "BroadcastChannel messages can't be relayed to other processes through {}: {err}",
+          address.display(),
+        );
+      }
+    }
+    // A connection which lasted is retried right away, while repeated
+    // failures, e.g. an address which can't be bound, back off.
+    delay = if started.elapsed() > MAX_RETRY_DELAY {
+      MIN_RETRY_DELAY
+    } else {
+      (delay * 2).min(MAX_RETRY_DELAY)
+    };
+    tokio::time::sleep(delay).await;
+  }
+}
+
+/// Fails when the relay can't start. A connection which ends, e.g. because
+/// the broker exited, isn't a failure.
+async fn run_once(
+  address: &Path,
+  tx: &broadcast::Sender<Message>,
+  rx: &mut broadcast::Receiver<Message>,
+) -> io::Result<()> {
+  if let Ok(stream) = transport::connect(address).await {
+    let _ = run_client(stream, tx, rx).await;
+    return Ok(());
+  }
+  match transport::Listener::bind(address)? {
+    Some(listener) => run_broker(listener, tx, rx).await,
+    // Another process is the broker, but isn't listening yet.
+    None => Ok(()),
+  }
+}
+
+async fn run_client<S>(
+  stream: S,
+  tx: &broadcast::Sender<Message>,
+  rx: &mut broadcast::Receiver<Message>,
+) -> io::Result<()>
+where
+  S: AsyncRead + AsyncWrite,
+{
+  let (mut reader, mut writer) = tokio::io::split(stream);
+  tokio::select! {
+    r = receive_frames(&mut reader, tx, None) => r,
+    r = send_local_frames(&mut writer, rx) => r,
+  }
+}
+
+async fn run_broker(
+  mut listener: transport::Listener,
+  tx: &broadcast::Sender<Message>,
+  rx: &mut broadcast::Receiver<Message>,
+) -> io::Result<()> {
+  let peers = Peers::default();
+  tokio::select! {
+    r = accept_peers(&mut listener, &peers, tx) => r,
+    r = send_local_frames_to_peers(rx, &peers) => r,
+  }
+}
+
+async fn accept_peers(
+  listener: &mut transport::Listener,
+  peers: &Peers,
+  tx: &broadcast::Sender<Message>,
+) -> io::Result<()> {
+  let mut next_id = 0;
+  loop {
+    let stream = listener.accept().await?;
+    next_id += 1;
+    tokio::spawn(serve_peer(next_id, stream, peers.clone(), tx.clone()));
+  }
+}
+
+async fn serve_peer<S>(
+  id: u64,
+  stream: S,
+  peers: Peers,
+  tx: broadcast::Sender<Message>,
+) where
+  S: AsyncRead + AsyncWrite,
+{
+  let (mut reader, mut writer) = tokio::io::split(stream);
+  let (frame_tx, mut frame_rx) = mpsc::unbounded_channel();
+  peers.lock().insert(id, frame_tx);
+  let _ = tokio::select! {
+    r = receive_frames(&mut reader, &tx, Some((&peers, id))) => r,
+    r = send_frames(&mut writer, &mut frame_rx) => r,
+  };
+  peers.lock().remove(&id);
+}
+
+/// Delivers the messages of another process to the subscribers of this
+/// process and, when this process is the broker, to the other peers.
+async fn receive_frames<R>(
+  reader: &mut R,
+  tx: &broadcast::Sender<Message>,
+  peers: Option<(&Peers, u64)>,
+) -> io::Result<()>
+where
+  R: AsyncRead + Unpin,
+{
+  loop {
+    let (name, data) = read_frame(reader).await?;
+    if let Some((peers, id)) = peers {
+      send_to_peers(peers, Some(id), Arc::new(encode_frame(&name, &data)));
+    }
+    // Fails when there are no subscribers left, which is fine.
+    let _ = tx.send(Message::relayed(name, data));
+  }
+}
+
+async fn send_local_frames<W>(
+  writer: &mut W,
+  rx: &mut broadcast::Receiver<Message>,
+) -> io::Result<()>
+where
+  W: AsyncWrite + Unpin,
+{
+  while let Some(frame) = next_local_frame(rx).await {
+    writer.write_all(&frame).await?;
+  }
+  Ok(())
+}
+
+async fn send_local_frames_to_peers(
+  rx: &mut broadcast::Receiver<Message>,
+  peers: &Peers,
+) -> io::Result<()> {
+  while let Some(frame) = next_local_frame(rx).await {
+    send_to_peers(peers, None, Arc::new(frame));
+  }
+  Ok(())
+}
+
+async fn send_frames<W>(
+  writer: &mut W,
+  frame_rx: &mut mpsc::UnboundedReceiver<Arc<Vec<u8>>>,
+) -> io::Result<()>
+where
+  W: AsyncWrite + Unpin,
+{
+  while let Some(frame) = frame_rx.recv().await {
+    writer.write_all(&frame).await?;
+  }
+  Ok(())
+}
+
+fn send_to_peers(peers: &Peers, sender: Option<u64>, frame: Arc<Vec<u8>>) {
+  for (id, peer) in peers.lock().iter() {
+    if Some(*id) != sender {
+      let _ = peer.send(frame.clone());
+    }
+  }
+}
+
+/// Returns the next message sent by this process, encoded for the other
+/// processes, or `None` once the channel is closed.
+async fn next_local_frame(
+  rx: &mut broadcast::Receiver<Message>,
+) -> Option<Vec<u8>> {
+  loop {
+    match rx.recv().await {
+      Ok(message) if !message.is_relayed() => {
+        return Some(encode_frame(&message.name, &message.data))
+      }
+      // Messages from other processes were already relayed.
+      Ok(_) => {}
+      Err(RecvError::Lagged(count)) => {
+        static WARN_LAGGED: Once = Once::new();
+        WARN_LAGGED.call_once(|| {
+          log::warn!(
+            "BroadcastChannel relay fell behind, {count} messages were not sent to other processes",
+          );
+        });
+      }
+      Err(RecvError::Closed) => return None,
+    }
+  }
+}
+
+/// A frame is the channel name followed by the message, each prefixed with
+/// its length as a little endian u32.
+fn encode_frame(name: &str, data: &[u8]) -> Vec<u8> {
+  let mut frame = Vec::with_capacity(8 + name.len() + data.len());
+  frame.extend_from_slice(&(name.len() as u32).to_le_bytes());
+  frame.extend_from_slice(name.as_bytes());
+  frame.extend_from_slice(&(data.len() as u32).to_le_bytes());
+  frame.extend_from_slice(data);
+  frame
+}
+
+async fn read_frame<R>(reader: &mut R) -> io::Result<(String, Vec<u8>)>
+where
+  R: AsyncRead + Unpin,
+{
+  let name = read_chunk(reader).await?;
+  let name = String::from_utf8(name)
+    .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+  let data = read_chunk(reader).await?;
+  Ok((name, data))
+}
+
+async fn read_chunk<R>(reader: &mut R) -> io::Result<Vec<u8>>
+where
+  R: AsyncRead + Unpin,
+{
+  let len = reader.read_u32_le().await? as usize;
+  if len > MAX_CHUNK_SIZE {
+    return Err(io::Error::new(
+      io::ErrorKind::InvalidData,
+      "broadcast channel message too large",
+    ));
+  }
+  let mut chunk = vec![0; len];
+  reader.read_exact(&mut chunk).await?;
+  Ok(chunk)
+}
+
+#[cfg(unix)]
+mod transport {
+  use std::fs::DirBuilder;
+  use std::fs::File;
+  use std::fs::Permissions;
+  use std::io;
+  use std::os::unix::fs::DirBuilderExt;
+  use std::os::unix::fs::OpenOptionsExt;
+  use std::os::unix::fs::PermissionsExt;
+  use std::path::Path;
+
+  use fs3::FileExt;
+  use tokio::net::UnixListener;
+  use tokio::net::UnixStream;
+
+  pub async fn connect(address: &Path) -> io::Result<UnixStream> {
+    UnixStream::connect(address).await
+  }
+
+  pub struct Listener {
+    listener: UnixListener,
+    // Held for as long as this process is the broker.
+    _lock: File,
+  }
+
+  impl Listener {
+    /// Returns `None` when another process is the broker.
+    ///
+    /// Only the current user may connect, since the deno dir might be shared
+    /// with other users.
+    pub fn bind(address: &Path) -> io::Result<Option<Self>> {
+      if let Some(parent) = address.parent() {
+        DirBuilder::new()
+          .recursive(true)
+          .mode(0o700)
+          .create(parent)?;
+      }
+      let lock = File::options()
+        .create(true)
+        .truncate(false)
+        .write(true)
+        .mode(0o600)
+        .open(address.with_extension("lock"))?;
+      if lock.try_lock_exclusive().is_err() {
+        return Ok(None);
+      }
+      // The socket of a broker which exited is left behind.
+      let _ = std::fs::remove_file(address);
+      let listener = UnixListener::bind(address)?;
+      std::fs::set_permissions(address, Permissions::from_mode(0o600))?;
+      Ok(Some(Self {
+        listener,
+        _lock: lock,
+      }))
+    }
+
+    pub async fn accept(&mut self) -> io::Result<UnixStream> {
+      let (stream, _) = self.listener.accept().await?;
+      Ok(stream)
+    }
+  }
+}
+
+#[cfg(windows)]
+mod transport {
+  use std::ffi::c_void;
+  use std::io;
+  use std::path::Path;
+  use std::ptr::null_mut;
+
+  use tokio::net::windows::named_pipe::ClientOptions;
+  use tokio::net::windows::named_pipe::NamedPipeClient;
+  use tokio::net::windows::named_pipe::NamedPipeServer;
+  use tokio::net::windows::named_pipe::ServerOptions;
+  use winapi::shared::minwindef::FALSE;
+  use winapi::um::handleapi::CloseHandle;
+  use winapi::um::minwinbase::SECURITY_ATTRIBUTES;
+  use winapi::um::processthreadsapi::GetCurrentProcess;
+  use winapi::um::processthreadsapi::OpenProcessToken;
+  use winapi::um::sddl::ConvertSidToStringSidW;
+  use winapi::um::sddl::ConvertStringSecurityDescriptorToSecurityDescriptorW;
+  use winapi::um::sddl::SDDL_REVISION_1;
+  use winapi::um::securitybaseapi::GetTokenInformation;
+  use winapi::um::winbase::LocalFree;
+  use winapi::um::winnt::TokenUser;
+  use winapi::um::winnt::HANDLE;
+  use winapi::um::winnt::PSECURITY_DESCRIPTOR;
+  use winapi::um::winnt::TOKEN_QUERY;
+  use winapi::um::winnt::TOKEN_USER;
+
+  /// Pipe names are global, so the name includes the user and a hash of the
+  /// whole address, which is under the DENO_DIR.
+  fn pipe_name(sid: &str, address: &Path) -> String {
+    // FNV-1a, which unlike the std hasher is the same in every build.
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in address.to_string_lossy().bytes() {
+      hash ^= byte as u64;
+      hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!(r"\\.\pipe\deno-broadcast-channel-{sid}-{hash:016x}")
+  }
+
+  /// The SID of the user running this process, e.g. `S-1-5-21-...`.
+  fn current_user_sid() -> io::Result<String> {
+    // SAFETY: the token handle is closed and the string allocated by
+    // `ConvertSidToStringSidW` is freed, and the buffer holding the
+    // `TOKEN_USER` is aligned and as large as `GetTokenInformation` needs.
+    unsafe {
+      let mut token: HANDLE = null_mut();
+      if OpenProcessToken(GetCurrentProcess(), TOKEN_QUERY, &mut token) == 0 {
+        return Err(io::Error::last_os_error());
+      }
+      let mut len = 0;
+      GetTokenInformation(token, TokenUser, null_mut(), 0, &mut len);
+      let mut buf = vec![0u64; (len as usize).div_ceil(8)];
+      let ok = GetTokenInformation(
+        token,
+        TokenUser,
+        buf.as_mut_ptr() as *mut c_void,
+        len,
+        &mut len,
+      );
+      CloseHandle(token);
+      if ok == 0 {
+        return Err(io::Error::last_os_error());
+      }
+      let user = &*(buf.as_ptr() as *const TOKEN_USER);
+      let mut sid = null_mut();
+      if ConvertSidToStringSidW(user.User.Sid, &mut sid) == 0 {
+        return Err(io::Error::last_os_error());
+      }
+      let sid_len = (0..).take_while(|i| *sid.add(*i) != 0).count();
+      let result =
+        String::from_utf16_lossy(std::slice::from_raw_parts(sid, sid_len));
+      LocalFree(sid as *mut c_void);
+      Ok(result)
+    }
+  }
+
+  /// A security descriptor whose DACL only allows the given user.
+  struct OwnerOnly(PSECURITY_DESCRIPTOR);
+
+  impl OwnerOnly {
+    fn new(sid: &str) -> io::Result<Self> {
+      let sddl = format!("D:P(A;;GA;;;{sid})")
+        .encode_utf16()
+        .chain(Some(0))
+        .collect::<Vec<_>>();
+      let mut descriptor = null_mut();
+      // SAFETY: `sddl` is a nul terminated wide string.
+      let ok = unsafe {
+        ConvertStringSecurityDescriptorToSecurityDescriptorW(
+          sddl.as_ptr(),
+          SDDL_REVISION_1 as u32,
+          &mut descriptor,
+          null_mut(),
+        )
+      };
+      if ok == 0 {
+        return Err(io::Error::last_os_error());
+      }
+      Ok(Self(descriptor))
+    }
+
+    fn create(
+      &self,
+      options: &ServerOptions,
+      name: &str,
+    ) -> io::Result<NamedPipeServer> {
+      let mut attributes = SECURITY_ATTRIBUTES {
+        nLength: std::mem::size_of::<SECURITY_ATTRIBUTES>() as u32,
+        lpSecurityDescriptor: self.0,
+        bInheritHandle: FALSE,
+      };
+      // SAFETY: `attributes` points to a valid security descriptor for the
+      // duration of the call.
+      unsafe {
+        options.create_with_security_attributes_raw(
+          name,
+          &mut attributes as *mut SECURITY_ATTRIBUTES as *mut c_void,
+        )
+      }
+    }
+  }
+
+  impl Drop for OwnerOnly {
+    fn drop(&mut self) {
+      // SAFETY: the descriptor was allocated with `LocalAlloc` by
+      // `ConvertStringSecurityDescriptorToSecurityDescriptorW`.
+      unsafe {
+        LocalFree(self.0);
+      }
+    }
+  }
+
+  pub async fn connect(address: &Path) -> io::Result<NamedPipeClient> {
+    ClientOptions::new().open(pipe_name(&current_user_sid()?, address))
+  }
+
+  pub struct Listener {
+    name: String,
+    security: OwnerOnly,
+    server: NamedPipeServer,
+  }
+
+  impl Listener {
+    /// Returns `None` when another process is the broker.
+    pub fn bind(address: &Path) -> io::Result<Option<Self>> {
+      let sid = current_user_sid()?;
+      let name = pipe_name(&sid, address);
+      let security = OwnerOnly::new(&sid)?;
+      // Creating the first instance of a pipe fails if it already exists,
+      // which makes the process creating it the broker.
+      let mut options = ServerOptions::new();
+      options
+        .first_pipe_instance(true)
+        .reject_remote_clients(true);
+      match security.create(&options, &name) {
+        Ok(server) => Ok(Some(Self {
+          name,
+          security,
+          server,
+        })),
+        Err(err) if err.kind() == io::ErrorKind::PermissionDenied => Ok(None),
+        Err(err) => Err(err),
+      }
+    }
+
+    pub async fn accept(&mut self) -> io::Result<NamedPipeServer> {
+      self.server.connect().await?;
+      let mut options = ServerOptions::new();
+      options.reject_remote_clients(true);
+      let next = self.security.create(&options, &self.name)?;
+      Ok(std::mem::replace(&mut self.server, next))
+    }
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use tokio::sync::oneshot;
+  use uuid::Uuid;
+
+  use super::*;
+
+  /// A relay running on its own runtime, as it would in its own process.
+  struct Process {
+    tx: broadcast::Sender<Message>,
+    stop: oneshot::Sender<()>,
+    thread: std::thread::JoinHandle<()>,
+  }
+
+  impl Process {
+    fn start(address: &Path) -> Self {
+      let (tx, rx) = broadcast::channel(256);
+      let (stop, stop_rx) = oneshot::channel();
+      let address = address.to_path_buf();
+      let relay_tx = tx.clone();
+      let thread = std::thread::spawn(move || {
+        let rt = tokio::runtime::Builder::new_current_thread()
+          .enable_all()
+          .build()
+          .unwrap();
+        rt.block_on(async {
+          tokio::select! {
+            _ = run(address, relay_tx, rx) => {}
+            _ = stop_rx => {}
+          }
+        });
+        // dropping the runtime closes all the connections of the process
+      });
+      Self { tx, stop, thread }
+    }
+
+    fn exit(self) {
+      let _ = self.stop.send(());
+      self.thread.join().unwrap();
+    }
+  }
+
+  /// Sends a message from `from` until `to` receives it.
+  async fn deliver(from: &Process, to: &Process, name: &str) {
+    let mut rx = to.tx.subscribe();
+    let received = async {
+      loop {
+        match rx.recv().await {
+          Ok(message) if message.is_relayed() && *message.name == name => {
+            return
+          }
+          Err(RecvError::Closed) => unreachable!(),
+          _ => {}
+        }
+      }
+    };
+    tokio::pin!(received);
+    let delivered = tokio::time::timeout(Duration::from_secs(30), async {
+      loop {
+        from.tx.send(Message::new(name, b"data")).unwrap();
+        let retry = tokio::time::sleep(Duration::from_millis(100));
+        tokio::select! {
+          _ = &mut received => return,
+          _ = retry => {}
+        }
+      }
+    })
+    .await;
+    assert!(delivered.is_ok(), "message {name} wasn't delivered");
+  }
+
+  #[tokio::test]
+  async fn elects_new_broker_when_broker_exits() {
+    let dir = std::env::temp_dir().join(format!(
+      "deno_relay_{}",
+      &Uuid::new_v4().simple().to_string()[..8]
+    ));
+    let address = dir.join("relay.sock");
+
+    let broker = Process::start(&address);
+    while transport::connect(&address).await.is_err() {
+      tokio::time::sleep(MIN_RETRY_DELAY).await;
+    }
+    let a = Process::start(&address);
+    let b = Process::start(&address);
+    deliver(&a, &b, "a to b").await;
+    deliver(&b, &broker, "b to broker").await;
+
+    broker.exit();
+    deliver(&a, &b, "a to b after exit").await;
+    deliver(&b, &a, "b to a after exit").await;
+
+    a.exit();
+    b.exit();
+    let _ = std::fs::remove_dir_all(dir);
+  }
+}
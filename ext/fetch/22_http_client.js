@@ -10,7 +10,7 @@
 /// <reference path="./lib.deno_fetch.d.ts" />
 /// <reference lib="esnext" />
 
-import { core, primordials } from "ext:core/mod.js";
+import { core, internals, primordials } from "ext:core/mod.js";
 
 import { SymbolDispose } from "ext:deno_web/00_infra.js";
 import { op_fetch_custom_client } from "ext:core/ops";
@@ -46,6 +46,7 @@ class HttpClient {
       value: rid,
     });
     this.#rid = rid;
+    internals.traceResourceCreation?.(rid);
   }
 
   close() {
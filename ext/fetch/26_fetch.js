@@ -10,7 +10,7 @@
 /// <reference path="./lib.deno_fetch.d.ts" />
 /// <reference lib="esnext" />
 
-import { core, primordials } from "ext:core/mod.js";
+import { core, internals, primordials } from "ext:core/mod.js";
 import {
   op_fetch,
   op_fetch_send,
@@ -77,6 +77,7 @@ function opFetchSend(rid) {
  * @returns {ReadableStream<Uint8Array>}
  */
 function createResponseBodyStream(responseBodyRid, terminator) {
+  internals.traceResourceCreation?.(responseBodyRid);
   const readable = readableStreamForRid(responseBodyRid);
 
   function onAbort() {
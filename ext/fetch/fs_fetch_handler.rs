@@ -11,13 +11,30 @@ use deno_core::url::Url;
 use deno_core::CancelFuture;
 use deno_core::OpState;
 use reqwest::StatusCode;
+use std::path::Path;
 use std::rc::Rc;
+use std::sync::Arc;
 use tokio_util::io::ReaderStream;
 
+/// Reads a file from somewhere other than the real file system, such as
+/// the files embedded in a standalone binary. Returns `None` for paths that
+/// should be read from the real file system.
+pub type FsFetchReadFileFn = Arc<
+  dyn Fn(&Path) -> Option<std::io::Result<Vec<u8>>> + Send + Sync + 'static,
+>;
+
 /// An implementation which tries to read file URLs from the file system via
 /// tokio::fs.
-#[derive(Clone)]
-pub struct FsFetchHandler;
+#[derive(Clone, Default)]
+pub struct FsFetchHandler {
+  maybe_read_file: Option<FsFetchReadFileFn>,
+}
+
+impl FsFetchHandler {
+  pub fn new(maybe_read_file: Option<FsFetchReadFileFn>) -> Self {
+    Self { maybe_read_file }
+  }
+}
 
 impl FetchHandler for FsFetchHandler {
   fn fetch_file(
@@ -26,11 +43,17 @@ impl FetchHandler for FsFetchHandler {
     url: Url,
   ) -> (CancelableResponseFuture, Option<Rc<CancelHandle>>) {
     let cancel_handle = CancelHandle::new_rc();
+    let maybe_read_file = self.maybe_read_file.clone();
     let response_fut = async move {
       let path = url.to_file_path()?;
-      let file = tokio::fs::File::open(path).map_err(|_| ()).await?;
-      let stream = ReaderStream::new(file);
-      let body = reqwest::Body::wrap_stream(stream);
+      let maybe_bytes = maybe_read_file.and_then(|read_file| read_file(&path));
+      let body = match maybe_bytes {
+        Some(bytes) => reqwest::Body::from(bytes.map_err(|_| ())?),
+        None => {
+          let file = tokio::fs::File::open(path).map_err(|_| ()).await?;
+          reqwest::Body::wrap_stream(ReaderStream::new(file))
+        }
+      };
       let response = http_v02::Response::builder()
         .status(StatusCode::OK)
         .body(body)
@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 mod fs_fetch_handler;
+mod unix_socket;
 
 use std::borrow::Cow;
 use std::cell::RefCell;
@@ -71,6 +72,7 @@ pub use data_url;
 pub use reqwest;
 
 pub use fs_fetch_handler::FsFetchHandler;
+pub use fs_fetch_handler::FsFetchReadFileFn;
 
 #[derive(Clone)]
 pub struct Options {
@@ -82,6 +84,10 @@ pub struct Options {
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
   pub client_cert_chain_and_key: Option<TlsKey>,
   pub file_fetch_handler: Rc<dyn FetchHandler>,
+  /// Disables HTTP/2 for the default client and the clients created with
+  /// `Deno.createHttpClient()`, e.g. for servers or proxies that negotiate
+  /// HTTP/2 but don't handle it properly.
+  pub http1_only: bool,
 }
 
 impl Options {
@@ -103,6 +109,7 @@ impl Default for Options {
       unsafely_ignore_certificate_errors: None,
       client_cert_chain_and_key: None,
       file_fetch_handler: Rc::new(DefaultFileFetchHandler),
+      http1_only: false,
     }
   }
 }
@@ -209,7 +216,8 @@ pub fn create_client_from_options(
       pool_max_idle_per_host: None,
       pool_idle_timeout: None,
       http1: true,
-      http2: true,
+      http2: !options.http1_only,
+      cookie_store: false,
     },
   )
 }
@@ -274,6 +282,7 @@ pub trait FetchPermissions {
     api_name: &str,
   ) -> Result<(), AnyError>;
   fn check_read(&mut self, _p: &Path, api_name: &str) -> Result<(), AnyError>;
+  fn check_write(&mut self, _p: &Path, api_name: &str) -> Result<(), AnyError>;
 }
 
 #[op2]
@@ -292,11 +301,11 @@ pub fn op_fetch<FP>(
 where
   FP: FetchPermissions + 'static,
 {
-  let (client, allow_host) = if let Some(rid) = client_rid {
+  let (client, allow_host, unix_socket_path) = if let Some(rid) = client_rid {
     let r = state.resource_table.get::<HttpClientResource>(rid)?;
-    (r.client.clone(), r.allow_host)
+    (r.client.clone(), r.allow_host, r.unix_socket_path.clone())
   } else {
-    (get_or_create_client_from_state(state)?, false)
+    (get_or_create_client_from_state(state)?, false, None)
   };
 
   let method = Method::from_bytes(&method)?;
@@ -331,8 +340,12 @@ where
       (request_rid, maybe_cancel_handle_rid)
     }
     "http" | "https" => {
-      let permissions = state.borrow_mut::<FP>();
-      permissions.check_net_url(&url, "fetch()")?;
+      // Requests made through a unix socket never reach the network, the
+      // socket path was checked when the client was created.
+      if unix_socket_path.is_none() {
+        let permissions = state.borrow_mut::<FP>();
+        permissions.check_net_url(&url, "fetch()")?;
+      }
 
       // Make sure that we have a valid URI early, as reqwest's `RequestBuilder::send`
       // internally uses `expect_uri`, which panics instead of returning a usable `Result`.
@@ -340,25 +353,40 @@ where
         return Err(type_error("Invalid URL"));
       }
 
-      let mut request = client.request(method.clone(), url);
+      let mut header_map = HeaderMap::new();
+      for (key, value) in headers {
+        let name = HeaderName::from_bytes(&key)
+          .map_err(|err| type_error(err.to_string()))?;
+        let v = HeaderValue::from_bytes(&value)
+          .map_err(|err| type_error(err.to_string()))?;
+
+        if (name != HOST || allow_host) && name != CONTENT_LENGTH {
+          header_map.append(name, v);
+        }
+      }
+
+      if header_map.contains_key(RANGE) {
+        // https://fetch.spec.whatwg.org/#http-network-or-cache-fetch step 18
+        // If httpRequest’s header list contains `Range`, then append (`Accept-Encoding`, `identity`)
+        header_map
+          .insert(ACCEPT_ENCODING, HeaderValue::from_static("identity"));
+      }
 
-      if has_body {
+      let body = if has_body {
         match (data, resource) {
           (Some(data), _) => {
             // If a body is passed, we use it, and don't return a body for streaming.
-            request = request.body(data.to_vec());
+            Some(RequestBody::Bytes(data.to_vec().into()))
           }
           (_, Some(resource)) => {
             let resource = state.resource_table.take_any(resource)?;
             match resource.size_hint() {
               (body_size, Some(n)) if body_size == n && body_size > 0 => {
-                request =
-                  request.header(CONTENT_LENGTH, HeaderValue::from(body_size));
+                header_map.insert(CONTENT_LENGTH, HeaderValue::from(body_size));
               }
               _ => {}
             }
-            request = request
-              .body(Body::wrap_stream(ResourceToBodyAdapter::new(resource)))
+            Some(RequestBody::Stream(ResourceToBodyAdapter::new(resource)))
           }
           (None, None) => unreachable!(),
         }
@@ -366,50 +394,54 @@ where
         // POST and PUT requests should always have a 0 length content-length,
         // if there is no body. https://fetch.spec.whatwg.org/#http-network-or-cache-fetch
         if matches!(method, Method::POST | Method::PUT) {
-          request = request.header(CONTENT_LENGTH, HeaderValue::from(0));
+          header_map.insert(CONTENT_LENGTH, HeaderValue::from(0));
         }
+        None
       };
 
-      let mut header_map = HeaderMap::new();
-      for (key, value) in headers {
-        let name = HeaderName::from_bytes(&key)
-          .map_err(|err| type_error(err.to_string()))?;
-        let v = HeaderValue::from_bytes(&value)
-          .map_err(|err| type_error(err.to_string()))?;
+      let cancel_handle = CancelHandle::new_rc();
+      let cancel_handle_ = cancel_handle.clone();
 
-        if (name != HOST || allow_host) && name != CONTENT_LENGTH {
-          header_map.append(name, v);
+      let fut: CancelableResponseFuture = match unix_socket_path {
+        Some(socket_path) => {
+          let options = state.borrow::<Options>();
+          if !header_map.contains_key(USER_AGENT) {
+            header_map
+              .insert(USER_AGENT, HeaderValue::from_str(&options.user_agent)?);
+          }
+          Box::pin(async move {
+            unix_socket::fetch(socket_path, method, url, header_map, body)
+              .or_cancel(cancel_handle_)
+              .await
+          })
         }
-      }
-
-      if header_map.contains_key(RANGE) {
-        // https://fetch.spec.whatwg.org/#http-network-or-cache-fetch step 18
-        // If httpRequest’s header list contains `Range`, then append (`Accept-Encoding`, `identity`)
-        header_map
-          .insert(ACCEPT_ENCODING, HeaderValue::from_static("identity"));
-      }
-      request = request.headers(header_map);
-
-      let options = state.borrow::<Options>();
-      if let Some(request_builder_hook) = options.request_builder_hook {
-        request = request_builder_hook(request)
-          .map_err(|err| type_error(err.to_string()))?;
-      }
+        None => {
+          let mut request = client.request(method, url).headers(header_map);
+          match body {
+            Some(RequestBody::Bytes(bytes)) => request = request.body(bytes),
+            Some(RequestBody::Stream(stream)) => {
+              request = request.body(Body::wrap_stream(stream))
+            }
+            None => {}
+          }
 
-      let cancel_handle = CancelHandle::new_rc();
-      let cancel_handle_ = cancel_handle.clone();
+          let options = state.borrow::<Options>();
+          if let Some(request_builder_hook) = options.request_builder_hook {
+            request = request_builder_hook(request)
+              .map_err(|err| type_error(err.to_string()))?;
+          }
 
-      let fut = async move {
-        request
-          .send()
-          .or_cancel(cancel_handle_)
-          .await
-          .map(|res| res.map_err(|err| err.into()))
+          Box::pin(async move {
+            request
+              .send()
+              .or_cancel(cancel_handle_)
+              .await
+              .map(|res| res.map_err(|err| err.into()))
+          })
+        }
       };
 
-      let request_rid = state
-        .resource_table
-        .add(FetchRequestResource(Box::pin(fut)));
+      let request_rid = state.resource_table.add(FetchRequestResource(fut));
 
       let cancel_handle_rid =
         state.resource_table.add(FetchCancelHandle(cancel_handle));
@@ -653,6 +685,12 @@ impl Resource for UpgradeStream {
 
 type CancelableResponseResult = Result<Result<Response, AnyError>, Canceled>;
 
+/// The body of an outgoing request.
+pub enum RequestBody {
+  Bytes(Bytes),
+  Stream(ResourceToBodyAdapter),
+}
+
 pub struct FetchRequestResource(
   pub Pin<Box<dyn Future<Output = CancelableResponseResult>>>,
 );
@@ -783,6 +821,9 @@ impl Resource for FetchResponseResource {
 pub struct HttpClientResource {
   pub client: Client,
   pub allow_host: bool,
+  /// When set, requests are sent over this unix domain socket instead of
+  /// connecting to the host of the URL.
+  pub unix_socket_path: Option<PathBuf>,
 }
 
 impl Resource for HttpClientResource {
@@ -792,8 +833,16 @@ impl Resource for HttpClientResource {
 }
 
 impl HttpClientResource {
-  fn new(client: Client, allow_host: bool) -> Self {
-    Self { client, allow_host }
+  fn new(
+    client: Client,
+    allow_host: bool,
+    unix_socket_path: Option<PathBuf>,
+  ) -> Self {
+    Self {
+      client,
+      allow_host,
+      unix_socket_path,
+    }
   }
 }
 
@@ -810,6 +859,9 @@ pub struct CreateHttpClientArgs {
   http2: bool,
   #[serde(default)]
   allow_host: bool,
+  #[serde(default)]
+  cookie_store: bool,
+  unix_socket_path: Option<String>,
 }
 
 fn default_true() -> bool {
@@ -832,12 +884,33 @@ where
     permissions.check_net_url(&url, "Deno.createHttpClient()")?;
   }
 
+  let unix_socket_path = match args.unix_socket_path {
+    Some(path) => {
+      if !cfg!(unix) {
+        return Err(type_error(
+          "`unixSocketPath` is not supported on this platform",
+        ));
+      }
+      let path = PathBuf::from(path);
+      let permissions = state.borrow_mut::<FP>();
+      permissions.check_read(&path, "Deno.createHttpClient()")?;
+      permissions.check_write(&path, "Deno.createHttpClient()")?;
+      Some(path)
+    }
+    None => None,
+  };
+
   let client_cert_chain_and_key = match tls_keys {
     TlsKeys::Null => None,
     TlsKeys::Static(key) => Some(key.clone()),
   };
 
   let options = state.borrow::<Options>();
+  if options.http1_only && !args.http1 {
+    return Err(type_error(
+      "`http1` can't be disabled when HTTP/2 is disabled with --http1-only",
+    ));
+  }
   let ca_certs = args
     .ca_certs
     .into_iter()
@@ -866,13 +939,16 @@ where
         },
       ),
       http1: args.http1,
-      http2: args.http2,
+      http2: args.http2 && !options.http1_only,
+      cookie_store: args.cookie_store,
     },
   )?;
 
-  let rid = state
-    .resource_table
-    .add(HttpClientResource::new(client, args.allow_host));
+  let rid = state.resource_table.add(HttpClientResource::new(
+    client,
+    args.allow_host,
+    unix_socket_path,
+  ));
   Ok(rid)
 }
 
@@ -887,6 +963,10 @@ pub struct CreateHttpClientOptions {
   pub pool_idle_timeout: Option<Option<u64>>,
   pub http1: bool,
   pub http2: bool,
+  /// Store the cookies set by responses in memory and send them along with
+  /// the following requests of the client. The cookies are dropped with the
+  /// client.
+  pub cookie_store: bool,
 }
 
 impl Default for CreateHttpClientOptions {
@@ -901,6 +981,7 @@ impl Default for CreateHttpClientOptions {
       pool_idle_timeout: None,
       http1: true,
       http2: true,
+      cookie_store: false,
     }
   }
 }
@@ -944,6 +1025,10 @@ pub fn create_http_client(
     builder = builder.proxy(reqwest_proxy);
   }
 
+  if options.cookie_store {
+    builder = builder.cookie_store(true);
+  }
+
   if let Some(pool_max_idle_per_host) = options.pool_max_idle_per_host {
     builder = builder.pool_max_idle_per_host(pool_max_idle_per_host);
   }
@@ -0,0 +1,72 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+use std::path::PathBuf;
+
+use deno_core::error::AnyError;
+use deno_core::url::Url;
+use reqwest::header::HeaderMap;
+use reqwest::Method;
+use reqwest::Response;
+
+use crate::RequestBody;
+
+/// Send a request to the server listening on the unix domain socket at
+/// `socket_path`. Every request is made on a new HTTP/1.1 connection.
+#[cfg(unix)]
+pub async fn fetch(
+  socket_path: PathBuf,
+  method: Method,
+  url: Url,
+  mut headers: HeaderMap,
+  body: Option<RequestBody>,
+) -> Result<Response, AnyError> {
+  use deno_core::url::Position;
+  use reqwest::header::HeaderValue;
+  use reqwest::header::HOST;
+  use reqwest::ResponseBuilderExt;
+
+  if !headers.contains_key(HOST) {
+    let host = &url[Position::BeforeHost..Position::AfterPort];
+    headers.insert(HOST, HeaderValue::from_str(host)?);
+  }
+  let body = match body {
+    Some(RequestBody::Bytes(bytes)) => hyper_v014::Body::from(bytes),
+    Some(RequestBody::Stream(stream)) => hyper_v014::Body::wrap_stream(stream),
+    None => hyper_v014::Body::empty(),
+  };
+  let mut request = http_v02::Request::builder()
+    .method(method)
+    .uri(&url[Position::BeforePath..Position::AfterQuery])
+    .body(body)?;
+  *request.headers_mut() = headers;
+
+  let stream = tokio::net::UnixStream::connect(&socket_path).await?;
+  let (mut sender, conn) = hyper_v014::client::conn::handshake(stream).await?;
+  deno_core::unsync::spawn(async move {
+    // Connection errors are surfaced through the pending request.
+    let _ = conn.await;
+  });
+  let response = sender.send_request(request).await?;
+
+  let (parts, body) = response.into_parts();
+  let mut builder = http_v02::Response::builder()
+    .status(parts.status)
+    .version(parts.version)
+    .url(url);
+  *builder.headers_mut().unwrap() = parts.headers;
+  let response = builder.body(reqwest::Body::wrap_stream(body))?;
+  Ok(Response::from(response))
+}
+
+#[cfg(not(unix))]
+pub async fn fetch(
+  _socket_path: PathBuf,
+  _method: Method,
+  _url: Url,
+  _headers: HeaderMap,
+  _body: Option<RequestBody>,
+) -> Result<Response, AnyError> {
+  Err(deno_core::error::type_error(
+    "`unixSocketPath` is not supported on this platform",
+  ))
+}
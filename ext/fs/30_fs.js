@@ -15,6 +15,10 @@ import {
   op_fs_copy_file_async,
   op_fs_copy_file_sync,
   op_fs_cwd,
+  op_fs_fallocate_async,
+  op_fs_fallocate_sync,
+  op_fs_fcopy_async,
+  op_fs_fcopy_sync,
   op_fs_fdatasync_async,
   op_fs_fdatasync_async_unstable,
   op_fs_fdatasync_sync,
@@ -31,10 +35,14 @@ import {
   op_fs_ftruncate_sync,
   op_fs_funlock_async,
   op_fs_funlock_sync,
+  op_fs_get_xattr_async,
+  op_fs_get_xattr_sync,
   op_fs_futime_async,
   op_fs_futime_sync,
   op_fs_link_async,
   op_fs_link_sync,
+  op_fs_list_xattrs_async,
+  op_fs_list_xattrs_sync,
   op_fs_lstat_async,
   op_fs_lstat_sync,
   op_fs_make_temp_dir_async,
@@ -57,14 +65,21 @@ import {
   op_fs_realpath_sync,
   op_fs_remove_async,
   op_fs_remove_sync,
+  op_fs_remove_xattr_async,
+  op_fs_remove_xattr_sync,
   op_fs_rename_async,
   op_fs_rename_sync,
   op_fs_seek_async,
   op_fs_seek_sync,
+  op_fs_set_immutable_async,
+  op_fs_set_immutable_sync,
+  op_fs_set_xattr_async,
+  op_fs_set_xattr_sync,
   op_fs_stat_async,
   op_fs_stat_sync,
   op_fs_symlink_async,
   op_fs_symlink_sync,
+  op_fs_temp_cleanup_unregister,
   op_fs_truncate_async,
   op_fs_truncate_sync,
   op_fs_umask,
@@ -81,10 +96,12 @@ const {
   Error,
   Function,
   MathTrunc,
+  NumberIsSafeInteger,
   ObjectEntries,
   ObjectDefineProperty,
   ObjectPrototypeIsPrototypeOf,
   ObjectValues,
+  RangeError,
   StringPrototypeSlice,
   StringPrototypeStartsWith,
   SymbolAsyncIterator,
@@ -164,14 +181,18 @@ function makeTempDirSync(options = {}) {
     options.dir,
     options.prefix,
     options.suffix,
+    options.mode,
+    !!options.cleanupAtExit,
   );
 }
 
-function makeTempDir(options = {}) {
-  return op_fs_make_temp_dir_async(
+async function makeTempDir(options = {}) {
+  return await op_fs_make_temp_dir_async(
     options.dir,
     options.prefix,
     options.suffix,
+    options.mode,
+    !!options.cleanupAtExit,
   );
 }
 
@@ -180,17 +201,37 @@ function makeTempFileSync(options = {}) {
     options.dir,
     options.prefix,
     options.suffix,
+    options.mode,
+    !!options.cleanupAtExit,
   );
 }
 
-function makeTempFile(options = {}) {
-  return op_fs_make_temp_file_async(
+async function makeTempFile(options = {}) {
+  return await op_fs_make_temp_file_async(
     options.dir,
     options.prefix,
     options.suffix,
+    options.mode,
+    !!options.cleanupAtExit,
   );
 }
 
+async function withTempDir(fn, options = {}) {
+  const path = await makeTempDir({ ...options, cleanupAtExit: true });
+  try {
+    return await fn(path);
+  } finally {
+    try {
+      await op_fs_remove_async(path, true);
+    } catch (error) {
+      // the callback may have removed the directory itself
+      if (error.name !== "NotFound") throw error;
+    } finally {
+      op_fs_temp_cleanup_unregister(path);
+    }
+  }
+}
+
 function mkdirSync(path, options) {
   op_fs_mkdir_sync(
     pathFromURL(path),
@@ -298,7 +339,8 @@ async function rename(oldpath, newpath) {
 // 4. ?u64 converts a zero u64 value to JS null on Windows.
 //    ?bool converts a false bool value to JS null on Windows.
 function createByteStruct(types) {
-  // types can be "date", "bool" or "u64".
+  // types can be "date", "bool", "u64" or "flag" (a bool which may be
+  // unknown).
   let offset = 0;
   let str =
     'const unix = Deno.build.os === "darwin" || Deno.build.os === "linux" || Deno.build.os === "android" || Deno.build.os === "openbsd" || Deno.build.os === "freebsd"; return {';
@@ -324,6 +366,9 @@ function createByteStruct(types) {
         offset + 2
       }] + view[${offset + 3}] * 2**32),`;
       offset += 2;
+    } else if (type == "flag") {
+      str += `${name}: view[${offset}] === 0 ? null : !!view[${offset + 2}],`;
+      offset += 2;
     } else {
       if (!optional) {
         str += `${name}: !!(view[${offset}] + view[${offset + 1}] * 2**32),`;
@@ -363,6 +408,7 @@ const { 0: statStruct, 1: statBuf } = createByteStruct({
   isCharDevice: "?bool",
   isFifo: "?bool",
   isSocket: "?bool",
+  immutable: "flag",
 });
 
 function parseFileInfo(response) {
@@ -394,6 +440,7 @@ function parseFileInfo(response) {
     isCharDevice: unix ? response.isCharDevice : null,
     isFifo: unix ? response.isFifo : null,
     isSocket: unix ? response.isSocket : null,
+    immutable: response.immutableSet === true ? response.immutable : null,
   };
 }
 
@@ -540,6 +587,50 @@ async function utime(
   );
 }
 
+function getXattrSync(path, name) {
+  return op_fs_get_xattr_sync(pathFromURL(path), name);
+}
+
+async function getXattr(path, name) {
+  return await op_fs_get_xattr_async(pathFromURL(path), name);
+}
+
+function xattrValue(value) {
+  return typeof value === "string" ? core.encode(value) : value;
+}
+
+function setXattrSync(path, name, value) {
+  op_fs_set_xattr_sync(pathFromURL(path), name, xattrValue(value));
+}
+
+async function setXattr(path, name, value) {
+  await op_fs_set_xattr_async(pathFromURL(path), name, xattrValue(value));
+}
+
+function removeXattrSync(path, name) {
+  op_fs_remove_xattr_sync(pathFromURL(path), name);
+}
+
+async function removeXattr(path, name) {
+  await op_fs_remove_xattr_async(pathFromURL(path), name);
+}
+
+function listXattrsSync(path) {
+  return op_fs_list_xattrs_sync(pathFromURL(path));
+}
+
+async function listXattrs(path) {
+  return await op_fs_list_xattrs_async(pathFromURL(path));
+}
+
+function setImmutableSync(path, immutable) {
+  op_fs_set_immutable_sync(pathFromURL(path), !!immutable);
+}
+
+async function setImmutable(path, immutable) {
+  await op_fs_set_immutable_async(pathFromURL(path), !!immutable);
+}
+
 function symlinkSync(
   oldpath,
   newpath,
@@ -638,6 +729,48 @@ async function open(
   return new FsFile(rid, SymbolFor("Deno.internal.FsFile"));
 }
 
+function checkStreamPosition(name, value) {
+  if (value !== undefined && (!NumberIsSafeInteger(value) || value < 0)) {
+    throw new RangeError(
+      `'${name}' must be a non-negative integer, received ${value}`,
+    );
+  }
+}
+
+async function openReadableStream(path, options = { __proto__: null }) {
+  const { chunkSize, start } = options;
+  checkStreamPosition("start", start);
+  if (chunkSize !== undefined) {
+    checkStreamPosition("chunkSize", chunkSize);
+    if (chunkSize === 0) {
+      throw new RangeError("'chunkSize' must be greater than 0");
+    }
+  }
+  const rid = await op_fs_open_async(pathFromURL(path), { read: true });
+  if (start) {
+    try {
+      await op_fs_seek_async(rid, start, 0 /* SeekMode.Start */);
+    } catch (error) {
+      core.tryClose(rid);
+      throw error;
+    }
+  }
+  return readableStreamForRid(rid, true, chunkSize);
+}
+
+async function openWritableStream(path, options = { __proto__: null }) {
+  const append = options.append ?? false;
+  const rid = await op_fs_open_async(pathFromURL(path), {
+    write: true,
+    append,
+    truncate: !append,
+    create: options.create ?? true,
+    createNew: options.createNew ?? false,
+    mode: options.mode,
+  });
+  return writableStreamForRid(rid);
+}
+
 function createSync(path) {
   return openSync(path, {
     read: true,
@@ -668,6 +801,7 @@ class FsFile {
       value: rid,
     });
     this.#rid = rid;
+    internals.traceResourceCreation?.(rid);
     if (!symbol || symbol !== SymbolFor("Deno.internal.FsFile")) {
       internals.warnOnDeprecatedApi(
         "new Deno.FsFile()",
@@ -707,6 +841,30 @@ class FsFile {
     return ftruncateSync(this.#rid, len);
   }
 
+  async allocate(len) {
+    await op_fs_fallocate_async(this.#rid, coerceLen(len));
+  }
+
+  allocateSync(len) {
+    op_fs_fallocate_sync(this.#rid, coerceLen(len));
+  }
+
+  copyTo(dest, len) {
+    return op_fs_fcopy_async(
+      this.#rid,
+      dest[internalRidSymbol],
+      len == null ? null : coerceLen(len),
+    );
+  }
+
+  copyToSync(dest, len) {
+    return op_fs_fcopy_sync(
+      this.#rid,
+      dest[internalRidSymbol],
+      len == null ? null : coerceLen(len),
+    );
+  }
+
   read(p) {
     return read(this.#rid, p);
   }
@@ -1005,8 +1163,12 @@ export {
   funlockSync,
   futime,
   futimeSync,
+  getXattr,
+  getXattrSync,
   link,
   linkSync,
+  listXattrs,
+  listXattrsSync,
   lstat,
   lstatSync,
   makeTempDir,
@@ -1016,7 +1178,9 @@ export {
   mkdir,
   mkdirSync,
   open,
+  openReadableStream,
   openSync,
+  openWritableStream,
   readDir,
   readDirSync,
   readFile,
@@ -1029,10 +1193,16 @@ export {
   realPathSync,
   remove,
   removeSync,
+  removeXattr,
+  removeXattrSync,
   rename,
   renameSync,
   seek,
   seekSync,
+  setImmutable,
+  setImmutableSync,
+  setXattr,
+  setXattrSync,
   stat,
   statSync,
   symlink,
@@ -1042,6 +1212,7 @@ export {
   umask,
   utime,
   utimeSync,
+  withTempDir,
   writeFile,
   writeFileSync,
   writeTextFile,
@@ -224,6 +224,7 @@ impl FileSystem for InMemoryFs {
           is_char_device: false,
           is_fifo: false,
           is_socket: false,
+          is_immutable: None,
         }),
         PathEntry::File(data) => Ok(FsStat {
           is_file: true,
@@ -246,6 +247,7 @@ impl FileSystem for InMemoryFs {
           is_char_device: false,
           is_fifo: false,
           is_socket: false,
+          is_immutable: None,
         }),
       },
       None => Err(FsError::Io(Error::new(ErrorKind::NotFound, "Not found"))),
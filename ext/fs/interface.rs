@@ -8,6 +8,7 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use deno_io::fs::File;
+use deno_io::fs::FsError;
 use deno_io::fs::FsResult;
 use deno_io::fs::FsStat;
 
@@ -219,6 +220,70 @@ pub trait FileSystem: std::fmt::Debug + MaybeSend + MaybeSync {
     mtime_nanos: u32,
   ) -> FsResult<()>;
 
+  /// Returns `None` if the file has no extended attribute `name`.
+  fn get_xattr_sync(
+    &self,
+    _path: &Path,
+    _name: &str,
+  ) -> FsResult<Option<Vec<u8>>> {
+    Err(FsError::NotSupported)
+  }
+  async fn get_xattr_async(
+    &self,
+    path: PathBuf,
+    name: String,
+  ) -> FsResult<Option<Vec<u8>>> {
+    self.get_xattr_sync(&path, &name)
+  }
+
+  fn set_xattr_sync(
+    &self,
+    _path: &Path,
+    _name: &str,
+    _value: &[u8],
+  ) -> FsResult<()> {
+    Err(FsError::NotSupported)
+  }
+  async fn set_xattr_async(
+    &self,
+    path: PathBuf,
+    name: String,
+    value: Vec<u8>,
+  ) -> FsResult<()> {
+    self.set_xattr_sync(&path, &name, &value)
+  }
+
+  fn remove_xattr_sync(&self, _path: &Path, _name: &str) -> FsResult<()> {
+    Err(FsError::NotSupported)
+  }
+  async fn remove_xattr_async(
+    &self,
+    path: PathBuf,
+    name: String,
+  ) -> FsResult<()> {
+    self.remove_xattr_sync(&path, &name)
+  }
+
+  fn list_xattrs_sync(&self, _path: &Path) -> FsResult<Vec<String>> {
+    Err(FsError::NotSupported)
+  }
+  async fn list_xattrs_async(&self, path: PathBuf) -> FsResult<Vec<String>> {
+    self.list_xattrs_sync(&path)
+  }
+
+  /// Sets or clears the flag making the file immutable (`chattr +i` on
+  /// Linux, `chflags uchg` on macOS).
+  fn set_immutable_sync(&self, _path: &Path, _immutable: bool) -> FsResult<()> {
+    Err(FsError::NotSupported)
+  }
+  async fn set_immutable_async(
+    &self,
+    path: PathBuf,
+    immutable: bool,
+  ) -> FsResult<()> {
+    self.set_immutable_sync(&path, immutable)
+  }
+
   fn write_file_sync(
     &self,
     path: &Path,
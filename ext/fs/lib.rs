@@ -5,6 +5,7 @@ mod interface;
 mod ops;
 mod std_fs;
 pub mod sync;
+pub mod temp_cleanup;
 
 pub use crate::in_memory_fs::InMemoryFs;
 pub use crate::interface::AccessCheckCb;
@@ -129,10 +130,21 @@ deno_core::extension!(deno_fs,
     op_fs_truncate_async<P>,
     op_fs_utime_sync<P>,
     op_fs_utime_async<P>,
+    op_fs_get_xattr_sync<P>,
+    op_fs_get_xattr_async<P>,
+    op_fs_set_xattr_sync<P>,
+    op_fs_set_xattr_async<P>,
+    op_fs_remove_xattr_sync<P>,
+    op_fs_remove_xattr_async<P>,
+    op_fs_list_xattrs_sync<P>,
+    op_fs_list_xattrs_async<P>,
+    op_fs_set_immutable_sync<P>,
+    op_fs_set_immutable_async<P>,
     op_fs_make_temp_dir_sync<P>,
     op_fs_make_temp_dir_async<P>,
     op_fs_make_temp_file_sync<P>,
     op_fs_make_temp_file_async<P>,
+    op_fs_temp_cleanup_unregister,
     op_fs_write_file_sync<P>,
     op_fs_write_file_async<P>,
     op_fs_read_file_sync<P>,
@@ -160,6 +172,10 @@ deno_core::extension!(deno_fs,
     op_fs_ftruncate_async,
     op_fs_futime_sync,
     op_fs_futime_async,
+    op_fs_fallocate_sync,
+    op_fs_fallocate_async,
+    op_fs_fcopy_sync,
+    op_fs_fcopy_async,
 
   ],
   esm = [ "30_fs.js" ],
@@ -32,6 +32,7 @@ use crate::interface::AccessCheckFn;
 use crate::interface::FileSystemRc;
 use crate::interface::FsDirEntry;
 use crate::interface::FsFileType;
+use crate::temp_cleanup;
 use crate::FsPermissions;
 use crate::OpenOptions;
 
@@ -912,6 +913,264 @@ where
   Ok(())
 }
 
+#[op2]
+#[serde]
+pub fn op_fs_get_xattr_sync<P>(
+  state: &mut OpState,
+  #[string] path: String,
+  #[string] name: String,
+) -> Result<Option<ToJsBuffer>, AnyError>
+where
+  P: FsPermissions + 'static,
+{
+  check_unstable(state, "Deno.getXattrSync");
+  let path = PathBuf::from(path);
+
+  state
+    .borrow_mut::<P>()
+    .check_read(&path, "Deno.getXattrSync()")?;
+
+  let fs = state.borrow::<FileSystemRc>();
+  let value = fs
+    .get_xattr_sync(&path, &name)
+    .context_path("getxattr", &path)?;
+
+  Ok(value.map(Into::into))
+}
+
+#[op2(async)]
+#[serde]
+pub async fn op_fs_get_xattr_async<P>(
+  state: Rc<RefCell<OpState>>,
+  #[string] path: String,
+  #[string] name: String,
+) -> Result<Option<ToJsBuffer>, AnyError>
+where
+  P: FsPermissions + 'static,
+{
+  let path = PathBuf::from(path);
+
+  let fs = {
+    let mut state = state.borrow_mut();
+    check_unstable(&state, "Deno.getXattr");
+    state
+      .borrow_mut::<P>()
+      .check_read(&path, "Deno.getXattr()")?;
+    state.borrow::<FileSystemRc>().clone()
+  };
+
+  let value = fs
+    .get_xattr_async(path.clone(), name)
+    .await
+    .context_path("getxattr", &path)?;
+
+  Ok(value.map(Into::into))
+}
+
+#[op2]
+pub fn op_fs_set_xattr_sync<P>(
+  state: &mut OpState,
+  #[string] path: String,
+  #[string] name: String,
+  #[buffer] value: JsBuffer,
+) -> Result<(), AnyError>
+where
+  P: FsPermissions + 'static,
+{
+  check_unstable(state, "Deno.setXattrSync");
+  let path = PathBuf::from(path);
+
+  state
+    .borrow_mut::<P>()
+    .check_write(&path, "Deno.setXattrSync()")?;
+
+  let fs = state.borrow::<FileSystemRc>();
+  fs.set_xattr_sync(&path, &name, &value)
+    .context_path("setxattr", &path)?;
+
+  Ok(())
+}
+
+#[op2(async)]
+pub async fn op_fs_set_xattr_async<P>(
+  state: Rc<RefCell<OpState>>,
+  #[string] path: String,
+  #[string] name: String,
+  #[buffer] value: JsBuffer,
+) -> Result<(), AnyError>
+where
+  P: FsPermissions + 'static,
+{
+  let path = PathBuf::from(path);
+
+  let fs = {
+    let mut state = state.borrow_mut();
+    check_unstable(&state, "Deno.setXattr");
+    state
+      .borrow_mut::<P>()
+      .check_write(&path, "Deno.setXattr()")?;
+    state.borrow::<FileSystemRc>().clone()
+  };
+
+  fs.set_xattr_async(path.clone(), name, value.to_vec())
+    .await
+    .context_path("setxattr", &path)?;
+
+  Ok(())
+}
+
+#[op2(fast)]
+pub fn op_fs_remove_xattr_sync<P>(
+  state: &mut OpState,
+  #[string] path: &str,
+  #[string] name: &str,
+) -> Result<(), AnyError>
+where
+  P: FsPermissions + 'static,
+{
+  check_unstable(state, "Deno.removeXattrSync");
+  let path = PathBuf::from(path);
+
+  state
+    .borrow_mut::<P>()
+    .check_write(&path, "Deno.removeXattrSync()")?;
+
+  let fs = state.borrow::<FileSystemRc>();
+  fs.remove_xattr_sync(&path, name)
+    .context_path("removexattr", &path)?;
+
+  Ok(())
+}
+
+#[op2(async)]
+pub async fn op_fs_remove_xattr_async<P>(
+  state: Rc<RefCell<OpState>>,
+  #[string] path: String,
+  #[string] name: String,
+) -> Result<(), AnyError>
+where
+  P: FsPermissions + 'static,
+{
+  let path = PathBuf::from(path);
+
+  let fs = {
+    let mut state = state.borrow_mut();
+    check_unstable(&state, "Deno.removeXattr");
+    state
+      .borrow_mut::<P>()
+      .check_write(&path, "Deno.removeXattr()")?;
+    state.borrow::<FileSystemRc>().clone()
+  };
+
+  fs.remove_xattr_async(path.clone(), name)
+    .await
+    .context_path("removexattr", &path)?;
+
+  Ok(())
+}
+
+#[op2]
+#[serde]
+pub fn op_fs_list_xattrs_sync<P>(
+  state: &mut OpState,
+  #[string] path: String,
+) -> Result<Vec<String>, AnyError>
+where
+  P: FsPermissions + 'static,
+{
+  check_unstable(state, "Deno.listXattrsSync");
+  let path = PathBuf::from(path);
+
+  state
+    .borrow_mut::<P>()
+    .check_read(&path, "Deno.listXattrsSync()")?;
+
+  let fs = state.borrow::<FileSystemRc>();
+  let names = fs
+    .list_xattrs_sync(&path)
+    .context_path("listxattr", &path)?;
+
+  Ok(names)
+}
+
+#[op2(async)]
+#[serde]
+pub async fn op_fs_list_xattrs_async<P>(
+  state: Rc<RefCell<OpState>>,
+  #[string] path: String,
+) -> Result<Vec<String>, AnyError>
+where
+  P: FsPermissions + 'static,
+{
+  let path = PathBuf::from(path);
+
+  let fs = {
+    let mut state = state.borrow_mut();
+    check_unstable(&state, "Deno.listXattrs");
+    state
+      .borrow_mut::<P>()
+      .check_read(&path, "Deno.listXattrs()")?;
+    state.borrow::<FileSystemRc>().clone()
+  };
+
+  let names = fs
+    .list_xattrs_async(path.clone())
+    .await
+    .context_path("listxattr", &path)?;
+
+  Ok(names)
+}
+
+#[op2(fast)]
+pub fn op_fs_set_immutable_sync<P>(
+  state: &mut OpState,
+  #[string] path: &str,
+  immutable: bool,
+) -> Result<(), AnyError>
+where
+  P: FsPermissions + 'static,
+{
+  check_unstable(state, "Deno.setImmutableSync");
+  let path = PathBuf::from(path);
+
+  state
+    .borrow_mut::<P>()
+    .check_write(&path, "Deno.setImmutableSync()")?;
+
+  let fs = state.borrow::<FileSystemRc>();
+  fs.set_immutable_sync(&path, immutable)
+    .context_path("setimmutable", &path)?;
+
+  Ok(())
+}
+
+#[op2(async)]
+pub async fn op_fs_set_immutable_async<P>(
+  state: Rc<RefCell<OpState>>,
+  #[string] path: String,
+  immutable: bool,
+) -> Result<(), AnyError>
+where
+  P: FsPermissions + 'static,
+{
+  let path = PathBuf::from(path);
+
+  let fs = {
+    let mut state = state.borrow_mut();
+    check_unstable(&state, "Deno.setImmutable");
+    state
+      .borrow_mut::<P>()
+      .check_write(&path, "Deno.setImmutable()")?;
+    state.borrow::<FileSystemRc>().clone()
+  };
+
+  fs.set_immutable_async(path.clone(), immutable)
+    .await
+    .context_path("setimmutable", &path)?;
+
+  Ok(())
+}
+
 #[op2]
 #[string]
 pub fn op_fs_make_temp_dir_sync<P>(
@@ -919,6 +1178,8 @@ pub fn op_fs_make_temp_dir_sync<P>(
   #[string] dir: Option<String>,
   #[string] prefix: Option<String>,
   #[string] suffix: Option<String>,
+  mode: Option<u32>,
+  cleanup_at_exit: bool,
 ) -> Result<String, AnyError>
 where
   P: FsPermissions + 'static,
@@ -931,8 +1192,8 @@ where
   const MAX_TRIES: u32 = 10;
   for _ in 0..MAX_TRIES {
     let path = tmp_name(&mut rng, &dir, prefix.as_deref(), suffix.as_deref())?;
-    match fs.mkdir_sync(&path, false, 0o700) {
-      Ok(_) => return path_into_string(path.into_os_string()),
+    match fs.mkdir_sync(&path, false, mode.unwrap_or(0o700) & 0o777) {
+      Ok(_) => return tmp_path_into_string(path, cleanup_at_exit),
       Err(FsError::Io(ref e)) if e.kind() == io::ErrorKind::AlreadyExists => {
         continue;
       }
@@ -954,6 +1215,8 @@ pub async fn op_fs_make_temp_dir_async<P>(
   #[string] dir: Option<String>,
   #[string] prefix: Option<String>,
   #[string] suffix: Option<String>,
+  mode: Option<u32>,
+  cleanup_at_exit: bool,
 ) -> Result<String, AnyError>
 where
   P: FsPermissions + 'static,
@@ -965,8 +1228,12 @@ where
   const MAX_TRIES: u32 = 10;
   for _ in 0..MAX_TRIES {
     let path = tmp_name(&mut rng, &dir, prefix.as_deref(), suffix.as_deref())?;
-    match fs.clone().mkdir_async(path.clone(), false, 0o700).await {
-      Ok(_) => return path_into_string(path.into_os_string()),
+    match fs
+      .clone()
+      .mkdir_async(path.clone(), false, mode.unwrap_or(0o700) & 0o777)
+      .await
+    {
+      Ok(_) => return tmp_path_into_string(path, cleanup_at_exit),
       Err(FsError::Io(ref e)) if e.kind() == io::ErrorKind::AlreadyExists => {
         continue;
       }
@@ -988,6 +1255,8 @@ pub fn op_fs_make_temp_file_sync<P>(
   #[string] dir: Option<String>,
   #[string] prefix: Option<String>,
   #[string] suffix: Option<String>,
+  mode: Option<u32>,
+  cleanup_at_exit: bool,
 ) -> Result<String, AnyError>
 where
   P: FsPermissions + 'static,
@@ -998,7 +1267,7 @@ where
   let open_opts = OpenOptions {
     write: true,
     create_new: true,
-    mode: Some(0o600),
+    mode: Some(mode.unwrap_or(0o600) & 0o777),
     ..Default::default()
   };
 
@@ -1007,7 +1276,7 @@ where
   for _ in 0..MAX_TRIES {
     let path = tmp_name(&mut rng, &dir, prefix.as_deref(), suffix.as_deref())?;
     match fs.open_sync(&path, open_opts, None) {
-      Ok(_) => return path_into_string(path.into_os_string()),
+      Ok(_) => return tmp_path_into_string(path, cleanup_at_exit),
       Err(FsError::Io(ref e)) if e.kind() == io::ErrorKind::AlreadyExists => {
         continue;
       }
@@ -1029,6 +1298,8 @@ pub async fn op_fs_make_temp_file_async<P>(
   #[string] dir: Option<String>,
   #[string] prefix: Option<String>,
   #[string] suffix: Option<String>,
+  mode: Option<u32>,
+  cleanup_at_exit: bool,
 ) -> Result<String, AnyError>
 where
   P: FsPermissions + 'static,
@@ -1039,7 +1310,7 @@ where
   let open_opts = OpenOptions {
     write: true,
     create_new: true,
-    mode: Some(0o600),
+    mode: Some(mode.unwrap_or(0o600) & 0o777),
     ..Default::default()
   };
 
@@ -1049,7 +1320,7 @@ where
   for _ in 0..MAX_TRIES {
     let path = tmp_name(&mut rng, &dir, prefix.as_deref(), suffix.as_deref())?;
     match fs.clone().open_async(path.clone(), open_opts, None).await {
-      Ok(_) => return path_into_string(path.into_os_string()),
+      Ok(_) => return tmp_path_into_string(path, cleanup_at_exit),
       Err(FsError::Io(ref e)) if e.kind() == io::ErrorKind::AlreadyExists => {
         continue;
       }
@@ -1063,6 +1334,23 @@ where
   .context("tmpfile")
 }
 
+/// Removal at exit isn't needed anymore for a temporary path that was
+/// already removed.
+#[op2(fast)]
+pub fn op_fs_temp_cleanup_unregister(#[string] path: &str) {
+  temp_cleanup::unregister(Path::new(path));
+}
+
+fn tmp_path_into_string(
+  path: PathBuf,
+  cleanup_at_exit: bool,
+) -> Result<String, AnyError> {
+  if cleanup_at_exit {
+    temp_cleanup::register(path.clone());
+  }
+  path_into_string(path.into_os_string())
+}
+
 fn make_temp_check_sync<P>(
   state: &mut OpState,
   dir: Option<String>,
@@ -1609,6 +1897,68 @@ pub async fn op_fs_ftruncate_async(
   Ok(())
 }
 
+#[op2(fast)]
+pub fn op_fs_fallocate_sync(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  #[number] len: u64,
+) -> Result<(), AnyError> {
+  check_unstable(state, "Deno.FsFile.allocateSync");
+  let file = FileResource::get_file(state, rid)?;
+  file.allocate_sync(len)?;
+  Ok(())
+}
+
+#[op2(async)]
+pub async fn op_fs_fallocate_async(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[number] len: u64,
+) -> Result<(), AnyError> {
+  check_unstable(&state.borrow(), "Deno.FsFile.allocate");
+  let file = FileResource::get_file(&state.borrow(), rid)?;
+  file.allocate_async(len).await?;
+  Ok(())
+}
+
+#[op2]
+#[number]
+pub fn op_fs_fcopy_sync(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  #[smi] dest_rid: ResourceId,
+  #[number] len: Option<u64>,
+) -> Result<u64, AnyError> {
+  check_unstable(state, "Deno.FsFile.copyToSync");
+  let file = FileResource::get_file(state, rid)?;
+  // only files, since waiting for a socket to be writable would block the
+  // thread with no way to cancel it
+  state.resource_table.get::<FileResource>(dest_rid)?;
+  let dest = state.resource_table.get_fd(dest_rid)?;
+  Ok(file.copy_to_sync(dest, len)?)
+}
+
+#[op2(async)]
+#[number]
+pub async fn op_fs_fcopy_async(
+  state: Rc<RefCell<OpState>>,
+  #[smi] rid: ResourceId,
+  #[smi] dest_rid: ResourceId,
+  #[number] len: Option<u64>,
+) -> Result<u64, AnyError> {
+  check_unstable(&state.borrow(), "Deno.FsFile.copyTo");
+  let (file, dest) = {
+    let state = state.borrow();
+    (
+      FileResource::get_file(&state, rid)?,
+      state.resource_table.get_fd(dest_rid)?,
+    )
+  };
+  // the destination is duplicated before the first await, so it can't be
+  // closed in the meantime
+  Ok(file.copy_to_async(dest, len).await?)
+}
+
 #[op2(fast)]
 pub fn op_fs_futime_sync(
   state: &mut OpState,
@@ -1777,6 +2127,9 @@ create_struct_writer! {
     is_char_device: bool,
     is_fifo: bool,
     is_socket: bool,
+    // Not available on all platforms.
+    immutable_set: bool,
+    immutable: bool,
   }
 }
 
@@ -1808,6 +2161,8 @@ impl From<FsStat> for SerializableStat {
       is_char_device: stat.is_char_device,
       is_fifo: stat.is_fifo,
       is_socket: stat.is_socket,
+      immutable_set: stat.is_immutable.is_some(),
+      immutable: stat.is_immutable.unwrap_or(false),
     }
   }
 }
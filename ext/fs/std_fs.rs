@@ -274,6 +274,67 @@ impl FileSystem for RealFs {
     .await?
   }
 
+  fn get_xattr_sync(
+    &self,
+    path: &Path,
+    name: &str,
+  ) -> FsResult<Option<Vec<u8>>> {
+    xattr::get(path, name)
+  }
+  async fn get_xattr_async(
+    &self,
+    path: PathBuf,
+    name: String,
+  ) -> FsResult<Option<Vec<u8>>> {
+    spawn_blocking(move || xattr::get(&path, &name)).await?
+  }
+
+  fn set_xattr_sync(
+    &self,
+    path: &Path,
+    name: &str,
+    value: &[u8],
+  ) -> FsResult<()> {
+    xattr::set(path, name, value)
+  }
+  async fn set_xattr_async(
+    &self,
+    path: PathBuf,
+    name: String,
+    value: Vec<u8>,
+  ) -> FsResult<()> {
+    spawn_blocking(move || xattr::set(&path, &name, &value)).await?
+  }
+
+  fn remove_xattr_sync(&self, path: &Path, name: &str) -> FsResult<()> {
+    xattr::remove(path, name)
+  }
+  async fn remove_xattr_async(
+    &self,
+    path: PathBuf,
+    name: String,
+  ) -> FsResult<()> {
+    spawn_blocking(move || xattr::remove(&path, &name)).await?
+  }
+
+  fn list_xattrs_sync(&self, path: &Path) -> FsResult<Vec<String>> {
+    xattr::list(path)
+  }
+  async fn list_xattrs_async(&self, path: PathBuf) -> FsResult<Vec<String>> {
+    spawn_blocking(move || xattr::list(&path)).await?
+  }
+
+  fn set_immutable_sync(&self, path: &Path, immutable: bool) -> FsResult<()> {
+    set_immutable(path, immutable)
+  }
+  async fn set_immutable_async(
+    &self,
+    path: PathBuf,
+    immutable: bool,
+  ) -> FsResult<()> {
+    spawn_blocking(move || set_immutable(&path, immutable)).await?
+  }
+
   fn write_file_sync(
     &self,
     path: &Path,
@@ -669,6 +730,10 @@ fn cp(from: &Path, to: &Path) -> FsResult<()> {
 
 #[cfg(not(windows))]
 fn stat(path: &Path) -> FsResult<FsStat> {
+  #[cfg(target_os = "linux")]
+  if let Some(fsstat) = statx(path, 0)? {
+    return Ok(fsstat);
+  }
   let metadata = fs::metadata(path)?;
   Ok(FsStat::from_std(metadata))
 }
@@ -685,10 +750,270 @@ fn stat(path: &Path) -> FsResult<FsStat> {
 
 #[cfg(not(windows))]
 fn lstat(path: &Path) -> FsResult<FsStat> {
+  #[cfg(target_os = "linux")]
+  if let Some(fsstat) = statx(path, libc::AT_SYMLINK_NOFOLLOW)? {
+    return Ok(fsstat);
+  }
   let metadata = fs::symlink_metadata(path)?;
   Ok(FsStat::from_std(metadata))
 }
 
+/// Stats the path with a single `statx` call, which also reads the immutable
+/// flag that std doesn't expose.
+#[cfg(target_os = "linux")]
+fn statx(path: &Path, flags: libc::c_int) -> FsResult<Option<FsStat>> {
+  let path = path_to_cstring(path)?;
+  Ok(FsStat::from_statx(libc::AT_FDCWD, &path, flags)?)
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn path_to_cstring(path: &Path) -> io::Result<std::ffi::CString> {
+  use std::os::unix::ffi::OsStrExt;
+  std::ffi::CString::new(path.as_os_str().as_bytes())
+    .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+mod xattr {
+  use std::ffi::CString;
+  use std::io;
+  use std::path::Path;
+
+  use deno_io::fs::FsResult;
+  use libc::c_char;
+  use libc::c_void;
+
+  use super::path_to_cstring;
+
+  #[cfg(target_os = "linux")]
+  const ENOATTR: i32 = libc::ENODATA;
+  #[cfg(target_os = "macos")]
+  const ENOATTR: i32 = libc::ENOATTR;
+
+  fn name_to_cstring(name: &str) -> io::Result<CString> {
+    CString::new(name)
+      .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))
+  }
+
+  // The macOS functions take an additional position and options.
+  unsafe fn getxattr(
+    path: *const c_char,
+    name: *const c_char,
+    value: *mut c_void,
+    size: usize,
+  ) -> isize {
+    #[cfg(target_os = "linux")]
+    return libc::getxattr(path, name, value, size);
+    #[cfg(target_os = "macos")]
+    return libc::getxattr(path, name, value, size, 0, 0);
+  }
+
+  unsafe fn listxattr(
+    path: *const c_char,
+    list: *mut c_char,
+    size: usize,
+  ) -> isize {
+    #[cfg(target_os = "linux")]
+    return libc::listxattr(path, list, size);
+    #[cfg(target_os = "macos")]
+    return libc::listxattr(path, list, size, 0);
+  }
+
+  pub fn get(path: &Path, name: &str) -> FsResult<Option<Vec<u8>>> {
+    let path = path_to_cstring(path)?;
+    let name = name_to_cstring(name)?;
+    loop {
+      // SAFETY: the strings are valid, and a null buffer queries the size.
+      let size = unsafe {
+        getxattr(path.as_ptr(), name.as_ptr(), std::ptr::null_mut(), 0)
+      };
+      if size < 0 {
+        let err = io::Error::last_os_error();
+        return match err.raw_os_error() {
+          Some(ENOATTR) => Ok(None),
+          _ => Err(err.into()),
+        };
+      }
+      let mut value = vec![0u8; size as usize];
+      // SAFETY: `value` is valid for writes of its length.
+      let size = unsafe {
+        getxattr(
+          path.as_ptr(),
+          name.as_ptr(),
+          value.as_mut_ptr() as *mut c_void,
+          value.len(),
+        )
+      };
+      if size < 0 {
+        let err = io::Error::last_os_error();
+        match err.raw_os_error() {
+          // the value grew since its size was queried
+          Some(libc::ERANGE) => continue,
+          Some(ENOATTR) => return Ok(None),
+          _ => return Err(err.into()),
+        }
+      }
+      value.truncate(size as usize);
+      return Ok(Some(value));
+    }
+  }
+
+  pub fn set(path: &Path, name: &str, value: &[u8]) -> FsResult<()> {
+    let path = path_to_cstring(path)?;
+    let name = name_to_cstring(name)?;
+    let value_ptr = value.as_ptr() as *const c_void;
+    // SAFETY: the strings are valid, and `value` is valid for reads of its
+    // length.
+    #[cfg(target_os = "linux")]
+    let result = unsafe {
+      libc::setxattr(path.as_ptr(), name.as_ptr(), value_ptr, value.len(), 0)
+    };
+    // SAFETY: see above.
+    #[cfg(target_os = "macos")]
+    let result = unsafe {
+      libc::setxattr(path.as_ptr(), name.as_ptr(), value_ptr, value.len(), 0, 0)
+    };
+    if result != 0 {
+      return Err(io::Error::last_os_error().into());
+    }
+    Ok(())
+  }
+
+  pub fn remove(path: &Path, name: &str) -> FsResult<()> {
+    let path = path_to_cstring(path)?;
+    let name = name_to_cstring(name)?;
+    // SAFETY: the strings are valid.
+    #[cfg(target_os = "linux")]
+    let result = unsafe { libc::removexattr(path.as_ptr(), name.as_ptr()) };
+    // SAFETY: see above.
+    #[cfg(target_os = "macos")]
+    let result = unsafe { libc::removexattr(path.as_ptr(), name.as_ptr(), 0) };
+    if result != 0 {
+      return Err(io::Error::last_os_error().into());
+    }
+    Ok(())
+  }
+
+  pub fn list(path: &Path) -> FsResult<Vec<String>> {
+    let path = path_to_cstring(path)?;
+    loop {
+      // SAFETY: the path is valid, and a null buffer queries the size.
+      let size = unsafe { listxattr(path.as_ptr(), std::ptr::null_mut(), 0) };
+      if size < 0 {
+        return Err(io::Error::last_os_error().into());
+      }
+      let mut list = vec![0u8; size as usize];
+      // SAFETY: `list` is valid for writes of its length.
+      let size = unsafe {
+        listxattr(path.as_ptr(), list.as_mut_ptr() as *mut c_char, list.len())
+      };
+      if size < 0 {
+        let err = io::Error::last_os_error();
+        if err.raw_os_error() == Some(libc::ERANGE) {
+          continue;
+        }
+        return Err(err.into());
+      }
+      list.truncate(size as usize);
+      // the names are separated by null bytes
+      return Ok(
+        list
+          .split(|b| *b == 0)
+          .filter(|name| !name.is_empty())
+          .map(|name| String::from_utf8_lossy(name).into_owned())
+          .collect(),
+      );
+    }
+  }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+mod xattr {
+  use std::path::Path;
+
+  use deno_io::fs::FsError;
+  use deno_io::fs::FsResult;
+
+  pub fn get(_path: &Path, _name: &str) -> FsResult<Option<Vec<u8>>> {
+    Err(FsError::NotSupported)
+  }
+
+  pub fn set(_path: &Path, _name: &str, _value: &[u8]) -> FsResult<()> {
+    Err(FsError::NotSupported)
+  }
+
+  pub fn remove(_path: &Path, _name: &str) -> FsResult<()> {
+    Err(FsError::NotSupported)
+  }
+
+  pub fn list(_path: &Path) -> FsResult<Vec<String>> {
+    Err(FsError::NotSupported)
+  }
+}
+
+#[cfg(target_os = "linux")]
+fn set_immutable(path: &Path, immutable: bool) -> FsResult<()> {
+  use std::os::unix::fs::OpenOptionsExt;
+  use std::os::unix::io::AsRawFd;
+
+  const FS_IMMUTABLE_FL: libc::c_int = 0x10;
+  // The kernel reads and writes an int, although the request codes are
+  // defined with the size of a long.
+  nix::ioctl_read_bad!(
+    fs_ioc_getflags,
+    nix::request_code_read!(b'f', 1, std::mem::size_of::<libc::c_long>()),
+    libc::c_int
+  );
+  nix::ioctl_write_ptr_bad!(
+    fs_ioc_setflags,
+    nix::request_code_write!(b'f', 2, std::mem::size_of::<libc::c_long>()),
+    libc::c_int
+  );
+
+  let file = fs::OpenOptions::new()
+    .read(true)
+    .custom_flags(libc::O_NONBLOCK)
+    .open(path)?;
+  let mut flags = 0;
+  // SAFETY: the fd is open, and `flags` is a valid int.
+  unsafe { fs_ioc_getflags(file.as_raw_fd(), &mut flags) }
+    .map_err(io::Error::from)?;
+  if immutable {
+    flags |= FS_IMMUTABLE_FL;
+  } else {
+    flags &= !FS_IMMUTABLE_FL;
+  }
+  // SAFETY: see above.
+  unsafe { fs_ioc_setflags(file.as_raw_fd(), &flags) }
+    .map_err(io::Error::from)?;
+  Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn set_immutable(path: &Path, immutable: bool) -> FsResult<()> {
+  use std::os::macos::fs::MetadataExt;
+
+  const UF_IMMUTABLE: u32 = 0x2;
+
+  let flags = fs::metadata(path)?.st_flags();
+  let flags = if immutable {
+    flags | UF_IMMUTABLE
+  } else {
+    flags & !UF_IMMUTABLE
+  };
+  let path = path_to_cstring(path)?;
+  // SAFETY: the path is a valid C string.
+  if unsafe { libc::chflags(path.as_ptr(), flags as _) } != 0 {
+    return Err(io::Error::last_os_error().into());
+  }
+  Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn set_immutable(_path: &Path, _immutable: bool) -> FsResult<()> {
+  Err(FsError::NotSupported)
+}
+
 #[cfg(windows)]
 fn lstat(path: &Path) -> FsResult<FsStat> {
   use winapi::um::winbase::FILE_FLAG_BACKUP_SEMANTICS;
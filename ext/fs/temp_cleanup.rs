@@ -0,0 +1,153 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+//! Removal of the temporary files and directories created with the
+//! `cleanupAtExit` option when the process exits.
+//!
+//! Ctrl+C terminates the process without running the exit handlers, so a
+//! native SIGINT / ctrl-c handler removes the paths before terminating the
+//! process, unless the program listens for SIGINT itself. Embedders with
+//! their own ctrl-c handling, like the REPL and the test runner of the CLI,
+//! hold a [`SigintListener`] for as long as they handle it.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::sync::Mutex;
+use std::sync::Once;
+
+static TEMP_PATHS: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// Whether the SIGINT / ctrl-c handler removing the paths is installed.
+static SIGINT_HANDLER_INSTALLED: AtomicBool = AtomicBool::new(false);
+
+/// The number of SIGINT listeners of the program, over all its workers.
+static SIGINT_LISTENERS: AtomicUsize = AtomicUsize::new(0);
+
+/// Removes `path` when the process exits, including through
+/// `std::process::exit()` and Ctrl+C.
+pub fn register(path: PathBuf) {
+  static REGISTER_ATEXIT: Once = Once::new();
+  REGISTER_ATEXIT.call_once(|| {
+    // SAFETY: `remove_all_at_exit` is a valid `extern "C"` function which
+    // doesn't unwind.
+    unsafe {
+      libc::atexit(remove_all_at_exit);
+    }
+    match install_sigint_handler() {
+      Ok(()) => SIGINT_HANDLER_INSTALLED.store(true, Ordering::Release),
+      Err(err) => {
+        log::debug!("Failed to install the SIGINT handler removing temporary paths: {err}")
+      }
+    }
+  });
+  TEMP_PATHS.lock().unwrap().push(absolute(path));
+}
+
+/// Whether the default action of SIGINT is taken over by the handler removing
+/// the paths. Only reads an atomic, so it can be called from a signal handler.
+pub fn handles_sigint() -> bool {
+  SIGINT_HANDLER_INSTALLED.load(Ordering::Acquire)
+}
+
+/// Keeps the process from being terminated on SIGINT by the handler removing
+/// the paths, for as long as it is alive.
+pub struct SigintListener(());
+
+impl SigintListener {
+  pub fn new() -> Self {
+    SIGINT_LISTENERS.fetch_add(1, Ordering::AcqRel);
+    Self(())
+  }
+}
+
+impl Drop for SigintListener {
+  fn drop(&mut self) {
+    SIGINT_LISTENERS.fetch_sub(1, Ordering::AcqRel);
+  }
+}
+
+fn has_sigint_listeners() -> bool {
+  SIGINT_LISTENERS.load(Ordering::Acquire) > 0
+}
+
+/// The paths are removed from a separate thread, woken up through a pipe by
+/// the signal handler, since removing them isn't async-signal-safe.
+#[cfg(unix)]
+fn install_sigint_handler() -> std::io::Result<()> {
+  use signal_hook::consts::SIGINT;
+  use signal_hook::iterator::Signals;
+
+  let mut signals = Signals::new([SIGINT])?;
+  std::thread::Builder::new()
+    .name("temp-cleanup-sigint".to_string())
+    .spawn(move || {
+      for _ in signals.forever() {
+        if has_sigint_listeners() {
+          continue;
+        }
+        remove_all_at_exit();
+        // terminates the process the way the default action would
+        let _ = signal_hook::low_level::emulate_default_handler(SIGINT);
+      }
+    })?;
+  Ok(())
+}
+
+/// Windows calls the ctrl handlers on a new thread, so the paths can be
+/// removed from the handler itself.
+#[cfg(windows)]
+fn install_sigint_handler() -> std::io::Result<()> {
+  use winapi::shared::minwindef::BOOL;
+  use winapi::shared::minwindef::DWORD;
+  use winapi::shared::minwindef::FALSE;
+  use winapi::shared::minwindef::TRUE;
+  use winapi::um::consoleapi::SetConsoleCtrlHandler;
+  use winapi::um::wincon::CTRL_C_EVENT;
+
+  unsafe extern "system" fn handler(ctrl_type: DWORD) -> BOOL {
+    if ctrl_type == CTRL_C_EVENT && !has_sigint_listeners() {
+      remove_all_at_exit();
+    }
+    // pass the event on to the next handler, which is the default one that
+    // terminates the process unless the program listens for ctrl-c
+    FALSE
+  }
+
+  // SAFETY: `handler` is a valid handler routine which doesn't unwind.
+  if unsafe { SetConsoleCtrlHandler(Some(handler), TRUE) } == 0 {
+    return Err(std::io::Error::last_os_error());
+  }
+  Ok(())
+}
+
+/// Stops tracking `path`, once it was removed.
+pub fn unregister(path: &Path) {
+  let path = absolute(path.to_path_buf());
+  TEMP_PATHS.lock().unwrap().retain(|p| *p != path);
+}
+
+/// The current directory may have changed by the time the process exits.
+fn absolute(path: PathBuf) -> PathBuf {
+  if path.is_absolute() {
+    return path;
+  }
+  match std::env::current_dir() {
+    Ok(cwd) => cwd.join(path),
+    Err(_) => path,
+  }
+}
+
+extern "C" fn remove_all_at_exit() {
+  let Ok(mut paths) = TEMP_PATHS.lock() else {
+    return;
+  };
+  for path in paths.drain(..) {
+    let _ = if path.is_dir() {
+      std::fs::remove_dir_all(&path)
+    } else {
+      std::fs::remove_file(&path)
+    };
+  }
+}
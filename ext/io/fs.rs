@@ -109,6 +109,8 @@ pub struct FsStat {
   pub is_char_device: bool,
   pub is_fifo: bool,
   pub is_socket: bool,
+  /// Whether the immutable flag is set, if the platform has one.
+  pub is_immutable: Option<bool>,
 }
 
 impl FsStat {
@@ -177,8 +179,106 @@ impl FsStat {
       is_char_device: unix_or_false!(is_char_device),
       is_fifo: unix_or_false!(is_fifo),
       is_socket: unix_or_false!(is_socket),
+      is_immutable: immutable_flag(&metadata),
     }
   }
+
+  /// Reads the stat of `path`, relative to the directory `dirfd`, with a
+  /// single `statx` call, which unlike the std metadata includes the
+  /// immutable flag. Returns `None` where `statx` isn't available.
+  #[cfg(target_os = "linux")]
+  pub fn from_statx(
+    dirfd: std::os::fd::RawFd,
+    path: &std::ffi::CStr,
+    flags: libc::c_int,
+  ) -> io::Result<Option<Self>> {
+    const STATX_ATTR_IMMUTABLE: u64 = 0x10;
+
+    fn to_msec(time: libc::statx_timestamp) -> u64 {
+      // like the std metadata, times before the epoch are made positive
+      let msec =
+        time.tv_sec as i128 * 1000 + (time.tv_nsec / 1_000_000) as i128;
+      msec.unsigned_abs() as u64
+    }
+
+    fn makedev(major: u32, minor: u32) -> u64 {
+      let (major, minor) = (major as u64, minor as u64);
+      ((major & 0xffff_f000) << 32)
+        | ((major & 0xfff) << 8)
+        | ((minor & 0xffff_ff00) << 12)
+        | (minor & 0xff)
+    }
+
+    // SAFETY: all zeroes is a valid `statx` struct.
+    let mut buf: libc::statx = unsafe { std::mem::zeroed() };
+    // SAFETY: `path` is a valid C string and `buf` is a valid `statx` struct.
+    // The raw syscall is used as older glibc versions don't have a wrapper.
+    let result = unsafe {
+      libc::syscall(
+        libc::SYS_statx,
+        dirfd,
+        path.as_ptr(),
+        flags,
+        libc::STATX_BASIC_STATS | libc::STATX_BTIME,
+        &mut buf as *mut libc::statx,
+      )
+    };
+    if result != 0 {
+      let err = io::Error::last_os_error();
+      // not supported by the kernel or blocked by a seccomp filter
+      return match err.raw_os_error() {
+        Some(libc::ENOSYS | libc::EPERM) => Ok(None),
+        _ => Err(err),
+      };
+    }
+
+    let mode = buf.stx_mode as u32;
+    let file_type = mode & libc::S_IFMT;
+    Ok(Some(Self {
+      is_file: file_type == libc::S_IFREG,
+      is_directory: file_type == libc::S_IFDIR,
+      is_symlink: file_type == libc::S_IFLNK,
+      size: buf.stx_size,
+
+      mtime: Some(to_msec(buf.stx_mtime)),
+      atime: Some(to_msec(buf.stx_atime)),
+      birthtime: (buf.stx_mask & libc::STATX_BTIME != 0)
+        .then(|| to_msec(buf.stx_btime)),
+
+      dev: makedev(buf.stx_dev_major, buf.stx_dev_minor),
+      ino: buf.stx_ino,
+      mode,
+      nlink: buf.stx_nlink as u64,
+      uid: buf.stx_uid,
+      gid: buf.stx_gid,
+      rdev: makedev(buf.stx_rdev_major, buf.stx_rdev_minor),
+      blksize: buf.stx_blksize as u64,
+      blocks: buf.stx_blocks,
+      is_block_device: file_type == libc::S_IFBLK,
+      is_char_device: file_type == libc::S_IFCHR,
+      is_fifo: file_type == libc::S_IFIFO,
+      is_socket: file_type == libc::S_IFSOCK,
+      is_immutable: (buf.stx_attributes_mask & STATX_ATTR_IMMUTABLE != 0)
+        .then_some(buf.stx_attributes & STATX_ATTR_IMMUTABLE != 0),
+    }))
+  }
+}
+
+/// On Linux, the flag isn't part of the std metadata, so it is read with
+/// [`FsStat::from_statx`] instead.
+#[allow(unused_variables)]
+fn immutable_flag(metadata: &std::fs::Metadata) -> Option<bool> {
+  #[cfg(target_os = "macos")]
+  {
+    use std::os::macos::fs::MetadataExt;
+    const UF_IMMUTABLE: u32 = 0x2;
+    const SF_IMMUTABLE: u32 = 0x20000;
+    Some(metadata.st_flags() & (UF_IMMUTABLE | SF_IMMUTABLE) != 0)
+  }
+  #[cfg(not(target_os = "macos"))]
+  {
+    None
+  }
 }
 
 #[async_trait::async_trait(?Send)]
@@ -231,6 +331,25 @@ pub trait File {
   fn truncate_sync(self: Rc<Self>, len: u64) -> FsResult<()>;
   async fn truncate_async(self: Rc<Self>, len: u64) -> FsResult<()>;
 
+  /// Preallocates disk space so that the file is at least `len` bytes.
+  fn allocate_sync(self: Rc<Self>, len: u64) -> FsResult<()>;
+  async fn allocate_async(self: Rc<Self>, len: u64) -> FsResult<()>;
+
+  /// Copies up to `len` bytes (or until the end of the file) from the
+  /// current position of this file to the current position of `dest`,
+  /// returning the number of bytes copied. `dest` is the handle of a file
+  /// or a socket.
+  fn copy_to_sync(
+    self: Rc<Self>,
+    dest: ResourceHandleFd,
+    len: Option<u64>,
+  ) -> FsResult<u64>;
+  async fn copy_to_async(
+    self: Rc<Self>,
+    dest: ResourceHandleFd,
+    len: Option<u64>,
+  ) -> FsResult<u64>;
+
   fn utime_sync(
     self: Rc<Self>,
     atime_secs: i64,
@@ -648,13 +648,11 @@ impl crate::fs::File for StdFileResourceInner {
   }
 
   fn stat_sync(self: Rc<Self>) -> FsResult<FsStat> {
-    self.with_sync(|file| Ok(file.metadata().map(FsStat::from_std)?))
+    self.with_sync(|file| Ok(file_stat(file)?))
   }
   async fn stat_async(self: Rc<Self>) -> FsResult<FsStat> {
     self
-      .with_inner_blocking_task(|file| {
-        Ok(file.metadata().map(FsStat::from_std)?)
-      })
+      .with_inner_blocking_task(|file| Ok(file_stat(file)?))
       .await
   }
 
@@ -699,6 +697,36 @@ impl crate::fs::File for StdFileResourceInner {
       .await
   }
 
+  fn allocate_sync(self: Rc<Self>, len: u64) -> FsResult<()> {
+    self.with_sync(|file| Ok(file.allocate(len)?))
+  }
+  async fn allocate_async(self: Rc<Self>, len: u64) -> FsResult<()> {
+    self
+      .with_inner_blocking_task(move |file| Ok(file.allocate(len)?))
+      .await
+  }
+
+  fn copy_to_sync(
+    self: Rc<Self>,
+    dest: ResourceHandleFd,
+    len: Option<u64>,
+  ) -> FsResult<u64> {
+    let mut dest = clone_backing_file(dest)?;
+    self.with_sync(|file| Ok(copy_file_to(file, &mut dest, len)?))
+  }
+  async fn copy_to_async(
+    self: Rc<Self>,
+    dest: ResourceHandleFd,
+    len: Option<u64>,
+  ) -> FsResult<u64> {
+    let mut dest = clone_backing_file(dest)?;
+    self
+      .with_inner_blocking_task(move |file| {
+        Ok(copy_file_to(file, &mut dest, len)?)
+      })
+      .await
+  }
+
   fn utime_sync(
     self: Rc<Self>,
     atime_secs: i64,
@@ -772,6 +800,152 @@ impl crate::fs::File for StdFileResourceInner {
   }
 }
 
+/// Stats the open file, including the immutable flag on Linux.
+fn file_stat(file: &StdFile) -> io::Result<FsStat> {
+  #[cfg(target_os = "linux")]
+  {
+    use std::os::fd::AsRawFd;
+    if let Some(stat) =
+      FsStat::from_statx(file.as_raw_fd(), c"", libc::AT_EMPTY_PATH)?
+    {
+      return Ok(stat);
+    }
+  }
+  file.metadata().map(FsStat::from_std)
+}
+
+/// Duplicates the OS handle of a file or socket resource, so it can be used
+/// outside of the resource.
+fn clone_backing_file(handle: ResourceHandleFd) -> FsResult<StdFile> {
+  // SAFETY: the handle belongs to an open resource, and the `ManuallyDrop`
+  // keeps it from being closed here.
+  #[cfg(unix)]
+  let file = unsafe { StdFile::from_raw_fd(handle) };
+  // SAFETY: see above.
+  #[cfg(windows)]
+  let file = unsafe { StdFile::from_raw_handle(handle) };
+  let file = std::mem::ManuallyDrop::new(file);
+  Ok(file.try_clone()?)
+}
+
+/// Copies between the files in the kernel with `copy_file_range` or
+/// `sendfile` where possible, and through a buffer otherwise. The
+/// destination may be a non-blocking socket, in which case the copy waits
+/// for it to become writable.
+fn copy_file_to(
+  src: &mut StdFile,
+  dest: &mut StdFile,
+  len: Option<u64>,
+) -> io::Result<u64> {
+  let len = len.unwrap_or(u64::MAX);
+  #[cfg(any(target_os = "android", target_os = "linux"))]
+  {
+    use std::os::unix::io::AsRawFd;
+
+    const CHUNK_SIZE: u64 = 1 << 30;
+
+    let mut copied = 0;
+    let mut use_sendfile = false;
+    while copied < len {
+      let chunk = (len - copied).min(CHUNK_SIZE) as usize;
+      // SAFETY: both fds are open, and passing null offsets uses and
+      // advances the file positions.
+      let n = unsafe {
+        if use_sendfile {
+          libc::sendfile(
+            dest.as_raw_fd(),
+            src.as_raw_fd(),
+            std::ptr::null_mut(),
+            chunk,
+          )
+        } else {
+          libc::copy_file_range(
+            src.as_raw_fd(),
+            std::ptr::null_mut(),
+            dest.as_raw_fd(),
+            std::ptr::null_mut(),
+            chunk,
+            0,
+          )
+        }
+      };
+      if n < 0 {
+        let err = io::Error::last_os_error();
+        match err.raw_os_error() {
+          Some(libc::EINTR) => continue,
+          Some(libc::EAGAIN) => wait_writable(dest)?,
+          // copy_file_range is unsupported for these files, e.g. across
+          // file systems on older kernels or to a pipe or socket
+          Some(
+            libc::EXDEV | libc::ENOSYS | libc::EINVAL | libc::EOPNOTSUPP,
+          ) if !use_sendfile && copied == 0 => use_sendfile = true,
+          Some(libc::EINVAL | libc::ENOSYS) if copied == 0 => break,
+          _ => return Err(err),
+        }
+        continue;
+      }
+      if n == 0 {
+        return Ok(copied);
+      }
+      copied += n as u64;
+    }
+    if copied > 0 {
+      return Ok(copied);
+    }
+  }
+  let mut src = Read::take(&mut *src, len);
+  let mut buf = vec![0; 64 * 1024];
+  let mut copied = 0;
+  loop {
+    let n = match src.read(&mut buf) {
+      Ok(0) => return Ok(copied),
+      Ok(n) => n,
+      Err(err) if err.kind() == ErrorKind::Interrupted => continue,
+      Err(err) => return Err(err),
+    };
+    let mut written = 0;
+    while written < n {
+      match dest.write(&buf[written..n]) {
+        Ok(0) => return Err(ErrorKind::WriteZero.into()),
+        Ok(nwritten) => written += nwritten,
+        Err(err) if err.kind() == ErrorKind::Interrupted => {}
+        Err(err) if err.kind() == ErrorKind::WouldBlock => wait_writable(dest)?,
+        Err(err) => return Err(err),
+      }
+    }
+    copied += n as u64;
+  }
+}
+
+/// Waits for a non-blocking destination, like a socket, to be writable.
+#[cfg(unix)]
+fn wait_writable(file: &StdFile) -> io::Result<()> {
+  use std::os::unix::io::AsRawFd;
+
+  let mut pollfd = libc::pollfd {
+    fd: file.as_raw_fd(),
+    events: libc::POLLOUT,
+    revents: 0,
+  };
+  loop {
+    // SAFETY: `pollfd` points to a single valid pollfd.
+    if unsafe { libc::poll(&mut pollfd, 1, -1) } >= 0 {
+      return Ok(());
+    }
+    let err = io::Error::last_os_error();
+    if err.kind() != ErrorKind::Interrupted {
+      return Err(err);
+    }
+  }
+}
+
+/// Sockets have no backing handle on Windows, so the destination is always
+/// a blocking file.
+#[cfg(windows)]
+fn wait_writable(_file: &StdFile) -> io::Result<()> {
+  Err(ErrorKind::WouldBlock.into())
+}
+
 // override op_print to use the stdout and stderr in the resource table
 #[op2(fast)]
 pub fn op_print(
@@ -26,6 +26,7 @@ import {
   op_net_set_multi_loopback_udp,
   op_net_set_multi_ttl_udp,
   op_set_keepalive,
+  op_set_keepalive_with_options,
   op_set_nodelay,
 } from "ext:core/ops";
 const {
@@ -113,6 +114,7 @@ class Conn {
     this.#rid = rid;
     this.#remoteAddr = remoteAddr;
     this.#localAddr = localAddr;
+    internals.traceResourceCreation?.(rid);
   }
 
   get rid() {
@@ -232,8 +234,11 @@ class TcpConn extends Conn {
     return op_set_nodelay(this.#rid, noDelay);
   }
 
-  setKeepAlive(keepAlive = true) {
-    return op_set_keepalive(this.#rid, keepAlive);
+  setKeepAlive(keepAlive = true, options = undefined) {
+    if (options === undefined) {
+      return op_set_keepalive(this.#rid, keepAlive);
+    }
+    return op_set_keepalive_with_options(this.#rid, keepAlive, options);
   }
 }
 
@@ -278,6 +283,7 @@ class Listener {
     });
     this.#rid = rid;
     this.#addr = addr;
+    internals.traceResourceCreation?.(rid);
   }
 
   get rid() {
@@ -380,6 +386,7 @@ class DatagramConn {
 
   constructor(rid, addr, bufSize = 1024) {
     this.#rid = rid;
+    internals.traceResourceCreation?.(rid);
     this.#addr = addr;
     this.bufSize = bufSize;
   }
@@ -532,7 +539,7 @@ function listen(args) {
       const { 0: rid, 1: addr } = op_net_listen_tcp({
         hostname: args.hostname ?? "0.0.0.0",
         port: Number(args.port),
-      }, args.reusePort);
+      }, args.reusePort, args.backlog);
       addr.transport = "tcp";
       return new Listener(rid, addr);
     }
@@ -561,8 +568,13 @@ function createListenDatagram(udpOpFn, unixOpFn) {
             hostname: args.hostname ?? "127.0.0.1",
             port: args.port,
           },
-          args.reuseAddress ?? false,
-          args.loopback ?? false,
+          {
+            reuseAddress: args.reuseAddress ?? false,
+            reusePort: args.reusePort ?? false,
+            loopback: args.loopback ?? false,
+            broadcast: args.broadcast ?? true,
+            ttl: args.ttl,
+          },
         );
         addr.transport = "udp";
         return new DatagramConn(rid, addr);
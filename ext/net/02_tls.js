@@ -238,6 +238,8 @@ function listenTls({
   transport = "tcp",
   alpnProtocols = undefined,
   reusePort = false,
+  backlog = undefined,
+  clientCaCerts = undefined,
 }) {
   if (transport !== "tcp") {
     throw new TypeError(`Unsupported transport: '${transport}'`);
@@ -251,7 +253,7 @@ function listenTls({
   const keyPair = loadTlsKeyPair("Deno.listenTls", arguments[0]);
   const { 0: rid, 1: localAddr } = op_net_listen_tls(
     { hostname, port: Number(port) },
-    { alpnProtocols, reusePort },
+    { alpnProtocols, reusePort, backlog, clientCaCerts },
     keyPair,
   );
   return new TlsListener(rid, localAddr);
@@ -264,14 +266,18 @@ async function startTls(
     hostname = "127.0.0.1",
     caCerts = [],
     alpnProtocols = undefined,
+    keyFormat = undefined,
+    cert = undefined,
+    key = undefined,
   } = {},
 ) {
+  const keyPair = loadTlsKeyPair("Deno.startTls", { keyFormat, cert, key });
   const { 0: rid, 1: localAddr, 2: remoteAddr } = op_tls_start({
     rid: conn[internalRidSymbol],
     hostname,
     caCerts,
     alpnProtocols,
-  });
+  }, keyPair);
   return new TlsConn(rid, remoteAddr, localAddr);
 }
 
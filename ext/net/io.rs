@@ -1,6 +1,8 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use crate::ops::KeepAliveOptions;
 use deno_core::error::generic_error;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::AsyncMutFuture;
 use deno_core::AsyncRefCell;
@@ -9,9 +11,12 @@ use deno_core::CancelHandle;
 use deno_core::CancelTryFuture;
 use deno_core::RcRef;
 use deno_core::Resource;
+#[cfg(unix)]
+use deno_core::ResourceHandleFd;
 use socket2::SockRef;
 use std::borrow::Cow;
 use std::rc::Rc;
+use std::time::Duration;
 use tokio::io::AsyncRead;
 use tokio::io::AsyncReadExt;
 use tokio::io::AsyncWrite;
@@ -106,6 +111,13 @@ impl Resource for TcpStreamResource {
   fn close(self: Rc<Self>) {
     self.cancel_read_ops();
   }
+
+  #[cfg(unix)]
+  fn backing_fd(self: Rc<Self>) -> Option<ResourceHandleFd> {
+    use std::os::unix::io::AsRawFd;
+    let wr = RcRef::map(self, |r| &r.wr).try_borrow()?;
+    Some(wr.as_ref().as_ref().as_raw_fd())
+  }
 }
 
 impl TcpStreamResource {
@@ -116,9 +128,35 @@ impl TcpStreamResource {
   pub fn set_keepalive(
     self: Rc<Self>,
     keepalive: bool,
+    options: KeepAliveOptions,
   ) -> Result<(), AnyError> {
-    self
-      .map_socket(Box::new(move |socket| Ok(socket.set_keepalive(keepalive)?)))
+    let time = options
+      .time
+      .map(|time| keepalive_duration("time", time))
+      .transpose()?;
+    let interval = options
+      .interval
+      .map(|interval| keepalive_duration("interval", interval))
+      .transpose()?;
+    self.map_socket(Box::new(move |socket| {
+      if !keepalive || (time.is_none() && interval.is_none()) {
+        return Ok(socket.set_keepalive(keepalive)?);
+      }
+      let mut params = socket2::TcpKeepalive::new();
+      if let Some(time) = time {
+        params = params.with_time(time);
+      }
+      #[cfg(any(
+        target_os = "android",
+        target_os = "linux",
+        target_os = "macos",
+        windows
+      ))]
+      if let Some(interval) = interval {
+        params = params.with_interval(interval);
+      }
+      Ok(socket.set_tcp_keepalive(&params)?)
+    }))
   }
 
   #[allow(clippy::type_complexity)]
@@ -137,6 +175,20 @@ impl TcpStreamResource {
   }
 }
 
+/// Most platforms only support setting the keep-alive timing in whole
+/// seconds, so the milliseconds are rounded up rather than truncated to zero.
+pub(crate) fn keepalive_duration(
+  name: &str,
+  ms: u64,
+) -> Result<Duration, AnyError> {
+  if ms == 0 {
+    return Err(type_error(format!(
+      "Keep-alive {name} must be greater than 0"
+    )));
+  }
+  Ok(Duration::from_secs(ms.div_ceil(1000)))
+}
+
 #[cfg(unix)]
 pub type UnixStreamResource =
   FullDuplexResource<unix::OwnedReadHalf, unix::OwnedWriteHalf>;
@@ -176,4 +228,11 @@ impl Resource for UnixStreamResource {
   fn close(self: Rc<Self>) {
     self.cancel_read_ops();
   }
+
+  #[cfg(unix)]
+  fn backing_fd(self: Rc<Self>) -> Option<ResourceHandleFd> {
+    use std::os::unix::io::AsRawFd;
+    let wr = RcRef::map(self, |r| &r.wr).try_borrow()?;
+    Some(wr.as_ref().as_ref().as_raw_fd())
+  }
 }
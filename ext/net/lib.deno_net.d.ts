@@ -108,7 +108,14 @@ declare namespace Deno {
     readonly writable: WritableStream<Uint8Array>;
   }
 
-  /** @category Network */
+  /** Information about an established TLS connection.
+   *
+   * The certificates presented by the peer are not available. With
+   * `clientCaCerts` on {@linkcode Deno.listenTls}, a completed handshake
+   * means the client certificate was verified against those CAs.
+   *
+   * @category Network
+   */
   export interface TlsHandshakeInfo {
     /**
      * Contains the ALPN protocol selected during negotiation with the server.
@@ -149,8 +156,23 @@ declare namespace Deno {
   }
 
   /** @category Network */
-  // deno-lint-ignore no-empty-interface
   export interface TcpListenOptions extends ListenOptions {
+    /** When `true` the SO_REUSEPORT flag will be set on the listener. This
+     * allows multiple processes to listen on the same address and port.
+     *
+     * On Linux this will cause the kernel to distribute incoming connections
+     * across the different processes that are listening on the same address and
+     * port.
+     *
+     * This flag is only supported on Linux. It is silently ignored on other
+     * platforms.
+     *
+     * @default {false} */
+    reusePort?: boolean;
+    /** The maximum length of the queue of pending connections.
+     *
+     * @default {128} */
+    backlog?: number;
   }
 
   /** Listen announces on the local transport address.
@@ -197,6 +219,146 @@ declare namespace Deno {
     options: UnixListenOptions & { transport: "unix" },
   ): UnixListener;
 
+  /** Represents membership of a IPv4 multicast group.
+   *
+   * @category Network
+   */
+  export interface MulticastV4Membership {
+    /** Leaves the multicast group. */
+    leave: () => Promise<void>;
+    /** Sets the multicast loopback option. If enabled, multicast packets will be looped back to the local socket. */
+    setLoopback: (loopback: boolean) => Promise<void>;
+    /** Sets the time-to-live of outgoing multicast packets for this socket. */
+    setTTL: (ttl: number) => Promise<void>;
+  }
+
+  /** Represents membership of a IPv6 multicast group.
+   *
+   * @category Network
+   */
+  export interface MulticastV6Membership {
+    /** Leaves the multicast group. */
+    leave: () => Promise<void>;
+    /** Sets the multicast loopback option. If enabled, multicast packets will be looped back to the local socket. */
+    setLoopback: (loopback: boolean) => Promise<void>;
+  }
+
+  /** A generic transport listener for message-oriented protocols.
+   *
+   * @category Network
+   */
+  export interface DatagramConn extends AsyncIterable<[Uint8Array, Addr]> {
+    /** Joins an IPv4 multicast group. */
+    joinMulticastV4(
+      address: string,
+      networkInterface: string,
+    ): Promise<MulticastV4Membership>;
+
+    /** Joins an IPv6 multicast group. */
+    joinMulticastV6(
+      address: string,
+      networkInterface: number,
+    ): Promise<MulticastV6Membership>;
+
+    /** Waits for and resolves to the next message to the instance.
+     *
+     * Messages are received in the format of a tuple containing the data array
+     * and the address information.
+     */
+    receive(p?: Uint8Array): Promise<[Uint8Array, Addr]>;
+    /** Sends a message to the target via the connection. The method resolves
+     * with the number of bytes sent. */
+    send(p: Uint8Array, addr: Addr): Promise<number>;
+    /** Close closes the socket. Any pending message promises will be rejected
+     * with errors. */
+    close(): void;
+    /** Return the address of the instance. */
+    readonly addr: Addr;
+    [Symbol.asyncIterator](): AsyncIterableIterator<[Uint8Array, Addr]>;
+  }
+
+  /** Options which can be set when opening a datagram listener via
+   * {@linkcode Deno.listenDatagram}.
+   *
+   * @category Network
+   */
+  export interface UdpListenOptions extends ListenOptions {
+    /** When `true` the specified address will be reused, even if another
+     * process has already bound a socket on it. This effectively steals the
+     * socket from the listener.
+     *
+     * @default {false} */
+    reuseAddress?: boolean;
+
+    /** When `true` the SO_REUSEPORT flag will be set on the socket. This
+     * allows multiple processes to bind the same address and port.
+     *
+     * On Linux this will cause the kernel to distribute incoming datagrams
+     * across the different sockets that are bound to the same address and
+     * port.
+     *
+     * This flag is only supported on Linux. It is silently ignored on other
+     * platforms.
+     *
+     * @default {false} */
+    reusePort?: boolean;
+
+    /** When `true`, datagrams may be sent to the broadcast address.
+     *
+     * @default {true} */
+    broadcast?: boolean;
+
+    /** The time-to-live of outgoing unicast datagrams. Use
+     * {@linkcode MulticastV4Membership.setTTL} for multicast datagrams. */
+    ttl?: number;
+
+    /** When `true`, sent multicast packets will be looped back to the local socket.
+     *
+     * @default {false} */
+    loopback?: boolean;
+  }
+
+  /** Listen announces on the local transport address.
+   *
+   * ```ts
+   * const listener1 = Deno.listenDatagram({
+   *   port: 80,
+   *   transport: "udp"
+   * });
+   * const listener2 = Deno.listenDatagram({
+   *   hostname: "golang.org",
+   *   port: 80,
+   *   transport: "udp"
+   * });
+   * ```
+   *
+   * Requires `allow-net` permission.
+   *
+   * @tags allow-net
+   * @category Network
+   */
+  export function listenDatagram(
+    options: UdpListenOptions & { transport: "udp" },
+  ): DatagramConn;
+
+  /** Listen announces on the local transport address.
+   *
+   * ```ts
+   * const listener = Deno.listenDatagram({
+   *   path: "/foo/bar.sock",
+   *   transport: "unixpacket"
+   * });
+   * ```
+   *
+   * Requires `allow-read` and `allow-write` permission.
+   *
+   * @tags allow-read, allow-write
+   * @category Network
+   */
+  export function listenDatagram(
+    options: UnixListenOptions & { transport: "unixpacket" },
+  ): DatagramConn;
+
   /** Provides TLS certified keys, ie: a key that has been certified by a trusted certificate authority.
    * A certified key generally consists of a private key and certificate part.
    *
@@ -295,6 +457,12 @@ declare namespace Deno {
      * TLS handshake.
      */
     alpnProtocols?: string[];
+
+    /** A list of root certificates in PEM format. When set, clients must
+     * present a certificate issued by one of them (mutual TLS), otherwise the
+     * handshake fails.
+     */
+    clientCaCerts?: string[];
   }
 
   /** Listen announces on the local transport address over TLS (transport layer
@@ -347,6 +515,19 @@ declare namespace Deno {
    */
   export function connect(options: ConnectOptions): Promise<TcpConn>;
 
+  /** Options for {@linkcode Deno.TcpConn.setKeepAlive}.
+   *
+   * @category Network
+   */
+  export interface TcpKeepAliveOptions {
+    /** The time in milliseconds the connection needs to be idle before the
+     * first keep-alive probe is sent. Rounded up to whole seconds. */
+    time?: number;
+    /** The time in milliseconds between two keep-alive probes. Rounded up to
+     * whole seconds and ignored on platforms that don't support setting it. */
+    interval?: number;
+  }
+
   /** @category Network */
   export interface TcpConn extends Conn<NetAddr> {
     /**
@@ -355,8 +536,13 @@ declare namespace Deno {
      * @param [noDelay=true]
      */
     setNoDelay(noDelay?: boolean): void;
-    /** Enable/disable keep-alive functionality. */
-    setKeepAlive(keepAlive?: boolean): void;
+    /** Enable/disable keep-alive functionality. The timing of the keep-alive
+     * probes can be set with `options`, otherwise the system defaults are
+     * used.
+     *
+     * @param [keepAlive=true]
+     */
+    setKeepAlive(keepAlive?: boolean, options?: TcpKeepAliveOptions): void;
     /**
      * The resource ID of the connection.
      *
@@ -527,6 +713,29 @@ declare namespace Deno {
     options?: StartTlsOptions,
   ): Promise<TlsConn>;
 
+  /** Start TLS handshake from an existing connection, presenting a client
+   * certificate to the server.
+   *
+   * ```ts
+   * const conn = await Deno.connect({ port: 5432, hostname: "db.example.com" });
+   * // negotiate the upgrade with the server first, e.g. with an SSLRequest
+   * const tlsConn = await Deno.startTls(conn, {
+   *   hostname: "db.example.com",
+   *   cert: Deno.readTextFileSync("./client.crt"),
+   *   key: Deno.readTextFileSync("./client.key"),
+   * });
+   * ```
+   *
+   * Requires `allow-net` permission.
+   *
+   * @tags allow-net
+   * @category Network
+   */
+  export function startTls(
+    conn: TcpConn,
+    options: StartTlsOptions & TlsCertifiedKeyPem,
+  ): Promise<TlsConn>;
+
   /** Shutdown socket send operations.
    *
    * Matches behavior of POSIX shutdown(3).
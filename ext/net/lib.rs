@@ -10,7 +10,6 @@ pub mod resolve_addr;
 mod tcp;
 
 use deno_core::error::AnyError;
-use deno_core::OpState;
 use deno_tls::rustls::RootCertStore;
 use deno_tls::RootCertStoreProvider;
 use std::path::Path;
@@ -30,15 +29,6 @@ pub trait NetPermissions {
     -> Result<(), AnyError>;
 }
 
-/// Helper for checking unstable features. Used for sync ops.
-fn check_unstable(state: &OpState, api_name: &str) {
-  // TODO(bartlomieju): replace with `state.feature_checker.check_or_exit`
-  // once we phase out `check_or_exit_with_legacy_fallback`
-  state
-    .feature_checker
-    .check_or_exit_with_legacy_fallback(UNSTABLE_FEATURE_NAME, api_name);
-}
-
 pub fn get_declaration() -> PathBuf {
   PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("lib.deno_net.d.ts")
 }
@@ -83,6 +73,7 @@ deno_core::extension!(deno_net,
     ops::op_dns_resolve<P>,
     ops::op_set_nodelay,
     ops::op_set_keepalive,
+    ops::op_set_keepalive_with_options,
 
     ops_tls::op_tls_key_null,
     ops_tls::op_tls_key_static,
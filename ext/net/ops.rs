@@ -353,13 +353,11 @@ pub fn op_net_listen_tcp<NP>(
   state: &mut OpState,
   #[serde] addr: IpAddr,
   reuse_port: bool,
+  #[serde] backlog: Option<u32>,
 ) -> Result<(ResourceId, IpAddr), AnyError>
 where
   NP: NetPermissions + 'static,
 {
-  if reuse_port {
-    super::check_unstable(state, "Deno.listen({ reusePort: true })");
-  }
   state
     .borrow_mut::<NP>()
     .check_net(&(&addr.hostname, Some(addr.port)), "Deno.listen()")?;
@@ -367,7 +365,7 @@ where
     .next()
     .ok_or_else(|| generic_error("No resolved address found"))?;
 
-  let listener = TcpListener::bind_direct(addr, reuse_port)?;
+  let listener = TcpListener::bind_direct(addr, reuse_port, backlog)?;
   let local_addr = listener.local_addr()?;
   let listener_resource = NetworkListenerResource::new(listener);
   let rid = state.resource_table.add(listener_resource);
@@ -375,11 +373,40 @@ where
   Ok((rid, IpAddr::from(local_addr)))
 }
 
+#[derive(Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListenUdpOptions {
+  #[serde(default)]
+  reuse_address: bool,
+  #[serde(default)]
+  reuse_port: bool,
+  #[serde(default)]
+  loopback: bool,
+  #[serde(default = "default_broadcast")]
+  broadcast: bool,
+  ttl: Option<u32>,
+}
+
+fn default_broadcast() -> bool {
+  true
+}
+
+impl Default for ListenUdpOptions {
+  fn default() -> Self {
+    Self {
+      reuse_address: false,
+      reuse_port: false,
+      loopback: false,
+      broadcast: true,
+      ttl: None,
+    }
+  }
+}
+
 fn net_listen_udp<NP>(
   state: &mut OpState,
   addr: IpAddr,
-  reuse_address: bool,
-  loopback: bool,
+  options: ListenUdpOptions,
 ) -> Result<(ResourceId, IpAddr), AnyError>
 where
   NP: NetPermissions + 'static,
@@ -397,7 +424,7 @@ where
     Domain::IPV6
   };
   let socket_tmp = Socket::new(domain, Type::DGRAM, Some(Protocol::UDP))?;
-  if reuse_address {
+  if options.reuse_address {
     // This logic is taken from libuv:
     //
     // On the BSDs, SO_REUSEPORT implies SO_REUSEADDR but with some additional
@@ -416,17 +443,25 @@ where
     #[cfg(all(unix, not(target_os = "linux")))]
     socket_tmp.set_reuse_port(true)?;
   }
+  #[cfg(any(target_os = "android", target_os = "linux"))]
+  if options.reuse_port {
+    socket_tmp.set_reuse_port(true)?;
+  }
   let socket_addr = socket2::SockAddr::from(addr);
   socket_tmp.bind(&socket_addr)?;
   socket_tmp.set_nonblocking(true)?;
 
-  // Enable messages to be sent to the broadcast address (255.255.255.255) by default
-  socket_tmp.set_broadcast(true)?;
+  // Messages can be sent to the broadcast address (255.255.255.255) by default
+  socket_tmp.set_broadcast(options.broadcast)?;
+
+  if let Some(ttl) = options.ttl {
+    socket_tmp.set_ttl(ttl)?;
+  }
 
   if domain == Domain::IPV4 {
-    socket_tmp.set_multicast_loop_v4(loopback)?;
+    socket_tmp.set_multicast_loop_v4(options.loopback)?;
   } else {
-    socket_tmp.set_multicast_loop_v6(loopback)?;
+    socket_tmp.set_multicast_loop_v6(options.loopback)?;
   }
 
   let std_socket: std::net::UdpSocket = socket_tmp.into();
@@ -447,14 +482,12 @@ where
 pub fn op_net_listen_udp<NP>(
   state: &mut OpState,
   #[serde] addr: IpAddr,
-  reuse_address: bool,
-  loopback: bool,
+  #[serde] options: ListenUdpOptions,
 ) -> Result<(ResourceId, IpAddr), AnyError>
 where
   NP: NetPermissions + 'static,
 {
-  super::check_unstable(state, "Deno.listenDatagram");
-  net_listen_udp::<NP>(state, addr, reuse_address, loopback)
+  net_listen_udp::<NP>(state, addr, options)
 }
 
 #[op2]
@@ -468,7 +501,15 @@ pub fn op_node_unstable_net_listen_udp<NP>(
 where
   NP: NetPermissions + 'static,
 {
-  net_listen_udp::<NP>(state, addr, reuse_address, loopback)
+  net_listen_udp::<NP>(
+    state,
+    addr,
+    ListenUdpOptions {
+      reuse_address,
+      loopback,
+      ..Default::default()
+    },
+  )
 }
 
 #[derive(Serialize, Eq, PartialEq, Debug)]
@@ -652,13 +693,34 @@ pub fn op_set_nodelay_inner(
   resource.set_nodelay(nodelay)
 }
 
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct KeepAliveOptions {
+  /// Idle time in milliseconds before the first keepalive probe is sent,
+  /// rounded up to whole seconds.
+  pub time: Option<u64>,
+  /// Time in milliseconds between two keepalive probes, rounded up to whole
+  /// seconds.
+  pub interval: Option<u64>,
+}
+
 #[op2(fast)]
 pub fn op_set_keepalive(
   state: &mut OpState,
   #[smi] rid: ResourceId,
   keepalive: bool,
 ) -> Result<(), AnyError> {
-  op_set_keepalive_inner(state, rid, keepalive)
+  op_set_keepalive_inner(state, rid, keepalive, Default::default())
+}
+
+#[op2]
+pub fn op_set_keepalive_with_options(
+  state: &mut OpState,
+  #[smi] rid: ResourceId,
+  keepalive: bool,
+  #[serde] options: KeepAliveOptions,
+) -> Result<(), AnyError> {
+  op_set_keepalive_inner(state, rid, keepalive, options)
 }
 
 #[inline]
@@ -666,10 +728,11 @@ pub fn op_set_keepalive_inner(
   state: &mut OpState,
   rid: ResourceId,
   keepalive: bool,
+  options: KeepAliveOptions,
 ) -> Result<(), AnyError> {
   let resource: Rc<TcpStreamResource> =
     state.resource_table.get::<TcpStreamResource>(rid)?;
-  resource.set_keepalive(keepalive)
+  resource.set_keepalive(keepalive, options)
 }
 
 fn rdata_to_return_record(
@@ -771,6 +834,7 @@ fn rdata_to_return_record(
 #[cfg(test)]
 mod tests {
   use super::*;
+  use crate::io::keepalive_duration;
   use deno_core::futures::FutureExt;
   use deno_core::JsRuntime;
   use deno_core::RuntimeOptions;
@@ -781,6 +845,7 @@ mod tests {
   use std::path::Path;
   use std::sync::Arc;
   use std::sync::Mutex;
+  use std::time::Duration;
   use trust_dns_proto::rr::rdata::caa::KeyValue;
   use trust_dns_proto::rr::rdata::caa::CAA;
   use trust_dns_proto::rr::rdata::mx::MX;
@@ -1010,7 +1075,7 @@ mod tests {
   #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
   async fn tcp_set_keepalive() {
     let set_keepalive = Box::new(|state: &mut OpState, rid| {
-      op_set_keepalive_inner(state, rid, true).unwrap();
+      op_set_keepalive_inner(state, rid, true, Default::default()).unwrap();
     });
     let test_fn = Box::new(|socket: SockRef| {
       assert!(!socket.nodelay().unwrap());
@@ -1019,6 +1084,41 @@ mod tests {
     check_sockopt(String::from("127.0.0.1:4146"), set_keepalive, test_fn).await;
   }
 
+  #[test]
+  fn keepalive_duration_rounds_up_to_seconds() {
+    assert_eq!(
+      keepalive_duration("time", 1).unwrap(),
+      Duration::from_secs(1)
+    );
+    assert_eq!(
+      keepalive_duration("time", 1_500).unwrap(),
+      Duration::from_secs(2)
+    );
+    assert_eq!(
+      keepalive_duration("time", 30_000).unwrap(),
+      Duration::from_secs(30)
+    );
+    assert!(keepalive_duration("interval", 0).is_err());
+  }
+
+  #[cfg(any(target_os = "android", target_os = "linux"))]
+  #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+  async fn tcp_set_keepalive_options() {
+    let set_keepalive = Box::new(|state: &mut OpState, rid| {
+      let options = KeepAliveOptions {
+        time: Some(30_000),
+        interval: Some(5_000),
+      };
+      op_set_keepalive_inner(state, rid, true, options).unwrap();
+    });
+    let test_fn = Box::new(|socket: SockRef| {
+      assert!(socket.keepalive().unwrap());
+      assert_eq!(socket.keepalive_time().unwrap(), Duration::from_secs(30));
+      assert_eq!(socket.keepalive_interval().unwrap(), Duration::from_secs(5));
+    });
+    check_sockopt(String::from("127.0.0.1:4147"), set_keepalive, test_fn).await;
+  }
+
   #[allow(clippy::type_complexity)]
   async fn check_sockopt(
     addr: String,
@@ -1028,7 +1128,7 @@ mod tests {
     let sockets = Arc::new(Mutex::new(vec![]));
     let clone_addr = addr.clone();
     let addr = addr.to_socket_addrs().unwrap().next().unwrap();
-    let listener = TcpListener::bind_direct(addr, false).unwrap();
+    let listener = TcpListener::bind_direct(addr, false, None).unwrap();
     let accept_fut = listener.accept().boxed_local();
     let store_fut = async move {
       let socket = accept_fut.await.unwrap();
@@ -29,10 +29,16 @@ use deno_core::ResourceId;
 use deno_tls::create_client_config;
 use deno_tls::load_certs;
 use deno_tls::load_private_keys;
+use deno_tls::rustls::client::ClientSessionMemoryCache;
+use deno_tls::rustls::client::Resumption;
+use deno_tls::rustls::server::AllowAnyAuthenticatedClient;
 use deno_tls::rustls::Certificate;
+use deno_tls::rustls::ClientConfig;
 use deno_tls::rustls::PrivateKey;
+use deno_tls::rustls::RootCertStore;
 use deno_tls::rustls::ServerConfig;
 use deno_tls::rustls::ServerName;
+use deno_tls::rustls::Ticketer;
 use deno_tls::SocketUse;
 use deno_tls::TlsKey;
 use deno_tls::TlsKeys;
@@ -61,6 +67,27 @@ pub use rustls_tokio_stream::TlsStream;
 pub(crate) const TLS_BUFFER_SIZE: Option<NonZeroUsize> =
   NonZeroUsize::new(65536);
 
+/// The number of TLS client sessions kept for resumption.
+const TLS_CLIENT_SESSIONS: usize = 256;
+
+/// TLS sessions of the client connections made with the default
+/// configuration, kept to resume them when connecting to the same server
+/// again. Connections with custom CA certificates or a client certificate
+/// don't share sessions, as resuming one skips the certificate checks.
+struct TlsClientSessions(Arc<ClientSessionMemoryCache>);
+
+fn resume_client_sessions(state: &mut OpState, tls_config: &mut ClientConfig) {
+  let sessions = match state.try_borrow::<TlsClientSessions>() {
+    Some(sessions) => sessions.0.clone(),
+    None => {
+      let sessions = ClientSessionMemoryCache::new(TLS_CLIENT_SESSIONS);
+      state.put(TlsClientSessions(sessions.clone()));
+      sessions
+    }
+  };
+  tls_config.resumption = Resumption::store(sessions);
+}
+
 pub struct TlsListener {
   pub(crate) tcp_listener: TcpListener,
   pub(crate) tls_config: Arc<ServerConfig>,
@@ -134,6 +161,8 @@ impl TlsStreamResource {
     let cancel_handle = RcRef::map(self, |r| &r.cancel_handle);
     let handshake = wr.handshake().try_or_cancel(cancel_handle).await?;
 
+    // The peer certificates aren't part of the handshake result of
+    // `rustls_tokio_stream`, so they can't be exposed here.
     let alpn_protocol = handshake.alpn.map(|alpn| alpn.into());
     let tls_info = TlsHandshakeInfo { alpn_protocol };
     self.handshake_info.replace(Some(tls_info.clone()));
@@ -233,6 +262,7 @@ where
 pub fn op_tls_start<NP>(
   state: Rc<RefCell<OpState>>,
   #[serde] args: StartTlsArgs,
+  #[cppgc] key_pair: &TlsKeys,
 ) -> Result<(ResourceId, IpAddr, IpAddr), AnyError>
 where
   NP: NetPermissions + 'static,
@@ -249,6 +279,11 @@ where
     permissions.check_net(&(hostname, Some(0)), "Deno.startTls()")?;
   }
 
+  let cert_and_key = match key_pair {
+    TlsKeys::Null => None,
+    TlsKeys::Static(key) => Some(key.clone()),
+  };
+  let default_config = args.ca_certs.is_empty() && cert_and_key.is_none();
   let ca_certs = args
     .ca_certs
     .into_iter()
@@ -287,7 +322,7 @@ where
     root_cert_store,
     ca_certs,
     unsafely_ignore_certificate_errors,
-    None,
+    cert_and_key,
     SocketUse::GeneralSsl,
   )?;
 
@@ -296,6 +331,10 @@ where
       alpn_protocols.into_iter().map(|s| s.into_bytes()).collect();
   }
 
+  if default_config {
+    resume_client_sessions(&mut state.borrow_mut(), &mut tls_config);
+  }
+
   let tls_config = Arc::new(tls_config);
   let tls_stream = TlsStream::new_client_side(
     tcp_stream,
@@ -371,6 +410,7 @@ where
     TlsKeys::Null => None,
     TlsKeys::Static(key) => Some(key.clone()),
   };
+  let default_config = ca_certs.is_empty() && cert_and_key.is_none();
   let mut tls_config = create_client_config(
     root_cert_store,
     ca_certs,
@@ -384,6 +424,10 @@ where
       alpn_protocols.into_iter().map(|s| s.into_bytes()).collect();
   }
 
+  if default_config {
+    resume_client_sessions(&mut state.borrow_mut(), &mut tls_config);
+  }
+
   let tls_config = Arc::new(tls_config);
 
   let tls_stream = TlsStream::new_client_side(
@@ -421,6 +465,8 @@ fn load_private_keys_from_file(
 pub struct ListenTlsArgs {
   alpn_protocols: Option<Vec<String>>,
   reuse_port: bool,
+  backlog: Option<u32>,
+  client_ca_certs: Option<Vec<String>>,
 }
 
 #[op2]
@@ -434,19 +480,28 @@ pub fn op_net_listen_tls<NP>(
 where
   NP: NetPermissions + 'static,
 {
-  if args.reuse_port {
-    super::check_unstable(state, "Deno.listenTls({ reusePort: true })");
-  }
-
   {
     let permissions = state.borrow_mut::<NP>();
     permissions
       .check_net(&(&addr.hostname, Some(addr.port)), "Deno.listenTls()")?;
   }
 
-  let tls_config = ServerConfig::builder()
-    .with_safe_defaults()
-    .with_no_client_auth();
+  let tls_config = ServerConfig::builder().with_safe_defaults();
+  let tls_config = match args.client_ca_certs {
+    // Only accept clients with a certificate issued by one of these CAs.
+    Some(client_ca_certs) => {
+      let mut roots = RootCertStore::empty();
+      for ca_cert in client_ca_certs {
+        for cert in load_certs(&mut BufReader::new(ca_cert.as_bytes()))? {
+          roots.add(&cert)?;
+        }
+      }
+      tls_config.with_client_cert_verifier(
+        AllowAnyAuthenticatedClient::new(roots).boxed(),
+      )
+    }
+    None => tls_config.with_no_client_auth(),
+  };
 
   let mut tls_config = match keys {
     TlsKeys::Null => Err(anyhow!("Deno.listenTls requires a key")),
@@ -463,11 +518,16 @@ where
       alpn_protocols.into_iter().map(|s| s.into_bytes()).collect();
   }
 
+  // Hand out session tickets so clients can resume their sessions.
+  tls_config.ticketer = Ticketer::new()
+    .map_err(|_| generic_error("Failed to create the TLS session ticketer"))?;
+
   let bind_addr = resolve_addr_sync(&addr.hostname, addr.port)?
     .next()
     .ok_or_else(|| generic_error("No resolved address found"))?;
 
-  let tcp_listener = TcpListener::bind_direct(bind_addr, args.reuse_port)?;
+  let tcp_listener =
+    TcpListener::bind_direct(bind_addr, args.reuse_port, args.backlog)?;
   let local_addr = tcp_listener.local_addr()?;
 
   let tls_listener_resource = NetworkListenerResource::new(TlsListener {
@@ -228,7 +228,6 @@ pub fn op_net_listen_unixpacket<NP>(
 where
   NP: NetPermissions + 'static,
 {
-  super::check_unstable(state, "Deno.listenDatagram");
   net_listen_unixpacket::<NP>(state, path)
 }
 
@@ -32,7 +32,7 @@ pub struct TcpConnection {
 impl TcpConnection {
   /// Boot a load-balanced TCP connection
   pub fn start(key: SocketAddr) -> std::io::Result<Self> {
-    let listener = bind_socket_and_listen(key, false)?;
+    let listener = bind_socket_and_listen(key, false, None)?;
     let sock = listener.into();
 
     Ok(Self { sock, key })
@@ -51,6 +51,9 @@ pub struct TcpListener {
   conn: Option<Arc<TcpConnection>>,
 }
 
+/// The listen backlog used when none is specified.
+const DEFAULT_BACKLOG: u32 = 128;
+
 /// Does this platform implement `SO_REUSEPORT` in a load-balancing manner?
 const REUSE_PORT_LOAD_BALANCES: bool =
   cfg!(any(target_os = "android", target_os = "linux"));
@@ -82,7 +85,7 @@ impl TcpListener {
     if REUSE_PORT_LOAD_BALANCES && reuse_port {
       Self::bind_load_balanced(socket_addr)
     } else {
-      Self::bind_direct(socket_addr, reuse_port)
+      Self::bind_direct(socket_addr, reuse_port, None)
     }
   }
 
@@ -91,9 +94,10 @@ impl TcpListener {
   pub fn bind_direct(
     socket_addr: SocketAddr,
     reuse_port: bool,
+    backlog: Option<u32>,
   ) -> std::io::Result<Self> {
     // We ignore `reuse_port` on platforms other than Linux to match the existing behaviour.
-    let listener = bind_socket_and_listen(socket_addr, reuse_port)?;
+    let listener = bind_socket_and_listen(socket_addr, reuse_port, backlog)?;
     Ok(Self {
       listener: Some(tokio::net::TcpListener::from_std(listener)?),
       conn: None,
@@ -151,6 +155,7 @@ impl Drop for TcpListener {
 fn bind_socket_and_listen(
   socket_addr: SocketAddr,
   reuse_port: bool,
+  backlog: Option<u32>,
 ) -> Result<std::net::TcpListener, std::io::Error> {
   let socket = if socket_addr.is_ipv4() {
     socket2::Socket::new(Domain::IPV4, Type::STREAM, Some(Protocol::TCP))?
@@ -170,7 +175,8 @@ fn bind_socket_and_listen(
   socket.set_reuse_address(true)?;
   socket.set_nonblocking(true)?;
   socket.bind(&socket_addr.into())?;
-  socket.listen(128)?;
+  let backlog = backlog.unwrap_or(DEFAULT_BACKLOG);
+  socket.listen(i32::try_from(backlog).unwrap_or(i32::MAX))?;
   let listener = socket.into();
   Ok(listener)
 }
@@ -150,7 +150,11 @@ export class TLSSocket extends net.Socket {
       const afterConnect = handle.afterConnect;
       handle.afterConnect = async (req: any, status: number) => {
         try {
-          const conn = await Deno.startTls(handle[kStreamBaseField], options);
+          const conn = await Deno.startTls(handle[kStreamBaseField], {
+            hostname: options.hostname,
+            caCerts: options.caCerts,
+            alpnProtocols: options.alpnProtocols,
+          });
           handle[kStreamBaseField] = conn;
           tlssock.emit("secure");
           tlssock.removeListener("end", onConnectEnd);
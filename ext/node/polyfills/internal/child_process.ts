@@ -169,6 +169,7 @@ export class ChildProcess extends EventEmitter {
       shell = false,
       signal,
       windowsVerbatimArguments = false,
+      detached = false,
     } = options || {};
     const normalizedStdio = normalizeStdioOption(stdio);
     const [
@@ -197,6 +198,7 @@ export class ChildProcess extends EventEmitter {
         stdout: toDenoStdio(stdout),
         stderr: toDenoStdio(stderr),
         windowsRawArguments: windowsVerbatimArguments,
+        detached,
         ipc, // internal
       }).spawn();
       this.pid = this.#process.pid;
@@ -263,7 +265,11 @@ export class ChildProcess extends EventEmitter {
 
       (async () => {
         const status = await this.#process.status;
-        this.exitCode = status.code;
+        if (status.signal == null) {
+          this.exitCode = status.code;
+        } else {
+          this.signalCode = status.signal;
+        }
         this.#spawned.promise.then(async () => {
           const exitCode = this.signalCode == null ? this.exitCode : null;
           const signalCode = this.signalCode == null ? null : this.signalCode;
@@ -7,6 +7,7 @@ import {
   op_host_post_message,
   op_host_recv_ctrl,
   op_host_recv_message,
+  op_host_take_error,
   op_host_terminate_worker,
   op_message_port_recv_message_sync,
   op_worker_threads_filename,
@@ -207,6 +208,7 @@ class NodeWorker extends EventEmitter {
         } /* falls through */
         case 2: { // Error
           this.#handleError(data);
+          op_host_take_error(this.#id, true);
           break;
         }
         case 3: { // Close
@@ -911,9 +911,14 @@ const _original = Symbol("[[original]]");
  *
  * @param {number} rid The resource ID to read from.
  * @param {boolean=} autoClose If the resource should be auto-closed when the stream closes. Defaults to true.
+ * @param {number=} chunkSize The size of the chunks read from the resource. Defaults to 64 KiB.
  * @returns {ReadableStream<Uint8Array>}
  */
-function readableStreamForRid(rid, autoClose = true) {
+function readableStreamForRid(
+  rid,
+  autoClose = true,
+  chunkSize = DEFAULT_CHUNK_SIZE,
+) {
   const stream = new ReadableStream(_brand);
   stream[_resourceBacking] = { rid, autoClose };
 
@@ -959,7 +964,7 @@ function readableStreamForRid(rid, autoClose = true) {
     cancel() {
       tryClose();
     },
-    autoAllocateChunkSize: DEFAULT_CHUNK_SIZE,
+    autoAllocateChunkSize: chunkSize,
   };
   initializeReadableStream(stream);
   setUpReadableByteStreamControllerFromUnderlyingSource(
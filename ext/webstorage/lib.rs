@@ -3,13 +3,17 @@
 // NOTE to all: use **cached** prepared statements when interfacing with SQLite.
 
 use std::fmt;
+use std::path::Path;
 use std::path::PathBuf;
 
 use deno_core::error::AnyError;
 use deno_core::op2;
 use deno_core::OpState;
+use rusqlite::ffi;
 use rusqlite::params;
 use rusqlite::Connection;
+use rusqlite::ErrorCode;
+use rusqlite::OpenFlags;
 use rusqlite::OptionalExtension;
 
 pub use rusqlite;
@@ -19,6 +23,19 @@ struct OriginStorageDir(PathBuf);
 
 const MAX_STORAGE_BYTES: usize = 10 * 1024 * 1024;
 
+/// Name of the `localStorage` database in the origin storage directory.
+pub const LOCAL_STORAGE_FILE_NAME: &str = "local_storage";
+
+/// Options of the persistent `localStorage`.
+#[derive(Clone, Debug, Default)]
+pub struct LocalStorageOptions {
+  /// The origin the storage belongs to. It is recorded in the database, as
+  /// origin storage directories are named after a hash of the origin.
+  pub origin: Option<String>,
+  /// Maximum size of the stored data in bytes, 10MB when not set.
+  pub quota: Option<usize>,
+}
+
 deno_core::extension!(deno_webstorage,
   deps = [ deno_webidl ],
   ops = [
@@ -32,12 +49,14 @@ deno_core::extension!(deno_webstorage,
   ],
   esm = [ "01_webstorage.js" ],
   options = {
-    origin_storage_dir: Option<PathBuf>
+    origin_storage_dir: Option<PathBuf>,
+    local_storage_options: LocalStorageOptions,
   },
   state = |state, options| {
     if let Some(origin_storage_dir) = options.origin_storage_dir {
       state.put(OriginStorageDir(origin_storage_dir));
     }
+    state.put(options.local_storage_options);
   },
 );
 
@@ -59,27 +78,8 @@ fn get_webstorage(
           "LocalStorage is not supported in this context.",
         )
       })?;
-      std::fs::create_dir_all(&path.0)?;
-      let conn = Connection::open(path.0.join("local_storage"))?;
-      // Enable write-ahead-logging and tweak some other stuff.
-      let initial_pragmas = "
-        -- enable write-ahead-logging mode
-        PRAGMA journal_mode=WAL;
-        PRAGMA synchronous=NORMAL;
-        PRAGMA temp_store=memory;
-        PRAGMA page_size=4096;
-        PRAGMA mmap_size=6000000;
-        PRAGMA optimize;
-      ";
-
-      conn.execute_batch(initial_pragmas)?;
-      conn.set_prepared_statement_cache_capacity(128);
-      {
-        let mut stmt = conn.prepare_cached(
-          "CREATE TABLE IF NOT EXISTS data (key VARCHAR UNIQUE, value VARCHAR)",
-        )?;
-        stmt.execute(params![])?;
-      }
+      let conn =
+        open_local_storage(&path.0, state.borrow::<LocalStorageOptions>())?;
       state.put(LocalStorage(conn));
     }
 
@@ -102,6 +102,167 @@ fn get_webstorage(
   Ok(conn)
 }
 
+fn open_local_storage(
+  dir: &Path,
+  options: &LocalStorageOptions,
+) -> Result<Connection, AnyError> {
+  std::fs::create_dir_all(dir)?;
+  let path = dir.join(LOCAL_STORAGE_FILE_NAME);
+  let conn = match init_local_storage(&path) {
+    Ok(conn) => conn,
+    Err(err) if is_corruption_error(&err) => {
+      // Keep the damaged database around for inspection, but don't let it
+      // make localStorage unusable for the origin.
+      let corrupted_path = with_suffix(&path, ".corrupted");
+      std::fs::rename(&path, &corrupted_path)?;
+      remove_journal_files(&path);
+      log::warn!(
+        "Warning: the localStorage database at {} is corrupted ({}), starting with an empty storage. The damaged file was moved to {}.",
+        path.display(),
+        err,
+        corrupted_path.display(),
+      );
+      init_local_storage(&path)?
+    }
+    Err(err) => return Err(err.into()),
+  };
+
+  if let Some(origin) = &options.origin {
+    let mut stmt = conn.prepare_cached(
+      "INSERT OR REPLACE INTO meta (key, value) VALUES ('origin', ?)",
+    )?;
+    stmt.execute(params![origin])?;
+  }
+
+  Ok(conn)
+}
+
+fn init_local_storage(path: &Path) -> Result<Connection, rusqlite::Error> {
+  let conn = Connection::open(path)?;
+  // Enable write-ahead-logging and tweak some other stuff.
+  let initial_pragmas = "
+    -- enable write-ahead-logging mode
+    PRAGMA journal_mode=WAL;
+    PRAGMA synchronous=NORMAL;
+    PRAGMA temp_store=memory;
+    PRAGMA page_size=4096;
+    PRAGMA mmap_size=6000000;
+    PRAGMA optimize;
+  ";
+
+  conn.execute_batch(initial_pragmas)?;
+
+  // Damaged pages are otherwise only noticed once they are read.
+  let check: String =
+    conn.query_row("PRAGMA quick_check", params![], |row| row.get(0))?;
+  if check != "ok" {
+    return Err(rusqlite::Error::SqliteFailure(
+      ffi::Error::new(ffi::SQLITE_CORRUPT),
+      Some(check),
+    ));
+  }
+
+  conn.set_prepared_statement_cache_capacity(128);
+  conn.execute_batch(
+    "
+    CREATE TABLE IF NOT EXISTS data (key VARCHAR UNIQUE, value VARCHAR);
+    CREATE TABLE IF NOT EXISTS meta (key VARCHAR UNIQUE, value VARCHAR);
+  ",
+  )?;
+  Ok(conn)
+}
+
+fn is_corruption_error(err: &rusqlite::Error) -> bool {
+  matches!(
+    err.sqlite_error_code(),
+    Some(ErrorCode::DatabaseCorrupt | ErrorCode::NotADatabase)
+  )
+}
+
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+  let mut path = path.as_os_str().to_owned();
+  path.push(suffix);
+  PathBuf::from(path)
+}
+
+fn remove_journal_files(path: &Path) {
+  for suffix in ["-wal", "-shm"] {
+    let _ = std::fs::remove_file(with_suffix(path, suffix));
+  }
+}
+
+/// Summary of the `localStorage` database of an origin.
+#[derive(Clone, Debug)]
+pub struct LocalStorageInfo {
+  /// The recorded origin, missing for databases created by older versions.
+  pub origin: Option<String>,
+  /// The number of stored keys.
+  pub entries: u64,
+  /// The size of the database files on disk, in bytes.
+  pub size: u64,
+}
+
+/// Reads the summary of the `localStorage` database in `origin_storage_dir`,
+/// if there is one. The database isn't modified.
+pub fn read_local_storage_info(
+  origin_storage_dir: &Path,
+) -> Result<Option<LocalStorageInfo>, AnyError> {
+  let path = origin_storage_dir.join(LOCAL_STORAGE_FILE_NAME);
+  if !path.is_file() {
+    return Ok(None);
+  }
+  let size = ["", "-wal"]
+    .iter()
+    .filter_map(|suffix| std::fs::metadata(with_suffix(&path, suffix)).ok())
+    .map(|metadata| metadata.len())
+    .sum();
+
+  let conn = Connection::open_with_flags(
+    &path,
+    OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+  )?;
+  let entries: u64 =
+    conn.query_row("SELECT COUNT(*) FROM data", params![], |row| row.get(0))?;
+  let has_meta: bool = conn.query_row(
+    "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = 'meta')",
+    params![],
+    |row| row.get(0),
+  )?;
+  let origin = if has_meta {
+    conn
+      .query_row(
+        "SELECT value FROM meta WHERE key = 'origin'",
+        params![],
+        |row| row.get(0),
+      )
+      .optional()?
+  } else {
+    None
+  };
+
+  Ok(Some(LocalStorageInfo {
+    origin,
+    entries,
+    size,
+  }))
+}
+
+/// Removes the `localStorage` database in `origin_storage_dir`, along with
+/// its journal files and a database moved aside because it was corrupted.
+/// Returns whether there was a database to remove.
+pub fn remove_local_storage(
+  origin_storage_dir: &Path,
+) -> Result<bool, std::io::Error> {
+  let path = origin_storage_dir.join(LOCAL_STORAGE_FILE_NAME);
+  let _ = std::fs::remove_file(with_suffix(&path, ".corrupted"));
+  remove_journal_files(&path);
+  match std::fs::remove_file(&path) {
+    Ok(()) => Ok(true),
+    Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(false),
+    Err(err) => Err(err),
+  }
+}
+
 #[op2(fast)]
 pub fn op_webstorage_length(
   state: &mut OpState,
@@ -135,8 +296,8 @@ pub fn op_webstorage_key(
 }
 
 #[inline]
-fn size_check(input: usize) -> Result<(), AnyError> {
-  if input >= MAX_STORAGE_BYTES {
+fn size_check(input: usize, max: usize) -> Result<(), AnyError> {
+  if input >= max {
     return Err(
       deno_web::DomExceptionQuotaExceededError::new(
         "Exceeded maximum storage size",
@@ -155,15 +316,23 @@ pub fn op_webstorage_set(
   #[string] value: &str,
   persistent: bool,
 ) -> Result<(), AnyError> {
+  let max = if persistent {
+    state
+      .borrow::<LocalStorageOptions>()
+      .quota
+      .unwrap_or(MAX_STORAGE_BYTES)
+  } else {
+    MAX_STORAGE_BYTES
+  };
   let conn = get_webstorage(state, persistent)?;
 
-  size_check(key.len() + value.len())?;
+  size_check(key.len() + value.len(), max)?;
 
   let mut stmt = conn
     .prepare_cached("SELECT SUM(pgsize) FROM dbstat WHERE name = 'data'")?;
   let size: u32 = stmt.query_row(params![], |row| row.get(0))?;
 
-  size_check(size as usize)?;
+  size_check(size as usize, max)?;
 
   let mut stmt = conn
     .prepare_cached("INSERT OR REPLACE INTO data (key, value) VALUES (?, ?)")?;
@@ -227,8 +396,7 @@ pub fn op_webstorage_iterate_keys(
   let mut stmt = conn.prepare_cached("SELECT key FROM data")?;
   let keys = stmt
     .query_map(params![], |row| row.get::<_, String>(0))?
-    .map(|r| r.unwrap())
-    .collect();
+    .collect::<Result<_, _>>()?;
 
   Ok(keys)
 }
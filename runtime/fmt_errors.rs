@@ -3,11 +3,353 @@
 use deno_core::error::format_file_name;
 use deno_core::error::JsError;
 use deno_core::error::JsStackFrame;
+use deno_core::serde_json;
+use deno_core::url::Url;
 use deno_terminal::colors::cyan;
-use deno_terminal::colors::italic_bold;
-use deno_terminal::colors::red;
+use deno_terminal::colors::cyan_bold;
+use deno_terminal::colors::gray;
+use deno_terminal::colors::red_bold;
+use deno_terminal::colors::use_color;
 use deno_terminal::colors::yellow;
+use once_cell::sync::Lazy;
+use serde::Serialize;
 use std::fmt::Write as _;
+use std::str::FromStr;
+use std::sync::RwLock;
+
+/// A terminal color that can be used in an [`ErrorTheme`].
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ThemeColor {
+  /// The default foreground color of the terminal.
+  None,
+  Black,
+  Red,
+  Green,
+  Yellow,
+  Blue,
+  Magenta,
+  Cyan,
+  White,
+  Gray,
+}
+
+impl ThemeColor {
+  fn ansi_code(&self) -> Option<&'static str> {
+    match self {
+      ThemeColor::None => None,
+      ThemeColor::Black => Some("30"),
+      ThemeColor::Red => Some("31"),
+      ThemeColor::Green => Some("32"),
+      ThemeColor::Yellow => Some("33"),
+      ThemeColor::Blue => Some("34"),
+      ThemeColor::Magenta => Some("35"),
+      ThemeColor::Cyan => Some("36"),
+      ThemeColor::White => Some("37"),
+      ThemeColor::Gray => Some("90"),
+    }
+  }
+}
+
+impl FromStr for ThemeColor {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "none" => Ok(ThemeColor::None),
+      "black" => Ok(ThemeColor::Black),
+      "red" => Ok(ThemeColor::Red),
+      "green" => Ok(ThemeColor::Green),
+      "yellow" => Ok(ThemeColor::Yellow),
+      "blue" => Ok(ThemeColor::Blue),
+      "magenta" => Ok(ThemeColor::Magenta),
+      "cyan" => Ok(ThemeColor::Cyan),
+      "white" => Ok(ThemeColor::White),
+      "gray" | "grey" => Ok(ThemeColor::Gray),
+      _ => Err(format!("Unknown color \"{s}\"")),
+    }
+  }
+}
+
+/// The colors used to render the parts of a formatted [`JsError`].
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ErrorTheme {
+  /// File names and other frame locations.
+  pub location: ThemeColor,
+  /// Line and column numbers.
+  pub line_number: ThemeColor,
+  /// The caret underlining the failing column of the source line.
+  pub underline: ThemeColor,
+  /// Function and method names of stack frames.
+  pub function_name: ThemeColor,
+  /// Render function names in italics.
+  pub italic: bool,
+  /// Render all themed parts in bold.
+  pub bold: bool,
+}
+
+impl Default for ErrorTheme {
+  fn default() -> Self {
+    Self {
+      location: ThemeColor::Cyan,
+      line_number: ThemeColor::Yellow,
+      underline: ThemeColor::Red,
+      function_name: ThemeColor::None,
+      italic: true,
+      bold: false,
+    }
+  }
+}
+
+impl ErrorTheme {
+  /// A theme using bright, bold colors that stand out on most backgrounds.
+  pub fn high_contrast() -> Self {
+    Self {
+      location: ThemeColor::White,
+      line_number: ThemeColor::Yellow,
+      underline: ThemeColor::Red,
+      function_name: ThemeColor::White,
+      italic: false,
+      bold: true,
+    }
+  }
+
+  /// The default theme, for terminals that render italics poorly.
+  pub fn no_italic() -> Self {
+    Self {
+      italic: false,
+      ..Default::default()
+    }
+  }
+
+  /// Get one of the preset themes by name.
+  pub fn from_preset(name: &str) -> Option<Self> {
+    match name {
+      "default" => Some(Self::default()),
+      "high-contrast" => Some(Self::high_contrast()),
+      "no-italic" => Some(Self::no_italic()),
+      _ => None,
+    }
+  }
+
+  /// Override a single property of the theme, where `key` is one of
+  /// `location`, `lineNumber`, `underline`, `functionName`, `italic` or
+  /// `bold`.
+  pub fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+    fn parse_bool(value: &str) -> Result<bool, String> {
+      value
+        .parse()
+        .map_err(|_| format!("Expected true or false, got \"{value}\""))
+    }
+    match key {
+      "location" => self.location = value.parse()?,
+      "lineNumber" => self.line_number = value.parse()?,
+      "underline" => self.underline = value.parse()?,
+      "functionName" => self.function_name = value.parse()?,
+      "italic" => self.italic = parse_bool(value)?,
+      "bold" => self.bold = parse_bool(value)?,
+      _ => return Err(format!("Unknown error theme property \"{key}\"")),
+    }
+    Ok(())
+  }
+
+  fn paint(&self, s: &str, color: ThemeColor, italic: bool) -> String {
+    if !use_color() {
+      return s.to_string();
+    }
+    let mut codes = Vec::new();
+    if self.bold {
+      codes.push("1");
+    }
+    if italic {
+      codes.push("3");
+    }
+    codes.extend(color.ansi_code());
+    if codes.is_empty() {
+      return s.to_string();
+    }
+    format!("\x1b[{}m{s}\x1b[0m", codes.join(";"))
+  }
+
+  fn location(&self, s: &str) -> String {
+    self.paint(s, self.location, false)
+  }
+
+  fn line_number(&self, s: &str) -> String {
+    self.paint(s, self.line_number, false)
+  }
+
+  fn underline(&self, s: &str) -> String {
+    self.paint(s, self.underline, false)
+  }
+
+  fn function_name(&self, s: &str) -> String {
+    // function names are always bold, like the default `italic_bold` style
+    let theme = ErrorTheme {
+      bold: true,
+      ..self.clone()
+    };
+    theme.paint(s, self.function_name, self.italic)
+  }
+}
+
+/// Parses either a preset name (`high-contrast`, `no-italic`) or a comma
+/// separated list of overrides, optionally starting with a preset, e.g.
+/// `no-italic,location=blue,lineNumber=magenta`.
+impl FromStr for ErrorTheme {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let mut theme = ErrorTheme::default();
+    for (i, part) in s.split(',').map(str::trim).enumerate() {
+      if part.is_empty() {
+        continue;
+      }
+      match part.split_once('=') {
+        Some((key, value)) => theme.set(key.trim(), value.trim())?,
+        None if i == 0 => {
+          theme = ErrorTheme::from_preset(part)
+            .ok_or_else(|| format!("Unknown error theme preset \"{part}\""))?;
+        }
+        None => return Err(format!("Expected key=value, got \"{part}\"")),
+      }
+    }
+    Ok(theme)
+  }
+}
+
+/// Number of causes of an error that are formatted by default, before the
+/// rest of the cause chain is summarized as `... N more causes`.
+pub const DEFAULT_MAX_CAUSE_DEPTH: usize = 20;
+
+/// Process wide options used by [`format_js_error`].
+#[derive(Debug, Clone)]
+pub struct FormatJsErrorOptions {
+  /// Number of source lines to display above and below the line an error
+  /// was thrown from.
+  pub context_lines: usize,
+  pub theme: ErrorTheme,
+  /// Wrap file locations in OSC 8 terminal hyperlinks when formatting errors
+  /// for stderr with [`format_js_error`].
+  pub hyperlinks: bool,
+  /// Stack frames belonging to these categories are left out of the
+  /// formatted stack.
+  pub hidden_frames: Vec<StackFrameCategory>,
+  /// Maximum number of causes formatted for an error. Deeper causes are
+  /// summarized as `... N more causes`.
+  pub max_cause_depth: usize,
+}
+
+impl Default for FormatJsErrorOptions {
+  fn default() -> Self {
+    Self {
+      context_lines: 0,
+      theme: ErrorTheme::default(),
+      hyperlinks: false,
+      hidden_frames: Vec::new(),
+      max_cause_depth: DEFAULT_MAX_CAUSE_DEPTH,
+    }
+  }
+}
+
+/// A category of stack frames that can be hidden from formatted errors, so
+/// that application frames stand out.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum StackFrameCategory {
+  /// Frames from npm packages, either in a `node_modules` directory or in the
+  /// global npm cache.
+  NodeModules,
+  /// Frames from a `vendor` directory.
+  Vendor,
+  /// Frames from Deno's internal `ext:` and `node:` modules.
+  Internal,
+}
+
+impl StackFrameCategory {
+  pub fn matches(&self, frame: &JsStackFrame) -> bool {
+    let Some(file_name) = &frame.file_name else {
+      return false;
+    };
+    let file_name = file_name.replace('\\', "/");
+    match self {
+      StackFrameCategory::NodeModules => {
+        file_name.contains("/node_modules/")
+          || file_name.contains("/npm/registry.npmjs.org/")
+      }
+      StackFrameCategory::Vendor => file_name.contains("/vendor/"),
+      StackFrameCategory::Internal => {
+        file_name.starts_with("ext:") || file_name.starts_with("node:")
+      }
+    }
+  }
+}
+
+impl FromStr for StackFrameCategory {
+  type Err = String;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "node_modules" => Ok(StackFrameCategory::NodeModules),
+      "vendor" => Ok(StackFrameCategory::Vendor),
+      "internal" => Ok(StackFrameCategory::Internal),
+      _ => Err(format!("Unknown stack frame category \"{s}\"")),
+    }
+  }
+}
+
+/// Detect whether `stream` is a terminal that renders OSC 8 hyperlinks. The
+/// `FORCE_HYPERLINK` environment variable can be used to override the
+/// detection, e.g. `FORCE_HYPERLINK=0`.
+pub fn supports_hyperlinks(stream: &impl std::io::IsTerminal) -> bool {
+  if let Ok(value) = std::env::var("FORCE_HYPERLINK") {
+    return !value.is_empty() && value != "0";
+  }
+  if !use_color() || !stream.is_terminal() {
+    return false;
+  }
+  if std::env::var_os("WT_SESSION").is_some()
+    || std::env::var_os("DOMTERM").is_some()
+  {
+    return true;
+  }
+  if let Ok(term_program) = std::env::var("TERM_PROGRAM") {
+    if matches!(
+      term_program.as_str(),
+      "iTerm.app" | "WezTerm" | "vscode" | "ghostty" | "Hyper"
+    ) {
+      return true;
+    }
+  }
+  if let Ok(term) = std::env::var("TERM") {
+    if term == "xterm-kitty" || term == "alacritty" {
+      return true;
+    }
+  }
+  // VTE based terminals (e.g. GNOME Terminal) support hyperlinks since 0.50
+  std::env::var("VTE_VERSION")
+    .ok()
+    .and_then(|v| v.parse::<u32>().ok())
+    .map(|v| v >= 5000)
+    .unwrap_or(false)
+}
+
+/// Wrap `text` in an OSC 8 hyperlink pointing to `url`.
+fn hyperlink(url: &str, text: &str) -> String {
+  format!("\x1b]8;;{url}\x1b\\{text}\x1b]8;;\x1b\\")
+}
+
+static FORMAT_JS_ERROR_OPTIONS: Lazy<RwLock<FormatJsErrorOptions>> =
+  Lazy::new(Default::default);
+
+/// Set the options used by [`format_js_error`]. This is expected to be called
+/// during startup, before any error is formatted.
+pub fn set_format_js_error_options(options: FormatJsErrorOptions) {
+  *FORMAT_JS_ERROR_OPTIONS.write().unwrap() = options;
+}
+
+/// Get the options set by [`set_format_js_error_options`].
+pub fn format_js_error_options() -> FormatJsErrorOptions {
+  FORMAT_JS_ERROR_OPTIONS.read().unwrap().clone()
+}
 
 #[derive(Debug, Clone)]
 struct ErrorReference<'a> {
@@ -23,35 +365,59 @@ struct IndexedErrorReference<'a> {
 
 // Keep in sync with `/core/error.js`.
 pub fn format_location(frame: &JsStackFrame) -> String {
+  let options = format_js_error_options();
+  format_location_with_theme(frame, &options.theme, options.hyperlinks)
+}
+
+fn format_location_with_theme(
+  frame: &JsStackFrame,
+  theme: &ErrorTheme,
+  hyperlinks: bool,
+) -> String {
   let _internal = frame
     .file_name
     .as_ref()
     .map(|f| f.starts_with("ext:"))
     .unwrap_or(false);
   if frame.is_native {
-    return cyan("native").to_string();
+    return theme.location("native");
   }
   let mut result = String::new();
   let file_name = frame.file_name.clone().unwrap_or_default();
   if !file_name.is_empty() {
-    result += &cyan(&format_file_name(&file_name)).to_string();
+    result += &theme.location(&format_file_name(&file_name));
   } else {
     if frame.is_eval {
-      result +=
-        &(cyan(&frame.eval_origin.as_ref().unwrap()).to_string() + ", ");
+      result += &(theme.location(frame.eval_origin.as_ref().unwrap()) + ", ");
     }
-    result += &cyan("<anonymous>").to_string();
+    result += &theme.location("<anonymous>");
   }
   if let Some(line_number) = frame.line_number {
-    write!(result, ":{}", yellow(&line_number.to_string())).unwrap();
+    write!(result, ":{}", theme.line_number(&line_number.to_string())).unwrap();
     if let Some(column_number) = frame.column_number {
-      write!(result, ":{}", yellow(&column_number.to_string())).unwrap();
+      write!(result, ":{}", theme.line_number(&column_number.to_string()))
+        .unwrap();
     }
   }
+  if hyperlinks
+    && (file_name.starts_with("file:")
+      || file_name.starts_with("http:")
+      || file_name.starts_with("https:"))
+  {
+    let url = match frame.line_number {
+      Some(line_number) => format!("{file_name}:{line_number}"),
+      None => file_name,
+    };
+    return hyperlink(&url, &result);
+  }
   result
 }
 
-fn format_frame(frame: &JsStackFrame) -> String {
+fn format_frame(
+  frame: &JsStackFrame,
+  theme: &ErrorTheme,
+  hyperlinks: bool,
+) -> String {
   let _internal = frame
     .file_name
     .as_ref()
@@ -64,11 +430,10 @@ fn format_frame(frame: &JsStackFrame) -> String {
     result += "async ";
   }
   if frame.is_promise_all {
-    result += &italic_bold(&format!(
+    result += &theme.function_name(&format!(
       "Promise.all (index {})",
       frame.promise_index.unwrap_or_default()
-    ))
-    .to_string();
+    ));
     return result;
   }
   if is_method_call {
@@ -95,31 +460,179 @@ fn format_frame(frame: &JsStackFrame) -> String {
         formatted_method += "<anonymous>";
       }
     }
-    result += &italic_bold(&formatted_method).to_string();
+    result += &theme.function_name(&formatted_method);
   } else if frame.is_constructor {
     result += "new ";
     if let Some(function_name) = &frame.function_name {
-      write!(result, "{}", italic_bold(&function_name)).unwrap();
+      result += &theme.function_name(function_name);
     } else {
-      result += &cyan("<anonymous>").to_string();
+      result += &theme.location("<anonymous>");
     }
   } else if let Some(function_name) = &frame.function_name {
-    result += &italic_bold(&function_name).to_string();
+    result += &theme.function_name(function_name);
   } else {
-    result += &format_location(frame);
+    result += &format_location_with_theme(frame, theme, hyperlinks);
     return result;
   }
-  write!(result, " ({})", format_location(frame)).unwrap();
+  write!(
+    result,
+    " ({})",
+    format_location_with_theme(frame, theme, hyperlinks)
+  )
+  .unwrap();
   result
 }
 
+/// Source lines surrounding the line an error was thrown from.
+#[derive(Debug, Clone, Default)]
+struct SourceContext {
+  /// 1-based line number of the line the error was thrown from.
+  line_number: usize,
+  before: Vec<String>,
+  after: Vec<String>,
+}
+
+/// Read the lines surrounding the source line of `frame` from disk. Only local
+/// files are supported, and nothing is returned if the file changed since the
+/// error's source line was captured.
+fn load_source_context(
+  frame: &JsStackFrame,
+  source_line: &str,
+  context_lines: usize,
+) -> Option<SourceContext> {
+  if context_lines == 0 {
+    return None;
+  }
+  let line_number = usize::try_from(frame.line_number?).ok()?;
+  let index = line_number.checked_sub(1)?;
+  let path = Url::parse(frame.file_name.as_ref()?)
+    .ok()?
+    .to_file_path()
+    .ok()?;
+  let text = std::fs::read_to_string(path).ok()?;
+  let lines = text
+    .split('\n')
+    .map(|line| line.strip_suffix('\r').unwrap_or(line))
+    .collect::<Vec<_>>();
+  if *lines.get(index)? != source_line {
+    return None;
+  }
+  let start = index.saturating_sub(context_lines);
+  let end = (index + context_lines + 1).min(lines.len());
+  Some(SourceContext {
+    line_number,
+    before: lines[start..index].iter().map(|l| l.to_string()).collect(),
+    after: lines[index + 1..end]
+      .iter()
+      .map(|l| l.to_string())
+      .collect(),
+  })
+}
+
+/// Render `source_line` as a code frame with a line number gutter, the lines
+/// of `context` around it and the underline below it.
+fn format_source_context(
+  source_line: &str,
+  color_underline: &str,
+  context: &SourceContext,
+  indent: &str,
+) -> String {
+  let first_line_number = context.line_number - context.before.len();
+  let last_line_number = context.line_number + context.after.len();
+  let width = last_line_number.to_string().len();
+  let mut s = String::new();
+  let mut line_number = first_line_number;
+  for line in &context.before {
+    let gutter = format!("  {line_number:>width$} |");
+    write!(s, "\n{indent}{} {line}", gray(gutter)).unwrap();
+    line_number += 1;
+  }
+  let gutter = format!("{line_number:>width$} |");
+  write!(s, "\n{indent}{} {} {source_line}", red_bold(">"), gutter).unwrap();
+  let gutter = format!("  {:>width$} |", "");
+  write!(s, "\n{indent}{} {color_underline}", gray(gutter)).unwrap();
+  for line in &context.after {
+    line_number += 1;
+    let gutter = format!("  {line_number:>width$} |");
+    write!(s, "\n{indent}{} {line}", gray(gutter)).unwrap();
+  }
+  s
+}
+
+/// Runs of identical frames at least this long are collapsed into a single
+/// frame followed by a summary line.
+const MIN_COLLAPSED_FRAMES: usize = 3;
+
+/// Format stack frames, collapsing runs of identical frames (e.g. caused by
+/// deep recursion) into `... N more frames like this ...`.
+fn format_frames(frames: &[JsStackFrame], hyperlinks: bool) -> String {
+  let options = FormatJsErrorOptions {
+    hyperlinks,
+    ..format_js_error_options()
+  };
+  format_frames_with_options(frames, &options)
+}
+
+/// Like [`format_frames`], but also replaces runs of frames matching one of
+/// [`FormatJsErrorOptions::hidden_frames`] with `... N hidden frames ...`.
+/// Frames are never hidden if that would leave no frame to display.
+fn format_frames_with_options(
+  frames: &[JsStackFrame],
+  options: &FormatJsErrorOptions,
+) -> String {
+  let (theme, hyperlinks) = (&options.theme, options.hyperlinks);
+  let is_hidden = |frame: &JsStackFrame| {
+    options
+      .hidden_frames
+      .iter()
+      .any(|category| category.matches(frame))
+  };
+  let hide_frames = !frames.iter().all(is_hidden);
+  let mut s = String::new();
+  let mut i = 0;
+  while i < frames.len() {
+    let frame = &frames[i];
+    if hide_frames && is_hidden(frame) {
+      let hidden = frames[i..].iter().take_while(|f| is_hidden(*f)).count();
+      let noun = if hidden == 1 { "frame" } else { "frames" };
+      write!(
+        s,
+        "\n    {}",
+        gray(format!("... {hidden} hidden {noun} ..."))
+      )
+      .unwrap();
+      i += hidden;
+      continue;
+    }
+    let run_len = frames[i..].iter().take_while(|f| *f == frame).count();
+    write!(s, "\n    at {}", format_frame(frame, theme, hyperlinks)).unwrap();
+    if run_len >= MIN_COLLAPSED_FRAMES {
+      write!(
+        s,
+        "\n    {}",
+        gray(format!("... {} more frames like this ...", run_len - 1))
+      )
+      .unwrap();
+    } else {
+      for frame in &frames[i + 1..i + run_len] {
+        write!(s, "\n    at {}", format_frame(frame, theme, hyperlinks))
+          .unwrap();
+      }
+    }
+    i += run_len;
+  }
+  s
+}
+
 /// Take an optional source line and associated information to format it into
-/// a pretty printed version of that line.
+/// a pretty printed version of that line. If `context` is provided, the
+/// surrounding lines are rendered as well, with line numbers in a gutter.
 fn format_maybe_source_line(
   source_line: Option<&str>,
   column_number: Option<i64>,
   is_error: bool,
   level: usize,
+  context: Option<&SourceContext>,
 ) -> String {
   if source_line.is_none() || column_number.is_none() {
     return "".to_string();
@@ -154,22 +667,37 @@ fn format_maybe_source_line(
   }
   s.push('^');
   let color_underline = if is_error {
-    red(&s).to_string()
+    format_js_error_options().theme.underline(&s)
   } else {
     cyan(&s).to_string()
   };
 
   let indent = format!("{:indent$}", "", indent = level);
 
+  if let Some(context) = context {
+    return format_source_context(
+      source_line,
+      &color_underline,
+      context,
+      &indent,
+    );
+  }
+
   format!("\n{indent}{source_line}\n{indent}{color_underline}")
 }
 
 fn find_recursive_cause(js_error: &JsError) -> Option<ErrorReference> {
+  let max_cause_depth = format_js_error_options().max_cause_depth;
   let mut history = Vec::<&JsError>::new();
 
   let mut current_error: &JsError = js_error;
 
   while let Some(cause) = &current_error.cause {
+    // Causes past the maximum depth aren't formatted, so a cycle among them
+    // doesn't need to be labeled.
+    if history.len() >= max_cause_depth {
+      break;
+    }
     history.push(current_error);
 
     if let Some(seen) = history.iter().find(|&el| cause.is_same_error(el)) {
@@ -185,14 +713,18 @@ fn find_recursive_cause(js_error: &JsError) -> Option<ErrorReference> {
   None
 }
 
+/// Format the `errors` of an `AggregateError`. Each child error is labeled
+/// with its index and indented under the parent, including its own stack,
+/// cause chain and nested aggregated errors.
 fn format_aggregated_error(
-  aggregated_errors: &Vec<JsError>,
+  aggregated_errors: &[JsError],
   circular_reference_index: usize,
+  hyperlinks: bool,
 ) -> String {
   let mut s = String::new();
   let mut nested_circular_reference_index = circular_reference_index;
 
-  for js_error in aggregated_errors {
+  for (index, js_error) in aggregated_errors.iter().enumerate() {
     let aggregated_circular = find_recursive_cause(js_error);
     if aggregated_circular.is_some() {
       nested_circular_reference_index += 1;
@@ -204,9 +736,15 @@ fn format_aggregated_error(
         index: nested_circular_reference_index,
       }),
       false,
+      0,
+      hyperlinks,
     );
 
-    for line in error_string.trim_start_matches("Uncaught ").lines() {
+    let mut lines = error_string.trim_start_matches("Uncaught ").lines();
+    if let Some(first_line) = lines.next() {
+      write!(s, "\n    {} {first_line}", gray(format!("[{index}]"))).unwrap();
+    }
+    for line in lines {
       write!(s, "\n    {line}").unwrap();
     }
   }
@@ -214,10 +752,25 @@ fn format_aggregated_error(
   s
 }
 
+/// Count the errors in the cause chain starting at `cause`.
+fn count_causes(cause: &JsError) -> usize {
+  std::iter::successors(Some(cause), |e| e.cause.as_deref()).count()
+}
+
+fn format_more_causes(count: usize) -> String {
+  let s = if count == 1 { "" } else { "s" };
+  gray(format!("... {count} more cause{s}")).to_string()
+}
+
+/// Format `js_error`, including its cause chain. `depth` is the number of
+/// errors this error is the cause of, used to stop formatting causes past
+/// [`FormatJsErrorOptions::max_cause_depth`].
 fn format_js_error_inner(
   js_error: &JsError,
   circular: Option<IndexedErrorReference>,
   include_source_code: bool,
+  depth: usize,
+  hyperlinks: bool,
 ) -> String {
   let mut s = String::new();
 
@@ -229,6 +782,28 @@ fn format_js_error_inner(
     }
   }
 
+  let source_line_frame = js_error
+    .source_line_frame_index
+    .and_then(|i| js_error.frames.get(i));
+  let column_number = source_line_frame.and_then(|f| f.column_number);
+  let source_line = if include_source_code {
+    js_error.source_line.as_deref()
+  } else {
+    None
+  };
+  let context = source_line
+    .zip(source_line_frame)
+    .and_then(|(line, frame)| {
+      load_source_context(frame, line, format_js_error_options().context_lines)
+    });
+  s.push_str(&format_maybe_source_line(
+    source_line,
+    column_number,
+    true,
+    0,
+    context.as_ref(),
+  ));
+  s.push_str(&format_frames(&js_error.frames, hyperlinks));
   if let Some(aggregated) = &js_error.aggregated {
     let aggregated_message = format_aggregated_error(
       aggregated,
@@ -236,57 +811,251 @@ fn format_js_error_inner(
         .as_ref()
         .map(|circular| circular.index)
         .unwrap_or(0),
+      hyperlinks,
     );
     s.push_str(&aggregated_message);
   }
-
-  let column_number = js_error
-    .source_line_frame_index
-    .and_then(|i| js_error.frames.get(i).unwrap().column_number);
-  s.push_str(&format_maybe_source_line(
-    if include_source_code {
-      js_error.source_line.as_deref()
-    } else {
-      None
-    },
-    column_number,
-    true,
-    0,
-  ));
-  for frame in &js_error.frames {
-    write!(s, "\n    at {}", format_frame(frame)).unwrap();
-  }
   if let Some(cause) = &js_error.cause {
     let is_caused_by_circular = circular
       .as_ref()
       .map(|circular| js_error.is_same_error(circular.reference.from))
       .unwrap_or(false);
 
-    let error_string = if is_caused_by_circular {
-      cyan(format!("[Circular *{}]", circular.unwrap().index)).to_string()
+    if is_caused_by_circular {
+      let error_string =
+        cyan(format!("[Circular *{}]", circular.unwrap().index));
+      write!(s, "\nCaused by: {error_string}").unwrap();
+    } else if depth >= format_js_error_options().max_cause_depth {
+      write!(s, "\n{}", format_more_causes(count_causes(cause))).unwrap();
     } else {
-      format_js_error_inner(cause, circular, false)
-    };
+      let error_string =
+        format_js_error_inner(cause, circular, false, depth + 1, hyperlinks);
+      write!(
+        s,
+        "\nCaused by: {}",
+        error_string.trim_start_matches("Uncaught ")
+      )
+      .unwrap();
+    }
+  }
+  s
+}
+
+/// Known error messages and the hint displayed for each of them. Unstable
+/// APIs are handled separately in [`get_unstable_api_hint`].
+const ERROR_HINTS: &[(&str, &str)] = &[
+  (
+    "ReferenceError: require is not defined",
+    "Use `import` instead of `require`, or rename the file to use the .cjs extension if it is a CommonJS module.",
+  ),
+  (
+    "ReferenceError: module is not defined",
+    "`module.exports` is only available in CommonJS modules. Use `export` instead, or rename the file to use the .cjs extension.",
+  ),
+  (
+    "ReferenceError: exports is not defined",
+    "`exports` is only available in CommonJS modules. Use `export` instead, or rename the file to use the .cjs extension.",
+  ),
+  (
+    "ReferenceError: __dirname is not defined",
+    "Use `import.meta.dirname` instead of `__dirname` in ES modules.",
+  ),
+  (
+    "ReferenceError: __filename is not defined",
+    "Use `import.meta.filename` instead of `__filename` in ES modules.",
+  ),
+  (
+    "await is only valid in async functions and the top level bodies of modules",
+    "Top-level await is not supported in CommonJS modules. Convert the module to an ES module, for example by renaming it to use the .mjs extension.",
+  ),
+];
+
+/// Unstable `Deno` APIs and the `--unstable-*` flag that enables them.
+const UNSTABLE_API_HINTS: &[(&str, &str)] = &[
+  ("Deno.openKv", "kv"),
+  ("Deno.cron", "cron"),
+  ("Deno.dlopen", "ffi"),
+];
+
+fn get_unstable_api_hint(message: &str) -> Option<String> {
+  UNSTABLE_API_HINTS
+    .iter()
+    .find(|(api_name, _)| {
+      message.contains(&format!("{api_name} is not a function"))
+    })
+    .map(|(api_name, feature)| {
+      format!(
+        "`{api_name}` is an unstable API. Run again with the `--unstable-{feature}` flag to enable it."
+      )
+    })
+}
+
+fn get_permission_hint(js_error: &JsError) -> Option<String> {
+  if js_error.name.as_deref() != Some("PermissionDenied") {
+    return None;
+  }
+  // The message looks like:
+  // Requires net access to "deno.land:443", run again with the --allow-net flag
+  let message = js_error.message.as_deref()?;
+  let (_, rest) = message.split_once("Requires ")?;
+  let (access, rest) = rest.split_once(" access to \"")?;
+  let (descriptor, _) = rest.split_once('"')?;
+  let flag = match access {
+    "net" | "read" | "write" | "env" | "run" | "sys" | "ffi" => access,
+    _ => return None,
+  };
+  Some(format!(
+    "Use `--allow-{flag}={descriptor}` to grant access to only this resource, or `--allow-{flag}` to grant full {flag} access."
+  ))
+}
+
+fn get_cannot_find_module_hint(message: &str) -> Option<String> {
+  let (_, rest) = message.split_once("Cannot find module '")?;
+  let (specifier, _) = rest.split_once('\'')?;
+  if specifier.starts_with('.')
+    || specifier.starts_with('/')
+    || specifier.contains(':')
+  {
+    return Some(
+      "Make sure the file exists and the path is relative to the importing module, including its extension.".to_string(),
+    );
+  }
+  Some(format!(
+    "If you are trying to import an npm package, use the `npm:` prefix (e.g. `npm:{specifier}`) or add it to your package.json."
+  ))
+}
+
+/// Get actionable suggestions on how to fix a well-known error. These are
+/// rendered as `hint:` lines after the formatted stack trace.
+pub fn get_error_hints(js_error: &JsError) -> Vec<String> {
+  let message = &js_error.exception_message;
+  let mut hints = ERROR_HINTS
+    .iter()
+    .filter(|(pattern, _)| message.contains(pattern))
+    .map(|(_, hint)| hint.to_string())
+    .collect::<Vec<_>>();
+  hints.extend(get_unstable_api_hint(message));
+  hints.extend(get_permission_hint(js_error));
+  hints.extend(get_cannot_find_module_hint(message));
+  hints
+}
 
-    write!(
-      s,
-      "\nCaused by: {}",
-      error_string.trim_start_matches("Uncaught ")
-    )
-    .unwrap();
+fn format_error_hints(hints: &[String]) -> String {
+  let mut s = String::new();
+  for hint in hints {
+    write!(s, "\n    {} {hint}", cyan_bold("hint:")).unwrap();
   }
   s
 }
 
-/// Format a [`JsError`] for terminal output.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JsonJsError<'a> {
+  name: Option<&'a str>,
+  message: &'a str,
+  source_line: Option<&'a str>,
+  frames: &'a [JsStackFrame],
+  #[serde(skip_serializing_if = "Option::is_none")]
+  cause: Option<Box<JsonJsError<'a>>>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  aggregated: Option<Vec<JsonJsError<'a>>>,
+  /// Set when this error was already reported further up the cause chain.
+  #[serde(skip_serializing_if = "std::ops::Not::not")]
+  circular: bool,
+  /// Number of causes left out past the maximum cause depth.
+  #[serde(skip_serializing_if = "Option::is_none")]
+  more_causes: Option<usize>,
+  #[serde(skip_serializing_if = "Vec::is_empty")]
+  hints: Vec<String>,
+}
+
+fn to_json_js_error<'a>(
+  js_error: &'a JsError,
+  history: &mut Vec<&'a JsError>,
+  include_source_code: bool,
+) -> JsonJsError<'a> {
+  if history.iter().any(|seen| js_error.is_same_error(seen)) {
+    return JsonJsError {
+      name: js_error.name.as_deref(),
+      message: &js_error.exception_message,
+      source_line: None,
+      frames: &[],
+      cause: None,
+      aggregated: None,
+      circular: true,
+      more_causes: None,
+      hints: Vec::new(),
+    };
+  }
+  history.push(js_error);
+  let aggregated = js_error.aggregated.as_ref().map(|aggregated| {
+    aggregated
+      .iter()
+      .map(|e| to_json_js_error(e, &mut Vec::new(), false))
+      .collect()
+  });
+  // `history` holds this error and the errors it is the cause of.
+  let max_cause_depth = format_js_error_options().max_cause_depth;
+  let (cause, more_causes) = match &js_error.cause {
+    Some(cause) if history.len() > max_cause_depth => {
+      (None, Some(count_causes(cause)))
+    }
+    Some(cause) => (
+      Some(Box::new(to_json_js_error(cause, history, false))),
+      None,
+    ),
+    None => (None, None),
+  };
+  JsonJsError {
+    name: js_error.name.as_deref(),
+    message: &js_error.exception_message,
+    source_line: if include_source_code {
+      js_error.source_line.as_deref()
+    } else {
+      None
+    },
+    frames: &js_error.frames,
+    cause,
+    aggregated,
+    circular: false,
+    more_causes,
+    hints: Vec::new(),
+  }
+}
+
+/// Format a [`JsError`] as a single line of JSON, including its stack frames,
+/// source line, aggregated errors and cause chain.
+pub fn format_js_error_json(js_error: &JsError) -> String {
+  let mut json_error = to_json_js_error(js_error, &mut Vec::new(), true);
+  json_error.hints = get_error_hints(js_error);
+  serde_json::to_string(&json_error).unwrap()
+}
+
+/// Format a [`JsError`] for terminal output on stderr.
 pub fn format_js_error(js_error: &JsError) -> String {
+  format_js_error_with_hyperlinks(
+    js_error,
+    format_js_error_options().hyperlinks,
+  )
+}
+
+/// Like [`format_js_error`], for output that isn't written to stderr. File
+/// locations are only wrapped in OSC 8 hyperlinks if `hyperlinks` is set,
+/// which should be decided with [`supports_hyperlinks`] for the stream the
+/// output is written to.
+pub fn format_js_error_with_hyperlinks(
+  js_error: &JsError,
+  hyperlinks: bool,
+) -> String {
   let circular =
     find_recursive_cause(js_error).map(|reference| IndexedErrorReference {
       reference,
       index: 1,
     });
 
-  format_js_error_inner(js_error, circular, true)
+  let mut s = format_js_error_inner(js_error, circular, true, 0, hyperlinks);
+  s.push_str(&format_error_hints(&get_error_hints(js_error)));
+  s
 }
 
 #[cfg(test)]
@@ -296,17 +1065,281 @@ mod tests {
 
   #[test]
   fn test_format_none_source_line() {
-    let actual = format_maybe_source_line(None, None, false, 0);
+    let actual = format_maybe_source_line(None, None, false, 0, None);
     assert_eq!(actual, "");
   }
 
   #[test]
   fn test_format_some_source_line() {
-    let actual =
-      format_maybe_source_line(Some("console.log('foo');"), Some(9), true, 0);
+    let actual = format_maybe_source_line(
+      Some("console.log('foo');"),
+      Some(9),
+      true,
+      0,
+      None,
+    );
     assert_eq!(
       strip_ansi_codes(&actual),
       "\nconsole.log(\'foo\');\n        ^"
     );
   }
+
+  fn frame(function_name: &str, line_number: i64) -> JsStackFrame {
+    let mut frame = JsStackFrame::from_location(
+      Some("file:///a.js".to_string()),
+      Some(line_number),
+      Some(1),
+    );
+    frame.function_name = Some(function_name.to_string());
+    frame
+  }
+
+  #[test]
+  fn test_format_location_hyperlink() {
+    let frame = frame("main", 5);
+    let theme = ErrorTheme::default();
+    let actual = format_location_with_theme(&frame, &theme, true);
+    let expected = format_location_with_theme(&frame, &theme, false);
+    assert_eq!(actual, hyperlink("file:///a.js:5", &expected));
+
+    let mut frame = frame;
+    frame.file_name = Some("ext:runtime/01_errors.js".to_string());
+    let actual = format_location_with_theme(&frame, &theme, true);
+    assert!(!actual.contains("\x1b]8;;"));
+  }
+
+  #[test]
+  fn test_format_frames_collapses_repeated_frames() {
+    let mut frames = vec![frame("recurse", 2); 512];
+    frames.push(frame("main", 5));
+    frames.push(frame("main", 5));
+    let actual = strip_ansi_codes(&format_frames(&frames, false)).to_string();
+    assert_eq!(
+      actual,
+      concat!(
+        "\n    at recurse (file:///a.js:2:1)",
+        "\n    ... 511 more frames like this ...",
+        "\n    at main (file:///a.js:5:1)",
+        "\n    at main (file:///a.js:5:1)",
+      )
+    );
+  }
+
+  #[test]
+  fn test_format_frames_hides_dependency_frames() {
+    let mut dependency = frame("dependency", 1);
+    dependency.file_name =
+      Some("file:///project/node_modules/dep/index.js".to_string());
+    let mut vendored = frame("vendored", 1);
+    vendored.file_name =
+      Some("file:///project/vendor/deno.land/x/mod.ts".to_string());
+    let frames = vec![
+      dependency.clone(),
+      dependency.clone(),
+      frame("main", 5),
+      vendored.clone(),
+    ];
+    let options = FormatJsErrorOptions {
+      hidden_frames: vec![
+        StackFrameCategory::NodeModules,
+        StackFrameCategory::Vendor,
+      ],
+      ..Default::default()
+    };
+    let actual = format_frames_with_options(&frames, &options);
+    assert_eq!(
+      strip_ansi_codes(&actual),
+      concat!(
+        "\n    ... 2 hidden frames ...",
+        "\n    at main (file:///a.js:5:1)",
+        "\n    ... 1 hidden frame ...",
+      )
+    );
+
+    // frames are all shown if every one of them would be hidden
+    let frames = vec![dependency];
+    let actual = format_frames_with_options(&frames, &options);
+    assert_eq!(
+      strip_ansi_codes(&actual),
+      "\n    at dependency (file:///project/node_modules/dep/index.js:1:1)"
+    );
+  }
+
+  #[test]
+  fn test_format_source_line_with_context() {
+    let context = SourceContext {
+      line_number: 9,
+      before: vec!["const a = 1;".to_string(), "".to_string()],
+      after: vec!["a;".to_string()],
+    };
+    let actual = format_maybe_source_line(
+      Some("throw new Error();"),
+      Some(1),
+      true,
+      0,
+      Some(&context),
+    );
+    assert_eq!(
+      strip_ansi_codes(&actual),
+      concat!(
+        "\n   7 | const a = 1;",
+        "\n   8 | ",
+        "\n>  9 | throw new Error();",
+        "\n     | ^",
+        "\n  10 | a;",
+      )
+    );
+  }
+
+  fn js_error_with_message(name: &str, message: &str) -> JsError {
+    JsError {
+      name: Some(name.to_string()),
+      message: Some(message.to_string()),
+      stack: None,
+      cause: None,
+      exception_message: format!("Uncaught {name}: {message}"),
+      frames: vec![],
+      source_line: None,
+      source_line_frame_index: None,
+      aggregated: None,
+    }
+  }
+
+  #[test]
+  fn test_error_theme_from_str() {
+    assert_eq!(ErrorTheme::from_str("").unwrap(), ErrorTheme::default());
+    assert_eq!(
+      ErrorTheme::from_str("high-contrast").unwrap(),
+      ErrorTheme::high_contrast()
+    );
+    assert_eq!(
+      ErrorTheme::from_str("no-italic, location=blue,lineNumber=magenta")
+        .unwrap(),
+      ErrorTheme {
+        location: ThemeColor::Blue,
+        line_number: ThemeColor::Magenta,
+        ..ErrorTheme::no_italic()
+      }
+    );
+    assert!(ErrorTheme::from_str("location=purple").is_err());
+    assert!(ErrorTheme::from_str("unknown").is_err());
+    assert!(ErrorTheme::from_str("location=blue,no-italic").is_err());
+  }
+
+  #[test]
+  fn test_format_aggregated_error() {
+    let mut error = js_error_with_message("AggregateError", "Multiple errors");
+    let mut child = js_error_with_message("Error", "first");
+    child.exception_message = "Error: first".to_string();
+    child.frames = vec![frame("first", 2)];
+    let mut nested = js_error_with_message("AggregateError", "");
+    nested.exception_message = "AggregateError".to_string();
+    nested.aggregated = Some(vec![child.clone()]);
+    error.frames = vec![frame("main", 1)];
+    error.aggregated = Some(vec![child, nested]);
+    assert_eq!(
+      strip_ansi_codes(&format_js_error_inner(&error, None, false, 0, false)),
+      concat!(
+        "Uncaught AggregateError: Multiple errors",
+        "\n    at main (file:///a.js:1:1)",
+        "\n    [0] Error: first",
+        "\n        at first (file:///a.js:2:1)",
+        "\n    [1] AggregateError",
+        "\n        [0] Error: first",
+        "\n            at first (file:///a.js:2:1)",
+      )
+    );
+  }
+
+  #[test]
+  fn test_format_js_error_limits_cause_depth() {
+    let depth = DEFAULT_MAX_CAUSE_DEPTH + 5;
+    let mut error = js_error_with_message("Error", &depth.to_string());
+    for i in (0..depth).rev() {
+      let mut parent = js_error_with_message("Error", &i.to_string());
+      parent.cause = Some(Box::new(error));
+      error = parent;
+    }
+    let actual = strip_ansi_codes(&format_js_error(&error)).to_string();
+    assert_eq!(
+      actual.matches("Caused by:").count(),
+      DEFAULT_MAX_CAUSE_DEPTH
+    );
+    assert!(actual.ends_with("Caused by: Error: 20\n... 5 more causes"));
+  }
+
+  #[test]
+  fn test_get_error_hints() {
+    let error =
+      js_error_with_message("ReferenceError", "require is not defined");
+    assert_eq!(get_error_hints(&error).len(), 1);
+
+    let error = js_error_with_message(
+      "PermissionDenied",
+      "Requires net access to \"deno.land:443\", run again with the --allow-net flag",
+    );
+    assert_eq!(
+      get_error_hints(&error),
+      vec!["Use `--allow-net=deno.land:443` to grant access to only this resource, or `--allow-net` to grant full net access.".to_string()]
+    );
+
+    let error = js_error_with_message("Error", "Cannot find module 'chalk'");
+    assert_eq!(
+      get_error_hints(&error),
+      vec!["If you are trying to import an npm package, use the `npm:` prefix (e.g. `npm:chalk`) or add it to your package.json.".to_string()]
+    );
+
+    let error =
+      js_error_with_message("TypeError", "Deno.openKv is not a function");
+    assert_eq!(
+      get_error_hints(&error),
+      vec!["`Deno.openKv` is an unstable API. Run again with the `--unstable-kv` flag to enable it.".to_string()]
+    );
+
+    let error = js_error_with_message("Error", "boom");
+    assert!(get_error_hints(&error).is_empty());
+  }
+
+  #[test]
+  fn test_format_js_error_json() {
+    let cause = JsError {
+      name: Some("TypeError".to_string()),
+      message: Some("inner".to_string()),
+      stack: None,
+      cause: None,
+      exception_message: "TypeError: inner".to_string(),
+      frames: vec![],
+      source_line: None,
+      source_line_frame_index: None,
+      aggregated: None,
+    };
+    let error = JsError {
+      name: Some("Error".to_string()),
+      message: Some("outer".to_string()),
+      stack: None,
+      cause: Some(Box::new(cause)),
+      exception_message: "Uncaught Error: outer".to_string(),
+      frames: vec![],
+      source_line: Some("throw new Error('outer');".to_string()),
+      source_line_frame_index: None,
+      aggregated: None,
+    };
+    let actual: serde_json::Value =
+      serde_json::from_str(&format_js_error_json(&error)).unwrap();
+    assert_eq!(
+      actual,
+      serde_json::json!({
+        "name": "Error",
+        "message": "Uncaught Error: outer",
+        "sourceLine": "throw new Error('outer');",
+        "frames": [],
+        "cause": {
+          "name": "TypeError",
+          "message": "TypeError: inner",
+          "sourceLine": null,
+          "frames": [],
+        },
+      })
+    );
+  }
 }
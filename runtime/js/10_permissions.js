@@ -290,6 +290,9 @@ function serializePermissions(permissions) {
         serializedPermissions[key] = permissions[key];
       }
     }
+    if (permissions.prompt !== undefined) {
+      serializedPermissions.prompt = permissions.prompt;
+    }
     return serializedPermissions;
   }
   return permissions;
@@ -6,6 +6,7 @@ import {
   op_host_post_message,
   op_host_recv_ctrl,
   op_host_recv_message,
+  op_host_take_error,
   op_host_terminate_worker,
 } from "ext:core/ops";
 const {
@@ -200,7 +201,9 @@ class Worker extends EventTarget {
           this.#status = "CLOSED";
         } /* falls through */
         case 2: { // Error
-          if (!this.#handleError(data)) {
+          const handled = this.#handleError(data);
+          op_host_take_error(this.#id, handled);
+          if (!handled) {
             throw new Error("Unhandled error in child worker.");
           }
           break;
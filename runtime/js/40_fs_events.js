@@ -27,8 +27,15 @@ class FsWatcher {
         value: undefined,
       });
     }
-    const { recursive } = options;
-    this.#rid = op_fs_events_open({ recursive, paths });
+    const { recursive, include, exclude, debounce } = options;
+    this.#rid = op_fs_events_open({
+      recursive,
+      paths,
+      include,
+      exclude,
+      debounce,
+    });
+    internals.traceResourceCreation?.(this.#rid);
   }
 
   get rid() {
@@ -75,6 +75,7 @@ class Process {
   constructor(res) {
     this.rid = res.rid;
     this.pid = res.pid;
+    internals.traceResourceCreation?.(res.rid);
 
     if (res.stdinRid && res.stdinRid > 0) {
       this.stdin = new FsFile(res.stdinRid, SymbolFor("Deno.internal.FsFile"));
@@ -180,6 +181,7 @@ function spawnChildInner(opFn, command, apiName, {
   stderr = "piped",
   signal = undefined,
   windowsRawArguments = false,
+  detached = false,
   ipc = -1,
 } = {}) {
   const child = opFn({
@@ -194,11 +196,13 @@ function spawnChildInner(opFn, command, apiName, {
     stdout,
     stderr,
     windowsRawArguments,
+    detached,
     ipc,
   }, apiName);
   return new ChildProcess(illegalConstructorKey, {
     ...child,
     signal,
+    detached,
   });
 }
 
@@ -229,6 +233,7 @@ class ChildProcess {
   #rid;
   #waitPromise;
   #waitComplete = false;
+  #detached;
 
   [_pipeFd];
 
@@ -263,6 +268,7 @@ class ChildProcess {
 
   constructor(key = null, {
     signal,
+    detached = false,
     rid,
     pid,
     stdinRid,
@@ -276,17 +282,22 @@ class ChildProcess {
 
     this.#rid = rid;
     this.#pid = pid;
+    this.#detached = detached;
     this[_pipeFd] = pipeFd;
+    internals.traceResourceCreation?.(rid);
 
     if (stdinRid !== null) {
+      internals.traceResourceCreation?.(stdinRid);
       this.#stdin = writableStreamForRid(stdinRid);
     }
 
     if (stdoutRid !== null) {
+      internals.traceResourceCreation?.(stdoutRid);
       this.#stdout = readableStreamForRidUnrefable(stdoutRid);
     }
 
     if (stderrRid !== null) {
+      internals.traceResourceCreation?.(stderrRid);
       this.#stderr = readableStreamForRidUnrefable(stderrRid);
     }
 
@@ -344,16 +355,21 @@ class ChildProcess {
     };
   }
 
-  kill(signo = "SIGTERM") {
+  kill(signo = "SIGTERM", { processGroup = false } = {}) {
     if (this.#waitComplete) {
       throw new TypeError("Child process has already terminated.");
     }
-    op_spawn_kill(this.#rid, signo);
+    if (processGroup && !this.#detached) {
+      throw new TypeError(
+        "Only detached child processes have a process group of their own",
+      );
+    }
+    op_spawn_kill(this.#rid, signo, processGroup);
   }
 
   async [SymbolAsyncDispose]() {
     try {
-      op_spawn_kill(this.#rid, "SIGTERM");
+      op_spawn_kill(this.#rid, "SIGTERM", false);
     } catch {
       // ignore errors from killing the process (such as ESRCH or BadResource)
     }
@@ -399,6 +415,7 @@ function spawnSync(command, {
   stdout = "piped",
   stderr = "piped",
   windowsRawArguments = false,
+  detached = false,
 } = {}) {
   if (stdin === "piped") {
     throw new TypeError(
@@ -417,6 +434,7 @@ function spawnSync(command, {
     stdout,
     stderr,
     windowsRawArguments,
+    detached,
   });
   return {
     success: result.status.success,
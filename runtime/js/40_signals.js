@@ -1,6 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
-import { core, primordials } from "ext:core/mod.js";
+import { core, internals, primordials } from "ext:core/mod.js";
 import { op_signal_bind, op_signal_poll, op_signal_unbind } from "ext:core/ops";
 const {
   SafeSet,
@@ -11,7 +11,9 @@ const {
 } = primordials;
 
 function bindSignal(signo) {
-  return op_signal_bind(signo);
+  const rid = op_signal_bind(signo);
+  internals.traceResourceCreation?.(rid);
+  return rid;
 }
 
 function pollSignal(rid) {
@@ -186,6 +186,10 @@ const denoNs = {
   },
   connect: net.connect,
   listen: net.listen,
+  listenDatagram: net.createListenDatagram(
+    op_net_listen_udp,
+    op_net_listen_unixpacket,
+  ),
   loadavg: os.loadavg,
   connectTls: tls.connectTls,
   listenTls: tls.listenTls,
@@ -285,7 +289,20 @@ denoNsUnstableById[unstableIds.fs] = {
   flockSync: fs.flockSync,
   funlock: fs.funlock,
   funlockSync: fs.funlockSync,
+  getXattr: fs.getXattr,
+  getXattrSync: fs.getXattrSync,
+  listXattrs: fs.listXattrs,
+  listXattrsSync: fs.listXattrsSync,
+  openReadableStream: fs.openReadableStream,
+  openWritableStream: fs.openWritableStream,
+  removeXattr: fs.removeXattr,
+  removeXattrSync: fs.removeXattrSync,
+  setImmutable: fs.setImmutable,
+  setImmutableSync: fs.setImmutableSync,
+  setXattr: fs.setXattr,
+  setXattrSync: fs.setXattrSync,
   umask: fs.umask,
+  withTempDir: fs.withTempDir,
 };
 
 denoNsUnstableById[unstableIds.http] = {
@@ -301,13 +318,6 @@ denoNsUnstableById[unstableIds.kv] = {
   KvListIterator: kv.KvListIterator,
 };
 
-denoNsUnstableById[unstableIds.net] = {
-  listenDatagram: net.createListenDatagram(
-    op_net_listen_udp,
-    op_net_listen_unixpacket,
-  ),
-};
-
 // denoNsUnstableById[unstableIds.unsafeProto] = {}
 
 denoNsUnstableById[unstableIds.webgpu] = {
@@ -318,10 +328,6 @@ denoNsUnstableById[unstableIds.webgpu] = {
 
 // when editing this list, also update unstableDenoProps in cli/tsc/99_main_compiler.js
 const denoNsUnstable = {
-  listenDatagram: net.createListenDatagram(
-    op_net_listen_udp,
-    op_net_listen_unixpacket,
-  ),
   umask: fs.umask,
   HttpClient: httpClient.HttpClient,
   createHttpClient: httpClient.createHttpClient,
@@ -335,6 +341,19 @@ const denoNsUnstable = {
   flockSync: fs.flockSync,
   funlock: fs.funlock,
   funlockSync: fs.funlockSync,
+  getXattr: fs.getXattr,
+  getXattrSync: fs.getXattrSync,
+  listXattrs: fs.listXattrs,
+  listXattrsSync: fs.listXattrsSync,
+  openReadableStream: fs.openReadableStream,
+  openWritableStream: fs.openWritableStream,
+  removeXattr: fs.removeXattr,
+  removeXattrSync: fs.removeXattrSync,
+  setImmutable: fs.setImmutable,
+  setImmutableSync: fs.setImmutableSync,
+  setXattr: fs.setXattr,
+  setXattrSync: fs.setXattrSync,
+  withTempDir: fs.withTempDir,
   openKv: kv.openKv,
   AtomicOperation: kv.AtomicOperation,
   Kv: kv.Kv,
@@ -0,0 +1,24 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// Stand-in for `ext:deno_canvas/01_image.js` when the runtime is built with
+// the `exclude_canvas` feature.
+
+import { primordials } from "ext:core/mod.js";
+const { Error, PromiseReject, Symbol, TypeError } = primordials;
+
+const _bitmapData = Symbol("[[bitmapData]]");
+const _detached = Symbol("[[detached]]");
+
+class ImageBitmap {
+  constructor() {
+    throw new TypeError("Illegal constructor.");
+  }
+}
+
+function createImageBitmap() {
+  return PromiseReject(
+    new Error("createImageBitmap is not supported in this runtime."),
+  );
+}
+
+export { _bitmapData, _detached, createImageBitmap, ImageBitmap };
@@ -0,0 +1,9 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// Stand-in for `ext:deno_webgpu/00_init.js` when the runtime is built with the
+// `exclude_webgpu` feature. The WebGPU globals are `undefined`, so that code
+// can detect that WebGPU is unavailable with `navigator.gpu`.
+
+const loadWebGPU = () => ({});
+
+export { loadWebGPU };
@@ -0,0 +1,25 @@
+// Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
+
+// Stand-in for `ext:deno_webgpu/02_surface.js` when the runtime is built with
+// the `exclude_webgpu` feature.
+
+import { primordials } from "ext:core/mod.js";
+const { Error } = primordials;
+
+function notSupported() {
+  throw new Error("WebGPU is not supported in this runtime.");
+}
+
+class GPUCanvasContext {
+  constructor() {
+    notSupported();
+  }
+}
+
+class UnsafeWindowSurface {
+  constructor() {
+    notSupported();
+  }
+}
+
+export { GPUCanvasContext, UnsafeWindowSurface };
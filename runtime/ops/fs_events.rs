@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use crate::permissions::PermissionsContainer;
+use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::parking_lot::Mutex;
 use deno_core::AsyncRefCell;
@@ -13,7 +14,11 @@ use deno_core::ResourceId;
 
 use deno_core::op2;
 
+use glob::MatchOptions;
+use glob::Pattern;
 use notify::event::Event as NotifyEvent;
+use notify::event::ModifyKind;
+use notify::event::RenameMode;
 use notify::Error as NotifyError;
 use notify::EventKind;
 use notify::RecommendedWatcher;
@@ -23,10 +28,16 @@ use serde::Deserialize;
 use serde::Serialize;
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::collections::HashSet;
+use std::collections::VecDeque;
 use std::convert::From;
+use std::path::Component;
+use std::path::Path;
 use std::path::PathBuf;
 use std::rc::Rc;
+use std::time::Duration;
 use tokio::sync::mpsc;
+use tokio::time::Instant;
 
 deno_core::extension!(
   deno_fs_events,
@@ -37,6 +48,10 @@ struct FsEventsResource {
   #[allow(unused)]
   watcher: RecommendedWatcher,
   receiver: AsyncRefCell<mpsc::Receiver<Result<FsEvent, AnyError>>>,
+  /// Events coalesced during the last debounce period, not yet polled, and
+  /// the error that ended the period, if any.
+  pending: RefCell<VecDeque<Result<FsEvent, AnyError>>>,
+  debounce: Option<Duration>,
   cancel: CancelHandle,
 }
 
@@ -58,11 +73,19 @@ impl Resource for FsEventsResource {
 ///
 /// Feel free to expand this struct as long as you can add tests to demonstrate
 /// the complexity.
-#[derive(Serialize, Debug)]
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash)]
 struct FsEvent {
   kind: &'static str,
   paths: Vec<PathBuf>,
   flag: Option<&'static str>,
+  #[serde(skip_serializing_if = "Option::is_none")]
+  rename: Option<FsEventRename>,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq, Hash)]
+struct FsEventRename {
+  from: PathBuf,
+  to: PathBuf,
 }
 
 impl From<NotifyEvent> for FsEvent {
@@ -78,18 +101,154 @@ impl From<NotifyEvent> for FsEvent {
     let flag = e.flag().map(|f| match f {
       notify::event::Flag::Rescan => "rescan",
     });
+    let rename = match e.kind {
+      EventKind::Modify(ModifyKind::Name(RenameMode::Both))
+        if e.paths.len() == 2 =>
+      {
+        Some(FsEventRename {
+          from: e.paths[0].clone(),
+          to: e.paths[1].clone(),
+        })
+      }
+      _ => None,
+    };
     FsEvent {
       kind,
       paths: e.paths,
       flag,
+      rename,
     }
   }
 }
 
+/// Pairs the two halves of a rename on the platforms where the watcher
+/// reports them as separate events, and adds an event for the pair like
+/// the one inotify reports on Linux.
+#[derive(Default)]
+struct RenameTracker {
+  from: Option<PathBuf>,
+}
+
+impl RenameTracker {
+  fn track(&mut self, event: &NotifyEvent) -> Option<FsEvent> {
+    if cfg!(any(target_os = "android", target_os = "linux")) {
+      return None;
+    }
+    match event.kind {
+      EventKind::Modify(ModifyKind::Name(RenameMode::From)) => {
+        self.from = event.paths.first().cloned();
+        None
+      }
+      EventKind::Modify(ModifyKind::Name(RenameMode::To)) => {
+        let from = self.from.take()?;
+        let to = event.paths.first()?.clone();
+        Some(rename_event(from, to))
+      }
+      // FSEvents on macOS doesn't tell the two sides of a rename apart, so
+      // the first of two consecutive rename events is taken as the source.
+      EventKind::Modify(ModifyKind::Name(RenameMode::Any)) => {
+        let path = event.paths.first()?.clone();
+        match self.from.take() {
+          Some(from) if from != path => Some(rename_event(from, path)),
+          _ => {
+            self.from = Some(path);
+            None
+          }
+        }
+      }
+      _ => {
+        self.from = None;
+        None
+      }
+    }
+  }
+}
+
+fn rename_event(from: PathBuf, to: PathBuf) -> FsEvent {
+  FsEvent {
+    kind: "modify",
+    paths: vec![from.clone(), to.clone()],
+    flag: None,
+    rename: Some(FsEventRename { from, to }),
+  }
+}
+
+/// Include and exclude globs for the paths of the events. Relative globs
+/// are resolved against the current directory.
+struct PathFilter {
+  cwd: PathBuf,
+  include: Vec<Pattern>,
+  exclude: Vec<Pattern>,
+}
+
+impl PathFilter {
+  fn new(include: Vec<String>, exclude: Vec<String>) -> Result<Self, AnyError> {
+    let cwd = std::env::current_dir()?;
+    let parse = |globs: Vec<String>| {
+      globs
+        .into_iter()
+        .map(|glob| {
+          let base = Pattern::escape(&cwd.to_string_lossy());
+          let glob = if Path::new(&glob).is_absolute() {
+            glob
+          } else {
+            format!("{base}/{glob}")
+          };
+          Pattern::new(&normalize(Path::new(&glob)).to_string_lossy()).map_err(
+            |err| type_error(format!("Invalid glob \"{glob}\": {err}")),
+          )
+        })
+        .collect::<Result<Vec<_>, _>>()
+    };
+    Ok(Self {
+      include: parse(include)?,
+      exclude: parse(exclude)?,
+      cwd,
+    })
+  }
+
+  fn matches(&self, event: &FsEvent) -> bool {
+    // events without paths can't be filtered
+    event.paths.is_empty()
+      || event.paths.iter().any(|path| self.matches_path(path))
+  }
+
+  fn matches_path(&self, path: &Path) -> bool {
+    let options = MatchOptions {
+      require_literal_separator: true,
+      ..Default::default()
+    };
+    let path = normalize(&self.cwd.join(path));
+    let matches = |patterns: &[Pattern]| {
+      patterns
+        .iter()
+        .any(|pattern| pattern.matches_path_with(&path, options))
+    };
+    (self.include.is_empty() || matches(&self.include))
+      && !matches(&self.exclude)
+  }
+}
+
+fn normalize(path: &Path) -> PathBuf {
+  path
+    .components()
+    .filter(|component| *component != Component::CurDir)
+    .collect()
+}
+
+/// The most debounce periods a burst of events is held back for.
+const MAX_DEBOUNCE_WAIT: u32 = 10;
+
 #[derive(Deserialize)]
 pub struct OpenArgs {
   recursive: bool,
   paths: Vec<String>,
+  #[serde(default)]
+  include: Vec<String>,
+  #[serde(default)]
+  exclude: Vec<String>,
+  /// Milliseconds to wait for more events after one is received.
+  debounce: Option<u64>,
 }
 
 #[op2]
@@ -99,14 +258,27 @@ fn op_fs_events_open(
   #[serde] args: OpenArgs,
 ) -> Result<ResourceId, AnyError> {
   let (sender, receiver) = mpsc::channel::<Result<FsEvent, AnyError>>(16);
-  let sender = Mutex::new(sender);
+  let sender = Mutex::new((sender, RenameTracker::default()));
+  let filter = PathFilter::new(args.include, args.exclude)?;
   let mut watcher: RecommendedWatcher = Watcher::new(
     move |res: Result<NotifyEvent, NotifyError>| {
-      let res2 = res.map(FsEvent::from).map_err(AnyError::from);
-      let sender = sender.lock();
-      // Ignore result, if send failed it means that watcher was already closed,
-      // but not all messages have been flushed.
-      let _ = sender.try_send(res2);
+      let (sender, rename_tracker) = &mut *sender.lock();
+      let events = match res {
+        Ok(event) => {
+          let rename = rename_tracker.track(&event);
+          std::iter::once(FsEvent::from(event))
+            .chain(rename)
+            .filter(|event| filter.matches(event))
+            .map(Ok)
+            .collect()
+        }
+        Err(err) => vec![Err(AnyError::from(err))],
+      };
+      for event in events {
+        // Ignore result, if send failed it means that watcher was already closed,
+        // but not all messages have been flushed.
+        let _ = sender.try_send(event);
+      }
     },
     Default::default(),
   )?;
@@ -125,6 +297,8 @@ fn op_fs_events_open(
   let resource = FsEventsResource {
     watcher,
     receiver: AsyncRefCell::new(receiver),
+    pending: Default::default(),
+    debounce: args.debounce.map(Duration::from_millis),
     cancel: Default::default(),
   };
   let rid = state.resource_table.add(resource);
@@ -138,12 +312,50 @@ async fn op_fs_events_poll(
   #[smi] rid: ResourceId,
 ) -> Result<Option<FsEvent>, AnyError> {
   let resource = state.borrow().resource_table.get::<FsEventsResource>(rid)?;
+  if let Some(result) = resource.pending.borrow_mut().pop_front() {
+    return result.map(Some);
+  }
   let mut receiver = RcRef::map(&resource, |r| &r.receiver).borrow_mut().await;
-  let cancel = RcRef::map(resource, |r| &r.cancel);
+  let cancel = RcRef::map(&resource, |r| &r.cancel);
   let maybe_result = receiver.recv().or_cancel(cancel).await?;
-  match maybe_result {
-    Some(Ok(value)) => Ok(Some(value)),
-    Some(Err(err)) => Err(err),
-    None => Ok(None),
+  let event = match maybe_result {
+    Some(Ok(value)) => value,
+    Some(Err(err)) => return Err(err),
+    None => return Ok(None),
+  };
+  let Some(debounce) = resource.debounce else {
+    return Ok(Some(event));
+  };
+
+  // Coalesce the burst of events following this one, dropping the
+  // duplicates, until no event was received for the debounce period. A
+  // continuous stream of events is still yielded after `MAX_DEBOUNCE_WAIT`
+  // debounce periods.
+  let deadline = Instant::now() + debounce * MAX_DEBOUNCE_WAIT;
+  let mut seen = HashSet::from([event.clone()]);
+  let mut events = VecDeque::from([Ok(event)]);
+  loop {
+    let wait = debounce.min(deadline.saturating_duration_since(Instant::now()));
+    let cancel = RcRef::map(&resource, |r| &r.cancel);
+    let next = tokio::time::timeout(wait, receiver.recv())
+      .or_cancel(cancel)
+      .await?;
+    match next {
+      Ok(Some(Ok(event))) => {
+        if seen.insert(event.clone()) {
+          events.push_back(Ok(event));
+        }
+      }
+      // yield the events collected so far before the error
+      Ok(Some(Err(err))) => {
+        events.push_back(Err(err));
+        break;
+      }
+      // the watcher is closed or the burst is over
+      Ok(None) | Err(_) => break,
+    }
   }
+  let result = events.pop_front().unwrap();
+  resource.pending.borrow_mut().extend(events);
+  result.map(Some)
 }
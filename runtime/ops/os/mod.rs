@@ -6,6 +6,7 @@ use crate::worker::ExitCode;
 use deno_core::error::type_error;
 use deno_core::error::AnyError;
 use deno_core::op2;
+use deno_core::parking_lot::Mutex;
 use deno_core::url::Url;
 use deno_core::v8;
 use deno_core::OpState;
@@ -13,9 +14,36 @@ use deno_node::NODE_ENV_VAR_ALLOWLIST;
 use serde::Serialize;
 use std::collections::HashMap;
 use std::env;
+use std::sync::Arc;
 
 mod sys_info;
 
+/// Environment variables of the program that are set in addition to the
+/// ones of the process. Unlike `std::env::set_var`, they can be changed
+/// safely while other threads are running.
+#[derive(Clone, Default)]
+pub struct EnvVarOverrides(Arc<Mutex<HashMap<String, String>>>);
+
+impl EnvVarOverrides {
+  pub fn set(&self, key: &str, value: Option<String>) {
+    let mut vars = self.0.lock();
+    match value {
+      Some(value) => vars.insert(key.to_string(), value),
+      None => vars.remove(key),
+    };
+  }
+
+  fn get(&self, key: &str) -> Option<String> {
+    self.0.lock().get(key).cloned()
+  }
+
+  /// The variables to set for the subprocesses of the program, which inherit
+  /// them like the ones of the process.
+  pub fn vars(&self) -> HashMap<String, String> {
+    self.0.lock().clone()
+  }
+}
+
 deno_core::extension!(
   deno_os,
   ops = [
@@ -114,7 +142,11 @@ fn op_set_env(
 #[serde]
 fn op_env(state: &mut OpState) -> Result<HashMap<String, String>, AnyError> {
   state.borrow_mut::<PermissionsContainer>().check_env_all()?;
-  Ok(env::vars().collect())
+  let mut vars = env::vars().collect::<HashMap<_, _>>();
+  if let Some(overrides) = state.try_borrow::<EnvVarOverrides>() {
+    vars.extend(overrides.vars());
+  }
+  Ok(vars)
 }
 
 #[op2]
@@ -139,6 +171,13 @@ fn op_get_env(
     )));
   }
 
+  if let Some(value) = state
+    .try_borrow::<EnvVarOverrides>()
+    .and_then(|overrides| overrides.get(&key))
+  {
+    return Ok(Some(value));
+  }
+
   let r = match env::var(key) {
     Err(env::VarError::NotPresent) => None,
     v => Some(v?),
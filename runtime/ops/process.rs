@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
 use super::check_unstable;
+use super::os::EnvVarOverrides;
 use crate::permissions::PermissionsContainer;
 use deno_core::anyhow::Context;
 use deno_core::error::type_error;
@@ -141,6 +142,7 @@ pub struct SpawnArgs {
   uid: Option<u32>,
   #[cfg(windows)]
   windows_raw_arguments: bool,
+  detached: bool,
   ipc: Option<i32>,
 
   #[serde(flatten)]
@@ -237,6 +239,8 @@ fn create_command(
 
   if args.clear_env {
     command.env_clear();
+  } else if let Some(overrides) = state.try_borrow::<EnvVarOverrides>() {
+    command.envs(overrides.vars());
   }
   command.envs(args.env);
 
@@ -249,6 +253,16 @@ fn create_command(
     command.uid(uid);
   }
 
+  if args.detached {
+    // the child gets a process group of its own, so that it doesn't receive
+    // the signals sent to the group of this process (e.g. on Ctrl+C) and so
+    // that signals can be sent to the child along with its descendants
+    #[cfg(unix)]
+    command.process_group(0);
+    #[cfg(windows)]
+    command.creation_flags(winapi::um::winbase::CREATE_NEW_PROCESS_GROUP);
+  }
+
   command.stdin(args.stdio.stdin.as_stdio());
   command.stdout(match args.stdio.stdout {
     Stdio::Inherit => StdioOrRid::Rid(1).as_stdio(state)?,
@@ -639,14 +653,35 @@ fn op_spawn_kill(
   state: &mut OpState,
   #[smi] rid: ResourceId,
   #[string] signal: String,
+  process_group: bool,
 ) -> Result<(), AnyError> {
   if let Ok(child_resource) = state.resource_table.get::<ChildResource>(rid) {
-    deprecated::kill(child_resource.1 as i32, &signal)?;
+    let pid = child_resource.1 as i32;
+    if process_group {
+      kill_process_group(pid, &signal)?;
+    } else {
+      deprecated::kill(pid, &signal)?;
+    }
     return Ok(());
   }
   Err(type_error("Child process has already terminated."))
 }
 
+/// Send a signal to every process in the group of a detached child, whose
+/// process group id is its pid.
+#[cfg(unix)]
+fn kill_process_group(pgid: i32, signal: &str) -> Result<(), AnyError> {
+  // a negative pid signals the process group
+  deprecated::kill(-pgid, signal)
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(_pgid: i32, _signal: &str) -> Result<(), AnyError> {
+  Err(type_error(
+    "Sending signals to a process group is not supported on Windows",
+  ))
+}
+
 mod deprecated {
   use super::*;
 
@@ -716,6 +751,8 @@ mod deprecated {
     if run_args.clear_env {
       super::check_unstable(state, UNSTABLE_FEATURE_NAME, "Deno.run.clearEnv");
       c.env_clear();
+    } else if let Some(overrides) = state.try_borrow::<EnvVarOverrides>() {
+      c.envs(overrides.vars());
     }
     for (key, value) in &env {
       c.env(key, value);
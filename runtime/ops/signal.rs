@@ -9,6 +9,7 @@ use deno_core::OpState;
 use deno_core::RcRef;
 use deno_core::Resource;
 use deno_core::ResourceId;
+use deno_fs::temp_cleanup::SigintListener;
 
 use std::borrow::Cow;
 use std::cell::RefCell;
@@ -20,6 +21,8 @@ use std::sync::atomic::AtomicBool;
 #[cfg(unix)]
 use std::sync::Arc;
 
+#[cfg(windows)]
+use deno_core::anyhow::Context;
 #[cfg(unix)]
 use tokio::signal::unix::signal;
 #[cfg(unix)]
@@ -85,6 +88,7 @@ struct SignalStreamResource {
   signal: AsyncRefCell<Signal>,
   enable_default_handler: Arc<AtomicBool>,
   cancel: CancelHandle,
+  _sigint_listener: Option<SigintListener>,
 }
 
 #[cfg(unix)]
@@ -134,6 +138,7 @@ impl WindowsSignal {
 struct SignalStreamResource {
   signal: AsyncRefCell<WindowsSignal>,
   cancel: CancelHandle,
+  _sigint_listener: Option<SigintListener>,
 }
 
 #[cfg(windows)]
@@ -604,16 +609,32 @@ fn op_signal_bind(
     signal,
     cancel: Default::default(),
     enable_default_handler: enable_default_handler.clone(),
+    _sigint_listener: (signo == libc::SIGINT).then(SigintListener::new),
   };
   let rid = state.resource_table.add(resource);
 
   if !has_default_handler {
     // restore default signal handler when the signal is unbound
     // this can error if the signal is not supported, if so let's just leave it as is
-    let _ = signal_hook::flag::register_conditional_default(
-      signo,
-      enable_default_handler,
-    );
+    if signo == libc::SIGINT {
+      // SAFETY: the action only loads atomics and emulates the default
+      // handler, which is what `register_conditional_default` does too.
+      let _ = unsafe {
+        signal_hook_registry::register(signo, move || {
+          // the temporary paths are removed before terminating the process
+          if enable_default_handler.load(std::sync::atomic::Ordering::Acquire)
+            && !deno_fs::temp_cleanup::handles_sigint()
+          {
+            let _ = signal_hook::low_level::emulate_default_handler(signo);
+          }
+        })
+      };
+    } else {
+      let _ = signal_hook::flag::register_conditional_default(
+        signo,
+        enable_default_handler,
+      );
+    }
   }
 
   Ok(rid)
@@ -626,20 +647,23 @@ fn op_signal_bind(
   state: &mut OpState,
   #[string] sig: &str,
 ) -> Result<ResourceId, AnyError> {
+  // the console control handlers are registered with SetConsoleCtrlHandler
   let signo = signal_str_to_int(sig)?;
+  let signal: WindowsSignal = match signo {
+    // SIGINT
+    2 => ctrl_c()
+      .context("There was an issue creating ctrl+c event stream.")?
+      .into(),
+    // SIGBREAK
+    21 => ctrl_break()
+      .context("There was an issue creating ctrl+break event stream.")?
+      .into(),
+    _ => unreachable!(),
+  };
   let resource = SignalStreamResource {
-    signal: AsyncRefCell::new(match signo {
-      // SIGINT
-      2 => ctrl_c()
-        .expect("There was an issue creating ctrl+c event stream.")
-        .into(),
-      // SIGBREAK
-      21 => ctrl_break()
-        .expect("There was an issue creating ctrl+break event stream.")
-        .into(),
-      _ => unimplemented!(),
-    }),
+    signal: AsyncRefCell::new(signal),
     cancel: Default::default(),
+    _sigint_listener: (signo == 2).then(SigintListener::new),
   };
   let rid = state.resource_table.add(resource);
   Ok(rid)
@@ -14,6 +14,7 @@ use crate::web_worker::WorkerId;
 use crate::web_worker::WorkerMetadata;
 use crate::worker::FormatJsErrorFn;
 use deno_core::error::AnyError;
+use deno_core::error::JsError;
 use deno_core::op2;
 use deno_core::serde::Deserialize;
 use deno_core::CancelFuture;
@@ -56,6 +57,7 @@ struct CreateWebWorkerCbHolder(Arc<CreateWebWorkerCb>);
 struct FormatJsErrorFnHolder(Option<Arc<FormatJsErrorFn>>);
 
 pub struct WorkerThread {
+  name: String,
   worker_handle: WebWorkerHandle,
   cancel_handle: Rc<CancelHandle>,
 
@@ -82,6 +84,70 @@ impl Drop for WorkerThread {
 
 pub type WorkersTable = HashMap<WorkerId, WorkerThread>;
 
+/// Message of the error thrown by a `Worker` when its "error" event isn't
+/// handled. Keep in sync with `runtime/js/11_workers.js`.
+const UNHANDLED_WORKER_ERROR_MESSAGE: &str = "Unhandled error in child worker.";
+
+/// The last error reported by each child worker. If the host doesn't handle
+/// it, it becomes the cause of the error thrown on the host, so that it is
+/// reported once, together with the host's stack.
+#[derive(Default)]
+struct WorkerErrors {
+  last_errors: HashMap<WorkerId, JsError>,
+  /// The error of the worker whose "error" event wasn't handled by the host.
+  unhandled: Option<JsError>,
+}
+
+/// Convert an error of the worker `name` into a [`JsError`] that is labeled
+/// with the name of the worker.
+fn to_worker_js_error(error: &AnyError, name: &str) -> JsError {
+  let mut js_error = match error.downcast_ref::<JsError>() {
+    Some(js_error) => js_error.clone(),
+    None => JsError {
+      name: None,
+      message: Some(error.to_string()),
+      stack: None,
+      cause: None,
+      exception_message: error.to_string(),
+      frames: vec![],
+      source_line: None,
+      source_line_frame_index: None,
+      aggregated: None,
+    },
+  };
+  js_error.exception_message = format!(
+    "(in worker \"{}\") {}",
+    name,
+    js_error.exception_message.trim_start_matches("Uncaught ")
+  );
+  js_error
+}
+
+/// If `error` was thrown because the error of a child worker wasn't handled,
+/// attach the child's error as its cause. Otherwise `error` is returned as is.
+pub fn attach_worker_error_cause(
+  state: &mut OpState,
+  error: AnyError,
+) -> AnyError {
+  let Some(js_error) = error.downcast_ref::<JsError>() else {
+    return error;
+  };
+  if js_error.message.as_deref() != Some(UNHANDLED_WORKER_ERROR_MESSAGE)
+    || js_error.cause.is_some()
+  {
+    return error;
+  }
+  let Some(worker_error) = state
+    .try_borrow_mut::<WorkerErrors>()
+    .and_then(|errors| errors.unhandled.take())
+  else {
+    return error;
+  };
+  let mut js_error = js_error.clone();
+  js_error.cause = Some(Box::new(worker_error));
+  js_error.into()
+}
+
 deno_core::extension!(
   deno_worker_host,
   ops = [
@@ -90,6 +156,7 @@ deno_core::extension!(
     op_host_post_message,
     op_host_recv_ctrl,
     op_host_recv_message,
+    op_host_take_error,
   ],
   options = {
     create_web_worker_cb: Arc<CreateWebWorkerCb>,
@@ -97,6 +164,7 @@ deno_core::extension!(
   },
   state = |state, options| {
     state.put::<WorkersTable>(WorkersTable::default());
+    state.put::<WorkerErrors>(WorkerErrors::default());
 
     let create_web_worker_cb_holder =
       CreateWebWorkerCbHolder(options.create_web_worker_cb);
@@ -170,6 +238,7 @@ fn op_create_worker(
 
   let module_specifier = deno_core::resolve_url(&specifier)?;
   let worker_name = args_name.unwrap_or_default();
+  let worker_thread_name = worker_name.clone();
 
   let (handle_sender, handle_receiver) = std::sync::mpsc::sync_channel::<
     Result<SendableWebWorkerHandle, AnyError>,
@@ -226,6 +295,7 @@ fn op_create_worker(
   let worker_handle = handle_receiver.recv().unwrap()?;
 
   let worker_thread = WorkerThread {
+    name: worker_thread_name,
     worker_handle: worker_handle.into(),
     cancel_handle: CancelHandle::new_rc(),
     ctrl_closed: false,
@@ -243,6 +313,7 @@ fn op_create_worker(
 
 #[op2]
 fn op_host_terminate_worker(state: &mut OpState, #[serde] id: WorkerId) {
+  state.borrow_mut::<WorkerErrors>().last_errors.remove(&id);
   if let Some(worker_thread) = state.borrow_mut::<WorkersTable>().remove(&id) {
     worker_thread.terminate();
   } else {
@@ -250,6 +321,22 @@ fn op_host_terminate_worker(state: &mut OpState, #[serde] id: WorkerId) {
   }
 }
 
+/// Take the last error of the worker `id` once its "error" event was
+/// dispatched on the host. If the event wasn't handled, the error becomes the
+/// cause of the error that is thrown on the host next.
+#[op2]
+fn op_host_take_error(
+  state: &mut OpState,
+  #[serde] id: WorkerId,
+  handled: bool,
+) {
+  let errors = state.borrow_mut::<WorkerErrors>();
+  let error = errors.last_errors.remove(&id);
+  if !handled {
+    errors.unhandled = error;
+  }
+}
+
 enum WorkerChannel {
   Ctrl,
   Messages,
@@ -297,12 +384,16 @@ async fn op_host_recv_ctrl(
   state: Rc<RefCell<OpState>>,
   #[serde] id: WorkerId,
 ) -> Result<WorkerControlEvent, AnyError> {
-  let (worker_handle, cancel_handle) = {
+  let (worker_handle, cancel_handle, name) = {
     let state = state.borrow();
     let workers_table = state.borrow::<WorkersTable>();
     let maybe_handle = workers_table.get(&id);
     if let Some(handle) = maybe_handle {
-      (handle.worker_handle.clone(), handle.cancel_handle.clone())
+      (
+        handle.worker_handle.clone(),
+        handle.cancel_handle.clone(),
+        handle.name.clone(),
+      )
     } else {
       // If handle was not found it means worker has already shutdown
       return Ok(WorkerControlEvent::Close);
@@ -315,6 +406,16 @@ async fn op_host_recv_ctrl(
     .await;
   match maybe_event {
     Ok(Ok(Some(event))) => {
+      if let WorkerControlEvent::TerminalError(error)
+      | WorkerControlEvent::Error(error) = &event
+      {
+        let worker_error = to_worker_js_error(error, &name);
+        let mut state = state.borrow_mut();
+        state
+          .borrow_mut::<WorkerErrors>()
+          .last_errors
+          .insert(id, worker_error);
+      }
       // Terminal error means that worker should be removed from worker table.
       if let WorkerControlEvent::TerminalError(_) = &event {
         close_channel(state, id, WorkerChannel::Ctrl);
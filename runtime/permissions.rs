@@ -8,6 +8,7 @@ use deno_core::url::Url;
 pub use deno_io::fs::FsError;
 pub use deno_permissions::create_child_permissions;
 pub use deno_permissions::parse_sys_kind;
+pub use deno_permissions::set_persist_grant_callback;
 pub use deno_permissions::set_prompt_callbacks;
 pub use deno_permissions::ChildPermissionsArg;
 pub use deno_permissions::Permissions;
@@ -94,6 +95,15 @@ impl deno_fetch::FetchPermissions for PermissionsContainer {
   ) -> Result<(), AnyError> {
     self.0.check_read(path, api_name)
   }
+
+  #[inline(always)]
+  fn check_write(
+    &mut self,
+    path: &Path,
+    api_name: &str,
+  ) -> Result<(), AnyError> {
+    self.0.check_write(path, api_name)
+  }
 }
 
 impl deno_net::NetPermissions for PermissionsContainer {
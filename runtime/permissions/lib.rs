@@ -25,6 +25,8 @@ use std::ffi::OsStr;
 use std::fmt;
 use std::fmt::Debug;
 use std::hash::Hash;
+use std::net::IpAddr;
+use std::ops::RangeInclusive;
 use std::path::Path;
 use std::path::PathBuf;
 use std::str::FromStr;
@@ -34,10 +36,13 @@ use which::which;
 
 pub mod prompter;
 use prompter::permission_prompt;
+use prompter::persist_grant;
 use prompter::PromptResponse;
 use prompter::PERMISSION_EMOJI;
 
+pub use prompter::set_persist_grant_callback;
 pub use prompter::set_prompt_callbacks;
+pub use prompter::PersistGrantCallback;
 pub use prompter::PromptCallback;
 
 /// Fast exit from permission check routines if this permission
@@ -141,7 +146,8 @@ impl PermissionState {
     )
   }
 
-  /// Check the permission state. bool is whether a prompt was issued.
+  /// Check the permission state. The prompt response is returned if a prompt
+  /// was issued.
   #[inline]
   fn check(
     self,
@@ -149,7 +155,7 @@ impl PermissionState {
     api_name: Option<&str>,
     info: Option<&str>,
     prompt: bool,
-  ) -> (Result<(), AnyError>, bool, bool) {
+  ) -> (Result<(), AnyError>, Option<PromptResponse>) {
     self.check2(name, api_name, || info.map(|s| s.to_string()), prompt)
   }
 
@@ -160,11 +166,11 @@ impl PermissionState {
     api_name: Option<&str>,
     info: impl Fn() -> Option<String>,
     prompt: bool,
-  ) -> (Result<(), AnyError>, bool, bool) {
+  ) -> (Result<(), AnyError>, Option<PromptResponse>) {
     match self {
       PermissionState::Granted => {
         Self::log_perm_access(name, info);
-        (Ok(()), false, false)
+        (Ok(()), None)
       }
       PermissionState::Prompt if prompt => {
         let msg = format!(
@@ -175,18 +181,16 @@ impl PermissionState {
             .unwrap_or_default(),
         );
         match permission_prompt(&msg, name, api_name, true) {
-          PromptResponse::Allow => {
-            Self::log_perm_access(name, info);
-            (Ok(()), true, false)
+          PromptResponse::Deny => {
+            (Err(Self::error(name, info)), Some(PromptResponse::Deny))
           }
-          PromptResponse::AllowAll => {
+          response => {
             Self::log_perm_access(name, info);
-            (Ok(()), true, true)
+            (Ok(()), Some(response))
           }
-          PromptResponse::Deny => (Err(Self::error(name, info)), true, false),
         }
       }
-      _ => (Err(Self::error(name, info)), false, false),
+      _ => (Err(Self::error(name, info)), None),
     }
   }
 }
@@ -217,17 +221,22 @@ impl UnitPermission {
 
   pub fn request(&mut self) -> PermissionState {
     if self.state == PermissionState::Prompt {
-      if PromptResponse::Allow
-        == permission_prompt(
-          &format!("access to {}", self.description),
-          self.name,
-          Some("Deno.permissions.query()"),
-          false,
-        )
-      {
-        self.state = PermissionState::Granted;
-      } else {
-        self.state = PermissionState::Denied;
+      match permission_prompt(
+        &format!("access to {}", self.description),
+        self.name,
+        Some("Deno.permissions.query()"),
+        false,
+      ) {
+        PromptResponse::Allow => {
+          self.state = PermissionState::Granted;
+        }
+        PromptResponse::AllowAlways => {
+          self.state = PermissionState::Granted;
+          persist_grant(self.name, None);
+        }
+        _ => {
+          self.state = PermissionState::Denied;
+        }
       }
     }
     self.state
@@ -241,11 +250,14 @@ impl UnitPermission {
   }
 
   pub fn check(&mut self) -> Result<(), AnyError> {
-    let (result, prompted, _is_allow_all) =
+    let (result, prompt_response) =
       self.state.check(self.name, None, None, self.prompt);
-    if prompted {
+    if let Some(response) = prompt_response {
       if result.is_ok() {
         self.state = PermissionState::Granted;
+        if response == PromptResponse::AllowAlways {
+          persist_grant(self.name, None);
+        }
       } else {
         self.state = PermissionState::Denied;
       }
@@ -277,6 +289,23 @@ impl UnitPermission {
     }
     Ok(perm)
   }
+
+  fn intersect(&self, other: &Self) -> Self {
+    let state = match (self.state, other.state) {
+      (PermissionState::Granted, PermissionState::Granted) => {
+        PermissionState::Granted
+      }
+      (PermissionState::Denied, _) | (_, PermissionState::Denied) => {
+        PermissionState::Denied
+      }
+      _ => PermissionState::Prompt,
+    };
+    Self {
+      state,
+      prompt: false,
+      ..self.clone()
+    }
+  }
 }
 
 /// A normalized environment variable name. On Windows this will
@@ -385,7 +414,7 @@ impl<T: Descriptor + Hash> UnaryPermission<T> {
     get_display_name: impl Fn() -> Option<String>,
   ) -> Result<(), AnyError> {
     skip_check_if_is_permission_fully_granted!(self);
-    let (result, prompted, is_allow_all) = self
+    let (result, prompt_response) = self
       .query_desc(desc, AllowPartial::from(!assert_non_partial))
       .check2(
         T::flag_name(),
@@ -396,16 +425,15 @@ impl<T: Descriptor + Hash> UnaryPermission<T> {
         },
         self.prompt,
       );
-    if prompted {
-      if result.is_ok() {
-        if is_allow_all {
-          self.insert_granted(None);
-        } else {
-          self.insert_granted(desc.cloned());
-        }
-      } else {
-        self.insert_prompt_denied(desc.cloned());
+    match prompt_response {
+      Some(PromptResponse::Allow) => self.insert_granted(desc.cloned()),
+      Some(PromptResponse::AllowAll) => self.insert_granted(None),
+      Some(PromptResponse::AllowAlways) => {
+        self.insert_granted(desc.cloned());
+        persist_grant(T::flag_name(), desc.map(|d| d.name()).as_deref());
       }
+      Some(PromptResponse::Deny) => self.insert_prompt_denied(desc.cloned()),
+      None => {}
     }
     result
   }
@@ -497,6 +525,11 @@ impl<T: Descriptor + Hash> UnaryPermission<T> {
         self.insert_granted(None);
         PermissionState::Granted
       }
+      PromptResponse::AllowAlways => {
+        self.insert_granted(desc.cloned());
+        persist_grant(T::flag_name(), desc.map(|d| d.name()).as_deref());
+        PermissionState::Granted
+      }
     }
   }
 
@@ -622,6 +655,45 @@ impl<T: Descriptor + Hash> UnaryPermission<T> {
 
     Ok(perms)
   }
+
+  /// The permissions granted by both `self` and `other`. The deny lists of
+  /// both are kept and the result never prompts.
+  fn intersect(&self, other: &Self) -> Self {
+    let is_granted = |perm: &Self, desc: &T| {
+      perm.query_desc(Some(desc), AllowPartial::TreatAsDenied)
+        == PermissionState::Granted
+    };
+    let granted_list = self
+      .granted_list
+      .iter()
+      .filter(|desc| is_granted(other, desc))
+      .chain(
+        other
+          .granted_list
+          .iter()
+          .filter(|desc| is_granted(self, desc)),
+      )
+      .cloned()
+      .collect();
+    Self {
+      granted_global: self.granted_global && other.granted_global,
+      granted_list,
+      flag_denied_global: self.flag_denied_global || other.flag_denied_global,
+      flag_denied_list: self
+        .flag_denied_list
+        .union(&other.flag_denied_list)
+        .cloned()
+        .collect(),
+      prompt_denied_global: self.prompt_denied_global
+        || other.prompt_denied_global,
+      prompt_denied_list: self
+        .prompt_denied_list
+        .union(&other.prompt_denied_list)
+        .cloned()
+        .collect(),
+      prompt: false,
+    }
+  }
 }
 
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
@@ -688,12 +760,125 @@ impl Descriptor for WriteDescriptor {
   }
 }
 
+/// The host of a net permission.
+#[derive(Clone, Eq, PartialEq, Hash, Debug)]
+pub enum NetHost {
+  /// A host name.
+  Fqdn(FQDN),
+  /// An IP address.
+  Ip(IpAddr),
+  /// `*.example.com`, which matches the subdomains of a domain but not the
+  /// domain itself.
+  Wildcard(FQDN),
+  /// `10.0.0.0/8`, which matches the IP addresses in the block. Host names
+  /// are not resolved, so they never match a block.
+  Cidr(IpAddr, u8),
+}
+
+impl NetHost {
+  fn stronger_than(&self, other: &Self) -> bool {
+    match (self, other) {
+      (NetHost::Fqdn(a), NetHost::Fqdn(b)) => a == b,
+      (NetHost::Ip(a), NetHost::Ip(b)) => a.to_canonical() == b.to_canonical(),
+      (NetHost::Wildcard(domain), NetHost::Fqdn(host)) => {
+        is_subdomain(host, domain)
+      }
+      (NetHost::Wildcard(a), NetHost::Wildcard(b)) => {
+        a == b || is_subdomain(b, a)
+      }
+      (NetHost::Cidr(addr, prefix_len), NetHost::Ip(ip)) => {
+        cidr_contains(addr, *prefix_len, ip)
+      }
+      (NetHost::Cidr(a, a_len), NetHost::Cidr(b, b_len)) => {
+        let (a, a_len) = canonical_cidr(a, *a_len);
+        let (b, b_len) = canonical_cidr(b, *b_len);
+        a_len <= b_len && cidr_contains(&a, a_len, &b)
+      }
+      _ => false,
+    }
+  }
+}
+
+impl fmt::Display for NetHost {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      NetHost::Fqdn(host) => write!(f, "{host}"),
+      NetHost::Ip(IpAddr::V4(addr)) => write!(f, "{addr}"),
+      NetHost::Ip(IpAddr::V6(addr)) => write!(f, "[{addr}]"),
+      NetHost::Wildcard(domain) => write!(f, "*.{domain}"),
+      NetHost::Cidr(IpAddr::V4(addr), prefix_len) => {
+        write!(f, "{addr}/{prefix_len}")
+      }
+      NetHost::Cidr(IpAddr::V6(addr), prefix_len) => {
+        write!(f, "[{addr}]/{prefix_len}")
+      }
+    }
+  }
+}
+
+/// Parses IP addresses, including IPv6 addresses in brackets and addresses
+/// with the trailing dot of a fully qualified domain name.
+fn parse_ip(host: &str) -> Option<IpAddr> {
+  host
+    .trim_end_matches('.')
+    .trim_start_matches('[')
+    .trim_end_matches(']')
+    .parse()
+    .ok()
+}
+
+fn is_subdomain(host: &FQDN, domain: &FQDN) -> bool {
+  let host = host.to_string().to_ascii_lowercase();
+  let domain = domain.to_string().to_ascii_lowercase();
+  let host = host.trim_end_matches('.');
+  let domain = domain.trim_end_matches('.');
+  host.len() > domain.len()
+    && host.ends_with(domain)
+    && host.as_bytes()[host.len() - domain.len() - 1] == b'.'
+}
+
+/// Converts a CIDR block of IPv4-mapped IPv6 addresses to the IPv4 block it
+/// maps to.
+fn canonical_cidr(network: &IpAddr, prefix_len: u8) -> (IpAddr, u8) {
+  match network {
+    IpAddr::V6(addr) if prefix_len >= 96 => match addr.to_ipv4_mapped() {
+      Some(addr) => (IpAddr::V4(addr), prefix_len - 96),
+      None => (*network, prefix_len),
+    },
+    _ => (*network, prefix_len),
+  }
+}
+
+/// Checks whether the address is in the CIDR block. IPv4-mapped IPv6
+/// addresses are compared as the IPv4 address they map to, so that
+/// `[::ffff:10.0.0.1]` can't be used to get around `10.0.0.0/8`.
+fn cidr_contains(network: &IpAddr, prefix_len: u8, ip: &IpAddr) -> bool {
+  let (network, prefix_len) = canonical_cidr(network, prefix_len);
+  match (&network, &ip.to_canonical()) {
+    (IpAddr::V4(network), IpAddr::V4(ip)) => {
+      let mask = u32::MAX.checked_shl(32 - prefix_len as u32).unwrap_or(0);
+      u32::from(*network) & mask == u32::from(*ip) & mask
+    }
+    (IpAddr::V6(network), IpAddr::V6(ip)) => {
+      let mask = u128::MAX.checked_shl(128 - prefix_len as u32).unwrap_or(0);
+      u128::from(*network) & mask == u128::from(*ip) & mask
+    }
+    _ => false,
+  }
+}
+
+/// A net permission: a host pattern and an optional inclusive port range.
 #[derive(Clone, Eq, PartialEq, Hash, Debug)]
-pub struct NetDescriptor(pub FQDN, pub Option<u16>);
+pub struct NetDescriptor(pub NetHost, pub Option<RangeInclusive<u16>>);
 
 impl NetDescriptor {
   fn new<T: AsRef<str>>(host: &&(T, Option<u16>)) -> Self {
-    NetDescriptor(fqdn!(host.0.as_ref()), host.1)
+    let host_name = host.0.as_ref();
+    let host_pattern = match parse_ip(host_name) {
+      Some(ip) => NetHost::Ip(ip),
+      None => NetHost::Fqdn(fqdn!(host_name)),
+    };
+    NetDescriptor(host_pattern, host.1.map(|port| port..=port))
   }
 }
 
@@ -722,7 +907,12 @@ impl Descriptor for NetDescriptor {
   }
 
   fn stronger_than(&self, other: &Self) -> bool {
-    self.0 == other.0 && (self.1.is_none() || self.1 == other.1)
+    let ports_contain = match (&self.1, &other.1) {
+      (None, _) => true,
+      (Some(_), None) => false,
+      (Some(a), Some(b)) => a.start() <= b.start() && b.end() <= a.end(),
+    };
+    ports_contain && self.0.stronger_than(&other.0)
   }
 }
 
@@ -730,25 +920,86 @@ impl FromStr for NetDescriptor {
   type Err = AnyError;
 
   fn from_str(s: &str) -> Result<Self, Self::Err> {
-    // Set the scheme to `unknown` to parse the URL, as we really don't know
-    // what the scheme is. We only using Url::parse to parse the host and port
-    // and don't care about the scheme.
-    let url = url::Url::parse(&format!("unknown://{s}"))?;
-    let hostname = url
-      .host_str()
-      .ok_or(url::ParseError::EmptyHost)?
-      .to_string();
+    // CIDR blocks and port ranges aren't valid in a URL, so split them off
+    // before parsing the host.
+    let (host, ports) = if let Some(rest) = s.strip_prefix('[') {
+      let (addr, rest) = rest
+        .split_once(']')
+        .ok_or_else(|| uri_error(format!("Invalid host \"{s}\"")))?;
+      let (prefix_len, ports) = match rest.split_once(':') {
+        Some((prefix_len, ports)) => (prefix_len, Some(ports)),
+        None => (rest, None),
+      };
+      (format!("[{addr}]{prefix_len}"), ports)
+    } else if s.matches(':').count() == 1 {
+      let (host, ports) = s.split_once(':').unwrap();
+      (host.to_string(), Some(ports))
+    } else {
+      // a host without a port, or an IPv6 address without brackets
+      (s.to_string(), None)
+    };
+    let ports = ports.map(parse_port_range).transpose()?;
+
+    let host = if let Some((addr, prefix_len)) = host.split_once('/') {
+      let addr = parse_ip(addr)
+        .ok_or_else(|| uri_error(format!("Invalid CIDR block \"{host}\"")))?;
+      let max_prefix_len = if addr.is_ipv4() { 32 } else { 128 };
+      let prefix_len = prefix_len
+        .parse::<u8>()
+        .ok()
+        .filter(|prefix_len| *prefix_len <= max_prefix_len)
+        .ok_or_else(|| {
+          uri_error(format!("Invalid CIDR prefix length in \"{host}\""))
+        })?;
+      NetHost::Cidr(addr, prefix_len)
+    } else if let Some(domain) = host.strip_prefix("*.") {
+      NetHost::Wildcard(fqdn!(&parse_host_name(domain)?))
+    } else if let Some(ip) = parse_ip(&host) {
+      NetHost::Ip(ip)
+    } else {
+      NetHost::Fqdn(fqdn!(&parse_host_name(&host)?))
+    };
 
-    Ok(NetDescriptor(fqdn!(&hostname), url.port()))
+    Ok(NetDescriptor(host, ports))
   }
 }
 
+fn parse_host_name(host: &str) -> Result<String, AnyError> {
+  // Set the scheme to `unknown` to parse the URL, as we really don't know
+  // what the scheme is. We only using Url::parse to parse the host and don't
+  // care about the scheme.
+  let url = url::Url::parse(&format!("unknown://{host}"))?;
+  if url.port().is_some() || !matches!(url.path(), "" | "/") {
+    return Err(uri_error(format!("Invalid host \"{host}\"")));
+  }
+  match url.host_str() {
+    Some(host) if !host.is_empty() => Ok(host.to_string()),
+    _ => Err(url::ParseError::EmptyHost.into()),
+  }
+}
+
+fn parse_port_range(ports: &str) -> Result<RangeInclusive<u16>, AnyError> {
+  let invalid = || uri_error(format!("Invalid port or port range \"{ports}\""));
+  let (start, end) = ports.split_once('-').unwrap_or((ports, ports));
+  let start = start.parse::<u16>().map_err(|_| invalid())?;
+  let end = end.parse::<u16>().map_err(|_| invalid())?;
+  if start > end {
+    return Err(invalid());
+  }
+  Ok(start..=end)
+}
+
 impl fmt::Display for NetDescriptor {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-    f.write_str(&match self.1 {
-      None => self.0.to_string(),
-      Some(port) => format!("{}:{}", self.0, port),
-    })
+    match &self.1 {
+      None => write!(f, "{}", self.0),
+      Some(ports) if ports.start() == ports.end() => {
+        write!(f, "{}:{}", self.0, ports.start())
+      }
+      Some(ports) => {
+        write!(f, "{}:{}-{}", self.0, ports.start(), ports.end())
+      }
+    }
   }
 }
 
@@ -1487,6 +1738,23 @@ impl Permissions {
     }
   }
 
+  /// Restrict the permissions to the ones that are also granted by `other`,
+  /// e.g. by a permission policy. The restricted permissions never prompt, so
+  /// they can't be widened past either set.
+  pub fn intersect(&self, other: &Permissions) -> Self {
+    Self {
+      read: self.read.intersect(&other.read),
+      write: self.write.intersect(&other.write),
+      net: self.net.intersect(&other.net),
+      env: self.env.intersect(&other.env),
+      sys: self.sys.intersect(&other.sys),
+      run: self.run.intersect(&other.run),
+      ffi: self.ffi.intersect(&other.ffi),
+      all: self.all.intersect(&other.all),
+      hrtime: self.hrtime.intersect(&other.hrtime),
+    }
+  }
+
   /// A helper function that determines if the module specifier is a local or
   /// remote, and performs a read or net check for the specifier.
   pub fn check_specifier(
@@ -2007,6 +2275,9 @@ pub struct ChildPermissionsArg {
   run: ChildUnaryPermissionArg,
   sys: ChildUnaryPermissionArg,
   write: ChildUnaryPermissionArg,
+  /// Whether the child may prompt for permissions it wasn't granted. A child
+  /// can only prompt when its parent can, which is also the default.
+  prompt: Option<bool>,
 }
 
 impl ChildPermissionsArg {
@@ -2020,6 +2291,7 @@ impl ChildPermissionsArg {
       run: ChildUnaryPermissionArg::Inherit,
       sys: ChildUnaryPermissionArg::Inherit,
       write: ChildUnaryPermissionArg::Inherit,
+      prompt: None,
     }
   }
 
@@ -2033,6 +2305,7 @@ impl ChildPermissionsArg {
       run: ChildUnaryPermissionArg::NotGranted,
       sys: ChildUnaryPermissionArg::NotGranted,
       write: ChildUnaryPermissionArg::NotGranted,
+      prompt: None,
     }
   }
 }
@@ -2118,6 +2391,11 @@ impl<'de> Deserialize<'de> for ChildPermissionsArg {
             child_permissions_arg.write = arg.map_err(|e| {
               de::Error::custom(format!("(deno.permissions.write) {e}"))
             })?;
+          } else if key == "prompt" {
+            let arg = serde_json::from_value::<bool>(value);
+            child_permissions_arg.prompt = Some(arg.map_err(|e| {
+              de::Error::custom(format!("(deno.permissions.prompt) {e}"))
+            })?);
           } else {
             return Err(de::Error::custom("unknown permission name"));
           }
@@ -2133,6 +2411,7 @@ pub fn create_child_permissions(
   main_perms: &mut Permissions,
   child_permissions_arg: ChildPermissionsArg,
 ) -> Result<Permissions, AnyError> {
+  let prompt = child_permissions_arg.prompt;
   let mut worker_perms = Permissions::none_without_prompt();
   worker_perms.read = main_perms
     .read
@@ -2161,6 +2440,17 @@ pub fn create_child_permissions(
   worker_perms.all = main_perms
     .all
     .create_child_permissions(ChildUnitPermissionArg::Inherit)?;
+  if prompt == Some(false) {
+    worker_perms.read.prompt = false;
+    worker_perms.write.prompt = false;
+    worker_perms.net.prompt = false;
+    worker_perms.env.prompt = false;
+    worker_perms.sys.prompt = false;
+    worker_perms.run.prompt = false;
+    worker_perms.ffi.prompt = false;
+    worker_perms.all.prompt = false;
+    worker_perms.hrtime.prompt = false;
+  }
 
   Ok(worker_perms)
 }
@@ -2333,6 +2623,135 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_check_net_with_patterns() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = Permissions::from_options(&PermissionsOptions {
+      allow_net: Some(svec![
+        "*.deno.land",
+        "*.example.com:443",
+        "10.0.0.0/8",
+        "[fd00::]/8:8000-8080",
+        "localhost:3000-3010"
+      ]),
+      deny_net: Some(svec!["blocked.deno.land", "10.1.0.0/16"]),
+      prompt: false,
+      ..Default::default()
+    })
+    .unwrap();
+
+    let domain_tests = vec![
+      ("jsr.deno.land", 80, true),
+      ("a.b.deno.land", 80, true),
+      ("deno.land", 80, false),
+      ("notdeno.land", 80, false),
+      ("blocked.deno.land", 80, false),
+      ("www.example.com", 443, true),
+      ("www.example.com", 80, false),
+      ("10.2.3.4", 22, true),
+      ("10.1.3.4", 22, false),
+      ("11.0.0.1", 22, false),
+      ("[fd12::1]", 8000, true),
+      ("[fd12::1]", 8081, false),
+      ("[fe80::1]", 8000, false),
+      ("localhost", 3000, true),
+      ("localhost", 3010, true),
+      ("localhost", 3011, false),
+    ];
+
+    for (host, port, is_ok) in domain_tests {
+      assert_eq!(
+        is_ok,
+        perms.net.check(&(host, Some(port)), None).is_ok(),
+        "{}:{}",
+        host,
+        port,
+      );
+    }
+
+    assert!(perms
+      .net
+      .check_url(&Url::parse("https://jsr.deno.land/x").unwrap(), None)
+      .is_ok());
+    assert!(perms
+      .net
+      .check_url(&Url::parse("wss://deno.land/").unwrap(), None)
+      .is_err());
+  }
+
+  #[test]
+  fn test_net_descriptor_patterns() {
+    for (value, display) in [
+      ("*.deno.land", "*.deno.land"),
+      ("deno.land:80-90", "deno.land:80-90"),
+      ("10.0.0.0/8:443", "10.0.0.0/8:443"),
+      ("fe80::/10", "[fe80::]/10"),
+      ("[::1]:8000", "[::1]:8000"),
+    ] {
+      assert_eq!(NetDescriptor::from_str(value).unwrap().to_string(), display);
+    }
+    for value in ["10.0.0.0/33", "::1/129", "deno.land:90-80", "deno.land/x"] {
+      assert!(NetDescriptor::from_str(value).is_err(), "{value}");
+    }
+
+    let wildcard = NetDescriptor::from_str("*.deno.land").unwrap();
+    let nested = NetDescriptor::from_str("*.jsr.deno.land:443").unwrap();
+    assert!(wildcard.stronger_than(&nested));
+    assert!(!nested.stronger_than(&wildcard));
+    let block = NetDescriptor::from_str("10.0.0.0/8").unwrap();
+    let subnet = NetDescriptor::from_str("10.1.0.0/16").unwrap();
+    assert!(block.stronger_than(&subnet));
+    assert!(!subnet.stronger_than(&block));
+    let mapped_subnet =
+      NetDescriptor::from_str("[::ffff:10.1.0.0]/112").unwrap();
+    assert!(block.stronger_than(&mapped_subnet));
+    assert!(!mapped_subnet.stronger_than(&block));
+  }
+
+  #[test]
+  fn test_check_net_ipv4_mapped_addresses() {
+    set_prompter(Box::new(TestPrompter));
+    let mut perms = Permissions::from_options(&PermissionsOptions {
+      allow_net: Some(vec![]),
+      deny_net: Some(svec!["10.0.0.0/8", "8.8.8.8"]),
+      prompt: false,
+      ..Default::default()
+    })
+    .unwrap();
+    for (host, is_ok) in [
+      ("[::ffff:10.0.0.1]", false),
+      ("[::ffff:a00:1]", false),
+      ("[::ffff:8.8.8.8]", false),
+      ("10.0.0.1", false),
+      ("[::ffff:11.0.0.1]", true),
+    ] {
+      assert_eq!(
+        is_ok,
+        perms.net.check(&(host, None), None).is_ok(),
+        "{host}"
+      );
+    }
+
+    let mut perms = Permissions::from_options(&PermissionsOptions {
+      allow_net: Some(svec!["[::ffff:192.168.0.0]/112", "127.0.0.1"]),
+      prompt: false,
+      ..Default::default()
+    })
+    .unwrap();
+    for (host, is_ok) in [
+      ("192.168.1.1", true),
+      ("[::ffff:192.168.1.1]", true),
+      ("192.169.1.1", false),
+      ("[::ffff:127.0.0.1]", true),
+    ] {
+      assert_eq!(
+        is_ok,
+        perms.net.check(&(host, None), None).is_ok(),
+        "{host}"
+      );
+    }
+  }
+
   #[test]
   fn test_check_net_only_flag() {
     set_prompter(Box::new(TestPrompter));
@@ -3024,6 +3443,7 @@ mod tests {
         run: ChildUnaryPermissionArg::Inherit,
         sys: ChildUnaryPermissionArg::Inherit,
         write: ChildUnaryPermissionArg::Inherit,
+        prompt: None,
       }
     );
     assert_eq!(
@@ -3037,6 +3457,7 @@ mod tests {
         run: ChildUnaryPermissionArg::NotGranted,
         sys: ChildUnaryPermissionArg::NotGranted,
         write: ChildUnaryPermissionArg::NotGranted,
+        prompt: None,
       }
     );
     assert_eq!(
@@ -3071,6 +3492,20 @@ mod tests {
         ..ChildPermissionsArg::none()
       }
     );
+    assert_eq!(
+      serde_json::from_value::<ChildPermissionsArg>(json!({
+        "prompt": false,
+      }))
+      .unwrap(),
+      ChildPermissionsArg {
+        prompt: Some(false),
+        ..ChildPermissionsArg::none()
+      }
+    );
+    assert!(serde_json::from_value::<ChildPermissionsArg>(json!({
+      "prompt": "inherit",
+    }))
+    .is_err());
     assert_eq!(
       serde_json::from_value::<ChildPermissionsArg>(json!({
         "hrtime": false,
@@ -3165,6 +3600,58 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_intersect_permissions() {
+    set_prompter(Box::new(TestPrompter));
+    let perms = Permissions::from_options(&PermissionsOptions {
+      allow_env: Some(vec![]),
+      allow_hrtime: true,
+      allow_net: Some(svec!["*.deno.land", "example.com"]),
+      allow_read: Some(vec![PathBuf::from("/data")]),
+      deny_read: Some(vec![PathBuf::from("/data/secret")]),
+      prompt: true,
+      ..Default::default()
+    })
+    .unwrap();
+    let policy = Permissions::from_options(&PermissionsOptions {
+      allow_env: Some(svec!["HOME"]),
+      allow_net: Some(svec!["jsr.deno.land", "*.example.com"]),
+      allow_read: Some(vec![
+        PathBuf::from("/data/public"),
+        PathBuf::from("/etc"),
+      ]),
+      allow_write: Some(vec![]),
+      ..Default::default()
+    })
+    .unwrap();
+    let mut restricted = perms.intersect(&policy);
+
+    assert!(restricted.env.check("HOME", None).is_ok());
+    assert!(restricted.env.check("PATH", None).is_err());
+    assert!(restricted.hrtime.check().is_err());
+    assert!(restricted.net.check(&("jsr.deno.land", None), None).is_ok());
+    assert!(restricted.net.check(&("x.deno.land", None), None).is_err());
+    assert!(restricted.net.check(&("example.com", None), None).is_err());
+    assert!(restricted
+      .read
+      .check(Path::new("/data/public/a.txt"), None)
+      .is_ok());
+    assert!(restricted
+      .read
+      .check(Path::new("/data/a.txt"), None)
+      .is_err());
+    assert!(restricted
+      .read
+      .check(Path::new("/etc/hosts"), None)
+      .is_err());
+    assert!(restricted
+      .read
+      .check(Path::new("/data/secret"), None)
+      .is_err());
+    // neither set is widened by prompting
+    assert!(restricted.write.check(Path::new("/data"), None).is_err());
+  }
+
   #[test]
   fn test_create_child_permissions() {
     set_prompter(Box::new(TestPrompter));
@@ -3248,6 +3735,41 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_create_child_permissions_without_prompt() {
+    set_prompter(Box::new(TestPrompter));
+    let prompt_value = PERMISSION_PROMPT_STUB_VALUE_SETTER.lock();
+    let mut main_perms = Permissions::from_options(&PermissionsOptions {
+      prompt: true,
+      ..Default::default()
+    })
+    .unwrap();
+    prompt_value.set(true);
+    let mut worker_perms = create_child_permissions(
+      &mut main_perms,
+      ChildPermissionsArg {
+        prompt: Some(false),
+        ..ChildPermissionsArg::none()
+      },
+    )
+    .unwrap();
+    assert!(worker_perms.env.check("HOME", None).is_err());
+    assert!(worker_perms.hrtime.check().is_err());
+    assert!(main_perms.env.check("HOME", None).is_ok());
+
+    // a child can't prompt when its parent can't
+    let mut main_perms = Permissions::none_without_prompt();
+    let mut worker_perms = create_child_permissions(
+      &mut main_perms,
+      ChildPermissionsArg {
+        prompt: Some(true),
+        ..ChildPermissionsArg::none()
+      },
+    )
+    .unwrap();
+    assert!(worker_perms.env.check("HOME", None).is_err());
+  }
+
   #[test]
   fn test_create_child_permissions_with_inherited_denied_list() {
     set_prompter(Box::new(TestPrompter));
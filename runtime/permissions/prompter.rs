@@ -41,6 +41,8 @@ pub enum PromptResponse {
   Allow,
   Deny,
   AllowAll,
+  /// Allow, and persist the grant for future runs of the project.
+  AllowAlways,
 }
 
 static PERMISSION_PROMPTER: Lazy<Mutex<Box<dyn PermissionPrompter>>> =
@@ -52,6 +54,9 @@ static MAYBE_BEFORE_PROMPT_CALLBACK: Lazy<Mutex<Option<PromptCallback>>> =
 static MAYBE_AFTER_PROMPT_CALLBACK: Lazy<Mutex<Option<PromptCallback>>> =
   Lazy::new(|| Mutex::new(None));
 
+static MAYBE_PERSIST_GRANT_CALLBACK: Lazy<Mutex<Option<PersistGrantCallback>>> =
+  Lazy::new(|| Mutex::new(None));
+
 pub fn permission_prompt(
   message: &str,
   flag: &str,
@@ -80,6 +85,27 @@ pub fn set_prompt_callbacks(
 
 pub type PromptCallback = Box<dyn FnMut() + Send + Sync>;
 
+/// Called with the permission name and the granted value (`None` when all
+/// access was granted) when a prompt is answered with "always allow".
+pub type PersistGrantCallback =
+  Box<dyn FnMut(&str, Option<&str>) + Send + Sync>;
+
+/// Set the callback that stores grants answered with "always allow". The
+/// option is only offered in prompts once a callback is set.
+pub fn set_persist_grant_callback(callback: PersistGrantCallback) {
+  *MAYBE_PERSIST_GRANT_CALLBACK.lock() = Some(callback);
+}
+
+pub(crate) fn persist_grant(name: &str, value: Option<&str>) {
+  if let Some(callback) = MAYBE_PERSIST_GRANT_CALLBACK.lock().as_mut() {
+    callback(name, value);
+  }
+}
+
+fn can_persist_grants() -> bool {
+  MAYBE_PERSIST_GRANT_CALLBACK.lock().is_some()
+}
+
 pub trait PermissionPrompter: Send + Sync {
   fn prompt(
     &mut self,
@@ -308,10 +334,12 @@ impl PermissionPrompter for TtyPrompter {
     let api_name = api_name.map(escape_control_characters);
 
     // print to stderr so that if stdout is piped this is still displayed.
-    let opts: String = if is_unary {
-      format!("[y/n/A] (y = yes, allow; n = no, deny; A = allow all {name} permissions)")
-    } else {
-      "[y/n] (y = yes, allow; n = no, deny)".to_string()
+    let can_persist = can_persist_grants();
+    let opts: String = match (is_unary, can_persist) {
+      (true, true) => format!("[y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all {name} permissions)"),
+      (true, false) => format!("[y/n/A] (y = yes, allow; n = no, deny; A = allow all {name} permissions)"),
+      (false, true) => "[y/n/p] (y = yes, allow; n = no, deny; p = always allow for this project)".to_string(),
+      (false, false) => "[y/n] (y = yes, allow; n = no, deny)".to_string(),
     };
 
     // output everything in one shot to make the tests more reliable
@@ -367,6 +395,15 @@ impl PermissionPrompter for TtyPrompter {
           writeln!(stderr_lock, "❌ {}", colors::bold(&msg)).unwrap();
           break PromptResponse::Deny;
         }
+        'p' | 'P' if can_persist => {
+          clear_n_lines(
+            &mut stderr_lock,
+            if api_name.is_some() { 4 } else { 3 },
+          );
+          let msg = format!("Granted {message} for this project.");
+          writeln!(stderr_lock, "✅ {}", colors::bold(&msg)).unwrap();
+          break PromptResponse::AllowAlways;
+        }
         'A' if is_unary => {
           clear_n_lines(
             &mut stderr_lock,
@@ -65,6 +65,44 @@ extension!(runtime,
   }
 );
 
+// Replaces the `deno_webgpu` extension when the `exclude_webgpu` feature is
+// enabled, providing the modules the runtime imports without the WebGPU
+// implementation, which is a large part of the binary.
+#[cfg(feature = "exclude_webgpu")]
+extension!(deno_webgpu,
+  esm = [dir "js/webgpu_stub", "00_init.js", "02_surface.js"],
+);
+
+pub fn webgpu_init_ops_and_esm() -> Extension {
+  #[cfg(not(feature = "exclude_webgpu"))]
+  {
+    deno_webgpu::deno_webgpu::init_ops_and_esm()
+  }
+  #[cfg(feature = "exclude_webgpu")]
+  {
+    deno_webgpu::init_ops_and_esm()
+  }
+}
+
+// Replaces the `deno_canvas` extension when the `exclude_canvas` feature is
+// enabled, leaving out the image decoding of `createImageBitmap()`.
+#[cfg(feature = "exclude_canvas")]
+extension!(deno_canvas,
+  deps = [deno_webidl, deno_web, deno_webgpu],
+  lazy_loaded_esm = [dir "js/canvas_stub", "01_image.js"],
+);
+
+pub fn canvas_init_ops_and_esm() -> Extension {
+  #[cfg(not(feature = "exclude_canvas"))]
+  {
+    deno_canvas::deno_canvas::init_ops_and_esm()
+  }
+  #[cfg(feature = "exclude_canvas")]
+  {
+    deno_canvas::init_ops_and_esm()
+  }
+}
+
 pub fn maybe_transpile_source(
   name: ModuleName,
   source: ModuleCodeString,
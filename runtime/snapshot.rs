@@ -52,6 +52,14 @@ impl deno_fetch::FetchPermissions for Permissions {
   ) -> Result<(), deno_core::error::AnyError> {
     unreachable!("snapshotting!")
   }
+
+  fn check_write(
+    &mut self,
+    _p: &Path,
+    _api_name: &str,
+  ) -> Result<(), deno_core::error::AnyError> {
+    unreachable!("snapshotting!")
+  }
 }
 
 impl deno_ffi::FfiPermissions for Permissions {
@@ -227,8 +235,8 @@ pub fn create_runtime_snapshot(
       Default::default(),
       Default::default(),
     ),
-    deno_webgpu::deno_webgpu::init_ops_and_esm(),
-    deno_canvas::deno_canvas::init_ops_and_esm(),
+    crate::shared::webgpu_init_ops_and_esm(),
+    crate::shared::canvas_init_ops_and_esm(),
     deno_fetch::deno_fetch::init_ops_and_esm::<Permissions>(Default::default()),
     deno_cache::deno_cache::init_ops_and_esm::<SqliteBackedCache>(None),
     deno_websocket::deno_websocket::init_ops_and_esm::<Permissions>(
@@ -236,7 +244,10 @@ pub fn create_runtime_snapshot(
       None,
       None,
     ),
-    deno_webstorage::deno_webstorage::init_ops_and_esm(None),
+    deno_webstorage::deno_webstorage::init_ops_and_esm(
+      None,
+      Default::default(),
+    ),
     deno_crypto::deno_crypto::init_ops_and_esm(None),
     deno_broadcast_channel::deno_broadcast_channel::init_ops_and_esm(
       deno_broadcast_channel::InMemoryBroadcastChannel::default(),
@@ -1,6 +1,7 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 use crate::inspector_server::InspectorServer;
 use crate::ops;
+use crate::ops::worker_host::attach_worker_error_cause;
 use crate::ops::worker_host::WorkersTable;
 use crate::permissions::PermissionsContainer;
 use crate::shared::maybe_transpile_source;
@@ -359,9 +360,12 @@ pub struct WebWorkerOptions {
   pub extensions: Vec<Extension>,
   pub startup_snapshot: Option<&'static [u8]>,
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
+  /// Disables HTTP/2 for `fetch()`.
+  pub http1_only: bool,
   pub root_cert_store_provider: Option<Arc<dyn RootCertStoreProvider>>,
   pub seed: Option<u64>,
   pub fs: Arc<dyn FileSystem>,
+  pub fs_fetch_read_file: Option<deno_fetch::FsFetchReadFileFn>,
   pub module_loader: Rc<dyn ModuleLoader>,
   pub npm_resolver: Option<Arc<dyn deno_node::NpmResolver>>,
   pub create_web_worker_cb: Arc<ops::worker_host::CreateWebWorkerCb>,
@@ -435,8 +439,8 @@ impl WebWorker {
         options.blob_store.clone(),
         Some(main_module.clone()),
       ),
-      deno_webgpu::deno_webgpu::init_ops_and_esm(),
-      deno_canvas::deno_canvas::init_ops_and_esm(),
+      crate::shared::webgpu_init_ops_and_esm(),
+      crate::shared::canvas_init_ops_and_esm(),
       deno_fetch::deno_fetch::init_ops_and_esm::<PermissionsContainer>(
         deno_fetch::Options {
           user_agent: options.bootstrap.user_agent.clone(),
@@ -444,7 +448,10 @@ impl WebWorker {
           unsafely_ignore_certificate_errors: options
             .unsafely_ignore_certificate_errors
             .clone(),
-          file_fetch_handler: Rc::new(deno_fetch::FsFetchHandler),
+          file_fetch_handler: Rc::new(deno_fetch::FsFetchHandler::new(
+            options.fs_fetch_read_file.clone(),
+          )),
+          http1_only: options.http1_only,
           ..Default::default()
         },
       ),
@@ -456,7 +463,11 @@ impl WebWorker {
         options.root_cert_store_provider.clone(),
         options.unsafely_ignore_certificate_errors.clone(),
       ),
-      deno_webstorage::deno_webstorage::init_ops_and_esm(None).disable(),
+      deno_webstorage::deno_webstorage::init_ops_and_esm(
+        None,
+        Default::default(),
+      )
+      .disable(),
       deno_crypto::deno_crypto::init_ops_and_esm(options.seed),
       deno_broadcast_channel::deno_broadcast_channel::init_ops_and_esm(
         options.broadcast_channel.clone(),
@@ -878,11 +889,11 @@ impl WebWorker {
   }
 }
 
-fn print_worker_error(
+fn format_worker_error(
   error: &AnyError,
   name: &str,
   format_js_error_fn: Option<&FormatJsErrorFn>,
-) {
+) -> String {
   let error_str = match format_js_error_fn {
     Some(format_js_error_fn) => match error.downcast_ref::<JsError>() {
       Some(js_error) => format_js_error_fn(js_error),
@@ -890,12 +901,12 @@ fn print_worker_error(
     },
     None => error.to_string(),
   };
-  eprintln!(
+  format!(
     "{}: Uncaught (in worker \"{}\") {}",
     colors::red_bold("error"),
     name,
     error_str.trim_start_matches("Uncaught "),
-  );
+  )
 }
 
 /// This function should be called from a thread dedicated to this worker.
@@ -949,10 +960,21 @@ pub fn run_web_worker(
     };
 
     if let Err(e) = result {
-      print_worker_error(&e, &name, format_js_error_fn.as_deref());
-      internal_handle
+      let e = attach_worker_error_cause(
+        &mut worker.js_runtime.op_state().borrow_mut(),
+        e,
+      );
+      // The error is reported by the host, either to an "error" event
+      // listener or as the cause of the error it throws if it's unhandled.
+      // Only print it here if the host has already gone away.
+      let error_str =
+        format_worker_error(&e, &name, format_js_error_fn.as_deref());
+      if internal_handle
         .post_event(WorkerControlEvent::TerminalError(e))
-        .expect("Failed to post message to host");
+        .is_err()
+      {
+        eprintln!("{error_str}");
+      }
 
       // Failure to execute script is a terminal error, bye, bye.
       return Ok(());
@@ -145,10 +145,15 @@ pub struct WorkerOptions {
   pub create_params: Option<v8::CreateParams>,
 
   pub unsafely_ignore_certificate_errors: Option<Vec<String>>,
+  /// Disables HTTP/2 for `fetch()`.
+  pub http1_only: bool,
   pub root_cert_store_provider: Option<Arc<dyn RootCertStoreProvider>>,
   pub seed: Option<u64>,
 
   pub fs: Arc<dyn FileSystem>,
+  /// Reads the files of `file:` urls passed to `fetch` that don't come from
+  /// the real file system.
+  pub fs_fetch_read_file: Option<deno_fetch::FsFetchReadFileFn>,
   /// Implementation of `ModuleLoader` which will be
   /// called when V8 requests to load ES modules.
   ///
@@ -178,6 +183,8 @@ pub struct WorkerOptions {
   pub get_error_class_fn: Option<GetErrorClassFn>,
   pub cache_storage_dir: Option<std::path::PathBuf>,
   pub origin_storage_dir: Option<std::path::PathBuf>,
+  /// Options of `localStorage`, which is persisted in `origin_storage_dir`.
+  pub local_storage: deno_webstorage::LocalStorageOptions,
   pub blob_store: Arc<BlobStore>,
   pub broadcast_channel: InMemoryBroadcastChannel,
 
@@ -209,10 +216,12 @@ impl Default for WorkerOptions {
         unimplemented!("web workers are not supported")
       }),
       fs: Arc::new(deno_fs::RealFs),
+      fs_fetch_read_file: None,
       module_loader: Rc::new(FsModuleLoader),
       skip_op_registration: false,
       seed: None,
       unsafely_ignore_certificate_errors: Default::default(),
+      http1_only: false,
       should_break_on_first_statement: Default::default(),
       should_wait_for_inspector_session: Default::default(),
       strace_ops: Default::default(),
@@ -222,6 +231,7 @@ impl Default for WorkerOptions {
       format_js_error_fn: Default::default(),
       get_error_class_fn: Default::default(),
       origin_storage_dir: Default::default(),
+      local_storage: Default::default(),
       cache_storage_dir: Default::default(),
       broadcast_channel: Default::default(),
       source_map_getter: Default::default(),
@@ -405,8 +415,8 @@ impl MainWorker {
         options.blob_store.clone(),
         options.bootstrap.location.clone(),
       ),
-      deno_webgpu::deno_webgpu::init_ops_and_esm(),
-      deno_canvas::deno_canvas::init_ops_and_esm(),
+      crate::shared::webgpu_init_ops_and_esm(),
+      crate::shared::canvas_init_ops_and_esm(),
       deno_fetch::deno_fetch::init_ops_and_esm::<PermissionsContainer>(
         deno_fetch::Options {
           user_agent: options.bootstrap.user_agent.clone(),
@@ -414,7 +424,10 @@ impl MainWorker {
           unsafely_ignore_certificate_errors: options
             .unsafely_ignore_certificate_errors
             .clone(),
-          file_fetch_handler: Rc::new(deno_fetch::FsFetchHandler),
+          file_fetch_handler: Rc::new(deno_fetch::FsFetchHandler::new(
+            options.fs_fetch_read_file.clone(),
+          )),
+          http1_only: options.http1_only,
           ..Default::default()
         },
       ),
@@ -428,6 +441,7 @@ impl MainWorker {
       ),
       deno_webstorage::deno_webstorage::init_ops_and_esm(
         options.origin_storage_dir.clone(),
+        options.local_storage.clone(),
       ),
       deno_crypto::deno_crypto::init_ops_and_esm(options.seed),
       deno_broadcast_channel::deno_broadcast_channel::init_ops_and_esm(
@@ -752,7 +766,7 @@ impl MainWorker {
   ) -> Result<(), AnyError> {
     self.wait_for_inspector_session();
     let mut receiver = self.js_runtime.mod_evaluate(id);
-    tokio::select! {
+    let result = tokio::select! {
       // Not using biased mode leads to non-determinism for relatively simple
       // programs.
       biased;
@@ -766,7 +780,8 @@ impl MainWorker {
         event_loop_result?;
         receiver.await
       }
-    }
+    };
+    result.map_err(|e| self.attach_worker_error_cause(e))
   }
 
   /// Run the event loop up to a given duration. If the runtime resolves early, returns
@@ -839,6 +854,15 @@ impl MainWorker {
         ..Default::default()
       })
       .await
+      .map_err(|e| self.attach_worker_error_cause(e))
+  }
+
+  /// Report the error of a child worker, whose "error" event wasn't handled,
+  /// as the cause of the error that was thrown because of it.
+  fn attach_worker_error_cause(&mut self, error: AnyError) -> AnyError {
+    let op_state = self.js_runtime.op_state();
+    let mut op_state = op_state.borrow_mut();
+    ops::worker_host::attach_worker_error_cause(&mut op_state, error)
   }
 
   /// Return exit code set by the executed code (either in main worker
@@ -1,10 +1,12 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::serde_json::json;
 use test_util as util;
 use test_util::assert_contains;
 use test_util::assert_ends_with;
 use test_util::itest;
 use test_util::TempDir;
+use test_util::TestContextBuilder;
 
 #[test]
 fn bundle_exports() {
@@ -416,6 +418,124 @@ fn bundle_json_module_escape_sub() {
   );
 }
 
+#[test]
+fn bundle_code_splitting() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "a.ts",
+    concat!(
+      "import { greet } from './shared.ts';\n",
+      "console.log(greet('a'));\n",
+      "const { lazy } = await import('./lazy.ts');\n",
+      "console.log(lazy());\n",
+    ),
+  );
+  temp_dir.write(
+    "b.ts",
+    "import { greet } from './shared.ts';\nconsole.log(greet('b'));\n",
+  );
+  temp_dir.write(
+    "shared.ts",
+    concat!(
+      "import { PREFIX } from './util.ts';\n",
+      "export function greet(name: string) { return `${PREFIX} ${name}`; }\n",
+    ),
+  );
+  temp_dir.write("util.ts", "export const PREFIX = 'Hello from';\n");
+  temp_dir.write("lazy.ts", "export function lazy() { return 'lazy'; }\n");
+
+  let output = context
+    .new_command()
+    .args("bundle --outdir build/dist a.ts b.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  // the output directory is created, including its parents
+  let dist = temp_dir.path().join("build/dist");
+  let manifest = dist.join("manifest.json").read_json_value();
+  assert_eq!(
+    manifest["entries"],
+    json!({
+      temp_dir.uri().join("a.ts").unwrap().to_string(): "a.js",
+      temp_dir.uri().join("b.ts").unwrap().to_string(): "b.js",
+    })
+  );
+  let chunks = &manifest["chunks"];
+  assert_eq!(chunks["a.js"]["imports"], json!(["chunk-shared.js"]));
+  assert_eq!(chunks["a.js"]["dynamicImports"], json!(["chunk-lazy.js"]));
+  assert_eq!(chunks["b.js"]["imports"], json!(["chunk-shared.js"]));
+  assert_eq!(
+    chunks["chunk-shared.js"]["modules"],
+    json!([
+      temp_dir.uri().join("shared.ts").unwrap().to_string(),
+      temp_dir.uri().join("util.ts").unwrap().to_string(),
+    ])
+  );
+
+  // the shared code is only emitted once
+  assert_contains!(dist.join("chunk-shared.js").read_to_string(), "Hello from");
+  assert!(!dist.join("a.js").read_to_string().contains("Hello from"));
+  assert!(!dist.join("b.js").read_to_string().contains("Hello from"));
+
+  let output = context.new_command().args("run dist/a.js").run();
+  output.assert_matches_text("Hello from a\nlazy\n");
+  let output = context.new_command().args("run dist/b.js").run();
+  output.assert_matches_text("Hello from b\n");
+}
+
+#[test]
+fn bundle_minify_with_source_map() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "main.ts",
+    concat!(
+      "import { add } from './math.ts';\n",
+      "const firstNumber: number = 1;\n",
+      "const secondNumber: number = 2;\n",
+      "console.log(add(firstNumber, secondNumber));\n",
+    ),
+  );
+  temp_dir.write(
+    "math.ts",
+    "export function add(left: number, right: number) {\n  return left + right;\n}\n",
+  );
+
+  let output = context
+    .new_command()
+    .args("bundle --minify --sourcemap main.ts out.js")
+    .run();
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let code = temp_dir.read_to_string("out.js");
+  assert!(!code.contains("firstNumber"));
+  assert!(!code.contains("    "));
+  assert!(code.ends_with("//# sourceMappingURL=out.js.map"));
+  let source_map = temp_dir.path().join("out.js.map").read_json_value();
+  let sources = source_map["sources"].as_array().unwrap();
+  assert!(sources.contains(&json!(temp_dir
+    .uri()
+    .join("math.ts")
+    .unwrap()
+    .to_string())));
+
+  let output = context.new_command().args("run out.js").run();
+  output.assert_matches_text("3\n");
+
+  let output = context
+    .new_command()
+    .args("bundle --sourcemap=inline main.ts")
+    .run();
+  output.assert_exit_code(0);
+  assert_contains!(
+    output.stdout(),
+    "//# sourceMappingURL=data:application/json;base64,"
+  );
+}
+
 itest!(lockfile_check_error {
   args: "bundle --lock=bundle/lockfile/check_error.json http://127.0.0.1:4545/subdir/mod1.ts",
   output: "bundle/lockfile/check_error.out",
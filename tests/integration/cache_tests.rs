@@ -45,6 +45,262 @@ fn cache_matching_package_json_dep_should_not_install_all() {
   ));
 }
 
+#[test]
+fn cache_prune() {
+  let context = TestContextBuilder::new()
+    .use_http_server()
+    .use_temp_cwd()
+    .build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "a.ts",
+    "import { printHello } from 'http://localhost:4545/subdir/print_hello.ts';
+printHello();",
+  );
+  temp_dir.write(
+    "b.ts",
+    "import 'http://localhost:4545/subdir/single_module.ts';",
+  );
+  context
+    .new_command()
+    .args("cache a.ts b.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+
+  let output = context
+    .new_command()
+    .args("cache --prune --dry-run a.ts")
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text(
+    "[WILDCARD]  http://localhost:4545 [WILDCARD]
+[WILDCARD]Would remove [WILDCARD]
+Would remove [WILDCARD] files and 0 npm packages ([WILDCARD]).
+",
+  );
+
+  // nothing was removed by the dry run
+  context
+    .new_command()
+    .args("run --cached-only b.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+
+  // a dry run is the default
+  context
+    .new_command()
+    .args("cache --prune a.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  context
+    .new_command()
+    .args("run --cached-only b.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+
+  context
+    .new_command()
+    .args("cache --prune --force a.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  context
+    .new_command()
+    .args("run --cached-only a.ts")
+    .run()
+    .assert_matches_text("Hello\n")
+    .assert_exit_code(0);
+  context
+    .new_command()
+    .args("run --cached-only b.ts")
+    .run()
+    .assert_matches_text(
+      "error: Specifier not found in cache: \"http://localhost:4545/subdir/single_module.ts\", --cached-only is specified.[WILDCARD]",
+    )
+    .assert_exit_code(1);
+}
+
+#[test]
+fn cache_prune_force_requires_modules_or_lockfile() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  context
+    .new_command()
+    .args("cache --prune --force")
+    .run()
+    .assert_matches_text(
+      "error: Refusing to prune the cache without a lockfile or modules to keep[WILDCARD]",
+    )
+    .assert_exit_code(1);
+}
+
+#[test]
+fn cache_prune_keeps_registered_projects() {
+  let context = TestContextBuilder::new()
+    .use_http_server()
+    .use_temp_cwd()
+    .build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "a.ts",
+    "import { printHello } from 'http://localhost:4545/subdir/print_hello.ts';
+printHello();",
+  );
+  temp_dir.create_dir_all("other");
+  temp_dir.write(
+    "other/b.ts",
+    "import 'http://localhost:4545/subdir/single_module.ts';",
+  );
+  context
+    .new_command()
+    .args("cache a.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  // registers the lockfile of the other project
+  context
+    .new_command()
+    .args("cache --lock=other/deno.lock other/b.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+
+  context
+    .new_command()
+    .args("cache --prune --force a.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  context
+    .new_command()
+    .args("run --cached-only other/b.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+}
+
+#[test]
+fn cache_export_and_import() {
+  let main_ts =
+    "import { printHello } from 'http://localhost:4546/subdir/print_hello.ts';
+printHello();";
+  let context = TestContextBuilder::new()
+    .use_http_server()
+    .use_temp_cwd()
+    .build();
+  context.temp_dir().write("main.ts", main_ts);
+  context
+    .new_command()
+    .args("cache --export cache.tar.zst main.ts")
+    .run()
+    .assert_matches_text(
+      "[WILDCARD]Exported 2 remote modules to [WILDCARD]cache.tar.zst\n",
+    )
+    .assert_exit_code(0);
+  let archive_path = context.temp_dir().path().join("cache.tar.zst");
+
+  // a fresh DENO_DIR
+  let context = TestContextBuilder::new()
+    .use_http_server()
+    .use_temp_cwd()
+    .build();
+  context.temp_dir().write("main.ts", main_ts);
+  context
+    .new_command()
+    .args_vec(["cache", "--import", &archive_path.to_string_lossy()])
+    .run()
+    .assert_matches_text("Imported 2 remote modules from [WILDCARD]\n")
+    .assert_exit_code(0);
+  context
+    .new_command()
+    .args("run --cached-only main.ts")
+    .run()
+    .assert_matches_text("Hello\n")
+    .assert_exit_code(0);
+}
+
+#[test]
+fn cache_export_and_import_npm() {
+  let main_ts = "import { getValue } from 'npm:@denotest/esm-basic';
+console.log(getValue());";
+  let context = TestContextBuilder::for_npm().use_temp_cwd().build();
+  context.temp_dir().write("main.ts", main_ts);
+  context
+    .new_command()
+    .args("cache --export cache.tar.zst main.ts")
+    .run()
+    .assert_matches_text(
+      "[WILDCARD]Exported 0 remote modules and 1 npm package to [WILDCARD]cache.tar.zst\n",
+    )
+    .assert_exit_code(0);
+  let archive_path = context.temp_dir().path().join("cache.tar.zst");
+
+  // a fresh DENO_DIR
+  let context = TestContextBuilder::for_npm().use_temp_cwd().build();
+  context.temp_dir().write("main.ts", main_ts);
+  context
+    .new_command()
+    .args_vec(["cache", "--import", &archive_path.to_string_lossy()])
+    .run()
+    .assert_matches_text(
+      "Imported 0 remote modules and 1 npm package from [WILDCARD]\n",
+    )
+    .assert_exit_code(0);
+  context
+    .new_command()
+    .args("run --cached-only main.ts")
+    .run()
+    .assert_matches_text("0\n")
+    .assert_exit_code(0);
+}
+
+#[test]
+fn cache_import_verifies_lockfile() {
+  let context = TestContextBuilder::new()
+    .use_http_server()
+    .use_temp_cwd()
+    .build();
+  context.temp_dir().write(
+    "main.ts",
+    "import { printHello } from 'http://localhost:4546/subdir/print_hello.ts';
+printHello();",
+  );
+  context
+    .new_command()
+    .args("cache --export cache.tar.zst main.ts")
+    .run()
+    .skip_output_check()
+    .assert_exit_code(0);
+  let archive_path = context.temp_dir().path().join("cache.tar.zst");
+
+  // a fresh DENO_DIR with a lockfile that doesn't match the archive
+  let context = TestContextBuilder::new()
+    .use_http_server()
+    .use_temp_cwd()
+    .build();
+  context.temp_dir().write("deno.json", "{}");
+  context.temp_dir().write(
+    "deno.lock",
+    r#"{
+  "version": "3",
+  "remote": {
+    "http://localhost:4546/subdir/print_hello.ts": "0000000000000000000000000000000000000000000000000000000000000000"
+  }
+}"#,
+  );
+  context
+    .new_command()
+    .args_vec(["cache", "--import", &archive_path.to_string_lossy()])
+    .run()
+    .assert_matches_text(
+      "error: Integrity check failed for http://localhost:4546/subdir/print_hello.ts, which doesn't match the lockfile.[WILDCARD]",
+    )
+    .assert_exit_code(1);
+}
+
 // Regression test for https://github.com/denoland/deno/issues/17299
 #[test]
 fn cache_put_overwrite() {
@@ -48,6 +48,49 @@ fn compile_basic() {
   output.assert_matches_text("Welcome to Deno!\n");
 }
 
+#[test]
+fn compile_no_web_apis() {
+  let context = TestContextBuilder::new().build();
+  let dir = context.temp_dir();
+  dir.write(
+    "main.ts",
+    "console.log(typeof navigator.gpu, typeof GPUBuffer);\n",
+  );
+  let exe = if cfg!(windows) {
+    dir.path().join("main.exe")
+  } else {
+    dir.path().join("main")
+  };
+  let exe_path = exe.to_string_lossy().to_string();
+  let main_path = dir.path().join("main.ts").to_string_lossy().to_string();
+  let args = [
+    "compile",
+    "--no-web-apis",
+    "--output",
+    &exe_path,
+    &main_path,
+  ];
+
+  // the full binary given with DENORT_BIN isn't used
+  let output = context
+    .new_command()
+    .env_remove("DENORT_LITE_BIN")
+    .args_vec(args)
+    .run();
+  output.assert_exit_code(1);
+  output.assert_matches_text(
+    "[WILDCARD]error: DENORT_LITE_BIN must be set to use --no-web-apis with DENORT_BIN.\n",
+  );
+  assert!(!exe.exists());
+
+  // uses the denort binary built with the `lite` feature
+  let output = context.new_command().args_vec(args).run();
+  output.assert_exit_code(0);
+  output.skip_output_check();
+  let output = context.new_command().name(&exe).run();
+  output.assert_matches_text("undefined undefined\n");
+}
+
 #[test]
 fn standalone_args() {
   let context = TestContextBuilder::new().build();
@@ -612,12 +655,38 @@ fn workers_not_in_module_map() {
   output.assert_exit_code(0);
   output.skip_output_check();
 
+  // the worker entrypoint is discovered from the `new Worker()` call
   let output = context.new_command().name(exe).env("NO_COLOR", "").run();
-  output.assert_exit_code(1);
-  output.assert_matches_text(concat!(
-    "error: Uncaught (in worker \"\") Module not found: [WILDCARD]",
-    "error: Uncaught (in promise) Error: Unhandled error in child worker.\n[WILDCARD]"
-  ));
+  output.assert_exit_code(0);
+  output.assert_matches_text("Hello from worker!\nReceived 42\nClosing\n");
+}
+
+#[test]
+fn workers_unresolvable() {
+  let context = TestContext::with_http_server();
+  let temp_dir = context.temp_dir();
+  let exe = if cfg!(windows) {
+    temp_dir.path().join("unresolvable_worker.exe")
+  } else {
+    temp_dir.path().join("unresolvable_worker")
+  };
+  let output = context
+    .new_command()
+    .args_vec([
+      "compile",
+      "--output",
+      &exe.to_string_lossy(),
+      "./compile/workers/unresolvable_worker.ts",
+    ])
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text(
+    "[WILDCARD]Warning Worker file:///[WILDCARD]/missing_worker.ts won't be included in the executable: [WILDCARD]",
+  );
+
+  let output = context.new_command().name(exe).env("NO_COLOR", "").run();
+  output.assert_exit_code(0);
+  output.assert_matches_text("Hello from main!\n");
 }
 
 #[test]
@@ -591,6 +591,50 @@ fn test_html_reporter() {
   assert!(baz_quux_ts_html.contains("<h1>Coverage report for baz/quux.ts</h1>"));
 }
 
+#[test]
+fn test_html_reporter_output_dir() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let cov_dir = tempdir.path().join("cov");
+  let html_dir = tempdir.path().join("report");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "test".to_string(),
+      "--quiet".to_string(),
+      format!("--coverage={}", cov_dir),
+      "coverage/multisource".to_string(),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      format!("--html={}", html_dir),
+      format!("{}/", cov_dir),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.assert_matches_text(
+    "HTML coverage report has been generated at [WILDCARD]/report/index.html\n",
+  );
+
+  assert!(!cov_dir.join("html").exists());
+  let index_html = fs::read_to_string(html_dir.join("index.html")).unwrap();
+  assert!(index_html.contains("<h1>Coverage report for all files</h1>"));
+  assert!(index_html.contains("href='baz/index.html'"));
+
+  let foo_ts_html = fs::read_to_string(html_dir.join("foo.ts.html")).unwrap();
+  assert!(foo_ts_html.contains("<h1>Coverage report for foo.ts</h1>"));
+  assert!(html_dir.join("baz").join("qux.ts.html").exists());
+}
+
 #[test]
 fn test_summary_reporter() {
   let context = TestContext::default();
@@ -621,6 +665,7 @@ fn test_summary_reporter() {
 File         | Branch % | Line % |
 ----------------------------------
  bar.ts      |      0.0 |   57.1 |
+ baz/        |     50.0 |   52.4 |
  baz/quux.ts |      0.0 |   28.6 |
  baz/qux.ts  |    100.0 |  100.0 |
  foo.ts      |     50.0 |   76.9 |
@@ -630,3 +675,132 @@ File         | Branch % | Line % |
 ",
   );
 }
+
+#[test]
+fn test_fail_under_thresholds() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let tempdir = tempdir.path().join("cov");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "test".to_string(),
+      "--quiet".to_string(),
+      format!("--coverage={}", tempdir),
+      "coverage/multisource".to_string(),
+    ])
+    .run();
+
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--fail-under-lines=60".to_string(),
+      format!("{}/", tempdir),
+    ])
+    .run();
+  output.assert_exit_code(0);
+  output.skip_output_check();
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--fail-under-lines=80".to_string(),
+      "--fail-under-branches=30".to_string(),
+      format!("{}/", tempdir),
+    ])
+    .run();
+  output.assert_exit_code(1);
+  output.assert_matches_text(
+    "[WILDCARD] All files   |     40.0 |   61.0 |
+----------------------------------
+error: Line coverage of 61.0% is below the threshold of 80%
+",
+  );
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--fail-under-lines=80".to_string(),
+      "--fail-under-branches=50".to_string(),
+      format!("{}/", tempdir),
+    ])
+    .run();
+  output.assert_exit_code(1);
+  output.assert_matches_text(
+    "[WILDCARD] All files   |     40.0 |   61.0 |
+----------------------------------
+error: Line coverage of 61.0% is below the threshold of 80%
+Branch coverage of 40.0% is below the threshold of 50%
+",
+  );
+}
+
+#[test]
+fn test_merge_profiles() {
+  let context = TestContext::default();
+  let tempdir = context.temp_dir();
+  let merged_dir = tempdir.path().join("cov_merged");
+
+  for name in ["cov_a", "cov_b"] {
+    let output = context
+      .new_command()
+      .args_vec(vec![
+        "test".to_string(),
+        "--quiet".to_string(),
+        format!("--coverage={}", tempdir.path().join(name)),
+        "coverage/branch_test.ts".to_string(),
+      ])
+      .run();
+    output.assert_exit_code(0);
+    output.skip_output_check();
+  }
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "merge".to_string(),
+      format!("{}/", tempdir.path().join("cov_a")),
+      format!("{}/", tempdir.path().join("cov_b")),
+      format!("--output={}", merged_dir),
+    ])
+    .run();
+  output.assert_exit_code(0);
+  output.assert_matches_text("Merged [WILDCARD] coverage files into [WILDCARD] scripts at [WILDCARD]cov_merged\n");
+
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "--lcov".to_string(),
+      format!("{}/", merged_dir),
+    ])
+    .run();
+  output.assert_exit_code(0);
+  let lcov = output.combined_output();
+  assert!(lcov.contains("FNDA:2,branch\n"));
+  assert!(lcov.contains("DA:2,4\n"));
+  assert!(lcov.contains("BRDA:4,1,0,0\n"));
+
+  // merging into a directory that already holds a profile is refused
+  let output = context
+    .new_command()
+    .args_vec(vec![
+      "coverage".to_string(),
+      "merge".to_string(),
+      format!("{}/", tempdir.path().join("cov_a")),
+      format!("--output={}", merged_dir),
+    ])
+    .run();
+  output.assert_exit_code(1);
+  output.assert_matches_text(
+    "error: Output directory [WILDCARD]cov_merged is not empty\n",
+  );
+}
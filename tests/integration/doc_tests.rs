@@ -1,5 +1,6 @@
 // Copyright 2018-2024 the Deno authors. All rights reserved. MIT license.
 
+use deno_core::serde_json;
 use test_util as util;
 use test_util::itest;
 use util::assert_contains;
@@ -81,6 +82,23 @@ itest!(deno_doc_lint_json_success {
   output: "doc/lint_success_json.out",
 });
 
+itest!(deno_doc_json_filter {
+  args: "doc --json --filter=Widget doc/lint_unresolved_link.ts",
+  output: "doc/json_filter.out",
+});
+
+itest!(deno_doc_lint_unresolved_link {
+  args: "doc --lint doc/lint_unresolved_link.ts",
+  exit_code: 1,
+  output: "doc/lint_unresolved_link.out",
+});
+
+itest!(deno_doc_lint_json_unresolved_link {
+  args: "doc --lint --json --json-version=1 doc/lint_unresolved_link.ts",
+  exit_code: 1,
+  output: "doc/lint_unresolved_link_json.out",
+});
+
 itest!(deno_doc_lint_html_success {
   args: "doc --lint --html --name=Library lint_success.ts",
   copy_temp_dir: Some("doc"),
@@ -124,7 +142,7 @@ fn deno_doc_html() {
     .run();
 
   output.assert_exit_code(0);
-  assert_contains!(output.stderr(), "Written 13 files to");
+  assert_contains!(output.stderr(), "Written 14 files to");
   assert!(temp_dir.path().join("all_symbols.html").exists());
   assert!(temp_dir.path().join("index.html").exists());
   assert!(temp_dir.path().join("fuse.js").exists());
@@ -132,6 +150,17 @@ fn deno_doc_html() {
   assert!(temp_dir.path().join("script.js").exists());
   assert!(temp_dir.path().join("search.js").exists());
   assert!(temp_dir.path().join("search_index.js").exists());
+  let search_index: serde_json::Value = serde_json::from_str(
+    &temp_dir.path().join("search_index.json").read_to_string(),
+  )
+  .unwrap();
+  let names = search_index["nodes"]
+    .as_array()
+    .unwrap()
+    .iter()
+    .map(|node| node["name"].as_str().unwrap())
+    .collect::<Vec<_>>();
+  assert!(names.contains(&"MyClass"), "{names:?}");
   assert!(temp_dir.path().join("styles.css").exists());
   assert!(temp_dir.path().join("~/MyInterface.html").exists());
   assert!(temp_dir.path().join("~/MyInterface.prop.html").exists());
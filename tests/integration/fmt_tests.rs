@@ -230,6 +230,43 @@ itest!(fmt_stdin_check_not_formatted {
   output_str: Some("Not formatted stdin\n"),
 });
 
+itest!(fmt_stdin_filepath_markdown {
+  args: "fmt --stdin-filepath=docs/README.md",
+  input: Some("# Hello      Markdown\n```ts\nconsole.log( \"text\")\n```"),
+  output_str: Some("# Hello Markdown\n\n```ts\nconsole.log(\"text\");\n```\n"),
+});
+
+#[test]
+fn fmt_stdin_filepath_config() {
+  let context = TestContext::default();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "project/deno.json",
+    r#"{ "fmt": { "singleQuote": true, "exclude": ["generated/"] } }"#,
+  );
+  temp_dir.create_dir_all("project/src");
+
+  // the config file is discovered from the path, not the cwd
+  let output = context
+    .new_command()
+    .args("fmt --stdin-filepath=project/src/mod.ts")
+    .stdin_text("const a = \"b\"\n")
+    .split_output()
+    .run();
+  output.assert_exit_code(0);
+  assert_eq!(output.stdout(), "const a = 'b';\n");
+
+  // excluded paths are written back unchanged
+  let output = context
+    .new_command()
+    .args("fmt --stdin-filepath=project/generated/mod.ts")
+    .stdin_text("const a = \"b\"\n")
+    .split_output()
+    .run();
+  output.assert_exit_code(0);
+  assert_eq!(output.stdout(), "const a = \"b\"\n");
+}
+
 itest!(fmt_with_config {
   args: "fmt --config fmt/with_config/deno.jsonc fmt/with_config/subdir",
   output: "fmt/fmt_with_config.out",
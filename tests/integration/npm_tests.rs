@@ -2403,6 +2403,45 @@ console.log(getValue());
   output.assert_matches_text("Check file:///[WILDCARD]/main.ts\n");
 }
 
+#[test]
+fn byonm_package_json_exports_and_imports() {
+  let test_context = TestContextBuilder::for_npm()
+    .env("DENO_UNSTABLE_BYONM", "1")
+    .use_temp_cwd()
+    .build();
+  let dir = test_context.temp_dir();
+  dir.write(
+    "package.json",
+    r##"{
+    "name": "my-project",
+    "version": "1.0.0",
+    "type": "module",
+    "imports": {
+      "#greeting": "./greeting.js"
+    },
+    "dependencies": {
+      "@denotest/conditional-exports": "^1.0"
+    }
+}"##,
+  );
+  test_context.run_npm("install");
+
+  dir.write("greeting.js", "export const greeting = 'Hello';\n");
+  dir.write(
+    "main.ts",
+    r##"
+// resolved with the "imports" field of the package.json
+import { greeting } from "#greeting";
+// resolved with the "exports" field of the package in node_modules
+import client from "@denotest/conditional-exports/client/foo";
+
+console.log(greeting, client.hello);
+"##,
+  );
+  let output = test_context.new_command().args("run main.ts").run();
+  output.assert_matches_text("Hello from esm client foo\n");
+}
+
 #[test]
 fn future_byonm_import_map() {
   let test_context = TestContextBuilder::for_npm()
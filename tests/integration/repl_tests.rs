@@ -163,6 +163,9 @@ fn pty_complete_imports() {
       console.expect("\"./run/output.ts\"");
       console.write_line_raw("output('testing output');");
       console.expect("testing output");
+      // dynamic import
+      console.write_line_raw("await import('./subdir/my_f\t')");
+      console.expect("[Module: null prototype]");
     });
 
   // ensure when the directory changes that the suggestions come from the cwd
@@ -328,6 +331,47 @@ fn repl_cwd() {
     });
 }
 
+#[test]
+fn interrupt_evaluation() {
+  util::with_pty(&["repl"], |mut console| {
+    console.write_line("const a = 5;");
+    console.expect("undefined");
+    console.write_line("while (true) {}");
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    console.write_raw("\x03");
+    console.expect("Interrupted");
+    console.write_line("await new Promise(() => {})");
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    console.write_raw("\x03");
+    console.expect("Interrupted");
+    console.write_line("a + 1");
+    console.expect("6");
+    // ctrl+c between evaluations clears the line rather than exiting
+    console.write_raw("a +");
+    console.write_raw("\x03");
+    console.expect("press ctrl+c again to exit");
+    console.write_line("a + 2");
+    console.expect("7");
+  });
+}
+
+#[test]
+fn interrupt_evaluation_with_temp_cleanup() {
+  util::with_pty(&["repl", "-A"], |mut console| {
+    // installs the SIGINT handler removing the temporary paths
+    console.write_line(
+      "const dir = Deno.makeTempDirSync({ cleanupAtExit: true }); 'created'",
+    );
+    console.expect("created");
+    console.write_line("while (true) {}");
+    std::thread::sleep(std::time::Duration::from_millis(500));
+    console.write_raw("\x03");
+    console.expect("Interrupted");
+    console.write_line("Deno.statSync(dir).isDirectory");
+    console.expect("true");
+  });
+}
+
 #[test]
 fn typescript() {
   util::with_pty(&["repl"], |mut console| {
@@ -340,6 +384,20 @@ fn typescript() {
   });
 }
 
+#[test]
+fn type_command() {
+  util::with_pty(&["repl"], |mut console| {
+    console.write_line("const user = { name: 'Deno', age: 5 };");
+    console.expect("undefined");
+    console.write_line(".type user.age");
+    console.expect("number");
+    console.write_line(".type [user.name]");
+    console.expect("string[]");
+    console.write_line("user.age");
+    console.expect("5");
+  });
+}
+
 #[test]
 fn typescript_declarations() {
   util::with_pty(&["repl"], |mut console| {
@@ -600,6 +658,28 @@ fn custom_history_path() {
   assert!(err.is_empty());
 }
 
+#[test]
+fn history_ignores_duplicates_and_space_prefixed_lines() {
+  let temp_dir = TempDir::new();
+  let history_path = temp_dir.path().join("history.txt");
+  let (out, err) = util::run_and_collect_output(
+    true,
+    "repl",
+    Some(vec!["1", "1", " 2", "3"]),
+    Some(vec![
+      ("DENO_REPL_HISTORY".to_owned(), history_path.to_string()),
+      ("DENO_REPL_HISTORY_SIZE".to_owned(), "10".to_owned()),
+      ("NO_COLOR".to_owned(), "1".to_owned()),
+    ]),
+    false,
+  );
+  assert_ends_with!(out, "3\n");
+  assert!(err.is_empty());
+  let history = history_path.read_to_string();
+  let entries = history.lines().filter(|l| !l.starts_with('#'));
+  assert_eq!(entries.collect::<Vec<_>>(), vec!["1", "3"]);
+}
+
 #[test]
 fn disable_history_file() {
   let deno_dir = util::new_deno_dir();
@@ -771,6 +851,30 @@ fn eval_file_flag_multiple_files() {
   assert_contains!(err, "Download");
 }
 
+#[test]
+fn config_repl_init() {
+  let context = TestContextBuilder::default().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "deno.json",
+    r#"{ "repl": { "init": ["./init/utils.ts", "./init/greeting.ts"] } }"#,
+  );
+  temp_dir.create_dir_all("init");
+  temp_dir.write(
+    "init/utils.ts",
+    "function shout(text: string) { return text.toUpperCase(); }",
+  );
+  temp_dir.write("init/greeting.ts", "const greeting = shout('hello');");
+  context
+    .new_command()
+    .args_vec(["repl", "--eval-file=./extra.ts"])
+    .with_pty(|mut console| {
+      console.expect("Error in --eval-file file \"./extra.ts\"");
+      console.write_line("greeting + '!'");
+      console.expect("HELLO!");
+    });
+}
+
 #[flaky_test::flaky_test]
 fn pty_clear_function() {
   util::with_pty(&["repl"], |mut console| {
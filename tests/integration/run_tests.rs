@@ -412,6 +412,128 @@ fn webstorage_main_module() {
     .assert_matches_text("Storage { hello: \"deno\", length: 1 }\n");
 }
 
+#[test]
+fn webstorage_local_storage_quota() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  context.temp_dir().write(
+    "main.ts",
+    r#"try {
+  localStorage.setItem("k", "v".repeat(2 * 1024 * 1024));
+} catch (e) {
+  console.log(e.name);
+}
+console.log(localStorage.length);
+"#,
+  );
+
+  context
+    .new_command()
+    .args("run --local-storage-quota=1 main.ts")
+    .run()
+    .assert_matches_text("QuotaExceededError\n0\n");
+  context
+    .new_command()
+    .args("run --local-storage-quota=4 main.ts")
+    .run()
+    .assert_matches_text("1\n");
+}
+
+#[test]
+fn webstorage_corrupted_database() {
+  let context = TestContext::default();
+
+  context
+    .new_command()
+    .args("run --location https://example.com run/webstorage/fixture.ts")
+    .run()
+    .assert_matches_text("Storage { length: 0 }\n");
+
+  let origin_dir = context
+    .deno_dir()
+    .path()
+    .join("location_data")
+    .read_dir()
+    .next()
+    .unwrap()
+    .unwrap()
+    .path();
+  let local_storage = origin_dir.join("local_storage");
+  std::fs::write(&local_storage, "not a database").unwrap();
+  let _ = std::fs::remove_file(origin_dir.join("local_storage-wal"));
+  let _ = std::fs::remove_file(origin_dir.join("local_storage-shm"));
+
+  context
+    .new_command()
+    .args("run --location https://example.com run/webstorage/fixture.ts")
+    .run()
+    .assert_matches_text(
+      "Warning: the localStorage database at [WILDCARD] is corrupted [WILDCARD]\nStorage { length: 0 }\n",
+    );
+  assert_eq!(
+    std::fs::read_to_string(origin_dir.join("local_storage.corrupted"))
+      .unwrap(),
+    "not a database"
+  );
+
+  // the storage is usable again
+  context
+    .new_command()
+    .args("run --location https://example.com run/webstorage/logger.ts")
+    .run()
+    .assert_matches_text("Storage { hello: \"deno\", length: 1 }\n");
+}
+
+#[test]
+fn storage_ls_and_clear() {
+  let context = TestContext::default();
+
+  context
+    .new_command()
+    .args("storage ls")
+    .run()
+    .assert_matches_text("No localStorage data stored in [WILDCARD]\n");
+
+  context
+    .new_command()
+    .args("run --location https://example.com/a.ts run/webstorage/fixture.ts")
+    .run()
+    .assert_matches_text("Storage { length: 0 }\n");
+  context
+    .new_command()
+    .args("run --location https://deno.land run/webstorage/fixture.ts")
+    .run()
+    .assert_matches_text("Storage { length: 0 }\n");
+
+  let output = context.new_command().args("storage ls").run();
+  output.assert_exit_code(0);
+  let text = output.combined_output();
+  assert!(text.contains("https://example.com\n  Path: "), "{text}");
+  assert!(text.contains("https://deno.land\n  Path: "), "{text}");
+  assert_eq!(text.matches("  Entries: 1\n").count(), 2, "{text}");
+
+  context
+    .new_command()
+    .args("storage clear https://example.com")
+    .run()
+    .assert_matches_text("Cleared localStorage data of https://example.com\n");
+  context
+    .new_command()
+    .args("run --location https://example.com/b.ts run/webstorage/logger.ts")
+    .run()
+    .assert_matches_text("Storage { length: 0 }\n");
+
+  context
+    .new_command()
+    .args("storage clear --all")
+    .run()
+    .assert_matches_text("Cleared localStorage data of 2 origins\n");
+  context
+    .new_command()
+    .args("storage ls")
+    .run()
+    .assert_matches_text("No localStorage data stored in [WILDCARD]\n");
+}
+
 itest!(_075_import_local_query_hash {
   args: "run run/075_import_local_query_hash.ts",
   output: "run/075_import_local_query_hash.ts.out",
@@ -517,7 +639,7 @@ fn _090_run_permissions_request() {
         "┌ ⚠️  Deno requests run access to \"ls\".\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-run to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all run permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all run permissions)",
       ));
       console.human_delay();
       console.write_line_raw("y");
@@ -526,7 +648,7 @@ fn _090_run_permissions_request() {
         "┌ ⚠️  Deno requests run access to \"cat\".\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-run to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all run permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all run permissions)",
       ));
       console.human_delay();
       console.write_line_raw("n");
@@ -546,7 +668,7 @@ fn _090_run_permissions_request_sync() {
         "┌ ⚠️  Deno requests run access to \"ls\".\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-run to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all run permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all run permissions)",
       ));
       console.human_delay();
       console.write_line_raw("y");
@@ -555,7 +677,7 @@ fn _090_run_permissions_request_sync() {
         "┌ ⚠️  Deno requests run access to \"cat\".\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-run to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all run permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all run permissions)",
       ));
       console.human_delay();
       console.write_line_raw("n");
@@ -576,7 +698,7 @@ fn permissions_prompt_allow_all() {
         "┌ ⚠️  Deno requests run access to \"FOO\".\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-run to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all run permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all run permissions)",
       ));
       console.human_delay();
       console.write_line_raw("A");
@@ -586,7 +708,7 @@ fn permissions_prompt_allow_all() {
         "┌ ⚠️  Deno requests read access to \"FOO\".\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-read to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all read permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all read permissions)",
       ));
       console.human_delay();
       console.write_line_raw("A");
@@ -596,7 +718,7 @@ fn permissions_prompt_allow_all() {
         "┌ ⚠️  Deno requests write access to \"FOO\".\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-write to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all write permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all write permissions)",
       ));
       console.human_delay();
       console.write_line_raw("A");
@@ -606,7 +728,7 @@ fn permissions_prompt_allow_all() {
         "┌ ⚠️  Deno requests net access to \"foo\".\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-net to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all net permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all net permissions)",
       ));
       console.human_delay();
       console.write_line_raw("A");
@@ -616,7 +738,7 @@ fn permissions_prompt_allow_all() {
         "┌ ⚠️  Deno requests env access to \"FOO\".\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-env to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all env permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all env permissions)",
       ));
       console.human_delay();
       console.write_line_raw("A");
@@ -626,7 +748,7 @@ fn permissions_prompt_allow_all() {
         "┌ ⚠️  Deno requests sys access to \"loadavg\".\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-sys to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all sys permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all sys permissions)",
       ));
       console.human_delay();
       console.write_line_raw("A");
@@ -636,7 +758,7 @@ fn permissions_prompt_allow_all() {
         "┌ ⚠️  Deno requests ffi access to \"FOO\".\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-ffi to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all ffi permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all ffi permissions)",
       ));
       console.human_delay();
       console.write_line_raw("A");
@@ -655,7 +777,7 @@ fn permissions_prompt_allow_all_2() {
       console.expect(concat!(
         "┌ ⚠️  Deno requests env access to \"FOO\".\r\n",
         "├ Run again with --allow-env to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all env permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all env permissions)",
       ));
       console.human_delay();
       console.write_line_raw("A");
@@ -666,7 +788,7 @@ fn permissions_prompt_allow_all_2() {
         "┌ ⚠️  Deno requests sys access to \"loadavg\".\r\n",
         "├ Requested by `Deno.loadavg()` API.\r\n",
         "├ Run again with --allow-sys to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all sys permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all sys permissions)",
       ));
       console.human_delay();
       console.write_line_raw("A");
@@ -677,7 +799,7 @@ fn permissions_prompt_allow_all_2() {
         "┌ ⚠️  Deno requests read access to <CWD>.\r\n",
         "├ Requested by `Deno.cwd()` API.\r\n",
         "├ Run again with --allow-read to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all read permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all read permissions)",
       ));
       console.human_delay();
       console.write_line_raw("A");
@@ -696,7 +818,7 @@ fn permissions_prompt_allow_all_lowercase_a() {
         "┌ ⚠️  Deno requests run access to \"FOO\".\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-run to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all run permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all run permissions)",
       ));
       console.human_delay();
       console.write_line_raw("a");
@@ -739,7 +861,7 @@ fn permissions_cache() {
         "┌ ⚠️  Deno requests read access to \"foo\".\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-read to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all read permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all read permissions)",
       ));
       console.human_delay();
       console.write_line_raw("y");
@@ -749,6 +871,77 @@ fn permissions_cache() {
     });
 }
 
+#[test]
+fn permissions_prompt_always_allow() {
+  let context = TestContextBuilder::new().use_temp_cwd().build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "main.ts",
+    r#"console.log(Deno.permissions.querySync({ name: "env", variable: "FOO" }).state);
+Deno.env.get("FOO");
+console.log("done");
+"#,
+  );
+  context
+    .new_command()
+    .args_vec(["run", "--quiet", "main.ts"])
+    .with_pty(|mut console| {
+      console.expect(concat!(
+        "prompt\r\n",
+        "┌ ⚠️  Deno requests env access to \"FOO\".\r\n",
+      ));
+      console.human_delay();
+      console.write_line_raw("p");
+      console.expect("✅ Granted env access to \"FOO\" for this project.");
+      console.expect("done");
+    });
+
+  // the grant is used on subsequent runs
+  context
+    .new_command()
+    .args("run --quiet main.ts")
+    .run()
+    .assert_matches_text("granted\ndone\n");
+  // without a config file the grant only applies to the script
+  temp_dir.write("other.ts", "await import('./main.ts');\n");
+  context
+    .new_command()
+    .args("run --quiet --no-prompt other.ts")
+    .run()
+    .assert_matches_text("prompt\n[WILDCARD]PermissionDenied[WILDCARD]")
+    .assert_exit_code(1);
+  context
+    .new_command()
+    .args("permissions list")
+    .run()
+    .assert_matches_text("error: No configuration file found. [WILDCARD]\n")
+    .assert_exit_code(1);
+  context
+    .new_command()
+    .args("permissions --script main.ts list")
+    .run()
+    .assert_matches_text(
+      "Permissions stored for file:///[WILDCARD]/main.ts\n  env FOO\n",
+    );
+
+  context
+    .new_command()
+    .args("permissions --script main.ts revoke env FOO")
+    .run()
+    .assert_matches_text("Revoked env access to FOO for [WILDCARD]\n");
+  context
+    .new_command()
+    .args("run --quiet --no-prompt main.ts")
+    .run()
+    .assert_matches_text("prompt\n[WILDCARD]PermissionDenied[WILDCARD]")
+    .assert_exit_code(1);
+  context
+    .new_command()
+    .args("permissions --script main.ts list")
+    .run()
+    .assert_matches_text("No permissions stored for [WILDCARD]\n");
+}
+
 itest!(env_file {
   args: "run --env=env --allow-env run/env_file.ts",
   output: "run/env_file.out",
@@ -2984,7 +3177,7 @@ mod permissions {
           "┌ ⚠️  Deno requests read access to \"foo\".\r\n",
           "├ Requested by `Deno.permissions.request()` API.\r\n",
           "├ Run again with --allow-read to bypass this prompt.\r\n",
-          "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all read permissions)",
+          "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all read permissions)",
         ));
         console.human_delay();
         console.write_line_raw("y");
@@ -2992,7 +3185,7 @@ mod permissions {
           "┌ ⚠️  Deno requests read access to \"bar\".\r\n",
           "├ Requested by `Deno.permissions.request()` API.\r\n",
           "├ Run again with --allow-read to bypass this prompt.\r\n",
-          "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all read permissions)",
+          "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all read permissions)",
         ));
         console.human_delay();
         console.write_line_raw("n");
@@ -3012,7 +3205,7 @@ mod permissions {
           "┌ ⚠️  Deno requests read access to \"foo\".\r\n",
           "├ Requested by `Deno.permissions.request()` API.\r\n",
           "├ Run again with --allow-read to bypass this prompt.\r\n",
-          "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all read permissions)",
+          "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all read permissions)",
         ));
         console.human_delay();
         console.write_line_raw("y");
@@ -3020,7 +3213,7 @@ mod permissions {
           "┌ ⚠️  Deno requests read access to \"bar\".\r\n",
           "├ Requested by `Deno.permissions.request()` API.\r\n",
           "├ Run again with --allow-read to bypass this prompt.\r\n",
-          "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all read permissions)",
+          "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all read permissions)",
         ));
         console.human_delay();
         console.write_line_raw("n");
@@ -3040,7 +3233,7 @@ mod permissions {
           "┌ ⚠️  Deno requests read access.\r\n",
           "├ Requested by `Deno.permissions.request()` API.\r\n",
           "├ Run again with --allow-read to bypass this prompt.\r\n",
-          "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all read permissions)",
+          "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all read permissions)",
         ));
         console.human_delay();
         console.write_line_raw("y\n");
@@ -3063,7 +3256,7 @@ mod permissions {
           "┌ ⚠️  Deno requests read access.\r\n",
           "├ Requested by `Deno.permissions.request()` API.\r\n",
           "├ Run again with --allow-read to bypass this prompt.\r\n",
-          "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all read permissions)",
+          "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all read permissions)",
         ));
         console.human_delay();
         console.write_line_raw("y");
@@ -3205,7 +3398,7 @@ fn issue9750() {
         "┌ ⚠️  Deno requests env access.\r\n",
         "├ Requested by `Deno.permissions.request()` API.\r\n",
         "├ Run again with --allow-env to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all env permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all env permissions)",
       ));
       console.human_delay();
       console.write_line_raw("n");
@@ -3213,7 +3406,7 @@ fn issue9750() {
       console.expect(concat!(
         "┌ ⚠️  Deno requests env access to \"SECRET\".\r\n",
         "├ Run again with --allow-env to bypass this prompt.\r\n",
-        "└ Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all env permissions)",
+        "└ Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all env permissions)",
       ));
       console.human_delay();
       console.write_line_raw("n");
@@ -3255,6 +3448,43 @@ fn navigator_language_unix() {
   assert_eq!(res, "pl-PL\n")
 }
 
+#[test]
+#[cfg(unix)]
+fn temp_cleanup_at_exit_on_sigint() {
+  use nix::sys::signal;
+  use nix::sys::signal::Signal;
+  use nix::unistd::Pid;
+  use std::io::BufRead;
+  use std::os::unix::process::ExitStatusExt;
+
+  let context = TestContext::default();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "main.ts",
+    r#"const dir = Deno.makeTempDirSync({ dir: ".", cleanupAtExit: true });
+Deno.writeTextFileSync(`${dir}/file.txt`, "hello");
+console.log(dir);
+// the paths are removed while the program is busy
+while (true) {}
+"#,
+  );
+  let mut child = context
+    .new_command()
+    .args("run --allow-read --allow-write main.ts")
+    .current_dir(temp_dir.path())
+    .spawn_with_piped_output();
+  let mut stdout = BufReader::new(child.stdout.take().unwrap());
+  let mut dir = String::new();
+  stdout.read_line(&mut dir).unwrap();
+  let dir = temp_dir.path().join(dir.trim());
+  assert!(dir.exists());
+
+  signal::kill(Pid::from_raw(child.id() as i32), Signal::SIGINT).unwrap();
+  let status = child.wait().unwrap();
+  assert_eq!(status.signal(), Some(Signal::SIGINT as i32));
+  assert!(!dir.exists());
+}
+
 #[test]
 fn navigator_language() {
   let (res, _) = util::run_and_collect_output(
@@ -4023,6 +4253,80 @@ fn basic_auth_tokens() {
   assert_eq!(util::strip_ansi_codes(stdout_str), "Hello World");
 }
 
+#[test]
+fn netrc_credentials_not_sent_over_http() {
+  let context = TestContextBuilder::new()
+    .use_temp_cwd()
+    .use_http_server()
+    .build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "netrc",
+    "machine 127.0.0.1\n  login testuser123\n  password testpassabc\n",
+  );
+  context
+    .new_command()
+    .args("run http://127.0.0.1:4554/run/001_hello.js")
+    .env("DENO_NETRC", temp_dir.path().join("netrc"))
+    .run()
+    .assert_matches_text(
+      "error: Import 'http://127.0.0.1:4554/run/001_hello.js' failed, not found.[WILDCARD]",
+    )
+    .assert_exit_code(1);
+}
+
+#[test]
+fn netrc_credentials_sent_over_https() {
+  let context = TestContextBuilder::new()
+    .use_temp_cwd()
+    .use_http_server()
+    .build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write(
+    "netrc",
+    "machine localhost\n  login testuser123\n  password testpassabc\n",
+  );
+  let root_ca = util::testdata_path().join("tls/RootCA.pem");
+  context
+    .new_command()
+    .args_vec([
+      "run",
+      "--cert",
+      root_ca.to_string_lossy().as_ref(),
+      "https://localhost:5553/run/001_hello.js",
+    ])
+    .env("DENO_NETRC", temp_dir.path().join("netrc"))
+    .run()
+    .assert_matches_text("[WILDCARD]Hello World\n")
+    .assert_exit_code(0);
+}
+
+#[test]
+fn client_certificates_from_env() {
+  let context = TestContextBuilder::new()
+    .use_temp_cwd()
+    .use_http_server()
+    .build();
+  let tls_dir = util::testdata_path().join("tls");
+  let client_certs = format!(
+    "{},{}@localhost:5552",
+    tls_dir.join("localhost.crt"),
+    tls_dir.join("localhost.key"),
+  );
+  context
+    .new_command()
+    .args_vec([
+      "run",
+      "--cert",
+      tls_dir.join("RootCA.pem").to_string_lossy().as_ref(),
+      "https://localhost:5552/run/001_hello.js",
+    ])
+    .env("DENO_CLIENT_CERTS", client_certs)
+    .run()
+    .assert_matches_text("[WILDCARD]Hello World\n")
+    .assert_exit_code(0);
+}
+
 #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
 async fn test_resolve_dns() {
   use std::net::SocketAddr;
@@ -4632,7 +4936,7 @@ fn stdio_streams_are_locked_in_permission_prompt() {
       console.expect(malicious_output);
       console.write_line(r#"Deno.readTextFileSync('../Cargo.toml');"#);
       // We will get a permission prompt
-      console.expect("Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all read permissions) > ");
+      console.expect("Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all read permissions) > ");
       // The worker is blocked, so nothing else should get written here
       console.human_delay();
       console.write_line_raw("i");
@@ -4642,7 +4946,7 @@ fn stdio_streams_are_locked_in_permission_prompt() {
       } else {
         "\r\n"
       };
-      console.expect_raw_next(format!("i{newline}\u{1b}[1A\u{1b}[0J└ Unrecognized option. Allow? [y/n/A] (y = yes, allow; n = no, deny; A = allow all read permissions) > "));
+      console.expect_raw_next(format!("i{newline}\u{1b}[1A\u{1b}[0J└ Unrecognized option. Allow? [y/n/p/A] (y = yes, allow; n = no, deny; p = always allow for this project; A = allow all read permissions) > "));
       console.human_delay();
       console.write_line_raw("y");
       // We ensure that nothing gets written here between the permission prompt and this text, despire the delay
@@ -114,13 +114,13 @@ itest!(parallel_flag_with_env_variable {
 itest!(jobs_flag {
   args: "test test/short-pass.ts --jobs",
   exit_code: 0,
-  output: "test/short-pass-jobs-flag-warning.out",
+  output: "test/short-pass.out",
 });
 
 itest!(jobs_flag_with_numeric_value {
   args: "test test/short-pass.ts --jobs=2",
   exit_code: 0,
-  output: "test/short-pass-jobs-flag-warning.out",
+  output: "test/short-pass.out",
 });
 
 itest!(load_unload {
@@ -189,6 +189,12 @@ itest!(fail_fast {
   output: "test/fail_fast.out",
 });
 
+itest!(fail_fast_in_flight {
+  args: "test --fail-fast --jobs=2 test/fail_fast_in_flight.ts test/fail_fast_in_flight_stuck.ts",
+  exit_code: 1,
+  output: "test/fail_fast_in_flight.out",
+});
+
 itest!(only {
   args: "test test/only.ts",
   exit_code: 1,
@@ -266,6 +272,18 @@ itest!(resource_sanitizer {
   output: "test/sanitizer/resource_sanitizer.out",
 });
 
+itest!(resource_sanitizer_trace {
+  args: "test --allow-read --trace-leaks test/sanitizer/resource_sanitizer.ts",
+  exit_code: 1,
+  output: "test/sanitizer/resource_sanitizer_trace.out",
+});
+
+itest!(resource_sanitizer_trace_more {
+  args: "test --allow-read --allow-run --trace-leaks test/sanitizer/resource_sanitizer_trace_more.ts",
+  exit_code: 1,
+  output: "test/sanitizer/resource_sanitizer_trace_more.out",
+});
+
 itest!(ops_sanitizer_tcp {
   args: "test --allow-net --trace-leaks test/sanitizer/ops_sanitizer_tcp.ts",
   exit_code: 1,
@@ -421,6 +439,48 @@ itest!(steps_tap_ignored_steps {
   output: "test/steps/ignored_steps.tap.out",
 });
 
+itest!(steps_json_ignored_steps {
+  args: "test --reporter=json test/steps/ignored_steps.ts",
+  exit_code: 0,
+  output: "test/steps/ignored_steps.json.out",
+});
+
+itest!(timeout {
+  args: "test test/timeout/timeout.ts",
+  exit_code: 1,
+  output: "test/timeout/timeout.out",
+});
+
+itest!(retries {
+  args: "test --retries=1 test/retries/flaky.ts",
+  exit_code: 1,
+  output: "test/retries/flaky.out",
+});
+
+itest!(retries_steps {
+  args: "test --retries=1 test/retries/flaky_steps.ts",
+  exit_code: 0,
+  output: "test/retries/flaky_steps.out",
+});
+
+itest!(retries_steps_json {
+  args: "test --retries=1 --reporter=json test/retries/flaky_steps.ts",
+  exit_code: 0,
+  output: "test/retries/flaky_steps.json.out",
+});
+
+itest!(retries_steps_junit {
+  args: "test --retries=1 --reporter=junit test/retries/flaky_steps.ts",
+  exit_code: 0,
+  output: "test/retries/flaky_steps.junit.out",
+});
+
+itest!(snapshot {
+  args: "test test/snapshot/snapshot.ts",
+  exit_code: 1,
+  output: "test/snapshot/snapshot.out",
+});
+
 itest!(steps_invalid_usage {
   args: "test test/steps/invalid_usage.ts",
   exit_code: 1,
@@ -663,6 +663,52 @@ fn vendor_only_npm_specifiers() {
   ));
 }
 
+#[test]
+fn update_existing_vendor_dir() {
+  let context = TestContextBuilder::new()
+    .use_http_server()
+    .use_temp_cwd()
+    .build();
+  let temp_dir = context.temp_dir();
+  temp_dir.write("deno.json", "{}");
+  temp_dir.write(
+    "my_app.ts",
+    "import { Logger } from 'http://localhost:4545/vendor/mod.ts'; new Logger().log('outputted');",
+  );
+
+  let output = context.new_command().args("vendor my_app.ts").run();
+  output.assert_exit_code(0);
+  output.skip_output_check();
+  let vendor_dir = temp_dir.path().join("vendor/localhost_4545/vendor");
+  assert!(vendor_dir.join("mod.ts").exists());
+  assert!(vendor_dir.join("logger.ts").exists());
+  // the integrity of the vendored modules is recorded in the lockfile
+  let lockfile = temp_dir.path().join("deno.lock").read_json_value();
+  assert!(lockfile["remote"]["http://localhost:4545/vendor/mod.ts"].is_string());
+  assert!(
+    lockfile["remote"]["http://localhost:4545/vendor/logger.ts"].is_string()
+  );
+
+  // local changes of the vendored modules are kept when updating
+  temp_dir.write(
+    "vendor/localhost_4545/vendor/logger.ts",
+    "export class Logger { log(text: string) { console.log('local', text); } }",
+  );
+  temp_dir.write(
+    "my_app.ts",
+    "import { Logger } from 'http://localhost:4545/vendor/logger.ts'; new Logger().log('outputted');",
+  );
+  let output = context.new_command().args("vendor my_app.ts").run();
+  output.assert_matches_text(format!(
+    "{}\nUpdated vendor/ directory with 1 vendored module (0 added, 1 removed).\n[WILDCARD]",
+    ignoring_import_map_text(),
+  ));
+  assert!(!vendor_dir.join("mod.ts").exists());
+
+  let output = context.new_command().args("run my_app.ts").run();
+  output.assert_matches_text("local outputted\n");
+}
+
 fn success_text(module_count: &str, dir: &str, has_import_map: bool) -> String {
   let mut text = format!("Vendored {module_count} into {dir} directory.");
   if has_import_map {
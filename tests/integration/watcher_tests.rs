@@ -764,6 +764,238 @@ async fn run_watch_external_watch_files() {
   check_alive_then_kill(child);
 }
 
+#[flaky_test]
+#[tokio::main]
+async fn run_watch_files_read_at_runtime() {
+  let t = TempDir::new();
+  let file_to_watch = t.path().join("file_to_watch.js");
+  file_to_watch.write(
+    "console.log(Deno.readTextFileSync('data.txt'));
+const { default: value } = await import('./dynamic.js');
+console.log(value);",
+  );
+  let data_file = t.path().join("data.txt");
+  data_file.write("Hello data");
+  let dynamic_file = t.path().join("dynamic.js");
+  dynamic_file.write("export default 'Hello dynamic';");
+
+  let mut child = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("run")
+    .arg("--watch")
+    .arg("--allow-read")
+    .arg("-L")
+    .arg("debug")
+    .arg(&file_to_watch)
+    .env("NO_COLOR", "1")
+    .piped_output()
+    .spawn()
+    .unwrap();
+  let (mut stdout_lines, mut stderr_lines) = child_lines(&mut child);
+  wait_contains("Hello data", &mut stdout_lines).await;
+  wait_contains("Hello dynamic", &mut stdout_lines).await;
+  wait_for_watcher("data.txt", &mut stderr_lines).await;
+
+  data_file.write("Hello data2");
+  wait_contains("Restarting", &mut stderr_lines).await;
+  wait_contains("Hello data2", &mut stdout_lines).await;
+  wait_for_watcher("dynamic.js", &mut stderr_lines).await;
+
+  dynamic_file.write("export default 'Hello dynamic2';");
+  wait_contains("Restarting", &mut stderr_lines).await;
+  wait_contains("Hello dynamic2", &mut stdout_lines).await;
+
+  check_alive_then_kill(child);
+}
+
+#[flaky_test]
+#[tokio::main]
+async fn run_watch_ignores_files_written_at_runtime() {
+  let t = TempDir::new();
+  let file_to_watch = t.path().join("file_to_watch.js");
+  file_to_watch.write(
+    "const count = Number(Deno.readTextFileSync('count.txt')) + 1;
+Deno.writeTextFileSync('count.txt', String(count));
+console.log(`count ${count}`);",
+  );
+  let count_file = t.path().join("count.txt");
+  count_file.write("0");
+
+  let mut child = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("run")
+    .arg("--watch")
+    .arg("--allow-read")
+    .arg("--allow-write")
+    .arg("-L")
+    .arg("debug")
+    .arg(&file_to_watch)
+    .env("NO_COLOR", "1")
+    .piped_output()
+    .spawn()
+    .unwrap();
+  let (mut stdout_lines, mut stderr_lines) = child_lines(&mut child);
+  wait_contains("count 1", &mut stdout_lines).await;
+  wait_contains("Process finished", &mut stderr_lines).await;
+  // writing count.txt must not restart the process
+  tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+  file_to_watch.write(
+    "const count = Number(Deno.readTextFileSync('count.txt')) + 1;
+Deno.writeTextFileSync('count.txt', String(count));
+console.log(`count ${count} restarted`);",
+  );
+  wait_contains("Restarting", &mut stderr_lines).await;
+  assert_eq!(
+    next_line(&mut stdout_lines).await.unwrap(),
+    "count 2 restarted"
+  );
+
+  check_alive_then_kill(child);
+}
+
+#[flaky_test]
+#[tokio::main]
+async fn run_watch_external_watch_globs() {
+  let t = TempDir::new();
+  let file_to_watch = t.path().join("file_to_watch.js");
+  file_to_watch.write("console.log('Hello world');");
+  t.create_dir_all("assets/nested");
+  let asset = t.path().join("assets/nested/config.json");
+  asset.write("{}");
+  let excluded = t.path().join("assets/nested/ignored.json");
+  excluded.write("{}");
+
+  let mut child = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("run")
+    .arg("--watch=assets/**/*.json")
+    .arg("--watch-exclude=assets/**/ignored.json")
+    .arg("-L")
+    .arg("debug")
+    .arg(&file_to_watch)
+    .env("NO_COLOR", "1")
+    .piped_output()
+    .spawn()
+    .unwrap();
+  let (mut stdout_lines, mut stderr_lines) = child_lines(&mut child);
+  wait_contains("Hello world", &mut stdout_lines).await;
+  wait_for_watcher("config.json", &mut stderr_lines).await;
+
+  asset.write("{ \"a\": 1 }");
+  wait_contains("Restarting", &mut stderr_lines).await;
+  wait_contains("Process finished", &mut stderr_lines).await;
+
+  check_alive_then_kill(child);
+}
+
+#[flaky_test]
+#[tokio::main]
+async fn task_watch_reruns_on_matching_files() {
+  let t = TempDir::new();
+  t.write(
+    "deno.json",
+    r#"{
+  "tasks": {
+    "build": {
+      "cmd": "echo built && echo output > src/output.txt",
+      "watch": ["src/*.ts"]
+    }
+  }
+}"#,
+  );
+  t.create_dir_all("src");
+  t.write("src/mod.ts", "export {};");
+
+  let mut child = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("task")
+    .arg("--watch")
+    .arg("build")
+    .env("NO_COLOR", "1")
+    .piped_output()
+    .spawn()
+    .unwrap();
+  let (mut stdout_lines, mut stderr_lines) = child_lines(&mut child);
+  wait_contains("built", &mut stdout_lines).await;
+  wait_contains("Task finished", &mut stderr_lines).await;
+  // writing a file that doesn't match the globs must not re-run the task
+  let timeout = std::time::Duration::from_secs(1);
+  assert!(tokio::time::timeout(timeout, stderr_lines.next_line())
+    .await
+    .is_err());
+
+  // a file created after starting that matches the globs
+  t.write("src/new.ts", "export {};");
+  wait_contains("Restarting", &mut stderr_lines).await;
+  assert_eq!(next_line(&mut stdout_lines).await.unwrap(), "built");
+  wait_contains("Task finished", &mut stderr_lines).await;
+
+  check_alive_then_kill(child);
+}
+
+#[flaky_test]
+#[tokio::main]
+async fn run_watch_changed_paths_env_var() {
+  let t = TempDir::new();
+  let file_to_watch = t.path().join("file_to_watch.js");
+  let data_file = t.path().join("data.txt");
+  data_file.write("Hello world");
+  file_to_watch.write(
+    r#"
+      console.log("started", Deno.env.get("DENO_WATCH_CHANGED_PATHS"));
+      const { stdout } = new Deno.Command(Deno.execPath(), {
+        args: ["eval", "console.log(Deno.env.get('DENO_WATCH_CHANGED_PATHS'))"],
+      }).outputSync();
+      console.log("subprocess", new TextDecoder().decode(stdout).trim());
+      setInterval(() => {}, 0);
+      globalThis.addEventListener("unload", () => {
+        console.log("unload", Deno.env.get("DENO_WATCH_CHANGED_PATHS"));
+      });
+    "#,
+  );
+
+  let mut child = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("run")
+    .arg("--watch=data.txt")
+    .arg("--allow-env")
+    .arg("--allow-read")
+    .arg("--allow-run")
+    .arg("-L")
+    .arg("debug")
+    .arg(&file_to_watch)
+    .env("NO_COLOR", "1")
+    .piped_output()
+    .spawn()
+    .unwrap();
+  let (mut stdout_lines, mut stderr_lines) = child_lines(&mut child);
+  assert_eq!(
+    next_line(&mut stdout_lines).await.unwrap(),
+    "started undefined"
+  );
+  assert_eq!(
+    next_line(&mut stdout_lines).await.unwrap(),
+    "subprocess undefined"
+  );
+  wait_for_watcher("data.txt", &mut stderr_lines).await;
+
+  data_file.write("Hello world2");
+  wait_contains("Restarting", &mut stderr_lines).await;
+  let line = next_line(&mut stdout_lines).await.unwrap();
+  assert!(line.starts_with("unload "), "{line}");
+  assert!(line.ends_with("data.txt"), "{line}");
+  let line = next_line(&mut stdout_lines).await.unwrap();
+  assert!(line.starts_with("started "), "{line}");
+  assert!(line.ends_with("data.txt"), "{line}");
+  // subprocesses inherit the variable
+  let line = next_line(&mut stdout_lines).await.unwrap();
+  assert!(line.starts_with("subprocess "), "{line}");
+  assert!(line.ends_with("data.txt"), "{line}");
+
+  check_alive_then_kill(child);
+}
+
 #[flaky_test]
 #[tokio::main]
 async fn run_watch_load_unload_events() {
@@ -1800,6 +2032,63 @@ export function foo() {
   check_alive_then_kill(child);
 }
 
+#[flaky_test]
+#[tokio::main]
+async fn run_hmr_event() {
+  let t = TempDir::new();
+  let file_to_watch = t.path().join("file_to_watch.js");
+  file_to_watch.write(
+    r#"
+import { foo } from "./foo.js";
+
+addEventListener("deno:hmr", (e) => {
+  console.log("replaced", e.detail.path);
+});
+setInterval(() => {
+  console.log(foo());
+}, 100);
+"#,
+  );
+  let file_to_watch2 = t.path().join("foo.js");
+  file_to_watch2.write(
+    r#"
+export function foo() {
+  return "Hello";
+}
+"#,
+  );
+
+  let mut child = util::deno_cmd()
+    .current_dir(t.path())
+    .arg("run")
+    .arg("--watch-hmr")
+    .arg("-L")
+    .arg("debug")
+    .arg(&file_to_watch)
+    .env("NO_COLOR", "1")
+    .piped_output()
+    .spawn()
+    .unwrap();
+  let (mut stdout_lines, mut stderr_lines) = child_lines(&mut child);
+  wait_for_watcher("file_to_watch.js", &mut stderr_lines).await;
+  wait_contains("Hello", &mut stdout_lines).await;
+
+  file_to_watch2.write(
+    r#"
+export function foo() {
+  return "Hello world";
+}
+"#,
+  );
+
+  wait_contains("Replaced changed module", &mut stderr_lines).await;
+  let line = wait_contains("replaced", &mut stdout_lines).await;
+  assert!(line.ends_with("foo.js"), "{line}");
+  wait_contains("Hello world", &mut stdout_lines).await;
+
+  check_alive_then_kill(child);
+}
+
 #[flaky_test]
 #[tokio::main]
 async fn run_hmr_uncaught_error() {
@@ -0,0 +1,28 @@
+// A baseline that every measurement regresses against.
+const stats = {
+  n: 10,
+  min: 0.001,
+  max: 0.001,
+  avg: 0.001,
+  p75: 0.001,
+  p99: 0.001,
+  p995: 0.001,
+  p999: 0.001,
+  rme: 0,
+  highPrecision: true,
+  usedExplicitTimers: false,
+};
+Deno.writeTextFileSync(
+  "baseline.json",
+  JSON.stringify({
+    runtime: "Deno",
+    cpu: "test",
+    benches: [{
+      origin: new URL("./bench.ts", import.meta.url).href,
+      group: null,
+      name: "noop",
+      baseline: false,
+      results: [{ ok: stats }],
+    }],
+  }),
+);
@@ -0,0 +1,7 @@
+import { value } from "./other.ts";
+
+const a: number = "a";
+const b: string = 1;
+const c: boolean = value;
+
+console.log(a, b, c);
@@ -0,0 +1,3 @@
+export const value: string = "value";
+const d: number = "d";
+console.log(d);
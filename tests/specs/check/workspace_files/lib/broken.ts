@@ -0,0 +1,2 @@
+const value: number = "not a number";
+console.log(value);
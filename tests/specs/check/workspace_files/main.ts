@@ -0,0 +1,3 @@
+import { add } from "./lib/add.ts";
+
+console.log(add(1, 2));
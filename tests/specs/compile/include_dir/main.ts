@@ -0,0 +1,7 @@
+const staticDir = new URL("./static/", import.meta.url);
+
+console.log(await Deno.readTextFile(new URL("hello.txt", staticDir)));
+const response = await fetch(new URL("nested/data.json", staticDir));
+console.log(await response.json());
+const names = [...Deno.readDirSync(staticDir)].map((entry) => entry.name);
+console.log(names.sort());
@@ -0,0 +1,4 @@
+import greeting from "greeting";
+import name from "name";
+
+console.log(`${greeting}, ${name}!`);
@@ -0,0 +1,4 @@
+import { value } from "./dep.ts";
+import "data:application/javascript,console.log(1)";
+
+console.log(value, await import("./lazy.ts"));
@@ -0,0 +1,4 @@
+export const foo = 1;
+
+// deno-lint-ignore my-plugin/no-foo -- testing suppressions
+export const baz = foo;
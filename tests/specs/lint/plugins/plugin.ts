@@ -0,0 +1,48 @@
+interface Node {
+  type: string;
+  range: [number, number];
+  text?: string;
+}
+
+interface Context {
+  report(diagnostic: {
+    node: Node;
+    message: string;
+    hint?: string;
+    fix?: { range: [number, number]; text: string };
+  }): void;
+}
+
+export default {
+  name: "my-plugin",
+  rules: {
+    "no-foo": {
+      create(context: Context) {
+        return {
+          Ident(node: Node) {
+            if (node.text === "foo") {
+              context.report({
+                node,
+                message: "Don't use foo.",
+                hint: "Use bar instead.",
+                fix: { range: node.range, text: "bar" },
+              });
+            }
+          },
+        };
+      },
+    },
+    "no-baz": {
+      tags: ["strict"],
+      create(context: Context) {
+        return {
+          Ident(node: Node) {
+            if (node.text === "baz") {
+              context.report({ node, message: "Don't use baz." });
+            }
+          },
+        };
+      },
+    },
+  },
+};
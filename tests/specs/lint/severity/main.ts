@@ -0,0 +1,3 @@
+var a = 1;
+debugger;
+console.log(a);
@@ -0,0 +1,9 @@
+// deno-lint-ignore-next-line no-var -- kept for the legacy loader
+var a = 1;
+// deno-lint-ignore-next-line no-var no-debugger -- generated
+var b = 2;
+// deno-lint-ignore no-var
+var c = 3;
+// deno-lint-ignore no-var -- the "var" keyword is required by the loader
+var d = 4;
+console.log(a, b, c, d);
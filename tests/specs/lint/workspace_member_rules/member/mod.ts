@@ -0,0 +1,2 @@
+const unused = 1;
+export const isOne = (value: number): boolean => value == 1;
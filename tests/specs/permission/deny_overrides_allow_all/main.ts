@@ -0,0 +1,15 @@
+console.log("public.txt:", Deno.readTextFileSync("public.txt").trim());
+try {
+  Deno.readTextFileSync("secret.txt");
+} catch (err) {
+  console.log("secret.txt:", err.name);
+}
+try {
+  Deno.env.get("SECRET");
+} catch (err) {
+  console.log("SECRET:", err.name);
+}
+// denied permissions can't be granted by a prompt either
+console.log(
+  Deno.permissions.requestSync({ name: "read", path: "secret.txt" }).state,
+);
@@ -0,0 +1,14 @@
+// host names are not resolved to check them against CIDR blocks
+const urls = [
+  "http://127.0.0.1:4545/run/001_hello.js",
+  "http://localhost:4545/run/001_hello.js",
+];
+for (const url of urls) {
+  try {
+    const res = await fetch(url);
+    await res.body?.cancel();
+    console.log(url, res.status);
+  } catch (err) {
+    console.log(url, err.name);
+  }
+}
@@ -0,0 +1,18 @@
+const urls = [
+  "http://127.0.0.1:4545/run/001_hello.js",
+  "http://127.0.0.1:4551/",
+  "http://localhost:4545/run/001_hello.js",
+];
+for (const url of urls) {
+  try {
+    const res = await fetch(url);
+    await res.body?.cancel();
+    console.log(url, res.status);
+  } catch (err) {
+    console.log(url, err.name);
+  }
+}
+
+const conn = await Deno.connect({ hostname: "127.0.0.1", port: 4545 });
+console.log("connected");
+conn.close();
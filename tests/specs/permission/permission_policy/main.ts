@@ -0,0 +1,23 @@
+console.log("public:", Deno.readTextFileSync("data/public/a.txt").trim());
+try {
+  Deno.readTextFileSync("data/private.txt");
+} catch (err) {
+  console.log("private:", err.name);
+}
+console.log("SPEC_USER:", Deno.env.get("SPEC_USER"));
+try {
+  Deno.env.get("PATH");
+} catch (err) {
+  console.log("PATH:", err.name);
+}
+
+// the worker is loaded from an origin without any permissions
+const worker = new Worker(
+  "http://localhost:4545/workers/env_read_check_worker.js",
+  { type: "module" },
+);
+worker.onmessage = ({ data }) => {
+  console.log("remote SPEC_USER:", data.permissions[0]);
+  worker.terminate();
+};
+worker.postMessage({ names: ["SPEC_USER"] });
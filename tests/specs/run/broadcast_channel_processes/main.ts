@@ -0,0 +1,29 @@
+const channel = new BroadcastChannel("processes");
+
+if (Deno.args[0] === "child") {
+  // the parent might not be connected yet, so announce until it answers
+  const interval = setInterval(() => channel.postMessage("ready"), 100);
+  channel.onmessage = (e) => {
+    clearInterval(interval);
+    channel.postMessage(`pong: ${e.data}`);
+    channel.close();
+  };
+} else {
+  const child = new Deno.Command(Deno.execPath(), {
+    args: [
+      "run",
+      "--unstable-broadcast-channel",
+      import.meta.filename!,
+      "child",
+    ],
+  }).spawn();
+  channel.onmessage = (e) => {
+    if (e.data === "ready") {
+      channel.postMessage("ping");
+    } else {
+      console.log(e.data);
+      channel.close();
+    }
+  };
+  await child.status;
+}
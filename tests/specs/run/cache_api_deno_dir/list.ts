@@ -0,0 +1,8 @@
+// the caches are stored in the location data of the DENO_DIR
+for (const entry of Deno.readDirSync("deno_dir/location_data")) {
+  const dir = `deno_dir/location_data/${entry.name}/cache_storage`;
+  if (Deno.statSync(dir).isDirectory) {
+    console.log(dir);
+    break;
+  }
+}
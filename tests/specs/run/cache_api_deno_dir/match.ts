@@ -0,0 +1,3 @@
+const cache = await caches.open("v1");
+const response = await cache.match("https://example.com/a");
+console.log(await response?.text());
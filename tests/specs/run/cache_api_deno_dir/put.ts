@@ -0,0 +1,2 @@
+const cache = await caches.open("v1");
+await cache.put("https://example.com/a", new Response("cached response"));
@@ -0,0 +1,8 @@
+// only type checks with the "noImplicitAny" override
+function state(descriptor) {
+  return Deno.permissions.querySync(descriptor).state;
+}
+
+console.log("env", state({ name: "env", variable: "HOME" }));
+console.log("read", state({ name: "read", path: import.meta.dirname }));
+console.log("net", state({ name: "net" }));
@@ -0,0 +1,3 @@
+// not in the directory of other/deno.json, so its permissions don't apply
+const { state } = Deno.permissions.querySync({ name: "env", variable: "HOME" });
+console.log("env", state);
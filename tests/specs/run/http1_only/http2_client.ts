@@ -0,0 +1 @@
+Deno.createHttpClient({ http1: false, http2: true });
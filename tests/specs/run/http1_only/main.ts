@@ -0,0 +1,8 @@
+const res = await fetch("https://localhost:5545/http_version");
+console.log("fetch:", await res.text());
+
+using client = Deno.createHttpClient({});
+const clientRes = await fetch("https://localhost:5545/http_version", {
+  client,
+});
+console.log("createHttpClient:", await clientRes.text());
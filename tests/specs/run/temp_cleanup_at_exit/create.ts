@@ -0,0 +1,14 @@
+const dir = Deno.makeTempDirSync({
+  dir: ".",
+  prefix: "cleanup_",
+  cleanupAtExit: true,
+});
+Deno.writeTextFileSync(`${dir}/file.txt`, "hello");
+await Deno.makeTempFile({ dir: ".", prefix: "cleanup_", cleanupAtExit: true });
+Deno.makeTempFileSync({ dir: ".", prefix: "kept_" });
+
+if (Deno.args[0] === "exit") {
+  Deno.exit(3);
+} else if (Deno.args[0] === "throw") {
+  throw new Error("boom");
+}
@@ -0,0 +1,6 @@
+const names = [];
+for (const entry of Deno.readDirSync(".")) {
+  const prefix = entry.name.match(/^(cleanup_|kept_)/)?.[0];
+  if (prefix) names.push(prefix);
+}
+console.log(names.sort().join("\n"));
@@ -0,0 +1,5 @@
+import { foo } from "./foo.ts";
+
+Deno.test("foo", () => {
+  if (foo < 1) throw new Error("bad foo");
+});
@@ -1,5 +1,5 @@
-// This time ./worker.ts is not in the module map, so the worker
-// initialization will fail unless worker.js is passed as a side module.
+// This time ./worker.ts is not in the module map, so it's only included
+// because `deno compile` finds the worker entrypoint in the `new Worker()` call.
 
 const worker = new Worker(
   new URL("./worker.ts", import.meta.url),
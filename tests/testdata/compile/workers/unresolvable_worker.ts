@@ -0,0 +1,11 @@
+// The worker in the unused branch doesn't exist, so `deno compile` leaves it
+// out with a warning instead of failing.
+
+if (Deno.args.includes("--missing")) {
+  new Worker(
+    new URL("./missing_worker.ts", import.meta.url),
+    { type: "module" },
+  );
+}
+
+console.log("Hello from main!");
@@ -0,0 +1,7 @@
+/** Creates a {@link Widget}, see {@link Gadget} and {@linkcode Promise}. */
+export function createWidget(): Widget {
+  return new Widget();
+}
+
+/** A widget. */
+export class Widget {}
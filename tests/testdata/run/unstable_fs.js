@@ -4,7 +4,20 @@ console.log(scope, Deno.flock);
 console.log(scope, Deno.flockSync);
 console.log(scope, Deno.funlock);
 console.log(scope, Deno.funlockSync);
+console.log(scope, Deno.getXattr);
+console.log(scope, Deno.getXattrSync);
+console.log(scope, Deno.listXattrs);
+console.log(scope, Deno.listXattrsSync);
+console.log(scope, Deno.openReadableStream);
+console.log(scope, Deno.openWritableStream);
+console.log(scope, Deno.removeXattr);
+console.log(scope, Deno.removeXattrSync);
+console.log(scope, Deno.setImmutable);
+console.log(scope, Deno.setImmutableSync);
+console.log(scope, Deno.setXattr);
+console.log(scope, Deno.setXattrSync);
 console.log(scope, Deno.umask);
+console.log(scope, Deno.withTempDir);
 
 if (scope === "worker") {
   postMessage("done");
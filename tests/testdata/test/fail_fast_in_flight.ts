@@ -0,0 +1,4 @@
+Deno.test("fails after a while", async () => {
+  await new Promise((resolve) => setTimeout(resolve, 1000));
+  throw new Error();
+});
@@ -0,0 +1,5 @@
+Deno.test("stuck", () => {
+  while (true) {
+    // Never yields, so only terminating the isolate stops this test.
+  }
+});
@@ -0,0 +1,16 @@
+let attempts = 0;
+
+Deno.test({
+  name: "flaky",
+  retries: 2,
+  fn() {
+    attempts++;
+    if (attempts < 2) {
+      throw new Error("Fail.");
+    }
+  },
+});
+
+Deno.test("fails", () => {
+  throw new Error("Fail.");
+});
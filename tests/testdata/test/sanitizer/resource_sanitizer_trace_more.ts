@@ -0,0 +1,9 @@
+Deno.test("leak", function () {
+  new Deno.Command(Deno.execPath(), {
+    args: ["eval", ""],
+    stdin: "null",
+    stdout: "null",
+    stderr: "null",
+  }).spawn();
+  Deno.watchFs(".");
+});
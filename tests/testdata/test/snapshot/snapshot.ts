@@ -0,0 +1,8 @@
+Deno.test("matches", (t) => {
+  t.assertSnapshot({ b: [1, 2], a: 1 });
+  t.assertSnapshot("hello");
+});
+
+Deno.test("mismatch", (t) => {
+  t.assertSnapshot("world");
+});
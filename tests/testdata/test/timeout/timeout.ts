@@ -0,0 +1,11 @@
+Deno.test("passes", { timeout: 1000 }, () => {});
+
+Deno.test("busy loop", { timeout: 100 }, () => {
+  while (true) {
+    // Never yields to the event loop.
+  }
+});
+
+Deno.test("never resolves", { timeout: 100 }, async () => {
+  await new Promise((resolve) => setTimeout(resolve, 100_000));
+});
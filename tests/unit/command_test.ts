@@ -267,6 +267,45 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { run: true, read: true } },
+  async function commandKillProcessGroup() {
+    const child = new Deno.Command(Deno.execPath(), {
+      args: ["eval", "setTimeout(() => {}, 10000)"],
+      stdout: "null",
+      stderr: "null",
+    }).spawn();
+    // the child shares the process group of this process
+    assertThrows(
+      () => child.kill("SIGKILL", { processGroup: true }),
+      TypeError,
+      "Only detached child processes have a process group of their own",
+    );
+    child.kill("SIGKILL");
+    await child.status;
+
+    const detached = new Deno.Command(Deno.execPath(), {
+      args: ["eval", "setTimeout(() => {}, 10000)"],
+      stdout: "null",
+      stderr: "null",
+      detached: true,
+    }).spawn();
+    if (Deno.build.os === "windows") {
+      assertThrows(
+        () => detached.kill("SIGKILL", { processGroup: true }),
+        TypeError,
+      );
+      detached.kill("SIGKILL");
+      await detached.status;
+    } else {
+      detached.kill("SIGKILL", { processGroup: true });
+      const status = await detached.status;
+      assertEquals(status.code, 137);
+      assertEquals(status.signal, "SIGKILL");
+    }
+  },
+);
+
 Deno.test(
   { permissions: { run: true, read: true } },
   // deno lint bug, see https://github.com/denoland/deno_lint/issues/1206
@@ -6,6 +6,7 @@ import {
   assertThrows,
   delay,
   fail,
+  tmpUnixSocketPath,
   unimplemented,
 } from "./test_util.ts";
 import { Buffer } from "@std/io/buffer.ts";
@@ -1262,6 +1263,41 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { net: true } },
+  async function fetchCustomClientCookieStore() {
+    const addr = `127.0.0.1:${listenPort}`;
+    const server = Deno.serve({ port: listenPort }, (req) => {
+      const url = new URL(req.url);
+      const cookie = req.headers.get("cookie") ?? "";
+      if (url.pathname === "/login") {
+        const headers = new Headers();
+        headers.append("set-cookie", "session=abc; Path=/");
+        headers.append("set-cookie", "admin=1; Path=/admin");
+        headers.append("set-cookie", "token=xyz; Path=/; Secure");
+        return new Response(null, { headers });
+      }
+      return new Response(cookie);
+    });
+
+    using client = Deno.createHttpClient({ cookieStore: true });
+    await (await fetch(`http://${addr}/login`, { client })).body?.cancel();
+    let response = await fetch(`http://${addr}/`, { client });
+    assertEquals(await response.text(), "session=abc");
+    response = await fetch(`http://${addr}/admin/users`, { client });
+    assertEquals((await response.text()).split("; ").sort(), [
+      "admin=1",
+      "session=abc",
+    ]);
+
+    // Cookies are not shared with other clients.
+    response = await fetch(`http://${addr}/`);
+    assertEquals(await response.text(), "");
+
+    await server.shutdown();
+  },
+);
+
 Deno.test(
   { permissions: { net: true } },
   async function fetchNoServerReadableStreamBody() {
@@ -2041,3 +2077,53 @@ Deno.test("Response with subarray TypedArray body", async () => {
   const expected = new Uint8Array([2, 3, 4, 5]);
   assertEquals(actual, expected);
 });
+
+Deno.test(
+  {
+    ignore: Deno.build.os === "windows",
+    permissions: { read: true, write: true },
+  },
+  async function fetchCustomClientUnixSocket() {
+    const ac = new AbortController();
+    const path = tmpUnixSocketPath();
+    const { promise, resolve } = Promise.withResolvers<void>();
+    const server = Deno.serve(
+      { signal: ac.signal, path, onListen: () => resolve() },
+      async (req) => {
+        const url = new URL(req.url);
+        return new Response(
+          `${req.method} ${url.pathname}${url.search} ${await req.text()}`,
+          { headers: { "x-host": req.headers.get("host")! } },
+        );
+      },
+    );
+    await promise;
+
+    using client = Deno.createHttpClient({ unixSocketPath: path });
+    const response = await fetch("http://localhost/containers/json?all=1", {
+      client,
+      method: "POST",
+      body: "hello",
+    });
+    assertEquals(response.status, 200);
+    assertEquals(response.url, "http://localhost/containers/json?all=1");
+    assertEquals(response.headers.get("x-host"), "localhost");
+    assertEquals(await response.text(), "POST /containers/json?all=1 hello");
+
+    ac.abort();
+    await server.finished;
+  },
+);
+
+Deno.test(
+  {
+    ignore: Deno.build.os === "windows",
+    permissions: { read: true, write: false },
+  },
+  function createHttpClientUnixSocketRequiresWritePermission() {
+    assertThrows(
+      () => Deno.createHttpClient({ unixSocketPath: "/tmp/app.sock" }),
+      Deno.errors.PermissionDenied,
+    );
+  },
+);
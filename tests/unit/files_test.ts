@@ -901,6 +901,164 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { read: true, write: true } },
+  function fsFileAllocateSyncSuccess() {
+    const filename = Deno.makeTempDirSync() + "/test_fallocateSync.txt";
+    using file = Deno.openSync(filename, { write: true, create: true });
+    file.allocateSync(4096);
+    assertEquals(file.statSync().size, 4096);
+    // allocating less than the size doesn't truncate the file
+    file.allocateSync(1024);
+    assertEquals(file.statSync().size, 4096);
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function fsFileAllocateSuccess() {
+    const filename = (await Deno.makeTempDir()) + "/test_fallocate.txt";
+    using file = await Deno.open(filename, { write: true, create: true });
+    await file.allocate(4096);
+    assertEquals((await file.stat()).size, 4096);
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  function fsFileCopyToSyncSuccess() {
+    const dir = Deno.makeTempDirSync();
+    const data = new TextEncoder().encode("hello world");
+    Deno.writeFileSync(dir + "/src.txt", data);
+    using src = Deno.openSync(dir + "/src.txt");
+    using dest = Deno.openSync(dir + "/dest.txt", {
+      write: true,
+      create: true,
+    });
+    assertEquals(src.copyToSync(dest, 5), 5);
+    // the copy continues from the current positions
+    assertEquals(src.copyToSync(dest), data.length - 5);
+    assertEquals(src.copyToSync(dest), 0);
+    assertEquals(Deno.readFileSync(dir + "/dest.txt"), data);
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function fsFileCopyToSuccess() {
+    const dir = await Deno.makeTempDir();
+    const data = new Uint8Array(1024 * 1024).fill(42);
+    await Deno.writeFile(dir + "/src.txt", data);
+    using src = await Deno.open(dir + "/src.txt");
+    using dest = await Deno.open(dir + "/dest.txt", {
+      write: true,
+      create: true,
+    });
+    await dest.write(new Uint8Array([1, 2, 3]));
+    assertEquals(await src.copyTo(dest), data.length);
+    const copy = await Deno.readFile(dir + "/dest.txt");
+    assertEquals(copy.length, data.length + 3);
+    assertEquals(copy.subarray(3), data);
+  },
+);
+
+Deno.test(
+  {
+    ignore: Deno.build.os === "windows",
+    permissions: { read: true, write: true, net: true },
+  },
+  async function fsFileCopyToConn() {
+    const dir = await Deno.makeTempDir();
+    const data = new Uint8Array(1024 * 1024).fill(42);
+    await Deno.writeFile(dir + "/src.txt", data);
+    using src = await Deno.open(dir + "/src.txt");
+    using listener = Deno.listen({ hostname: "127.0.0.1", port: 0 });
+    const conn = await Deno.connect(listener.addr as Deno.NetAddr);
+    using serverConn = await listener.accept();
+    const received = (async () => {
+      const chunks = [];
+      for await (const chunk of serverConn.readable) chunks.push(chunk);
+      return chunks.reduce((len, chunk) => len + chunk.length, 0);
+    })();
+    // the socket buffers are smaller than the file, so the copy waits for
+    // the socket to be writable
+    assertEquals(await src.copyTo(conn), data.length);
+    conn.close();
+    assertEquals(await received, data.length);
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true, net: true } },
+  async function fsFileCopyToSyncConnFails() {
+    const dir = await Deno.makeTempDir();
+    await Deno.writeTextFile(dir + "/src.txt", "hello world");
+    using src = await Deno.open(dir + "/src.txt");
+    using listener = Deno.listen({ hostname: "127.0.0.1", port: 0 });
+    using conn = await Deno.connect(listener.addr as Deno.NetAddr);
+    using _serverConn = await listener.accept();
+    assertThrows(
+      // @ts-expect-error only files can be the destination
+      () => src.copyToSync(conn),
+      Deno.errors.BadResource,
+    );
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function openReadableAndWritableStream() {
+    const dir = await Deno.makeTempDir();
+    const data = new Uint8Array(100_000);
+    for (let i = 0; i < data.length; i++) data[i] = i % 256;
+    await Deno.writeFile(dir + "/src.bin", data);
+
+    const src = await Deno.openReadableStream(dir + "/src.bin", {
+      chunkSize: 1000,
+      start: 10,
+    });
+    const chunks = [];
+    for await (const chunk of src) {
+      assert(chunk.byteLength <= 1000);
+      chunks.push(chunk);
+    }
+    assertEquals(chunks.length, 100);
+
+    const dest = await Deno.openWritableStream(dir + "/dest.bin");
+    const writer = dest.getWriter();
+    for (const chunk of chunks) await writer.write(chunk);
+    await writer.close();
+    assertEquals(await Deno.readFile(dir + "/dest.bin"), data.subarray(10));
+
+    const append = await Deno.openWritableStream(dir + "/dest.bin", {
+      append: true,
+    });
+    await ReadableStream.from([new Uint8Array([1, 2, 3])]).pipeTo(append);
+    const copy = await Deno.readFile(dir + "/dest.bin");
+    assertEquals(copy.length, data.length - 10 + 3);
+  },
+);
+
+Deno.test(
+  { permissions: { read: true } },
+  async function openReadableStreamInvalidOptions() {
+    await assertRejects(
+      () =>
+        Deno.openReadableStream("tests/testdata/assets/hello.txt", {
+          chunkSize: 0,
+        }),
+      RangeError,
+    );
+    await assertRejects(
+      () =>
+        Deno.openReadableStream("tests/testdata/assets/hello.txt", {
+          start: -1,
+        }),
+      RangeError,
+    );
+  },
+);
+
 Deno.test({ permissions: { read: true } }, function fsFileIsTerminal() {
   // CI not under TTY, so cannot test stdin/stdout/stderr.
   using file = Deno.openSync("tests/testdata/assets/hello.txt");
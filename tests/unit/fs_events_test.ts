@@ -137,3 +137,93 @@ Deno.test(
     assert(done);
   },
 );
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function watchFsIncludeExclude() {
+    const testDir = Deno.realPathSync(await makeTempDir());
+    const iter = Deno.watchFs(testDir, {
+      recursive: true,
+      include: [`${testDir}/*.txt`],
+      exclude: [`${testDir}/ignored.txt`],
+    });
+
+    const eventsPromise = getTwoEvents(iter);
+
+    Deno.writeFileSync(testDir + "/file.log", new Uint8Array([0, 1, 2]));
+    Deno.writeFileSync(testDir + "/ignored.txt", new Uint8Array([0, 1, 2]));
+    Deno.writeFileSync(testDir + "/file.txt", new Uint8Array([0, 1, 2]));
+
+    const events = await eventsPromise;
+    assert(events.length >= 2);
+    for (const event of events) {
+      assert(event.paths.every((path) => path.endsWith("file.txt")));
+    }
+    iter.close();
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  function watchFsInvalidGlob() {
+    assertThrows(
+      () => Deno.watchFs(".", { recursive: true, include: ["[a-"] }),
+      TypeError,
+      "Invalid glob",
+    );
+  },
+);
+
+Deno.test(
+  { permissions: { read: true, write: true } },
+  async function watchFsDebounce() {
+    const testDir = await makeTempDir();
+    using iter = Deno.watchFs(testDir, { recursive: true, debounce: 100 });
+
+    const file = testDir + "/file.txt";
+    Deno.writeFileSync(file, new Uint8Array([0, 1, 2]));
+    for (let i = 0; i < 10; i++) {
+      Deno.writeFileSync(file, new Uint8Array([i]), { append: true });
+    }
+    await delay(300);
+    Deno.writeFileSync(testDir + "/other.txt", new Uint8Array([0, 1, 2]));
+
+    // The burst of identical events for `file.txt` is coalesced before the
+    // events for `other.txt`.
+    const events = [];
+    for await (const event of iter) {
+      events.push(event);
+      if (event.paths[0].endsWith("other.txt")) break;
+    }
+    const keys = events.map((event) =>
+      JSON.stringify([event.kind, event.paths, event.flag])
+    );
+    assertEquals(keys.length, new Set(keys).size);
+  },
+);
+
+Deno.test(
+  {
+    ignore: Deno.build.os !== "linux",
+    permissions: { read: true, write: true },
+  },
+  async function watchFsRename() {
+    const testDir = Deno.realPathSync(await makeTempDir());
+    using iter = Deno.watchFs(testDir);
+
+    const oldPath = testDir + "/old.txt";
+    const newPath = testDir + "/new.txt";
+    Deno.writeFileSync(oldPath, new Uint8Array([0, 1, 2]));
+    await delay(100);
+    Deno.renameSync(oldPath, newPath);
+
+    for await (const event of iter) {
+      if (event.rename) {
+        assertEquals(event.kind, "modify");
+        assertEquals(event.rename, { from: oldPath, to: newPath });
+        assertEquals(event.paths, [oldPath, newPath]);
+        break;
+      }
+    }
+  },
+);
@@ -177,3 +177,62 @@ Deno.test(
     }
   },
 );
+
+Deno.test(
+  {
+    ignore: Deno.build.os === "windows",
+    permissions: { read: true, write: true },
+  },
+  function makeTempCustomMode() {
+    const dir = Deno.makeTempDirSync({ mode: 0o750 });
+    assertEquals(Deno.statSync(dir).mode! & 0o777, 0o750 & ~Deno.umask());
+    const file = Deno.makeTempFileSync({ dir, mode: 0o640 });
+    assertEquals(Deno.statSync(file).mode! & 0o777, 0o640 & ~Deno.umask());
+    Deno.removeSync(dir, { recursive: true });
+  },
+);
+
+Deno.test(
+  {
+    permissions: { read: true, write: true },
+    // the SIGINT listener removing the directory on exit stays installed
+    sanitizeOps: false,
+    sanitizeResources: false,
+  },
+  async function withTempDirRemovesDir() {
+    let tempDir = "";
+    const result = await Deno.withTempDir(async (dir) => {
+      tempDir = dir;
+      await Deno.writeTextFile(`${dir}/file.txt`, "hello");
+      return 42;
+    }, { prefix: "with_temp_dir_" });
+    assertEquals(result, 42);
+    assert(tempDir.replace(/^.*[\\\/]/, "").startsWith("with_temp_dir_"));
+    assertThrows(() => Deno.statSync(tempDir), Deno.errors.NotFound);
+  },
+);
+
+Deno.test(
+  {
+    permissions: { read: true, write: true },
+    // the SIGINT listener removing the directory on exit stays installed
+    sanitizeOps: false,
+    sanitizeResources: false,
+  },
+  async function withTempDirRemovesDirOnError() {
+    let tempDir = "";
+    await assertRejects(
+      () =>
+        Deno.withTempDir((dir) => {
+          tempDir = dir;
+          throw new Error("boom");
+        }),
+      Error,
+      "boom",
+    );
+    assertThrows(() => Deno.statSync(tempDir), Deno.errors.NotFound);
+
+    // the callback may remove the directory itself
+    await Deno.withTempDir((dir) => Deno.remove(dir));
+  },
+);
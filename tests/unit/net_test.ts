@@ -1160,6 +1160,46 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { ignore: Deno.build.os !== "linux", permissions: { net: true } },
+  function netUdpReusePort() {
+    const listener1 = Deno.listenDatagram({
+      port: 4003,
+      transport: "udp",
+      reusePort: true,
+    });
+    const listener2 = Deno.listenDatagram({
+      port: 4003,
+      transport: "udp",
+      reusePort: true,
+    });
+    listener1.close();
+    listener2.close();
+  },
+);
+
+Deno.test(
+  { ignore: Deno.build.os === "windows", permissions: { net: true } },
+  async function netUdpBroadcastDisabled() {
+    const socket = Deno.listenDatagram({
+      port: 4004,
+      transport: "udp",
+      broadcast: false,
+      ttl: 16,
+    });
+    await assertRejects(
+      () =>
+        socket.send(new Uint8Array([1]), {
+          transport: "udp",
+          hostname: "255.255.255.255",
+          port: 4005,
+        }),
+      Deno.errors.PermissionDenied,
+    );
+    socket.close();
+  },
+);
+
 Deno.test(
   { permissions: { net: true } },
   function netUdpNoReuseAddr() {
@@ -1188,6 +1228,30 @@ Deno.test(
   },
 );
 
+Deno.test(
+  { permissions: { net: true } },
+  async function netTcpListenBacklogAndKeepAlive() {
+    const listener = Deno.listen({ port: listenPort, backlog: 16 });
+    const [serverConn, clientConn] = await Promise.all([
+      listener.accept(),
+      Deno.connect({ port: listenPort }),
+    ]);
+    clientConn.setKeepAlive(true, { time: 30_000, interval: 5_000 });
+    // sub-second timing is rounded up to whole seconds
+    clientConn.setKeepAlive(true, { time: 500, interval: 1 });
+    assertThrows(
+      () => clientConn.setKeepAlive(true, { time: 0 }),
+      TypeError,
+      "Keep-alive time must be greater than 0",
+    );
+    clientConn.setKeepAlive(false);
+    clientConn.setNoDelay(true);
+    serverConn.close();
+    clientConn.close();
+    listener.close();
+  },
+);
+
 Deno.test({
   ignore: Deno.build.os !== "linux",
   permissions: { net: true },
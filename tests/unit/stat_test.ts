@@ -307,6 +307,7 @@ Deno.test(
     assert(s.isCharDevice === null);
     assert(s.isFifo === null);
     assert(s.isSocket === null);
+    assert(s.immutable === null);
   },
 );
 
@@ -338,5 +339,96 @@ Deno.test(
     assert(!s.isCharDevice);
     assert(!s.isFifo);
     assert(!s.isSocket);
+    assert(!s.immutable);
   },
 );
+
+Deno.test(
+  {
+    ignore: Deno.build.os !== "linux",
+    permissions: { read: true, write: true },
+  },
+  async function fileStatImmutable() {
+    const tempDir = Deno.makeTempDirSync();
+    const filename = tempDir + "/test.txt";
+    Deno.writeTextFileSync(filename, "Hello");
+    using file = Deno.openSync(filename);
+    const { immutable } = Deno.statSync(filename);
+    assertEquals(file.statSync().immutable, immutable);
+    assertEquals((await file.stat()).immutable, immutable);
+  },
+);
+
+Deno.test(
+  {
+    ignore: Deno.build.os === "windows",
+    permissions: { read: true, write: true },
+  },
+  function xattrSyncSuccess() {
+    const tempDir = Deno.makeTempDirSync();
+    const filename = tempDir + "/test.txt";
+    Deno.writeTextFileSync(filename, "Hello");
+
+    assertEquals(Deno.getXattrSync(filename, "user.deno.test"), null);
+    Deno.setXattrSync(filename, "user.deno.test", "value");
+    assertEquals(
+      Deno.getXattrSync(filename, "user.deno.test"),
+      new TextEncoder().encode("value"),
+    );
+    Deno.setXattrSync(filename, "user.deno.test", new Uint8Array([1, 2, 3]));
+    assertEquals(
+      Deno.getXattrSync(filename, "user.deno.test"),
+      new Uint8Array([1, 2, 3]),
+    );
+    assert(Deno.listXattrsSync(filename).includes("user.deno.test"));
+
+    Deno.removeXattrSync(filename, "user.deno.test");
+    assertEquals(Deno.getXattrSync(filename, "user.deno.test"), null);
+    assert(!Deno.listXattrsSync(filename).includes("user.deno.test"));
+    Deno.removeSync(tempDir, { recursive: true });
+  },
+);
+
+Deno.test(
+  {
+    ignore: Deno.build.os === "windows",
+    permissions: { read: true, write: true },
+  },
+  async function xattrSuccess() {
+    const tempDir = await Deno.makeTempDir();
+    const fileUrl = new URL(`file://${tempDir}/test.txt`);
+    await Deno.writeTextFile(fileUrl, "Hello");
+
+    await Deno.setXattr(fileUrl, "user.deno.test", "value");
+    assertEquals(
+      await Deno.getXattr(fileUrl, "user.deno.test"),
+      new TextEncoder().encode("value"),
+    );
+    assert((await Deno.listXattrs(fileUrl)).includes("user.deno.test"));
+
+    await Deno.removeXattr(fileUrl, "user.deno.test");
+    assertEquals(await Deno.getXattr(fileUrl, "user.deno.test"), null);
+    await Deno.remove(tempDir, { recursive: true });
+  },
+);
+
+Deno.test({ permissions: { read: false } }, function xattrPerm() {
+  assertThrows(() => {
+    Deno.getXattrSync("README.md", "user.deno.test");
+  }, Deno.errors.PermissionDenied);
+  assertThrows(() => {
+    Deno.listXattrsSync("README.md");
+  }, Deno.errors.PermissionDenied);
+});
+
+Deno.test({ permissions: { write: false } }, function setXattrPerm() {
+  assertThrows(() => {
+    Deno.setXattrSync("README.md", "user.deno.test", "value");
+  }, Deno.errors.PermissionDenied);
+  assertThrows(() => {
+    Deno.removeXattrSync("README.md", "user.deno.test");
+  }, Deno.errors.PermissionDenied);
+  assertThrows(() => {
+    Deno.setImmutableSync("README.md", true);
+  }, Deno.errors.PermissionDenied);
+});
@@ -1646,3 +1646,86 @@ Deno.test(
     listener.close();
   },
 );
+
+Deno.test(
+  { permissions: { net: true } },
+  async function listenTlsWithClientCaCerts() {
+    const listener = Deno.listenTls({
+      hostname: "localhost",
+      port: 0,
+      cert,
+      key,
+      clientCaCerts: caCerts,
+    });
+    const { port } = listener.addr;
+
+    const accept = async () => {
+      const conn = await listener.accept();
+      try {
+        await conn.handshake();
+        await conn.write(encoder.encode("PASS"));
+      } catch {
+        // the handshake fails for clients without a certificate
+      } finally {
+        conn.close();
+      }
+    };
+
+    let serverDone = accept();
+    const conn = await Deno.connectTls({
+      hostname: "localhost",
+      port,
+      caCerts,
+      cert,
+      key,
+    });
+    assertEquals(decoder.decode(await readAll(conn)), "PASS");
+    conn.close();
+    await serverDone;
+
+    serverDone = accept();
+    const anonymousConn = await Deno.connectTls({
+      hostname: "localhost",
+      port,
+      caCerts,
+    });
+    await assertRejects(() => readAll(anonymousConn));
+    anonymousConn.close();
+    await serverDone;
+
+    listener.close();
+  },
+);
+
+Deno.test(
+  { permissions: { net: true } },
+  async function startTlsWithClientCert() {
+    const listener = Deno.listenTls({
+      hostname: "localhost",
+      port: 0,
+      cert,
+      key,
+      clientCaCerts: caCerts,
+    });
+    const { port } = listener.addr;
+
+    const server = async () => {
+      const conn = await listener.accept();
+      await conn.write(encoder.encode("PASS"));
+      conn.close();
+    };
+    const client = async () => {
+      const tcpConn = await Deno.connect({ hostname: "localhost", port });
+      const conn = await Deno.startTls(tcpConn, {
+        hostname: "localhost",
+        caCerts,
+        cert,
+        key,
+      });
+      assertEquals(decoder.decode(await readAll(conn)), "PASS");
+      conn.close();
+    };
+    await Promise.all([server(), client()]);
+    listener.close();
+  },
+);
@@ -22,6 +22,7 @@ use crate::assertions::assert_wildcard_match;
 use crate::assertions::assert_wildcard_match_with_logger;
 use crate::deno_exe_path;
 use crate::denort_exe_path;
+use crate::denort_lite_exe_path;
 use crate::env_vars_for_jsr_tests;
 use crate::env_vars_for_npm_tests;
 use crate::fs::PathRef;
@@ -186,6 +187,10 @@ impl TestContextBuilder {
     // The `denort` binary is in the same artifact directory as the `deno` binary.
     let denort_bin = denort_exe_path();
     self = self.env("DENORT_BIN", denort_bin.to_string());
+    // built separately with `cargo build --bin denort --features lite
+    // --target-dir target/lite`
+    let denort_lite_bin = denort_lite_exe_path();
+    self = self.env("DENORT_LITE_BIN", denort_lite_bin.to_string());
     self
   }
 
@@ -233,6 +233,23 @@ pub fn denort_exe_path() -> PathRef {
   PathRef::new(p)
 }
 
+/// The `denort` binary built with the `lite` feature, which is built into
+/// the separate `target/lite` directory.
+pub fn denort_lite_exe_path() -> PathRef {
+  let target_dir = target_dir();
+  let profile = target_dir.as_path().file_name().unwrap();
+  let mut p = target_dir
+    .parent()
+    .join("lite")
+    .join(profile)
+    .join("denort")
+    .to_path_buf();
+  if cfg!(windows) {
+    p.set_extension("exe");
+  }
+  PathRef::new(p)
+}
+
 pub fn prebuilt_tool_path(tool: &str) -> PathRef {
   let mut exe = tool.to_string();
   exe.push_str(if cfg!(windows) { ".exe" } else { "" });
@@ -304,7 +321,7 @@ async fn get_tcp_listener_stream(
   futures::stream::select_all(listeners)
 }
 
-pub const TEST_SERVERS_COUNT: usize = 28;
+pub const TEST_SERVERS_COUNT: usize = 29;
 
 #[derive(Default)]
 struct HttpServerCount {
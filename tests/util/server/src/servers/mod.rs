@@ -79,6 +79,7 @@ const H2_ONLY_TLS_PORT: u16 = 5547;
 const H1_ONLY_PORT: u16 = 5548;
 const H2_ONLY_PORT: u16 = 5549;
 const HTTPS_CLIENT_AUTH_PORT: u16 = 5552;
+const BASIC_AUTH_REDIRECT_TLS_PORT: u16 = 5553;
 const WS_PORT: u16 = 4242;
 const WSS_PORT: u16 = 4243;
 const WSS2_PORT: u16 = 4249;
@@ -107,6 +108,8 @@ pub async fn run_all_servers() {
   let auth_redirect_server_fut = wrap_auth_redirect_server(AUTH_REDIRECT_PORT);
   let basic_auth_redirect_server_fut =
     wrap_basic_auth_redirect_server(BASIC_AUTH_REDIRECT_PORT);
+  let basic_auth_redirect_server_https_fut =
+    wrap_basic_auth_redirect_https_server(BASIC_AUTH_REDIRECT_TLS_PORT);
   let abs_redirect_server_fut =
     wrap_abs_redirect_server(REDIRECT_ABSOLUTE_PORT);
 
@@ -151,6 +154,7 @@ pub async fn run_all_servers() {
     another_redirect_server_fut.boxed_local(),
     auth_redirect_server_fut.boxed_local(),
     basic_auth_redirect_server_fut.boxed_local(),
+    basic_auth_redirect_server_https_fut.boxed_local(),
     inf_redirects_server_fut.boxed_local(),
     double_redirects_server_fut.boxed_local(),
     abs_redirect_server_fut.boxed_local(),
@@ -1237,6 +1241,22 @@ async fn wrap_basic_auth_redirect_server(port: u16) {
   .await;
 }
 
+async fn wrap_basic_auth_redirect_https_server(port: u16) {
+  let tls = get_tls_listener_stream(
+    "basic auth redirect (https)",
+    port,
+    Default::default(),
+  )
+  .await;
+  run_server_with_acceptor(
+    tls.boxed_local(),
+    basic_auth_redirect,
+    "Basic auth redirect TLS server error",
+    ServerKind::Auto,
+  )
+  .await
+}
+
 async fn wrap_abs_redirect_server(port: u16) {
   let abs_redirect_addr = SocketAddr::from(([127, 0, 0, 1], port));
   run_server(